@@ -20,6 +20,9 @@ fn main() {
     // Python 3 must be in the PATH.
     generate_symbols_for_linker(parent_dir);
 
+    // Lets patch code gate region-specific behavior with `#[cfg(region = "...")]`.
+    emit_region_cfg();
+
     // This collects the glue code from the `patches!` macro and dumps it into a .cotpatch file
     generate_cotpatch(parent_dir.join("patches/generated_by_rust.cotpatch").as_path());
 
@@ -1,10 +1,12 @@
 use ansi_term::{Color, Style};
+use anyhow::{bail, Context, Result};
+use cargo_metadata::{Metadata, MetadataCommand, Package};
 use clap::{Parser, Subcommand};
 use eos_rs_build::target_region::TargetRegion;
-use serde_json::Value;
+use eos_rs_patches_def::did_you_mean;
+use std::collections::BTreeMap;
 use std::env::current_dir;
 use std::ffi::{OsStr, OsString};
-use std::io::Read;
 use std::iter::once;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
@@ -12,7 +14,20 @@ use std::{env, fs, process};
 use which::which;
 
 const ABOUT: &str = "
-Cargo extension to build c-of-time projects and burn/write them to a ROM.";
+Cargo extension to build c-of-time projects and burn/write them to a ROM.
+User-defined aliases from `workspace.metadata.cot.alias` can also be used in place of `build`/`burn`.";
+
+/// How build/burn progress is reported on stdout.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum MessageFormat {
+    /// Colored icons and prose, for interactive terminals. The default.
+    Human,
+    /// One JSON object per step (`{"phase":...,"status":"start"|"ok"|"error","message":...}`),
+    /// for CI and editor integrations. Also forwarded to the underlying `cargo build` invocation.
+    Json,
+    /// Plain, uncolored single-line messages, for logging to a file.
+    Short,
+}
 
 #[derive(Parser, Debug)]
 #[clap(
@@ -43,16 +58,28 @@ enum Commands {
     /// This is equivalent to `cargo build -Zbuild-std=core,alloc --target ./armv5te-none-ndseoseabi-XX.json`,
     /// where XX is the region specified.
     Build {
-        /// The region to build for; `eu`, `na` or `jp`.
+        /// The region to build for; `eu`, `na` or `ja`.
         ///
-        /// If not specified the region will be taken from `workspace.metadata.cot.region` in the
-        /// Cargo.toml (if it is specified).
+        /// If not specified the region will be taken from each selected package's
+        /// `package.metadata.cot.region` (or the legacy `workspace.metadata.cot.region`) in the
+        /// Cargo.toml.
         region: Option<String>,
 
         /// Build artifacts in release mode, with optimization.
         #[clap(short, long)]
         release: bool,
 
+        /// Build only the named workspace member. May be repeated to build several members.
+        ///
+        /// If omitted, every workspace member that declares a `package.metadata.cot` block is
+        /// built.
+        #[clap(short = 'p', long = "package")]
+        package: Vec<String>,
+
+        /// How to report build progress on stdout.
+        #[clap(long = "message-format", value_enum, default_value_t = MessageFormat::Human)]
+        message_format: MessageFormat,
+
         /// Any additional argument after '--' will be forwarded to cargo build.
         #[clap(last = true, value_parser)]
         cargo_args: Vec<OsString>,
@@ -62,10 +89,11 @@ enum Commands {
     /// Overlay 36 is patched and patches in ../patches are applied to the game (including the glue
     /// code from the `patches!` macro).
     Burn {
-        /// The region to build for; `eu`, `na` or `jp`.
+        /// The region to build for; `eu`, `na` or `ja`.
         ///
-        /// If not specified the region will be taken from `workspace.metadata.cot.region` in the
-        /// Cargo.toml (if it is specified).
+        /// If not specified the region will be taken from each selected package's
+        /// `package.metadata.cot.region` (or the legacy `workspace.metadata.cot.region`) in the
+        /// Cargo.toml.
         region: Option<String>,
 
         /// Path to the input ROM to patch.
@@ -78,29 +106,289 @@ enum Commands {
         #[clap(short, long)]
         release: bool,
 
+        /// Build and burn only the named workspace member. May be repeated to select several
+        /// members.
+        ///
+        /// If omitted, every workspace member that declares a `package.metadata.cot` block is
+        /// built and burned.
+        #[clap(short = 'p', long = "package")]
+        package: Vec<String>,
+
+        /// How to report build/burn progress on stdout.
+        #[clap(long = "message-format", value_enum, default_value_t = MessageFormat::Human)]
+        message_format: MessageFormat,
+
         /// Any additional argument after '--' will be forwarded to cargo build.
         #[clap(last = true, value_parser)]
         cargo_args: Vec<OsString>,
     },
 }
 
-fn main() -> ! {
-    let Opt::Cot { command } = Opt::parse();
+/// A single workspace member's resolved `cot` build configuration, read from its
+/// `package.metadata.cot` table (falling back to the legacy workspace-level
+/// `workspace.metadata.cot.region` for the region only).
+struct CotPackage {
+    name: String,
+    /// Directory containing this package's own `Cargo.toml`.
+    manifest_dir: PathBuf,
+    region: Option<String>,
+    /// Override for the target JSON file's base name (without the `.json` extension).
+    /// Defaults to [`TargetRegion::target_str`] for the resolved region when absent.
+    target: Option<String>,
+}
+
+/// Loads workspace metadata via `cargo metadata` and resolves the `cot` configuration for each
+/// selected package.
+///
+/// If `selected` is empty, every workspace member with a `package.metadata.cot` table is
+/// returned. Otherwise, only the named members are returned, in the order they were requested;
+/// returns an error if a requested package doesn't exist or has no `cot` table.
+fn resolve_cot_packages(manifest_dir: &Path, selected: &[String]) -> Result<Vec<CotPackage>> {
+    let metadata = cargo_workspace_metadata(manifest_dir)?;
+    let workspace_region = workspace_cot_region(&metadata);
+
+    let to_cot_package = |package: &Package| -> Option<CotPackage> {
+        let cot = package.metadata.get("cot")?.as_object()?;
+        let region = cot
+            .get("region")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .or_else(|| workspace_region.clone());
+        let target = cot.get("target").and_then(|v| v.as_str()).map(String::from);
+        Some(CotPackage {
+            name: package.name.clone(),
+            manifest_dir: package
+                .manifest_path
+                .parent()
+                .expect("package manifest path must have a parent directory")
+                .into(),
+            region,
+            target,
+        })
+    };
+
+    if selected.is_empty() {
+        Ok(metadata
+            .workspace_packages()
+            .into_iter()
+            .filter_map(to_cot_package)
+            .collect())
+    } else {
+        selected
+            .iter()
+            .map(|name| {
+                let package = metadata
+                    .workspace_packages()
+                    .into_iter()
+                    .find(|p| &p.name == name)
+                    .with_context(|| format!("No workspace member named '{}' was found", name))?;
+                to_cot_package(package).with_context(|| {
+                    format!("Package '{}' has no [package.metadata.cot] table", name)
+                })
+            })
+            .collect()
+    }
+}
+
+fn cargo_workspace_metadata(manifest_dir: &Path) -> Result<Metadata> {
+    MetadataCommand::new()
+        .no_deps()
+        .manifest_path(manifest_dir.join("Cargo.toml"))
+        .exec()
+        .context("Failed to run 'cargo metadata'")
+}
+
+/// Reads the legacy `workspace.metadata.cot.region` key, used as a fallback for packages that
+/// don't declare their own `package.metadata.cot.region`.
+fn workspace_cot_region(metadata: &Metadata) -> Option<String> {
+    metadata
+        .workspace_metadata
+        .get("cot")?
+        .get("region")?
+        .as_str()
+        .map(String::from)
+}
+
+/// The subcommand names built into `cargo-cot` itself, as opposed to user-defined aliases.
+const BUILTIN_COMMANDS: &[&str] = &["build", "burn"];
+
+/// Reads `workspace.metadata.cot.alias`, a table mapping an alias name to the command line it
+/// expands to, e.g. `alias = { release-eu = "burn eu game.nds out.nds --release" }`.
+fn workspace_cot_aliases(metadata: &Metadata) -> BTreeMap<String, String> {
+    metadata
+        .workspace_metadata
+        .get("cot")
+        .and_then(|cot| cot.get("alias"))
+        .and_then(|alias| alias.as_object())
+        .map(|alias| {
+            alias
+                .iter()
+                .filter_map(|(name, expansion)| {
+                    Some((name.clone(), expansion.as_str()?.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Expands a user-defined alias in `raw_args` (the process's own `argv`) into the command line
+/// it stands for, before clap ever sees it.
+///
+/// `raw_args` is expected in the shape cargo invokes subcommands with: `[<binary>, "cot",
+/// <command-or-alias>, <rest...>]`. If the third token is a built-in subcommand, `raw_args` is
+/// returned unchanged. If it matches a `workspace.metadata.cot.alias` entry, that entry's
+/// whitespace-separated tokens are spliced in its place. Otherwise, an error is returned
+/// suggesting the closest built-in command or alias name, if any looks like a plausible typo.
+fn resolve_command_args(raw_args: Vec<OsString>, manifest_dir: &Path) -> Result<Vec<OsString>> {
+    let Some(token) = raw_args.get(2).and_then(|a| a.to_str()) else {
+        // Too few arguments for a subcommand to be present yet; let clap report the usage error.
+        return Ok(raw_args);
+    };
+    if BUILTIN_COMMANDS.contains(&token) {
+        return Ok(raw_args);
+    }
+
+    let aliases = cargo_workspace_metadata(manifest_dir)
+        .ok()
+        .map(|metadata| workspace_cot_aliases(&metadata))
+        .unwrap_or_default();
+
+    if let Some(expansion) = aliases.get(token) {
+        let mut expanded = raw_args[..2].to_vec();
+        expanded.extend(expansion.split_whitespace().map(OsString::from));
+        expanded.extend(raw_args[3..].to_vec());
+        return Ok(expanded);
+    }
+
+    let known: Vec<&str> = BUILTIN_COMMANDS
+        .iter()
+        .copied()
+        .chain(aliases.keys().map(String::as_str))
+        .collect();
+    match did_you_mean(token, &known) {
+        Some(suggestion) => bail!(
+            "Unknown command or alias '{}' (did you mean `{}`?)",
+            token,
+            suggestion
+        ),
+        None => bail!("Unknown command or alias '{}'", token),
+    }
+}
+
+/// Reports build/burn progress, either as colored human-readable lines or as a stream of JSON
+/// objects (one per step) that CI and editor integrations can parse.
+#[derive(Clone, Copy)]
+struct Reporter {
+    format: MessageFormat,
+}
+
+impl Reporter {
+    fn new(format: MessageFormat) -> Self {
+        Self { format }
+    }
+
+    /// Reports the start of a named step, e.g. `"objcopy"` or `"patch"`.
+    fn step_start<S: AsRef<str>>(&self, phase: &str, msg: S) {
+        self.emit_json(phase, "start", msg.as_ref());
+        if self.format != MessageFormat::Json {
+            print_task(self, msg);
+        }
+    }
+
+    /// Reports that a named step finished successfully.
+    fn step_ok<S: AsRef<str>>(&self, phase: &str, msg: S) {
+        self.emit_json(phase, "ok", msg.as_ref());
+        if self.format != MessageFormat::Json {
+            print_success(self, msg);
+        }
+    }
+
+    /// Reports that a named step failed. `err` is still returned by the caller via `Result`;
+    /// this only records that the step didn't make it to `ok`.
+    fn step_error<S: AsRef<str>>(&self, phase: &str, msg: S) {
+        self.emit_json(phase, "error", msg.as_ref());
+        if self.format != MessageFormat::Json {
+            print_error(self, msg);
+        }
+    }
+
+    fn note<S: AsRef<str>>(&self, msg: S) {
+        if self.format == MessageFormat::Json {
+            self.emit_json("note", "ok", msg.as_ref());
+        } else {
+            print_note(self, msg);
+        }
+    }
+
+    fn warning<S: AsRef<str>>(&self, msg: S) {
+        if self.format == MessageFormat::Json {
+            self.emit_json("warning", "error", msg.as_ref());
+        } else {
+            print_warning(self, msg);
+        }
+    }
+
+    fn emit_json(&self, phase: &str, status: &str, message: &str) {
+        if self.format == MessageFormat::Json {
+            println!(
+                "{}",
+                serde_json::json!({ "phase": phase, "status": status, "message": message })
+            );
+        }
+    }
+}
+
+fn main() {
+    if let Err(err) = run() {
+        print_error_chain(&err);
+        process::exit(1)
+    }
+}
+
+fn print_error_chain(err: &anyhow::Error) {
+    // The command hasn't necessarily parsed far enough to know the requested message format
+    // (e.g. an argument error), so this always reports as a human, regardless of the flag.
+    print_error(&Reporter::new(MessageFormat::Human), format!("{}", err));
+    for cause in err.chain().skip(1) {
+        eprintln!(
+            "  {} {}",
+            Color::Red.paint("caused by:"),
+            Style::new().paint(format!("{}", cause))
+        );
+    }
+}
+
+fn run() -> Result<()> {
+    let raw_args: Vec<OsString> = env::args_os().collect();
+    // Resolved relative to the current directory; unlike the packages built/burned below, this
+    // doesn't honor a `--manifest-path` argument, since that comes after the very token being
+    // resolved here.
+    let cwd = fs::canonicalize(current_dir().context("Failed to get the current directory")?)
+        .context("Failed to canonicalize the current directory")?;
+    let args = resolve_command_args(raw_args, &cwd)?;
+
+    let Opt::Cot { command } = Opt::parse_from(args);
 
     let burn_rom_path;
     let burn_rom_out_path;
-    let build_region_str;
+    let region_override;
     let build_release;
+    let build_package;
     let build_cargo_args;
+    let message_format;
 
     match command {
         Commands::Build {
             region,
             release,
+            package,
+            message_format: format,
             cargo_args,
         } => {
-            build_region_str = region;
+            region_override = region;
             build_release = release;
+            build_package = package;
+            message_format = format;
             build_cargo_args = cargo_args;
             burn_rom_path = None;
             burn_rom_out_path = None;
@@ -108,123 +396,92 @@ fn main() -> ! {
         Commands::Burn {
             region,
             release,
+            package,
+            message_format: format,
             cargo_args,
             rom_path,
             out_path,
         } => {
-            build_region_str = region;
+            region_override = region;
             build_release = release;
+            build_package = package;
+            message_format = format;
             build_cargo_args = cargo_args;
-            burn_rom_path = Some(fs::canonicalize(rom_path).expect("The ROM path does not exist."));
-            burn_rom_out_path =
-                Some(fs::canonicalize(out_path).expect("The out path does not exist."));
+            burn_rom_path = Some(
+                fs::canonicalize(&rom_path)
+                    .with_context(|| format!("ROM path does not exist: {:?}", rom_path))?,
+            );
+            burn_rom_out_path = Some(
+                fs::canonicalize(&out_path)
+                    .with_context(|| format!("Out path does not exist: {:?}", out_path))?,
+            );
         }
     }
 
-    let manifest_dir = get_manifest_dir(&build_cargo_args);
-    assert!(
-        manifest_dir.exists(),
-        "The manifest directory must exist: {:?}",
-        manifest_dir
-    );
+    let reporter = Reporter::new(message_format);
 
-    let build_region_str = match build_region_str {
-        None => {
-            // Try to read the build region from the Cargo.toml
-            match cargo_metadata_region(manifest_dir.as_path()) {
-                None => {
-                    eprintln!("{}", Color::Red.paint("Error: A region must be specified."));
-                    process::exit(1)
-                }
-                Some(build_region_str) => build_region_str,
-            }
-        }
-        Some(build_region_str) => build_region_str,
-    };
+    let manifest_dir = get_manifest_dir(&build_cargo_args)?;
+    if !manifest_dir.exists() {
+        bail!("The manifest directory must exist: {:?}", manifest_dir);
+    }
 
-    match TargetRegion::from_str(build_region_str) {
-        Ok(build_region) => {
-            cargo_build(
-                manifest_dir.as_path(),
+    let cot_packages = resolve_cot_packages(manifest_dir.as_path(), &build_package)
+        .context("Failed to resolve the workspace's cot packages")?;
+    if cot_packages.is_empty() {
+        bail!("No workspace member declares a [package.metadata.cot] table");
+    }
+
+    for cot_package in &cot_packages {
+        let region_str = region_override
+            .clone()
+            .or_else(|| cot_package.region.clone())
+            .with_context(|| format!("A region must be specified for package '{}'", cot_package.name))?;
+
+        let build_region = TargetRegion::from_str(region_str)
+            .with_context(|| format!("Invalid region for package '{}'", cot_package.name))?;
+
+        cargo_build(
+            &reporter,
+            cot_package,
+            &build_region,
+            build_release,
+            build_cargo_args.clone(),
+        )
+        .with_context(|| format!("Failed to build package '{}'", cot_package.name))?;
+        if let (Some(rom_path), Some(rom_out_path)) = (&burn_rom_path, &burn_rom_out_path) {
+            burn(
+                &reporter,
+                cot_package,
                 &build_region,
+                rom_path.clone(),
+                rom_out_path.clone(),
                 build_release,
-                build_cargo_args,
-            );
-            if let Some(rom_path) = burn_rom_path {
-                burn(
-                    manifest_dir.as_path(),
-                    &build_region,
-                    rom_path,
-                    burn_rom_out_path.unwrap(),
-                    build_release,
-                );
-            }
-            process::exit(0)
-        }
-        Err(err) => {
-            eprintln!("{}", Color::Red.paint(format!("Error: {}", err)));
-            process::exit(1)
+            )
+            .with_context(|| format!("Failed to burn package '{}'", cot_package.name))?;
         }
     }
-}
 
-fn cargo_metadata_region(manifest_dir: &Path) -> Option<String> {
-    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
-    let mut child = Command::new(cargo)
-        .args([
-            "metadata",
-            "--no-deps",
-            "--manifest-path",
-            manifest_dir.join("Cargo.toml").to_string_lossy().as_ref(),
-            "--format-version",
-            "1",
-        ])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
-    let exit = child.wait().unwrap();
-    if !exit.success() {
-        process::exit(exit.code().unwrap_or(1));
-    }
-    let mut output = vec![];
-    child
-        .stdout
-        .take()
-        .unwrap()
-        .read_to_end(&mut output)
-        .unwrap();
-    let output_parsed: Value = serde_json::from_str(&String::from_utf8(output).unwrap()).unwrap();
-
-    if let Value::Object(package) = output_parsed {
-        if let Some(Value::Object(metadata)) = package.get("metadata") {
-            if let Some(Value::Object(cot)) = metadata.get("cot") {
-                if let Some(Value::String(region)) = cot.get("region") {
-                    return Some(region.clone());
-                }
-            }
-        }
-    }
-    None
+    Ok(())
 }
 
 fn cargo_build(
-    manifest_dir: &Path,
+    reporter: &Reporter,
+    cot_package: &CotPackage,
     build_region: &TargetRegion,
     build_release: bool,
     build_cargo_args: Vec<OsString>,
-) {
-    let target_fname = build_region.target_str();
+) -> Result<()> {
+    let manifest_dir = cot_package.manifest_dir.as_path();
+    let target_fname = cot_package
+        .target
+        .as_deref()
+        .unwrap_or_else(|| build_region.target_str());
     let target_file = manifest_dir.join(&format!("{}.json", target_fname));
     if !target_file.exists() {
-        eprintln!(
-            "{}",
-            Color::Red.paint(format!(
-                "Error: The target file '{}.json' was not found in the manifest directory.",
-                target_fname
-            ))
+        bail!(
+            "The target file '{}.json' was not found in the manifest directory",
+            target_fname
         );
-        process::exit(1)
     };
     let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
     let mut args_iter: Box<dyn Iterator<Item = OsString>> = Box::new(
@@ -240,88 +497,124 @@ fn cargo_build(
     if build_release {
         args_iter = Box::new(args_iter.chain(once(OsString::from("--release"))));
     }
-    let exit = Command::new(cargo)
+    if reporter.format == MessageFormat::Json {
+        // Forwarded so the compiler's own diagnostics join the same machine-readable stream.
+        args_iter = Box::new(args_iter.chain(once(OsString::from("--message-format=json"))));
+    }
+    reporter.step_start(
+        "cargo_build",
+        format!("Building package '{}'...", cot_package.name),
+    );
+    let exit = Command::new(&cargo)
         .args(args_iter)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .status()
-        .unwrap();
+        .with_context(|| format!("Failed to spawn '{:?}'", cargo))?;
     if !exit.success() {
-        process::exit(exit.code().unwrap_or(1));
+        reporter.step_error("cargo_build", format!("cargo build failed with {}", exit));
+        bail!("cargo build failed with {}", exit);
     }
+    reporter.step_ok(
+        "cargo_build",
+        format!("Built package '{}'.", cot_package.name),
+    );
+    Ok(())
 }
 
 fn burn(
-    manifest_dir: &Path,
+    reporter: &Reporter,
+    cot_package: &CotPackage,
     build_region: &TargetRegion,
     rom_path: PathBuf,
     rom_out_path: PathBuf,
     build_release: bool,
-) {
-    let cot_base_path = manifest_dir.parent().unwrap();
+) -> Result<()> {
+    let manifest_dir = cot_package.manifest_dir.as_path();
+    let target_fname = cot_package
+        .target
+        .as_deref()
+        .unwrap_or_else(|| build_region.target_str());
+    let cot_base_path = manifest_dir
+        .parent()
+        .context("The manifest directory has no parent directory")?;
 
     let out_dir_profile = if build_release { "release" } else { "debug" };
     let elf_path = manifest_dir.join(format!(
         "target/{}/{}/eos-rs-bin.elf",
-        build_region.target_str(),
-        out_dir_profile
+        target_fname, out_dir_profile
     ));
     let bin_path = manifest_dir.join(format!(
         "target/{}/{}/eos-rs-bin.bin",
-        build_region.target_str(),
-        out_dir_profile
+        target_fname, out_dir_profile
     ));
 
-    print_info("Starting burning...");
+    reporter.step_start("burn", format!("Starting burning package '{}'...", cot_package.name));
 
-    let objcopy = which("arm-none-eabi-objcopy").unwrap_or_else(|_| {
-        print_error(
-            "Was unable to find 'arm-none-eabi-objcopy' command. Is DevkitPro correctly set up?",
-        );
-        process::exit(1);
-    });
+    let objcopy = which("arm-none-eabi-objcopy")
+        .context("Was unable to find 'arm-none-eabi-objcopy'. Is DevkitPro correctly set up?")?;
 
     if !build_release {
-        print_warning("You are burning a version with debugging information, for the final hack, you should use the --release flag.");
+        reporter.warning("You are burning a version with debugging information, for the final hack, you should use the --release flag.");
     }
 
-    let python = get_python_interpreter(cot_base_path);
+    let python = get_python_interpreter(reporter, cot_base_path)?;
 
-    print_task("Extracting & stripping binary...");
+    reporter.step_start("objcopy", "Extracting & stripping binary...");
     burn_run(
+        reporter,
         objcopy,
         &[
             "--strip-all",
             "-O",
             "binary",
-            elf_path.to_str().unwrap(),
-            bin_path.to_str().unwrap(),
+            elf_path.to_str().context("ELF path is not valid UTF-8")?,
+            bin_path.to_str().context("bin path is not valid UTF-8")?,
         ],
         manifest_dir,
-    );
-
-    print_task("Running patcher...");
+    )
+    .map_err(|err| {
+        reporter.step_error("objcopy", err.to_string());
+        err
+    })
+    .context("Failed to extract & strip the binary")?;
+    reporter.step_ok("objcopy", "Binary extracted & stripped.");
+
+    reporter.step_start("patch", "Running patcher...");
     burn_run(
+        reporter,
         python,
         &[
             "scripts/patch.py",
             build_region.as_str_upper(),
-            rom_path.to_str().unwrap(),
-            bin_path.to_str().unwrap(),
-            elf_path.to_str().unwrap(),
-            rom_out_path.to_str().unwrap(),
+            rom_path.to_str().context("ROM path is not valid UTF-8")?,
+            bin_path.to_str().context("bin path is not valid UTF-8")?,
+            elf_path.to_str().context("ELF path is not valid UTF-8")?,
+            rom_out_path
+                .to_str()
+                .context("Out path is not valid UTF-8")?,
         ],
         cot_base_path,
+    )
+    .map_err(|err| {
+        reporter.step_error("patch", err.to_string());
+        err
+    })
+    .context("Failed to run the patcher")?;
+    reporter.step_ok("patch", "Patcher finished.");
+
+    reporter.step_ok(
+        "burn",
+        format!(
+            "Output ROM written to: {}",
+            rom_out_path.to_string_lossy()
+        ),
     );
-
-    print_success(format!(
-        "Output ROM written to: {}",
-        rom_out_path.to_string_lossy()
-    ))
+    Ok(())
 }
 
-fn get_manifest_dir(cargo_args: &[OsString]) -> PathBuf {
-    let mut path = current_dir().unwrap();
+fn get_manifest_dir(cargo_args: &[OsString]) -> Result<PathBuf> {
+    let mut path = current_dir().context("Failed to get the current directory")?;
     let mut cargo_args_iter = cargo_args.iter();
     if let Ok(cargo_manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
         path = PathBuf::from(cargo_manifest_dir)
@@ -329,110 +622,145 @@ fn get_manifest_dir(cargo_args: &[OsString]) -> PathBuf {
     while let Some(arg) = cargo_args_iter.next() {
         if arg == "--manifest-path" {
             if let Some(manifest_path) = cargo_args_iter.next() {
-                path = PathBuf::from(manifest_path).parent().unwrap().to_path_buf();
+                path = PathBuf::from(manifest_path)
+                    .parent()
+                    .context("--manifest-path has no parent directory")?
+                    .to_path_buf();
             }
             break;
         }
     }
-    fs::canonicalize(current_dir().unwrap().join(path)).unwrap()
+    let cwd = current_dir().context("Failed to get the current directory")?;
+    fs::canonicalize(cwd.join(&path))
+        .with_context(|| format!("Failed to canonicalize manifest directory: {:?}", path))
 }
 
-fn get_python_interpreter(base_dir: &Path) -> PathBuf {
+fn get_python_interpreter(reporter: &Reporter, base_dir: &Path) -> Result<PathBuf> {
     #[cfg(windows)]
     let interpreter_path = base_dir.join("venv/bin/python.exe");
     #[cfg(not(windows))]
     let interpreter_path = base_dir.join("venv/bin/python");
 
     if !interpreter_path.exists() {
-        print_task("Creating Python virtualenv...");
-        let base_python = which("python3").unwrap_or_else(|_| {
-            print_error("Was unable to find Python 3. Is it installed?");
-            process::exit(1);
-        });
+        reporter.step_start("venv", "Creating Python virtualenv...");
+        let base_python =
+            which("python3").context("Was unable to find Python 3. Is it installed?")?;
         burn_run(
+            reporter,
             &base_python,
-            &["-m", "venv", base_dir.join("venv").to_str().unwrap()],
+            &[
+                "-m",
+                "venv",
+                base_dir
+                    .join("venv")
+                    .to_str()
+                    .context("venv path is not valid UTF-8")?,
+            ],
             base_dir,
-        );
+        )
+        .map_err(|err| {
+            reporter.step_error("venv", err.to_string());
+            err
+        })
+        .context("Failed to create the Python virtualenv")?;
         if !interpreter_path.exists() {
-            print_error("Was unable to find the Python interpreter after creating the venv.");
-            process::exit(1);
+            bail!("Was unable to find the Python interpreter after creating the venv");
         }
+        reporter.step_ok("venv", "Python virtualenv created.");
+
+        reporter.step_start("pip_install", "Installing patcher dependencies...");
         burn_run(
+            reporter,
             &interpreter_path,
             &["-m", "pip", "install", "ndspy", "keystone-engine", "pyyaml"],
             base_dir,
-        );
+        )
+        .map_err(|err| {
+            reporter.step_error("pip_install", err.to_string());
+            err
+        })
+        .context("Failed to install the patcher's Python dependencies")?;
+        reporter.step_ok("pip_install", "Patcher dependencies installed.");
     }
 
-    print_note(format!(
+    reporter.note(format!(
         "Using Python interpreter at: {}",
         interpreter_path.to_string_lossy()
     ));
-    interpreter_path
+    Ok(interpreter_path)
 }
 
-fn burn_run<S: AsRef<OsStr>>(cmd: S, args: &[&str], dir: &Path) {
+fn burn_run<S: AsRef<OsStr>>(reporter: &Reporter, cmd: S, args: &[&str], dir: &Path) -> Result<()> {
     let arg_list = args.to_vec().join(" ");
-    burn_print(
-        "$",
-        format!("{} {}", cmd.as_ref().to_string_lossy(), arg_list),
-        Color::Purple,
-        false,
-        false,
-    );
-    assert!(
-        dir.exists(),
-        "The working directory for the command ({:?}) does not exist.",
-        dir
-    );
-    let exit = Command::new(cmd)
+    if reporter.format != MessageFormat::Json {
+        burn_print(
+            reporter,
+            "$",
+            format!("{} {}", cmd.as_ref().to_string_lossy(), arg_list),
+            Color::Purple,
+            false,
+            false,
+        );
+    }
+    if !dir.exists() {
+        bail!(
+            "The working directory for the command ({:?}) does not exist",
+            dir
+        );
+    }
+    let exit = Command::new(&cmd)
         .args(args)
         .current_dir(dir)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .status()
-        .unwrap_or_else(|e| {
-            print_error(format!("Failed to spawn command: {:?}", e));
-            process::exit(1)
-        });
+        .with_context(|| format!("Failed to spawn '{}'", cmd.as_ref().to_string_lossy()))?;
     if !exit.success() {
-        print_error("Command failed!");
-        process::exit(exit.code().unwrap_or(1));
+        bail!("Command failed with {}", exit);
     }
+    Ok(())
 }
 
 #[inline(always)]
-fn print_info<S: AsRef<str>>(msg: S) {
-    burn_print("ℹ", msg, Color::Cyan, true, true);
+fn print_note<S: AsRef<str>>(reporter: &Reporter, msg: S) {
+    burn_print(reporter, "ℹ", msg, Color::Purple, true, false);
 }
 
 #[inline(always)]
-fn print_note<S: AsRef<str>>(msg: S) {
-    burn_print("ℹ", msg, Color::Purple, true, false);
+fn print_task<S: AsRef<str>>(reporter: &Reporter, msg: S) {
+    burn_print(reporter, "⚒", msg, Color::Green, true, false);
 }
 
 #[inline(always)]
-fn print_task<S: AsRef<str>>(msg: S) {
-    burn_print("⚒", msg, Color::Green, true, false);
+fn print_error<S: AsRef<str>>(reporter: &Reporter, msg: S) {
+    burn_print(reporter, "❌", msg, Color::Red, true, true);
 }
 
 #[inline(always)]
-fn print_error<S: AsRef<str>>(msg: S) {
-    burn_print("❌", msg, Color::Red, true, true);
+fn print_warning<S: AsRef<str>>(reporter: &Reporter, msg: S) {
+    burn_print(reporter, "⚠", msg, Color::Yellow, true, false);
 }
 
 #[inline(always)]
-fn print_warning<S: AsRef<str>>(msg: S) {
-    burn_print("⚠", msg, Color::Yellow, true, false);
+fn print_success<S: AsRef<str>>(reporter: &Reporter, msg: S) {
+    burn_print(reporter, "✅", msg, Color::Green, true, true);
 }
 
-#[inline(always)]
-fn print_success<S: AsRef<str>>(msg: S) {
-    burn_print("✅", msg, Color::Green, true, true);
-}
+/// Prints a single human-readable progress line. In [`MessageFormat::Short`], icons and color
+/// are dropped in favor of plain, single-line, log-friendly text.
+fn burn_print<S: AsRef<str>>(
+    reporter: &Reporter,
+    icon: &str,
+    msg: S,
+    color_icon: Color,
+    color_msg: bool,
+    bold: bool,
+) {
+    if reporter.format == MessageFormat::Short {
+        println!("{}", msg.as_ref());
+        return;
+    }
 
-fn burn_print<S: AsRef<str>>(icon: &str, msg: S, color_icon: Color, color_msg: bool, bold: bool) {
     let mut style_icon = Style::new().fg(color_icon);
     let mut style_msg = Style::new();
 
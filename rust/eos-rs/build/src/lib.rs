@@ -1,42 +1,131 @@
 pub mod target_region;
 
+use std::collections::HashMap;
 use std::{env, fs};
 use std::fs::{read_to_string, remove_file};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::FromStr;
 use glob::glob;
-use syn::{ItemMacro, parse2, parse_file};
+use syn::{ItemMacro, ItemUse, UseTree, parse2, parse_file};
 use syn::visit::Visit;
 use crate::target_region::TargetRegion;
+use eos_rs_patches_def::manifest::{PatchManifest, PatchManifestEntry, PATCH_MANIFEST_SCHEMA_VERSION};
 use eos_rs_patches_def::PatchesDef;
 use which::which;
 
+/// Canonical paths that are recognized as referring to `eos_rs`'s `patches!` macro, however a
+/// given file happens to have imported it.
+const PATCHES_MACRO_CANONICAL_PATHS: [&str; 2] = ["eos_rs::patches", "crate::patches"];
+
+/// Walks a single `use` item's tree, recording `local identifier -> fully qualified path` for
+/// every name it brings into scope (flattening renames and `{a, b}` groups). Glob imports
+/// (`use eos_rs::*;`) can't be resolved to a specific name without a full name-resolution pass,
+/// so they're skipped; a file relying on one to call `patches!` still matches via the bare-name
+/// fallback in [`is_patches_macro_path`].
+fn collect_use_tree(tree: &UseTree, prefix: &mut Vec<String>, aliases: &mut HashMap<String, String>) {
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.to_string());
+            collect_use_tree(&path.tree, prefix, aliases);
+            prefix.pop();
+        }
+        UseTree::Name(name) => {
+            let canonical = prefix.iter().map(String::as_str).chain([name.ident.to_string().as_str()]).collect::<Vec<_>>().join("::");
+            aliases.insert(name.ident.to_string(), canonical);
+        }
+        UseTree::Rename(rename) => {
+            let canonical = prefix.iter().map(String::as_str).chain([rename.ident.to_string().as_str()]).collect::<Vec<_>>().join("::");
+            aliases.insert(rename.rename.to_string(), canonical);
+        }
+        UseTree::Group(group) => {
+            for item in &group.items {
+                collect_use_tree(item, prefix, aliases);
+            }
+        }
+        UseTree::Glob(_) => {}
+    }
+}
+
+/// Builds the `use`-alias map for a single parsed file (see [`collect_use_tree`]).
+#[derive(Default)]
+struct UseAliasCollector {
+    /// Local identifier -> fully qualified path, e.g. `"pat" -> "eos_rs::patches"` for
+    /// `use eos_rs::patches as pat;`.
+    aliases: HashMap<String, String>,
+}
+
+impl<'ast> Visit<'ast> for UseAliasCollector {
+    fn visit_item_use(&mut self, i: &'ast ItemUse) {
+        collect_use_tree(&i.tree, &mut Vec::new(), &mut self.aliases);
+    }
+}
+
+/// Whether `path` (an invoked macro's path, e.g. `patches` or `eos_rs::patches`) refers to
+/// `eos_rs`'s `patches!` macro, resolving single-segment paths through `aliases` (this file's
+/// `use` imports) first.
+///
+/// A single-segment path that isn't a known alias still matches if it's literally named
+/// `patches`, since that's overwhelmingly the common case (`use eos_rs::patches;` or a glob
+/// import via the prelude) and rejecting it would be a regression from matching too little.
+fn is_patches_macro_path(path: &syn::Path, aliases: &HashMap<String, String>) -> bool {
+    if let Some(ident) = path.get_ident() {
+        let ident = ident.to_string();
+        return match aliases.get(&ident) {
+            Some(canonical) => PATCHES_MACRO_CANONICAL_PATHS.contains(&canonical.as_str()),
+            None => ident == "patches",
+        };
+    }
+
+    let joined = path.segments.iter().map(|s| s.ident.to_string()).collect::<Vec<_>>().join("::");
+    let joined = joined.strip_prefix("crate::").unwrap_or(&joined);
+    PATCHES_MACRO_CANONICAL_PATHS.iter().any(|canonical| canonical.ends_with(joined))
+}
+
 struct SourceVisitor<'a> {
-    cotpatch: &'a mut String
+    cotpatch: &'a mut String,
+    /// `(type path's last segment, expected size in bytes)` for every `layout_asserts` entry
+    /// found in a `patches!` invocation.
+    layout_asserts: &'a mut Vec<(String, u64)>,
+    /// The manifest built from the `patches!` invocation in this file, once one has been visited.
+    manifest: &'a mut Option<PatchManifest>,
+    /// This file's `use`-alias map, consulted to resolve bare macro identifiers.
+    aliases: &'a HashMap<String, String>,
 }
 
 impl<'a> SourceVisitor<'a> {
-    fn new(cotpatch: &'a mut String) -> Self {
-        Self { cotpatch }
+    fn new(
+        cotpatch: &'a mut String,
+        layout_asserts: &'a mut Vec<(String, u64)>,
+        manifest: &'a mut Option<PatchManifest>,
+        aliases: &'a HashMap<String, String>,
+    ) -> Self {
+        Self { cotpatch, layout_asserts, manifest, aliases }
     }
 }
 
 impl<'ast> Visit<'ast> for SourceVisitor<'ast> {
     fn visit_item_macro(&mut self, i: &'ast ItemMacro) {
-        // TODO: This won't work for paths.
-        let name = i.mac.path.get_ident();
-        if name.is_none() {
+        if !is_patches_macro_path(&i.mac.path, self.aliases) {
             return;
         }
-        // TODO: This doesn't actually make sure this is *our* patches macro.
-        if name.unwrap().to_string().as_str() == "patches" {
-            // Process a patches macro.
-            let input = i.mac.tokens.clone().into();
-            let def = parse2::<PatchesDef>(input).unwrap();
-            if let Some(glue) = def.glue {
-                self.cotpatch.push_str(&glue);
-            }
+
+        // Process a patches macro.
+        let input = i.mac.tokens.clone().into();
+        let def = parse2::<PatchesDef>(input).unwrap();
+        *self.manifest = Some(PatchManifest::from_patches_def(&def));
+        if let Some(glue) = def.glue {
+            self.cotpatch.push_str(&glue);
+        }
+        for (ty, size) in def.layout_asserts {
+            let label = ty
+                .segments
+                .last()
+                .expect("a type path always has at least one segment")
+                .ident
+                .to_string();
+            let size: u64 = size.base10_parse().expect("layout assert size must fit a u64");
+            self.layout_asserts.push((label, size));
         }
     }
 }
@@ -83,22 +172,234 @@ pub fn generate_symbols_for_linker(cot_rot: &Path) {
     assert!(make_cmd.success(), "{}", ERR);
 }
 
+/// Emits `cargo:rustc-cfg=region="..."` (and the matching `cargo:rustc-check-cfg`) for the
+/// target's region, derived the same way [`generate_symbols_for_linker`] derives it for the
+/// linker script, so patch code can gate region-specific behavior with `#[cfg(region = "eu")]`
+/// instead of duplicating the target-triple parsing itself.
+///
+/// Call this from a downstream crate's `build.rs` alongside [`generate_symbols_for_linker`].
+pub fn emit_region_cfg() {
+    let target_region = TargetRegion::from_target_env()
+        .expect("Failed to determine game region. Make sure your target name ends with -na, -ja or -eu.");
+
+    println!("cargo:rustc-cfg=region=\"{}\"", target_region.as_str_lower());
+    println!("cargo:rustc-check-cfg=cfg(region, values(\"na\", \"ja\", \"eu\"))");
+}
 
 /// This collects the glue code from the !patches macro and dumps it into a .cotpatch file
 pub fn generate_cotpatch(out_file: &Path) {
-    // TODO: This only works if the patches block is in the main.rs.
-    let fname = PathBuf::from_str(env::var("CARGO_MANIFEST_DIR").unwrap().as_str())
+    let (cotpatch, _layout_asserts, _manifest) = parse_crate_patches_macros();
+
+    fs::write(out_file, cotpatch)
+        .expect(&format!("Unable to write to file: {:?}", out_file));
+}
+
+/// Generates a C header declaring the prototypes of the three entrypoints the `patches!` macro
+/// emits (`eos_rs_apply_item_effect`, `eos_rs_apply_move_effect`, `eos_rs_call_special_process`),
+/// plus one `_Static_assert` per `layout_asserts` entry declared in the `patches!` invocation.
+///
+/// Include this header from `src/item_effects.c` and friends instead of hand-writing the
+/// prototypes, so a signature change on the Rust side fails the C build instead of silently
+/// producing a mismatched call.
+///
+/// # Caveat
+/// The `_Static_assert`s use the last segment of the asserted Rust type's path as the C struct
+/// name (e.g. `eos_rs::ffi::entity` becomes `entity`). If a type's C name doesn't match its Rust
+/// name one-for-one (for example a trailing underscore used to dodge a Rust keyword, like
+/// `move_`), the generated assertion will need a manual name fix.
+pub fn generate_ffi_header(out_file: &Path) {
+    let (_cotpatch, layout_asserts, _manifest) = parse_crate_patches_macros();
+
+    let mut header = String::new();
+    header.push_str("// Generated by eos-rs-build. Do not edit by hand.\n");
+    header.push_str("#pragma once\n\n");
+    header.push_str("#include <stdint.h>\n\n");
+    header.push_str(
+        "bool_ eos_rs_apply_item_effect(struct entity *user, struct entity *target, \
+         struct item *used_item, bool_ is_thrown);\n",
+    );
+    header.push_str(
+        "bool_ eos_rs_apply_move_effect(struct move_effect_input *data, struct entity *user, \
+         struct entity *target, struct move *used_move);\n",
+    );
+    header.push_str(
+        "void eos_rs_call_special_process(undefined4 *unknown, unsigned int special_process_id, \
+         short arg1, short arg2, int *return_val);\n",
+    );
+
+    if !layout_asserts.is_empty() {
+        header.push('\n');
+        for (label, size) in layout_asserts {
+            header.push_str(&format!(
+                "_Static_assert(sizeof(struct {label}) == {size}, \"eos-rs/C layout mismatch for {label}\");\n"
+            ));
+        }
+    }
+
+    fs::write(out_file, header)
+        .expect(&format!("Unable to write to file: {:?}", out_file));
+}
+
+/// Generates a standalone C test program that verifies, at both compile time and run time, that
+/// every type named in a `layout_asserts` entry actually has the asserted size in the real game
+/// headers.
+///
+/// This is meant to be compiled and run as its own CI target (linked against the project's C
+/// headers/stubs, the same way `src/item_effects.c` is), separately from the normal game build,
+/// so a layout mismatch shows up as a dedicated test failure instead of a miscompile or a
+/// silently wrong item ID at runtime.
+///
+/// # Caveat
+/// This only checks `sizeof`, reusing the same `layout_asserts` data [`generate_ffi_header`]
+/// does. Verifying alignment, field offsets, and `extern fn` signatures across the *entire*
+/// binding surface (as opposed to the types a `patches!` invocation happens to assert on) would
+/// need to walk the bindgen-generated `ffi` declarations themselves; that's left as a follow-up,
+/// since this build crate only sees what `patches!` was told to assert, not the full binding set.
+pub fn generate_layout_verification_harness(out_file: &Path) {
+    let (_cotpatch, layout_asserts, _manifest) = parse_crate_patches_macros();
+
+    let mut harness = String::new();
+    harness.push_str("// Generated by eos-rs-build. Do not edit by hand.\n");
+    harness.push_str("#include <stdio.h>\n#include <assert.h>\n#include <stdint.h>\n\n");
+
+    for (label, size) in &layout_asserts {
+        harness.push_str(&format!(
+            "_Static_assert(sizeof(struct {label}) == {size}, \"eos-rs/C layout mismatch for {label}\");\n"
+        ));
+    }
+
+    harness.push_str("\nint main(void) {\n");
+    for (label, size) in &layout_asserts {
+        harness.push_str(&format!(
+            "    assert(sizeof(struct {label}) == {size});\n    printf(\"OK  sizeof(struct {label}) == {size}\\n\");\n"
+        ));
+    }
+    harness.push_str("    return 0;\n}\n");
+
+    fs::write(out_file, harness)
+        .expect(&format!("Unable to write to file: {:?}", out_file));
+}
+
+/// Writes the [`PatchManifest`] merged from every `patches!` invocation across the crate to
+/// `out_file` as JSON, so external tooling (e.g. SkyTemple) can see every registered item/move
+/// effect and special process, and every raw patch's glue label, without re-parsing Rust source
+/// itself.
+///
+/// Call this from a downstream crate's `build.rs` with an `OUT_DIR`-relative path, then
+/// `include_str!`/read it back at whatever point the tooling needs it.
+pub fn generate_patch_manifest(out_file: &Path) {
+    let (_cotpatch, _layout_asserts, manifest) = parse_crate_patches_macros();
+    let manifest = manifest.expect("No `patches!` invocation found anywhere in `src/`");
+
+    let json = serde_json::to_string_pretty(&manifest).expect("Failed to serialize patch manifest");
+    fs::write(out_file, json).expect(&format!("Unable to write to file: {:?}", out_file));
+}
+
+/// Merges `new_asserts` (gathered from `file`) into `asserts`, which accumulates across every
+/// file visited so far. An identical assertion (same label and size) repeated in multiple files
+/// is fine and only kept once; the same label asserted at two different sizes is a build error,
+/// since at most one of the two could ever be right.
+fn merge_layout_asserts(asserts: &mut Vec<(String, u64)>, new_asserts: Vec<(String, u64)>, file: &Path) {
+    for (label, size) in new_asserts {
+        match asserts.iter().find(|(existing_label, _)| *existing_label == label) {
+            Some((_, existing_size)) if *existing_size != size => panic!(
+                "conflicting `layout_asserts` for `{label}`: asserted to be {size} bytes in {}, but {existing_size} bytes elsewhere in the crate",
+                file.display(),
+            ),
+            Some(_) => {}
+            None => asserts.push((label, size)),
+        }
+    }
+}
+
+/// Merges `new_entries` (gathered from a `patches!` invocation in `file`) into `entries`, which
+/// accumulates across every file visited so far. Panics if `file`'s invocation registers the same
+/// numeric ID, or the same Rust source identifier, as an invocation already merged from an
+/// earlier file: runtime dispatch (for IDs) or the linker (for a `RawPatch`'s `#[no_mangle]` name)
+/// couldn't tell the two apart either.
+fn merge_manifest_entries(entries: &mut Vec<PatchManifestEntry>, new_entries: Vec<PatchManifestEntry>, file: &Path) {
+    let merged_before = entries.len();
+    for entry in new_entries {
+        if let Some(id) = entry.id {
+            if let Some(conflict) = entries[..merged_before].iter().find(|e| e.category == entry.category && e.id == Some(id)) {
+                panic!(
+                    "conflicting `patches!` definitions in {}: ID {id} is registered by both `{}` and `{}`",
+                    file.display(),
+                    conflict.source_identifier,
+                    entry.source_identifier,
+                );
+            }
+        }
+        if let Some(conflict) = entries[..merged_before]
+            .iter()
+            .find(|e| e.category == entry.category && e.source_identifier == entry.source_identifier)
+        {
+            panic!(
+                "conflicting `patches!` definitions in {}: `{}` is registered more than once (already registered by a `patches!` invocation elsewhere in the crate, as `{}`)",
+                file.display(),
+                entry.source_identifier,
+                conflict.source_identifier,
+            );
+        }
+        entries.push(entry);
+    }
+}
+
+/// Parses every `patches!` invocation across the whole crate (every file under `src/`), matching
+/// the macro by its resolved path instead of only a bare `patches` identifier in `src/main.rs`,
+/// so patch definitions can be organized across multiple modules instead of crammed into one
+/// file. See [`is_patches_macro_path`] for exactly which spellings are recognized.
+///
+/// Glue and `layout_asserts` are accumulated across every matched invocation (see
+/// [`merge_layout_asserts`]); manifest entries are merged into one [`PatchManifest`] (see
+/// [`merge_manifest_entries`]). Files are visited in sorted path order, so the output is
+/// deterministic regardless of the filesystem's own directory iteration order.
+fn parse_crate_patches_macros() -> (String, Vec<(String, u64)>, Option<PatchManifest>) {
+    let src_dir = PathBuf::from_str(env::var("CARGO_MANIFEST_DIR").unwrap().as_str())
         .unwrap()
-        .join("src/main.rs");
+        .join("src");
 
-    let content = read_to_string(&fname).expect(&format!("Unable to read Rust source file: {:?}", &fname));
-    let syntax = parse_file(&content).expect(&format!("Unable to parse Rust source file: {:?}", &fname));
+    let mut files: Vec<PathBuf> = glob(&format!("{}/**/*.rs", src_dir.to_str().unwrap()))
+        .expect("Invalid glob pattern for crate source files")
+        .flatten()
+        .collect();
+    files.sort();
 
     let mut cotpatch = String::new();
+    let mut layout_asserts = Vec::new();
+    let mut manifest_entries = Vec::new();
+    let mut any_matched = false;
 
-    let mut visitor = SourceVisitor::new(&mut cotpatch);
-    visitor.visit_file(&syntax);
+    for file in &files {
+        let content = read_to_string(file).expect(&format!("Unable to read Rust source file: {:?}", file));
+        let syntax = parse_file(&content).expect(&format!("Unable to parse Rust source file: {:?}", file));
 
-    fs::write(out_file, cotpatch)
-        .expect(&format!("Unable to write to file: {:?}", out_file));
+        let mut alias_collector = UseAliasCollector::default();
+        alias_collector.visit_file(&syntax);
+
+        let mut file_cotpatch = String::new();
+        let mut file_layout_asserts = Vec::new();
+        let mut file_manifest = None;
+        let mut visitor = SourceVisitor::new(
+            &mut file_cotpatch,
+            &mut file_layout_asserts,
+            &mut file_manifest,
+            &alias_collector.aliases,
+        );
+        visitor.visit_file(&syntax);
+
+        cotpatch.push_str(&file_cotpatch);
+        merge_layout_asserts(&mut layout_asserts, file_layout_asserts, file);
+        if let Some(file_manifest) = file_manifest {
+            any_matched = true;
+            merge_manifest_entries(&mut manifest_entries, file_manifest.entries, file);
+        }
+    }
+
+    let manifest = any_matched.then(|| PatchManifest {
+        schema_version: PATCH_MANIFEST_SCHEMA_VERSION,
+        entries: manifest_entries,
+    });
+
+    (cotpatch, layout_asserts, manifest)
 }
@@ -2,6 +2,8 @@ use std::env;
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 
+use eos_rs_patches_def::did_you_mean;
+
 #[derive(Debug)]
 pub enum TargetRegionError {
     Unknown(String),
@@ -12,7 +14,11 @@ impl Display for TargetRegionError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             TargetRegionError::Unknown(reg) => {
-                write!(f, "The game region '{reg}' is unknown.")
+                write!(f, "The game region '{reg}' is unknown.")?;
+                if let Some(suggestion) = did_you_mean(reg, &["eu", "na", "ja"]) {
+                    write!(f, " (did you mean `{suggestion}`?)")?;
+                }
+                Ok(())
             }
             TargetRegionError::Missing => {
                 write!(f, "The game region could not be determined from the target name. Make sure the target name ends in -na, -eu or -ja.")
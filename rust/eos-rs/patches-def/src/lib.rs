@@ -1,8 +1,96 @@
+use std::collections::HashMap;
+
 use syn::__private::quote::__private::TokenStream;
 use syn::__private::ToTokens;
 use syn::parse::{Parse, ParseStream};
 use syn::{Result, TypePath};
 
+pub mod manifest;
+
+/// The largest patch ID `patches!` will accept: IDs are matched against a 16-bit field
+/// (`used_item.id.val()`, `used_move.id.val()`, or the script engine's special process ID), so
+/// anything above this can never actually be reached at runtime.
+const MAX_PATCH_ID: u32 = u16::MAX as u32;
+
+/// Parses a patch ID literal, rejecting it (pointing at its own span) if it falls outside
+/// [`MAX_PATCH_ID`].
+fn parse_id_literal(input: ParseStream) -> Result<u32> {
+    let lit = input.parse::<syn::LitInt>()?;
+    let val: u32 = lit
+        .base10_digits()
+        .parse()
+        .map_err(|_| syn::Error::new(lit.span(), "patch ID literal must fit in a u32"))?;
+    if val > MAX_PATCH_ID {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!("patch ID {val} is out of range: IDs must fit in 0..={MAX_PATCH_ID}"),
+        ));
+    }
+    Ok(val)
+}
+
+/// Registers `id` as belonging to `name` in `seen`, returning a `compile_error!`-worthy
+/// [`syn::Error`] pointing at `name`'s span (and naming the earlier registration) if `id` was
+/// already registered by a different patch in the same category.
+fn check_duplicate_id(seen: &mut HashMap<u32, syn::Ident>, id: u32, name: &syn::Ident) -> Result<()> {
+    if let Some(first) = seen.get(&id) {
+        return Err(syn::Error::new(
+            name.span(),
+            format!(
+                "duplicate patch ID {id}: already registered by `{first}`, also registered here by `{name}`"
+            ),
+        ));
+    }
+    seen.insert(id, name.clone());
+    Ok(())
+}
+
+/// Computes the Levenshtein (edit) distance between `a` and `b`: the minimum number of
+/// single-character insertions, deletions or substitutions needed to turn one into the other.
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let n = b.len();
+
+    let mut prev: Vec<usize> = (0..=n).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut cur = vec![0; n + 1];
+        cur[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            cur[j + 1] = (prev[j + 1] + 1)
+                .min(cur[j] + 1)
+                .min(prev[j] + usize::from(ca != cb));
+        }
+        prev = cur;
+    }
+    prev[n]
+}
+
+/// Picks the closest match for `input` among `candidates` by Levenshtein distance, to use in a
+/// "did you mean `X`?" suggestion.
+///
+/// Returns `None` if `candidates` is empty, or if even the closest candidate is too far from
+/// `input` to plausibly be a typo (distance greater than both `3` and half of `input`'s length).
+pub fn did_you_mean<'a>(input: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let input_lower = input.to_lowercase();
+    let (candidate, distance) = candidates
+        .iter()
+        .map(|&candidate| {
+            (
+                candidate,
+                levenshtein_distance(&input_lower, &candidate.to_lowercase()),
+            )
+        })
+        .min_by_key(|(_, distance)| *distance)?;
+
+    let max_allowed = 3.max(input.chars().count() / 2);
+    if distance <= max_allowed {
+        Some(candidate)
+    } else {
+        None
+    }
+}
+
 pub enum Idx {
     TypePath(TypePath),
     U32(u32),
@@ -17,10 +105,22 @@ impl ToTokens for Idx {
     }
 }
 
+/// Parsing rejects (with a span pointing at the offending identifier or literal) a duplicate
+/// numeric ID within the same category, and a literal ID outside `0..=MAX_PATCH_ID`.
 pub struct PatchesDef {
     pub item_effects: Vec<(Idx, syn::Ident)>,
     pub move_effects: Vec<(Idx, syn::Ident)>,
     pub special_processes: Vec<(Idx, syn::Ident)>,
+    /// Entries from an optional `layout_asserts: { <path> => <expected size>, ... }` block.
+    ///
+    /// The macro has no way to know the true size of an opaque FFI struct on its own, so the
+    /// expected size must be supplied by the invoker (e.g. sourced from `pmdsky-debug`); the
+    /// macro only turns it into a mechanical, always-checked assertion.
+    pub layout_asserts: Vec<(syn::Path, syn::LitInt)>,
+    /// Raw patches, identified by their own `#[no_mangle]` function name. These aren't otherwise
+    /// tracked by this struct; the name is only kept so tooling (see [`manifest`]) can see that
+    /// they were registered.
+    pub raw_patches: Vec<syn::Ident>,
     pub glue: Option<String>,
 }
 
@@ -29,8 +129,14 @@ impl Parse for PatchesDef {
         let mut item_effects = Vec::new();
         let mut move_effects = Vec::new();
         let mut special_processes = Vec::new();
+        let mut layout_asserts = Vec::new();
+        let mut raw_patches = Vec::new();
         let mut glue = None;
 
+        let mut item_ids_seen = HashMap::new();
+        let mut move_ids_seen = HashMap::new();
+        let mut special_process_ids_seen = HashMap::new();
+
         while !input.is_empty() {
             while input.peek(syn::Token![,]) {
                 input.parse::<syn::Token![,]>()?;
@@ -41,6 +147,21 @@ impl Parse for PatchesDef {
                 break;
             }
             let name = input.parse::<syn::Ident>()?;
+            if name == "layout_asserts" {
+                input.parse::<syn::Token![:]>()?;
+                let content;
+                syn::braced!(content in input);
+                while !content.is_empty() {
+                    let ty = content.parse::<syn::Path>()?;
+                    content.parse::<syn::Token![=>]>()?;
+                    let size = content.parse::<syn::LitInt>()?;
+                    layout_asserts.push((ty, size));
+                    if content.peek(syn::Token![,]) {
+                        content.parse::<syn::Token![,]>()?;
+                    }
+                }
+                continue;
+            }
             if input.peek(syn::Token![:]) {
                 input.parse::<syn::Token![:]>()?;
                 let typ = input.parse::<syn::Ident>()?.to_string();
@@ -49,42 +170,51 @@ impl Parse for PatchesDef {
                         if let Ok(tp) = input.parse::<TypePath>() {
                             special_processes.push((Idx::TypePath(tp), name));
                         } else {
-                            let i = input.parse::<syn::LitInt>()?;
-                            let j = i.base10_digits();
-                            special_processes.push((Idx::U32(j.parse::<u32>().unwrap()), name));
+                            let id = parse_id_literal(input)?;
+                            check_duplicate_id(&mut special_process_ids_seen, id, &name)?;
+                            special_processes.push((Idx::U32(id), name));
                         }
                     }
                     "item_effect" => {
                         if let Ok(tp) = input.parse::<TypePath>() {
                             item_effects.push((Idx::TypePath(tp), name));
                         } else {
-                            let i = input.parse::<syn::LitInt>()?;
-                            let j = i.base10_digits();
-                            item_effects.push((Idx::U32(j.parse::<u32>().unwrap()), name));
+                            let id = parse_id_literal(input)?;
+                            check_duplicate_id(&mut item_ids_seen, id, &name)?;
+                            item_effects.push((Idx::U32(id), name));
                         }
                     }
                     "move_effect" => {
                         if let Ok(tp) = input.parse::<TypePath>() {
                             move_effects.push((Idx::TypePath(tp), name));
                         } else {
-                            let i = input.parse::<syn::LitInt>()?;
-                            let j = i.base10_digits();
-                            move_effects.push((Idx::U32(j.parse::<u32>().unwrap()), name));
+                            let id = parse_id_literal(input)?;
+                            check_duplicate_id(&mut move_ids_seen, id, &name)?;
+                            move_effects.push((Idx::U32(id), name));
                         }
                     }
                     x => {
-                        return Err(syn::Error::new(
-                            input.span(),
-                            format!("Unknown patch type for patch {name}: {x}"),
-                        ));
+                        let mut msg = format!("Unknown patch type for patch {name}: {x}");
+                        if let Some(suggestion) = did_you_mean(
+                            x,
+                            &["special_process", "item_effect", "move_effect"],
+                        ) {
+                            msg.push_str(&format!(" (did you mean `{suggestion}`?)"));
+                        }
+                        return Err(syn::Error::new(input.span(), msg));
                     }
                 }
+            } else {
+                // A raw patch: just the bare function name, nothing to register it against.
+                raw_patches.push(name);
             }
         }
         Ok(Self {
             item_effects,
             move_effects,
             special_processes,
+            layout_asserts,
+            raw_patches,
             glue,
         })
     }
@@ -0,0 +1,86 @@
+//! A machine-readable description of everything a `patches!` invocation registers, so
+//! `eos-rs-build` and downstream SkyTemple tooling can introspect a build's occupied item/move
+//! effect and special process IDs without re-parsing Rust source themselves.
+//!
+//! `eos-rs-build` is responsible for actually writing this out (see `generate_patch_manifest`
+//! there); this module only defines the shape of the data.
+
+use quote::quote;
+use serde::{Deserialize, Serialize};
+
+use crate::{Idx, PatchesDef};
+
+/// Bumped whenever [`PatchManifest`]'s shape changes in a way older readers can't cope with.
+pub const PATCH_MANIFEST_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchCategory {
+    ItemEffect,
+    MoveEffect,
+    SpecialProcess,
+    /// A raw patch, registered only by its `#[no_mangle]` function name with no ID of its own.
+    RawPatch,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PatchManifestEntry {
+    pub category: PatchCategory,
+    /// The Rust identifier of the function that handles this patch.
+    pub source_identifier: String,
+    /// The numeric ID, when the patch was registered with an integer literal.
+    pub id: Option<u32>,
+    /// The source text of the ID expression, when the patch was registered with a path (e.g.
+    /// `ItemId::ITEM_ORAN_BERRY`) whose numeric value isn't known without a full compile.
+    pub id_expr: Option<String>,
+    /// For [`PatchCategory::RawPatch`] only: the ASM glue label used to branch into this patch,
+    /// which is just its own function name (raw patches are called directly by name from glue).
+    pub glue_label: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PatchManifest {
+    pub schema_version: u32,
+    pub entries: Vec<PatchManifestEntry>,
+}
+
+impl PatchManifest {
+    /// Builds a manifest describing everything registered in `def`.
+    pub fn from_patches_def(def: &PatchesDef) -> Self {
+        let mut entries = Vec::new();
+
+        let mut collect = |category: PatchCategory, items: &[(Idx, syn::Ident)]| {
+            for (idx, name) in items {
+                let (id, id_expr) = match idx {
+                    Idx::U32(id) => (Some(*id), None),
+                    Idx::TypePath(tp) => (None, Some(quote!(#tp).to_string())),
+                };
+                entries.push(PatchManifestEntry {
+                    category,
+                    source_identifier: name.to_string(),
+                    id,
+                    id_expr,
+                    glue_label: None,
+                });
+            }
+        };
+        collect(PatchCategory::ItemEffect, &def.item_effects);
+        collect(PatchCategory::MoveEffect, &def.move_effects);
+        collect(PatchCategory::SpecialProcess, &def.special_processes);
+
+        for name in &def.raw_patches {
+            entries.push(PatchManifestEntry {
+                category: PatchCategory::RawPatch,
+                source_identifier: name.to_string(),
+                id: None,
+                id_expr: None,
+                glue_label: Some(name.to_string()),
+            });
+        }
+
+        Self {
+            schema_version: PATCH_MANIFEST_SCHEMA_VERSION,
+            entries,
+        }
+    }
+}
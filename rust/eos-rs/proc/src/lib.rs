@@ -55,9 +55,13 @@ use syn::parse_macro_input;
 ///     target: &mut eos_rs::api::objects::DungeonEntity,
 ///     used_item: &mut eos_rs::api::objects::DungeonItem,
 ///     is_thrown: bool
-/// ) { /* ... */ }
+/// ) /* -> () or eos_rs::api::dungeon_mode::ItemEffectOutcome */ { /* ... */ }
 /// ```
 ///
+/// Returning `()` is always treated as `ItemEffectOutcome::Applied`, matching this macro's
+/// historical behavior. Return `eos_rs::api::dungeon_mode::ItemEffectOutcome` directly for control
+/// over whether `src/item_effects.c`'s fallback chain considers the item handled.
+///
 /// ## Move Effect
 /// Registers a function that will be called for the defined move when it is used in a dungeon.
 ///
@@ -73,9 +77,14 @@ use syn::parse_macro_input;
 ///     user: &mut eos_rs::api::objects::DungeonEntity,
 ///     target: &mut eos_rs::api::objects::DungeonEntity,
 ///     used_move: &mut eos_rs::api::objects::Move
-/// ) { /* ... */ }
+/// ) -> bool /* or eos_rs::api::dungeon_mode::MoveEffectOutcome */ { /* ... */ }
 /// ```
 ///
+/// The `bool` form (whether the move dealt damage) is always treated as
+/// `MoveEffectOutcome::Applied`, matching this macro's historical behavior. Return
+/// `eos_rs::api::dungeon_mode::MoveEffectOutcome` directly for control over whether
+/// `src/item_effects.c`'s fallback chain considers the move handled.
+///
 /// ## Special Process
 /// Registers a function that can be called from the script engine using the "special process"
 /// mechanism.
@@ -91,6 +100,29 @@ use syn::parse_macro_input;
 /// pub fn function(arg1: i16, arg2: i16, ov11: &eos_rs::api::overlay::OverlayLoadLease<11>) -> i32 { /* ... */ 0 }
 /// ```
 ///
+/// ## Layout assertions
+/// An optional `layout_asserts: { <path to type> => <expected size in bytes>, ... }` block checks
+/// that the listed FFI struct(s) still have the size you expect, failing the build with a normal
+/// `assert!` panic message (pointing at this macro invocation) if they don't. This is meant to
+/// catch accidental drift between the `ffi` bindings and the actual ROM layout, e.g. after
+/// regenerating bindings from an updated `pmdsky-debug` symbol table.
+///
+/// The macro has no way to independently know the correct size of an opaque FFI struct, so you
+/// must supply the expected size yourself (e.g. from `pmdsky-debug`'s documented struct sizes).
+///
+/// ### Example
+/// ```
+/// # use eos_rs_proc::patches;
+/// patches! {
+///     layout_asserts: {
+///         eos_rs::ffi::entity => 0xC,
+///     },
+/// }
+/// ```
+///
+/// A matching header with `_Static_assert`s for the same types can be generated on the C side
+/// by `eos-rs-build`'s `generate_ffi_header`, so both languages agree on the same expected sizes.
+///
 /// ## ASM glue code
 /// This is a literal string. It will later during the build process be converted into a `cotpatch`
 /// file that is placed in the `patches/` directory of `c-of-time`.
@@ -171,13 +203,30 @@ pub fn patches(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
     let item_effects_cases = def.item_effects.iter().map(|(idx, fn_name)| {
         quote! {
-            #idx => {#fn_name(&effects, user, target, used_item, is_thrown > 0); 1},
+            #idx => {
+                let outcome: eos_rs::api::dungeon_mode::ItemEffectOutcome =
+                    #fn_name(&effects, user, target, used_item, is_thrown > 0).into();
+                match outcome {
+                    eos_rs::api::dungeon_mode::ItemEffectOutcome::Applied => 1,
+                    eos_rs::api::dungeon_mode::ItemEffectOutcome::NotApplied => 0,
+                }
+            },
         }
     });
 
     let move_effects_cases = def.move_effects.iter().map(|(idx, fn_name)| {
         quote! {
-            #idx => {data.out_dealt_damage = #fn_name(&effects, user, target, used_move) as u8; 1},
+            #idx => {
+                let outcome: eos_rs::api::dungeon_mode::MoveEffectOutcome =
+                    #fn_name(&effects, user, target, used_move).into();
+                match outcome {
+                    eos_rs::api::dungeon_mode::MoveEffectOutcome::Applied { dealt_damage } => {
+                        data.out_dealt_damage = dealt_damage as u8;
+                        1
+                    }
+                    eos_rs::api::dungeon_mode::MoveEffectOutcome::NotApplied => 0,
+                }
+            },
         }
     });
 
@@ -187,7 +236,16 @@ pub fn patches(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         }
     });
 
+    let layout_asserts = def.layout_asserts.iter().map(|(ty, size)| {
+        quote! {
+            const _: () = assert!(core::mem::size_of::<#ty>() == #size);
+        }
+    });
+
     (quote! {
+        #(#layout_asserts)*
+
+
         #[no_mangle]
         pub unsafe extern "C" fn eos_rs_apply_item_effect(
             user: *mut eos_rs::ffi::entity,
@@ -195,7 +253,7 @@ pub fn patches(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             used_item: *mut eos_rs::ffi::item,
             is_thrown: eos_rs::ffi::bool_
         ) -> eos_rs::ffi::bool_ {
-            eos_rs::log_impl::register_logger();
+            eos_rs::log_impl::register_logger(eos_rs::log_impl::LevelFilter::Debug);
             let effects = eos_rs::api::dungeon_mode::DungeonEffectsEmitter::new_unchecked();
             let user = &mut *user;
             let target = &mut *target;
@@ -213,7 +271,7 @@ pub fn patches(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             target: *mut eos_rs::ffi::entity,
             used_move: *mut eos_rs::ffi::move_,
         ) -> eos_rs::ffi::bool_ {
-            eos_rs::log_impl::register_logger();
+            eos_rs::log_impl::register_logger(eos_rs::log_impl::LevelFilter::Debug);
             let effects = eos_rs::api::dungeon_mode::DungeonEffectsEmitter::new_unchecked();
             let user = &mut *user;
             let target = &mut *target;
@@ -234,7 +292,7 @@ pub fn patches(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             return_val: *mut i32
         ) {
             let return_val = unsafe { &mut*return_val };
-            eos_rs::log_impl::register_logger();
+            eos_rs::log_impl::register_logger(eos_rs::log_impl::LevelFilter::Debug);
             let lease = eos_rs::api::overlay::OverlayLoadLease::<11>::acquire_unchecked();
             match special_process_id {
                 #(#special_processes_cases)*
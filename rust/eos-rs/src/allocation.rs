@@ -5,8 +5,65 @@ use crate::ffi;
 #[cfg(not(test))]
 use crate::panic;
 use core::alloc::{AllocError, Allocator, GlobalAlloc, Layout};
+use core::marker::PhantomData;
+use core::mem::size_of;
 use core::ptr::NonNull;
 
+/// The alignment the game's allocation functions guarantee on their own, with no help from
+/// [`alloc_aligned`]/[`dealloc_aligned`]'s over-alignment scheme.
+const MIN_ALIGN: usize = size_of::<usize>();
+
+/// Implements [`Layout`]-correct over-alignment on top of a raw allocator that only guarantees
+/// [`MIN_ALIGN`] itself (`raw_alloc` wraps `ffi::MemAlloc`/`ffi::MemLocateSet`, already adapted by
+/// the caller to take just a byte count and return the raw pointer).
+///
+/// For `layout.align() <= MIN_ALIGN` this is just `raw_alloc(layout.size())` -- the common case
+/// pays no overhead. Otherwise it over-allocates `layout.size() + layout.align() +
+/// size_of::<usize>()` bytes from `raw_alloc`, rounds the returned pointer up to `layout.align()`
+/// (reserving room for a `usize` immediately before the aligned pointer), and stashes the original
+/// pointer `raw_alloc` returned in that slot, so [`dealloc_aligned`] can recover it and hand the
+/// *exact* pointer back to the matching `raw_dealloc`, as the game's allocator requires.
+unsafe fn alloc_aligned(layout: Layout, raw_alloc: impl FnOnce(u32) -> *mut u8) -> *mut u8 {
+    let align = layout.align();
+    assert!(align.is_power_of_two(), "alignment must be a power of two");
+    if align <= MIN_ALIGN {
+        return raw_alloc(layout.size() as u32);
+    }
+    let slack = align + MIN_ALIGN;
+    let raw_ptr = raw_alloc((layout.size() + slack) as u32);
+    if raw_ptr.is_null() {
+        return raw_ptr;
+    }
+    let min_data_start = raw_ptr as usize + MIN_ALIGN;
+    let aligned_addr = (min_data_start + align - 1) & !(align - 1);
+    let aligned_ptr = aligned_addr as *mut u8;
+    unsafe {
+        (aligned_ptr as *mut usize).sub(1).write(raw_ptr as usize);
+    }
+    aligned_ptr
+}
+
+/// Bit in `ffi::MemAlloc`/`ffi::MemLocateSet`'s `flags` argument that asks the game's allocator to
+/// clear the returned block itself, so callers that want zeroed memory (`Box::new_zeroed`,
+/// `vec![0u8; n]`, ...) don't pay for a second, CPU-side `write_bytes` on top of the allocation.
+const MEM_ALLOC_FLAG_CLEAR: u32 = 1;
+
+/// Reverses [`alloc_aligned`]: when `layout.align() > MIN_ALIGN`, recovers the original pointer
+/// `raw_alloc` returned (stored immediately before `ptr`) and hands *that* to `raw_dealloc`,
+/// since the game's allocator requires the exact pointer it originally returned. For
+/// `layout.align() <= MIN_ALIGN`, `ptr` already is that pointer.
+unsafe fn dealloc_aligned(ptr: *mut u8, layout: Layout, raw_dealloc: impl FnOnce(*mut u8)) {
+    if ptr.is_null() {
+        return;
+    }
+    if layout.align() <= MIN_ALIGN {
+        raw_dealloc(ptr);
+        return;
+    }
+    let raw_ptr = unsafe { (ptr as *mut usize).sub(1).read() } as *mut u8;
+    raw_dealloc(raw_ptr);
+}
+
 #[cfg(feature = "global_allocator")]
 #[cfg(not(test))]
 #[global_allocator]
@@ -23,11 +80,21 @@ pub struct EoSDefaultAllocator;
 
 unsafe impl GlobalAlloc for EoSDefaultAllocator {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        ffi::MemAlloc(layout.size() as u32, 0) as *mut u8
+        unsafe { alloc_aligned(layout, |size| ffi::MemAlloc(size, 0) as *mut u8) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        unsafe {
+            alloc_aligned(layout, |size| {
+                ffi::MemAlloc(size, MEM_ALLOC_FLAG_CLEAR) as *mut u8
+            })
+        }
     }
 
-    unsafe fn dealloc(&self, ptr: *mut u8, _layout: Layout) {
-        ffi::MemFree(ptr as *mut ctypes::c_void);
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe {
+            dealloc_aligned(ptr, layout, |raw| ffi::MemFree(raw as *mut ctypes::c_void));
+        }
     }
 }
 
@@ -35,14 +102,28 @@ unsafe impl GlobalAlloc for EoSDefaultAllocator {
 unsafe impl Allocator for EoSDefaultAllocator {
     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
         unsafe {
-            let raw_ptr = ffi::MemAlloc(layout.size() as u32, 0) as *mut u8;
+            let raw_ptr = alloc_aligned(layout, |size| ffi::MemAlloc(size, 0) as *mut u8);
             let ptr = NonNull::new(raw_ptr).ok_or(AllocError)?;
             Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
         }
     }
 
-    unsafe fn deallocate(&self, ptr: NonNull<u8>, _layout: Layout) {
-        ffi::MemFree(ptr.as_ptr() as *mut ctypes::c_void);
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe {
+            let raw_ptr = alloc_aligned(layout, |size| {
+                ffi::MemAlloc(size, MEM_ALLOC_FLAG_CLEAR) as *mut u8
+            });
+            let ptr = NonNull::new(raw_ptr).ok_or(AllocError)?;
+            Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+        }
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe {
+            dealloc_aligned(ptr.as_ptr(), layout, |raw| {
+                ffi::MemFree(raw as *mut ctypes::c_void)
+            });
+        }
     }
 }
 
@@ -64,11 +145,23 @@ impl EoSCustomAllocator {
 /// and register your custom allocator as the global allocator.
 unsafe impl GlobalAlloc for EoSCustomAllocator {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        ffi::MemLocateSet(self.0, layout.size() as u32, 0) as *mut u8
+        unsafe { alloc_aligned(layout, |size| ffi::MemLocateSet(self.0, size, 0) as *mut u8) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        unsafe {
+            alloc_aligned(layout, |size| {
+                ffi::MemLocateSet(self.0, size, MEM_ALLOC_FLAG_CLEAR) as *mut u8
+            })
+        }
     }
 
-    unsafe fn dealloc(&self, ptr: *mut u8, _layout: Layout) {
-        ffi::MemLocateUnset(self.0, ptr as *mut ctypes::c_void);
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe {
+            dealloc_aligned(ptr, layout, |raw| {
+                ffi::MemLocateUnset(self.0, raw as *mut ctypes::c_void)
+            });
+        }
     }
 }
 
@@ -76,14 +169,30 @@ unsafe impl GlobalAlloc for EoSCustomAllocator {
 unsafe impl Allocator for EoSCustomAllocator {
     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
         unsafe {
-            let raw_ptr = ffi::MemLocateSet(self.0, layout.size() as u32, 0) as *mut u8;
+            let raw_ptr = alloc_aligned(layout, |size| {
+                ffi::MemLocateSet(self.0, size, 0) as *mut u8
+            });
+            let ptr = NonNull::new(raw_ptr).ok_or(AllocError)?;
+            Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+        }
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe {
+            let raw_ptr = alloc_aligned(layout, |size| {
+                ffi::MemLocateSet(self.0, size, MEM_ALLOC_FLAG_CLEAR) as *mut u8
+            });
             let ptr = NonNull::new(raw_ptr).ok_or(AllocError)?;
             Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
         }
     }
 
-    unsafe fn deallocate(&self, ptr: NonNull<u8>, _layout: Layout) {
-        ffi::MemLocateUnset(self.0, ptr.as_ptr() as *mut ctypes::c_void);
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe {
+            dealloc_aligned(ptr.as_ptr(), layout, |raw| {
+                ffi::MemLocateUnset(self.0, raw as *mut ctypes::c_void)
+            });
+        }
     }
 }
 
@@ -100,10 +209,116 @@ pub unsafe fn create_mem_arena(mem: *mut ffi::iovec, max_blocks: u32) -> *mut ff
     ffi::CreateMemArena(mem, max_blocks)
 }
 
-/// Allocation error handler. Will freeze the game and output an error message.
+/// A scratch memory arena borrowed from a backing buffer, for one dungeon-generation pass, one
+/// frame of AI work, or anything else that wants a stack-discipline "allocate everything, drop
+/// it all at once" chunk of the game's fixed heap.
+///
+/// Wraps [`create_mem_arena`]/[`EoSCustomAllocator`] in a safe API: build one over a `&mut [u8]`
+/// backing buffer, pass [`ScopedArena::allocator`] to `Vec::new_in`/`Box::new_in`/..., and let it
+/// go out of scope to release everything at once. Tying the arena's lifetime to the backing
+/// buffer's borrow makes a dangling [`EoSCustomAllocator`] impossible: the borrow checker won't
+/// let the buffer be reused, or the arena outlive it.
+pub struct ScopedArena<'a> {
+    allocator: EoSCustomAllocator,
+    _backing: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> ScopedArena<'a> {
+    /// Creates a scratch arena within `backing`, able to hold up to `max_blocks` allocations.
+    ///
+    /// `max_blocks` must still be small enough to fit within `backing`'s length, same as for
+    /// [`create_mem_arena`] -- this constructor can't check that itself, since the arena header's
+    /// exact per-block overhead isn't part of this crate's safe surface; pick a generous backing
+    /// buffer if you're unsure.
+    pub fn new(backing: &'a mut [u8], max_blocks: u32) -> Self {
+        let mut iov = ffi::iovec {
+            iov_base: backing.as_mut_ptr() as *mut ctypes::c_void,
+            iov_len: backing.len() as u32,
+        };
+        // SAFETY: `iov` points at `backing`, which stays validly borrowed for `'a`, i.e. for as
+        // long as `self` (and the `EoSCustomAllocator` built from the resulting arena) exists.
+        let arena = unsafe { create_mem_arena(&mut iov, max_blocks) };
+        Self {
+            // SAFETY: `arena` was just created above from `backing`, which outlives `self`.
+            allocator: unsafe { EoSCustomAllocator::new(arena) },
+            _backing: PhantomData,
+        }
+    }
+
+    /// The allocator over this arena, for use with `Vec::new_in`/`Box::new_in`/... .
+    pub fn allocator(&self) -> &EoSCustomAllocator {
+        &self.allocator
+    }
+}
+
+impl Drop for ScopedArena<'_> {
+    fn drop(&mut self) {
+        // Tearing down one of these arenas is implicit rather than a separate FFI call:
+        // `CreateMemArena` writes the arena's header into the backing buffer itself instead of
+        // tracking it anywhere external, and there's no corresponding "destroy arena" function in
+        // this game's allocator API. Once `self` drops, releasing the `&'a mut [u8]` borrow it
+        // holds, the arena is simply unreachable scratch bytes again. This impl exists so
+        // dropping a `ScopedArena` reads as the deliberate "free everything at once" step it is,
+        // rather than looking like an oversight.
+    }
+}
+
+/// Function pointer type for a custom allocation-failure handler; see
+/// [`set_alloc_error_handler`]. Like [`alloc_error_handler`] itself, it must never return.
+pub type AllocErrorHandler = fn(Layout) -> !;
+
+/// The handler installed by [`set_alloc_error_handler`], if any. This is safe to access by the
+/// functions in this module, since the NDS is single-threaded and allocation failures can only
+/// ever be handled from the main game loop.
+static mut ALLOC_ERROR_HANDLER: Option<AllocErrorHandler> = None;
+
+/// Installs `handler` to run instead of the default freeze-and-print behavior whenever the
+/// global allocator fails to satisfy an allocation.
+///
+/// This only affects allocations that go through the ordinary infallible paths (`Box::new`,
+/// `vec!`, etc., and anything else that ends up calling [`alloc_error_handler`]); it has no
+/// effect on [`try_alloc`]/[`try_allocate`], which never invoke the handler at all. `handler`
+/// itself must never return, e.g. because it logs and then resets to the title screen, or
+/// otherwise halts the game some other way than the default `WaitForever` freeze.
+pub fn set_alloc_error_handler(handler: AllocErrorHandler) {
+    // SAFETY: single-threaded; see `ALLOC_ERROR_HANDLER`.
+    unsafe {
+        ALLOC_ERROR_HANDLER = Some(handler);
+    }
+}
+
+/// Attempts to allocate `layout` from the default memory arena, returning `Err(layout)` instead
+/// of invoking the installed [`AllocErrorHandler`] if the arena has no room for it, so a caller
+/// that expects to sometimes run out of the game's fixed heap can degrade gracefully instead of
+/// dying.
+///
+/// This is the `GlobalAlloc`-style, raw-pointer counterpart to [`try_allocate`]; prefer
+/// `try_allocate` when carrying the allocation's length around as a slice is more convenient.
+pub fn try_alloc(layout: Layout) -> Result<NonNull<u8>, Layout> {
+    // SAFETY: `raw_alloc` just wraps `ffi::MemAlloc`, same as `EoSDefaultAllocator::alloc`.
+    let ptr = unsafe { alloc_aligned(layout, |size| ffi::MemAlloc(size, 0) as *mut u8) };
+    NonNull::new(ptr).ok_or(layout)
+}
+
+/// Attempts to allocate `layout` from the default memory arena via the [`Allocator`] API,
+/// returning `Err(AllocError)` instead of invoking the installed [`AllocErrorHandler`] if the
+/// arena has no room for it.
+///
+/// Equivalent to `EoSDefaultAllocator.allocate(layout)`, exposed as a free function for
+/// convenience since [`EoSDefaultAllocator`] is a zero-sized marker type.
+pub fn try_allocate(layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+    EoSDefaultAllocator.allocate(layout)
+}
+
+/// Allocation error handler. Runs the handler installed via [`set_alloc_error_handler`], if any;
+/// otherwise freezes the game and outputs an error message.
 #[cfg(not(test))]
 #[alloc_error_handler]
-pub fn alloc_error_handler(_: Layout) -> ! {
+pub fn alloc_error_handler(layout: Layout) -> ! {
+    // SAFETY: single-threaded; see `ALLOC_ERROR_HANDLER`.
+    if let Some(handler) = unsafe { ALLOC_ERROR_HANDLER } {
+        handler(layout)
+    }
     let err = b"[rs] OUT OF MEMORY!\0";
     unsafe {
         ffi::DebugPrint(2, err.as_ptr() as *const ctypes::c_char);
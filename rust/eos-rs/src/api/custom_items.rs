@@ -0,0 +1,214 @@
+//! Declarative custom exclusive items, registered in one [`define_item!`] block instead of by
+//! patching `ApplyExclusiveItemStatBoosts`/`ExclusiveItemEffectFlagTest` and the bag/storage
+//! pickup handlers by hand for each one -- inspired by the single scripted-effect row an item
+//! gets in Hercules' `item_db`.
+//!
+//! A block records an item's stat boosts and the [`ExclusiveItemEffectId`]s it should contribute
+//! to the exclusive-item effect bitvector, plus up to three lifecycle callbacks. The
+//! [`apply_exclusive_item_stat_boosts`], [`build_exclusive_item_effect_flags`],
+//! [`exclusive_item_effect_flag_test`] and [`dispatch_on_pickup`] functions below are the hook
+//! points meant to be called from the corresponding patch glue; each falls back to vanilla
+//! behavior untouched for any [`ItemId`] with no registration.
+
+use crate::api::dungeon_mode::DungeonEntity;
+use crate::api::items::{ExclusiveItemEffectId, ItemId};
+use alloc::vec::Vec;
+
+/// Per-stat boosts a custom item contributes on top of whatever
+/// [`ItemId::apply_exclusive_item_stat_boosts`] already computed for it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StatBoosts {
+    pub atk: i8,
+    pub sp_atk: i8,
+    pub def: i8,
+    pub sp_def: i8,
+}
+
+/// Lifecycle callbacks a custom item can hook. Each is optional; a `None` callback is simply
+/// never called.
+#[derive(Clone, Copy, Default)]
+pub struct ItemHooks {
+    /// Called after the item is added to the bag/storage (see `AddItemToBag`/`AddItemToStorage`).
+    pub on_pickup: Option<fn()>,
+    /// Called whenever [`apply_exclusive_item_stat_boosts`] runs for an equipped instance of this
+    /// item, after its [`StatBoosts`] have been added in.
+    pub on_equip: Option<fn(entity: &mut DungeonEntity)>,
+    /// Called once per turn for each equipped instance of this item.
+    pub on_turn: Option<fn(entity: &mut DungeonEntity)>,
+}
+
+struct CustomExclusiveItem {
+    item: ItemId,
+    boosts: StatBoosts,
+    effects: &'static [ExclusiveItemEffectId],
+    hooks: ItemHooks,
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// exclusive item effects are only ever resolved from the main game loop.
+static mut CUSTOM_EXCLUSIVE_ITEMS: Vec<CustomExclusiveItem> = Vec::new();
+
+/// Registers a custom exclusive item. See [`define_item!`] for the declarative form of this call.
+pub fn register_custom_item(
+    item: ItemId,
+    boosts: StatBoosts,
+    effects: &'static [ExclusiveItemEffectId],
+    hooks: ItemHooks,
+) {
+    // SAFETY: single-threaded; see `CUSTOM_EXCLUSIVE_ITEMS`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        CUSTOM_EXCLUSIVE_ITEMS.push(CustomExclusiveItem {
+            item,
+            boosts,
+            effects,
+            hooks,
+        });
+    }
+}
+
+fn find_custom_item(item: ItemId) -> Option<&'static CustomExclusiveItem> {
+    // SAFETY: single-threaded; see `CUSTOM_EXCLUSIVE_ITEMS`. Entries are only ever appended, never
+    // removed or reordered, so a `'static` borrow from here outlives the rest of the program.
+    #[allow(static_mut_refs)]
+    unsafe {
+        CUSTOM_EXCLUSIVE_ITEMS
+            .iter()
+            .find(|custom| custom.item.id() == item.id())
+    }
+}
+
+/// Applies `item`'s stat boosts to an equipped instance of it: first the vanilla
+/// [`ItemId::apply_exclusive_item_stat_boosts`], then (if `item` was registered with
+/// [`register_custom_item`]/[`define_item!`]) its [`StatBoosts`] on top, followed by its
+/// [`ItemHooks::on_equip`] hook.
+///
+/// Intended to be called from the `ApplyExclusiveItemStatBoosts` patch glue in place of calling
+/// [`ItemId::apply_exclusive_item_stat_boosts`] directly.
+pub fn apply_exclusive_item_stat_boosts(
+    item: ItemId,
+    entity: &mut DungeonEntity,
+    atk: &mut u8,
+    sp_atk: &mut u8,
+    def: &mut u8,
+    sp_def: &mut u8,
+) {
+    item.apply_exclusive_item_stat_boosts(atk, sp_atk, def, sp_def);
+    let Some(custom) = find_custom_item(item) else {
+        return;
+    };
+    *atk = atk.saturating_add_signed(custom.boosts.atk);
+    *sp_atk = sp_atk.saturating_add_signed(custom.boosts.sp_atk);
+    *def = def.saturating_add_signed(custom.boosts.def);
+    *sp_def = sp_def.saturating_add_signed(custom.boosts.sp_def);
+    if let Some(on_equip) = custom.hooks.on_equip {
+        on_equip(entity);
+    }
+}
+
+/// Sets every bit `known_effects` and (if `item` is registered) `item`'s custom effects
+/// contribute to `effect_flags`, routing each one through the existing
+/// [`ExclusiveItemEffectId::set_exclusive_item_effect`] (i.e. `SetExclusiveItemEffect`) so the
+/// bitvector layout is exactly what the game itself expects.
+///
+/// `known_effects` is `item`'s own built-in exclusive-item-effect table entries (however the
+/// caller already determines those); this just adds a custom item's declared effects on top.
+pub fn build_exclusive_item_effect_flags(
+    item: ItemId,
+    known_effects: &[ExclusiveItemEffectId],
+    effect_flags: &mut u32,
+) {
+    for effect in known_effects {
+        effect.set_exclusive_item_effect(effect_flags);
+    }
+    if let Some(custom) = find_custom_item(item) {
+        for effect in custom.effects {
+            effect.set_exclusive_item_effect(effect_flags);
+        }
+    }
+}
+
+/// Tests whether `effect` is active for `item`: the vanilla bitvector test (see
+/// [`ExclusiveItemEffectId::test_exclusive_item_effect_flag`], i.e.
+/// `ExclusiveItemEffectFlagTest`), or'd with whether `item` was registered with `effect` among
+/// its declared effects.
+///
+/// Intended to be called from the `ExclusiveItemEffectFlagTest` patch glue in place of calling
+/// [`ExclusiveItemEffectId::test_exclusive_item_effect_flag`] directly.
+pub fn exclusive_item_effect_flag_test(
+    item: ItemId,
+    effect: ExclusiveItemEffectId,
+    effect_flags: &mut u32,
+) -> bool {
+    if effect.test_exclusive_item_effect_flag(effect_flags) {
+        return true;
+    }
+    find_custom_item(item)
+        .map(|custom| custom.effects.iter().any(|e| e.id() == effect.id()))
+        .unwrap_or(false)
+}
+
+/// Runs `item`'s registered [`ItemHooks::on_pickup`] hook, if any.
+///
+/// Intended to be called from the `AddItemToBag`/`AddItemToStorage` patch glue after the vanilla
+/// call succeeds.
+pub fn dispatch_on_pickup(item: ItemId) {
+    if let Some(on_pickup) = find_custom_item(item).and_then(|custom| custom.hooks.on_pickup) {
+        on_pickup();
+    }
+}
+
+/// Runs `item`'s registered [`ItemHooks::on_turn`] hook, if any.
+///
+/// Intended to be called once per turn for each equipped instance of `item` from the dungeon turn
+/// loop.
+pub fn dispatch_on_turn(item: ItemId, entity: &mut DungeonEntity) {
+    if let Some(on_turn) = find_custom_item(item).and_then(|custom| custom.hooks.on_turn) {
+        on_turn(entity);
+    }
+}
+
+/// Declares a custom exclusive item and registers it with [`register_custom_item`].
+///
+/// ```ignore
+/// define_item!(
+///     ItemId::ITEM_PECHA_SCARF,
+///     boosts: (2, 0, 0, 0),
+///     effects: [ExclusiveItemEffectId::EXCLUSIVE_EFF_STATUS_ATTACKS_POWER_UP],
+///     on_pickup: my_on_pickup,
+///     on_equip: my_on_equip,
+///     on_turn: my_on_turn,
+/// );
+/// ```
+///
+/// All three `on_*` hooks are optional and, if present, must appear in the order shown above.
+#[macro_export]
+macro_rules! define_item {
+    (
+        $item:expr,
+        boosts: ($atk:expr, $sp_atk:expr, $def:expr, $sp_def:expr),
+        effects: [$($effect:expr),* $(,)?]
+        $(, on_pickup: $on_pickup:expr)?
+        $(, on_equip: $on_equip:expr)?
+        $(, on_turn: $on_turn:expr)?
+        $(,)?
+    ) => {
+        $crate::api::custom_items::register_custom_item(
+            $item,
+            $crate::api::custom_items::StatBoosts {
+                atk: $atk,
+                sp_atk: $sp_atk,
+                def: $def,
+                sp_def: $sp_def,
+            },
+            &[$($effect),*],
+            $crate::api::custom_items::ItemHooks {
+                on_pickup: $crate::define_item!(@opt $($on_pickup)?),
+                on_equip: $crate::define_item!(@opt $($on_equip)?),
+                on_turn: $crate::define_item!(@opt $($on_turn)?),
+            },
+        )
+    };
+    (@opt $e:expr) => { ::core::option::Option::Some($e) };
+    (@opt) => { ::core::option::Option::None };
+}
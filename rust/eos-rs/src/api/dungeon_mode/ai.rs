@@ -0,0 +1,631 @@
+//! A composable, pure-Rust layer on top of the game's AI turn-decision functions
+//! ([`DungeonMonsterWrite::choose_ai_move`], [`DungeonMonsterWrite::ai_movement`], etc.), for
+//! mods that want to slot in custom decision logic without reimplementing the whole AI loop.
+
+use crate::api::dungeon_mode::{
+    DungeonEntity, DungeonMonsterMut, DungeonMonsterRead, DungeonMonsterWrite, DungeonTypeMatchup,
+    StatusEffect,
+};
+use crate::api::enums::Direction;
+use crate::api::items::ItemId;
+use crate::api::moves::{Move, MoveExt, MoveTarget, MoveTargetAndRange};
+use crate::api::random::rand_i32;
+use crate::ffi;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// The outcome of a single AI turn-decision step.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum AiDecision {
+    /// Use the move at the given index, in the given direction.
+    UseMove { move_index: u8, direction: Direction },
+    /// Consume the given held emergency item instead of attacking or fleeing. See
+    /// [`crate::api::dungeon_mode::DungeonMonsterRead::should_use_emergency_item`].
+    UseItem(ItemId),
+    /// Walk in the given direction.
+    Walk(Direction),
+    /// Do nothing productive this turn, but don't skip the turn either.
+    Nothing,
+    /// Skip the turn entirely.
+    PassTurn,
+}
+
+/// A single, independently pluggable piece of AI behavior.
+///
+/// Returns `Some(decision)` if this behavior applies and has decided what the monster should
+/// do, or `None` to defer to the next behavior in an [`AiPipeline`].
+pub trait AiBehavior {
+    /// Attempts to produce a decision for `monster`'s turn.
+    fn decide(&self, monster: &mut DungeonMonsterMut) -> Option<AiDecision>;
+}
+
+impl<F> AiBehavior for F
+where
+    F: Fn(&mut DungeonMonsterMut) -> Option<AiDecision>,
+{
+    fn decide(&self, monster: &mut DungeonMonsterMut) -> Option<AiDecision> {
+        self(monster)
+    }
+}
+
+/// An ordered list of [`AiBehavior`]s, tried in turn until one of them produces a decision.
+///
+/// This lets mods layer custom priorities (eg. "heal first, then attack, then wander") on top
+/// of or instead of the game's own move-selection AI, composing small, independently testable
+/// behaviors instead of one large decision function.
+#[derive(Default)]
+pub struct AiPipeline {
+    behaviors: Vec<Box<dyn AiBehavior>>,
+}
+
+impl AiPipeline {
+    /// Creates an empty pipeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a behavior to the end of the pipeline (lowest priority).
+    pub fn then(mut self, behavior: impl AiBehavior + 'static) -> Self {
+        self.behaviors.push(Box::new(behavior));
+        self
+    }
+
+    /// Runs the pipeline against `monster`, returning the first non-`None` decision, or
+    /// [`AiDecision::PassTurn`] if no behavior in the pipeline applies.
+    pub fn decide(&self, monster: &mut DungeonMonsterMut) -> AiDecision {
+        for behavior in &self.behaviors {
+            if let Some(decision) = behavior.decide(monster) {
+                return decision;
+            }
+        }
+        AiDecision::PassTurn
+    }
+
+    /// Runs [`Self::decide`] and immediately applies the result to `monster`'s action fields via
+    /// the appropriate `DungeonMonsterWrite` setter.
+    pub fn decide_and_apply(&self, monster: &mut DungeonMonsterMut) {
+        apply_decision(monster, self.decide(monster));
+    }
+}
+
+/// Applies `decision` to `monster`'s action fields via the matching `DungeonMonsterWrite`
+/// setter. Shared by [`AiPipeline::decide_and_apply`] and [`MonsterTurn`]'s default
+/// `commit_action` phase.
+fn apply_decision(monster: &mut DungeonMonsterMut, decision: AiDecision) {
+    match decision {
+        AiDecision::UseMove {
+            move_index,
+            direction,
+        } => monster.set_action_use_move_ai(move_index, direction),
+        AiDecision::UseItem(_) => monster.set_action_use_item(),
+        AiDecision::Walk(direction) => monster.set_action_regular_attack(direction),
+        AiDecision::Nothing | AiDecision::PassTurn => {
+            let species = monster.monster().apparent_id;
+            monster.set_action_pass_turn_or_walk(species);
+        }
+    }
+}
+
+/// Event hooks into a monster's turn, observing or adjusting individual steps around the
+/// game's own turn-decision and turn-execution functions.
+///
+/// Unlike [`AiBehavior`], which replaces the whole decision for a turn, a `MonsterTurnScript`
+/// sits around specific steps of a turn that's still driven by the native AI (or by an
+/// [`AiPipeline`], if one is plugged in upstream). Each method has a no-op default, so a script
+/// only needs to override the hooks it cares about. Multiple scripts can be layered via
+/// [`MonsterTurnScripts`], each seeing the adjustments made by the ones before it.
+#[allow(unused_variables)]
+pub trait MonsterTurnScript {
+    /// Called once at the very start of a monster's turn, before anything else in
+    /// [`MonsterTurnScripts::run_turn`] happens.
+    fn on_before_turn(&self, monster: &mut DungeonMonsterMut) {}
+
+    /// Adjusts the speed stage computed by [`DungeonMonsterWrite::calc_speed_stage`].
+    ///
+    /// Purely observational for now: turn order is decided by the game before
+    /// [`MonsterTurnScripts::run_turn`] gets a chance to run, so there's no setter yet to feed
+    /// the adjusted value back. Returns `speed_stage` unchanged by default.
+    fn change_speed(&self, monster: &mut DungeonMonsterMut, speed_stage: i32) -> i32 {
+        speed_stage
+    }
+
+    /// Adjusts the priority of the move the AI is about to use, if any.
+    ///
+    /// Also purely observational for now, for the same reason as [`Self::change_speed`].
+    /// Returns `priority` unchanged by default.
+    fn change_priority(&self, monster: &mut DungeonMonsterMut, priority: i8) -> i8 {
+        priority
+    }
+
+    /// Overrides the move index the AI chose via [`DungeonMonsterWrite::choose_ai_move`],
+    /// before [`DungeonMonsterWrite::execute_action`] runs it.
+    ///
+    /// `move_index` is `None` if the AI didn't decide to use a move this turn (eg. it chose to
+    /// walk or pass). Returns `move_index` unchanged by default.
+    fn change_move(&self, monster: &mut DungeonMonsterMut, move_index: Option<u8>) -> Option<u8> {
+        move_index
+    }
+}
+
+/// An ordered list of [`MonsterTurnScript`]s, run together around a monster's turn.
+#[derive(Default)]
+pub struct MonsterTurnScripts {
+    scripts: Vec<Box<dyn MonsterTurnScript>>,
+}
+
+impl MonsterTurnScripts {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a script to the end of the list (run last).
+    pub fn push(&mut self, script: impl MonsterTurnScript + 'static) {
+        self.scripts.push(Box::new(script));
+    }
+
+    /// Runs a monster's full turn, letting every registered script observe or adjust it at the
+    /// appropriate step:
+    ///
+    /// 1. [`MonsterTurnScript::on_before_turn`] for each script, in order.
+    /// 2. [`DungeonMonsterWrite::calc_speed_stage`], folded through [`MonsterTurnScript::change_speed`],
+    ///    and [`MonsterTurnScript::change_priority`] folded starting from `0` (there's no
+    ///    accessor yet for the chosen move's actual priority).
+    /// 3. [`DungeonMonsterWrite::choose_ai_move`], then [`MonsterTurnScript::change_move`] folded
+    ///    starting from `None` (there's no safe accessor yet for reading back which move the
+    ///    native AI picked) and, if a script settles on `Some(move_index)`, re-applied via
+    ///    [`DungeonMonsterWrite::set_action_use_move_ai`].
+    /// 4. [`DungeonMonsterWrite::execute_action`].
+    pub fn run_turn(&self, monster: &mut DungeonMonsterMut, counter_weight: i32) {
+        for script in &self.scripts {
+            script.on_before_turn(monster);
+        }
+
+        let speed_stage = monster.calc_speed_stage(counter_weight);
+        let _speed_stage = self
+            .scripts
+            .iter()
+            .fold(speed_stage, |stage, script| script.change_speed(monster, stage));
+        let _priority = self
+            .scripts
+            .iter()
+            .fold(0i8, |priority, script| script.change_priority(monster, priority));
+
+        monster.choose_ai_move();
+        if let Some(move_index) = self
+            .scripts
+            .iter()
+            .fold(None, |idx, script| script.change_move(monster, idx))
+        {
+            monster.set_action_use_move_ai(move_index, Direction::Current);
+        }
+
+        monster.execute_action();
+    }
+}
+
+/// A safe, managed wrapper around the AI's potential-target list, built up by repeated calls to
+/// [`DungeonMonsterWrite::try_add_target_to_ai_target_list`].
+///
+/// The native function only reports back an updated target count; per its own `// TODO`, this
+/// crate doesn't yet know where the game keeps the rest of the list (the chosen direction for
+/// each target, and at least one other field of unknown purpose) in memory, so there's nothing
+/// to safely read back from there. Instead, this type mirrors that list on the Rust side: each
+/// successful [`Self::add_target`] call records the direction the caller supplied, so
+/// [`Self::targets`] can give a safe, owned view of what's been added so far.
+#[derive(Default)]
+pub struct AiTargetList {
+    directions: Vec<Direction>,
+    /// Placeholder for the second, still-unidentified per-target field the native list keeps.
+    unknown: Vec<i32>,
+}
+
+impl AiTargetList {
+    /// Creates an empty target list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attempts to add `target` to the list, via
+    /// [`DungeonMonsterWrite::try_add_target_to_ai_target_list`] on `monster`, using
+    /// `move_ai_range` and `the_move` to decide eligibility. `direction` is the direction
+    /// `monster` would need to turn to act on `target`, recorded locally if the target is
+    /// actually added. Returns the new total target count reported by the native function.
+    ///
+    /// # Safety
+    /// Same caveats as [`DungeonMonsterWrite::try_add_target_to_ai_target_list`].
+    pub unsafe fn add_target(
+        &mut self,
+        monster: &mut DungeonMonsterMut,
+        move_ai_range: MoveTargetAndRange,
+        target: &DungeonEntity,
+        the_move: &Move,
+        check_all_conditions: bool,
+        direction: Direction,
+    ) -> i32 {
+        let new_count = monster.try_add_target_to_ai_target_list(
+            self.directions.len() as i32,
+            move_ai_range,
+            target,
+            the_move,
+            check_all_conditions,
+        );
+        if new_count as usize > self.directions.len() {
+            self.directions.push(direction);
+            self.unknown.push(0);
+        }
+        new_count
+    }
+
+    /// The number of targets currently in the list.
+    pub fn len(&self) -> usize {
+        self.directions.len()
+    }
+
+    /// Whether the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.directions.is_empty()
+    }
+
+    /// Iterates over `(target_index, turn_direction)` for each target added so far.
+    pub fn targets(&self) -> impl Iterator<Item = (usize, Direction)> + '_ {
+        self.directions.iter().copied().enumerate()
+    }
+}
+
+/// The outcome of a single phase of [`MonsterTurn::run`], deciding whether the turn continues
+/// to its next phase or stops early.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum TurnStatus {
+    /// Proceed to the next phase.
+    Continue,
+    /// Stop the turn here; no further phases run this turn.
+    Stop,
+}
+
+/// A composable orchestrator for a monster's turn, built from four independently overridable
+/// phases: [`Self::gather_context`], [`Self::select_action`], [`Self::commit_action`] and
+/// [`Self::execute`].
+///
+/// Each phase defaults to delegating to the matching native/game behavior; overriding one phase
+/// doesn't require reimplementing the others. Every phase can also stop the turn early by
+/// returning [`TurnStatus::Stop`] (from [`Self::select_action`], alongside its decision).
+#[derive(Default)]
+pub struct MonsterTurn {
+    gather_context: Option<Box<dyn Fn(&mut DungeonMonsterMut) -> TurnStatus>>,
+    select_action: Option<Box<dyn Fn(&mut DungeonMonsterMut) -> (TurnStatus, Option<AiDecision>)>>,
+    commit_action: Option<Box<dyn Fn(&mut DungeonMonsterMut, Option<AiDecision>) -> TurnStatus>>,
+    execute: Option<Box<dyn Fn(&mut DungeonMonsterMut) -> TurnStatus>>,
+}
+
+impl MonsterTurn {
+    /// Creates a turn orchestrator with every phase at its default, native behavior.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the context-gathering phase, which otherwise defaults to
+    /// [`DungeonMonsterWrite::calculate_ai_target_pos`].
+    pub fn gather_context(mut self, phase: impl Fn(&mut DungeonMonsterMut) -> TurnStatus + 'static) -> Self {
+        self.gather_context = Some(Box::new(phase));
+        self
+    }
+
+    /// Overrides the action-selection phase, which otherwise defaults to
+    /// [`DungeonMonsterWrite::choose_ai_move`] (reporting `None`, since there's no accessor yet
+    /// for reading back the decision it made).
+    pub fn select_action(
+        mut self,
+        phase: impl Fn(&mut DungeonMonsterMut) -> (TurnStatus, Option<AiDecision>) + 'static,
+    ) -> Self {
+        self.select_action = Some(Box::new(phase));
+        self
+    }
+
+    /// Overrides the action-commit phase, which otherwise applies the decision (if any) from
+    /// [`Self::select_action`] via [`apply_decision`].
+    pub fn commit_action(
+        mut self,
+        phase: impl Fn(&mut DungeonMonsterMut, Option<AiDecision>) -> TurnStatus + 'static,
+    ) -> Self {
+        self.commit_action = Some(Box::new(phase));
+        self
+    }
+
+    /// Overrides the execution phase, which otherwise defaults to
+    /// [`DungeonMonsterWrite::execute_action`].
+    pub fn execute(mut self, phase: impl Fn(&mut DungeonMonsterMut) -> TurnStatus + 'static) -> Self {
+        self.execute = Some(Box::new(phase));
+        self
+    }
+
+    /// Runs the turn's four phases in order, stopping early if any phase reports
+    /// [`TurnStatus::Stop`].
+    pub fn run(&self, monster: &mut DungeonMonsterMut) {
+        let status = match &self.gather_context {
+            Some(phase) => phase(monster),
+            None => {
+                monster.calculate_ai_target_pos();
+                TurnStatus::Continue
+            }
+        };
+        if status == TurnStatus::Stop {
+            return;
+        }
+
+        let (status, decision) = match &self.select_action {
+            Some(phase) => phase(monster),
+            None => {
+                monster.choose_ai_move();
+                (TurnStatus::Continue, None)
+            }
+        };
+        if status == TurnStatus::Stop {
+            return;
+        }
+
+        let status = match &self.commit_action {
+            Some(phase) => phase(monster, decision),
+            None => {
+                if let Some(decision) = decision {
+                    apply_decision(monster, decision);
+                }
+                TurnStatus::Continue
+            }
+        };
+        if status == TurnStatus::Stop {
+            return;
+        }
+
+        match &self.execute {
+            Some(phase) => {
+                phase(monster);
+            }
+            None => monster.execute_action(),
+        }
+    }
+}
+
+/// A single pluggable adjustment to the weight [`DungeonMonsterWrite::ai_consider_move`]
+/// assigns a candidate move, run via [`MoveWeightModifiers::consider_move`].
+pub trait MoveWeightModifier {
+    /// Given `the_move` under consideration for `monster` (with its native AI weight `weight`,
+    /// as last computed by [`DungeonMonsterWrite::ai_consider_move`] or a previous modifier in
+    /// the list), returns the adjusted weight, and optionally a `can_be_used` override.
+    fn adjust_weight(
+        &self,
+        monster: &mut DungeonMonsterMut,
+        ai_possible_move: &ffi::ai_possible_move,
+        the_move: &Move,
+        weight: i32,
+    ) -> (i32, Option<bool>);
+}
+
+impl<F> MoveWeightModifier for F
+where
+    F: Fn(&mut DungeonMonsterMut, &ffi::ai_possible_move, &Move, i32) -> (i32, Option<bool>),
+{
+    fn adjust_weight(
+        &self,
+        monster: &mut DungeonMonsterMut,
+        ai_possible_move: &ffi::ai_possible_move,
+        the_move: &Move,
+        weight: i32,
+    ) -> (i32, Option<bool>) {
+        self(monster, ai_possible_move, the_move, weight)
+    }
+}
+
+/// An ordered list of [`MoveWeightModifier`]s, applied in turn after
+/// [`DungeonMonsterWrite::ai_consider_move`].
+#[derive(Default)]
+pub struct MoveWeightModifiers {
+    modifiers: Vec<Box<dyn MoveWeightModifier>>,
+}
+
+impl MoveWeightModifiers {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a modifier to the end of the list (applied last, seeing every earlier
+    /// modifier's adjustment).
+    pub fn then(mut self, modifier: impl MoveWeightModifier + 'static) -> Self {
+        self.modifiers.push(Box::new(modifier));
+        self
+    }
+
+    /// Runs [`DungeonMonsterWrite::ai_consider_move`], then folds its result through every
+    /// registered modifier in order. Returns the final weight and `can_be_used` override, if
+    /// any modifier provided one.
+    ///
+    /// This doesn't write the adjusted weight or `can_be_used` override back onto
+    /// `ai_possible_move` itself: this crate doesn't have verified field names for
+    /// [`ffi::ai_possible_move`] yet, so applying the result is left to the caller.
+    pub fn consider_move(
+        &self,
+        monster: &mut DungeonMonsterMut,
+        ai_possible_move: &mut ffi::ai_possible_move,
+        the_move: &Move,
+    ) -> (i32, Option<bool>) {
+        let native_weight = monster.ai_consider_move(ai_possible_move, the_move);
+        self.modifiers.iter().fold(
+            (native_weight, None),
+            |(weight, can_be_used), modifier| {
+                let (weight, override_can_be_used) =
+                    modifier.adjust_weight(monster, ai_possible_move, the_move, weight);
+                (weight, override_can_be_used.or(can_be_used))
+            },
+        )
+    }
+}
+
+/// Whether `a` and `b` are on the same side (both team members, or both not); `false` if either
+/// isn't a monster. A local equivalent of [`crate::api::dungeon_mode::trajectory`]'s private
+/// `is_ally` helper, which isn't visible from this module.
+fn is_same_side(a: &DungeonEntity, b: &DungeonEntity) -> bool {
+    match (a.info_for_monster(), b.info_for_monster()) {
+        (Some(a), Some(b)) => a.0.is_not_team_member == b.0.is_not_team_member,
+        _ => false,
+    }
+}
+
+/// A move a monster could use this turn, as a candidate for [`pick_move`].
+///
+/// This crate has no safe accessor yet for a monster's own moveset or per-slot PP, so both are
+/// supplied by the caller rather than read off `attacker` internally.
+#[derive(Clone, Copy)]
+pub struct MoveCandidate<'a> {
+    pub move_index: u8,
+    pub the_move: &'a Move,
+    /// Remaining PP in this move's slot. Candidates at `0` are skipped by [`pick_move`].
+    pub current_pp: i32,
+    /// The status this move inflicts on a hit, if any. Used to favor targets that don't already
+    /// have a status problem; `None` for moves that only deal damage.
+    pub inflicts_status: Option<StatusEffect>,
+}
+
+impl<'a> MoveCandidate<'a> {
+    /// A plain damaging move candidate, with no secondary status.
+    pub const fn new(move_index: u8, the_move: &'a Move, current_pp: i32) -> Self {
+        Self {
+            move_index,
+            the_move,
+            current_pp,
+            inflicts_status: None,
+        }
+    }
+
+    /// Marks this candidate as inflicting `status` on a hit.
+    pub const fn with_status(mut self, status: StatusEffect) -> Self {
+        self.inflicts_status = Some(status);
+        self
+    }
+}
+
+/// Broad behavioral leaning for [`pick_move`], analogous to Crawl's distinction between monsters
+/// that fight to kill and ones that fight to support their allies.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum AiPolicy {
+    /// Weighs expected damage and type effectiveness heavily.
+    Aggressive,
+    /// Favors status-inflicting moves over raw damage.
+    Support,
+    /// Picks uniformly among every viable (move, target) pairing, ignoring score.
+    Random,
+}
+
+/// A single scored (move, target) pairing, as returned by [`pick_move`].
+#[derive(Clone, Copy, Debug)]
+pub struct ScoredMove<'a> {
+    pub move_index: u8,
+    pub target: &'a DungeonEntity,
+    pub direction: Direction,
+    pub score: i32,
+}
+
+/// Scores every viable pairing of `candidates` against `targets` and returns the best one under
+/// `policy`, so custom monsters can feed the result straight into
+/// [`DungeonEffectsEmitter::do_move_damage`](crate::api::dungeon_mode::DungeonEffectsEmitter::do_move_damage)
+/// and its siblings instead of hardcoding which move to use.
+///
+/// Inspired by Crawl's `handle_behaviour`/`mon-cast` monster decision code. Scoring combines:
+/// - expected damage and type effectiveness, via [`DungeonMonsterRead::predict_damage`] (no crit
+///   assumed, since that's the move's own baseline crit chance rather than a guaranteed hit);
+/// - a flat bonus for status-inflicting candidates against a target that doesn't already have a
+///   status problem, checked via [`DungeonMonsterRead::has_status_that_prevents_acting`] -- this
+///   crate has no per-[`StatusEffect`] query, so this is only a proxy for "already afflicted", not
+///   an exact check against `inflicts_status` specifically;
+/// - a flat penalty for candidates whose [`MoveTarget`] risks catching allies ([`MoveTarget::Party`],
+///   [`MoveTarget::All`], [`MoveTarget::AllExceptUser`], [`MoveTarget::Teammates`]) -- this crate
+///   doesn't track ally positions beyond `targets`, so the penalty is static rather than computed
+///   against where allies actually stand.
+///
+/// Candidates with `current_pp <= 0` are skipped. Entries of `targets` on the same side as
+/// `attacker` are never selected: this function only chooses among hostile targets, so
+/// support moves aimed at allies are out of scope here. `targets` pairs each candidate target
+/// with the direction `attacker` would need to face to hit it, since this crate has no safe
+/// accessor for entity grid positions to derive that itself.
+///
+/// Returns `None` if every candidate is out of PP, type-immune against every hostile target, or
+/// `targets` contains no hostile entries.
+pub fn pick_move<'a>(
+    attacker: &DungeonEntity,
+    candidates: &[MoveCandidate],
+    targets: &[(&'a DungeonEntity, Direction)],
+    policy: AiPolicy,
+) -> Option<ScoredMove<'a>> {
+    let attacker_monster = attacker.info_for_monster()?;
+
+    let mut scored: Vec<ScoredMove> = Vec::new();
+    for candidate in candidates {
+        if candidate.current_pp <= 0 {
+            continue;
+        }
+
+        let hits_allies = matches!(
+            candidate.the_move.get_target_and_range(true).target,
+            Some(MoveTarget::Party)
+                | Some(MoveTarget::All)
+                | Some(MoveTarget::AllExceptUser)
+                | Some(MoveTarget::Teammates)
+        );
+
+        for &(target, direction) in targets {
+            if is_same_side(attacker, target) {
+                continue;
+            }
+
+            let prediction = attacker_monster.predict_damage(target, candidate.the_move, false);
+            if prediction.is_blocked() {
+                continue;
+            }
+
+            let mut score = prediction.damage.unwrap_or(0);
+            score += match prediction.type_matchup {
+                DungeonTypeMatchup::SuperEffective => 50,
+                DungeonTypeMatchup::NotVeryEffective => -50,
+                _ => 0,
+            };
+
+            if candidate.inflicts_status.is_some() {
+                let already_afflicted = target
+                    .info_for_monster()
+                    .is_some_and(|m| m.has_status_that_prevents_acting());
+                if !already_afflicted {
+                    score += 75;
+                }
+            }
+
+            if hits_allies {
+                score -= 40;
+            }
+
+            let score = match policy {
+                AiPolicy::Aggressive => score * 2,
+                AiPolicy::Support if candidate.inflicts_status.is_some() => score * 2,
+                AiPolicy::Support => score / 2,
+                AiPolicy::Random => score,
+            };
+
+            scored.push(ScoredMove {
+                move_index: candidate.move_index,
+                target,
+                direction,
+                score,
+            });
+        }
+    }
+
+    if scored.is_empty() {
+        return None;
+    }
+
+    if policy == AiPolicy::Random {
+        let index = rand_i32(0..scored.len() as i32) as usize;
+        return Some(scored[index]);
+    }
+
+    scored.into_iter().max_by_key(|scored_move| scored_move.score)
+}
@@ -0,0 +1,193 @@
+//! Structured, indexable announcement log: lets mods post a typed, flagged, positioned [`Report`]
+//! and get back a stable index they can later [`AnnouncementLog::attach_combat_report`] to a
+//! monster, instead of only driving the built-in [menus](crate::api::dungeon_mode::menus).
+//!
+//! Vanilla doesn't expose a report/announcement database to patch against, so [`AnnouncementLog`]
+//! is entirely caller-owned bookkeeping, the same way [`crate::api::save_states::SaveStates`] and
+//! [`crate::api::dungeon_mode::cinematics::ScreenOverlay`] are: nothing here reaches into the
+//! game's own state, and a caller decides when/whether to actually render a posted report.
+//! [`AnnouncementLog::write_to_game_log`] is the one exception, since a quiet log write *is* a
+//! real, bound primitive -- the same one [`LogMessageBuilder`] uses when [`LogMessageBuilder::popup`]
+//! isn't called.
+
+use crate::api::dungeon_mode::{DungeonEntity, LogMessageBuilder, WindowColor};
+use crate::api::overlay::{CreatableWithLease, OverlayLoadLease};
+use crate::ffi;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+/// Broad category for a posted [`Report`], left for callers to key their own display/filtering
+/// logic off of (this crate has no opinion on how categories should be presented).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnouncementCategory {
+    Combat,
+    Item,
+    Status,
+    Environment,
+    Narrative,
+}
+
+/// Display flags for a posted [`Report`]; a plain bitset in the same style as
+/// [`crate::api::dungeon_mode::MoveFlags`], since this crate has no `bitflags` dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AnnouncementFlags(u32);
+
+impl AnnouncementFlags {
+    /// Show this report in whatever scrollback/list UI the caller drives off [`AnnouncementLog`].
+    pub const SHOW_IN_LIST: Self = Self(1 << 0);
+    /// Recenter the camera on [`Report::position`] when this report is surfaced.
+    pub const RECENTER_CAMERA: Self = Self(1 << 1);
+    /// Show a popup when this report is surfaced.
+    pub const POPUP: Self = Self(1 << 2);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn show_in_list(self) -> bool {
+        self.contains(Self::SHOW_IN_LIST)
+    }
+
+    pub const fn recenter_camera(self) -> bool {
+        self.contains(Self::RECENTER_CAMERA)
+    }
+
+    pub const fn popup(self) -> bool {
+        self.contains(Self::POPUP)
+    }
+}
+
+impl core::ops::BitOr for AnnouncementFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// A single posted announcement, as stored by [`AnnouncementLog::post`]. Drawing/surfacing one of
+/// these is left entirely to the caller; this crate only assigns it a stable index and stores it.
+#[derive(Clone)]
+pub struct Report {
+    pub category: AnnouncementCategory,
+    pub flags: AnnouncementFlags,
+    pub color: WindowColor,
+    pub brightness: u8,
+    pub position: ffi::position,
+    pub text: String,
+}
+
+/// How many combat-history slots [`AnnouncementLog::attach_combat_report`] tracks per monster.
+pub const COMBAT_HISTORY_SLOTS: usize = 8;
+
+/// A monster's attached combat-history reports, keyed by the [`DungeonEntity`] pointer it was
+/// attached against (the same keying the message log's dedup ring buffer uses for the same
+/// reason: this crate has no stable, accessible monster ID to key on instead).
+struct CombatHistory {
+    entity: *const DungeonEntity,
+    slots: [Option<usize>; COMBAT_HISTORY_SLOTS],
+}
+
+/// A caller-owned log of posted [`Report`]s, plus which ones have been attached to which
+/// monsters' combat history. See the [module-level docs](self) for the overall idea.
+#[derive(Default)]
+pub struct AnnouncementLog {
+    reports: Vec<Report>,
+    combat_histories: Vec<CombatHistory>,
+}
+
+impl AnnouncementLog {
+    /// Creates an empty announcement log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Posts a new announcement, returning the index it was stored at. This only records the
+    /// report; whether (and how) it actually appears on screen is entirely up to the caller, who
+    /// can inspect `flags` (eg. [`AnnouncementFlags::popup`]) to decide.
+    pub fn post(
+        &mut self,
+        category: AnnouncementCategory,
+        flags: AnnouncementFlags,
+        color: WindowColor,
+        brightness: u8,
+        position: ffi::position,
+        text: impl Into<String>,
+    ) -> usize {
+        self.reports.push(Report {
+            category,
+            flags,
+            color,
+            brightness,
+            position,
+            text: text.into(),
+        });
+        self.reports.len() - 1
+    }
+
+    /// Records `text` as a plain line in the game's own message log, without posting a structured
+    /// [`Report`] and without ever triggering a popup -- the same quiet write
+    /// [`LogMessageBuilder::log_str`] does when [`LogMessageBuilder::popup`] isn't called.
+    pub fn write_to_game_log<S: AsRef<str> + Debug>(
+        &self,
+        ov29: &OverlayLoadLease<29>,
+        user: &DungeonEntity,
+        text: S,
+    ) {
+        LogMessageBuilder::_create(ov29.clone()).log_str(user, text);
+    }
+
+    /// Links a previously [`Self::post`]ed report to `entity`'s combat history, at `slot`
+    /// (wrapping around past [`COMBAT_HISTORY_SLOTS`]), creating that history first if `entity`
+    /// has never been attached to before. Returns whether `report_index` actually pointed at a
+    /// stored report.
+    pub fn attach_combat_report(
+        &mut self,
+        entity: &DungeonEntity,
+        slot: usize,
+        report_index: usize,
+    ) -> bool {
+        if report_index >= self.reports.len() {
+            return false;
+        }
+        let entity_ptr = entity as *const DungeonEntity;
+        let history = match self
+            .combat_histories
+            .iter_mut()
+            .find(|history| history.entity == entity_ptr)
+        {
+            Some(history) => history,
+            None => {
+                self.combat_histories.push(CombatHistory {
+                    entity: entity_ptr,
+                    slots: [None; COMBAT_HISTORY_SLOTS],
+                });
+                self.combat_histories.last_mut().unwrap()
+            }
+        };
+        history.slots[slot % COMBAT_HISTORY_SLOTS] = Some(report_index);
+        true
+    }
+
+    /// Looks up a stored report by index (eg. to resolve one found via
+    /// [`Self::combat_report_at`]).
+    pub fn report(&self, report_index: usize) -> Option<&Report> {
+        self.reports.get(report_index)
+    }
+
+    /// Looks up whichever report is attached at `entity`'s combat-history `slot`, if any.
+    pub fn combat_report_at(&self, entity: &DungeonEntity, slot: usize) -> Option<&Report> {
+        let entity_ptr = entity as *const DungeonEntity;
+        let report_index = self
+            .combat_histories
+            .iter()
+            .find(|history| history.entity == entity_ptr)?
+            .slots[slot % COMBAT_HISTORY_SLOTS]?;
+        self.report(report_index)
+    }
+}
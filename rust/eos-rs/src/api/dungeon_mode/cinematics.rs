@@ -0,0 +1,114 @@
+//! Timed letterbox/blackout overlay driven by the game clock, for framing cutscene-style moments
+//! without hand-rolling sprite draws per caller.
+//!
+//! This crate has no accessor for the engine's own frame counter or clock-scaling ratios (no
+//! `GetFrameCounter`-equivalent is bound anywhere yet), so every [`ScreenOverlay`] method takes
+//! `current_frame_counter`/`game_clock_ratio`/`process_clock_ratio` explicitly, the same way
+//! [`crate::api::dungeon_mode::DungeonEffectsEmitter::hit_probability`] takes stages it has no
+//! accessor for. Likewise there's no bound drawing primitive for the bars/fade themselves, so
+//! [`ScreenOverlay::draw_screen_overlay`] leaves the actual draw call to caller-supplied closures
+//! and only owns the timing.
+
+use crate::api::overlay::OverlayLoadLease;
+
+/// Tracks a letterbox-bars effect and a full-screen blackout fade, each as a target frame-counter
+/// deadline rather than a countdown, so they keep tracking the game's own clock scaling
+/// (slowdown, pause) instead of drifting if frames get skipped or doubled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScreenOverlay {
+    letterbox_deadline: Option<u32>,
+    blackout_deadline: Option<u32>,
+}
+
+impl ScreenOverlay {
+    /// Creates a [`ScreenOverlay`] with neither effect active.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Computes a frame-counter deadline `duration_seconds` from `current_frame_counter`, scaled
+    /// by the game's own clock ratios: `target = current_frame_counter + duration_seconds *
+    /// game_clock_ratio / process_clock_ratio`, so the deadline tracks the game's clock scaling
+    /// (and survives slowdown/pause) instead of counting raw frames. Exposed as its own function
+    /// since both [`Self::set_letterbox`] and [`Self::set_blackout`] need it.
+    pub fn target_frame(
+        current_frame_counter: u32,
+        duration_seconds: f32,
+        game_clock_ratio: f32,
+        process_clock_ratio: f32,
+    ) -> u32 {
+        let frames = duration_seconds * game_clock_ratio / process_clock_ratio;
+        current_frame_counter.saturating_add(frames.max(0.0).round() as u32)
+    }
+
+    /// Starts (or restarts) the letterbox bars, to stay up until [`Self::target_frame`] of
+    /// `duration_seconds` from `current_frame_counter`. Unlike [`Self::set_blackout`], this always
+    /// applies the new deadline outright, even if it's earlier than one already in progress.
+    pub fn set_letterbox(
+        &mut self,
+        _ov29: &OverlayLoadLease<29>,
+        current_frame_counter: u32,
+        duration_seconds: f32,
+        game_clock_ratio: f32,
+        process_clock_ratio: f32,
+    ) {
+        self.letterbox_deadline = Some(Self::target_frame(
+            current_frame_counter,
+            duration_seconds,
+            game_clock_ratio,
+            process_clock_ratio,
+        ));
+    }
+
+    /// Starts (or extends) the full-screen blackout fade, to stay up until [`Self::target_frame`]
+    /// of `duration_seconds` from `current_frame_counter` -- but only ever extends an in-progress
+    /// fade, never shortens one: if a later deadline is already pending, this call's earlier one
+    /// is ignored.
+    pub fn set_blackout(
+        &mut self,
+        _ov29: &OverlayLoadLease<29>,
+        current_frame_counter: u32,
+        duration_seconds: f32,
+        game_clock_ratio: f32,
+        process_clock_ratio: f32,
+    ) {
+        let target = Self::target_frame(
+            current_frame_counter,
+            duration_seconds,
+            game_clock_ratio,
+            process_clock_ratio,
+        );
+        self.blackout_deadline = Some(match self.blackout_deadline {
+            Some(existing) => existing.max(target),
+            None => target,
+        });
+    }
+
+    /// Called once per frame: runs `draw_letterbox`/`draw_blackout` for whichever effect is still
+    /// active as of `current_frame_counter`, and clears any effect whose deadline has already
+    /// passed so it stops being drawn on subsequent frames.
+    pub fn draw_screen_overlay(
+        &mut self,
+        _ov29: &OverlayLoadLease<29>,
+        current_frame_counter: u32,
+        mut draw_letterbox: impl FnMut(),
+        mut draw_blackout: impl FnMut(),
+    ) {
+        if Self::tick(&mut self.letterbox_deadline, current_frame_counter) {
+            draw_letterbox();
+        }
+        if Self::tick(&mut self.blackout_deadline, current_frame_counter) {
+            draw_blackout();
+        }
+    }
+
+    /// Whether `deadline` is still active as of `current_frame_counter`; clears it to `None` if
+    /// it has passed.
+    fn tick(deadline: &mut Option<u32>, current_frame_counter: u32) -> bool {
+        let active = deadline.is_some_and(|d| current_frame_counter < d);
+        if !active {
+            *deadline = None;
+        }
+        active
+    }
+}
@@ -0,0 +1,167 @@
+//! Lingering tile hazards ("clouds"), Dungeon Crawl's model of an area effect that keeps
+//! re-applying itself to anything standing in it instead of firing once and being done.
+//!
+//! The base game's gas/smog moves ([`DungeonEffectsEmitter::do_move_poison_gas`],
+//! [`DungeonEffectsEmitter::do_move_smelling_salt`], the 40%-poison rider on Smog et al. via
+//! [`DungeonEffectsEmitter::do_move_damage_poison_40`]) only ever apply an instantaneous status;
+//! [`DungeonCloud`] gives patch authors a way to leave a persistent hazard behind instead, via
+//! [`DungeonEffectsEmitter::do_move_gas_with_cloud`].
+
+use crate::api::dungeon_mode::{
+    DungeonEffectsEmitter, DungeonEntity, DungeonMonsterWrite, EntityTableWrite, GlobalDungeonData,
+    InflictOptions, StatusCondition, TerrainType,
+};
+use crate::api::random::rand_i32;
+use crate::ffi;
+use alloc::vec::Vec;
+
+/// The effect a [`DungeonCloud`] instance re-applies to every entity standing in it, each
+/// [`DungeonCloud::tick_clouds`] call, scaled by that instance's `power` (see
+/// [`DungeonCloud::spawn_cloud`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloudKind {
+    /// Directly removes `power` HP from every covered entity, via
+    /// [`DungeonMonsterWrite::deal_damage_direct`]. Used for a Smog/Poison Gas-style lingering
+    /// poison cloud.
+    Poison,
+    /// Has a `power` (clamped `0..=100`) percent chance per tick of paralyzing every covered
+    /// entity, via [`DungeonEffectsEmitter::try_inflict_status`].
+    Paralysis,
+    /// Lowers the hit-chance stat `stat_idx` by `power` stages on every covered entity, via
+    /// [`DungeonEffectsEmitter::lower_hit_chance_stat`]. `stat_idx` has no named constant in this
+    /// crate (see that method's own doc comment), so it's supplied here the same way every other
+    /// caller of it has to.
+    AccuracyReduction { stat_idx: i32 },
+    /// Like [`Self::Poison`], but semantically a fire hazard (e.g. a lingering flame left by a
+    /// move like Will-O-Wisp) rather than a poison one -- same direct-damage delivery, kept as
+    /// its own variant so a patch can tell the two apart when deciding what to animate.
+    Fire,
+}
+
+/// A single active cloud instance, as created by [`DungeonCloud::spawn_cloud`].
+struct CloudInstance {
+    tiles: Vec<ffi::position>,
+    kind: CloudKind,
+    power: i32,
+    lifetime: u16,
+}
+
+/// A per-floor registry of active [`CloudInstance`]s. Create one alongside the rest of a floor's
+/// transient state (it holds no FFI resource of its own, so it doesn't need to be recreated when
+/// entering/leaving overlay 29), [`DungeonCloud::spawn_cloud`] a hazard from a move effect, and
+/// call [`DungeonCloud::tick_clouds`] once per dungeon turn to apply and age them.
+#[derive(Default)]
+pub struct DungeonCloud {
+    clouds: Vec<CloudInstance>,
+}
+
+impl DungeonCloud {
+    /// Creates an empty registry (no active clouds).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawns a cloud of `kind` centered on `origin`, covering every passable tile within
+    /// Chebyshev distance `radius` except `origin` itself (the same footprint as
+    /// [`DungeonEffectsEmitter::deal_damage_area`]'s `AreaShape::Burst`), that re-applies its
+    /// effect at `power` for `lifetime` subsequent [`Self::tick_clouds`] calls.
+    pub fn spawn_cloud(&mut self, origin: ffi::position, radius: i32, kind: CloudKind, power: i32, lifetime: u16) {
+        self.clouds.push(CloudInstance {
+            tiles: tiles_in_radius(origin, radius),
+            kind,
+            power,
+            lifetime,
+        });
+    }
+
+    /// Applies every active cloud's effect to every monster currently standing on one of its
+    /// tiles, then ages every cloud by one tick, removing any whose lifetime has reached zero.
+    /// Intended to be called once per dungeon turn.
+    pub fn tick_clouds(&mut self, emitter: &mut DungeonEffectsEmitter, dungeon: &mut GlobalDungeonData) {
+        for cloud in &self.clouds {
+            for entity in dungeon
+                .inner_mut()
+                .get_entities_mut()
+                .get_active_monsters_mut()
+            {
+                let on_cloud = entity.get_tile().is_some_and(|tile| {
+                    cloud.tiles.iter().any(|pos| {
+                        // SAFETY: GetTileSafe bounds-checks the coordinates itself.
+                        let expected = unsafe { &*ffi::GetTileSafe(pos.x as i32, pos.y as i32) };
+                        core::ptr::eq(tile, expected)
+                    })
+                });
+                if on_cloud {
+                    apply_cloud_tick(emitter, entity, cloud.kind, cloud.power);
+                }
+            }
+        }
+
+        self.clouds.retain_mut(|cloud| {
+            cloud.lifetime = cloud.lifetime.saturating_sub(1);
+            cloud.lifetime > 0
+        });
+    }
+}
+
+/// Applies one tick of `kind` (at `power`) to `entity`, dispatching to whichever already-wrapped
+/// primitive on [`DungeonEffectsEmitter`]/[`DungeonMonsterWrite`] matches -- the same
+/// dispatch-by-variant shape [`crate::api::dungeon_mode::MoveSecondaryEffect`] uses.
+fn apply_cloud_tick(emitter: &mut DungeonEffectsEmitter, entity: &mut DungeonEntity, kind: CloudKind, power: i32) {
+    match kind {
+        CloudKind::Poison | CloudKind::Fire => {
+            if let Some(mut monster) = entity.info_for_monster_mut() {
+                monster.deal_damage_direct(power);
+            }
+        }
+        CloudKind::Paralysis => {
+            if rand_i32(0..100) < power.clamp(0, 100) {
+                // The cloud itself has no attacker entity to credit, so (matching
+                // `do_move_damage_with_effects`'s `Heal` case) the covered entity is passed as
+                // both the attacker and defender of its own status infliction.
+                let entity_ptr = entity as *mut DungeonEntity;
+                unsafe {
+                    emitter.try_inflict_status(
+                        &mut *entity_ptr,
+                        &mut *entity_ptr,
+                        StatusCondition::Paralysis,
+                        InflictOptions::default(),
+                    );
+                }
+            }
+        }
+        CloudKind::AccuracyReduction { stat_idx } => {
+            let entity_ptr = entity as *mut DungeonEntity;
+            unsafe {
+                emitter.lower_hit_chance_stat(&mut *entity_ptr, &mut *entity_ptr, stat_idx, power);
+            }
+        }
+    }
+}
+
+/// Every passable tile within Chebyshev distance `radius` of `origin`, excluding `origin` itself,
+/// mirroring
+/// [`DungeonEffectsEmitter::deal_damage_area`]'s private `AreaShape::Burst` tile walk (not
+/// reusable directly from here, since it's private to that module).
+fn tiles_in_radius(origin: ffi::position, radius: i32) -> Vec<ffi::position> {
+    let mut tiles = Vec::new();
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            let distance = dx.abs().max(dy.abs());
+            if distance == 0 || distance > radius {
+                continue;
+            }
+            let (x, y) = (origin.x as i32 + dx, origin.y as i32 + dy);
+            // SAFETY: GetTileSafe bounds-checks the coordinates itself.
+            let tile = unsafe { &*ffi::GetTileSafe(x, y) };
+            if tile.get_terrain() == Some(TerrainType::Wall) {
+                continue;
+            }
+            tiles.push(ffi::position {
+                x: x as i16,
+                y: y as i16,
+            });
+        }
+    }
+    tiles
+}
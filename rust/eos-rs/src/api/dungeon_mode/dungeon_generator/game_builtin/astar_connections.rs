@@ -0,0 +1,275 @@
+//! Weighted-A* corridor carving, as an alternative to
+//! [`DungeonGridMutator::create_grid_cell_connections`]'s fixed straight-hallway-with-kinks logic.
+//!
+//! [`DungeonGridMutator::carve_connections_astar`] routes each grid cell connection (as flagged by
+//! [`DungeonGridMutator::assign_grid_cell_connections`]) over the tile grid with A*, biasing the
+//! path to reuse already-open terrain and nudging it off dead-straight lines with a small random
+//! perturbation per tile, instead of the fixed kinked line [`Self::create_grid_cell_connections`]
+//! always draws. This is the same kind of reuse-existing-terrain bias
+//! [`super::connectivity`]'s repair corridors get from [`Self::carve_corridor`], just run proactively
+//! here instead of only when a floor turns out disconnected.
+
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::api::dungeon_mode::dungeon_generator::DungeonGridCell;
+use crate::api::dungeon_mode::{DungeonTileExt, RegionTag, TerrainType};
+use crate::api::random::rand_i32;
+use crate::ffi;
+
+use super::{DungeonGridMutator, FLOOR_HEIGHT, FLOOR_WIDTH};
+
+/// One of the 4 cardinal directions a grid cell can connect to a neighbor in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn opposite(self) -> Self {
+        match self {
+            Self::Top => Self::Bottom,
+            Self::Bottom => Self::Top,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// Whether `(x, y)` is strictly inside the floor's impassable outer border.
+fn in_bounds(x: i32, y: i32) -> bool {
+    x > 0 && y > 0 && x < FLOOR_WIDTH - 1 && y < FLOOR_HEIGHT - 1
+}
+
+/// An open-set entry ordered by ascending `f`, so [`BinaryHeap`] (a max-heap) pops the lowest cost
+/// first.
+struct OpenEntry {
+    f: f32,
+    pos: (i32, i32),
+}
+
+impl PartialEq for OpenEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.f == other.f
+    }
+}
+impl Eq for OpenEntry {}
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for OpenEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f.partial_cmp(&self.f).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// The cost of entering `(x, y)`: close to free for already-open terrain (scaled by `reuse_bias`,
+/// so paths prefer reusing existing corridors and rooms), `1.0` for wall/obstacle tiles, plus a
+/// small uniform random perturbation (magnitude `jitter`) to discourage long dead-straight runs.
+fn tile_cost(x: i32, y: i32, reuse_bias: f32, jitter: f32) -> f32 {
+    let is_wall = unsafe { &*ffi::GetTileSafe(x, y) }.get_terrain() == Some(TerrainType::Wall);
+    let base = if is_wall { 1.0 } else { 0.1 * reuse_bias };
+    base + jitter * (rand_i32(0..1000) as f32 / 1000.0)
+}
+
+/// Finds the lowest-cost path from `start` to `goal` via A*, with [`tile_cost`] as the per-tile
+/// cost and Manhattan distance to `goal` as the heuristic. Returns the path including both
+/// endpoints, or `None` if `goal` is unreachable from `start`.
+fn astar(start: (i32, i32), goal: (i32, i32), reuse_bias: f32, jitter: f32) -> Option<Vec<(i32, i32)>> {
+    let heuristic = |(x, y): (i32, i32)| ((x - goal.0).abs() + (y - goal.1).abs()) as f32;
+
+    let mut open = BinaryHeap::new();
+    open.push(OpenEntry { f: heuristic(start), pos: start });
+
+    let mut came_from = BTreeMap::new();
+    let mut g_score = BTreeMap::new();
+    g_score.insert(start, 0.0f32);
+
+    while let Some(OpenEntry { pos, .. }) = open.pop() {
+        if pos == goal {
+            let mut path = alloc::vec![pos];
+            let mut current = pos;
+            while let Some(&prev) = came_from.get(&current) {
+                path.push(prev);
+                current = prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let current_g = g_score[&pos];
+        for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+            let next = (pos.0 + dx, pos.1 + dy);
+            if next != goal && !in_bounds(next.0, next.1) {
+                continue;
+            }
+
+            let tentative_g = current_g + tile_cost(next.0, next.1, reuse_bias, jitter);
+            if tentative_g < *g_score.get(&next).unwrap_or(&f32::INFINITY) {
+                came_from.insert(next, pos);
+                g_score.insert(next, tentative_g);
+                open.push(OpenEntry { f: tentative_g + heuristic(next), pos: next });
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the exact hallway anchor tile within `cell`'s tile bounds (the one tile
+/// [`DungeonGridMutator::create_rooms_and_anchors`] marks with the hallway anchor room index), or
+/// the cell's center tile if none is found.
+fn anchor_tile(cell: &DungeonGridCell) -> (i32, i32) {
+    for y in cell.start_y..cell.end_y {
+        for x in cell.start_x..cell.end_x {
+            let tile = unsafe { &*ffi::GetTileSafe(x, y) };
+            if RegionTag::from_room_value(tile.room) == RegionTag::HallwayAnchor {
+                return (x, y);
+            }
+        }
+    }
+    ((cell.start_x + cell.end_x) / 2, (cell.start_y + cell.end_y) / 2)
+}
+
+/// Returns `cell`'s connection endpoint facing a neighbor in the given `direction`: the exact
+/// anchor tile for a hallway anchor cell, or a random point on the interior edge facing that
+/// neighbor for a room cell (matching the endpoint rules
+/// [`DungeonGridMutator::create_grid_cell_connections`] itself uses).
+fn connection_endpoint(cell: &DungeonGridCell, direction: Direction) -> (i32, i32) {
+    if !cell.is_room {
+        return anchor_tile(cell);
+    }
+
+    let (x1, y1, x2, y2) = (cell.start_x + 1, cell.start_y + 1, cell.end_x - 2, cell.end_y - 2);
+    let (x1, x2) = (x1.min(x2), x1.max(x2));
+    let (y1, y2) = (y1.min(y2), y1.max(y2));
+
+    match direction {
+        Direction::Top => (rand_i32(x1..=x2), y1),
+        Direction::Bottom => (rand_i32(x1..=x2), y2),
+        Direction::Left => (x1, rand_i32(y1..=y2)),
+        Direction::Right => (x2, rand_i32(y1..=y2)),
+    }
+}
+
+impl DungeonGridMutator {
+    /// Carves every grid cell connection flagged by [`Self::assign_grid_cell_connections`] as an
+    /// A*-routed corridor, instead of [`Self::create_grid_cell_connections`]'s straight
+    /// hallway-with-kinks.
+    ///
+    /// For each connected grid cell pair, the endpoints are picked the same way
+    /// [`Self::create_grid_cell_connections`] picks them (the exact anchor tile for a hallway
+    /// anchor cell, a random point on the room edge facing the neighbor for a room cell), but the
+    /// path between them is found with A* over the tile grid: entering an already-open tile costs
+    /// much less than entering a wall (scaled by `reuse_bias`, so corridors prefer merging into
+    /// existing rooms/hallways over cutting fresh ones), and a small random perturbation
+    /// (magnitude `jitter`) is added per tile to discourage long straight runs. The heuristic is
+    /// Manhattan distance to the endpoint.
+    ///
+    /// Every tile along a carved path that was a wall beforehand becomes open hallway terrain
+    /// (room index `0xFF`); tiles the path merely passes through (already open, e.g. an existing
+    /// room) keep their original room index, same as [`Self::carve_corridor`](super::connectivity)
+    /// does for repair corridors. Both cells of a carved connection have their
+    /// `is_connected`/`is_connected_to_*` flags set, matching what
+    /// [`Self::create_grid_cell_connections`] itself sets.
+    ///
+    /// `starts_x`/`starts_y` are validated the same way
+    /// [`Self::create_grid_cell_connections`]'s are, even though this implementation reads tile
+    /// bounds from each cell directly rather than through them.
+    ///
+    /// # Safety
+    /// The caller needs to make sure that overlay 29 is loaded and it's safe to manipulate the
+    /// global dungeon tile data.
+    pub unsafe fn carve_connections_astar(
+        &mut self,
+        starts_x: &[i32],
+        starts_y: &[i32],
+        reuse_bias: f32,
+        jitter: f32,
+    ) {
+        Self::assert_start_positions_valid(starts_x, starts_y);
+
+        let mut carved_edges = BTreeSet::new();
+        for grid_y in 0..self.height() {
+            for grid_x in 0..self.width() {
+                let (should_top, should_bottom, should_left, should_right) = {
+                    let cell = self.get(grid_x, grid_y);
+                    (
+                        cell.should_connect_to_top,
+                        cell.should_connect_to_bottom,
+                        cell.should_connect_to_left,
+                        cell.should_connect_to_right,
+                    )
+                };
+                let candidates = [
+                    (should_top, Direction::Top, grid_y.checked_sub(1).map(|y| (grid_x, y))),
+                    (should_bottom, Direction::Bottom, Some(grid_y + 1).filter(|&y| y < self.height()).map(|y| (grid_x, y))),
+                    (should_left, Direction::Left, grid_x.checked_sub(1).map(|x| (x, grid_y))),
+                    (should_right, Direction::Right, Some(grid_x + 1).filter(|&x| x < self.width()).map(|x| (x, grid_y))),
+                ];
+
+                for (should_connect, direction, neighbor) in candidates {
+                    if !should_connect {
+                        continue;
+                    }
+                    let Some(neighbor) = neighbor else { continue };
+
+                    let edge = ((grid_x, grid_y).min(neighbor), (grid_x, grid_y).max(neighbor));
+                    if !carved_edges.insert(edge) {
+                        continue;
+                    }
+
+                    self.carve_one_connection((grid_x, grid_y), direction, neighbor, reuse_bias, jitter);
+                }
+            }
+        }
+    }
+
+    /// Carves a single A*-routed corridor between the cell at `from` and its neighbor at `to`
+    /// (reached by leaving `from` in `direction`), and marks both cells connected.
+    fn carve_one_connection(
+        &mut self,
+        from: (usize, usize),
+        direction: Direction,
+        to: (usize, usize),
+        reuse_bias: f32,
+        jitter: f32,
+    ) {
+        let from_point = connection_endpoint(self.get(from.0, from.1), direction);
+        let to_point = connection_endpoint(self.get(to.0, to.1), direction.opposite());
+
+        if let Some(path) = astar(from_point, to_point, reuse_bias, jitter) {
+            for (x, y) in path {
+                // SAFETY: the caller of `carve_connections_astar` guarantees it's safe to
+                // manipulate the global tile data.
+                let tile = unsafe { &mut *ffi::GetTileSafe(x, y) };
+                if tile.get_terrain() == Some(TerrainType::Wall) {
+                    tile.set_terrain(TerrainType::Normal);
+                    tile.room = 0xFF;
+                }
+            }
+        }
+
+        self.mark_connected(from.0, from.1, direction);
+        self.mark_connected(to.0, to.1, direction.opposite());
+    }
+
+    /// Sets `is_connected` and the `is_connected_to_<direction>` flag on the cell at `(grid_x,
+    /// grid_y)`.
+    fn mark_connected(&mut self, grid_x: usize, grid_y: usize, direction: Direction) {
+        let cell = self.get_mut(grid_x, grid_y);
+        cell.is_connected = true;
+        match direction {
+            Direction::Top => cell.is_connected_to_top = true,
+            Direction::Bottom => cell.is_connected_to_bottom = true,
+            Direction::Left => cell.is_connected_to_left = true,
+            Direction::Right => cell.is_connected_to_right = true,
+        }
+    }
+}
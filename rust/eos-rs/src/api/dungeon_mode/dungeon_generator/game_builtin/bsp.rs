@@ -0,0 +1,157 @@
+//! A pure-Rust alternative to [`DungeonGridMutator::assign_rooms`], for mods that want control
+//! over room distribution the vanilla RNG can't produce.
+//!
+//! [`DungeonGridMutator::assign_rooms_bsp`] recursively splits the grid via binary space
+//! partitioning instead of delegating to the game's `AssignRooms`; the resulting [`BspNode`] tree
+//! is returned so later passes (e.g. a custom connection pass) can favor connecting sibling
+//! regions, which were adjacent before being split. The existing FFI anchor/connection passes
+//! (see [`DungeonGridMutator::create_rooms_and_anchors`]) still run afterwards exactly as they
+//! would after [`DungeonGridMutator::assign_rooms`] -- this only decides which cells become rooms.
+
+use alloc::boxed::Box;
+use rand_core::RngCore;
+
+use super::DungeonGridMutator;
+
+/// A node in the BSP partition tree built by [`DungeonGridMutator::assign_rooms_bsp`].
+#[derive(Debug, Clone)]
+pub enum BspNode {
+    /// A region that was split in two. `first` and `second` are the two halves in split order,
+    /// so a connection pass can favor connecting them directly -- they were one contiguous
+    /// region before the split.
+    Split {
+        first: Box<BspNode>,
+        second: Box<BspNode>,
+    },
+    /// A region that became exactly one room cell, at grid coordinates `(grid_x, grid_y)`.
+    Leaf { grid_x: usize, grid_y: usize },
+}
+
+#[derive(Clone, Copy)]
+struct BspRegion {
+    x0: usize,
+    y0: usize,
+    x1: usize,
+    y1: usize,
+}
+
+impl BspRegion {
+    fn width(&self) -> usize {
+        self.x1 - self.x0
+    }
+
+    fn height(&self) -> usize {
+        self.y1 - self.y0
+    }
+}
+
+impl DungeonGridMutator {
+    /// Computes which grid cells become rooms via binary space partitioning, instead of
+    /// delegating to the game's `AssignRooms`.
+    ///
+    /// Starting from the full `width x height` grid as the root region, each region is
+    /// recursively split in half, either horizontally or vertically (picking the longer axis, or
+    /// a random axis when the region is square), at a random line position constrained so
+    /// both halves are at least `min_leaf` cells wide. A region stops splitting once it's smaller
+    /// than `2 * min_leaf` on both axes, or `max_depth` has been reached. Each leaf region
+    /// becomes one room cell: a random cell inside it is marked `is_room`.
+    ///
+    /// Returns the partition tree, so later passes (e.g. a custom connection pass) can favor
+    /// connecting sibling regions together, since they were adjacent before being split.
+    ///
+    /// This only marks cells `is_room`; run [`Self::create_rooms_and_anchors`] afterwards as
+    /// usual to actually carve the rooms and hallway anchors into the tile data.
+    ///
+    /// # Panics
+    /// Panics if `min_leaf` is `0`.
+    pub fn assign_rooms_bsp(
+        &mut self,
+        min_leaf: usize,
+        max_depth: u32,
+        rng: &mut impl RngCore,
+    ) -> BspNode {
+        assert!(min_leaf >= 1, "min_leaf must be at least 1");
+        let root = BspRegion {
+            x0: 0,
+            y0: 0,
+            x1: self.width(),
+            y1: self.height(),
+        };
+        let tree = bsp_split(root, min_leaf, max_depth, rng);
+        self.apply_bsp_leaves(&tree);
+        tree
+    }
+
+    fn apply_bsp_leaves(&mut self, node: &BspNode) {
+        match node {
+            BspNode::Leaf { grid_x, grid_y } => {
+                self.get_mut(*grid_x, *grid_y).is_room = true;
+            }
+            BspNode::Split { first, second } => {
+                self.apply_bsp_leaves(first);
+                self.apply_bsp_leaves(second);
+            }
+        }
+    }
+}
+
+fn bsp_split(
+    region: BspRegion,
+    min_leaf: usize,
+    depth_remaining: u32,
+    rng: &mut impl RngCore,
+) -> BspNode {
+    let can_split_horizontally = region.width() >= 2 * min_leaf;
+    let can_split_vertically = region.height() >= 2 * min_leaf;
+
+    if depth_remaining == 0 || (!can_split_horizontally && !can_split_vertically) {
+        let grid_x = region.x0 + rand_below(rng, region.width());
+        let grid_y = region.y0 + rand_below(rng, region.height());
+        return BspNode::Leaf { grid_x, grid_y };
+    }
+
+    let split_horizontally = if can_split_horizontally && can_split_vertically {
+        match region.width().cmp(&region.height()) {
+            core::cmp::Ordering::Equal => rng.next_u32() % 2 == 0,
+            ordering => ordering == core::cmp::Ordering::Greater,
+        }
+    } else {
+        can_split_horizontally
+    };
+
+    let (first_region, second_region) = if split_horizontally {
+        let split_at = region.x0 + min_leaf + rand_below(rng, region.width() - 2 * min_leaf + 1);
+        (
+            BspRegion {
+                x1: split_at,
+                ..region
+            },
+            BspRegion {
+                x0: split_at,
+                ..region
+            },
+        )
+    } else {
+        let split_at = region.y0 + min_leaf + rand_below(rng, region.height() - 2 * min_leaf + 1);
+        (
+            BspRegion {
+                y1: split_at,
+                ..region
+            },
+            BspRegion {
+                y0: split_at,
+                ..region
+            },
+        )
+    };
+
+    BspNode::Split {
+        first: Box::new(bsp_split(first_region, min_leaf, depth_remaining - 1, rng)),
+        second: Box::new(bsp_split(second_region, min_leaf, depth_remaining - 1, rng)),
+    }
+}
+
+/// Returns a uniformly random value in `0..bound`. `bound` must be nonzero.
+fn rand_below(rng: &mut impl RngCore, bound: usize) -> usize {
+    (rng.next_u32() as usize) % bound
+}
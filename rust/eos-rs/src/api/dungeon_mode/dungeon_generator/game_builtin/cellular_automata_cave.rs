@@ -0,0 +1,272 @@
+//! Organic cavern layouts via cellular automata smoothing, as another alternative to
+//! [`super::BuiltinDungeonLayoutGenerators`] and [`super::FractalCaveGenerator`] for floors that
+//! shouldn't be made of the game's usual grid-cell rooms.
+//!
+//! Unlike [`super::FractalCaveGenerator`], this seeds its own small deterministic PRNG from a
+//! caller-provided seed rather than drawing from the game's global RNG state, so the same seed
+//! always produces the same floor; this makes it well suited to automated testing.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::api::dungeon_mode::dungeon_generator::{DungeonEntityGeneration, DungeonFloorGeneration};
+use crate::api::dungeon_mode::{DungeonTileExt, GlobalDungeonData, TerrainType};
+use crate::api::overlay::OverlayLoadLease;
+use crate::ffi;
+
+use super::{GlobalDungeonEntityGenerator, FLOOR_HEIGHT, FLOOR_WIDTH};
+
+/// The fraction of the usable floor area that must survive as a single connected region for
+/// [`CellularAutomataCaveGenerator`] to keep its generated cave; below this, the floor is too
+/// cramped to be worth playing and the generator falls back to a one-room monster house instead.
+const MIN_OPEN_TILE_FRACTION: f32 = 0.2;
+
+/// Parameters for [`CellularAutomataCaveGenerator`]'s smoothing pass.
+#[derive(Clone, Copy, Debug)]
+pub struct CellularAutomataCaveParams {
+    /// The probability, in `0.0..=1.0`, that a tile starts out as a wall before smoothing.
+    pub wall_fill: f32,
+    /// The number of smoothing passes to run. Each pass turns a tile into a wall if at least 5 of
+    /// its 8 Moore neighbors are walls (tiles outside the floor count as walls), and into open
+    /// floor otherwise.
+    pub iterations: u32,
+    /// Seed for the generator's own deterministic PRNG, independent of the game's global RNG
+    /// state, so the same seed always reproduces the same floor.
+    pub rng_seed: u32,
+}
+
+/// Generates a single organic cavern via cellular automata smoothing: an initial field of
+/// randomly-filled walls is repeatedly smoothed until it settles into cave-like blobs, then only
+/// the largest connected open region is kept.
+///
+/// To create an instance, use
+/// [`crate::api::dungeon_mode::GlobalDungeonData::get_cellular_automata_cave_generator`].
+pub struct CellularAutomataCaveGenerator<'a>(
+    pub(crate) OverlayLoadLease<29>,
+    pub(crate) &'a mut GlobalDungeonData<'a>,
+);
+
+impl<'a> CellularAutomataCaveGenerator<'a> {
+    /// Fills the usable floor area with walls with probability `wall_fill`, using `rng`.
+    fn seed_walls(rng: &mut Xorshift32, wall_fill: f32) -> Vec<bool> {
+        let mut field = vec![false; (FLOOR_WIDTH * FLOOR_HEIGHT) as usize];
+        for y in 1..FLOOR_HEIGHT - 1 {
+            for x in 1..FLOOR_WIDTH - 1 {
+                field[(y * FLOOR_WIDTH + x) as usize] = rng.next_unit() < wall_fill;
+            }
+        }
+        field
+    }
+
+    /// Counts how many of `(x, y)`'s 8 Moore neighbors are walls in `field`. Neighbors outside
+    /// the usable floor area (including the permanent border) count as walls.
+    fn wall_neighbor_count(field: &[bool], x: i32, y: i32) -> u32 {
+        let mut count = 0;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let (nx, ny) = (x + dx, y + dy);
+                let is_wall = if nx <= 0 || ny <= 0 || nx >= FLOOR_WIDTH - 1 || ny >= FLOOR_HEIGHT - 1
+                {
+                    true
+                } else {
+                    field[(ny * FLOOR_WIDTH + nx) as usize]
+                };
+                if is_wall {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Runs one smoothing pass over `field`, returning the new field.
+    fn smooth(field: &[bool]) -> Vec<bool> {
+        let mut next = field.to_vec();
+        for y in 1..FLOOR_HEIGHT - 1 {
+            for x in 1..FLOOR_WIDTH - 1 {
+                let walls = Self::wall_neighbor_count(field, x, y);
+                next[(y * FLOOR_WIDTH + x) as usize] = walls >= 5;
+            }
+        }
+        next
+    }
+
+    /// Writes `field` into the real floor tiles.
+    fn stamp(&mut self, field: &[bool]) {
+        for y in 1..FLOOR_HEIGHT - 1 {
+            for x in 1..FLOOR_WIDTH - 1 {
+                let tile = self.1.get_tile_mut(x, y);
+                if field[(y * FLOOR_WIDTH + x) as usize] {
+                    tile.set_terrain_obstacle_checked(false, 0);
+                } else {
+                    tile.init();
+                }
+            }
+        }
+    }
+
+    /// Flood-fills from `(start_x, start_y)` over all 4-orthogonally-connected non-wall tiles.
+    fn flood_fill_open(&self, start_x: i32, start_y: i32) -> Vec<(i32, i32)> {
+        if self.1.get_tile(start_x, start_y).get_terrain() == Some(TerrainType::Wall) {
+            return Vec::new();
+        }
+
+        let mut visited = vec![false; (FLOOR_WIDTH * FLOOR_HEIGHT) as usize];
+        let visited_idx = |x: i32, y: i32| (y * FLOOR_WIDTH + x) as usize;
+        let mut stack = vec![(start_x, start_y)];
+        let mut reached = Vec::new();
+        visited[visited_idx(start_x, start_y)] = true;
+
+        while let Some((x, y)) = stack.pop() {
+            reached.push((x, y));
+            for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx <= 0 || ny <= 0 || nx >= FLOOR_WIDTH - 1 || ny >= FLOOR_HEIGHT - 1 {
+                    continue;
+                }
+                let vi = visited_idx(nx, ny);
+                if !visited[vi] && self.1.get_tile(nx, ny).get_terrain() != Some(TerrainType::Wall) {
+                    visited[vi] = true;
+                    stack.push((nx, ny));
+                }
+            }
+        }
+
+        reached
+    }
+
+    /// Keeps only the largest 4-connected open region on the floor, filling every other open
+    /// tile with wall. Returns the size of the region that was kept.
+    fn keep_largest_open_region(&mut self) -> usize {
+        let mut visited = vec![false; (FLOOR_WIDTH * FLOOR_HEIGHT) as usize];
+        let visited_idx = |x: i32, y: i32| (y * FLOOR_WIDTH + x) as usize;
+        let mut largest: Vec<(i32, i32)> = Vec::new();
+
+        for y in 1..FLOOR_HEIGHT - 1 {
+            for x in 1..FLOOR_WIDTH - 1 {
+                if visited[visited_idx(x, y)]
+                    || self.1.get_tile(x, y).get_terrain() == Some(TerrainType::Wall)
+                {
+                    continue;
+                }
+                let region = self.flood_fill_open(x, y);
+                for &(rx, ry) in &region {
+                    visited[visited_idx(rx, ry)] = true;
+                }
+                if region.len() > largest.len() {
+                    largest = region;
+                }
+            }
+        }
+
+        let mut keep = vec![false; (FLOOR_WIDTH * FLOOR_HEIGHT) as usize];
+        for &(x, y) in &largest {
+            keep[visited_idx(x, y)] = true;
+        }
+        for y in 1..FLOOR_HEIGHT - 1 {
+            for x in 1..FLOOR_WIDTH - 1 {
+                if !keep[visited_idx(x, y)]
+                    && self.1.get_tile(x, y).get_terrain() != Some(TerrainType::Wall)
+                {
+                    self.1.get_tile_mut(x, y).set_terrain_obstacle_checked(false, 0);
+                }
+            }
+        }
+
+        largest.len()
+    }
+}
+
+/// A from-scratch floor generator; an alternative to
+/// [`super::GlobalDungeonStructureGenerator`] and [`super::FractalCaveGenerator`] for layouts
+/// they have no way to express.
+impl<'a> DungeonFloorGeneration for CellularAutomataCaveGenerator<'a> {
+    type EntityGenerator = GlobalDungeonEntityGenerator;
+    type LayoutGenerator = CellularAutomataCaveParams;
+
+    /// Resets the floor, then generates a cave with the given parameters.
+    fn generate_floor(
+        &mut self,
+        _width: usize,
+        _height: usize,
+        properties: &ffi::floor_properties,
+    ) -> &mut Self {
+        let mut params = CellularAutomataCaveParams {
+            wall_fill: 0.45,
+            iterations: 4,
+            rng_seed: 0,
+        };
+        self.generate_layout(&mut params, properties);
+        self
+    }
+
+    /// `width`/`height` on `properties` are ignored: the cave always fills the usable floor area.
+    ///
+    /// If the largest connected open region left after smoothing is too small to be worth
+    /// playing, falls back to generating a one-room monster house instead.
+    fn generate_layout(
+        &mut self,
+        layout: &mut Self::LayoutGenerator,
+        properties: &ffi::floor_properties,
+    ) -> &mut Self {
+        // SAFETY: We have a mutable reference to the dungeon.
+        unsafe { ffi::ResetFloor() };
+
+        let mut rng = Xorshift32::new(layout.rng_seed);
+        let mut field = Self::seed_walls(&mut rng, layout.wall_fill);
+        for _ in 0..layout.iterations {
+            field = Self::smooth(&field);
+        }
+        self.stamp(&field);
+        let kept = self.keep_largest_open_region();
+
+        let usable_tiles = ((FLOOR_WIDTH - 2) * (FLOOR_HEIGHT - 2)) as f32;
+        if (kept as f32) < usable_tiles * MIN_OPEN_TILE_FRACTION {
+            // SAFETY: We have a mutable reference to the dungeon.
+            unsafe { ffi::ResetFloor() };
+            unsafe { ffi::GenerateOneRoomMonsterHouseFloor() };
+            let _ = properties;
+        }
+
+        self
+    }
+
+    fn entities<F: FnOnce(&mut Self::EntityGenerator)>(&mut self, cb: F) -> &mut Self {
+        // SAFETY: We have a lease on the overlay and a mutable borrow on the global dungeon.
+        let mut ent = unsafe { GlobalDungeonEntityGenerator::new() };
+        cb(&mut ent);
+        self
+    }
+
+    /// This does nothing, this implementation will always update the global struct directly.
+    fn generate(self, _: &mut GlobalDungeonData) {}
+}
+
+/// A small, dependency-free xorshift32 PRNG, used purely so
+/// [`CellularAutomataCaveGenerator`] can be seeded deterministically and independently of the
+/// game's own RNG state.
+struct Xorshift32(u32);
+
+impl Xorshift32 {
+    fn new(seed: u32) -> Self {
+        // xorshift32 is undefined for a zero state, so nudge it to a fixed nonzero value.
+        Self(if seed == 0 { 0x9E3779B9 } else { seed })
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.0 = x;
+        x
+    }
+
+    /// Draws a pseudorandom value uniformly in `0.0..=1.0`.
+    fn next_unit(&mut self) -> f32 {
+        (self.next_u32() as f64 / u32::MAX as f64) as f32
+    }
+}
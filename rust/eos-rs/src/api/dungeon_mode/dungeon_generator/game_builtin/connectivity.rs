@@ -0,0 +1,166 @@
+//! Active connectivity repair, modeled on Hengband's tunnel generator.
+//!
+//! [`GlobalDungeonStructureGenerator::stairs_are_always_reachable`] can only report or flag
+//! unreachable tiles; the only recourse today when a custom layout produces disconnected regions
+//! is to scrap the whole floor and try again.
+//! [`GlobalDungeonStructureGenerator::connect_disconnected_regions`] instead carves corridors
+//! until nothing is left disconnected.
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::api::dungeon_mode::{DungeonTileExt, TerrainType};
+
+use super::{GlobalDungeonStructureGenerator, FLOOR_HEIGHT, FLOOR_WIDTH};
+
+/// Whether `(x, y)` is strictly inside the floor's impassable outer border.
+fn in_bounds(x: i32, y: i32) -> bool {
+    x > 0 && y > 0 && x < FLOOR_WIDTH - 1 && y < FLOOR_HEIGHT - 1
+}
+
+fn squared_distance(a: (i32, i32), b: (i32, i32)) -> i32 {
+    let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+    dx * dx + dy * dy
+}
+
+/// The tiles a [`GlobalDungeonStructureGenerator::create_hallway`] call between `from` and `to`
+/// (with the given `is_vertical`/`middle_x`/`middle_y`) would pass through.
+fn corridor_tiles(
+    from: (i32, i32),
+    to: (i32, i32),
+    is_vertical: bool,
+    middle_x: i32,
+    middle_y: i32,
+) -> Vec<(i32, i32)> {
+    let mut tiles = Vec::new();
+    if is_vertical {
+        let (y_lo, y_hi) = (from.1.min(middle_y), from.1.max(middle_y));
+        tiles.extend((y_lo..=y_hi).map(|y| (from.0, y)));
+        let (x_lo, x_hi) = (from.0.min(to.0), from.0.max(to.0));
+        tiles.extend((x_lo..=x_hi).map(|x| (x, middle_y)));
+        let (y_lo, y_hi) = (middle_y.min(to.1), middle_y.max(to.1));
+        tiles.extend((y_lo..=y_hi).map(|y| (to.0, y)));
+    } else {
+        let (x_lo, x_hi) = (from.0.min(middle_x), from.0.max(middle_x));
+        tiles.extend((x_lo..=x_hi).map(|x| (x, from.1)));
+        let (y_lo, y_hi) = (from.1.min(to.1), from.1.max(to.1));
+        tiles.extend((y_lo..=y_hi).map(|y| (middle_x, y)));
+        let (x_lo, x_hi) = (middle_x.min(to.0), middle_x.max(to.0));
+        tiles.extend((x_lo..=x_hi).map(|x| (x, to.1)));
+    }
+    tiles
+}
+
+impl<'a> GlobalDungeonStructureGenerator<'a> {
+    /// Floods outward from `(start_x, start_y)` over open (non-wall) terrain, returning every
+    /// tile reached.
+    fn flood_fill_open(&self, start_x: i32, start_y: i32) -> BTreeSet<(i32, i32)> {
+        let mut reached = BTreeSet::new();
+        if self.1.get_tile(start_x, start_y).get_terrain() == Some(TerrainType::Wall) {
+            return reached;
+        }
+
+        let mut stack = alloc::vec![(start_x, start_y)];
+        reached.insert((start_x, start_y));
+        while let Some((x, y)) = stack.pop() {
+            for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+                let (nx, ny) = (x + dx, y + dy);
+                if !in_bounds(nx, ny) || reached.contains(&(nx, ny)) {
+                    continue;
+                }
+                if self.1.get_tile(nx, ny).get_terrain() != Some(TerrainType::Wall) {
+                    reached.insert((nx, ny));
+                    stack.push((nx, ny));
+                }
+            }
+        }
+        reached
+    }
+
+    /// Finds the closest (by straight-line distance) pair of an already-`reached` open tile and
+    /// an open tile that isn't, returning `(reached_tile, unreached_tile)`.
+    ///
+    /// Returns `None` if every open tile on the floor is already reached.
+    fn nearest_unreached_pair(
+        &self,
+        reached: &BTreeSet<(i32, i32)>,
+    ) -> Option<((i32, i32), (i32, i32))> {
+        let mut unreached = Vec::new();
+        for y in 1..FLOOR_HEIGHT - 1 {
+            for x in 1..FLOOR_WIDTH - 1 {
+                if !reached.contains(&(x, y))
+                    && self.1.get_tile(x, y).get_terrain() != Some(TerrainType::Wall)
+                {
+                    unreached.push((x, y));
+                }
+            }
+        }
+
+        let mut best: Option<((i32, i32), (i32, i32), i32)> = None;
+        for &u in &unreached {
+            for &r in reached {
+                let d = squared_distance(u, r);
+                if best.map_or(true, |(_, _, best_d)| d < best_d) {
+                    best = Some((r, u, d));
+                }
+            }
+        }
+        best.map(|(r, u, _)| (r, u))
+    }
+
+    /// Carves an L-shaped corridor between `from` and `to` with
+    /// [`Self::create_hallway`](GlobalDungeonStructureGenerator::create_hallway), picking the
+    /// bend coordinate and orientation (`is_vertical`) from their relative offset: whichever axis
+    /// has the larger gap becomes the pair of straight segments, bent at the midpoint of that
+    /// same axis and joined by a segment along the other one.
+    ///
+    /// Every tile that was a wall before the carve and is open afterwards is marked as a hallway
+    /// tile (room index `0xFF`), so [`Self::finalize_junctions`](GlobalDungeonStructureGenerator::finalize_junctions)
+    /// can still find and flag its junctions later. Tiles the corridor merely passes through
+    /// (already open before the carve, e.g. an existing room) keep their original room index.
+    fn carve_corridor(&mut self, from: (i32, i32), to: (i32, i32)) {
+        let is_vertical = (to.1 - from.1).abs() >= (to.0 - from.0).abs();
+        let middle_x = (from.0 + to.0) / 2;
+        let middle_y = (from.1 + to.1) / 2;
+
+        let path = corridor_tiles(from, to, is_vertical, middle_x, middle_y);
+        let newly_opened: Vec<(i32, i32)> = path
+            .into_iter()
+            .filter(|&(x, y)| {
+                in_bounds(x, y) && self.1.get_tile(x, y).get_terrain() == Some(TerrainType::Wall)
+            })
+            .collect();
+
+        self.create_hallway(from.0, from.1, to.0, to.1, is_vertical, middle_x, middle_y);
+
+        for (x, y) in newly_opened {
+            let tile = self.1.get_tile_mut(x, y);
+            if tile.get_terrain() != Some(TerrainType::Wall) {
+                tile.room = 0xFF;
+            }
+        }
+    }
+
+    /// Actively repairs a disconnected floor by tunneling corridors, instead of merely detecting
+    /// the problem like [`Self::stairs_are_always_reachable`](GlobalDungeonStructureGenerator::stairs_are_always_reachable) does.
+    ///
+    /// Repeatedly floods outward from `(x_stairs, y_stairs)` over open terrain to find the tiles
+    /// that aren't reachable yet, carves a corridor from the nearest reached tile to the nearest
+    /// unreached one (see [`Self::carve_corridor`]), and re-floods. This continues until a flood
+    /// fill from the stairs reaches every open tile on the floor.
+    ///
+    /// The carve never crosses or converts the floor's impassable outer border.
+    pub fn connect_disconnected_regions(&mut self, x_stairs: i32, y_stairs: i32) {
+        // Each carve joins at least one previously-unreached tile into the reached component, so
+        // there can never be more carves than there are tiles on the floor; this bounds the loop
+        // even if a carve somehow fails to make progress.
+        let max_carves = (FLOOR_WIDTH * FLOOR_HEIGHT) as usize;
+        for _ in 0..max_carves {
+            let reached = self.flood_fill_open(x_stairs, y_stairs);
+            let Some((from, to)) = self.nearest_unreached_pair(&reached) else {
+                break;
+            };
+            self.carve_corridor(from, to);
+        }
+    }
+}
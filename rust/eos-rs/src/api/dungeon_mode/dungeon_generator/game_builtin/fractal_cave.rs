@@ -0,0 +1,367 @@
+//! Organic cavern layouts via the diamond-square (midpoint displacement) algorithm, as an
+//! alternative to [`super::BuiltinDungeonLayoutGenerators`] for floors that shouldn't be made of
+//! the game's usual grid-cell rooms.
+//!
+//! Unlike the builtin layouts, this has no concept of rooms, hallways, or grid cells: it
+//! thresholds a height field directly into open floor or wall tiles, via the same per-tile
+//! primitives [`crate::api::dungeon_mode::DungeonTileExt`] exposes elsewhere, then keeps only the
+//! largest connected open region.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use rand_core::RngCore;
+
+use crate::api::dungeon_mode::dungeon_generator::{DungeonEntityGeneration, DungeonFloorGeneration};
+use crate::api::dungeon_mode::{DungeonRng, DungeonTileExt, GlobalDungeonData, TerrainType};
+use crate::api::overlay::{CreatableWithLease, OverlayLoadLease};
+use crate::ffi;
+
+use super::{GlobalDungeonEntityGenerator, FLOOR_HEIGHT, FLOOR_WIDTH};
+
+/// Parameters for [`FractalCaveGenerator`]'s diamond-square height field.
+#[derive(Clone, Copy, Debug)]
+pub struct FractalCaveParams {
+    /// Threshold on the height field, after it is normalized to `0.0..=1.0`: tiles below it
+    /// become open floor rather than wall. Higher values produce more open caverns; lower values
+    /// produce tighter, winding tunnels.
+    pub density: f32,
+    /// How strongly the random displacement at each step of the algorithm scales with the
+    /// current step size. Higher values produce rougher, more jagged cave walls.
+    pub roughness: f32,
+    /// If `true`, once the height field is carved, every disconnected pocket of open space is
+    /// connected to the largest one with an L-shaped corridor (see
+    /// [`crate::api::dungeon_mode::GlobalDungeonData::carve_corridor`]) via
+    /// [`FractalCaveGenerator::connect_open_regions`], instead of being discarded by
+    /// [`FractalCaveGenerator::keep_largest_open_region`]. This keeps every carved tile
+    /// reachable, closer to Hengband's fractal cave rooms, at the cost of a few straight
+    /// corridors cutting through the cave's organic shape.
+    ///
+    /// As a safety net (this crate has no grid cells to run `EnsureConnectedGrid` against for a
+    /// cave with no rooms), connectivity is double-checked afterwards with the same
+    /// `StairsAlwaysReachable` pass [`super::GlobalDungeonStructureGenerator::stairs_are_always_reachable`]
+    /// wraps, using an arbitrary open tile in place of the stairs; if it still finds unreachable
+    /// tiles, this falls back to [`FractalCaveGenerator::keep_largest_open_region`] after all.
+    pub connect_disconnected_regions: bool,
+}
+
+/// Generates a single organic cavern via the diamond-square (midpoint displacement) algorithm,
+/// as used for Hengband's fractal-cave rooms.
+///
+/// To create an instance, use
+/// [`crate::api::dungeon_mode::GlobalDungeonData::get_fractal_cave_generator`].
+pub struct FractalCaveGenerator<'a>(
+    pub(crate) OverlayLoadLease<29>,
+    pub(crate) &'a mut GlobalDungeonData<'a>,
+);
+
+impl<'a> FractalCaveGenerator<'a> {
+    /// Builds a height field covering the largest `2^n + 1` square that fits the usable floor
+    /// area, seeds its four corners, then repeatedly halves the step, averaging corners
+    /// ("diamond" step) or edge midpoints ("square" step) plus a random displacement scaled by
+    /// the current step and `roughness`.
+    fn build_height_field(side: usize, roughness: f32, rng: &mut impl RngCore) -> Vec<f32> {
+        let mut field = vec![0.0f32; side * side];
+        let idx = |x: usize, y: usize| y * side + x;
+
+        field[idx(0, 0)] = rand_unit(rng);
+        field[idx(side - 1, 0)] = rand_unit(rng);
+        field[idx(0, side - 1)] = rand_unit(rng);
+        field[idx(side - 1, side - 1)] = rand_unit(rng);
+
+        let mut step = side - 1;
+        let mut scale = roughness;
+        while step > 1 {
+            let half = step / 2;
+
+            // Diamond step: fill in the center of each square from its four corners.
+            let mut y = half;
+            while y < side {
+                let mut x = half;
+                while x < side {
+                    let avg = (field[idx(x - half, y - half)]
+                        + field[idx(x + half, y - half)]
+                        + field[idx(x - half, y + half)]
+                        + field[idx(x + half, y + half)])
+                        / 4.0;
+                    field[idx(x, y)] = avg + rand_unit(rng) * scale;
+                    x += step;
+                }
+                y += step;
+            }
+
+            // Square step: fill in the midpoint of each edge from its (up to four) neighbors.
+            let mut y = 0;
+            while y < side {
+                let row_offset = if y % step == 0 { half } else { 0 };
+                let mut x = row_offset;
+                while x < side {
+                    let neighbors: [(isize, isize); 4] = [
+                        (x as isize - half as isize, y as isize),
+                        (x as isize + half as isize, y as isize),
+                        (x as isize, y as isize - half as isize),
+                        (x as isize, y as isize + half as isize),
+                    ];
+                    let mut sum = 0.0;
+                    let mut count = 0u32;
+                    for (nx, ny) in neighbors {
+                        if nx >= 0 && ny >= 0 && (nx as usize) < side && (ny as usize) < side {
+                            sum += field[idx(nx as usize, ny as usize)];
+                            count += 1;
+                        }
+                    }
+                    field[idx(x, y)] = sum / count as f32 + rand_unit(rng) * scale;
+                    x += step;
+                }
+                y += half;
+            }
+
+            step = half;
+            scale *= 0.5;
+        }
+
+        field
+    }
+
+    /// Writes `field` into the real floor tiles, offset by one to leave the outer border intact,
+    /// thresholding each value (after min-max normalization) against `density`.
+    fn stamp_thresholded(&mut self, field: &[f32], side: usize, density: f32) {
+        let idx = |x: usize, y: usize| y * side + x;
+        let min = field.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = field.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let range = (max - min).max(f32::EPSILON);
+
+        for y in 0..side {
+            for x in 0..side {
+                let normalized = (field[idx(x, y)] - min) / range;
+                let tile = self.1.get_tile_mut(x as i32 + 1, y as i32 + 1);
+                if normalized < density {
+                    tile.init();
+                } else {
+                    tile.set_terrain_obstacle_checked(false, 0);
+                }
+            }
+        }
+    }
+
+    /// Flood-fills from `(start_x, start_y)` over all 4-orthogonally-connected non-wall tiles.
+    fn flood_fill_open(&self, start_x: i32, start_y: i32) -> Vec<(i32, i32)> {
+        if self.1.get_tile(start_x, start_y).get_terrain() == Some(TerrainType::Wall) {
+            return Vec::new();
+        }
+
+        let mut visited = vec![false; (FLOOR_WIDTH * FLOOR_HEIGHT) as usize];
+        let visited_idx = |x: i32, y: i32| (y * FLOOR_WIDTH + x) as usize;
+        let mut stack = vec![(start_x, start_y)];
+        let mut reached = Vec::new();
+        visited[visited_idx(start_x, start_y)] = true;
+
+        while let Some((x, y)) = stack.pop() {
+            reached.push((x, y));
+            for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx <= 0 || ny <= 0 || nx >= FLOOR_WIDTH - 1 || ny >= FLOOR_HEIGHT - 1 {
+                    continue;
+                }
+                let vi = visited_idx(nx, ny);
+                if !visited[vi] && self.1.get_tile(nx, ny).get_terrain() != Some(TerrainType::Wall) {
+                    visited[vi] = true;
+                    stack.push((nx, ny));
+                }
+            }
+        }
+
+        reached
+    }
+
+    /// Keeps only the largest 4-connected open region on the floor, filling every other open
+    /// tile with wall. The thresholded height field often leaves several disconnected pockets of
+    /// open space; this ensures the floor is always a single, fully traversable cavern.
+    fn keep_largest_open_region(&mut self) {
+        let mut visited = vec![false; (FLOOR_WIDTH * FLOOR_HEIGHT) as usize];
+        let visited_idx = |x: i32, y: i32| (y * FLOOR_WIDTH + x) as usize;
+        let mut largest: Vec<(i32, i32)> = Vec::new();
+
+        for y in 1..FLOOR_HEIGHT - 1 {
+            for x in 1..FLOOR_WIDTH - 1 {
+                if visited[visited_idx(x, y)]
+                    || self.1.get_tile(x, y).get_terrain() == Some(TerrainType::Wall)
+                {
+                    continue;
+                }
+                let region = self.flood_fill_open(x, y);
+                for &(rx, ry) in &region {
+                    visited[visited_idx(rx, ry)] = true;
+                }
+                if region.len() > largest.len() {
+                    largest = region;
+                }
+            }
+        }
+
+        let mut keep = vec![false; (FLOOR_WIDTH * FLOOR_HEIGHT) as usize];
+        for &(x, y) in &largest {
+            keep[visited_idx(x, y)] = true;
+        }
+        for y in 1..FLOOR_HEIGHT - 1 {
+            for x in 1..FLOOR_WIDTH - 1 {
+                if !keep[visited_idx(x, y)]
+                    && self.1.get_tile(x, y).get_terrain() != Some(TerrainType::Wall)
+                {
+                    self.1.get_tile_mut(x, y).set_terrain_obstacle_checked(false, 0);
+                }
+            }
+        }
+    }
+    /// Finds every disconnected 4-connected open region on the floor, via repeated flood fills
+    /// starting from every not-yet-visited open tile.
+    fn find_open_regions(&self) -> Vec<Vec<(i32, i32)>> {
+        let mut visited = vec![false; (FLOOR_WIDTH * FLOOR_HEIGHT) as usize];
+        let visited_idx = |x: i32, y: i32| (y * FLOOR_WIDTH + x) as usize;
+        let mut regions = Vec::new();
+
+        for y in 1..FLOOR_HEIGHT - 1 {
+            for x in 1..FLOOR_WIDTH - 1 {
+                if visited[visited_idx(x, y)]
+                    || self.1.get_tile(x, y).get_terrain() == Some(TerrainType::Wall)
+                {
+                    continue;
+                }
+                let region = self.flood_fill_open(x, y);
+                for &(rx, ry) in &region {
+                    visited[visited_idx(rx, ry)] = true;
+                }
+                regions.push(region);
+            }
+        }
+
+        regions
+    }
+
+    /// Connects every disconnected open region to the largest one, carving an L-shaped corridor
+    /// (via [`crate::api::dungeon_mode::GlobalDungeonData::carve_corridor`], with no drunkenness)
+    /// from a representative tile of each smaller region to one of the largest. Returns the
+    /// number of regions found before connecting them (`1` means the cave was already a single
+    /// connected pocket).
+    fn connect_open_regions(&mut self) -> usize {
+        let mut regions = self.find_open_regions();
+        regions.sort_by_key(|region| core::cmp::Reverse(region.len()));
+
+        let region_count = regions.len();
+        if let Some((largest, rest)) = regions.split_first() {
+            if let Some(&anchor) = largest.first() {
+                for region in rest {
+                    if let Some(&(rx, ry)) = region.first() {
+                        let path = self.1.carve_corridor(rx, ry, anchor.0, anchor.1, 0);
+                        // `carve_corridor` leaves each tile where the path enters wall as a
+                        // junction-flagged wall; open those so the corridor is walkable.
+                        for (x, y) in path {
+                            let tile = self.1.get_tile_mut(x, y);
+                            if tile.get_terrain() == Some(TerrainType::Wall) {
+                                tile.set_terrain(TerrainType::Normal);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        region_count
+    }
+
+    /// Returns the position of an arbitrary open (non-wall) tile, or `None` if the floor has no
+    /// open tiles at all.
+    fn any_open_tile(&self) -> Option<(i32, i32)> {
+        for y in 1..FLOOR_HEIGHT - 1 {
+            for x in 1..FLOOR_WIDTH - 1 {
+                if self.1.get_tile(x, y).get_terrain() != Some(TerrainType::Wall) {
+                    return Some((x, y));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A from-scratch floor generator; an alternative to
+/// [`super::GlobalDungeonStructureGenerator`] for layouts it has no way to express.
+impl<'a> DungeonFloorGeneration for FractalCaveGenerator<'a> {
+    type EntityGenerator = GlobalDungeonEntityGenerator;
+    type LayoutGenerator = FractalCaveParams;
+
+    /// Resets the floor, then generates a cave with the given parameters.
+    fn generate_floor(
+        &mut self,
+        _width: usize,
+        _height: usize,
+        properties: &ffi::floor_properties,
+    ) -> &mut Self {
+        let mut params = FractalCaveParams {
+            density: 0.55,
+            roughness: 1.0,
+            connect_disconnected_regions: false,
+        };
+        self.generate_layout(&mut params, properties);
+        self
+    }
+
+    /// `width`/`height` on `properties` are ignored: the cave always fills the largest
+    /// `2^n + 1` square that fits the usable floor area.
+    fn generate_layout(
+        &mut self,
+        layout: &mut Self::LayoutGenerator,
+        _properties: &ffi::floor_properties,
+    ) -> &mut Self {
+        // SAFETY: We have a mutable reference to the dungeon.
+        unsafe { ffi::ResetFloor() };
+
+        let side = largest_diamond_square_side((FLOOR_WIDTH - 2).min(FLOOR_HEIGHT - 2) as usize);
+        if side >= 3 {
+            let mut rng = DungeonRng::new(self.0.clone());
+            let field = Self::build_height_field(side, layout.roughness, &mut rng);
+            self.stamp_thresholded(&field, side, layout.density);
+
+            if layout.connect_disconnected_regions {
+                self.connect_open_regions();
+                let all_reachable = match self.any_open_tile() {
+                    Some((x, y)) => {
+                        // SAFETY: We have a mutable reference to the dungeon.
+                        unsafe { ffi::StairsAlwaysReachable(x, y, false as ffi::bool_) > 0 }
+                    }
+                    None => true,
+                };
+                if !all_reachable {
+                    self.keep_largest_open_region();
+                }
+            } else {
+                self.keep_largest_open_region();
+            }
+        }
+        self
+    }
+
+    fn entities<F: FnOnce(&mut Self::EntityGenerator)>(&mut self, cb: F) -> &mut Self {
+        // SAFETY: We have a lease on the overlay and a mutable borrow on the global dungeon.
+        let mut ent = unsafe { GlobalDungeonEntityGenerator::new() };
+        cb(&mut ent);
+        self
+    }
+
+    /// This does nothing, this implementation will always update the global struct directly.
+    fn generate(self, _: &mut GlobalDungeonData) {}
+}
+
+/// The largest `2^n + 1` value that is `<= limit`, or `0` if even `3` (`n = 1`) doesn't fit.
+fn largest_diamond_square_side(limit: usize) -> usize {
+    if limit < 3 {
+        return 0;
+    }
+    let mut side = 3;
+    while side * 2 - 1 <= limit {
+        side = side * 2 - 1;
+    }
+    side
+}
+
+/// Draws a pseudorandom value uniformly in `-1.0..=1.0`.
+fn rand_unit(rng: &mut impl RngCore) -> f32 {
+    (rng.next_u32() as f64 / u32::MAX as f64 * 2.0 - 1.0) as f32
+}
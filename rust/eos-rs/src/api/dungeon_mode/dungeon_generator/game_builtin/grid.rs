@@ -6,11 +6,15 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::iter::repeat_with;
 use crate::api::dungeon_mode::dungeon_generator::DungeonGridCell;
+use crate::api::dungeon_mode::{DungeonTileExt, TerrainType};
 use crate::api::overlay::OverlayLoadLease;
+use crate::api::random::rand_i32;
 use crate::ctypes::c_int;
 use crate::ffi;
 use crate::ffi::floor_properties;
 
+use super::{FLOOR_HEIGHT, FLOOR_WIDTH};
+
 /// The capacity of the dungeon grid in both X and Y directions.
 pub const GRID_CAPACITY_DIM: usize = 15;
 
@@ -164,10 +168,20 @@ impl DungeonGridMutator {
         (self.cells, self.width, self.height)
     }
 
+    /// The width of this grid, in grid cells.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of this grid, in grid cells.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
     /// Get the cell at the given coordinates.
     /// Panics if the coordinates are out of bounds.
     pub fn get(&self, x: usize, y: usize) -> &DungeonGridCell {
-        debug_assert!(cells.len() == GRID_CAPACITY_DIM * GRID_CAPACITY_DIM);
+        debug_assert!(self.cells.len() == GRID_CAPACITY_DIM * GRID_CAPACITY_DIM);
         let coords = Self::get_coords(x, y);
         if coords >= self.cells.len() {
             panic!("Grid cell at ({}, {}) is out of bounds", x, y);
@@ -184,7 +198,7 @@ impl DungeonGridMutator {
     /// Get the cell at the given coordinates, mutably.
     /// Panics if the coordinates are out of bounds.
     pub fn get_mut(&mut self, x: usize, y: usize) -> &mut DungeonGridCell {
-        debug_assert!(cells.len() == GRID_CAPACITY_DIM * GRID_CAPACITY_DIM);
+        debug_assert!(self.cells.len() == GRID_CAPACITY_DIM * GRID_CAPACITY_DIM);
         let coords = Self::get_coords(x, y);
         if coords >= self.cells.len() {
             panic!("Grid cell at ({}, {}) is out of bounds", x, y);
@@ -459,4 +473,163 @@ impl DungeonGridMutator {
     pub fn set_spawn_flag_5<'a>(&'a self, cell: &'a mut ffi::dungeon_grid_cell) {
         unsafe { ffi::SetSpawnFlag5(cell as *mut _) }
     }
+
+    /// Finds space on the floor for a `width`x`height` rectangular room, mirroring Hengband's
+    /// `find_space`.
+    ///
+    /// Scans for an all-wall area of `width`x`height` plus a one tile margin on every side, and
+    /// returns the top-left tile coordinate of the room itself (not the margin), chosen uniformly
+    /// at random among every valid position via reservoir sampling, so the full candidate list
+    /// never needs to be materialized.
+    ///
+    /// Returns `None` if no position fits.
+    ///
+    /// # Safety
+    /// The caller needs to make sure that overlay 29 is loaded and it's safe to read the global
+    /// dungeon tile data.
+    pub unsafe fn find_space(&self, height: usize, width: usize) -> Option<(i32, i32)> {
+        let (width, height) = (width as i32, height as i32);
+        let mut chosen = None;
+        let mut seen = 0i32;
+
+        for y in 1..(FLOOR_HEIGHT - height - 1) {
+            for x in 1..(FLOOR_WIDTH - width - 1) {
+                if all_wall(x - 1, y - 1, x + width, y + height) {
+                    seen += 1;
+                    if rand_i32(0..seen) == 0 {
+                        chosen = Some((x, y));
+                    }
+                }
+            }
+        }
+
+        chosen
+    }
+
+    /// Tries a bounded number of random top-left candidates for a `width`x`height` room (plus a
+    /// one tile margin on every side), returning the first one that lands entirely on wall tiles
+    /// (and so touches no already-carved room or hallway).
+    ///
+    /// Unlike [`Self::find_space`], which exhaustively scans every valid position to pick one
+    /// uniformly at random, this gives up after `attempts` random tries. That makes it cheaper
+    /// on floors where most of the area is already spoken for (an exhaustive scan would do a lot
+    /// of wasted work confirming there's nowhere left), at the cost of occasionally missing a
+    /// fit that does exist.
+    ///
+    /// Returns `None` if no candidate tried fits, or if the requested size can't possibly fit on
+    /// the floor at all.
+    ///
+    /// # Safety
+    /// The caller needs to make sure that overlay 29 is loaded and it's safe to read the global
+    /// dungeon tile data.
+    pub unsafe fn find_free_space(
+        &self,
+        height: usize,
+        width: usize,
+        attempts: u32,
+    ) -> Option<(i32, i32)> {
+        let (width, height) = (width as i32, height as i32);
+        let max_x = FLOOR_WIDTH - width - 1;
+        let max_y = FLOOR_HEIGHT - height - 1;
+        if width <= 0 || height <= 0 || max_x < 1 || max_y < 1 {
+            return None;
+        }
+
+        for _ in 0..attempts {
+            let x = rand_i32(1..max_x);
+            let y = rand_i32(1..max_y);
+            if all_wall(x - 1, y - 1, x + width, y + height) {
+                return Some((x, y));
+            }
+        }
+
+        None
+    }
+
+    /// Checks whether excavating a `(x1, y1)`-`(x2, y2)` room would sever existing corridors,
+    /// mirroring Hengband's `check_room_boundary`, and repairs the floor if so.
+    ///
+    /// Excavating a room walls off its own perimeter, so any existing open tile sitting on that
+    /// perimeter would otherwise be cut off from whatever it used to connect to. This counts the
+    /// separate runs of open tiles along the `(x1, y1)`-`(x2, y2)` perimeter; if there's more than
+    /// one (meaning more than one existing corridor touches the room footprint), it carves a
+    /// one-tile-wide floor path around the room's outside (at a margin of 1 tile) so all of them
+    /// stay connected to each other once the room's own walls go up.
+    ///
+    /// Returns `true` if a repair path was carved, `false` if the perimeter had at most one run of
+    /// open tiles (nothing to repair).
+    ///
+    /// # Safety
+    /// The caller needs to make sure that overlay 29 is loaded and it's safe to manipulate the
+    /// global dungeon tile data.
+    pub unsafe fn check_room_boundary(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
+        let perimeter = rectangle_ring(x1, y1, x2, y2);
+        if count_open_runs(&perimeter) <= 1 {
+            return false;
+        }
+
+        for (x, y) in rectangle_ring(x1 - 1, y1 - 1, x2 + 1, y2 + 1) {
+            let tile = unsafe { &mut *ffi::GetTileSafe(x, y) };
+            if tile.get_terrain() == Some(TerrainType::Wall) {
+                tile.init();
+            }
+        }
+        true
+    }
+}
+
+/// Whether every tile in the inclusive rectangle `(x1, y1)`-`(x2, y2)` is a wall.
+fn all_wall(x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
+    for y in y1..=y2 {
+        for x in x1..=x2 {
+            if unsafe { &*ffi::GetTileSafe(x, y) }.get_terrain() != Some(TerrainType::Wall) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// The tile coordinates forming the border of the inclusive rectangle `(x1, y1)`-`(x2, y2)`, in
+/// cyclic (walk-the-edge) order.
+fn rectangle_ring(x1: i32, y1: i32, x2: i32, y2: i32) -> Vec<(i32, i32)> {
+    let mut ring = Vec::new();
+    for x in x1..=x2 {
+        ring.push((x, y1));
+    }
+    for y in (y1 + 1)..=y2 {
+        ring.push((x2, y));
+    }
+    for x in (x1..x2).rev() {
+        ring.push((x, y2));
+    }
+    for y in ((y1 + 1)..y2).rev() {
+        ring.push((x1, y));
+    }
+    ring
+}
+
+/// Counts the separate runs of consecutive open (non-wall) tiles in `ring`, treating it as a
+/// cycle (so a run spanning the end and the start of the slice counts once, not twice).
+fn count_open_runs(ring: &[(i32, i32)]) -> usize {
+    let is_open: Vec<bool> = ring
+        .iter()
+        .map(|&(x, y)| unsafe { &*ffi::GetTileSafe(x, y) }.get_terrain() != Some(TerrainType::Wall))
+        .collect();
+
+    if is_open.is_empty() || is_open.iter().all(|&open| !open) {
+        return 0;
+    }
+    if is_open.iter().all(|&open| open) {
+        return 1;
+    }
+
+    let mut runs = 0;
+    for i in 0..is_open.len() {
+        let prev = is_open[(i + is_open.len() - 1) % is_open.len()];
+        if is_open[i] && !prev {
+            runs += 1;
+        }
+    }
+    runs
 }
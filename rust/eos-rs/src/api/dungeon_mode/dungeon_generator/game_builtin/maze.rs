@@ -0,0 +1,220 @@
+//! Alternative maze-carving algorithms, as a pluggable replacement for
+//! [`DungeonGridMutator::generate_maze`]'s trapped random walk.
+//!
+//! The stock algorithm drops a "maze line" (a stride-2 random walk that lays obstacles as it
+//! goes) from a series of starting points around the room; since lines can cross or peter out
+//! early, the result can leave isolated loops, uneven dead-end density, or small unreachable
+//! pockets. [`MazeAlgorithm::RecursiveBacktracker`] and [`MazeAlgorithm::WilsonsAlgorithm`]
+//! instead treat every other interior tile as a graph node and carve a spanning tree over that
+//! graph, which guarantees the result is a "perfect" maze: every two open tiles are connected by
+//! exactly one path, with no loops and no unreachable pockets.
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::api::dungeon_mode::{DungeonTileExt, TerrainType};
+use crate::api::random::rand_i32;
+use crate::ffi;
+
+use super::DungeonGridMutator;
+
+/// A maze-carving algorithm for [`DungeonGridMutator::generate_maze_with`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MazeAlgorithm {
+    /// The game's own algorithm: a series of stride-2 random walks
+    /// (see [`super::GlobalDungeonStructureGenerator::generate_maze_line`]), each trapped (and
+    /// therefore stopped) once it has no in-bounds, not-yet-obstructed neighbor left. Can leave
+    /// isolated loops and dead ends of uneven quality.
+    TrappedRandomWalk,
+    /// A recursive-backtracker spanning tree: starting from a random cell, repeatedly steps to a
+    /// random unvisited neighbor two tiles away (carving the wall tile between them), pushing
+    /// each visited cell onto a stack, and backtracks along the stack once a cell has no
+    /// unvisited neighbor left. Produces long, winding corridors with relatively few branches.
+    RecursiveBacktracker,
+    /// A spanning tree built from loop-erased random walks (Wilson's algorithm): repeatedly picks
+    /// a cell not yet in the maze and random-walks (erasing any loop the walk makes on itself)
+    /// until it hits a cell that is, then carves the resulting loop-free path into the maze.
+    /// Unlike the recursive backtracker, every valid spanning tree over the cell graph is equally
+    /// likely, giving more uniform branching.
+    WilsonsAlgorithm,
+}
+
+impl DungeonGridMutator {
+    /// Carves a maze into `cell`'s interior (the same one-tile wall margin [`Self::carve_room`]
+    /// leaves) using `algorithm`, writing obstacle terrain as secondary terrain (water/lava) if
+    /// `use_secondary_terrain` is set, or as walls otherwise - the same flag
+    /// [`Self::generate_maze`] takes.
+    ///
+    /// [`MazeAlgorithm::TrappedRandomWalk`] delegates directly to [`Self::generate_maze`] and
+    /// expects `cell`'s interior to already be open floor, exactly like that method. The other
+    /// two algorithms instead fill the entire interior with obstacles first and then carve
+    /// passages, since a spanning tree needs to start from a fully-walled grid.
+    ///
+    /// Every algorithm treats every other interior tile (starting from the first interior column
+    /// and row) as a maze cell and the tiles between cells as removable walls, so, like
+    /// [`Self::generate_maze`], this only produces a clean, fully-carved maze when the interior
+    /// has odd width and height; on an even dimension, the last row/column of cells is left as an
+    /// uncarvable sliver of obstacles.
+    ///
+    /// # Safety
+    /// The caller needs to make sure that overlay 29 is loaded and it's safe to manipulate the
+    /// global dungeon tile data.
+    pub unsafe fn generate_maze_with(
+        &self,
+        cell: &mut ffi::dungeon_grid_cell,
+        algorithm: MazeAlgorithm,
+        use_secondary_terrain: bool,
+    ) {
+        if algorithm == MazeAlgorithm::TrappedRandomWalk {
+            self.generate_maze(cell, use_secondary_terrain);
+            return;
+        }
+
+        let (x1, y1, x2, y2) = (cell.start_x + 1, cell.start_y + 1, cell.end_x - 2, cell.end_y - 2);
+        if x2 <= x1 || y2 <= y1 {
+            return;
+        }
+
+        for y in y1..=y2 {
+            for x in x1..=x2 {
+                // SAFETY: the caller guarantees it's safe to manipulate the global tile data, and
+                // `(x, y)` is within the cell's own bounds.
+                obstruct_tile(x, y, use_secondary_terrain);
+            }
+        }
+
+        match algorithm {
+            MazeAlgorithm::TrappedRandomWalk => unreachable!(),
+            MazeAlgorithm::RecursiveBacktracker => carve_recursive_backtracker(x1, y1, x2, y2),
+            MazeAlgorithm::WilsonsAlgorithm => carve_wilsons(x1, y1, x2, y2),
+        }
+    }
+}
+
+/// Sets `(x, y)`'s terrain to an obstacle: secondary terrain (water/lava) if
+/// `use_secondary_terrain`, otherwise a wall.
+///
+/// # Safety
+/// See [`DungeonGridMutator::generate_maze_with`].
+unsafe fn obstruct_tile(x: i32, y: i32, use_secondary_terrain: bool) {
+    let tile = unsafe { &mut *ffi::GetTileSafe(x, y) };
+    let room = tile.room;
+    tile.set_terrain_obstacle_checked(use_secondary_terrain, room);
+}
+
+/// Opens `(x, y)` back up to normal, walkable terrain, preserving its room index.
+///
+/// # Safety
+/// See [`DungeonGridMutator::generate_maze_with`].
+unsafe fn open_tile(x: i32, y: i32) {
+    let tile = unsafe { &mut *ffi::GetTileSafe(x, y) };
+    tile.set_terrain(TerrainType::Normal);
+}
+
+/// Whether `(x, y)` is currently open (part of the maze already).
+///
+/// # Safety
+/// See [`DungeonGridMutator::generate_maze_with`].
+unsafe fn is_open(x: i32, y: i32) -> bool {
+    let tile = unsafe { &*ffi::GetTileSafe(x, y) };
+    tile.get_terrain() == Some(TerrainType::Normal)
+}
+
+/// Carves a recursive-backtracker spanning tree over the cells of `(x1, y1)`-`(x2, y2)`.
+///
+/// # Safety
+/// See [`DungeonGridMutator::generate_maze_with`].
+unsafe fn carve_recursive_backtracker(x1: i32, y1: i32, x2: i32, y2: i32) {
+    let start = (
+        x1 + 2 * rand_i32(0..=(x2 - x1) / 2),
+        y1 + 2 * rand_i32(0..=(y2 - y1) / 2),
+    );
+    // SAFETY: see above.
+    unsafe { open_tile(start.0, start.1) };
+
+    let mut stack = Vec::from([start]);
+    while let Some(&(cx, cy)) = stack.last() {
+        let mut neighbors = Vec::new();
+        for (dx, dy) in [(2, 0), (-2, 0), (0, 2), (0, -2)] {
+            let (nx, ny) = (cx + dx, cy + dy);
+            // SAFETY: see above.
+            if nx >= x1 && nx <= x2 && ny >= y1 && ny <= y2 && !unsafe { is_open(nx, ny) } {
+                neighbors.push((nx, ny, cx + dx / 2, cy + dy / 2));
+            }
+        }
+
+        if neighbors.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let (nx, ny, wx, wy) = neighbors[rand_i32(0..neighbors.len() as i32) as usize];
+        // SAFETY: see above.
+        unsafe {
+            open_tile(wx, wy);
+            open_tile(nx, ny);
+        }
+        stack.push((nx, ny));
+    }
+}
+
+/// Carves a Wilson's-algorithm spanning tree (loop-erased random walks) over the cells of
+/// `(x1, y1)`-`(x2, y2)`.
+///
+/// # Safety
+/// See [`DungeonGridMutator::generate_maze_with`].
+unsafe fn carve_wilsons(x1: i32, y1: i32, x2: i32, y2: i32) {
+    let cells_x = (x2 - x1) / 2 + 1;
+    let cells_y = (y2 - y1) / 2 + 1;
+    let cell_at = |i: i32, j: i32| (x1 + i * 2, y1 + j * 2);
+
+    let mut in_maze = BTreeSet::new();
+    let root = (rand_i32(0..cells_x), rand_i32(0..cells_y));
+    in_maze.insert(root);
+    let (rx, ry) = cell_at(root.0, root.1);
+    // SAFETY: see above.
+    unsafe { open_tile(rx, ry) };
+
+    for start_i in 0..cells_x {
+        for start_j in 0..cells_y {
+            if in_maze.contains(&(start_i, start_j)) {
+                continue;
+            }
+
+            let mut path = Vec::from([(start_i, start_j)]);
+            let mut current = (start_i, start_j);
+            while !in_maze.contains(&current) {
+                let mut candidates = Vec::new();
+                for (di, dj) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                    let next = (current.0 + di, current.1 + dj);
+                    if next.0 >= 0 && next.0 < cells_x && next.1 >= 0 && next.1 < cells_y {
+                        candidates.push(next);
+                    }
+                }
+                let next = candidates[rand_i32(0..candidates.len() as i32) as usize];
+
+                // Loop erasure: if `next` is already on the current walk, cut the walk back to
+                // it instead of extending it, discarding the loop it just closed.
+                match path.iter().position(|&c| c == next) {
+                    Some(loop_start) => path.truncate(loop_start + 1),
+                    None => path.push(next),
+                }
+                current = next;
+            }
+
+            for pair in path.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                in_maze.insert(a);
+                let (ax, ay) = cell_at(a.0, a.1);
+                let (bx, by) = cell_at(b.0, b.1);
+                // SAFETY: see above.
+                unsafe {
+                    open_tile(ax, ay);
+                    open_tile(bx, by);
+                    open_tile((ax + bx) / 2, (ay + by) / 2);
+                }
+            }
+            in_maze.insert(current);
+        }
+    }
+}
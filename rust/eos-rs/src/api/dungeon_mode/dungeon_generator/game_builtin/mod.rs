@@ -11,9 +11,31 @@
 //! To get an instance of the generator, use
 //! [`crate::api::dungeon_mode::GlobalDungeonData::get_builtin_dungeon_generator`].
 
+mod astar_connections;
+mod bsp;
+mod cellular_automata_cave;
+mod connectivity;
+mod fractal_cave;
 mod grid;
-
+mod maze;
+mod monster_groups;
+mod room_shapes;
+mod room_template;
+mod stairs;
+mod trapped_room;
+mod vault;
+
+pub use self::bsp::BspNode;
+pub use self::cellular_automata_cave::{CellularAutomataCaveGenerator, CellularAutomataCaveParams};
+pub use self::fractal_cave::{FractalCaveGenerator, FractalCaveParams};
 pub use self::grid::{DungeonGridMutator, GRID_CAPACITY_DIM};
+pub use self::maze::MazeAlgorithm;
+pub use self::monster_groups::{roll_nest_chance, NestLayout, NestTheme, RoomBounds};
+pub use self::room_shapes::RoomShape;
+pub use self::room_template::{RoomTemplate, RoomTemplateSpawns};
+pub use self::vault::{
+    VaultRandomRegisters, VaultSpawnMarker, VaultStampError, VaultTemplate, VaultTransform,
+};
 use crate::api::dungeon_mode::GlobalDungeonData;
 
 use crate::api::dungeon_mode::dungeon_generator::{
@@ -24,6 +46,11 @@ use crate::api::overlay::OverlayLoadLease;
 use crate::ctypes::c_int;
 use crate::ffi;
 
+/// The full floor size (including the one-tile impassable outer border), matching
+/// [`crate::api::dungeon_mode::GlobalDungeonData::get_tiles`]'s `56x32` dimensions.
+pub(super) const FLOOR_WIDTH: i32 = 56;
+pub(super) const FLOOR_HEIGHT: i32 = 32;
+
 //-----------------------------------------------------------------------------------------------//
 
 /// The structure and layout generator for the global dungeon.
@@ -347,11 +374,28 @@ impl<'a> DungeonFloorGeneration for GlobalDungeonStructureGenerator<'a> {
     }
 
     /// Width and height are ignored for most layouts.
+    ///
+    /// # Panics
+    /// Panics if `layout` carries a grid size that could never produce a valid floor:
+    /// a non-positive `width`/`height` for [`BuiltinDungeonLayoutGenerators::Standard`] or
+    /// [`BuiltinDungeonLayoutGenerators::OuterRooms`], or an [`OuterRooms`](BuiltinDungeonLayoutGenerators::OuterRooms)
+    /// `width` below 4 (below that, the game's own generator fails to connect the ring of
+    /// rooms together; see the note on that variant).
     fn generate_layout(
         &mut self,
         layout: &mut Self::LayoutGenerator,
         properties: &ffi::floor_properties,
     ) -> &mut Self {
+        match layout {
+            BuiltinDungeonLayoutGenerators::Standard { width, height } => {
+                assert!(*width > 0 && *height > 0, "grid size must be positive");
+            }
+            BuiltinDungeonLayoutGenerators::OuterRooms { width, height } => {
+                assert!(*width > 0 && *height > 0, "grid size must be positive");
+                assert!(*width >= 4, "OuterRooms is bugged for width < 4");
+            }
+            _ => {}
+        }
         unsafe {
             match layout {
                 BuiltinDungeonLayoutGenerators::Standard { width, height } => {
@@ -0,0 +1,272 @@
+//! Themed group spawning for [`GlobalDungeonEntityGenerator`], inspired by Hengband's monster
+//! pits and nests: instead of `spawn_enemies`'s uniform placement, a caller-chosen set of species
+//! is arranged across a room either by scattering (a "nest") or in concentric rings of escalating
+//! strength (a "pit").
+//!
+//! Neither method spawns anything itself: [`GlobalDungeonEntityGenerator`] has no handle on the
+//! global dungeon struct to build a [`ffi::spawned_monster_data`] from, so both return the planned
+//! `(x, y, species)` assignments for the caller to hand to
+//! [`crate::api::dungeon_mode::GlobalDungeonData::spawn_monster`], the same way
+//! [`super::GlobalDungeonStructureGenerator::stamp_vault`] hands off its spawn markers rather than
+//! spawning them.
+
+use alloc::vec::Vec;
+
+use rand_core::RngCore;
+
+use crate::api::dungeon_mode::{DungeonTileExt, TerrainType};
+use crate::api::monsters::MonsterSpeciesId;
+use crate::api::random::rand_i32;
+use crate::api::types::MonsterTypeId;
+use crate::ffi;
+
+use super::{DungeonGridMutator, GlobalDungeonEntityGenerator};
+
+/// Which species a themed monster nest/pit (see
+/// [`crate::api::dungeon_mode::GlobalDungeonData::populate_nest`]) allows in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NestTheme {
+    /// Only species of the given type are eligible.
+    SingleType(MonsterTypeId),
+    /// No type restriction; every candidate species is eligible.
+    Mixed,
+}
+
+impl NestTheme {
+    /// Whether a candidate of `species_type` is eligible under this theme.
+    pub fn allows(&self, species_type: MonsterTypeId) -> bool {
+        match self {
+            NestTheme::SingleType(t) => t.id() == species_type.id(),
+            NestTheme::Mixed => true,
+        }
+    }
+}
+
+/// A rectangular tile range, used to scope [`GlobalDungeonEntityGenerator::spawn_monster_nest`]
+/// and [`GlobalDungeonEntityGenerator::spawn_monster_pit`] to a single room.
+#[derive(Clone, Copy, Debug)]
+pub struct RoomBounds {
+    /// Inclusive lower x bound.
+    pub x0: i32,
+    /// Inclusive lower y bound.
+    pub y0: i32,
+    /// Exclusive upper x bound.
+    pub x1: i32,
+    /// Exclusive upper y bound.
+    pub y1: i32,
+}
+
+impl RoomBounds {
+    /// Open (non-wall, non-hallway-junction) tile positions within these bounds.
+    ///
+    /// # Note
+    /// This only checks terrain and the junction flag, not whether a tile is a Kecleon shop, the
+    /// player's tile, or another kind of special tile, because this crate doesn't currently
+    /// expose those tile flags safely. Callers that care about those distinctions should filter
+    /// the result further.
+    fn open_tiles(&self) -> Vec<(i32, i32)> {
+        let mut tiles = Vec::new();
+        for y in self.y0..self.y1 {
+            for x in self.x0..self.x1 {
+                // SAFETY: We have a mutable reference to the dungeon (see the safety note on
+                // `GlobalDungeonEntityGenerator::new`).
+                let tile = unsafe { &*ffi::GetTileSafe(x, y) };
+                if tile.get_terrain() == Some(TerrainType::Normal) && !tile.get_junction_flag() {
+                    tiles.push((x, y));
+                }
+            }
+        }
+        tiles
+    }
+}
+
+/// Picks a random element of `species` with uniform probability.
+///
+/// Relative spawn weight is expressed by repeating a species in the slice: a species listed
+/// twice is twice as likely to be picked as one listed once.
+fn weighted_pick(species: &[MonsterSpeciesId]) -> MonsterSpeciesId {
+    species[rand_i32(0..species.len() as i32) as usize]
+}
+
+/// The Chebyshev (chessboard) distance between two tile positions.
+fn chebyshev_distance(a: (i32, i32), b: (i32, i32)) -> i32 {
+    (a.0 - b.0).abs().max((a.1 - b.1).abs())
+}
+
+/// Rolls whether a monster nest/pit should be generated, given a percentage chance (0-100),
+/// the same convention [`super::DungeonGridMutator::generate_monster_house`] and
+/// [`super::DungeonGridMutator::generate_kecleon_shop`] use for their own spawn chance
+/// parameters.
+pub fn roll_nest_chance(spawn_chance: u8) -> bool {
+    assert!(spawn_chance <= 100);
+    rand_i32(0..100) < spawn_chance as i32
+}
+
+impl GlobalDungeonEntityGenerator {
+    /// Plans a "monster nest": a weighted random selection of `species` scattered across every
+    /// open tile of `room_bounds`.
+    ///
+    /// Relative spawn weight is expressed by how often a species appears in `species` (a species
+    /// listed twice is twice as likely to be picked as one listed once).
+    ///
+    /// Returns the chosen `(x, y, species)` assignments, in no particular order. Doesn't spawn
+    /// anything; see the [module documentation](self) for why.
+    pub fn spawn_monster_nest(
+        &self,
+        room_bounds: RoomBounds,
+        species: &[MonsterSpeciesId],
+    ) -> Vec<(i32, i32, MonsterSpeciesId)> {
+        if species.is_empty() {
+            return Vec::new();
+        }
+        room_bounds
+            .open_tiles()
+            .into_iter()
+            .map(|(x, y)| (x, y, weighted_pick(species)))
+            .collect()
+    }
+
+    /// Plans a "monster pit": `species_by_strength` (a `(species, strength)` list, in any order)
+    /// is sorted strongest-first, then placed in concentric rings around the center of
+    /// `room_bounds` by Chebyshev distance, with the strongest species at the center and
+    /// progressively weaker ones outward.
+    ///
+    /// The room's open tiles are split into as many equal-sized, distance-sorted bands as there
+    /// are entries in `species_by_strength` (entries of equal strength each get their own band);
+    /// the strongest species fills the innermost band.
+    ///
+    /// Returns the chosen `(x, y, species)` assignments. Doesn't spawn anything; see the
+    /// [module documentation](self) for why.
+    pub fn spawn_monster_pit(
+        &self,
+        room_bounds: RoomBounds,
+        species_by_strength: &[(MonsterSpeciesId, i32)],
+    ) -> Vec<(i32, i32, MonsterSpeciesId)> {
+        if species_by_strength.is_empty() {
+            return Vec::new();
+        }
+
+        let mut ranked = species_by_strength.to_vec();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let center = (
+            (room_bounds.x0 + room_bounds.x1 - 1) / 2,
+            (room_bounds.y0 + room_bounds.y1 - 1) / 2,
+        );
+        let mut tiles = room_bounds.open_tiles();
+        tiles.sort_by_key(|&pos| chebyshev_distance(pos, center));
+
+        let band_count = ranked.len();
+        let band_size = ((tiles.len() + band_count - 1) / band_count).max(1);
+        tiles
+            .into_iter()
+            .enumerate()
+            .map(|(i, (x, y))| {
+                let band = (i / band_size).min(band_count - 1);
+                (x, y, ranked[band].0)
+            })
+            .collect()
+    }
+}
+
+/// Scatter ("nest") or concentric-ring ("pit") placement strategy for
+/// [`DungeonGridMutator::generate_monster_nest`].
+pub enum NestLayout<'a> {
+    /// Scatter `species` uniformly at random across the room's interior tiles, same as
+    /// [`GlobalDungeonEntityGenerator::spawn_monster_nest`].
+    Nest,
+    /// Sort `species` by `threat` (descending) and place the strongest band at the room's
+    /// center, working outward in concentric Chebyshev-distance rings, same as
+    /// [`GlobalDungeonEntityGenerator::spawn_monster_pit`].
+    Pit {
+        /// Scores a species by threat; higher means stronger, and ends up closer to the center.
+        threat: &'a dyn Fn(MonsterSpeciesId) -> i32,
+    },
+}
+
+/// Returns a uniformly random value in `0..bound`. `bound` must be nonzero.
+fn rand_below(rng: &mut impl RngCore, bound: usize) -> usize {
+    (rng.next_u32() as usize) % bound
+}
+
+impl DungeonGridMutator {
+    /// Plans a themed monster nest/pit (see [`NestLayout`]) for the room at grid cell `(x, y)`,
+    /// using its tile bounds (inset by the usual one-tile wall margin) as the room interior.
+    ///
+    /// Refuses the room (returning an empty `Vec`, leaving the grid untouched) unless it's a
+    /// valid, connected, plain room -- Monster House, merged, and maze rooms are all rejected,
+    /// the same "no other special features" rule [`Self::generate_kecleon_shop`] and
+    /// [`Self::generate_monster_house`] use for their own target room.
+    ///
+    /// Doesn't spawn anything itself, for the same reason
+    /// [`GlobalDungeonEntityGenerator::spawn_monster_nest`] doesn't (see the
+    /// [module documentation](self)): returns the planned `(x, y, species)` assignments for the
+    /// caller to hand to [`crate::api::dungeon_mode::GlobalDungeonData::spawn_monster`].
+    ///
+    /// # Safety
+    /// The caller needs to make sure that it's safe to read the global dungeon tile data.
+    pub unsafe fn generate_monster_nest(
+        &mut self,
+        x: usize,
+        y: usize,
+        species: &[MonsterSpeciesId],
+        layout: NestLayout,
+        rng: &mut impl RngCore,
+    ) -> Vec<(i32, i32, MonsterSpeciesId)> {
+        if species.is_empty() {
+            return Vec::new();
+        }
+
+        let cell = self.get(x, y);
+        let is_plain_room = cell.is_room
+            && cell.is_connected
+            && !cell.is_invalid
+            && !cell.is_monster_house
+            && !cell.is_maze_room
+            && !cell.is_merged_room
+            && !cell.was_merged_into_other_room;
+        if !is_plain_room {
+            return Vec::new();
+        }
+
+        let bounds = RoomBounds {
+            x0: cell.start_x + 1,
+            y0: cell.start_y + 1,
+            x1: cell.end_x - 1,
+            y1: cell.end_y - 1,
+        };
+
+        let mut tiles = bounds.open_tiles();
+        if tiles.is_empty() {
+            return Vec::new();
+        }
+
+        match layout {
+            NestLayout::Nest => tiles
+                .into_iter()
+                .map(|(tx, ty)| (tx, ty, species[rand_below(rng, species.len())]))
+                .collect(),
+            NestLayout::Pit { threat } => {
+                let mut ranked: Vec<MonsterSpeciesId> = species.to_vec();
+                ranked.sort_by_key(|s| core::cmp::Reverse(threat(*s)));
+
+                let center = (
+                    (bounds.x0 + bounds.x1 - 1) / 2,
+                    (bounds.y0 + bounds.y1 - 1) / 2,
+                );
+                tiles.sort_by_key(|&pos| chebyshev_distance(pos, center));
+
+                let band_count = ranked.len();
+                let band_size = ((tiles.len() + band_count - 1) / band_count).max(1);
+                tiles
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (tx, ty))| {
+                        let band = (i / band_size).min(band_count - 1);
+                        (tx, ty, ranked[band])
+                    })
+                    .collect()
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Non-rectangular room shapes, carved directly into a grid cell's tile bounds as an alternative
+//! to [`DungeonGridMutator::create_rooms_and_anchors`]'s rectangles.
+//!
+//! Hengband's room builder also offers circular, cross-shaped, and overlapping rooms; the
+//! builtin `CreateRoomsAndAnchors` can only ever emit axis-aligned rectangles, so
+//! [`DungeonGridMutator::carve_room`] reimplements those three shapes directly against the tile
+//! data, the same way [`super::vault`] stamps its own terrain rather than going through the
+//! game's room-carving functions.
+
+use alloc::vec::Vec;
+
+use crate::api::dungeon_mode::{DungeonTileExt, TerrainType};
+use crate::api::random::rand_i32;
+use crate::ffi;
+
+use super::DungeonGridMutator;
+
+/// A non-rectangular room shape [`DungeonGridMutator::carve_room`] can stamp into a grid cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoomShape {
+    /// An ellipse inscribed in the cell's tile bounds (after the one-tile wall margin): every
+    /// tile where `(x-cx)^2*h^2 + (y-cy)^2*w^2 <= (w*h/2)^2` is carved.
+    Circle,
+    /// The union of a full-width horizontal band and a full-height vertical band, both centered
+    /// in the cell (after the margin).
+    Cross,
+    /// The union of two rectangles, each covering roughly two thirds of the cell along one axis,
+    /// independently offset by a random fraction of the remaining space. Depending on the two
+    /// offsets, the union comes out as an L, a T, or a plus shape.
+    Overlapping,
+}
+
+impl DungeonGridMutator {
+    /// Carves `shape` into the grid cell at `(grid_x, grid_y)`'s tile bounds, leaving a one-tile
+    /// wall margin against the cell boundary (matching the margin
+    /// [`Self::create_rooms_and_anchors`]'s rectangles leave), and stamps `room_index` onto every
+    /// carved tile so the stock connection and junction passes, which key off of each tile's room
+    /// index, treat it like any other room.
+    ///
+    /// Marks the cell as a room (sets its `is_room` flag).
+    /// Call this instead of, not in addition to, carving a rectangle into the cell; calling it on
+    /// a cell that isn't empty produces an inconsistent mix of terrain and room indices.
+    ///
+    /// `room_index` should be the cell's own index (`grid_y * width + grid_x`), matching what
+    /// [`Self::create_rooms_and_anchors`] stamps onto its own rectangles.
+    ///
+    /// Does nothing if the cell's tile bounds are too small to leave any interior after the
+    /// margin.
+    ///
+    /// # Safety
+    /// The caller needs to make sure that overlay 29 is loaded and it's safe to manipulate the
+    /// global dungeon tile data.
+    pub unsafe fn carve_room(
+        &mut self,
+        grid_x: usize,
+        grid_y: usize,
+        shape: RoomShape,
+        room_index: u8,
+        use_secondary_terrain: bool,
+    ) {
+        let cell = self.get_mut(grid_x, grid_y);
+        let (x1, y1, x2, y2) = (cell.start_x + 1, cell.start_y + 1, cell.end_x - 2, cell.end_y - 2);
+
+        if x2 <= x1 || y2 <= y1 {
+            return;
+        }
+        cell.is_room = true;
+
+        let tiles = match shape {
+            RoomShape::Circle => circle_tiles(x1, y1, x2, y2),
+            RoomShape::Cross => cross_tiles(x1, y1, x2, y2),
+            RoomShape::Overlapping => overlapping_tiles(x1, y1, x2, y2),
+        };
+
+        for (x, y) in tiles {
+            // SAFETY: the caller guarantees it's safe to manipulate the global tile data, and
+            // `(x, y)` is within the cell's own bounds.
+            let tile = unsafe { &mut *ffi::GetTileSafe(x, y) };
+            tile.set_terrain_obstacle_checked(use_secondary_terrain, room_index);
+            if !use_secondary_terrain {
+                tile.set_terrain(TerrainType::Normal);
+            }
+            tile.room = room_index;
+        }
+    }
+}
+
+/// Every tile of the inclusive rectangle `(x1, y1)`-`(x2, y2)` within the ellipse inscribed in
+/// it.
+fn circle_tiles(x1: i32, y1: i32, x2: i32, y2: i32) -> Vec<(i32, i32)> {
+    let (w, h) = ((x2 - x1 + 1) as f32, (y2 - y1 + 1) as f32);
+    let (cx, cy) = ((x1 + x2) as f32 / 2.0, (y1 + y2) as f32 / 2.0);
+    let threshold = (w * h / 2.0).powi(2);
+
+    let mut tiles = Vec::new();
+    for y in y1..=y2 {
+        for x in x1..=x2 {
+            let (dx, dy) = (x as f32 - cx, y as f32 - cy);
+            if dx * dx * h * h + dy * dy * w * w <= threshold {
+                tiles.push((x, y));
+            }
+        }
+    }
+    tiles
+}
+
+/// The union of a full-width horizontal band and a full-height vertical band, each roughly a
+/// third of the rectangle's size along their short axis, both centered in `(x1, y1)`-`(x2, y2)`.
+fn cross_tiles(x1: i32, y1: i32, x2: i32, y2: i32) -> Vec<(i32, i32)> {
+    let band_height = (((y2 - y1 + 1) / 3).max(1) - 1) / 2;
+    let band_width = (((x2 - x1 + 1) / 3).max(1) - 1) / 2;
+    let (cx, cy) = ((x1 + x2) / 2, (y1 + y2) / 2);
+
+    let mut tiles = Vec::new();
+    for y in y1..=y2 {
+        for x in x1..=x2 {
+            let in_horizontal_band = (y - cy).abs() <= band_height;
+            let in_vertical_band = (x - cx).abs() <= band_width;
+            if in_horizontal_band || in_vertical_band {
+                tiles.push((x, y));
+            }
+        }
+    }
+    tiles
+}
+
+/// The union of two rectangles within `(x1, y1)`-`(x2, y2)`: one spanning the full height and
+/// roughly two thirds of the width, the other spanning the full width and roughly two thirds of
+/// the height, each independently offset along its short axis by a random fraction of the
+/// remaining space.
+fn overlapping_tiles(x1: i32, y1: i32, x2: i32, y2: i32) -> Vec<(i32, i32)> {
+    let (width, height) = (x2 - x1 + 1, y2 - y1 + 1);
+
+    let rect_a_width = (width * 2 / 3).max(1);
+    let rect_a_x1 = x1 + rand_i32(0..=(width - rect_a_width).max(0));
+    let rect_a = (rect_a_x1, y1, rect_a_x1 + rect_a_width - 1, y2);
+
+    let rect_b_height = (height * 2 / 3).max(1);
+    let rect_b_y1 = y1 + rand_i32(0..=(height - rect_b_height).max(0));
+    let rect_b = (x1, rect_b_y1, x2, rect_b_y1 + rect_b_height - 1);
+
+    let mut tiles = Vec::new();
+    for y in y1..=y2 {
+        for x in x1..=x2 {
+            let in_a = x >= rect_a.0 && x <= rect_a.2 && y >= rect_a.1 && y <= rect_a.3;
+            let in_b = x >= rect_b.0 && x <= rect_b.2 && y >= rect_b.1 && y <= rect_b.3;
+            if in_a || in_b {
+                tiles.push((x, y));
+            }
+        }
+    }
+    tiles
+}
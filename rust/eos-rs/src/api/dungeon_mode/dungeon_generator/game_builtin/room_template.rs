@@ -0,0 +1,214 @@
+//! Hand-authored ASCII room templates ("set pieces"), stamped onto an already-assigned room's
+//! tiles, analogous to Angband's `v_info` vaults.
+//!
+//! Unlike [`super::vault`]'s [`VaultTemplate`](super::VaultTemplate), which paints directly onto
+//! arbitrary floor tiles outside of the grid cell system, [`RoomTemplate::stamp_template`]
+//! operates on a room a grid cell already owns: it targets grid coordinates, not tile
+//! coordinates, and panics on a bad target instead of returning a `Result`, since by the time a
+//! mod calls this the room is expected to already have been carved and sized by the regular
+//! pipeline (see [`DungeonGridMutator::create_rooms_and_anchors`]).
+
+use alloc::vec::Vec;
+
+use crate::api::dungeon_mode::traps::TrapId;
+use crate::api::dungeon_mode::DungeonTileExt;
+use crate::ffi;
+
+use super::DungeonGridMutator;
+
+/// A single cell of a [`RoomTemplate`], parsed from one ASCII character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RoomTemplateCell {
+    Wall,
+    Open,
+    Secondary,
+    /// The template leaves whatever terrain is already on the floor untouched here.
+    Transparent,
+}
+
+/// The positions [`RoomTemplate::stamp_template`] couldn't act on directly (no tile-level flag or
+/// room-agnostic game function exists for these), translated into floor coordinates for the
+/// caller to hand off to [`super::GlobalDungeonEntityGenerator`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RoomTemplateSpawns {
+    /// `$`/`*` cells: a position an item should spawn at.
+    pub items: Vec<(i32, i32)>,
+    /// `<`/`>` cells: a position stairs should spawn at.
+    pub stairs: Vec<(i32, i32)>,
+    /// `@` cells: a position a guaranteed monster should spawn at.
+    pub monsters: Vec<(i32, i32)>,
+}
+
+/// A pre-authored room template, parsed from a plain ASCII grid, for stamping into a room a grid
+/// cell already owns (see [`Self::stamp_template`]).
+///
+/// # Template syntax
+/// * `#` - wall
+/// * `.` - open floor
+/// * `~` - secondary terrain (water/lava)
+/// * `^` - open floor with a trap; [`Self::stamp_template`] spawns `trap_id` there directly
+/// * `$`/`*` - open floor with an item spawn marker
+/// * `<`/`>` - open floor with a stairs spawn marker
+/// * `@` - open floor with a guaranteed monster spawn marker
+/// * any other character (a space is conventional) - transparent: leaves the floor's existing
+///   terrain untouched
+pub struct RoomTemplate {
+    width: usize,
+    height: usize,
+    cells: Vec<RoomTemplateCell>,
+    traps: Vec<(usize, usize)>,
+    items: Vec<(usize, usize)>,
+    stairs: Vec<(usize, usize)>,
+    monsters: Vec<(usize, usize)>,
+    trap_id: TrapId,
+}
+
+impl RoomTemplate {
+    /// Parses a template out of `text`, a sequence of equal-length, non-empty lines (empty lines
+    /// are ignored, so a template can be written with a leading/trailing empty line for
+    /// readability; a line of only spaces is a row of transparent cells, not ignored). Every `^`
+    /// cell spawns `trap_id` once the template is stamped.
+    ///
+    /// Returns `None` if `text` has no non-empty lines, or if its lines aren't all the same
+    /// length.
+    pub fn parse(text: &str, trap_id: TrapId) -> Option<Self> {
+        let lines: Vec<&str> = text.lines().filter(|line| !line.is_empty()).collect();
+        let height = lines.len();
+        if height == 0 {
+            return None;
+        }
+        let width = lines[0].chars().count();
+        if lines.iter().any(|line| line.chars().count() != width) {
+            return None;
+        }
+
+        let mut cells = Vec::with_capacity(width * height);
+        let mut traps = Vec::new();
+        let mut items = Vec::new();
+        let mut stairs = Vec::new();
+        let mut monsters = Vec::new();
+        for (y, line) in lines.iter().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                let cell = match ch {
+                    '#' => RoomTemplateCell::Wall,
+                    '.' => RoomTemplateCell::Open,
+                    '~' => RoomTemplateCell::Secondary,
+                    '^' => {
+                        traps.push((x, y));
+                        RoomTemplateCell::Open
+                    }
+                    '$' | '*' => {
+                        items.push((x, y));
+                        RoomTemplateCell::Open
+                    }
+                    '<' | '>' => {
+                        stairs.push((x, y));
+                        RoomTemplateCell::Open
+                    }
+                    '@' => {
+                        monsters.push((x, y));
+                        RoomTemplateCell::Open
+                    }
+                    _ => RoomTemplateCell::Transparent,
+                };
+                cells.push(cell);
+            }
+        }
+
+        Some(Self { width, height, cells, traps, items, stairs, monsters, trap_id })
+    }
+
+    /// The template's width in tiles.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The template's height in tiles.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, x: usize, y: usize) -> RoomTemplateCell {
+        self.cells[y * self.width + x]
+    }
+}
+
+impl DungeonGridMutator {
+    /// Stamps `template` into the room occupying grid cell `(x, y)`, with the template's
+    /// top-left corner at the room's `start_x`/`start_y`.
+    ///
+    /// Sets each tile's terrain directly (wall, open or secondary) and spawns `template`'s traps
+    /// immediately via the game's `SpawnTrap`; items, stairs and guaranteed monsters have no
+    /// room-agnostic spawn function to call here, so their positions come back in the returned
+    /// [`RoomTemplateSpawns`] for the caller to hand off to
+    /// [`super::GlobalDungeonEntityGenerator`].
+    ///
+    /// # Panics
+    /// Panics if the cell at `(x, y)` isn't a valid, unmerged room, or if the room is smaller than
+    /// `template` along either axis.
+    ///
+    /// # Safety
+    /// The caller needs to make sure that overlay 29 is loaded and it's safe to manipulate the
+    /// global dungeon tile/entity data.
+    pub unsafe fn stamp_template(
+        &mut self,
+        x: usize,
+        y: usize,
+        template: &RoomTemplate,
+    ) -> RoomTemplateSpawns {
+        let cell = self.get(x, y);
+        assert!(
+            cell.is_room && !cell.is_invalid && !cell.was_merged_into_other_room,
+            "stamp_template target at ({x}, {y}) is not a valid, unmerged room"
+        );
+        let (start_x, start_y, end_x, end_y) = (cell.start_x, cell.start_y, cell.end_x, cell.end_y);
+        assert!(
+            (end_x - start_x) as usize >= template.width() && (end_y - start_y) as usize >= template.height(),
+            "stamp_template target at ({x}, {y}) is smaller than the template"
+        );
+
+        // SAFETY: the caller of `stamp_template` guarantees it's safe to manipulate the global
+        // tile data; `room_index` identifies the room being stamped into, already established by
+        // the room-generation pass that carved it.
+        let room_index = unsafe { (&*ffi::GetTileSafe(start_x, start_y)).room };
+
+        for template_y in 0..template.height() {
+            for template_x in 0..template.width() {
+                let cell = template.get(template_x, template_y);
+                if cell == RoomTemplateCell::Transparent {
+                    continue;
+                }
+                let tile = unsafe {
+                    &mut *ffi::GetTileSafe(start_x + template_x as i32, start_y + template_y as i32)
+                };
+                match cell {
+                    RoomTemplateCell::Wall => tile.set_terrain_obstacle_checked(false, room_index),
+                    RoomTemplateCell::Secondary => {
+                        tile.set_terrain_obstacle_checked(true, room_index)
+                    }
+                    RoomTemplateCell::Open => tile.init(),
+                    RoomTemplateCell::Transparent => unreachable!(),
+                }
+            }
+        }
+
+        for &(template_x, template_y) in &template.traps {
+            let position = ffi::position {
+                x: start_x + template_x as i32,
+                y: start_y + template_y as i32,
+            };
+            // SAFETY: the caller of `stamp_template` guarantees it's safe to spawn entities on
+            // the current floor.
+            unsafe { ffi::SpawnTrap(template.trap_id, force_mut_ptr!(position), 0, room_index) };
+        }
+
+        let to_floor_coords = |(template_x, template_y): &(usize, usize)| {
+            (start_x + *template_x as i32, start_y + *template_y as i32)
+        };
+        RoomTemplateSpawns {
+            items: template.items.iter().map(to_floor_coords).collect(),
+            stairs: template.stairs.iter().map(to_floor_coords).collect(),
+            monsters: template.monsters.iter().map(to_floor_coords).collect(),
+        }
+    }
+}
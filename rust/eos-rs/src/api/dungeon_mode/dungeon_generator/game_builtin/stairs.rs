@@ -0,0 +1,198 @@
+//! Wall-anchored stair placement, modeled on Hengband's `alloc_stairs_aux`.
+//!
+//! [`GlobalDungeonStructureGenerator::spawn_stairs`] takes an explicit `(x, y)` that the caller
+//! must already know is valid, and the builtin spawner (`spawn_non_enemies`) scatters stairs
+//! anywhere in open room interiors.
+//! [`GlobalDungeonStructureGenerator::find_wall_anchored_stair_position`] instead looks for tiles
+//! tucked against walls/alcoves, which is useful for hand-tuned difficulty.
+//!
+//! [`DungeonGridMutator::place_stairs`] is a third option, for custom floors that want more than
+//! one staircase: the game itself only ever tracks a single down staircase (plus, optionally,
+//! hidden stairs), so placing several up/down staircases and telling them apart is entirely a
+//! convention of the calling mod, not something the game engine models.
+
+use alloc::vec::Vec;
+
+use rand_core::RngCore;
+
+use crate::api::dungeon_mode::{DungeonTileExt, RegionTag, TerrainType};
+use crate::api::random::rand_i32;
+use crate::ffi;
+
+use super::{DungeonGridMutator, GlobalDungeonStructureGenerator, FLOOR_HEIGHT, FLOOR_WIDTH};
+
+impl<'a> GlobalDungeonStructureGenerator<'a> {
+    /// Counts the orthogonally adjacent wall tiles around `(x, y)`.
+    fn adjacent_wall_count(&self, x: i32, y: i32) -> u8 {
+        [(0, -1), (0, 1), (-1, 0), (1, 0)]
+            .into_iter()
+            .filter(|&(dx, dy)| self.1.get_tile(x + dx, y + dy).get_terrain() == Some(TerrainType::Wall))
+            .count() as u8
+    }
+
+    /// Looks for an open, in-room tile with at least `min_adjacent_walls` orthogonally adjacent
+    /// wall tiles, that isn't next to a hallway junction, among every tile on the floor, and
+    /// returns one chosen uniformly at random among the matches via reservoir sampling (so the
+    /// full candidate list never needs to be materialized).
+    ///
+    /// `gen_info` is accepted for API symmetry with
+    /// [`Self::spawn_stairs`](GlobalDungeonStructureGenerator::spawn_stairs), which a caller will
+    /// typically use to actually spawn the stairs at the returned position; it isn't used to
+    /// score candidates here.
+    ///
+    /// # Note
+    /// This doesn't exclude other kinds of special tiles (e.g. Kecleon shops), because this crate
+    /// doesn't currently expose those tile flags safely.
+    ///
+    /// Returns `None` if no tile satisfies the constraints.
+    pub fn find_wall_anchored_stair_position(
+        &self,
+        min_adjacent_walls: u8,
+        _gen_info: &ffi::dungeon_generation_info,
+    ) -> Option<(u8, u8)> {
+        let mut chosen = None;
+        let mut seen = 0i32;
+
+        for y in 1..FLOOR_HEIGHT - 1 {
+            for x in 1..FLOOR_WIDTH - 1 {
+                let tile = self.1.get_tile(x, y);
+                if tile.get_terrain() != Some(TerrainType::Normal) {
+                    continue;
+                }
+                if !matches!(RegionTag::from_room_value(tile.room), RegionTag::Room(_)) {
+                    continue;
+                }
+                if self.adjacent_wall_count(x, y) < min_adjacent_walls {
+                    continue;
+                }
+                if self.is_next_to_hallway(x, y) {
+                    continue;
+                }
+
+                seen += 1;
+                if rand_i32(0..seen) == 0 {
+                    chosen = Some((x as u8, y as u8));
+                }
+            }
+        }
+
+        chosen
+    }
+}
+
+/// Returns a uniformly random value in `0..bound`. `bound` must be nonzero.
+fn rand_below(rng: &mut impl RngCore, bound: usize) -> usize {
+    (rng.next_u32() as usize) % bound
+}
+
+impl DungeonGridMutator {
+    /// Places `n_up` up staircases and `n_down` down staircases in random interior positions of
+    /// valid, connected, non-special rooms (no Monster House, maze or merge involvement; Kecleon
+    /// shops are not checked for -- otherwise the same "no other special features" rule
+    /// [`Self::generate_kecleon_shop`] and [`Self::generate_monster_house`] use), rejecting any
+    /// candidate position within `min_separation` Chebyshev distance of a staircase already
+    /// placed by this call, so the up and down stairs spread out instead of clustering together.
+    ///
+    /// Every placed tile has [`DungeonTileExt::set_is_stairs`] set; the game itself only tracks
+    /// one kind of "this tile has stairs" bit, so up vs. down is only reflected in which of the
+    /// two returned vectors a position ends up in. Wiring an actual up/down warp at each position
+    /// is left to the caller (e.g. via a custom [`ffi::warp_type`] lookup keyed by position).
+    ///
+    /// Stops early, returning fewer stairs than requested, if no more candidate positions satisfy
+    /// `min_separation`.
+    ///
+    /// # Safety
+    /// The caller needs to make sure that overlay 29 is loaded and it's safe to manipulate the
+    /// global dungeon tile data.
+    pub unsafe fn place_stairs(
+        &mut self,
+        n_up: u32,
+        n_down: u32,
+        min_separation: i32,
+        rng: &mut impl RngCore,
+    ) -> (Vec<(i32, i32)>, Vec<(i32, i32)>) {
+        let mut placed: Vec<(i32, i32)> = Vec::new();
+        let mut up_stairs = Vec::new();
+        let mut down_stairs = Vec::new();
+
+        for (count, out) in [(n_up, &mut up_stairs), (n_down, &mut down_stairs)] {
+            for _ in 0..count {
+                let Some(pos) = self.find_stair_placement(min_separation, &placed, rng) else {
+                    break;
+                };
+                // SAFETY: the caller of `place_stairs` guarantees it's safe to manipulate the
+                // global tile data.
+                unsafe { &mut *ffi::GetTileSafe(pos.0, pos.1) }.set_is_stairs(true);
+                placed.push(pos);
+                out.push(pos);
+            }
+        }
+
+        (up_stairs, down_stairs)
+    }
+
+    /// Picks a random open interior tile, among every valid, connected, non-special room, that's
+    /// at least `min_separation` Chebyshev distance from every position in `placed`. Rooms are
+    /// visited in random order and abandoned after a handful of failed attempts, so this doesn't
+    /// degrade to scanning every tile on the floor when most of the floor is already too close to
+    /// a placed staircase.
+    fn find_stair_placement(
+        &self,
+        min_separation: i32,
+        placed: &[(i32, i32)],
+        rng: &mut impl RngCore,
+    ) -> Option<(i32, i32)> {
+        let mut rooms = Vec::new();
+        for grid_y in 0..self.height() {
+            for grid_x in 0..self.width() {
+                let cell = self.get(grid_x, grid_y);
+                let is_plain_room = cell.is_room
+                    && cell.is_connected
+                    && !cell.is_invalid
+                    && !cell.is_monster_house
+                    && !cell.is_maze_room
+                    && !cell.is_merged_room
+                    && !cell.was_merged_into_other_room;
+                if is_plain_room {
+                    rooms.push((cell.start_x, cell.start_y, cell.end_x, cell.end_y));
+                }
+            }
+        }
+
+        // Fisher-Yates shuffle, so rooms are tried in random order without weighting towards
+        // larger rooms the way picking a uniformly random tile up front would.
+        for i in (1..rooms.len()).rev() {
+            rooms.swap(i, rand_below(rng, i + 1));
+        }
+
+        for (start_x, start_y, end_x, end_y) in rooms {
+            let (x1, y1, x2, y2) = (start_x + 1, start_y + 1, end_x - 2, end_y - 2);
+            if x1 > x2 || y1 > y2 {
+                continue;
+            }
+
+            const ATTEMPTS_PER_ROOM: u32 = 8;
+            for _ in 0..ATTEMPTS_PER_ROOM {
+                let (x, y) = (rand_i32(x1..=x2), rand_i32(y1..=y2));
+                let tile = unsafe { &*ffi::GetTileSafe(x, y) };
+                if tile.get_terrain() != Some(TerrainType::Normal) {
+                    continue;
+                }
+                if !matches!(RegionTag::from_room_value(tile.room), RegionTag::Room(_)) {
+                    continue;
+                }
+                if tile.is_stairs() {
+                    continue;
+                }
+                let far_enough = placed
+                    .iter()
+                    .all(|&(px, py)| (x - px).abs().max((y - py).abs()) >= min_separation);
+                if far_enough {
+                    return Some((x, y));
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,90 @@
+//! A themed "trapped room" generator, modeled on Angband's trap-heavy vault room type: a room
+//! interior seeded with traps at a flat per-tile probability, instead of the handful the builtin
+//! spawner scatters across the whole floor.
+//!
+//! Spawns go through [`spawn_trap`], the wrapper over the game's `SpawnTrap` that
+//! [`super::room_template`]'s `^` cells call directly, rather than a raw tile field -- this crate
+//! doesn't expose one.
+
+use rand_core::RngCore;
+
+use crate::api::dungeon_mode::traps::{spawn_trap, TrapId};
+use crate::api::dungeon_mode::{DungeonTileExt, RegionTag, TerrainType};
+use crate::api::overlay::OverlayLoadLease;
+use crate::ffi;
+
+use super::DungeonGridMutator;
+
+impl DungeonGridMutator {
+    /// Fills the interior of the room at grid cell `(x, y)` with traps drawn uniformly from
+    /// `trap_pool`, at a `density` chance (`0.0..=1.0`) per eligible tile, emulating Angband's
+    /// trapped room type.
+    ///
+    /// Respects the room's one-tile wall margin, and skips the room's exact center tile (kept
+    /// clear so the room doesn't become impossible to cross safely) and any tile that isn't plain
+    /// open room floor (not normal terrain, not in a room, or already a stair). Whether a tile
+    /// already holds an item or a trap is not checked here.
+    ///
+    /// Does nothing if `trap_pool` is empty.
+    ///
+    /// # Safety
+    /// The caller needs to make sure that overlay 29 is loaded and it's safe to manipulate the
+    /// global dungeon tile/entity data.
+    pub unsafe fn generate_trapped_room(
+        &mut self,
+        x: usize,
+        y: usize,
+        density: f32,
+        trap_pool: &[TrapId],
+        rng: &mut impl RngCore,
+    ) {
+        if trap_pool.is_empty() {
+            return;
+        }
+
+        let cell = self.get(x, y);
+        let (start_x, start_y, end_x, end_y) = (cell.start_x, cell.start_y, cell.end_x, cell.end_y);
+        let (x1, y1, x2, y2) = (start_x + 1, start_y + 1, end_x - 2, end_y - 2);
+        if x1 > x2 || y1 > y2 {
+            return;
+        }
+        let (center_x, center_y) = ((x1 + x2) / 2, (y1 + y2) / 2);
+
+        // SAFETY: the caller guarantees overlay 29 is loaded.
+        let ov29 = unsafe { OverlayLoadLease::<29>::acquire_unchecked() };
+
+        for tile_y in y1..=y2 {
+            for tile_x in x1..=x2 {
+                if (tile_x, tile_y) == (center_x, center_y) {
+                    continue;
+                }
+                if rand_below_f32(rng) >= density {
+                    continue;
+                }
+
+                // SAFETY: the caller guarantees it's safe to manipulate the global tile data.
+                let tile = unsafe { &*ffi::GetTileSafe(tile_x, tile_y) };
+                let is_plain_floor = tile.get_terrain() == Some(TerrainType::Normal)
+                    && matches!(RegionTag::from_room_value(tile.room), RegionTag::Room(_))
+                    && !tile.is_stairs();
+                if !is_plain_floor {
+                    continue;
+                }
+
+                let trap_id = trap_pool[rand_below(rng, trap_pool.len())];
+                let position = ffi::position { x: tile_x, y: tile_y };
+                let _ = spawn_trap(&ov29, trap_id, &position, 0, tile.room);
+            }
+        }
+    }
+}
+
+/// Returns a uniformly random value in `0..bound`. `bound` must be nonzero.
+fn rand_below(rng: &mut impl RngCore, bound: usize) -> usize {
+    (rng.next_u32() as usize) % bound
+}
+
+/// Returns a uniformly random value in `0.0..1.0`.
+fn rand_below_f32(rng: &mut impl RngCore) -> f32 {
+    (rng.next_u32() as f32) / (u32::MAX as f32 + 1.0)
+}
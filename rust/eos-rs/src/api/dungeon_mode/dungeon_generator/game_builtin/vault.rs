@@ -0,0 +1,316 @@
+//! ASCII prefab/vault templates ("vaults"), parsed at runtime and stamped directly onto the
+//! floor's tiles.
+//!
+//! The only pre-authored layout support the game itself has is
+//! [`super::GlobalDungeonStructureGenerator::generate_fixed_room`], which can only select from the
+//! game's opaque, data-locked fixed room catalog. A [`VaultTemplate`] is a plain ASCII grid
+//! instead, so a hand-designed room can be added without touching the game's fixed-room binary
+//! tables.
+//!
+//! Unlike the grid cells [`super::DungeonGridMutator`] works with, a vault has no grid cell of its
+//! own: it's stamped directly onto whatever tiles already exist at its target position, the same
+//! way [`super::fractal_cave`] paints its height field.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::api::dungeon_mode::{DungeonTileExt, RegionTag, TerrainType};
+use crate::api::random::rand_i32;
+
+use super::{DungeonGridMutator, GlobalDungeonStructureGenerator, FLOOR_HEIGHT, FLOOR_WIDTH};
+
+/// A marker recorded at a cell of a [`VaultTemplate`], for later handoff to
+/// [`super::GlobalDungeonEntityGenerator`] once the vault has been stamped onto the floor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VaultSpawnMarker {
+    /// `^` in the template: a trap should spawn here.
+    Trap,
+    /// `*` in the template: an item should spawn here.
+    Item,
+    /// A digit `0`-`9` in the template: an enemy from group `n` should spawn here.
+    EnemyGroup(u8),
+}
+
+/// A single cell of a [`VaultTemplate`], parsed from one ASCII character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum VaultCell {
+    Wall,
+    Open,
+    Secondary,
+    /// The template leaves whatever terrain is already on the floor untouched here.
+    Transparent,
+}
+
+/// A resolved position for one letter of a [`VaultTemplate`]'s random-placement register, as
+/// returned by [`GlobalDungeonStructureGenerator::stamp_vault`].
+///
+/// Mirrors NetHack's `RANDOM_PLACES`/`RANDOM_OBJECTS`: an uppercase letter in the template marks a
+/// pool of candidate tiles, and exactly one of them is chosen at random when the vault is stamped.
+/// Every consumer that cares about that letter (an item spawn, a trap, a teleport destination,
+/// ...) is expected to use the same resolved position, so they end up coordinated without the
+/// template author having to hardcode a single tile.
+pub type VaultRandomRegisters = BTreeMap<char, (i32, i32)>;
+
+/// A pre-authored room template, parsed from a plain ASCII grid.
+///
+/// # Template syntax
+/// * `#` - wall
+/// * `.` - open floor
+/// * `~` - secondary terrain (water/lava)
+/// * `^` - open floor with a trap spawn marker
+/// * `*` - open floor with an item spawn marker
+/// * `0`-`9` - open floor with an enemy-group spawn marker
+/// * `A`-`Z` - open floor, added as a candidate position of that letter's random-placement
+///   register (see [`VaultRandomRegisters`]); a letter used more than once contributes one
+///   candidate per occurrence, and [`GlobalDungeonStructureGenerator::stamp_vault`] resolves each
+///   letter to exactly one of its candidates
+/// * any other character (a space is conventional) - transparent: leaves the floor's existing
+///   terrain untouched
+pub struct VaultTemplate {
+    width: usize,
+    height: usize,
+    cells: Vec<VaultCell>,
+    spawn_markers: Vec<(usize, usize, VaultSpawnMarker)>,
+    random_registers: BTreeMap<char, Vec<(usize, usize)>>,
+}
+
+impl VaultTemplate {
+    /// Parses a template out of `text`, a sequence of equal-length, non-empty lines (empty lines
+    /// are ignored, so a template can be written with a leading/trailing empty line for
+    /// readability; a line of only spaces is a row of transparent cells, not ignored).
+    ///
+    /// Returns `None` if `text` has no non-empty lines, or if its lines aren't all the same
+    /// length.
+    pub fn parse(text: &str) -> Option<Self> {
+        let lines: Vec<&str> = text.lines().filter(|line| !line.is_empty()).collect();
+        let height = lines.len();
+        if height == 0 {
+            return None;
+        }
+        let width = lines[0].chars().count();
+        if lines.iter().any(|line| line.chars().count() != width) {
+            return None;
+        }
+
+        let mut cells = Vec::with_capacity(width * height);
+        let mut spawn_markers = Vec::new();
+        let mut random_registers: BTreeMap<char, Vec<(usize, usize)>> = BTreeMap::new();
+        for (y, line) in lines.iter().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                let cell = match ch {
+                    '#' => VaultCell::Wall,
+                    '.' => VaultCell::Open,
+                    '~' => VaultCell::Secondary,
+                    '^' => {
+                        spawn_markers.push((x, y, VaultSpawnMarker::Trap));
+                        VaultCell::Open
+                    }
+                    '*' => {
+                        spawn_markers.push((x, y, VaultSpawnMarker::Item));
+                        VaultCell::Open
+                    }
+                    digit if digit.is_ascii_digit() => {
+                        spawn_markers.push((
+                            x,
+                            y,
+                            VaultSpawnMarker::EnemyGroup(digit as u8 - b'0'),
+                        ));
+                        VaultCell::Open
+                    }
+                    letter if letter.is_ascii_uppercase() => {
+                        random_registers.entry(letter).or_default().push((x, y));
+                        VaultCell::Open
+                    }
+                    _ => VaultCell::Transparent,
+                };
+                cells.push(cell);
+            }
+        }
+
+        Some(Self { width, height, cells, spawn_markers, random_registers })
+    }
+
+    /// The template's width in tiles, before any [`VaultTransform`] is applied.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The template's height in tiles, before any [`VaultTransform`] is applied.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, x: usize, y: usize) -> VaultCell {
+        self.cells[y * self.width + x]
+    }
+}
+
+/// One of the 8 symmetries of the square (the dihedral group D4): the 4 rotations, each with an
+/// optional mirror.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum VaultTransform {
+    #[default]
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    FlipMainDiagonal,
+    FlipAntiDiagonal,
+}
+
+impl VaultTransform {
+    /// Remaps a template-local `(x, y)` coordinate to its position after this transform is
+    /// applied to a template of the given `width`/`height`.
+    fn apply(self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Self::Identity => (x, y),
+            Self::Rotate90 => (height - 1 - y, x),
+            Self::Rotate180 => (width - 1 - x, height - 1 - y),
+            Self::Rotate270 => (y, width - 1 - x),
+            Self::FlipHorizontal => (width - 1 - x, y),
+            Self::FlipVertical => (x, height - 1 - y),
+            Self::FlipMainDiagonal => (y, x),
+            Self::FlipAntiDiagonal => (height - 1 - y, width - 1 - x),
+        }
+    }
+
+    /// The `(width, height)` of the footprint a template of the given size occupies once this
+    /// transform has been applied.
+    fn footprint(self, width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Self::Identity | Self::Rotate180 | Self::FlipHorizontal | Self::FlipVertical => {
+                (width, height)
+            }
+            Self::Rotate90 | Self::Rotate270 | Self::FlipMainDiagonal | Self::FlipAntiDiagonal => {
+                (height, width)
+            }
+        }
+    }
+}
+
+/// Why a [`GlobalDungeonStructureGenerator::stamp_vault`] call was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VaultStampError {
+    /// The (transformed) footprint would extend past the floor's impassable outer border.
+    OutOfBounds,
+    /// The footprint overlaps a tile that's already part of a room or a previously stamped
+    /// vault, at a position where the template doesn't leave the existing terrain untouched.
+    Occupied,
+}
+
+impl<'a> GlobalDungeonStructureGenerator<'a> {
+    /// Stamps `template` onto the floor with its top-left corner at `(x, y)`, after applying
+    /// `transform`.
+    ///
+    /// On success, returns the template's spawn markers translated into floor coordinates, for
+    /// the caller to hand off to [`super::GlobalDungeonEntityGenerator`] itself (stamping a vault
+    /// doesn't spawn anything by itself), alongside the resolved position of every random-placement
+    /// register (an uppercase letter in the template; see [`VaultTemplate::parse`]) used by the
+    /// template, each chosen uniformly at random among that letter's candidate positions.
+    ///
+    /// Rejects the placement, leaving the floor untouched, if the (transformed) footprint would
+    /// extend past the floor's impassable outer border ([`VaultStampError::OutOfBounds`]), or
+    /// would overwrite a tile that's already part of a room or a previously stamped vault at a
+    /// position where the template doesn't leave the existing terrain untouched
+    /// ([`VaultStampError::Occupied`]).
+    pub fn stamp_vault(
+        &mut self,
+        template: &VaultTemplate,
+        x: i32,
+        y: i32,
+        transform: VaultTransform,
+    ) -> Result<(Vec<(i32, i32, VaultSpawnMarker)>, VaultRandomRegisters), VaultStampError> {
+        let (footprint_width, footprint_height) =
+            transform.footprint(template.width(), template.height());
+
+        if x < 1
+            || y < 1
+            || x + footprint_width as i32 > FLOOR_WIDTH - 1
+            || y + footprint_height as i32 > FLOOR_HEIGHT - 1
+        {
+            return Err(VaultStampError::OutOfBounds);
+        }
+
+        for template_y in 0..template.height() {
+            for template_x in 0..template.width() {
+                if template.get(template_x, template_y) == VaultCell::Transparent {
+                    continue;
+                }
+                let (fx, fy) =
+                    transform.apply(template_x, template_y, template.width(), template.height());
+                let tile = self.1.get_tile(x + fx as i32, y + fy as i32);
+                let occupied = tile.get_terrain() != Some(TerrainType::Wall)
+                    || matches!(RegionTag::from_room_value(tile.room), RegionTag::Room(_));
+                if occupied {
+                    return Err(VaultStampError::Occupied);
+                }
+            }
+        }
+
+        let mut markers = Vec::with_capacity(template.spawn_markers.len());
+        for template_y in 0..template.height() {
+            for template_x in 0..template.width() {
+                let cell = template.get(template_x, template_y);
+                if cell == VaultCell::Transparent {
+                    continue;
+                }
+                let (fx, fy) =
+                    transform.apply(template_x, template_y, template.width(), template.height());
+                let tile = self.1.get_tile_mut(x + fx as i32, y + fy as i32);
+                match cell {
+                    VaultCell::Wall => tile.set_terrain_obstacle_checked(false, 0),
+                    VaultCell::Secondary => tile.set_terrain_obstacle_checked(true, 0),
+                    VaultCell::Open => tile.init(),
+                    VaultCell::Transparent => unreachable!(),
+                }
+            }
+        }
+        for &(template_x, template_y, marker) in &template.spawn_markers {
+            let (fx, fy) =
+                transform.apply(template_x, template_y, template.width(), template.height());
+            markers.push((x + fx as i32, y + fy as i32, marker));
+        }
+
+        let mut registers = VaultRandomRegisters::new();
+        for (&letter, candidates) in &template.random_registers {
+            let &(template_x, template_y) = &candidates[rand_i32(0..candidates.len() as i32) as usize];
+            let (fx, fy) =
+                transform.apply(template_x, template_y, template.width(), template.height());
+            registers.insert(letter, (x + fx as i32, y + fy as i32));
+        }
+
+        Ok((markers, registers))
+    }
+
+    /// Reserves a grid cell for a vault, so it's carved and populated independently of the stock
+    /// room-generation passes.
+    ///
+    /// Marks the cell (via [`DungeonGridMutator::get_mut`]) as already containing a Monster House
+    /// and a maze, which is how [`DungeonGridMutator::generate_kecleon_shop`],
+    /// [`DungeonGridMutator::generate_monster_house`], and
+    /// [`DungeonGridMutator::generate_maze_room`] each recognize a room as already spoken for by
+    /// another special feature and skip it (see their doc comments).
+    ///
+    /// Returns the cell's tile bounds (`start_x`, `start_y`, `end_x`, `end_y`), for the caller to
+    /// pass to [`Self::stamp_vault`] (typically after insetting by the one tile margin every grid
+    /// cell leaves against its neighbors).
+    ///
+    /// # Note
+    /// This crate doesn't have a verified, dedicated "this cell is reserved" flag to set, so this
+    /// reuses the two flags the stock special-feature passes already treat as mutually exclusive.
+    /// It doesn't mark the cell as a room or touch its connectivity; do that first (e.g. via
+    /// [`DungeonGridMutator::assign_rooms`] and [`DungeonGridMutator::create_rooms_and_anchors`])
+    /// so the vault's cell still gets hallways connected to it.
+    pub fn reserve_grid_cell_for_vault(
+        grid: &mut DungeonGridMutator,
+        x: usize,
+        y: usize,
+    ) -> (i32, i32, i32, i32) {
+        let cell = grid.get_mut(x, y);
+        cell.is_monster_house = true;
+        cell.is_maze_room = true;
+        (cell.start_x, cell.start_y, cell.end_x, cell.end_y)
+    }
+}
@@ -0,0 +1,309 @@
+//! A from-scratch orchestrator over [`game_builtin`]'s individually-safe wrappers.
+//!
+//! [`game_builtin`] already wraps every step of the game's own generation pipeline
+//! (`AssignRooms`, `CreateRoomsAndAnchors`, `AssignGridCellConnections`,
+//! `CreateGridCellConnections`, `GenerateRoomImperfections`, `EnsureConnectedGrid`,
+//! `GenerateSecondaryTerrainFormations`, `SpawnNonEnemies`, `SpawnEnemies`, `SpawnStairs`, ...) as
+//! individual safe methods on [`game_builtin::DungeonGridMutator`] and
+//! [`game_builtin::GlobalDungeonStructureGenerator`]. What's missing is the thing that calls them
+//! in the right order: [`FloorGenerator`] is that single, auditable entry point, mirroring how
+//! the game's own `floor-generate.c` calls each stage in sequence and retries the whole attempt
+//! if the result turns out to be invalid.
+//!
+//! Unlike calling [`super::DungeonFloorGeneration::generate_floor`] on the builtin generator
+//! (which hands the whole pipeline to the game's binary in one opaque call),
+//! [`FloorGenerator`] runs every stage as a separate, visible step, and lets a caller inject a
+//! hook between any two of them - to stamp a [`game_builtin::VaultTemplate`] into a reserved grid
+//! cell right after rooms are created, to force a specific room into a Monster House, or anything
+//! else that needs to run between two particular stages.
+
+use alloc::boxed::Box;
+
+use crate::api::dungeon_mode::DungeonRng;
+use crate::api::overlay::CreatableWithLease;
+use crate::ffi;
+
+use super::game_builtin::{
+    self, BuiltinDungeonLayoutGenerators, DungeonGridMutator, GlobalDungeonEntityGenerator,
+    GlobalDungeonStructureGenerator,
+};
+use super::{DungeonEntityGeneration, DungeonFloorGeneration};
+
+/// Tuning knobs for each stage of [`FloorGenerator::generate`], matching the parameters the
+/// underlying [`game_builtin`] wrappers themselves take.
+#[derive(Clone, Copy, Debug)]
+pub struct FloorGeneratorConfig {
+    /// Passed to [`DungeonGridMutator::assign_rooms`].
+    pub number_rooms: i32,
+    /// Passed to [`DungeonGridMutator::create_rooms_and_anchors`].
+    pub room_flags: u32,
+    /// Grid cell the connection random walk starts from, passed to
+    /// [`DungeonGridMutator::assign_grid_cell_connections`].
+    pub connection_cursor: (i32, i32),
+    /// Passed to [`DungeonGridMutator::create_grid_cell_connections`].
+    pub enable_room_merging: bool,
+    /// Passed to [`DungeonGridMutator::generate_kecleon_shop`].
+    pub kecleon_shop_chance: u8,
+    /// Passed to [`DungeonGridMutator::generate_monster_house`].
+    pub monster_house_chance: u8,
+    /// Passed to [`DungeonGridMutator::generate_maze_room`].
+    pub maze_room_chance: u8,
+    /// Passed to [`GlobalDungeonStructureGenerator::generate_secondary_terrain_formations`].
+    pub secondary_terrain_test_flag: u8,
+    /// Passed to both [`DungeonEntityGeneration::spawn_non_enemies`] and
+    /// [`DungeonEntityGeneration::spawn_enemies`].
+    pub empty_monster_house: bool,
+}
+
+impl Default for FloorGeneratorConfig {
+    fn default() -> Self {
+        Self {
+            number_rooms: -4,
+            room_flags: 0b100,
+            connection_cursor: (0, 0),
+            enable_room_merging: true,
+            kecleon_shop_chance: 0,
+            monster_house_chance: 0,
+            maze_room_chance: 0,
+            secondary_terrain_test_flag: 0,
+            empty_monster_house: false,
+        }
+    }
+}
+
+/// A safe, auditable replacement for the opaque builtin floor-generation pipeline, built out of
+/// [`game_builtin`]'s individual stage wrappers.
+///
+/// Construct with [`Self::new`], optionally inject hooks between stages (see
+/// [`Self::after_rooms_and_anchors`], [`Self::after_connections`],
+/// [`Self::after_special_features`], [`Self::before_enemy_spawns`]), then call [`Self::generate`]
+/// to run the whole pipeline, retrying from scratch if the resulting floor doesn't have its
+/// stairs reachable from everywhere.
+pub struct FloorGenerator<'a> {
+    grid: DungeonGridMutator,
+    structure: GlobalDungeonStructureGenerator<'a>,
+    grid_width: usize,
+    grid_height: usize,
+    config: FloorGeneratorConfig,
+    seed: Option<u32>,
+    after_rooms_and_anchors: Option<Box<dyn FnMut(&mut DungeonGridMutator) + 'a>>,
+    after_connections: Option<Box<dyn FnMut(&mut DungeonGridMutator) + 'a>>,
+    after_special_features: Option<Box<dyn FnMut(&mut GlobalDungeonStructureGenerator<'a>) + 'a>>,
+    before_enemy_spawns: Option<Box<dyn FnMut(&mut GlobalDungeonEntityGenerator) + 'a>>,
+}
+
+impl<'a> FloorGenerator<'a> {
+    /// Builds a generator out of an already-initialized `grid` (see
+    /// [`DungeonGridMutator::new_from_vec`]) of the given dimensions, and the builtin structure
+    /// generator (see
+    /// [`crate::api::dungeon_mode::GlobalDungeonData::get_builtin_dungeon_generator`]) it'll use
+    /// to carve tiles and spawn entities.
+    pub fn new(
+        grid: DungeonGridMutator,
+        structure: GlobalDungeonStructureGenerator<'a>,
+        grid_width: usize,
+        grid_height: usize,
+        config: FloorGeneratorConfig,
+    ) -> Self {
+        Self {
+            grid,
+            structure,
+            grid_width,
+            grid_height,
+            config,
+            seed: None,
+            after_rooms_and_anchors: None,
+            after_connections: None,
+            after_special_features: None,
+            before_enemy_spawns: None,
+        }
+    }
+
+    /// Makes [`Self::generate`] reproducible: `seed` is fed to
+    /// [`DungeonRng::init_dungeon_rng`] right before the first generation attempt, so every
+    /// `AssignRooms`/`CreateRoomsAndAnchors`/.../`SpawnEnemies` roll made by the underlying
+    /// [`game_builtin`] wrappers for the rest of the run reads from the same reseeded sequence.
+    ///
+    /// Every stage this orchestrates is a [`game_builtin`] wrapper around native game code that
+    /// reads the global dungeon PRNG directly -- there's no Rust-side call to substitute a
+    /// separate injectable RNG into, so "seedable" here means reinitializing that same global
+    /// PRNG rather than swapping it out for a different implementation. That's still enough to
+    /// make `(seed, width, height, properties, config)` reproduce an identical floor (including
+    /// retries: see the note on [`Self::generate`]), which is what golden-file tests and
+    /// shareable "daily dungeon" seeds actually need.
+    ///
+    /// Calling [`DungeonFloorGeneration::generate_floor`]/[`DungeonFloorGeneration::generate_layout`]
+    /// directly on [`game_builtin::GlobalDungeonStructureGenerator`] instead of going through
+    /// [`FloorGenerator`] ignores this entirely, since nothing seeds the PRNG on that path either.
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Runs `hook` right after rooms and hallway anchors are created, before any grid cell
+    /// connections are drawn. The usual place to reserve a grid cell for a vault (see
+    /// [`game_builtin::GlobalDungeonStructureGenerator::stamp_vault`] and
+    /// [`game_builtin::GlobalDungeonStructureGenerator::reserve_grid_cell_for_vault`]) or to force
+    /// a specific room into a Monster House/maze room by setting its flags directly.
+    pub fn after_rooms_and_anchors(
+        mut self,
+        hook: impl FnMut(&mut DungeonGridMutator) + 'a,
+    ) -> Self {
+        self.after_rooms_and_anchors = Some(Box::new(hook));
+        self
+    }
+
+    /// Runs `hook` right after grid cell connections (hallways and room merges) are drawn, before
+    /// room imperfections, secondary structures, and the Kecleon shop/Monster House/maze room
+    /// passes.
+    pub fn after_connections(mut self, hook: impl FnMut(&mut DungeonGridMutator) + 'a) -> Self {
+        self.after_connections = Some(Box::new(hook));
+        self
+    }
+
+    /// Runs `hook` right after every special feature (imperfections, secondary structures,
+    /// Kecleon shop, Monster House, maze room) has been generated and the grid has been made
+    /// fully connected, before invalid spawns are resolved and stairs/items/traps/enemies are
+    /// placed. Useful for stamping a vault's fixed terrain directly onto tiles, now that the
+    /// surrounding floor is otherwise finished.
+    pub fn after_special_features(
+        mut self,
+        hook: impl FnMut(&mut GlobalDungeonStructureGenerator<'a>) + 'a,
+    ) -> Self {
+        self.after_special_features = Some(Box::new(hook));
+        self
+    }
+
+    /// Runs `hook` after non-enemy entities (stairs, items, traps, the player) are spawned, but
+    /// before enemies are. Useful for a themed monster nest/pit (see
+    /// [`game_builtin::NestTheme`]) that should see the floor's final item/trap layout first.
+    pub fn before_enemy_spawns(
+        mut self,
+        hook: impl FnMut(&mut GlobalDungeonEntityGenerator) + 'a,
+    ) -> Self {
+        self.before_enemy_spawns = Some(Box::new(hook));
+        self
+    }
+
+    /// Runs one full generation attempt: grid init through entity spawning, with every
+    /// registered hook invoked at its stage.
+    fn generate_once(&mut self, properties: &ffi::floor_properties) {
+        let lease = self.structure.0.clone();
+        // SAFETY: `self.grid` was built for `self.grid_width`x`self.grid_height`.
+        let (mut starts_x, mut starts_y) = unsafe {
+            DungeonGridMutator::get_grid_positions(
+                self.grid_width as i32,
+                self.grid_height as i32,
+                &lease,
+            )
+        };
+
+        // SAFETY: `self.grid`'s dimensions match `starts_x`/`starts_y`, and the cursor/room
+        // count come from `self.config`, which the caller is responsible for keeping valid for
+        // this grid's size.
+        unsafe {
+            self.grid.assign_rooms(self.config.number_rooms);
+            self.grid
+                .create_rooms_and_anchors(&mut starts_x, &mut starts_y, self.config.room_flags);
+        }
+
+        if let Some(hook) = &mut self.after_rooms_and_anchors {
+            hook(&mut self.grid);
+        }
+
+        // SAFETY: see above.
+        unsafe {
+            let (cursor_x, cursor_y) = self.config.connection_cursor;
+            self.grid
+                .assign_grid_cell_connections(cursor_x, cursor_y, properties);
+            self.grid.create_grid_cell_connections(
+                &mut starts_x,
+                &mut starts_y,
+                self.config.enable_room_merging,
+            );
+        }
+
+        if let Some(hook) = &mut self.after_connections {
+            hook(&mut self.grid);
+        }
+
+        // SAFETY: see above.
+        unsafe {
+            self.grid.generate_room_imperfections();
+            self.grid.generate_secondary_structures(self.config.number_rooms);
+            self.grid.generate_kecleon_shop(self.config.kecleon_shop_chance);
+            self.grid.generate_monster_house(self.config.monster_house_chance);
+            self.grid.generate_maze_room(self.config.maze_room_chance);
+            self.grid.ensure_connected_grid(&mut starts_x, &mut starts_y);
+        }
+
+        self.structure.generate_secondary_terrain_formations(
+            self.config.secondary_terrain_test_flag,
+            properties,
+        );
+
+        if let Some(hook) = &mut self.after_special_features {
+            hook(&mut self.structure);
+        }
+
+        self.structure.resolve_invalid_spawns();
+
+        let empty_monster_house = self.config.empty_monster_house;
+        let before_enemy_spawns = &mut self.before_enemy_spawns;
+        self.structure.entities(|gen| {
+            gen.spawn_non_enemies(properties, empty_monster_house);
+            if let Some(hook) = before_enemy_spawns {
+                hook(gen);
+            }
+            gen.spawn_enemies(properties, empty_monster_house);
+        });
+    }
+
+    /// Runs the full generation pipeline, mirroring how the game's own `floor-generate.c` loops
+    /// until it gets a valid level: up to `max_attempts` times, resets the floor and runs
+    /// [`Self::generate_once`], then checks that down stairs were placed and are reachable from
+    /// every walkable tile (an attempt with no down stairs counts as failed), via the same
+    /// `StairsAlwaysReachable` pass
+    /// [`GlobalDungeonStructureGenerator::stairs_are_always_reachable`] wraps.
+    ///
+    /// Returns `true` as soon as an attempt produces a floor with reachable stairs. If every
+    /// attempt fails, falls back to [`BuiltinDungeonLayoutGenerators::OneRoomMonsterHouse`] (the
+    /// same fallback the builtin generator itself uses) and returns `false`.
+    ///
+    /// # Note
+    /// Unlike the game's own retry loop, which rebuilds the grid cell array from scratch on every
+    /// attempt, each retry here reuses and re-stamps the same [`DungeonGridMutator`]; a caller
+    /// that wants a fully independent grid per attempt should call [`Self::new`] again instead of
+    /// relying on `max_attempts` to do it.
+    ///
+    /// If [`Self::with_seed`] was called, the dungeon PRNG is reseeded exactly once, before the
+    /// first attempt -- not before every retry -- so that a failed attempt's rolls aren't replayed
+    /// identically on the next one; the whole sequence of attempts is what's reproducible from the
+    /// seed, not any single attempt in isolation.
+    pub fn generate(mut self, properties: &ffi::floor_properties, max_attempts: u32) -> bool {
+        if let Some(seed) = self.seed {
+            DungeonRng::new(self.structure.0.clone()).init_dungeon_rng(seed);
+        }
+        for _ in 0..max_attempts.max(1) {
+            self.structure.reset_floor();
+            self.generate_once(properties);
+
+            let stairs = self.structure.1.find_stairs();
+            if let Some((x, y)) = stairs.down_stairs {
+                if self.structure.stairs_are_always_reachable(x, y, false) {
+                    return true;
+                }
+            }
+        }
+
+        self.structure.reset_floor();
+        let mut fallback = BuiltinDungeonLayoutGenerators::OneRoomMonsterHouse;
+        self.structure.generate_layout(&mut fallback, properties);
+        let empty_monster_house = self.config.empty_monster_house;
+        self.structure.entities(|gen| {
+            gen.spawn_non_enemies(properties, empty_monster_house);
+            gen.spawn_enemies(properties, empty_monster_house);
+        });
+        false
+    }
+}
@@ -0,0 +1,256 @@
+//! A structured, timestamped history of dungeon turn-loop events (inspired by a battle log/
+//! history holder), recorded by the `_with_history` wrapper functions in this module around the
+//! plain [`GlobalDungeonData`] methods they wrap: spawns, faints, weather/ability activations,
+//! wind-counter ticks, and floor-over checks.
+//!
+//! [`DungeonHistory`] is caller-owned bookkeeping, the same shape as
+//! [`crate::api::dungeon_mode::announcements::AnnouncementLog`] -- `GlobalDungeonData` only wraps
+//! the native `ffi::dungeon` struct, with no spare field to stash a history in, so there's no
+//! `Dungeon::history()` accessor; a caller creates one and threads it through the `_with_history`
+//! wrappers alongside their own dungeon reference. It's backed by a [`VecDeque`] ring buffer
+//! capped at a caller-chosen size (the oldest record is dropped once the cap is reached), so a mod
+//! can dump the last N turns for debugging or compare two runs for deterministic-replay parity.
+//! Recording is gated on [`DungeonHistory::set_enabled`] -- disabled is a single `bool` check per
+//! `_with_history` call, with the wrapper still forwarding straight to the native method either
+//! way, so toggling it off costs nothing beyond that check.
+//!
+//! There's no wall clock available on this hardware, so "timestamped" here means a monotonically
+//! increasing sequence number ([`DungeonHistoryRecord::seq`]) assigned at record time, not a real
+//! time.
+//!
+//! `serde` support is opt-in via the `serde` feature (new to this crate, gated the same way
+//! `rune`/`eu` are), deriving `Serialize`/`Deserialize` on [`DungeonHistoryRecord`] and
+//! [`DungeonHistoryEvent`] so a mod can write a dump out through whatever transport it likes.
+//!
+//! # Note
+//! [`GlobalDungeonData::try_spawn_monster_and_tick_spawn_counter`]'s own doc comment mentions a
+//! special Illuminate spawn path taken once the spawn counter passes 900, but that branch happens
+//! entirely inside the single opaque `ffi::TrySpawnMonsterAndTickSpawnCounter` call -- there's no
+//! Rust-visible way to tell which path fired, so [`DungeonHistoryEvent::SpawnCounterTicked`]
+//! covers both.
+
+use crate::api::dungeon_mode::entity::DungeonEntity;
+use crate::api::dungeon_mode::GlobalDungeonData;
+use crate::api::monsters::MonsterSpeciesId;
+use crate::ffi;
+use alloc::collections::VecDeque;
+
+/// A single structured event captured by [`DungeonHistory::record`]. See the
+/// [module-level docs](self).
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DungeonHistoryEvent {
+    /// A monster was spawned via [`GlobalDungeonData::spawn_monster`], at the position it ended
+    /// up on (resolved via [`GlobalDungeonData::iter_monsters`] after the native call).
+    MonsterSpawned {
+        species: MonsterSpeciesId,
+        x: u8,
+        y: u8,
+        forced_awake: bool,
+    },
+    /// [`GlobalDungeonData::try_spawn_monster_and_tick_spawn_counter`] ran (see the
+    /// [module-level note](self) on why the Illuminate branch isn't distinguished).
+    SpawnCounterTicked,
+    /// [`GlobalDungeonData::get_kecleon_id_to_spawn_by_floor`] was consulted, on the given floor.
+    KecleonSpawnChosen { species: MonsterSpeciesId, floor: u8 },
+    /// [`GlobalDungeonData::faint_check`] ran (`eu` feature only, matching that method).
+    FaintChecked { non_team_member_fainted: bool },
+    /// [`GlobalDungeonData::try_activate_slow_start`] ran.
+    SlowStartChecked,
+    /// [`GlobalDungeonData::try_activate_artificial_weather_abilities`] ran.
+    ArtificialWeatherAbilitiesChecked,
+    /// [`GlobalDungeonData::decrement_wind_counter`] ran.
+    WindCounterDecremented,
+    /// [`GlobalDungeonData::is_floor_over`] was checked, with its result.
+    FloorOverChecked { floor_over: bool },
+}
+
+/// One [`DungeonHistoryEvent`], stamped with the sequence number it was recorded at. See the
+/// [module-level docs](self).
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DungeonHistoryRecord {
+    pub seq: u32,
+    pub event: DungeonHistoryEvent,
+}
+
+/// A capped, toggleable ring buffer of [`DungeonHistoryRecord`]s. See the
+/// [module-level docs](self).
+pub struct DungeonHistory {
+    enabled: bool,
+    cap: usize,
+    records: VecDeque<DungeonHistoryRecord>,
+    next_seq: u32,
+}
+
+impl DungeonHistory {
+    /// Creates an empty, enabled history holding at most `cap` records (at least `1`).
+    pub fn new(cap: usize) -> Self {
+        Self {
+            enabled: true,
+            cap: cap.max(1),
+            records: VecDeque::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Enables or disables recording. While disabled, [`Self::record`] (and so every
+    /// `_with_history` wrapper in this module) does nothing beyond checking this flag; already
+    /// recorded history is left untouched.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Whether recording is currently enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// The recorded history, oldest first.
+    pub fn records(&self) -> impl Iterator<Item = &DungeonHistoryRecord> {
+        self.records.iter()
+    }
+
+    /// How many records are currently held (at most the cap passed to [`Self::new`]).
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether no records are currently held.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Discards every recorded event without affecting the sequence counter or enabled state.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// Appends `event` with the next sequence number, evicting the oldest record first if already
+    /// at the cap. Does nothing while [`Self::is_enabled`] is `false`.
+    pub fn record(&mut self, event: DungeonHistoryEvent) {
+        if !self.enabled {
+            return;
+        }
+        if self.records.len() >= self.cap {
+            self.records.pop_front();
+        }
+        let seq = self.next_seq;
+        self.next_seq = self.next_seq.wrapping_add(1);
+        self.records.push_back(DungeonHistoryRecord { seq, event });
+    }
+}
+
+impl Default for DungeonHistory {
+    /// Creates an empty, enabled history capped at 256 records.
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+/// Like [`GlobalDungeonData::spawn_monster`], additionally recording a
+/// [`DungeonHistoryEvent::MonsterSpawned`] into `history` on success.
+pub fn spawn_monster_with_history<'d>(
+    dungeon: &'d mut GlobalDungeonData,
+    history: &mut DungeonHistory,
+    spawn_data: &mut ffi::spawned_monster_data,
+    force_awake: bool,
+) -> Option<&'d mut DungeonEntity> {
+    let ptr = dungeon
+        .spawn_monster(spawn_data, force_awake)
+        .map(|entity| entity as *mut DungeonEntity);
+    if let Some(ptr) = ptr.filter(|_| history.is_enabled()) {
+        let species = unsafe { (*ptr).info_for_monster() }.map(|monster| monster.monster().apparent_id);
+        let position = dungeon
+            .inner()
+            .iter_monsters()
+            .into_iter()
+            .find(|(_, _, entity)| core::ptr::eq(*entity as *const DungeonEntity, ptr as *const DungeonEntity))
+            .map(|(x, y, _)| (x, y));
+        if let (Some(species), Some((x, y))) = (species, position) {
+            history.record(DungeonHistoryEvent::MonsterSpawned {
+                species,
+                x,
+                y,
+                forced_awake: force_awake,
+            });
+        }
+    }
+    ptr.map(|p| unsafe { &mut *p })
+}
+
+/// Like [`GlobalDungeonData::try_spawn_monster_and_tick_spawn_counter`], additionally recording a
+/// [`DungeonHistoryEvent::SpawnCounterTicked`] into `history`.
+pub fn try_spawn_monster_and_tick_spawn_counter_with_history(
+    dungeon: &mut GlobalDungeonData,
+    history: &mut DungeonHistory,
+) {
+    dungeon.try_spawn_monster_and_tick_spawn_counter();
+    history.record(DungeonHistoryEvent::SpawnCounterTicked);
+}
+
+/// Like [`GlobalDungeonData::get_kecleon_id_to_spawn_by_floor`], additionally recording a
+/// [`DungeonHistoryEvent::KecleonSpawnChosen`] into `history` (floor number via
+/// [`crate::api::dungeon_mode::Dungeon::floor`]).
+pub fn get_kecleon_id_to_spawn_by_floor_with_history(
+    dungeon: &GlobalDungeonData,
+    history: &mut DungeonHistory,
+) -> MonsterSpeciesId {
+    let species = dungeon.get_kecleon_id_to_spawn_by_floor();
+    history.record(DungeonHistoryEvent::KecleonSpawnChosen {
+        species,
+        floor: dungeon.inner().floor(),
+    });
+    species
+}
+
+/// Like [`GlobalDungeonData::faint_check`], additionally recording a
+/// [`DungeonHistoryEvent::FaintChecked`] into `history`. Only compiled in with the `eu` feature,
+/// matching [`GlobalDungeonData::faint_check`] itself.
+#[cfg_attr(docsrs, doc(cfg(feature = "eu")))]
+#[cfg(feature = "eu")]
+pub fn faint_check_with_history(
+    dungeon: &mut GlobalDungeonData,
+    history: &mut DungeonHistory,
+    non_team_member_fainted: bool,
+    set_unk_byte: bool,
+) {
+    dungeon.faint_check(non_team_member_fainted, set_unk_byte);
+    history.record(DungeonHistoryEvent::FaintChecked {
+        non_team_member_fainted,
+    });
+}
+
+/// Like [`GlobalDungeonData::try_activate_slow_start`], additionally recording a
+/// [`DungeonHistoryEvent::SlowStartChecked`] into `history`.
+pub fn try_activate_slow_start_with_history(dungeon: &mut GlobalDungeonData, history: &mut DungeonHistory) {
+    dungeon.try_activate_slow_start();
+    history.record(DungeonHistoryEvent::SlowStartChecked);
+}
+
+/// Like [`GlobalDungeonData::try_activate_artificial_weather_abilities`], additionally recording
+/// a [`DungeonHistoryEvent::ArtificialWeatherAbilitiesChecked`] into `history`.
+pub fn try_activate_artificial_weather_abilities_with_history(
+    dungeon: &mut GlobalDungeonData,
+    history: &mut DungeonHistory,
+) {
+    dungeon.try_activate_artificial_weather_abilities();
+    history.record(DungeonHistoryEvent::ArtificialWeatherAbilitiesChecked);
+}
+
+/// Like [`GlobalDungeonData::decrement_wind_counter`], additionally recording a
+/// [`DungeonHistoryEvent::WindCounterDecremented`] into `history`.
+pub fn decrement_wind_counter_with_history(dungeon: &GlobalDungeonData, history: &mut DungeonHistory) {
+    dungeon.decrement_wind_counter();
+    history.record(DungeonHistoryEvent::WindCounterDecremented);
+}
+
+/// Like [`GlobalDungeonData::is_floor_over`], additionally recording a
+/// [`DungeonHistoryEvent::FloorOverChecked`] into `history`.
+pub fn is_floor_over_with_history(dungeon: &GlobalDungeonData, history: &mut DungeonHistory) -> bool {
+    let floor_over = dungeon.is_floor_over();
+    history.record(DungeonHistoryEvent::FloorOverChecked { floor_over });
+    floor_over
+}
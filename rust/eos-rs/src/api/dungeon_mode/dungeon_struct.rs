@@ -1,9 +1,13 @@
+use crate::api::dungeon_mode::dungeon_generator::DungeonFloorGeneration;
 use crate::api::dungeon_mode::*;
 use crate::api::dungeons::{DungeonGroupId, DungeonId};
+use crate::api::enums::DungeonEntityType;
 use crate::api::iq::IqSkillId;
 use crate::api::items::ItemId;
 use crate::api::monsters::MonsterSpeciesId;
+use crate::api::objects::DungeonTile;
 use crate::api::overlay::OverlayLoadLease;
+use crate::api::random::rand_i32;
 use crate::api::types::MonsterTypeId;
 use crate::ffi;
 use alloc::vec::Vec;
@@ -348,6 +352,36 @@ impl<T: AsRef<ffi::dungeon> + AsMut<ffi::dungeon>> Dungeon<T> {
         self.0.as_mut().weather_damage_counter = counter
     }
 
+    /// Pushes a temporary weather condition (e.g. from a weather-setting move) for the given
+    /// number of turns, by writing into [`Self::get_weather_turns_mut`].
+    ///
+    /// This does not touch [`Self::get_artificial_permaweather_turns`] (the counters used by
+    /// weather-setting abilities like Drizzle), so an active permaweather ability will still
+    /// take priority over this, same as it would over a weather move.
+    ///
+    /// # Panics
+    /// Panics if `weather` is [`Weather::Random`], which has no turn counter.
+    pub fn push_temporary_weather(&mut self, weather: Weather, turns: u16) {
+        assert!(
+            weather != Weather::Random,
+            "Weather::Random has no turn counter"
+        );
+        self.get_weather_turns_mut()[weather as usize] = turns;
+    }
+
+    /// Returns the number of turns remaining for the given temporary weather condition, as
+    /// tracked by [`Self::get_weather_turns`].
+    ///
+    /// # Panics
+    /// Panics if `weather` is [`Weather::Random`], which has no turn counter.
+    pub fn temporary_weather_turns_remaining(&self, weather: Weather) -> u16 {
+        assert!(
+            weather != Weather::Random,
+            "Weather::Random has no turn counter"
+        );
+        self.get_weather_turns()[weather as usize]
+    }
+
     /// Number of turns left for the Mud Sport condition.
     pub fn get_mud_sport_turns(&self) -> u8 {
         self.0.as_ref().mud_sport_turns
@@ -474,6 +508,114 @@ impl<T: AsRef<ffi::dungeon> + AsMut<ffi::dungeon>> Dungeon<T> {
         DungeonTileGridMut(&mut self.0.as_mut().tile_ptrs)
     }
 
+    /// Returns the monster entity standing at `(x, y)`, or `None` if the position is out of
+    /// bounds, the tile there has no monster on it, or the tile's monster pointer doesn't
+    /// actually resolve back to an entry in [`EntityTableRead::get_active_monsters`].
+    ///
+    /// Following Crawl's migration away from the raw `mgrd` array towards a `monster_at()`
+    /// accessor, this doesn't just trust [`ffi::tile::monster`]: it's used as a fast lookup into
+    /// the authoritative entity list, and the match is discarded unless that entity's own tile
+    /// (see [`DungeonEntity::get_tile`]) is the same tile, so a stale or desynced grid pointer
+    /// can never be handed out as if it were current.
+    ///
+    /// The returned borrow is invalidated by any call that moves or despawns entities on this
+    /// floor; don't hold onto it across such a call.
+    pub fn monster_at(&self, x: i32, y: i32) -> Option<&DungeonEntity> {
+        if self.is_pos_out_of_bounds(x, y) {
+            return None;
+        }
+        let tile = self.get_tiles().get(x as usize, y as usize)?;
+        let monster_ptr = tile.monster;
+        if monster_ptr.is_null() {
+            return None;
+        }
+        let tile_ptr = tile as *const DungeonTile;
+        self.get_entities().active_monsters_iter().find(|entity| {
+            core::ptr::eq(*entity as *const DungeonEntity, monster_ptr as *const DungeonEntity)
+                && entity
+                    .get_tile()
+                    .is_some_and(|t| core::ptr::eq(t as *const DungeonTile, tile_ptr))
+        })
+    }
+
+    /// Like [`Self::monster_at`], but mutable.
+    pub fn monster_at_mut(&mut self, x: i32, y: i32) -> Option<&mut DungeonEntity> {
+        if self.is_pos_out_of_bounds(x, y) {
+            return None;
+        }
+        let tile = self.get_tiles().get(x as usize, y as usize)?;
+        let monster_ptr = tile.monster;
+        if monster_ptr.is_null() {
+            return None;
+        }
+        let tile_ptr = tile as *const DungeonTile;
+        self.get_entities_mut()
+            .active_monsters_iter_mut()
+            .find(|entity| {
+                core::ptr::eq(*entity as *const DungeonEntity, monster_ptr as *const DungeonEntity)
+                    && entity
+                        .get_tile()
+                        .is_some_and(|t| core::ptr::eq(t as *const DungeonTile, tile_ptr))
+            })
+    }
+
+    /// Returns the monster entity at `(x, y)`, as an iterator with at most one element -- a
+    /// thin, iterator-shaped wrapper around [`Self::monster_at`] so it composes with
+    /// [`Self::entities_within`] and the [`EntityTableRead`] query methods.
+    pub fn entities_on_tile(&self, x: i32, y: i32) -> impl Iterator<Item = &DungeonEntity> {
+        self.monster_at(x, y).into_iter()
+    }
+
+    /// Iterates over every monster within Chebyshev `range` tiles of `(center_x, center_y)`
+    /// (inclusive), without allocating: checks [`Self::monster_at`] -- the same validated
+    /// tile/entity resolution [`Self::iter_monsters`] uses -- for every cell in the bounding box,
+    /// rather than scanning the whole floor.
+    pub fn entities_within(
+        &self,
+        center_x: i32,
+        center_y: i32,
+        range: i32,
+    ) -> impl Iterator<Item = &DungeonEntity> {
+        let range = range.max(0);
+        (center_y - range..=center_y + range)
+            .flat_map(move |y| (center_x - range..=center_x + range).map(move |x| (x, y)))
+            .filter_map(move |(x, y)| self.monster_at(x, y))
+    }
+
+    /// Iterates over every monster currently spawned on the floor, yielding its tile coordinates
+    /// alongside the entity, by walking the tile grid and resolving+validating each occupied
+    /// tile's monster pointer the same way [`Self::monster_at`] does (rather than re-deriving
+    /// coordinates for [`EntityTableRead::get_active_monsters`] some other, potentially
+    /// inconsistent way).
+    ///
+    /// The returned borrows are invalidated by any call that moves or despawns entities on this
+    /// floor; don't hold onto the result across such a call.
+    pub fn iter_monsters(&self) -> Vec<(u8, u8, &DungeonEntity)> {
+        let tiles = self.get_tiles();
+        let monsters = self.get_entities().get_active_monsters();
+        let mut result = Vec::with_capacity(monsters.len());
+        for y in 0..32usize {
+            for x in 0..56usize {
+                let Some(tile) = tiles.get(x, y) else {
+                    continue;
+                };
+                if tile.monster.is_null() {
+                    continue;
+                }
+                let tile_ptr = tile as *const DungeonTile;
+                if let Some(entity) = monsters.iter().find(|entity| {
+                    core::ptr::eq(**entity as *const DungeonEntity, tile.monster as *const DungeonEntity)
+                        && entity
+                            .get_tile()
+                            .is_some_and(|t| core::ptr::eq(t as *const DungeonTile, tile_ptr))
+                }) {
+                    result.push((x as u8, y as u8, *entity));
+                }
+            }
+        }
+        result
+    }
+
     /// Dungeon floor properties.
     pub fn get_floor_properties(&self) -> &ffi::floor_properties {
         &self.0.as_ref().floor_properties
@@ -619,6 +761,48 @@ impl Default for ffi::dungeon {
 /// state of the current floor correctly and use [`Self::generate_floor`].
 pub struct GlobalDungeonData<'a>(&'a OverlayLoadLease<29>, Dungeon<&'a mut ffi::dungeon>);
 
+/// The stair tile coordinates found on a floor by [`GlobalDungeonData::find_stairs`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct StairLocations {
+    /// The coordinates of the (regular) down stairs, if any are on the floor.
+    pub down_stairs: Option<(i32, i32)>,
+    /// The coordinates of the hidden stairs, if any are on the floor.
+    pub hidden_stairs: Option<(i32, i32)>,
+}
+
+/// A captured copy of a dungeon floor's tile grid, entities, items, traps, and
+/// mission-destination state, tagged with the dungeon ID and floor number it was taken from. See
+/// [`GlobalDungeonData::snapshot_floor`] / [`GlobalDungeonData::restore_floor`].
+///
+/// Modeled on Hengband's `floor-save`: a floor's grids, objects and monsters are kept around so
+/// the same floor can be re-entered in the same condition instead of regenerated. This doesn't
+/// serialize across process restarts -- restoring relies on each entity slot still existing at
+/// the same pool address, so it only makes sense within the same still-loaded dungeon session
+/// the snapshot was taken from (persistent floors you can walk back up into, deterministic
+/// replay within a single run, or a test harness resetting between cases).
+#[derive(Clone)]
+pub struct FloorSnapshot {
+    dungeon_id: DungeonId,
+    floor: u8,
+    tiles: Vec<DungeonTile>,
+    monsters: Vec<DungeonEntity>,
+    items: Vec<DungeonEntity>,
+    traps: Vec<DungeonEntity>,
+    mission_destination: ffi::mission_destination_info,
+}
+
+impl FloorSnapshot {
+    /// The dungeon ID this snapshot was captured from.
+    pub fn dungeon_id(&self) -> DungeonId {
+        self.dungeon_id
+    }
+
+    /// The floor number this snapshot was captured from.
+    pub fn floor(&self) -> u8 {
+        self.floor
+    }
+}
+
 impl<'a> GlobalDungeonData<'a> {
     /// Checks if the global dungeon pointer is null.
     pub fn is_global_dungeon_ptr_null(_ov29: &OverlayLoadLease<29>) -> bool {
@@ -704,6 +888,352 @@ impl<'a> GlobalDungeonData<'a> {
         dungeon_generator::game_builtin::GlobalDungeonStructureGenerator(self.0.clone(), self)
     }
 
+    /// Returns a from-scratch floor generator that produces organic cavern layouts instead of
+    /// the game's usual grid-cell rooms. See
+    /// [`dungeon_generator::game_builtin::FractalCaveGenerator`] for details.
+    pub fn get_fractal_cave_generator(
+        &'a mut self,
+    ) -> dungeon_generator::game_builtin::FractalCaveGenerator<'a> {
+        dungeon_generator::game_builtin::FractalCaveGenerator(self.0.clone(), self)
+    }
+
+    /// Returns a from-scratch floor generator that produces organic cavern layouts via cellular
+    /// automata smoothing, seeded by its own deterministic PRNG instead of the game's global RNG
+    /// state. See [`dungeon_generator::game_builtin::CellularAutomataCaveGenerator`] for details.
+    pub fn get_cellular_automata_cave_generator(
+        &'a mut self,
+    ) -> dungeon_generator::game_builtin::CellularAutomataCaveGenerator<'a> {
+        dungeon_generator::game_builtin::CellularAutomataCaveGenerator(self.0.clone(), self)
+    }
+
+    /// Generates a cave floor via cellular automata smoothing: each tile starts out as a wall
+    /// with probability `wall_fill`, then `iterations` smoothing passes turn each tile into a
+    /// wall if at least 5 of its 8 neighbors are walls, and into open floor otherwise. Only the
+    /// largest connected open region is kept; if it ends up too small to be worth playing, this
+    /// falls back to a one-room monster house instead.
+    ///
+    /// `rng_seed` seeds a small PRNG private to this generator, independent of the game's global
+    /// RNG state, so the same seed always reproduces the same floor.
+    ///
+    /// For more granular control (e.g. reusing the generator across several attempts), use
+    /// [`Self::get_cellular_automata_cave_generator`] directly.
+    pub fn generate_cave_floor(&'a mut self, wall_fill: f32, iterations: u32, rng_seed: u32) {
+        let mut params = dungeon_generator::game_builtin::CellularAutomataCaveParams {
+            wall_fill,
+            iterations,
+            rng_seed,
+        };
+        self.get_cellular_automata_cave_generator()
+            .generate_layout(&mut params, &unsafe { core::mem::zeroed() });
+    }
+
+    /// Generates a cave floor via diamond-square midpoint displacement: a height field over the
+    /// largest `2^n + 1` square that fits the usable floor area is seeded at its corners, then
+    /// repeatedly subdivided (see [`dungeon_generator::game_builtin::FractalCaveGenerator`]),
+    /// and thresholded by `density` into open floor or wall.
+    ///
+    /// If `connect_disconnected_regions` is `false`, only the largest connected open region is
+    /// kept (matching [`Self::generate_cave_floor`]'s cellular-automata fallback behavior). If
+    /// `true`, every disconnected pocket is instead stitched to the largest one with an L-shaped
+    /// corridor, falling back to discarding them only if that still leaves some tiles
+    /// unreachable.
+    ///
+    /// For more granular control (e.g. reusing the generator across several attempts), use
+    /// [`Self::get_fractal_cave_generator`] directly.
+    pub fn generate_fractal_cave_floor(
+        &'a mut self,
+        density: f32,
+        roughness: f32,
+        connect_disconnected_regions: bool,
+    ) {
+        let mut params = dungeon_generator::game_builtin::FractalCaveParams {
+            density,
+            roughness,
+            connect_disconnected_regions,
+        };
+        self.get_fractal_cave_generator()
+            .generate_layout(&mut params, &unsafe { core::mem::zeroed() });
+    }
+
+    /// Connects `(x0, y0)` and `(x1, y1)` with a tunnel, modeled on Hengband's tunnel generator
+    /// and [`crate::api::dungeon_mode::dungeon_generator::game_builtin::DungeonGridMutator::generate_extra_hallways`]'s
+    /// door/feature-generator split.
+    ///
+    /// With `drunkenness == 0`, carves a deterministic L-shaped path: first advances along x to
+    /// the target column, then along y to the target row, setting each traversed tile to open
+    /// terrain via [`Self::get_tile_mut`].
+    ///
+    /// With a higher `drunkenness`, at each step where neither axis is aligned with the target
+    /// yet there's a `1 - drunkenness / 255` chance of taking the next step toward the target
+    /// (along x), and a `drunkenness / 255` chance of taking a random perpendicular step instead;
+    /// once one axis is aligned, every step goes straight toward the target along the other.
+    /// Every step is clamped to the floor's usable bounds.
+    ///
+    /// Whenever the path steps off an open (non-wall) tile onto a wall tile - a room/hallway
+    /// boundary - that tile is marked as a junction via [`DungeonTileExt::set_junction_flag`]
+    /// (the game's "door" tile) instead of being opened as plain floor.
+    ///
+    /// Returns every tile coordinate the path touched, in order, starting with `(x0, y0)`, so
+    /// callers can post-process it (e.g. spawning traps or items along it).
+    pub fn carve_corridor(
+        &mut self,
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        drunkenness: u8,
+    ) -> Vec<(i32, i32)> {
+        let mut path = Vec::from([(x0, y0)]);
+        let (mut x, mut y) = (x0, y0);
+
+        while (x, y) != (x1, y1) {
+            let can_step_x = x != x1;
+            let can_step_y = y != y1;
+            let prefer_x = can_step_x;
+            let move_toward_target =
+                !(can_step_x && can_step_y) || rand_i32(0..255) >= drunkenness as i32;
+
+            let (dx, dy) = if move_toward_target {
+                if prefer_x {
+                    ((x1 - x).signum(), 0)
+                } else {
+                    (0, (y1 - y).signum())
+                }
+            } else if prefer_x {
+                (0, if rand_i32(0..2) == 0 { -1 } else { 1 })
+            } else {
+                (if rand_i32(0..2) == 0 { -1 } else { 1 }, 0)
+            };
+
+            // Matches the usable floor area excluding the permanent border; see
+            // [`Self::get_tiles`]'s `56x32` dimensions.
+            let (nx, ny) = ((x + dx).clamp(1, 54), (y + dy).clamp(1, 30));
+            if (nx, ny) == (x, y) {
+                break;
+            }
+
+            let was_open = self.get_tile(x, y).get_terrain() != Some(TerrainType::Wall);
+            let stepping_into_wall = self.get_tile(nx, ny).get_terrain() == Some(TerrainType::Wall);
+            let tile = self.get_tile_mut(nx, ny);
+            if was_open && stepping_into_wall {
+                tile.set_junction_flag(true);
+            } else {
+                tile.init();
+            }
+
+            x = nx;
+            y = ny;
+            path.push((x, y));
+        }
+
+        path
+    }
+
+    /// Finds door tiles - junction-flagged, open tiles - in the inclusive tile rectangle
+    /// `(x0, y0)`-`(x1, y1)`, built on [`DungeonTileExt::get_terrain`] and
+    /// [`DungeonTileExt::get_junction_flag`].
+    pub fn get_door_tiles(&self, x0: i32, y0: i32, x1: i32, y1: i32) -> Vec<(i32, i32)> {
+        let mut doors = Vec::new();
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let tile = self.get_tile(x, y);
+                if tile.get_terrain() == Some(TerrainType::Normal) && tile.get_junction_flag() {
+                    doors.push((x, y));
+                }
+            }
+        }
+        doors
+    }
+
+    /// Converts junction tiles in the inclusive tile rectangle `(x0, y0)`-`(x1, y1)` into doors,
+    /// the piece Hengband keeps factored out into its own `door.c`/`door.h`:
+    /// [`Self::carve_corridor`] and the builtin generator's `flag_hallway_junctions`/
+    /// `finalize_junctions` only ever flag candidate tiles; this is what actually turns a
+    /// flagged tile into a walkable door.
+    ///
+    /// For each tile with the junction flag set that is also adjacent (4-neighborhood) to at
+    /// least one open tile and at least one wall tile - i.e. it genuinely sits on a room/hallway
+    /// boundary rather than carrying a stray flag - there's a `probability` chance of opening it
+    /// into a door: plain, walkable [`TerrainType::Normal`] terrain, left junction-flagged so
+    /// [`Self::get_door_tiles`] can find it again afterwards.
+    ///
+    /// The roll is taken from a small PRNG seeded by `rng_seed`, independent of the game's
+    /// global RNG state, so the same seed always places the same doors.
+    pub fn place_doors_at_junctions(
+        &mut self,
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        probability: f32,
+        rng_seed: u32,
+    ) {
+        let mut rng_state = if rng_seed == 0 { 0x9E3779B9 } else { rng_seed };
+
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                if !self.get_tile(x, y).get_junction_flag() {
+                    continue;
+                }
+
+                let mut has_open = false;
+                let mut has_wall = false;
+                for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+                    match self.get_tile(x + dx, y + dy).get_terrain() {
+                        Some(TerrainType::Wall) => has_wall = true,
+                        Some(_) => has_open = true,
+                        None => {}
+                    }
+                }
+                if !has_open || !has_wall {
+                    continue;
+                }
+
+                if next_unit(&mut rng_state) < probability {
+                    self.get_tile_mut(x, y).set_terrain(TerrainType::Normal);
+                }
+            }
+        }
+    }
+
+    /// Plans a themed monster nest/pit filling the rectangular room `(x0, y0)`-`(x1, y1)`,
+    /// inspired by Hengband's `rooms-pit-nest.c`. A convenience wrapper around
+    /// [`dungeon_generator::game_builtin::GlobalDungeonEntityGenerator::spawn_monster_nest`] and
+    /// [`dungeon_generator::game_builtin::GlobalDungeonEntityGenerator::spawn_monster_pit`] that
+    /// adds theming on top.
+    ///
+    /// `candidates` is every species eligible to be considered, alongside its type (used for
+    /// theming - this crate has no live species-to-type lookup of its own, so the caller
+    /// supplies it) and level (used only when `difficulty_gradient` is set).
+    ///
+    /// If `difficulty_gradient` is `false`, the species `theme` allows are scattered uniformly
+    /// across the room's open tiles (a "nest"). If `true`, they're arranged strongest-to-weakest
+    /// from the room's center outward by Chebyshev distance (a "pit").
+    ///
+    /// Doesn't spawn anything itself, for the same reason neither of the methods it wraps does:
+    /// returns the planned `(x, y, species)` assignments for the caller to hand to
+    /// [`Self::spawn_monster`].
+    pub fn populate_nest(
+        &'a mut self,
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        candidates: &[(MonsterSpeciesId, MonsterTypeId, i32)],
+        theme: dungeon_generator::game_builtin::NestTheme,
+        difficulty_gradient: bool,
+    ) -> Vec<(i32, i32, MonsterSpeciesId)> {
+        let eligible: Vec<_> = candidates
+            .iter()
+            .copied()
+            .filter(|&(_, species_type, _)| theme.allows(species_type))
+            .collect();
+        if eligible.is_empty() {
+            return Vec::new();
+        }
+
+        let bounds = dungeon_generator::game_builtin::RoomBounds {
+            x0,
+            y0,
+            x1: x1 + 1,
+            y1: y1 + 1,
+        };
+
+        let mut placements = Vec::new();
+        self.get_builtin_dungeon_generator().entities(|gen| {
+            placements = if difficulty_gradient {
+                let species_by_strength: Vec<_> =
+                    eligible.iter().map(|&(s, _, level)| (s, level)).collect();
+                gen.spawn_monster_pit(bounds, &species_by_strength)
+            } else {
+                let species: Vec<_> = eligible.iter().map(|&(s, _, _)| s).collect();
+                gen.spawn_monster_nest(bounds, &species)
+            };
+        });
+        placements
+    }
+
+    /// Rolls a themed monster nest/pit into `grid_cell` (see [`Self::populate_nest`]), competing
+    /// with the same percentage chance the stock [`GenerateMonsterHouse`](
+    /// dungeon_generator::game_builtin::DungeonGridMutator::generate_monster_house) roll uses.
+    ///
+    /// `grid_cell` should already be carved into a room (e.g. by
+    /// [`dungeon_generator::game_builtin::DungeonGridMutator::create_rooms_and_anchors`]) and
+    /// should not otherwise be flagged as a Kecleon shop, Monster House, or maze room.
+    ///
+    /// On a successful roll, fills `grid_cell`'s interior (the same one-tile wall margin
+    /// [`dungeon_generator::game_builtin::DungeonGridMutator::carve_room`] leaves) with a nest
+    /// (`is_pit: false`) or pit (`is_pit: true`) chosen from `candidates`, and returns the planned
+    /// `(x, y, species)` assignments for the caller to hand to [`Self::spawn_monster`]. Returns
+    /// `None` if the roll fails or no candidate passes `theme`.
+    pub fn populate_monster_nest(
+        &'a mut self,
+        grid_cell: &ffi::dungeon_grid_cell,
+        candidates: &[(MonsterSpeciesId, MonsterTypeId, i32)],
+        theme: dungeon_generator::game_builtin::NestTheme,
+        is_pit: bool,
+        spawn_chance: u8,
+    ) -> Option<Vec<(i32, i32, MonsterSpeciesId)>> {
+        if !dungeon_generator::game_builtin::roll_nest_chance(spawn_chance) {
+            return None;
+        }
+
+        let placements = self.populate_nest(
+            grid_cell.start_x + 1,
+            grid_cell.start_y + 1,
+            grid_cell.end_x - 2,
+            grid_cell.end_y - 2,
+            candidates,
+            theme,
+            is_pit,
+        );
+
+        if placements.is_empty() {
+            None
+        } else {
+            Some(placements)
+        }
+    }
+
+    /// Scans the floor for down-stairs and hidden stairs, via [`DungeonTileExt::is_stairs`] and
+    /// [`DungeonTileExt::is_hidden_stairs`].
+    ///
+    /// Complements [`dungeon_generator::game_builtin::GlobalDungeonStructureGenerator::spawn_stairs`]
+    /// and [`Self::move_down_stairs`] for scripting custom descent layouts without poking the
+    /// raw dungeon struct.
+    pub fn find_stairs(&self) -> StairLocations {
+        let mut locations = StairLocations {
+            down_stairs: None,
+            hidden_stairs: None,
+        };
+        for y in 1..31 {
+            for x in 1..55 {
+                let tile = self.get_tile(x, y);
+                if !tile.is_stairs() {
+                    continue;
+                }
+                if tile.is_hidden_stairs() {
+                    locations.hidden_stairs = Some((x, y));
+                } else {
+                    locations.down_stairs = Some((x, y));
+                }
+            }
+        }
+        locations
+    }
+
+    /// Relocates the down stairs to `(x, y)`, clearing the old marker (found via
+    /// [`Self::find_stairs`]) and setting the new one, both through [`Self::get_tile_mut`].
+    ///
+    /// Does nothing to the old tile if there are no down stairs on the floor yet; either way,
+    /// `(x, y)` ends up marked as the down stairs.
+    pub fn move_down_stairs(&mut self, x: i32, y: i32) {
+        if let Some((old_x, old_y)) = self.find_stairs().down_stairs {
+            self.get_tile_mut(old_x, old_y).set_is_stairs(false);
+        }
+        self.get_tile_mut(x, y).set_is_stairs(true);
+    }
+
     /// Generates a dungeon floor.
     ///
     /// If not changed by a patch, this function will use the game's default built in generator
@@ -1024,6 +1554,104 @@ impl<'a> GlobalDungeonData<'a> {
         ffi::LoadFixedRoomData()
     }
 
+    /// Captures the current floor's tile grid, monsters, items, traps, and mission-destination
+    /// state into an owned [`FloorSnapshot`], tagged with the current dungeon ID and floor
+    /// number.
+    ///
+    /// See [`Self::restore_floor`] for the corresponding restore, and its caveats.
+    pub fn snapshot_floor(&self) -> FloorSnapshot {
+        let mut tiles = Vec::with_capacity(56 * 32);
+        for y in 0..32i32 {
+            for x in 0..56i32 {
+                tiles.push(self.get_tile(x, y).clone());
+            }
+        }
+        let entities = self.inner().get_entities();
+        FloorSnapshot {
+            dungeon_id: self.inner().id(),
+            floor: self.inner().floor(),
+            tiles,
+            monsters: entities.get_monsters().into_iter().cloned().collect(),
+            items: entities.get_items().into_iter().cloned().collect(),
+            traps: entities.get_traps().into_iter().cloned().collect(),
+            mission_destination: self.inner().get_mission_destination().clone(),
+        }
+    }
+
+    /// Rewrites the current floor's tile grid, monster/item/trap entities, and
+    /// mission-destination state back to `snapshot`, in place. Returns `false` (and changes
+    /// nothing) if `snapshot` wasn't taken from the dungeon ID and floor number currently loaded.
+    ///
+    /// If the number of live monsters/items/traps on the current floor differs from when
+    /// `snapshot` was taken, only the overlapping prefix is restored (in the same order
+    /// [`EntityTableRead::get_monsters`]/`get_items`/`get_traps` enumerate them) -- this crate has
+    /// no safe API to resurrect a despawned slot or retire one newly spawned since the snapshot
+    /// was taken, so a snapshot is best captured and restored without letting entities
+    /// spawn/despawn in between.
+    ///
+    /// # Safety
+    /// Like [`Self::load_fixed_room_data`], this rewrites global dungeon state in place: no
+    /// outstanding borrows into the global dungeon struct (tiles, entities, or otherwise) may
+    /// exist when this is called.
+    pub unsafe fn restore_floor(&mut self, snapshot: &FloorSnapshot) -> bool {
+        if self.inner().id() != snapshot.dungeon_id || self.inner().floor() != snapshot.floor {
+            return false;
+        }
+
+        for y in 0..32i32 {
+            for x in 0..56i32 {
+                let index = y as usize * 56 + x as usize;
+                *self.get_tile_mut(x, y) = snapshot.tiles[index].clone();
+            }
+        }
+
+        let mut entities = self.inner_mut().get_entities_mut();
+        for (entity, saved) in entities
+            .get_monsters_mut()
+            .into_iter()
+            .zip(snapshot.monsters.iter())
+        {
+            *entity = saved.clone();
+        }
+        for (entity, saved) in entities
+            .get_items_mut()
+            .into_iter()
+            .zip(snapshot.items.iter())
+        {
+            *entity = saved.clone();
+        }
+        for (entity, saved) in entities
+            .get_traps_mut()
+            .into_iter()
+            .zip(snapshot.traps.iter())
+        {
+            *entity = saved.clone();
+        }
+
+        *self.get_mission_destination_mut() = snapshot.mission_destination.clone();
+        true
+    }
+
+    /// Restores `snapshot` (see [`Self::restore_floor`]) if it matches the currently loaded
+    /// dungeon ID and floor number, otherwise falls back to [`Self::generate_floor`], preserving
+    /// the existing always-generate behavior for floors with no usable snapshot yet.
+    ///
+    /// Returns whether a snapshot was restored (`true`) or the floor was freshly generated
+    /// (`false`).
+    ///
+    /// # Safety
+    /// See [`Self::restore_floor`]: no outstanding borrows into the global dungeon struct may
+    /// exist when this is called.
+    pub unsafe fn restore_or_generate_floor(&'a mut self, snapshot: Option<&FloorSnapshot>) -> bool {
+        if let Some(snapshot) = snapshot {
+            if self.restore_floor(snapshot) {
+                return true;
+            }
+        }
+        self.generate_floor();
+        false
+    }
+
     /// Sets the forced loss reason to a given value.
     pub fn set_forced_loss_reason(&mut self, reason: ForcedLossReason) {
         // SAFETY: We hold a valid mutable reference to the global dungeon struct.
@@ -1423,36 +2051,180 @@ impl<'a> GlobalDungeonData<'a> {
     }
 }
 
+/// A lazy, allocation-free iterator over a raw `*mut ffi::entity` slot slice, as found in
+/// [`ffi::entity_table_header`]: walks the slice in order and stops at the first null entry,
+/// never yielding past it, without collecting into a [`Vec`] first. See
+/// [`EntityTableRead::monsters_iter`] and friends.
+pub struct EntitySlotIter<'a> {
+    slots: core::slice::Iter<'a, *mut ffi::entity>,
+    done: bool,
+}
+
+impl<'a> EntitySlotIter<'a> {
+    fn new(slots: &'a [*mut ffi::entity]) -> Self {
+        Self {
+            slots: slots.iter(),
+            done: false,
+        }
+    }
+}
+
+impl<'a> Iterator for EntitySlotIter<'a> {
+    type Item = &'a DungeonEntity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.slots.next() {
+            Some(ptr) if !ptr.is_null() => Some(unsafe { &**ptr }),
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+/// Like [`EntitySlotIter`], but yields mutable references. See [`EntityTableWrite::monsters_iter_mut`]
+/// and friends.
+pub struct EntitySlotIterMut<'a> {
+    slots: core::slice::IterMut<'a, *mut ffi::entity>,
+    done: bool,
+}
+
+impl<'a> EntitySlotIterMut<'a> {
+    fn new(slots: &'a mut [*mut ffi::entity]) -> Self {
+        Self {
+            slots: slots.iter_mut(),
+            done: false,
+        }
+    }
+}
+
+impl<'a> Iterator for EntitySlotIterMut<'a> {
+    type Item = &'a mut DungeonEntity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.slots.next() {
+            Some(ptr) if !ptr.is_null() => Some(unsafe { &mut **ptr }),
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
 /// Functions for reading data from an entity table.
 pub trait EntityTableRead {
     /// All monsters, whether they're used or not.
     ///
     /// Null entries are not included, and reading is stopped at them.
     /// Note that some may be invalid, check the validity flag!
-    fn get_monsters(&self) -> Vec<&DungeonEntity>;
+    fn get_monsters(&self) -> Vec<&DungeonEntity> {
+        self.monsters_iter().collect()
+    }
 
     /// Actually used monsters.
     ///
     /// Null entries are not included, and reading is stopped at them.
     /// Note that some may be invalid, check the validity flag!
-    fn get_active_monsters(&self) -> Vec<&DungeonEntity>;
+    fn get_active_monsters(&self) -> Vec<&DungeonEntity> {
+        self.active_monsters_iter().collect()
+    }
 
     /// All items.
     ///
     /// Null entries are not included, and reading is stopped at them.
     /// Note that some may be invalid, check the validity flag!
-    fn get_items(&self) -> Vec<&DungeonEntity>;
+    fn get_items(&self) -> Vec<&DungeonEntity> {
+        self.items_iter().collect()
+    }
 
     /// All traps.
     ///
     /// Null entries are not included, and reading is stopped at them.
     /// Note that some may be invalid, check the validity flag!
-    fn get_traps(&self) -> Vec<&DungeonEntity>;
+    fn get_traps(&self) -> Vec<&DungeonEntity> {
+        self.traps_iter().collect()
+    }
 
     /// Hidden stairs entity.
     ///
     /// Returns None if null. Note that it still may be invalid, check the validity flag!
     fn get_hidden_stairs(&self) -> Option<&DungeonEntity>;
+
+    /// Like [`Self::get_monsters`], but walks the slots lazily instead of collecting them into a
+    /// `Vec` -- prefer this in hot per-turn code.
+    fn monsters_iter(&self) -> EntitySlotIter<'_>;
+
+    /// Like [`Self::get_active_monsters`], but walks the slots lazily instead of collecting them
+    /// into a `Vec` -- prefer this in hot per-turn code.
+    fn active_monsters_iter(&self) -> EntitySlotIter<'_>;
+
+    /// Like [`Self::get_items`], but walks the slots lazily instead of collecting them into a
+    /// `Vec` -- prefer this in hot per-turn code.
+    fn items_iter(&self) -> EntitySlotIter<'_>;
+
+    /// Like [`Self::get_traps`], but walks the slots lazily instead of collecting them into a
+    /// `Vec` -- prefer this in hot per-turn code.
+    fn traps_iter(&self) -> EntitySlotIter<'_>;
+
+    /// Iterates over every monster, item, and trap in the table (not the hidden stairs entity --
+    /// see [`Self::get_hidden_stairs`] for that), in that order, yielding only the ones matching
+    /// `predicate`, without allocating.
+    ///
+    /// This is the composable replacement for one-off functions like
+    /// [`GlobalDungeonData::get_team_member_with_iq_skill`] that only ever find the first match:
+    /// reuse this (or [`Self::monsters_with_iq_skill`]) when every match is needed instead.
+    fn find_entities<F: Fn(&DungeonEntity) -> bool>(
+        &self,
+        predicate: F,
+    ) -> impl Iterator<Item = &DungeonEntity> {
+        self.monsters_iter()
+            .chain(self.items_iter())
+            .chain(self.traps_iter())
+            .filter(predicate)
+    }
+
+    /// Iterates over every active monster with the given IQ skill enabled (see
+    /// [`DungeonMonsterRead::is_iq_skill_enabled`]), without allocating. Unlike
+    /// [`GlobalDungeonData::get_team_member_with_iq_skill`], this isn't limited to the first
+    /// match, and isn't limited to team members.
+    fn monsters_with_iq_skill(&self, iq_skill: IqSkillId) -> impl Iterator<Item = &DungeonEntity> {
+        self.active_monsters_iter().filter(move |entity| {
+            entity
+                .info_for_monster()
+                .is_some_and(|monster| monster.is_iq_skill_enabled(iq_skill))
+        })
+    }
+
+    /// Iterates over every active monster of the given species, without allocating.
+    fn monsters_of_species(&self, species: MonsterSpeciesId) -> impl Iterator<Item = &DungeonEntity> {
+        self.active_monsters_iter().filter(move |entity| {
+            entity
+                .info_for_monster()
+                .is_some_and(|monster| monster.monster().apparent_id == species)
+        })
+    }
+
+    /// Iterates over every valid entity in the table (see [`DungeonEntity::is_valid`]), without
+    /// allocating.
+    fn valid_entities(&self) -> impl Iterator<Item = &DungeonEntity> {
+        self.find_entities(|entity| DungeonEntity::is_valid(force_mut_ptr!(entity)))
+    }
+
+    /// Iterates over every entity in the table of the given [`DungeonEntityType`], without
+    /// allocating. For [`DungeonEntityType::Monster`]/`Item`/`Trap` this is equivalent to (but
+    /// slower than) [`Self::monsters_iter`]/[`Self::items_iter`]/[`Self::traps_iter`], since it
+    /// has to walk and filter all three; prefer those when the type is already known statically.
+    fn entities_of_type(&self, entity_type: DungeonEntityType) -> impl Iterator<Item = &DungeonEntity> {
+        self.find_entities(move |entity| entity.entity_type() == Some(entity_type))
+    }
 }
 
 /// Functions for writing data into an entity table.
@@ -1485,6 +2257,22 @@ pub trait EntityTableWrite: EntityTableRead {
     ///
     /// Returns None if null. Note that it still may be invalid, check the validity flag!
     fn get_hidden_stairs_mut(&mut self) -> Option<&mut DungeonEntity>;
+
+    /// Like [`Self::get_monsters_mut`], but walks the slots lazily instead of collecting them
+    /// into a `Vec` -- prefer this in hot per-turn code.
+    fn monsters_iter_mut(&mut self) -> EntitySlotIterMut<'_>;
+
+    /// Like [`Self::get_active_monsters_mut`], but walks the slots lazily instead of collecting
+    /// them into a `Vec` -- prefer this in hot per-turn code.
+    fn active_monsters_iter_mut(&mut self) -> EntitySlotIterMut<'_>;
+
+    /// Like [`Self::get_items_mut`], but walks the slots lazily instead of collecting them into a
+    /// `Vec` -- prefer this in hot per-turn code.
+    fn items_iter_mut(&mut self) -> EntitySlotIterMut<'_>;
+
+    /// Like [`Self::get_traps_mut`], but walks the slots lazily instead of collecting them into a
+    /// `Vec` -- prefer this in hot per-turn code.
+    fn traps_iter_mut(&mut self) -> EntitySlotIterMut<'_>;
 }
 
 /// See [`EntityTableRead`].
@@ -1493,22 +2281,6 @@ pub struct EntityTableRef<'a>(&'a ffi::entity_table);
 pub struct EntityTableMut<'a>(&'a mut ffi::entity_table);
 
 impl<'a> EntityTableRead for EntityTableRef<'a> {
-    fn get_monsters(&self) -> Vec<&DungeonEntity> {
-        check_and_return(&self.0.header.monster_slot_ptrs)
-    }
-
-    fn get_active_monsters(&self) -> Vec<&DungeonEntity> {
-        check_and_return(&self.0.header.active_monster_ptrs)
-    }
-
-    fn get_items(&self) -> Vec<&DungeonEntity> {
-        check_and_return(&self.0.header.item_ptrs)
-    }
-
-    fn get_traps(&self) -> Vec<&DungeonEntity> {
-        check_and_return(&self.0.header.trap_ptrs)
-    }
-
     fn get_hidden_stairs(&self) -> Option<&DungeonEntity> {
         let ptr = self.0.header.hidden_stairs_ptr;
         if ptr.is_null() {
@@ -1518,25 +2290,25 @@ impl<'a> EntityTableRead for EntityTableRef<'a> {
             Some(unsafe { &*ptr })
         }
     }
-}
 
-impl<'a> EntityTableRead for EntityTableMut<'a> {
-    fn get_monsters(&self) -> Vec<&DungeonEntity> {
-        check_and_return(&self.0.header.monster_slot_ptrs)
+    fn monsters_iter(&self) -> EntitySlotIter<'_> {
+        EntitySlotIter::new(&self.0.header.monster_slot_ptrs)
     }
 
-    fn get_active_monsters(&self) -> Vec<&DungeonEntity> {
-        check_and_return(&self.0.header.active_monster_ptrs)
+    fn active_monsters_iter(&self) -> EntitySlotIter<'_> {
+        EntitySlotIter::new(&self.0.header.active_monster_ptrs)
     }
 
-    fn get_items(&self) -> Vec<&DungeonEntity> {
-        check_and_return(&self.0.header.item_ptrs)
+    fn items_iter(&self) -> EntitySlotIter<'_> {
+        EntitySlotIter::new(&self.0.header.item_ptrs)
     }
 
-    fn get_traps(&self) -> Vec<&DungeonEntity> {
-        check_and_return(&self.0.header.trap_ptrs)
+    fn traps_iter(&self) -> EntitySlotIter<'_> {
+        EntitySlotIter::new(&self.0.header.trap_ptrs)
     }
+}
 
+impl<'a> EntityTableRead for EntityTableMut<'a> {
     fn get_hidden_stairs(&self) -> Option<&DungeonEntity> {
         let ptr = self.0.header.hidden_stairs_ptr;
         if ptr.is_null() {
@@ -1546,23 +2318,39 @@ impl<'a> EntityTableRead for EntityTableMut<'a> {
             Some(unsafe { &*ptr })
         }
     }
+
+    fn monsters_iter(&self) -> EntitySlotIter<'_> {
+        EntitySlotIter::new(&self.0.header.monster_slot_ptrs)
+    }
+
+    fn active_monsters_iter(&self) -> EntitySlotIter<'_> {
+        EntitySlotIter::new(&self.0.header.active_monster_ptrs)
+    }
+
+    fn items_iter(&self) -> EntitySlotIter<'_> {
+        EntitySlotIter::new(&self.0.header.item_ptrs)
+    }
+
+    fn traps_iter(&self) -> EntitySlotIter<'_> {
+        EntitySlotIter::new(&self.0.header.trap_ptrs)
+    }
 }
 
 impl<'a> EntityTableWrite for EntityTableMut<'a> {
     fn get_monsters_mut(&mut self) -> Vec<&mut DungeonEntity> {
-        check_and_return_mut(&mut self.0.header.monster_slot_ptrs)
+        self.monsters_iter_mut().collect()
     }
 
     fn get_active_monsters_mut(&mut self) -> Vec<&mut DungeonEntity> {
-        check_and_return_mut(&mut self.0.header.active_monster_ptrs)
+        self.active_monsters_iter_mut().collect()
     }
 
     fn get_items_mut(&mut self) -> Vec<&mut DungeonEntity> {
-        check_and_return_mut(&mut self.0.header.item_ptrs)
+        self.items_iter_mut().collect()
     }
 
     fn get_traps_mut(&mut self) -> Vec<&mut DungeonEntity> {
-        check_and_return_mut(&mut self.0.header.trap_ptrs)
+        self.traps_iter_mut().collect()
     }
 
     fn get_hidden_stairs_mut(&mut self) -> Option<&mut DungeonEntity> {
@@ -1574,28 +2362,31 @@ impl<'a> EntityTableWrite for EntityTableMut<'a> {
             Some(unsafe { &mut *ptr })
         }
     }
-}
 
-fn check_and_return(ent: &[*mut ffi::entity]) -> Vec<&DungeonEntity> {
-    let mut res: Vec<&DungeonEntity> = Vec::with_capacity(ent.len());
-    for e in ent {
-        if e.is_null() {
-            break;
-        }
-        // SAFETY: We checked the pointer.
-        res.push(unsafe { &**e });
+    fn monsters_iter_mut(&mut self) -> EntitySlotIterMut<'_> {
+        EntitySlotIterMut::new(&mut self.0.header.monster_slot_ptrs)
     }
-    res
-}
 
-fn check_and_return_mut(ent: &mut [*mut ffi::entity]) -> Vec<&mut DungeonEntity> {
-    let mut res: Vec<&mut DungeonEntity> = Vec::with_capacity(ent.len());
-    for e in ent {
-        if e.is_null() {
-            break;
-        }
-        // SAFETY: We checked the pointer.
-        res.push(unsafe { &mut **e });
+    fn active_monsters_iter_mut(&mut self) -> EntitySlotIterMut<'_> {
+        EntitySlotIterMut::new(&mut self.0.header.active_monster_ptrs)
+    }
+
+    fn items_iter_mut(&mut self) -> EntitySlotIterMut<'_> {
+        EntitySlotIterMut::new(&mut self.0.header.item_ptrs)
+    }
+
+    fn traps_iter_mut(&mut self) -> EntitySlotIterMut<'_> {
+        EntitySlotIterMut::new(&mut self.0.header.trap_ptrs)
     }
-    res
+}
+
+/// A tiny xorshift32 step, used by [`GlobalDungeonData::place_doors_at_junctions`] to roll door
+/// placement independently of the game's global RNG state.
+fn next_unit(state: &mut u32) -> f32 {
+    let mut x = *state;
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    *state = x;
+    (x as f64 / u32::MAX as f64) as f32
 }
@@ -1,11 +1,15 @@
 use crate::api::dungeon_mode::*;
+use crate::api::dungeon_mode::clouds::{CloudKind, DungeonCloud};
 use crate::api::enums::{Direction, WarpType};
-use crate::api::items::{Item, ItemId};
-use crate::api::moves::Move;
-use crate::api::overlay::OverlayLoadLease;
+use crate::api::items::{InventoryBag, Item, ItemId};
+use crate::api::moves::{Move, MoveExt, MoveId};
+use crate::api::overlay::{CreatableWithLease, OverlayLoadLease};
+use crate::api::random::rand_i32;
 use crate::api::types::MonsterTypeId;
 use crate::ctypes::*;
 use crate::ffi;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use fixed::types::I24F8;
 
 /// Helper struct for emitting move and item effects.
@@ -15,6 +19,883 @@ use fixed::types::I24F8;
 /// You may find more things to do with monsters in the [`DungeonMonsterMut`] struct.
 pub struct DungeonEffectsEmitter<'a>(pub(crate) &'a OverlayLoadLease<29>);
 
+/// The margin NetHack's `FATAL_DAMAGE_MODIFIER` trick adds on top of twice a target's max HP when
+/// computing a guaranteed-lethal damage amount, shared by
+/// [`DungeonEffectsEmitter::deal_guaranteed_lethal_damage`] and
+/// [`DungeonEffectsInternals::apply_fatal_damage`] so a mod computing its own instant-KO amount
+/// (for a Fissure/Sheer Cold analogue, say) can depend on the same constant both use.
+pub const FATAL_DAMAGE_MARGIN: i32 = 200;
+
+/// A registry of per-item on-hit hooks, the artifact-style "attack bonus" model NetHack's
+/// `spec_applies` uses: pair an [`ItemId`] with a callback, and [`DungeonEffectsEmitter::deal_damage_with_item_hooks`]
+/// runs every hook registered for the item that connected after the hit resolves, passing it
+/// the attacker, defender, and damage dealt.
+#[derive(Default)]
+pub struct ItemOnHitHooks {
+    hooks: Vec<(ItemId, Box<dyn FnMut(&mut DungeonEntity, &mut DungeonEntity, i32)>)>,
+}
+
+impl ItemOnHitHooks {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `hook` to run whenever `item` is the item passed to
+    /// [`DungeonEffectsEmitter::deal_damage_with_item_hooks`] for a hit that connects.
+    pub fn register_item_on_hit(
+        &mut self,
+        item: ItemId,
+        hook: Box<dyn FnMut(&mut DungeonEntity, &mut DungeonEntity, i32)>,
+    ) {
+        self.hooks.push((item, hook));
+    }
+
+    /// Runs every hook registered for `item`, in registration order.
+    fn fire(&mut self, item: ItemId, attacker: &mut DungeonEntity, defender: &mut DungeonEntity, damage: i32) {
+        for (hook_item, hook) in &mut self.hooks {
+            if *hook_item == item {
+                hook(attacker, defender, damage);
+            }
+        }
+    }
+}
+
+/// Context passed to [`MoveEffectHook::modify_accuracy`]/[`MoveEffectHook::modify_damage`], so
+/// those hooks can see what move/item is involved without every hook method needing the full
+/// attacker/defender/move parameter list.
+#[derive(Clone, Copy)]
+pub struct MoveEffectContext<'a> {
+    pub the_move: &'a Move,
+    pub item_id: Option<ItemId>,
+}
+
+/// Adapts PkmnLib's `Script` hook model -- a pluggable interception point abilities and held
+/// items can register against [`DungeonEffectsEmitter::do_move_damage_with_hooks`] to uniformly
+/// alter how any move resolves, instead of each effect needing to be patched into the `do_move_*`
+/// dispatch path individually.
+///
+/// Every method has a default no-op implementation, so a hook only needs to override whichever
+/// stage it cares about.
+///
+/// # Note
+/// [`Self::modify_accuracy`]/[`Self::modify_damage`] can't reach into the engine's own internal
+/// accuracy check or damage formula -- this crate has no accessor for either (see
+/// [`DungeonEffectsEmitter::hit_probability`]'s doc comment for the same gap on stat-based
+/// accuracy). So [`DungeonEffectsEmitter::do_move_damage_with_hooks`] layers them as independent,
+/// hook-controlled inputs instead: `modify_accuracy` adjusts a percentage rolled *in addition to*
+/// the engine's own [`DungeonEffectsEmitter::move_hit_check`] (a hit needs to pass both), and
+/// `modify_damage` adjusts a damage multiplier (as a percentage, `100` meaning unchanged) applied
+/// *before* [`DungeonEffectsEmitter::deal_damage`] computes and applies the real damage, since the
+/// number [`DungeonEffectsEmitter::deal_damage`] returns has already been subtracted from the
+/// defender's HP by the time it comes back and can't be revised after the fact.
+pub trait MoveEffectHook {
+    /// Runs before the move resolves at all. Returning `false` cancels the move outright -- no
+    /// accuracy check, no damage, though [`Self::after_move`] still runs (with
+    /// [`MoveEffectOutcome::NotApplied`]) for every registered hook, cancelling one included.
+    fn before_move(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+    ) -> bool {
+        let _ = (attacker, defender, the_move);
+        true
+    }
+
+    /// Adjusts `accuracy_percent` (`0..=100`), rolled as an additional, independent hit check
+    /// alongside the engine's own. Starts at `100` (always hits) if no hook lowers it.
+    fn modify_accuracy(&mut self, ctx: &MoveEffectContext, accuracy_percent: &mut i32) {
+        let _ = (ctx, accuracy_percent);
+    }
+
+    /// Adjusts `damage_multiplier_percent` (`100` meaning unchanged), applied to the move's
+    /// damage before it's dealt.
+    fn modify_damage(&mut self, ctx: &MoveEffectContext, damage_multiplier_percent: &mut i32) {
+        let _ = (ctx, damage_multiplier_percent);
+    }
+
+    /// Runs after the move has fully resolved, including when it was cancelled by
+    /// [`Self::before_move`] or missed either hit check.
+    fn after_move(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        outcome: MoveEffectOutcome,
+    ) {
+        let _ = (attacker, defender, outcome);
+    }
+}
+
+/// A registry of [`MoveEffectHook`]s, run in registration order around the FFI call by
+/// [`DungeonEffectsEmitter::do_move_damage_with_hooks`], so abilities and held items can
+/// intercept and adjust any move uniformly instead of patching each effect individually.
+#[derive(Default)]
+pub struct MoveEffectHooks {
+    hooks: Vec<Box<dyn MoveEffectHook>>,
+}
+
+impl MoveEffectHooks {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `hook` to run for every subsequent
+    /// [`DungeonEffectsEmitter::do_move_damage_with_hooks`] call, returning an index that can be
+    /// passed to [`Self::remove`].
+    pub fn register(&mut self, hook: Box<dyn MoveEffectHook>) -> usize {
+        self.hooks.push(hook);
+        self.hooks.len() - 1
+    }
+
+    /// Removes and returns the hook previously registered at `index` (per [`Self::register`]'s
+    /// return value), if it's still present. Shifts every later hook's index down by one.
+    pub fn remove(&mut self, index: usize) -> Option<Box<dyn MoveEffectHook>> {
+        if index < self.hooks.len() {
+            Some(self.hooks.remove(index))
+        } else {
+            None
+        }
+    }
+}
+
+/// Mirrors PkmnLib_rs's move-effect script hooks (`prevent_move`, `fail_move`,
+/// `stop_before_move`, `change_number_of_hits`, `on_before_move`/`on_after_move`) as a single
+/// trait, registered once against a [`MoveId`] (or [`MoveHookRegistry::GLOBAL_MOVE`] for every
+/// move) via [`MoveHookRegistry::register`], and run by
+/// [`DungeonEffectsEmitter::run_with_move_hooks`] around *any* `do_move_*` wrapper passed to it --
+/// unlike [`MoveEffectHook`] (wired in explicitly per single
+/// [`DungeonEffectsEmitter::do_move_damage_with_hooks`] call), this works against all of the
+/// hundreds of thin `do_move_*` wrappers in this impl, not just the one damage-dealing call.
+///
+/// Every method has a default no-op implementation, so a hook only needs to override the stage it
+/// cares about.
+pub trait MoveInterceptHook {
+    /// Runs first, for every hook registered against the move; returning `true` cancels the move
+    /// before the real `ffi::DoMove*` call runs at all (matching PkmnLib's
+    /// `prevent_move`/`fail_move`/`stop_before_move`: the move just doesn't happen).
+    fn prevent_move(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+    ) -> bool {
+        let _ = (attacker, defender, the_move, item_id);
+        false
+    }
+
+    /// Runs once every hook's [`Self::prevent_move`] has passed, just before the real
+    /// `ffi::DoMove*` call, so a hook can mutate state (stats, status, etc.) ahead of the move
+    /// resolving.
+    fn on_before_move(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+    ) {
+        let _ = (attacker, defender, the_move);
+    }
+
+    /// Runs after the move resolves. `used` is `false` if any hook's [`Self::prevent_move`]
+    /// cancelled it, or the real call itself reported failure.
+    fn on_after_move(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        used: bool,
+    ) {
+        let _ = (attacker, defender, the_move, used);
+    }
+}
+
+/// A single [`MoveInterceptHook`] registered against the [`MoveId`] (or
+/// [`MoveHookRegistry::GLOBAL_MOVE`]) it runs for.
+struct RegisteredMoveHook {
+    move_id: MoveId,
+    hook: Box<dyn MoveInterceptHook>,
+}
+
+/// A registry of [`MoveInterceptHook`]s keyed by [`MoveId`], run by
+/// [`DungeonEffectsEmitter::run_with_move_hooks`] around whichever `do_move_*` call it's given --
+/// keeps a mod's interception logic in one place instead of threading an explicit hook list
+/// through every call site.
+#[derive(Default)]
+pub struct MoveHookRegistry {
+    hooks: Vec<RegisteredMoveHook>,
+}
+
+impl MoveHookRegistry {
+    /// The [`MoveId`] [`Self::register`] treats as "run for every move", rather than one
+    /// specific move.
+    pub const GLOBAL_MOVE: MoveId = 0;
+
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `hook` to run for every future [`DungeonEffectsEmitter::run_with_move_hooks`]
+    /// call against `move_id` (or [`Self::GLOBAL_MOVE`] for every move), returning an index that
+    /// can be passed to [`Self::remove`].
+    pub fn register(&mut self, move_id: MoveId, hook: Box<dyn MoveInterceptHook>) -> usize {
+        self.hooks.push(RegisteredMoveHook { move_id, hook });
+        self.hooks.len() - 1
+    }
+
+    /// Removes and returns the hook previously registered at `index` (per [`Self::register`]'s
+    /// return value), if it's still present. Shifts every later hook's index down by one.
+    pub fn remove(&mut self, index: usize) -> Option<Box<dyn MoveInterceptHook>> {
+        if index < self.hooks.len() {
+            Some(self.hooks.remove(index).hook)
+        } else {
+            None
+        }
+    }
+
+    /// Every registered hook matching `move_id` or [`Self::GLOBAL_MOVE`], in registration order.
+    fn hooks_for(&mut self, move_id: MoveId) -> impl Iterator<Item = &mut Box<dyn MoveInterceptHook>> {
+        self.hooks
+            .iter_mut()
+            .filter(move |entry| entry.move_id == move_id || entry.move_id == Self::GLOBAL_MOVE)
+            .map(|entry| &mut entry.hook)
+    }
+}
+
+/// Classifies a move's handler into the shared-family groups a few `do_move_*` methods on this
+/// impl already document in prose (their "Relevant moves: ..." lines), as returned by
+/// [`MoveDispatchTable::effect_group`] -- lets tooling enumerate which moves share a handler
+/// without re-reading those doc comments by hand.
+///
+/// Variant names mirror the `do_move_*` method each family routes through.
+/// [`Self::Unique`] covers every move with its own one-off handler, which is most of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveEffectKind {
+    /// [`DungeonEffectsEmitter::do_move_damage_constrict_10`] (Clamp, Bind, Fire Spin, Magma Storm).
+    ConstrictTen,
+    /// [`DungeonEffectsEmitter::do_move_damage_cringe_30`] (Rock Slide, Iron Head, Air Slash, Zen Headbutt, Dragon Rush).
+    CringeThirty,
+    /// [`DungeonEffectsEmitter::do_move_paralyze_20`] (Thunder, Force Palm).
+    ParalyzeTwenty,
+    /// [`DungeonEffectsEmitter::do_move_damage_lower_def_20`] (Crunch, Shadow Ball).
+    LowerDefenseTwenty,
+    /// [`DungeonEffectsEmitter::do_move_damage_lower_speed_20`] (Constrict, Bubblebeam).
+    LowerSpeedTwenty,
+    /// [`DungeonEffectsEmitter::do_move_damage_lower_accuracy_40`] (Muddy Water, Mud Bomb, Mirror Shot).
+    LowerAccuracyForty,
+    /// [`DungeonEffectsEmitter::do_move_damage_lower_special_defence_50`] (Luster Purge, Energy Ball).
+    LowerSpecialDefenseFifty,
+    /// [`DungeonEffectsEmitter::do_move_damage_poison_40`] (Smog, Poison Jab, Cross Poison).
+    PoisonForty,
+    /// [`DungeonEffectsEmitter::do_move_damage_freeze_15`] (Blizzard, Ice Beam).
+    FreezeFifteen,
+    /// [`DungeonEffectsEmitter::do_move_damage_drain`] (Giga Drain, Drain Punch).
+    Drain,
+    /// [`DungeonEffectsEmitter::do_move_damage_weight_dependent`] (Low Kick, Grass Knot).
+    WeightDependent,
+    /// [`DungeonEffectsEmitter::do_move_damage_hp_dependent`] (Wring Out, Crush Grip).
+    HpDependent,
+    /// [`DungeonEffectsEmitter::do_move_damage_eat_item`] (Pluck, Bug Bite).
+    EatItem,
+    /// [`DungeonEffectsEmitter::do_move_copycat`] (Mimic, Copycat).
+    Copycat,
+    /// A move with its own one-off handler, not shared with any other move.
+    Unique,
+}
+
+/// A single registered move in a [`MoveDispatchTable`].
+struct MoveDispatchEntry {
+    move_id: MoveId,
+    kind: MoveEffectKind,
+    handler: fn(
+        &mut DungeonEffectsEmitter,
+        &mut DungeonEntity,
+        &mut DungeonEntity,
+        &Move,
+        ItemId,
+    ) -> bool,
+}
+
+/// A [`MoveId`]-keyed table of which handler each move routes through, driving
+/// [`DungeonEffectsEmitter::do_move_by_id`].
+///
+/// This crate has no canonical table of numeric move IDs to generate this from automatically --
+/// the same gap noted on [`MoveId`]'s other registries (e.g. [`MoveEffectRegistry`],
+/// [`MoveHookRegistry`]) -- so the table starts empty. A patch author [`Self::register`]s every
+/// move they care about once, against a handler (one of this impl's own `do_move_*` methods,
+/// passed as a method reference, e.g. `DungeonEffectsEmitter::do_move_iron_tail`) and the
+/// [`MoveEffectKind`] it shares with other moves; from then on [`Self::effect_group`] and
+/// [`DungeonEffectsEmitter::do_move_by_id`] both pick it up. The Rune/data-driven path (see
+/// [`crate::api::dungeon_mode::rune_effects`]) can register a handler that forwards into its own
+/// script dispatcher the same way any other handler would.
+#[derive(Default)]
+pub struct MoveDispatchTable {
+    entries: Vec<MoveDispatchEntry>,
+}
+
+impl MoveDispatchTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers (or replaces) `move_id`'s handler and [`MoveEffectKind`].
+    pub fn register(
+        &mut self,
+        move_id: MoveId,
+        kind: MoveEffectKind,
+        handler: fn(
+            &mut DungeonEffectsEmitter,
+            &mut DungeonEntity,
+            &mut DungeonEntity,
+            &Move,
+            ItemId,
+        ) -> bool,
+    ) {
+        self.entries.retain(|entry| entry.move_id != move_id);
+        self.entries.push(MoveDispatchEntry {
+            move_id,
+            kind,
+            handler,
+        });
+    }
+
+    fn entry_for(&self, move_id: MoveId) -> Option<&MoveDispatchEntry> {
+        self.entries.iter().find(|entry| entry.move_id == move_id)
+    }
+
+    /// Which shared-handler family `move_id` is registered under, or `None` if it hasn't been
+    /// [`Self::register`]ed. See [`MoveEffectKind`] for what each group means.
+    pub fn effect_group(&self, move_id: MoveId) -> Option<MoveEffectKind> {
+        self.entry_for(move_id).map(|entry| entry.kind)
+    }
+}
+
+/// Porting the ability-flag concept from Crawl's `abl-show.cc`
+/// (`ABFLAG_BREATH`/`DELAY`/`PAIN`/`EXHAUSTION`) to moves: a bitset of queryable properties for a
+/// single move, looked up via [`MoveFlagsTable::flags_for`] and consulted by
+/// [`DungeonEffectsInternals::execute_move_effect_gated`]'s [`MoveUseGateRegistry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MoveFlags(u32);
+
+impl MoveFlags {
+    /// The move makes physical contact with its target (mirroring Crawl's distinction between a
+    /// touch-based and a ranged ability).
+    pub const MAKES_CONTACT: Self = Self(1 << 0);
+    /// Using the move damages its own user as a side effect (e.g. a crash-landing Recoil move).
+    pub const CAUSES_RECOIL: Self = Self(1 << 1);
+    /// The move needs a charge-up turn before it actually executes (Crawl's `ABFLAG_DELAY`).
+    pub const IS_CHARGING: Self = Self(1 << 2);
+    /// Using the move costs its user a resource or inflicts a status on them regardless of
+    /// whether it hits (Crawl's `ABFLAG_PAIN`/`ABFLAG_EXHAUSTION`).
+    pub const SELF_DAMAGING: Self = Self(1 << 3);
+    /// The move has its own independent recharge/cooldown delay, separate from the normal turn
+    /// order (Crawl's `ABFLAG_EXHAUSTION`-adjacent "this ability has its own timer" moves).
+    pub const HAS_OWN_DELAY: Self = Self(1 << 4);
+
+    /// No flags set.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Whether every bit set in `other` is also set in `self`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// [`Self::MAKES_CONTACT`] is set.
+    pub const fn makes_contact(self) -> bool {
+        self.contains(Self::MAKES_CONTACT)
+    }
+
+    /// [`Self::CAUSES_RECOIL`] is set.
+    pub const fn causes_recoil(self) -> bool {
+        self.contains(Self::CAUSES_RECOIL)
+    }
+
+    /// [`Self::IS_CHARGING`] is set.
+    pub const fn is_charging(self) -> bool {
+        self.contains(Self::IS_CHARGING)
+    }
+
+    /// [`Self::SELF_DAMAGING`] is set.
+    pub const fn self_damaging(self) -> bool {
+        self.contains(Self::SELF_DAMAGING)
+    }
+
+    /// [`Self::HAS_OWN_DELAY`] is set.
+    pub const fn has_own_delay(self) -> bool {
+        self.contains(Self::HAS_OWN_DELAY)
+    }
+}
+
+impl core::ops::BitOr for MoveFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// A [`MoveId`]-keyed table of each move's [`MoveFlags`], mirroring [`MoveDispatchTable`]'s shape.
+///
+/// This crate has no canonical source for which moves make contact, cause recoil, and so on --
+/// the same gap noted on [`MoveDispatchTable`] -- so the table starts empty and unregistered
+/// moves report [`MoveFlags::empty`]. A patch author [`Self::register`]s every move they care
+/// about once.
+#[derive(Default)]
+pub struct MoveFlagsTable {
+    entries: Vec<(MoveId, MoveFlags)>,
+}
+
+impl MoveFlagsTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers (or replaces) `move_id`'s [`MoveFlags`].
+    pub fn register(&mut self, move_id: MoveId, flags: MoveFlags) {
+        self.entries.retain(|(id, _)| *id != move_id);
+        self.entries.push((move_id, flags));
+    }
+
+    /// `move_id`'s registered flags, or [`MoveFlags::empty`] if it hasn't been
+    /// [`Self::register`]ed.
+    pub fn flags_for(&self, move_id: MoveId) -> MoveFlags {
+        self.entries
+            .iter()
+            .find(|(id, _)| *id == move_id)
+            .map(|(_, flags)| *flags)
+            .unwrap_or_default()
+    }
+}
+
+/// Result of a [`MoveUseGateRegistry`] predicate, and of
+/// [`DungeonEffectsInternals::execute_move_effect_gated`] itself: whether a move's native effect
+/// is (or was) allowed to proceed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanUse {
+    /// Nothing objects; the native effect may run.
+    Allow,
+    /// Some registered gate objects; the native effect is skipped entirely.
+    Block,
+}
+
+impl CanUse {
+    /// `true` for [`Self::Allow`].
+    pub fn allowed(self) -> bool {
+        matches!(self, Self::Allow)
+    }
+}
+
+/// A predicate consulted by [`MoveUseGateRegistry`] for every move whose [`MoveFlags`] include the
+/// flag it was [`MoveUseGateRegistry::register`]ed against (e.g. charge a resource cost for
+/// [`MoveFlags::SELF_DAMAGING`] moves). It's given the move and the move's user; the move's
+/// target isn't passed.
+pub type MoveUsePredicate = fn(&Move, &DungeonEntity) -> CanUse;
+
+/// A single [`MoveUsePredicate`] registered against the [`MoveFlags`] bit that triggers it.
+struct RegisteredMoveUseGate {
+    flag: MoveFlags,
+    predicate: MoveUsePredicate,
+}
+
+/// A registry of [`MoveUsePredicate`]s, each keyed by a [`MoveFlags`] bit, consulted by
+/// [`DungeonEffectsInternals::execute_move_effect_gated`] before the native move effect runs --
+/// keeps a mod's "block or alter a move ahead of time" rules in one place instead of threading an
+/// explicit predicate list through every call site.
+#[derive(Default)]
+pub struct MoveUseGateRegistry {
+    gates: Vec<RegisteredMoveUseGate>,
+}
+
+impl MoveUseGateRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `predicate` to be consulted for every future
+    /// [`DungeonEffectsInternals::execute_move_effect_gated`] call against a move whose
+    /// [`MoveFlags`] include `flag`, returning an index that can be passed to [`Self::remove`].
+    pub fn register(&mut self, flag: MoveFlags, predicate: MoveUsePredicate) -> usize {
+        self.gates.push(RegisteredMoveUseGate { flag, predicate });
+        self.gates.len() - 1
+    }
+
+    /// Removes and returns the predicate previously registered at `index` (per
+    /// [`Self::register`]'s return value), if it's still present. Shifts every later gate's index
+    /// down by one.
+    pub fn remove(&mut self, index: usize) -> Option<MoveUsePredicate> {
+        if index < self.gates.len() {
+            Some(self.gates.remove(index).predicate)
+        } else {
+            None
+        }
+    }
+
+    /// Consults every registered predicate whose `flag` is set in `flags`, in registration order;
+    /// the first [`CanUse::Block`] short-circuits the rest.
+    fn check(&self, flags: MoveFlags, the_move: &Move, user: &DungeonEntity) -> CanUse {
+        for gate in &self.gates {
+            if flags.contains(gate.flag) && (gate.predicate)(the_move, user) == CanUse::Block {
+                return CanUse::Block;
+            }
+        }
+        CanUse::Allow
+    }
+}
+
+/// A tuning value passed to [`MoveEffect::on_initialize`], so a single `MoveEffect`
+/// implementation can be registered against several different moves with different tuning (e.g.
+/// a generic "lower a stat" effect parameterized by which stat and how many stages), instead of
+/// one hardcoded struct per move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EffectParameter {
+    /// A bare integer tuning value (a stat index, a percentage, a stage count, ...).
+    Int(i32),
+    /// A status to inflict or otherwise reference.
+    Status(StatusEffect),
+}
+
+/// Per-call state passed to a [`MoveEffect`]'s hooks by
+/// [`DungeonEffectsEmitter::do_move_damage_with_registry`], mirroring PkmnLib_rs's per-execution
+/// `Script` context.
+#[derive(Clone, Copy)]
+pub struct MoveEffectRunContext<'a> {
+    pub attacker: &'a DungeonEntity,
+    pub defender: &'a DungeonEntity,
+    pub the_move: &'a Move,
+    pub item_id: ItemId,
+}
+
+/// Adapts PkmnLib_rs's hook-based `Script` design into an ordered, move-ID-keyed registry:
+/// unlike [`MoveEffectHook`] (wired in explicitly per [`DungeonEffectsEmitter::do_move_damage_with_hooks`]
+/// call), a `MoveEffect` is registered once against the move ID(s) it patches, and
+/// [`DungeonEffectsEmitter::do_move_damage_with_registry`] looks up and runs every effect for the
+/// move actually being used -- including any registered against [`MoveEffectRegistry::GLOBAL_MOVE`], which
+/// run on every move.
+///
+/// Every method has a default no-op implementation, so an effect only needs to override
+/// whichever stage(s) it actually changes.
+#[allow(unused_variables)]
+pub trait MoveEffect {
+    /// Called once, immediately after registration, with the tuning parameters passed to
+    /// [`MoveEffectRegistry::register`].
+    fn on_initialize(&mut self, params: &[EffectParameter]) {}
+
+    /// Runs before the move resolves at all. Returning `false` cancels the move outright for
+    /// this call, the same way [`MoveEffectHook::before_move`] does; no later effect in this
+    /// call's priority order runs afterward.
+    fn on_before_move(&mut self, ctx: &MoveEffectRunContext) -> bool {
+        true
+    }
+
+    /// Adjusts the move's accuracy percentage (`0..=100`) before
+    /// [`DungeonEffectsEmitter::move_hit_check`] runs, the same independent-roll model
+    /// [`MoveEffectHook::modify_accuracy`] uses (see that method's note on why this can't reach
+    /// into the engine's own accuracy check).
+    fn modify_accuracy(&mut self, ctx: &MoveEffectRunContext, accuracy_percent: &mut i32) {}
+
+    /// Adjusts the move's predicted damage (seeded from
+    /// [`DungeonMonsterRead::predict_damage`]) before it's dealt.
+    /// [`DungeonEffectsEmitter::do_move_damage_with_registry`] converts the net adjustment back into a multiplier for [`DungeonEffectsEmitter::deal_damage`],
+    /// since that's the only way to feed a revised number into the engine's own damage
+    /// computation (see [`MoveEffectHook::modify_damage`]'s note on the same gap).
+    fn modify_damage(&mut self, ctx: &MoveEffectRunContext, damage: &mut i32) {}
+
+    /// Runs once the move has actually hit, after damage has been dealt.
+    fn on_hit(&mut self, attacker: &mut DungeonEntity, defender: &mut DungeonEntity, the_move: &Move) {}
+
+    /// Adjusts the priority of the move being used.
+    fn change_priority(&mut self, priority: i8) -> i8 {
+        priority
+    }
+
+    /// Adjusts the user's speed stage for this move.
+    fn change_speed(&mut self, speed_stage: i32) -> i32 {
+        speed_stage
+    }
+}
+
+/// An opaque handle to a [`MoveEffect`] registered via [`MoveEffectRegistry::register`], for
+/// [`MoveEffectRegistry::unregister`]/[`MoveEffectRegistry::add_suppression`]/
+/// [`MoveEffectRegistry::remove_suppression`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveEffectHandle(u32);
+
+/// A single registered [`MoveEffect`], with the bookkeeping [`MoveEffectRegistry`] needs to run
+/// it in order and temporarily disable it.
+struct RegisteredMoveEffect {
+    handle: MoveEffectHandle,
+    move_id: MoveId,
+    priority: i32,
+    /// Stacked suppression count: nonzero skips this effect in
+    /// [`DungeonEffectsEmitter::do_move_damage_with_registry`].
+    /// Stacked rather than a bare flag so two unrelated callers can each suppress it without one
+    /// undoing the other's suppression early.
+    suppression_count: u32,
+    effect: Box<dyn MoveEffect>,
+}
+
+/// A registry of [`MoveEffect`]s, keyed by the [`MoveId`] they patch, run by
+/// [`DungeonEffectsEmitter::do_move_damage_with_registry`] whenever a move with a matching ID is
+/// used through it. Keeps move behavior
+/// extensible from outside this crate instead of requiring every new effect to be forked into
+/// the `do_move_*` dispatch list here.
+#[derive(Default)]
+pub struct MoveEffectRegistry {
+    effects: Vec<RegisteredMoveEffect>,
+    next_handle: u32,
+}
+
+impl MoveEffectRegistry {
+    /// The [`MoveId`] [`Self::register`] treats as "run on every move", rather than one specific
+    /// move.
+    pub const GLOBAL_MOVE: MoveId = 0;
+
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `effect` against `move_id` (or [`Self::GLOBAL_MOVE`] for every move), at
+    /// `priority` (higher runs first; ties broken by registration order), calling
+    /// [`MoveEffect::on_initialize`] with `params` before returning the handle.
+    pub fn register(
+        &mut self,
+        move_id: MoveId,
+        priority: i32,
+        mut effect: Box<dyn MoveEffect>,
+        params: &[EffectParameter],
+    ) -> MoveEffectHandle {
+        effect.on_initialize(params);
+        let handle = MoveEffectHandle(self.next_handle);
+        self.next_handle += 1;
+        self.effects.push(RegisteredMoveEffect {
+            handle,
+            move_id,
+            priority,
+            suppression_count: 0,
+            effect,
+        });
+        handle
+    }
+
+    /// Removes and returns the effect previously registered at `handle`, if it's still present.
+    pub fn unregister(&mut self, handle: MoveEffectHandle) -> Option<Box<dyn MoveEffect>> {
+        let index = self.effects.iter().position(|entry| entry.handle == handle)?;
+        Some(self.effects.remove(index).effect)
+    }
+
+    /// Temporarily disables the effect at `handle`, without unregistering it. Stacks: an effect
+    /// suppressed twice needs [`Self::remove_suppression`] called twice before it runs again.
+    /// No-op if `handle` isn't registered.
+    pub fn add_suppression(&mut self, handle: MoveEffectHandle) {
+        if let Some(entry) = self.effects.iter_mut().find(|entry| entry.handle == handle) {
+            entry.suppression_count += 1;
+        }
+    }
+
+    /// Reverses one [`Self::add_suppression`] call for `handle`. No-op if `handle` isn't
+    /// registered or isn't currently suppressed.
+    pub fn remove_suppression(&mut self, handle: MoveEffectHandle) {
+        if let Some(entry) = self.effects.iter_mut().find(|entry| entry.handle == handle) {
+            entry.suppression_count = entry.suppression_count.saturating_sub(1);
+        }
+    }
+
+    /// Every non-suppressed effect registered against `move_id` or [`Self::GLOBAL_MOVE`], in
+    /// descending priority order (ties broken by registration order, since
+    /// [`[T]::sort_by_key`](slice::sort_by_key) is stable).
+    fn active_effects_for(&mut self, move_id: MoveId) -> Vec<&mut RegisteredMoveEffect> {
+        let mut matching: Vec<&mut RegisteredMoveEffect> = self
+            .effects
+            .iter_mut()
+            .filter(|entry| {
+                (entry.move_id == move_id || entry.move_id == Self::GLOBAL_MOVE)
+                    && entry.suppression_count == 0
+            })
+            .collect();
+        matching.sort_by_key(|entry| core::cmp::Reverse(entry.priority));
+        matching
+    }
+}
+
+/// Alias for [`DungeonTypeMatchup`], for callers thinking in terms of PkmnLib's `Effectiveness`
+/// naming -- same four values (immune/not very effective/neutral/super effective), just a more
+/// familiar name for romhackers porting move logic from other engines.
+pub type Effectiveness = DungeonTypeMatchup;
+
+/// A structured account of what a `do_move_*_detailed` call actually did, following PkmnLib's
+/// `ExecutingMove`/`DamageSource` tracking -- in place of a bare `bool` that only says "used".
+///
+/// # Note
+/// Every field here is populated from a real, already-existing accessor (documented per field)
+/// rather than parsed out of engine-internal state this crate doesn't expose a reader for. In
+/// particular, whether the engine's own `DoMoveDamage`/`DealDamage` FFI calls rolled a critical
+/// hit is not exposed anywhere in this crate, so [`Self::was_critical`] is rolled independently
+/// here, the same way [`DungeonEffectsEmitter::roll_hit`] rolls its own accuracy check alongside
+/// (not inside) the engine's.
+#[derive(Debug, Clone, Default)]
+pub struct MoveResult {
+    /// Whether the move actually hit and dealt damage.
+    pub used: bool,
+    /// The amount of damage dealt, via [`DungeonEffectsEmitter::deal_damage`]'s return value.
+    /// `0` if the move missed.
+    pub damage_dealt: i32,
+    /// Whether this call's own crit roll (see the struct-level note) succeeded. Always `false`
+    /// if the move missed.
+    pub was_critical: bool,
+    /// The type matchup of the move's type against the defender's first type, via
+    /// [`DungeonMonsterRead::get_type_matchup`]. `None` if either side's monster info couldn't be
+    /// read (e.g. the entity isn't a monster).
+    pub type_effectiveness: Option<Effectiveness>,
+    /// Which [`StatusEffect`]s were successfully inflicted as part of this call, in the order
+    /// they were rolled. Always empty for variants (like
+    /// [`DungeonEffectsEmitter::do_move_damage_detailed`]) that don't attempt any status
+    /// infliction themselves.
+    pub statuses_inflicted: Vec<StatusEffect>,
+    /// Whether the defender's HP dropped to `0` or below as a result of this call, via
+    /// [`ffi::monster::hp_current`].
+    pub target_fainted: bool,
+}
+
+/// The footprint of an area-of-effect hit for [`DungeonEffectsEmitter::deal_damage_area`] and
+/// [`DungeonEffectsEmitter::apply_to_targets`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AreaShape {
+    /// A straight line `length` tiles long, stopping early at the first wall.
+    Line { length: i32 },
+    /// A line that widens by one tile on each side per step outward (capped at `width` tiles on
+    /// each side), also stopping early at the first wall along its centerline.
+    Cone { length: i32, width: i32 },
+    /// Every tile within Chebyshev distance `radius` of the origin (a `(2 * radius + 1)`-tile
+    /// square) other than the origin itself, ignoring `origin_direction` entirely. Walls don't
+    /// block the spread, but wall tiles themselves are skipped.
+    Burst { radius: i32 },
+    /// Every tile tagged with the same [`RegionTag`] as the origin's own tile (i.e. the whole
+    /// room the origin stands in, or just the origin's own tile if it's in a hallway).
+    ///
+    /// Unlike the other variants, [`walk_area`] doesn't enumerate this shape's tiles directly --
+    /// there's no existing helper that lists every tile belonging to a room, so
+    /// [`DungeonEffectsEmitter::apply_to_targets`] matches candidate monsters by comparing
+    /// [`RegionTag`]s instead of walking individual positions.
+    Room,
+}
+
+/// Pushes `(x, y)` onto `tiles` as a raw position paired with `distance`, unless it's a wall.
+/// Returns whether the tile was passable, so [`AreaShape::Line`]/[`AreaShape::Cone`] can stop
+/// their forward walk at the first wall.
+fn push_if_passable(tiles: &mut Vec<(ffi::position, i32)>, x: i32, y: i32, distance: i32) -> bool {
+    // SAFETY: GetTileSafe bounds-checks the coordinates itself, returning a default
+    // (out-of-bounds) tile instead of UB.
+    let tile = unsafe { &*ffi::GetTileSafe(x, y) };
+    if tile.get_terrain() == Some(TerrainType::Wall) {
+        return false;
+    }
+    tiles.push((
+        ffi::position {
+            x: x as i16,
+            y: y as i16,
+        },
+        distance,
+    ));
+    true
+}
+
+/// Enumerates the tiles an [`AreaShape`] covers from `origin`, outward in `direction`, paired
+/// with each tile's distance from `origin` (`1` for the nearest ring outward).
+fn walk_area(origin: ffi::position, direction: Direction, shape: AreaShape) -> Vec<(ffi::position, i32)> {
+    let mut tiles = Vec::new();
+    match shape {
+        AreaShape::Line { length } => {
+            if let Some((dx, dy)) = direction.step() {
+                let (mut x, mut y) = (origin.x as i32, origin.y as i32);
+                for distance in 1..=length {
+                    x += dx;
+                    y += dy;
+                    if !push_if_passable(&mut tiles, x, y, distance) {
+                        break;
+                    }
+                }
+            }
+        }
+        AreaShape::Cone { length, width } => {
+            if let Some((dx, dy)) = direction.step() {
+                let (perp_x, perp_y) = (-dy, dx);
+                let (mut cx, mut cy) = (origin.x as i32, origin.y as i32);
+                for distance in 1..=length {
+                    cx += dx;
+                    cy += dy;
+                    // SAFETY: GetTileSafe bounds-checks the coordinates itself.
+                    let center_tile = unsafe { &*ffi::GetTileSafe(cx, cy) };
+                    if center_tile.get_terrain() == Some(TerrainType::Wall) {
+                        break;
+                    }
+                    let half_width = (distance - 1).min(width);
+                    for offset in -half_width..=half_width {
+                        push_if_passable(
+                            &mut tiles,
+                            cx + perp_x * offset,
+                            cy + perp_y * offset,
+                            distance,
+                        );
+                    }
+                }
+            }
+        }
+        AreaShape::Burst { radius } => {
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    let distance = dx.abs().max(dy.abs());
+                    if distance == 0 || distance > radius {
+                        continue;
+                    }
+                    push_if_passable(&mut tiles, origin.x as i32 + dx, origin.y as i32 + dy, distance);
+                }
+            }
+            // The loops above run row by row; order nearest-first like the other shapes.
+            tiles.sort_by_key(|&(_, distance)| distance);
+        }
+        // Handled separately in `DungeonEffectsEmitter::apply_to_targets`, by `RegionTag`
+        // comparison rather than tile walking; see `AreaShape::Room`'s doc comment.
+        AreaShape::Room => {}
+    }
+    tiles
+}
+
+/// Which side of `attacker` [`DungeonEffectsEmitter::apply_to_targets`] invokes its callback
+/// against, mirroring the same is-team-member check
+/// [`crate::api::dungeon_mode::trajectory`]'s private `is_ally` helper uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetFilter {
+    /// Only monsters on the opposing side from the attacker.
+    FoesOnly,
+    /// Only monsters on the same side as the attacker.
+    AlliesOnly,
+    /// Every monster on an affected tile, regardless of side.
+    Everyone,
+}
+
+/// Whether `defender` passes `filter`, relative to `attacker`'s allegiance. `false` for
+/// `FoesOnly`/`AlliesOnly` if either isn't a monster.
+fn passes_target_filter(attacker: &DungeonEntity, defender: &DungeonEntity, filter: TargetFilter) -> bool {
+    match filter {
+        TargetFilter::Everyone => true,
+        TargetFilter::FoesOnly | TargetFilter::AlliesOnly => {
+            let same_side = match (attacker.info_for_monster(), defender.info_for_monster()) {
+                (Some(a), Some(d)) => a.0.is_not_team_member == d.0.is_not_team_member,
+                _ => return false,
+            };
+            same_side == (filter == TargetFilter::AlliesOnly)
+        }
+    }
+}
+
 impl<'a> DungeonEffectsEmitter<'a> {
     /// Low-level functions internal to the dungeon engine.
     /// Consider using one of the other functions instead for most cases.
@@ -95,6 +976,117 @@ impl<'a> DungeonEffectsEmitter<'a> {
         }
     }
 
+    /// A Brogue-style smooth to-hit curve, for mod moves that want a tunable accuracy model
+    /// instead of the engine's own built-in accuracy check: `accuracy * 0.987^defense`, clamped
+    /// to `[0.0, 1.0]`.
+    ///
+    /// `accuracy_stage` and `evasion_stage` are the attacker's accuracy stage and the defender's
+    /// evasion stage, in the usual `-6..=6` stage range; they're converted to Brogue's
+    /// `accuracy`/`defense` inputs as `accuracy = 1.0 + 0.1 * accuracy_stage` and
+    /// `defense = max(evasion_stage, 0)` (a defender made easier to hit shouldn't make the curve
+    /// steeper, so negative evasion stages don't lower `defense` below zero).
+    ///
+    /// # Note
+    /// This takes stages directly rather than `attacker`/`defender` entities, since this crate
+    /// doesn't currently expose a safe reader for a monster's accuracy/evasion stat stages (see
+    /// [`Self::boost_hit_chance_stat`] and friends, which only mutate them).
+    pub fn hit_probability(&self, accuracy_stage: i32, evasion_stage: i32) -> f32 {
+        let accuracy = 1.0 + 0.1 * accuracy_stage as f32;
+        let defense = evasion_stage.max(0) as f32;
+        (accuracy * 0.987f32.powf(defense)).clamp(0.0, 1.0)
+    }
+
+    /// Rolls a hit against [`Self::hit_probability`], except `defender` is always hit if it's in
+    /// a helpless state -- Sleep, Napping, Paused, Petrified, Frozen, Shadow Hold, or anything
+    /// else [`DungeonMonsterRead::has_status_that_prevents_acting`] reports -- the classic
+    /// roguelike "helpless targets can't dodge" rule.
+    pub fn roll_hit(
+        &mut self,
+        defender: &mut DungeonEntity,
+        accuracy_stage: i32,
+        evasion_stage: i32,
+    ) -> bool {
+        let is_helpless = defender
+            .info_for_monster()
+            .is_some_and(|m| m.has_status_that_prevents_acting());
+        if is_helpless {
+            return true;
+        }
+        let hit_pct = (self.hit_probability(accuracy_stage, evasion_stage) * 100.0) as i32;
+        rand_i32(0..100) < hit_pct
+    }
+
+    /// Rolls a hit via [`Self::roll_hit`] and, only if it connects, deals damage via
+    /// [`Self::deal_damage`].
+    ///
+    /// Returns the damage dealt, or `None` if the roll missed.
+    pub fn deal_damage_if_hit(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        used_move: &Move,
+        damage_multiplier: I24F8,
+        item_id: Option<ItemId>,
+        accuracy_stage: i32,
+        evasion_stage: i32,
+    ) -> Option<i32> {
+        if !self.roll_hit(defender, accuracy_stage, evasion_stage) {
+            return None;
+        }
+        Some(self.deal_damage(attacker, defender, used_move, damage_multiplier, item_id))
+    }
+
+    /// Deals damage via [`Self::deal_damage`], then runs any [`ItemOnHitHooks`] registered
+    /// against `item_id` for the result -- NetHack's artifact on-hit model (bonus status,
+    /// draining, conditional bonus damage) for mod items, instead of hardcoding each item's extra
+    /// effect into whatever move/item handler calls this.
+    ///
+    /// Hooks only fire if `item_id` is `Some`; there's nothing to key a hook off of otherwise.
+    pub fn deal_damage_with_item_hooks(
+        &mut self,
+        hooks: &mut ItemOnHitHooks,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        used_move: &Move,
+        damage_multiplier: I24F8,
+        item_id: Option<ItemId>,
+    ) -> i32 {
+        let damage = self.deal_damage(attacker, defender, used_move, damage_multiplier, item_id);
+        if let Some(item_id) = item_id {
+            hooks.fire(item_id, attacker, defender, damage);
+        }
+        damage
+    }
+
+    /// NetHack's "guaranteed kill" trick: picks a [`Self::deal_damage`] multiplier large enough
+    /// that the result is certain to exceed `2 * defender's max HP + `[`FATAL_DAMAGE_MARGIN`],
+    /// so the blow kills even after the engine's own post-damage adjustments (eg.
+    /// half-physical-damage effects) have had their say. See also
+    /// [`DungeonEffectsInternals::apply_fatal_damage`], the same trick built on [`Self::apply_damage`]
+    /// instead, for callers working with an [`ffi::damage_data`] directly.
+    ///
+    /// There's no accessor in this crate for the base damage [`Self::deal_damage`] would compute
+    /// at multiplier `1` (that's buried in the engine's internal damage formula), so this can't
+    /// target the threshold precisely. Instead it scales the multiplier itself by the threshold,
+    /// which overshoots by a comfortable margin for any move whose base damage is more than a
+    /// handful of HP -- true of essentially every damaging move in this game.
+    pub fn deal_guaranteed_lethal_damage(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        used_move: &Move,
+        item_id: Option<ItemId>,
+    ) -> i32 {
+        let max_hp = defender
+            .info_for_monster()
+            .map(|m| m.0.hp_max)
+            .unwrap_or(0)
+            .max(0);
+        let lethal_threshold = 2 * max_hp + FATAL_DAMAGE_MARGIN;
+        let multiplier = I24F8::from_num(lethal_threshold.max(1));
+        self.deal_damage(attacker, defender, used_move, multiplier, item_id)
+    }
+
     /// Inflicts the Sleep status condition on a target monster if possible.
     ///
     /// No status is returned.
@@ -593,6 +1585,349 @@ impl<'a> DungeonEffectsEmitter<'a> {
         unsafe { ffi::TryInflictDestinyBond(attacker, defender) }
     }
 
+    /// Inflicts `status` on `defender` according to `opts`, dispatching to whichever
+    /// `try_inflict_*_status` wrapper matches.
+    ///
+    /// This exists so callers (especially AI move-scoring code, see
+    /// [`InflictOptions::check_only`]) have one discoverable entry point instead of needing to
+    /// know the name of 20 near-identical methods; it doesn't do anything the individual methods
+    /// can't already do on their own.
+    ///
+    /// Returns whether the status took (or, under [`InflictOptions::check_only`], would take)
+    /// effect, and whether the underlying function actually reported that (see
+    /// [`InflictOutcome`]). [`InflictOptions::check_only`] for the subset of statuses that
+    /// support a dry-run check at all.
+    pub fn try_inflict_status(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        status: StatusCondition,
+        opts: InflictOptions,
+    ) -> InflictOutcome {
+        use InflictOutcome::{Applied, AppliedUnconditionally, Rejected};
+
+        match status {
+            StatusCondition::Sleep { number_turns } => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_sleep_status(attacker, defender, number_turns, opts.log_failure);
+                AppliedUnconditionally
+            }
+            StatusCondition::Nightmare { number_turns } => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_nightmare_status(attacker, defender, number_turns);
+                AppliedUnconditionally
+            }
+            StatusCondition::Napping { number_turns } => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_napping_status(attacker, defender, number_turns);
+                AppliedUnconditionally
+            }
+            StatusCondition::Yawning { number_turns } => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_yawning_status(attacker, defender, number_turns);
+                AppliedUnconditionally
+            }
+            StatusCondition::Sleepless => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_sleepless_status(attacker, defender);
+                AppliedUnconditionally
+            }
+            StatusCondition::Paused {
+                param3,
+                number_turns,
+            } => {
+                if self.try_inflict_paused_status(
+                    attacker,
+                    defender,
+                    param3,
+                    number_turns,
+                    opts.log_failure,
+                    opts.check_only,
+                ) {
+                    Applied
+                } else {
+                    Rejected
+                }
+            }
+            StatusCondition::Infatuated => {
+                if self.try_inflict_infatuated_status(attacker, defender, opts.log_failure, opts.check_only) {
+                    Applied
+                } else {
+                    Rejected
+                }
+            }
+            StatusCondition::Burn { special_effect } => {
+                if self.try_inflict_burn_status(
+                    attacker,
+                    defender,
+                    special_effect,
+                    opts.log_failure,
+                    opts.check_only,
+                ) {
+                    Applied
+                } else {
+                    Rejected
+                }
+            }
+            StatusCondition::Poisoned => {
+                if self.try_inflict_poisoned_status(attacker, defender, opts.log_failure, opts.check_only) {
+                    Applied
+                } else {
+                    Rejected
+                }
+            }
+            StatusCondition::BadlyPoisoned => {
+                if self.try_inflict_badly_poisoned_status(
+                    attacker,
+                    defender,
+                    opts.log_failure,
+                    opts.check_only,
+                ) {
+                    Applied
+                } else {
+                    Rejected
+                }
+            }
+            StatusCondition::Frozen => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_frozen_status(attacker, defender, opts.log_failure);
+                AppliedUnconditionally
+            }
+            StatusCondition::Constriction { animation_id } => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_constriction_status(
+                    attacker,
+                    defender,
+                    animation_id,
+                    opts.log_failure,
+                );
+                AppliedUnconditionally
+            }
+            StatusCondition::ShadowHold => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_shadow_hold_status(attacker, defender, opts.log_failure);
+                AppliedUnconditionally
+            }
+            StatusCondition::Ingrain => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_ingrain_status(attacker, defender);
+                AppliedUnconditionally
+            }
+            StatusCondition::Wrapped => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_wrapped_status(attacker, defender);
+                AppliedUnconditionally
+            }
+            StatusCondition::Petrified => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_petrified_status(attacker, defender);
+                AppliedUnconditionally
+            }
+            StatusCondition::Cringe => {
+                if self.try_inflict_cringe_status(attacker, defender, opts.log_failure, opts.check_only) {
+                    Applied
+                } else {
+                    Rejected
+                }
+            }
+            StatusCondition::Paralysis => {
+                if self.try_inflict_paralysis_status(
+                    attacker,
+                    defender,
+                    opts.log_failure,
+                    opts.check_only,
+                ) {
+                    Applied
+                } else {
+                    Rejected
+                }
+            }
+            StatusCondition::Confused => {
+                if self.try_inflict_confused_status(attacker, defender, opts.log_failure, opts.check_only) {
+                    Applied
+                } else {
+                    Rejected
+                }
+            }
+            StatusCondition::Cowering => {
+                if self.try_inflict_cowering_status(attacker, defender, opts.log_failure, opts.check_only) {
+                    Applied
+                } else {
+                    Rejected
+                }
+            }
+            StatusCondition::LeechSeed => {
+                if self.try_inflict_leech_seed_status(
+                    attacker,
+                    defender,
+                    opts.log_failure,
+                    opts.check_only,
+                ) {
+                    Applied
+                } else {
+                    Rejected
+                }
+            }
+            StatusCondition::DestinyBond => {
+                if opts.check_only {
+                    return Rejected;
+                }
+                self.try_inflict_destiny_bond_status(attacker, defender);
+                AppliedUnconditionally
+            }
+        }
+    }
+
+    /// A resistance-roll layer over [`Self::try_inflict_status`], Crawl's enchantment-resist
+    /// model for mod moves that want odds like "30% chance to paralyze" instead of the engine's
+    /// own all-or-nothing per-status logic.
+    ///
+    /// First runs `status` against `defender` in [`InflictOptions::check_only`] mode to confirm
+    /// it's actually eligible (not immune, not already affected, per the engine's own rules) --
+    /// if that probe doesn't report [`InflictOutcome::applied`], this returns `false` without
+    /// rolling or touching `defender` at all.
+    ///
+    /// If eligible, rolls `base_chance` (a percentage, `0..=100`) against `defender`'s
+    /// resistance, computed as `base_chance` scaled down by:
+    /// - `defender_resistance`, a caller-supplied `0..=100` value standing in for type-based
+    ///   immunities and stat stages -- this crate has no safe reader for either (see
+    ///   [`Self::hit_probability`]'s doc comment for the same gap on accuracy/evasion stages), so
+    ///   the caller is expected to derive it from whatever type-matchup/stat-stage logic their mod
+    ///   uses and pass the result in directly;
+    /// - `prior_applications`, a caller-supplied count of how many times this status has already
+    ///   landed on `defender` this battle (this crate keeps no persistent per-defender
+    ///   application history, so the caller must track it), applied as Crawl's escalating resist
+    ///   curve: each prior application halves the remaining chance, i.e. the effective chance is
+    ///   `base_chance * (1 - defender_resistance / 100) / 2^prior_applications`.
+    ///
+    /// Only commits the real infliction (a non-`check_only` [`Self::try_inflict_status`] call) if
+    /// the roll succeeds; returns whether it was applied.
+    pub fn try_inflict_with_chance(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        status: StatusEffect,
+        base_chance: u8,
+        defender_resistance: u8,
+        prior_applications: u32,
+        opts: InflictOptions,
+    ) -> bool {
+        let eligible = self
+            .try_inflict_status(
+                attacker,
+                defender,
+                status,
+                InflictOptions {
+                    check_only: true,
+                    log_failure: false,
+                },
+            )
+            .applied();
+        if !eligible {
+            return false;
+        }
+
+        let resistance_factor = 100 - defender_resistance.min(100) as i32;
+        let mut effective_chance = base_chance as i32 * resistance_factor / 100;
+        effective_chance >>= prior_applications.min(31);
+
+        if rand_i32(0..100) >= effective_chance {
+            return false;
+        }
+
+        self.try_inflict_status(attacker, defender, status, opts)
+            .applied()
+    }
+
+    /// Decides what a simple AI-controlled `actor` should do this turn: heal itself, disable
+    /// `nearest_target`, or fall through to a normal attack - analogous to how NetHack's monsters
+    /// decide when to quaff a healing potion versus reading a scroll at an enemy.
+    ///
+    /// This only decides; it doesn't execute anything itself (except the dry-run
+    /// [`InflictOptions::check_only`] probes this needs to evaluate candidates), so the caller is
+    /// expected to act on the returned [`EnemyAiAction`] (eg. by calling
+    /// [`Self::try_inflict_status`] for real, [`Self::try_increase_hp`] for
+    /// [`EnemyAiAction::HealSelf`], or [`Self::boost_offensive_stat`] for
+    /// [`EnemyAiAction::BoostOwnStat`]). Finding `nearest_target` itself is left to the caller, since
+    /// this crate doesn't currently expose a safe floor-wide nearest-neighbor search; the game's
+    /// own AI target selection (see [`crate::api::dungeon_mode::DungeonMonsterWrite::calculate_ai_target_pos`])
+    /// already solves this.
+    ///
+    /// # Decision order
+    /// 1. If `actor`'s HP fraction is at or below [`AiDecisionConfig::heal_hp_fraction`] and
+    ///    [`AiDecisionConfig::heal_amount`] is positive, heal (there's no dry-run check for
+    ///    healing in this tree, unlike statuses, so this is always chosen once triggered).
+    /// 2. Otherwise, [`AiDecisionConfig::disabling_statuses`] is tried highest-score first; the
+    ///    first one whose [`InflictOptions::check_only`] probe against `nearest_target` succeeds
+    ///    (not already affected, not immune) is chosen. Statuses that fail the probe are treated
+    ///    as unusable and skipped, never selected.
+    /// 3. Otherwise, if [`AiDecisionConfig::self_boost`] is set, [`EnemyAiAction::BoostOwnStat`]
+    ///    (there's no dry-run check for stat boosts either, so this is always chosen once reached).
+    /// 4. If nothing above applies, [`EnemyAiAction::FallThroughToNormalAttack`].
+    ///
+    /// Note: unverified, ported from Irdkwia's notes (the `hp_max` field name).
+    pub fn choose_enemy_action(
+        &mut self,
+        actor: &mut DungeonEntity,
+        nearest_target: &mut DungeonEntity,
+        config: &AiDecisionConfig,
+    ) -> EnemyAiAction {
+        let hp_fraction = actor
+            .info_for_monster()
+            .filter(|m| m.0.hp_max > 0)
+            .map(|m| I24F8::from_num(m.0.hp_current) / I24F8::from_num(m.0.hp_max));
+
+        if config.heal_amount > 0 && hp_fraction.is_some_and(|f| f <= config.heal_hp_fraction) {
+            return EnemyAiAction::HealSelf {
+                amount: config.heal_amount,
+                max_hp_boost: config.heal_max_hp_boost,
+            };
+        }
+
+        let mut ranked = config.disabling_statuses.clone();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1));
+        for (status, _score) in ranked {
+            let probe_opts = InflictOptions {
+                log_failure: false,
+                check_only: true,
+            };
+            if self.try_inflict_status(actor, nearest_target, status, probe_opts).applied() {
+                return EnemyAiAction::InflictStatus { status };
+            }
+        }
+
+        if let Some(boost) = config.self_boost {
+            return EnemyAiAction::BoostOwnStat {
+                stat_idx: boost.stat_idx,
+                n_stages: boost.n_stages,
+            };
+        }
+
+        EnemyAiAction::FallThroughToNormalAttack
+    }
+
     /// Lowers the specified offensive stat on the target monster.
     ///
     /// `param_5` and `param_6` are unknown.
@@ -778,6 +2113,82 @@ impl<'a> DungeonEffectsEmitter<'a> {
         unsafe { ffi::LowerSpeed(attacker, defender, n_stages, log_failure as ffi::bool_) }
     }
 
+    /// Applies every stat change configured on `change` to `defender` in one call, the way a
+    /// single move (eg. Charm, Screech, Memento) touches several stat kinds on the same turn.
+    ///
+    /// Each field on [`StatChange`] is independent: a stage change and a multiplier on the same
+    /// stat kind are both applied (the underlying game functions already compose a stage and a
+    /// multiplier correctly, per Hercules' `status.c` stacking model), and any combination of
+    /// offensive/defensive stages, accuracy/evasion, speed, and multipliers can be requested at
+    /// once. See [`StatChange`]'s fields for which method backs each one.
+    ///
+    /// Returns a [`StatChangeResult`] recording which of the requested changes were applied. Most
+    /// of the underlying functions don't report per-change success/failure, so (mirroring
+    /// [`Self::try_inflict_status`]'s handling of the same situation) a field is `true`
+    /// unconditionally whenever the corresponding [`StatChange`] field was set.
+    pub fn apply_stat_change(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        change: &StatChange,
+    ) -> StatChangeResult {
+        let mut result = StatChangeResult::default();
+
+        if let Some((stat_idx, n_stages)) = change.offensive_stage {
+            self.boost_offensive_stat(attacker, defender, stat_idx, n_stages);
+            result.offensive_stage = true;
+        }
+        if let Some((stat_idx, n_stages)) = change.defensive_stage {
+            self.boost_defensive_stat(attacker, defender, stat_idx, n_stages);
+            result.defensive_stage = true;
+        }
+        match change.hit_chance_stage {
+            Some(HitChanceChange::Boost { stat_idx }) => {
+                self.boost_hit_chance_stat(attacker, defender, stat_idx);
+                result.hit_chance_stage = true;
+            }
+            Some(HitChanceChange::Lower { stat_idx, param_4 }) => {
+                self.lower_hit_chance_stat(attacker, defender, stat_idx, param_4);
+                result.hit_chance_stage = true;
+            }
+            None => {}
+        }
+        if let Some((n_stages, n_turns)) = change.speed_stage {
+            self.boost_speed(attacker, defender, n_stages, n_turns, change.log_failure);
+            result.speed_stage = true;
+        }
+        if let Some((stat_idx, multiplier)) = change.offensive_multiplier {
+            // SAFETY: We have a lease on the overlay existing; param_5 is passed as 0, matching
+            // every other unknown-undefined-param call site in this crate that has no caller-
+            // supplied value to thread through.
+            unsafe {
+                ffi::ApplyOffensiveStatMultiplier(
+                    attacker,
+                    defender,
+                    stat_idx,
+                    multiplier,
+                    0 as ffi::undefined,
+                );
+            }
+            result.offensive_multiplier = true;
+        }
+        if let Some((stat_idx, multiplier)) = change.defensive_multiplier {
+            // SAFETY: See the offensive_multiplier branch above.
+            unsafe {
+                ffi::ApplyDefensiveStatMultiplier(
+                    attacker,
+                    defender,
+                    stat_idx,
+                    multiplier,
+                    0 as ffi::undefined,
+                );
+            }
+            result.defensive_multiplier = true;
+        }
+
+        result
+    }
+
     /// Randomly boosts or lowers the speed of the target monster by one stage with equal
     /// probability.
     ///
@@ -992,21 +2403,911 @@ impl<'a> DungeonEffectsEmitter<'a> {
         }
     }
 
-    /// Move effect: Deal damage.
-    /// Relevant moves: Many!
+    /// Deals damage over an [`AreaShape`] footprint radiating from `origin` in
+    /// `origin_direction` (ignored by [`AreaShape::Burst`]), the cone/beam pattern mod moves like
+    /// breath attacks or explosions need instead of a single [`Self::deal_damage`] call against
+    /// one target.
+    ///
+    /// Builds on the same tile-walking idea [`Self::is_target_in_range`] and
+    /// [`DungeonTrajectory::trace`] use for directional checks: a [`AreaShape::Line`] stops at
+    /// the first wall, and a [`AreaShape::Cone`] widens by one tile per step outward (capped at
+    /// `width` tiles per side), also stopping its centerline at the first wall.
+    ///
+    /// Every monster standing on an affected tile, other than `attacker` itself, is hit via
+    /// [`Self::deal_damage`], with `base_multiplier` scaled by `distance_falloff` (if given,
+    /// called with the tile's distance from `origin`) before being applied.
+    ///
+    /// Returns the damage dealt to each hit monster, in the order their tiles were walked. There
+    /// isn't a `DungeonEntityRef`/stable handle in this crate to return alongside each damage
+    /// value: the entity is already borrowed out of `global_dungeon_struct` to make the
+    /// [`Self::deal_damage`] call itself, and handing that borrow back out would outlive it.
+    pub fn deal_damage_area(
+        &mut self,
+        global_dungeon_struct: &mut GlobalDungeonData,
+        attacker: &mut DungeonEntity,
+        origin: ffi::position,
+        origin_direction: Direction,
+        shape: AreaShape,
+        used_move: &Move,
+        base_multiplier: I24F8,
+        item_id: Option<ItemId>,
+        distance_falloff: Option<impl Fn(i32) -> I24F8>,
+    ) -> Vec<i32> {
+        let mut damages = Vec::new();
+        for (tile_position, distance) in walk_area(origin, origin_direction, shape) {
+            let multiplier = match &distance_falloff {
+                Some(falloff) => base_multiplier * falloff(distance),
+                None => base_multiplier,
+            };
+
+            for defender in global_dungeon_struct
+                .inner_mut()
+                .get_entities_mut()
+                .get_active_monsters_mut()
+            {
+                if core::ptr::eq(defender as *const _, attacker as *const _) {
+                    continue;
+                }
+                let on_tile = defender.get_tile().is_some_and(|tile| {
+                    // SAFETY: GetTileSafe bounds-checks the coordinates itself.
+                    let expected = unsafe {
+                        &*ffi::GetTileSafe(tile_position.x as i32, tile_position.y as i32)
+                    };
+                    core::ptr::eq(tile, expected)
+                });
+                if on_tile {
+                    damages.push(self.deal_damage(attacker, defender, used_move, multiplier, item_id));
+                }
+            }
+        }
+        damages
+    }
+
+    /// Generalizes [`Self::deal_damage_area`]'s tile-walking into an arbitrary per-target
+    /// callback, so any single-target `do_move_*`/stat method on this struct can be promoted to
+    /// an area effect without a bespoke loop over entities for every move that needs one.
+    ///
+    /// Enumerates the tiles `shape` covers from `origin` (outward in `origin_direction`, ignored
+    /// by [`AreaShape::Burst`]/[`AreaShape::Room`]), resolves every monster standing on one,
+    /// nearest-first (ties within a tile broken by [`EntityTableWrite::get_active_monsters_mut`]'s
+    /// own order), other than `attacker` itself, and -- for any that pass `filter` -- calls
+    /// `on_target` with `self`, `attacker`, and the monster as the defender.
+    ///
+    /// `on_target` takes `&mut Self` so it can re-enter methods like [`Self::do_move_damage`] or
+    /// [`Self::try_inflict_status`] against each resolved defender, the same way a single-target
+    /// move body would call them directly.
+    pub fn apply_to_targets(
+        &mut self,
+        global_dungeon_struct: &mut GlobalDungeonData,
+        attacker: &mut DungeonEntity,
+        origin: ffi::position,
+        origin_direction: Direction,
+        shape: AreaShape,
+        filter: TargetFilter,
+        mut on_target: impl FnMut(&mut Self, &mut DungeonEntity, &mut DungeonEntity),
+    ) {
+        if shape == AreaShape::Room {
+            // SAFETY: GetTileSafe bounds-checks the coordinates itself.
+            let origin_tile = unsafe { &*ffi::GetTileSafe(origin.x as i32, origin.y as i32) };
+            let origin_region = RegionTag::from_room_value(origin_tile.room);
+            let origin_in_room = matches!(origin_region, RegionTag::Room(_));
+
+            for defender in global_dungeon_struct
+                .inner_mut()
+                .get_entities_mut()
+                .get_active_monsters_mut()
+            {
+                if core::ptr::eq(defender as *const _, attacker as *const _) {
+                    continue;
+                }
+                // Outside a room, only the origin's own tile counts (every hallway tile shares
+                // the same tag).
+                let in_room = defender.get_tile().is_some_and(|tile| {
+                    if origin_in_room {
+                        RegionTag::from_room_value(tile.room) == origin_region
+                    } else {
+                        core::ptr::eq(tile, origin_tile)
+                    }
+                });
+                if in_room && passes_target_filter(attacker, defender, filter) {
+                    on_target(self, attacker, defender);
+                }
+            }
+            return;
+        }
+
+        for (tile_position, _distance) in walk_area(origin, origin_direction, shape) {
+            for defender in global_dungeon_struct
+                .inner_mut()
+                .get_entities_mut()
+                .get_active_monsters_mut()
+            {
+                if core::ptr::eq(defender as *const _, attacker as *const _) {
+                    continue;
+                }
+                let on_tile = defender.get_tile().is_some_and(|tile| {
+                    // SAFETY: GetTileSafe bounds-checks the coordinates itself.
+                    let expected = unsafe {
+                        &*ffi::GetTileSafe(tile_position.x as i32, tile_position.y as i32)
+                    };
+                    core::ptr::eq(tile, expected)
+                });
+                if on_tile && passes_target_filter(attacker, defender, filter) {
+                    on_target(self, attacker, defender);
+                }
+            }
+        }
+    }
+
+    /// Opens a pit under the target, the kind of terrain-altering attack a custom monster or
+    /// move might use: converts the tile under `target` into a pit (walkable ground it falls
+    /// through), with a small chance of it becoming a chasm ("hole") instead.
+    ///
+    /// Flying/levitating targets are reported back as not trapped (and are not made to fall),
+    /// so the caller can decide what, if anything, to do about them (e.g. show a "but it
+    /// failed" message).
+    ///
+    /// Has no effect if `target` isn't currently standing on a tile.
+    pub fn open_pit_under(&mut self, target: &mut DungeonEntity) -> PitfallResult {
+        let rng = DungeonRng::new(self.0.clone());
+        let is_chasm = rng.rand_outcome(PIT_CHASM_CHANCE_PERCENT);
+
+        let levitating = target
+            .info_for_monster()
+            .map(|m| m.is_levitating())
+            .unwrap_or(false);
+        let trapped = !levitating;
+
+        let has_tile = if let Some(tile) = target.get_tile_mut() {
+            if is_chasm {
+                tile.convert_to_chasm();
+            } else {
+                tile.set_terrain(TerrainType::Normal);
+            }
+            true
+        } else {
+            false
+        };
+
+        if has_tile && trapped {
+            // SAFETY: We have a lease on the overlay existing.
+            unsafe { ffi::TryPitfallTrapFall(target, is_chasm as ffi::bool_) };
+        }
+
+        PitfallResult {
+            is_chasm: has_tile && is_chasm,
+            trapped: has_tile && trapped,
+        }
+    }
+
+    /// Move effect: Deal damage.
+    /// Relevant moves: Many!
+    ///
+    /// This just wraps DealDamage with a multiplier of 1 (i.e., the fixed-point number 0x100).
+    ///
+    /// Returns whether or not damage was dealt
+    pub fn do_move_damage(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+    ) -> bool {
+        // SAFETY: We have a lease on the overlay existing.
+        unsafe { ffi::DoMoveDamage(attacker, defender, force_mut_ptr!(the_move), item_id) > 0 }
+    }
+
+    /// Like [`Self::do_move_damage`], but returns a [`MoveResult`] instead of a bare `bool`, so
+    /// callers writing custom AI, logging, or combo moves can react to what actually happened
+    /// instead of re-reading raw struct fields manually.
+    ///
+    /// Built from [`Self::move_hit_check`] (the real accuracy roll) and [`Self::deal_damage`]
+    /// (the real damage application, with the same `1x` multiplier [`Self::do_move_damage`]
+    /// itself uses). `attack_type` is an explicit parameter since this crate has no accessor for
+    /// a move's own type (same rationale as [`Self::hit_probability`]'s stages); it's only used
+    /// for the [`MoveResult::type_effectiveness`] lookup. See [`MoveResult`]'s doc comment for
+    /// how [`MoveResult::was_critical`] is determined.
+    pub fn do_move_damage_detailed(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+        attack_type: MonsterTypeId,
+    ) -> MoveResult {
+        if !self.move_hit_check(attacker, defender, the_move, false) {
+            return MoveResult::default();
+        }
+
+        let was_critical = rand_i32(0..100) < the_move.get_crit_chance();
+        let damage_dealt = self.deal_damage(
+            attacker,
+            defender,
+            the_move,
+            I24F8::from_num(1),
+            Some(item_id),
+        );
+        let type_effectiveness = attacker
+            .info_for_monster()
+            .and_then(|m| m.get_type_matchup(defender, TargetTypeIndex::FirstType, attack_type));
+        let target_fainted = defender
+            .info_for_monster()
+            .is_some_and(|m| m.0.hp_current <= 0);
+
+        MoveResult {
+            used: true,
+            damage_dealt,
+            was_critical,
+            type_effectiveness,
+            statuses_inflicted: Vec::new(),
+            target_fainted,
+        }
+    }
+
+    /// Deals damage via [`Self::do_move_damage`], then, only if it landed, rolls each of
+    /// `effects`' [`SecondaryEffect::chance_percent`] independently and dispatches whichever
+    /// connect to the matching already-wrapped primitive on this impl.
+    ///
+    /// This is PkmnLib's secondary-effect model (an effect plus a trigger chance, attached
+    /// directly to the move instead of baked into a dedicated FFI wrapper): romhackers composing
+    /// a custom damaging move with a chance of flinch, a stat drop, a status, or a drain no longer
+    /// need a new `do_move_*` stub per combination, the way the base game's
+    /// `do_move_damage_cringe_30`/`do_move_damage_lower_def_20`/`do_move_paralyze_20` etc. each
+    /// hardcode one fixed chance and effect.
+    ///
+    /// Returns whether the move dealt damage, same as [`Self::do_move_damage`].
+    pub fn do_move_damage_with_effects(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+        effects: &[SecondaryEffect],
+    ) -> bool {
+        let hit = self.do_move_damage(attacker, defender, the_move, item_id);
+        if !hit {
+            return hit;
+        }
+
+        for secondary in effects {
+            if rand_i32(0..100) >= secondary.chance_percent as i32 {
+                continue;
+            }
+            match secondary.effect {
+                MoveSecondaryEffect::InflictStatus(status) => {
+                    self.try_inflict_status(attacker, defender, status, InflictOptions::default());
+                }
+                MoveSecondaryEffect::LowerStat { stat_idx, stages } => {
+                    self.boost_defensive_stat(attacker, defender, stat_idx, -stages);
+                }
+                MoveSecondaryEffect::BoostStat { stat_idx, stages } => {
+                    self.boost_defensive_stat(attacker, defender, stat_idx, stages);
+                }
+                MoveSecondaryEffect::Flinch => {
+                    self.try_inflict_cringe_status(attacker, defender, true, false);
+                }
+                MoveSecondaryEffect::Heal(hp_to_restore) => {
+                    // The attacker heals itself, so there's only one entity to borrow; matches the
+                    // same-entity raw-pointer pattern `DungeonMonsterWrite::restore_hp` uses.
+                    let attacker_ptr = attacker as *mut DungeonEntity;
+                    unsafe {
+                        ffi::TryIncreaseHp(
+                            attacker_ptr,
+                            attacker_ptr,
+                            hp_to_restore,
+                            0,
+                            false as ffi::bool_,
+                        );
+                    }
+                }
+            }
+        }
+
+        hit
+    }
+
+    /// Like [`Self::do_move_damage_with_effects`], but for the common case of exactly one
+    /// [`SecondaryEffect`] -- a single parameterized combinator in place of the base game's
+    /// proliferation of one dedicated `do_move_damage_*` wrapper per fixed chance-and-rider
+    /// combination (e.g. [`Self::do_move_damage_poison_40`], [`Self::do_move_damage_freeze_15`],
+    /// [`Self::do_move_damage_lower_speed_20`], [`Self::do_move_damage_lower_accuracy_40`]):
+    /// romhackers authoring a *new* move with a chance-based rider no longer need a matching FFI
+    /// symbol to exist for it.
+    ///
+    /// Note: the named wrappers above stay dedicated FFI calls rather than being rewired to go
+    /// through this -- they're thin bindings over the base game's own `ffi::DoMove*` assembly for
+    /// those specific moves, and reimplementing that behavior here (a different RNG call site, no
+    /// guarantee of matching whatever move-specific extras the original routine has) would
+    /// silently change already-verified vanilla move behavior. This combinator is for new
+    /// chance-and-rider combinations that don't have one of those symbols to call.
+    ///
+    /// Returns whether the move dealt damage, same as [`Self::do_move_damage`].
+    pub fn do_move_damage_with_secondary(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+        secondary: SecondaryEffect,
+    ) -> bool {
+        self.do_move_damage_with_effects(attacker, defender, the_move, item_id, &[secondary])
+    }
+
+    /// Resolves a move as a hit, then (modeled on NetHack's Magicbane) a weighted-random pick of
+    /// at most one effect from `effects`, rather than every [`SecondaryEffect`] in
+    /// [`Self::do_move_damage_with_effects`] getting its own independent roll.
+    ///
+    /// After [`Self::do_move_damage`] lands, draws one value from the shared dungeon RNG (via
+    /// [`rand_i32`], over `0..denominator`) and walks `effects` in order, accumulating each
+    /// [`WeightedEffect::numerator`], until the draw falls within one's accumulated range --
+    /// selecting exactly one entry. `effects`' numerators don't need to sum to `denominator`;
+    /// a draw past every accumulated weight selects nothing, same as Magicbane rolling no
+    /// special power. Drawing from [`rand_i32`] (rather than, say, a host-side RNG) keeps the
+    /// pick part of the same deterministic sequence a seeded replay would reproduce.
+    ///
+    /// The selected effect's [`WeightedEffect::message`] is logged for the attacker before its
+    /// closure runs, against the already-post-hit state (the damage is applied before the roll,
+    /// same as Magicbane resolving its secondary power after the main hit lands).
+    ///
+    /// Returns whether the move dealt damage; `false` means no effect was rolled for either,
+    /// since the hit never landed.
+    pub fn apply_weighted_secondary(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+        denominator: u32,
+        effects: &[WeightedEffect],
+    ) -> bool {
+        let hit = self.do_move_damage(attacker, defender, the_move, item_id);
+        if !hit {
+            return false;
+        }
+
+        let roll = rand_i32(0..denominator.max(1) as i32) as u32;
+        let mut accumulated = 0u32;
+        for effect in effects {
+            accumulated += effect.numerator;
+            if roll < accumulated {
+                LogMessageBuilder::new(self.0.clone()).log_str(attacker, effect.message);
+                (effect.effect)(self, attacker, defender);
+                break;
+            }
+        }
+
+        true
+    }
+
+    /// Move effect: attempts to steal the defender's held item into the attacker's bag, modeled
+    /// on NetHack's `steal_it` -- unlike the fixed [`Self::do_move_thief`]/[`Self::do_move_knock_off`]
+    /// FFI wrappers (thin bindings over the base game's own assembly for those two specific
+    /// moves), this is assembled from safe primitives so a patch can author new theft-flavored
+    /// moves (Covet, a custom Thief variant, etc.) without a matching native symbol.
+    ///
+    /// Theft only happens on a successful contact hit, so this resolves the hit via
+    /// [`Self::do_move_damage`] first, same spirit as `theft_petrifies` only firing on a landed
+    /// attack. If the hit lands and `held_item` isn't [`Item::is_sticky`] (NetHack's "can't be
+    /// freely dropped or moved" cursed-item case), attempts to move it into `attacker_bag` via
+    /// [`InventoryBag::add_item`] (crediting `attacker_index` as the new holder); if the bag is
+    /// full, `add_item` fails and nothing else happens. On a successful add, removes the item
+    /// from the defender via [`InventoryBag::remove_held_item_no_hole`] at `defender_held_index`,
+    /// then consults `theft_hooks` against the stolen item, inflicting the first non-`None`
+    /// [`TheftStatusHook::status_for_stolen_item`] result on the attacker -- the general version
+    /// of `theft_petrifies` ("stealing certain flagged items triggers a status on the thief").
+    ///
+    /// This crate has no generic "get a monster's currently held item" accessor (only
+    /// [`DungeonMonsterRead::is_holding_item`]/[`DungeonMonsterRead::has_held_item`], both of
+    /// which require a known [`ItemId`] rather than returning one), so the caller supplies
+    /// `held_item` and `defender_held_index` directly, the same way those methods require a
+    /// known `item_id` up front.
+    ///
+    /// Returns whether the item was actually stolen. A miss, a sticky item, or a full attacker
+    /// bag all return `false` without moving the item (damage from a hit that landed still
+    /// stands).
+    pub fn do_move_steal_held_item(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+        held_item: &mut Item,
+        defender_held_index: i32,
+        attacker_index: i32,
+        attacker_bag: &mut InventoryBag,
+        theft_hooks: &mut TheftStatusRegistry,
+    ) -> bool {
+        let hit = self.do_move_damage(attacker, defender, the_move, item_id);
+        if !hit || held_item.is_sticky() {
+            return false;
+        }
+
+        if !attacker_bag.add_item(held_item, Some(attacker_index)) {
+            return false;
+        }
+        attacker_bag.remove_held_item_no_hole(defender_held_index);
+
+        for hook in &mut theft_hooks.hooks {
+            if let Some(status) = hook.status_for_stolen_item(held_item) {
+                // SAFETY: the thief is both the attacker and defender of its own status
+                // infliction, same as `apply_cloud_tick`'s `Paralysis` case.
+                let attacker_ptr = attacker as *mut DungeonEntity;
+                unsafe {
+                    self.try_inflict_status(
+                        &mut *attacker_ptr,
+                        &mut *attacker_ptr,
+                        status,
+                        InflictOptions::default(),
+                    );
+                }
+                break;
+            }
+        }
+
+        true
+    }
+
+    /// Like [`Self::do_move_damage_with_effects`], but returns a [`MoveResult`] (via
+    /// [`Self::do_move_damage_detailed`]) instead of a bare `bool`, with
+    /// [`MoveResult::statuses_inflicted`] recording every [`MoveSecondaryEffect::InflictStatus`]
+    /// that actually rolled a hit and was applied (per [`InflictOutcome::applied`]), in roll
+    /// order.
+    pub fn do_move_damage_with_effects_detailed(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+        attack_type: MonsterTypeId,
+        effects: &[SecondaryEffect],
+    ) -> MoveResult {
+        let mut result =
+            self.do_move_damage_detailed(attacker, defender, the_move, item_id, attack_type);
+        if !result.used {
+            return result;
+        }
+
+        for secondary in effects {
+            if rand_i32(0..100) >= secondary.chance_percent as i32 {
+                continue;
+            }
+            match secondary.effect {
+                MoveSecondaryEffect::InflictStatus(status) => {
+                    let outcome =
+                        self.try_inflict_status(attacker, defender, status, InflictOptions::default());
+                    if outcome.applied() {
+                        result.statuses_inflicted.push(status);
+                    }
+                }
+                MoveSecondaryEffect::LowerStat { stat_idx, stages } => {
+                    self.boost_defensive_stat(attacker, defender, stat_idx, -stages);
+                }
+                MoveSecondaryEffect::BoostStat { stat_idx, stages } => {
+                    self.boost_defensive_stat(attacker, defender, stat_idx, stages);
+                }
+                MoveSecondaryEffect::Flinch => {
+                    self.try_inflict_cringe_status(attacker, defender, true, false);
+                }
+                MoveSecondaryEffect::Heal(hp_to_restore) => {
+                    let attacker_ptr = attacker as *mut DungeonEntity;
+                    unsafe {
+                        ffi::TryIncreaseHp(
+                            attacker_ptr,
+                            attacker_ptr,
+                            hp_to_restore,
+                            0,
+                            false as ffi::bool_,
+                        );
+                    }
+                }
+            }
+        }
+
+        result.target_fainted = defender
+            .info_for_monster()
+            .is_some_and(|m| m.0.hp_current <= 0);
+
+        result
+    }
+
+    /// Resolves `spec` against `attacker`/`defender`/`the_move`: deals damage at
+    /// `spec.power_multiplier` (or [`MoveEffectSpec::below_half_hp_multiplier`] instead, if set
+    /// and the defender is at or below half its max HP -- generalizing
+    /// [`Self::do_move_brine`]'s hardcoded 2x), via [`Self::do_move_damage_eat_item`] if
+    /// [`MoveEffectSpec::consumes_item`] is set, or [`Self::deal_damage`] otherwise; then, if the
+    /// move hit, rolls `spec.secondary`'s chance the same way
+    /// [`Self::do_move_damage_with_effects`] does.
+    ///
+    /// This is the data-driven counterpart to constant-coded wrappers like
+    /// [`Self::do_move_damage_constrict_10`]/[`Self::do_move_damage_lower_special_defence_50`]:
+    /// romhackers can describe a new move's power curve and rider as a [`MoveEffectSpec`] value
+    /// instead of needing a new `ffi::DoMove*` symbol for each combination.
+    ///
+    /// Returns whether the move dealt damage. When [`MoveEffectSpec::consumes_item`] is set, this
+    /// is [`Self::do_move_damage_eat_item`]'s own success/failure; otherwise it's `true` unless
+    /// [`Self::move_hit_check`] misses.
+    pub fn apply_move_effect(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+        spec: &MoveEffectSpec,
+    ) -> bool {
+        let hit = if spec.consumes_item {
+            self.do_move_damage_eat_item(attacker, defender, the_move, item_id)
+        } else {
+            if !self.move_hit_check(attacker, defender, the_move, false) {
+                return false;
+            }
+
+            let below_half_hp = defender
+                .info_for_monster()
+                .filter(|m| m.0.hp_max > 0)
+                .is_some_and(|m| {
+                    I24F8::from_num(m.0.hp_current)
+                        <= I24F8::from_num(m.0.hp_max) / I24F8::from_num(2)
+                });
+            let multiplier = match spec.below_half_hp_multiplier {
+                Some(low_hp_multiplier) if below_half_hp => low_hp_multiplier,
+                _ => spec.power_multiplier,
+            };
+
+            self.deal_damage(
+                attacker,
+                defender,
+                the_move,
+                I24F8::from_num(multiplier),
+                Some(item_id),
+            );
+            true
+        };
+
+        if hit {
+            if let Some(secondary) = &spec.secondary {
+                if rand_i32(0..100) < secondary.chance_percent as i32 {
+                    match secondary.effect {
+                        MoveSecondaryEffect::InflictStatus(status) => {
+                            self.try_inflict_status(
+                                attacker,
+                                defender,
+                                status,
+                                InflictOptions::default(),
+                            );
+                        }
+                        MoveSecondaryEffect::LowerStat { stat_idx, stages } => {
+                            self.boost_defensive_stat(attacker, defender, stat_idx, -stages);
+                        }
+                        MoveSecondaryEffect::BoostStat { stat_idx, stages } => {
+                            self.boost_defensive_stat(attacker, defender, stat_idx, stages);
+                        }
+                        MoveSecondaryEffect::Flinch => {
+                            self.try_inflict_cringe_status(attacker, defender, true, false);
+                        }
+                        MoveSecondaryEffect::Heal(hp_to_restore) => {
+                            let attacker_ptr = attacker as *mut DungeonEntity;
+                            unsafe {
+                                ffi::TryIncreaseHp(
+                                    attacker_ptr,
+                                    attacker_ptr,
+                                    hp_to_restore,
+                                    0,
+                                    false as ffi::bool_,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        hit
+    }
+
+    /// Resolves `do_move` (normally one of this impl's `do_move_damage*_detailed` calls, since
+    /// [`MoveResult::damage_dealt`] is what [`AttackBrand::Vampiric`] needs), then, only if it
+    /// hit, applies `brand` on top -- a rider attached independently of which move was used, the
+    /// way Dungeon Crawl separates an attack from the brand carried by the weapon delivering it.
+    /// This lets item- or ability-driven brands be layered onto any damaging move uniformly,
+    /// instead of being hardcoded into one `ffi::DoMove*` symbol each the way
+    /// [`Self::do_move_absorb`]/[`Self::do_move_damage_drain`] are.
+    ///
+    /// Any status the brand successfully inflicts is appended to the returned
+    /// [`MoveResult::statuses_inflicted`], same as the `do_move_damage_with_effects*` family.
+    pub fn do_move_damage_with_brand(
+        &mut self,
+        brand: &AttackBrand,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        do_move: impl FnOnce(&mut Self, &mut DungeonEntity, &mut DungeonEntity) -> MoveResult,
+    ) -> MoveResult {
+        let mut result = do_move(self, attacker, defender);
+        if result.used {
+            self.apply_attack_brand(brand, attacker, defender, &mut result);
+        }
+        result
+    }
+
+    fn apply_attack_brand(
+        &mut self,
+        brand: &AttackBrand,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        result: &mut MoveResult,
+    ) {
+        match brand {
+            AttackBrand::Vampiric { percent } => {
+                let heal = result.damage_dealt * i32::from(*percent) / 100;
+                if heal > 0 {
+                    // The attacker heals itself, so there's only one entity to borrow; matches
+                    // `do_move_damage_with_effects`'s `Heal` case.
+                    let attacker_ptr = attacker as *mut DungeonEntity;
+                    unsafe {
+                        ffi::TryIncreaseHp(attacker_ptr, attacker_ptr, heal, 0, false as ffi::bool_);
+                    }
+                }
+            }
+            AttackBrand::Freezing { chance_percent } => {
+                if rand_i32(0..100) < i32::from(*chance_percent) {
+                    let outcome = self.try_inflict_status(
+                        attacker,
+                        defender,
+                        StatusCondition::Frozen,
+                        InflictOptions::default(),
+                    );
+                    if outcome.applied() {
+                        result.statuses_inflicted.push(StatusCondition::Frozen);
+                    }
+                }
+            }
+            AttackBrand::Flaming { chance_percent } => {
+                if rand_i32(0..100) < i32::from(*chance_percent) {
+                    let status = StatusCondition::Burn {
+                        special_effect: false,
+                    };
+                    let outcome =
+                        self.try_inflict_status(attacker, defender, status, InflictOptions::default());
+                    if outcome.applied() {
+                        result.statuses_inflicted.push(status);
+                    }
+                }
+            }
+            AttackBrand::Draining {
+                stat_idx_choices,
+                stages,
+            } => {
+                if !stat_idx_choices.is_empty() {
+                    let stat_idx = stat_idx_choices[rand_i32(0..stat_idx_choices.len() as i32) as usize];
+                    self.boost_defensive_stat(attacker, defender, stat_idx, -*stages);
+                }
+            }
+            AttackBrand::Chaos { choices } => {
+                if !choices.is_empty() {
+                    let chosen = &choices[rand_i32(0..choices.len() as i32) as usize];
+                    self.apply_attack_brand(chosen, attacker, defender, result);
+                }
+            }
+        }
+    }
+
+    /// Resolves a damaging move with `hooks` run around it, so registered
+    /// [`MoveEffectHook`]s (abilities, held items) can intercept and adjust the move uniformly.
+    /// See [`MoveEffectHook`]'s doc comment for exactly what each stage can and can't influence.
+    ///
+    /// Runs, in order: every hook's [`MoveEffectHook::before_move`] (cancelling if any returns
+    /// `false`); [`Self::move_hit_check`] combined with every hook's
+    /// [`MoveEffectHook::modify_accuracy`] roll (both must pass); [`Self::deal_damage`] with a
+    /// multiplier folded in from every hook's [`MoveEffectHook::modify_damage`]; then every
+    /// hook's [`MoveEffectHook::after_move`] with the resulting [`MoveEffectOutcome`].
+    ///
+    /// Returns whether the move dealt damage.
+    pub fn do_move_damage_with_hooks(
+        &mut self,
+        hooks: &mut MoveEffectHooks,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+    ) -> bool {
+        for hook in &mut hooks.hooks {
+            if !hook.before_move(attacker, defender, the_move) {
+                for hook in &mut hooks.hooks {
+                    hook.after_move(attacker, defender, MoveEffectOutcome::NotApplied);
+                }
+                return false;
+            }
+        }
+
+        let ctx = MoveEffectContext {
+            the_move,
+            item_id: Some(item_id),
+        };
+
+        let mut accuracy_percent = 100;
+        for hook in &mut hooks.hooks {
+            hook.modify_accuracy(&ctx, &mut accuracy_percent);
+        }
+        let hit = self.move_hit_check(attacker, defender, the_move, false)
+            && rand_i32(0..100) < accuracy_percent.clamp(0, 100);
+        if !hit {
+            for hook in &mut hooks.hooks {
+                hook.after_move(attacker, defender, MoveEffectOutcome::NotApplied);
+            }
+            return false;
+        }
+
+        let mut damage_multiplier_percent = 100;
+        for hook in &mut hooks.hooks {
+            hook.modify_damage(&ctx, &mut damage_multiplier_percent);
+        }
+        let multiplier = I24F8::from_num(damage_multiplier_percent) / I24F8::from_num(100);
+        self.deal_damage(attacker, defender, the_move, multiplier, Some(item_id));
+
+        for hook in &mut hooks.hooks {
+            hook.after_move(
+                attacker,
+                defender,
+                MoveEffectOutcome::Applied { dealt_damage: true },
+            );
+        }
+
+        true
+    }
+
+    /// Like [`Self::do_move_damage_with_hooks`], but dispatches through a [`MoveEffectRegistry`]
+    /// keyed by `the_move`'s ID instead of an explicit per-call hook list: a patch author
+    /// registers a [`MoveEffect`] once, against the move (or [`MoveEffectRegistry::GLOBAL_MOVE`])
+    /// it patches, and every future use of that move through this method picks it up
+    /// automatically.
+    ///
+    /// Requires [`DungeonMonsterRead::predict_damage`] to seed [`MoveEffect::modify_damage`]'s
+    /// starting damage value, so returns `false` without running any effect if `attacker` isn't
+    /// a monster.
+    pub fn do_move_damage_with_registry(
+        &mut self,
+        registry: &mut MoveEffectRegistry,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+    ) -> bool {
+        let Some(attacker_monster) = attacker.info_for_monster() else {
+            return false;
+        };
+        let prediction = attacker_monster.predict_damage(defender, the_move, false);
+        let move_id = the_move.id.val();
+
+        for entry in registry.active_effects_for(move_id) {
+            let ctx = MoveEffectRunContext {
+                attacker: &*attacker,
+                defender: &*defender,
+                the_move,
+                item_id,
+            };
+            if !entry.effect.on_before_move(&ctx) {
+                return false;
+            }
+        }
+
+        let mut accuracy_percent = 100;
+        for entry in registry.active_effects_for(move_id) {
+            let ctx = MoveEffectRunContext {
+                attacker: &*attacker,
+                defender: &*defender,
+                the_move,
+                item_id,
+            };
+            entry.effect.modify_accuracy(&ctx, &mut accuracy_percent);
+        }
+        let hit = self.move_hit_check(attacker, defender, the_move, false)
+            && rand_i32(0..100) < accuracy_percent.clamp(0, 100);
+        if !hit {
+            return false;
+        }
+
+        let mut damage = prediction.damage.unwrap_or(0);
+        for entry in registry.active_effects_for(move_id) {
+            let ctx = MoveEffectRunContext {
+                attacker: &*attacker,
+                defender: &*defender,
+                the_move,
+                item_id,
+            };
+            entry.effect.modify_damage(&ctx, &mut damage);
+        }
+
+        // The engine's own damage formula is opaque (see `MoveEffectHook::modify_damage`'s
+        // note), so the only way to feed `modify_damage`'s adjustment back in is as a multiplier
+        // relative to the un-adjusted prediction.
+        let multiplier = match prediction.damage {
+            Some(predicted) if predicted != 0 => {
+                I24F8::from_num(damage) / I24F8::from_num(predicted)
+            }
+            _ => I24F8::from_num(1),
+        };
+        self.deal_damage(attacker, defender, the_move, multiplier, Some(item_id));
+
+        for entry in registry.active_effects_for(move_id) {
+            entry.effect.on_hit(attacker, defender, the_move);
+        }
+
+        true
+    }
+
+    /// Runs `do_move` -- typically one of this impl's own `do_move_*` methods, passed as a
+    /// method reference (e.g. `Self::do_move_knock_off`) -- with every [`MoveInterceptHook`]
+    /// registered in `hooks` for `the_move`'s ID run around it: every hook's
+    /// [`MoveInterceptHook::prevent_move`] first (any `true` cancels the move before `do_move`
+    /// is even called), then every hook's [`MoveInterceptHook::on_before_move`], then `do_move`
+    /// itself, then every hook's [`MoveInterceptHook::on_after_move`] with the result.
+    ///
+    /// Unlike [`Self::do_move_damage_with_hooks`] (built specifically around
+    /// [`Self::deal_damage`]), this wraps whichever `do_move_*` wrapper `do_move` is -- so a mod
+    /// can, e.g., add lifesteal to every damaging move via a [`MoveHookRegistry::GLOBAL_MOVE`]
+    /// hook, without reimplementing each move's effect individually.
+    pub fn run_with_move_hooks(
+        &mut self,
+        hooks: &mut MoveHookRegistry,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+        do_move: impl FnOnce(&mut Self, &mut DungeonEntity, &mut DungeonEntity, &Move, ItemId) -> bool,
+    ) -> bool {
+        let move_id = the_move.id.val();
+
+        for hook in hooks.hooks_for(move_id) {
+            if hook.prevent_move(attacker, defender, the_move, item_id) {
+                for hook in hooks.hooks_for(move_id) {
+                    hook.on_after_move(attacker, defender, the_move, false);
+                }
+                return false;
+            }
+        }
+
+        for hook in hooks.hooks_for(move_id) {
+            hook.on_before_move(attacker, defender, the_move);
+        }
+
+        let used = do_move(self, attacker, defender, the_move, item_id);
+
+        for hook in hooks.hooks_for(move_id) {
+            hook.on_after_move(attacker, defender, the_move, used);
+        }
+
+        used
+    }
+
+    /// Dispatches `the_move` to whichever handler `table` has [`MoveDispatchTable::register`]ed
+    /// for its ID, running `hooks` around it via [`Self::run_with_move_hooks`] -- a single
+    /// integration point third-party effects, hooks, and the Rune/data-driven path can all route
+    /// through instead of each needing their own `MoveId` switch.
     ///
-    /// This just wraps DealDamage with a multiplier of 1 (i.e., the fixed-point number 0x100).
-    ///
-    /// Returns whether or not damage was dealt
-    pub fn do_move_damage(
+    /// Returns `false` without doing anything if `the_move`'s ID hasn't been registered in
+    /// `table`.
+    pub fn do_move_by_id(
         &mut self,
+        table: &MoveDispatchTable,
+        hooks: &mut MoveHookRegistry,
         attacker: &mut DungeonEntity,
         defender: &mut DungeonEntity,
         the_move: &Move,
         item_id: ItemId,
     ) -> bool {
-        // SAFETY: We have a lease on the overlay existing.
-        unsafe { ffi::DoMoveDamage(attacker, defender, force_mut_ptr!(the_move), item_id) > 0 }
+        let move_id = the_move.id.val();
+        let Some(handler) = table.entry_for(move_id).map(|entry| entry.handler) else {
+            return false;
+        };
+        self.run_with_move_hooks(
+            hooks,
+            attacker,
+            defender,
+            the_move,
+            item_id,
+            |emitter, attacker, defender, the_move, item_id| {
+                handler(emitter, attacker, defender, the_move, item_id)
+            },
+        )
     }
 
     /// Move effect: Iron Tail
@@ -1951,6 +4252,36 @@ impl<'a> DungeonEffectsEmitter<'a> {
         unsafe { ffi::DoMovePoisonGas(attacker, defender, force_mut_ptr!(the_move), item_id) > 0 }
     }
 
+    /// Like [`Self::do_move_poison_gas`], but leaves a persistent [`DungeonCloud`] hazard behind
+    /// on hit instead of applying an instantaneous status -- for a custom gas/smog-style move that
+    /// should keep re-applying `kind` to anything standing in the area, rather than once on use.
+    ///
+    /// This deals damage via the generic [`Self::do_move_damage`] rather than calling into
+    /// [`ffi::DoMovePoisonGas`]/[`ffi::DoMoveSmellingSalt`] -- those remain dedicated FFI calls for
+    /// the base game's own gas moves (see [`Self::do_move_damage_with_secondary`]'s doc comment for
+    /// why); this method is for new moves that want the lingering-cloud behavior instead.
+    ///
+    /// Returns whether or not the move dealt damage.
+    pub fn do_move_gas_with_cloud(
+        &mut self,
+        clouds: &mut DungeonCloud,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        item_id: ItemId,
+        origin: ffi::position,
+        kind: CloudKind,
+        radius: i32,
+        power: i32,
+        lifetime: u16,
+    ) -> bool {
+        let hit = self.do_move_damage(attacker, defender, the_move, item_id);
+        if hit {
+            clouds.spawn_cloud(origin, radius, kind, power, lifetime);
+        }
+        hit
+    }
+
     /// Move effect: Toxic
     ///
     /// Returns whether or not the move was successfully used.
@@ -3044,6 +5375,75 @@ impl<'a> DungeonEffectsInternals<'a> {
         ) > 0
     }
 
+    /// Like [`Self::apply_damage`], but first runs every [`DamageModifier`] in `modifiers` whose
+    /// [`DamageModifier::applies`] passes against `damage_data`, letting a mod implement
+    /// "bane"-style conditional damage bonuses (e.g. double damage against a species/type/
+    /// ability, or a flat bonus against a status) without patching every `do_move_*` entry point
+    /// individually -- the same integration-point role [`DungeonEffectsEmitter::do_move_by_id`]
+    /// plays for whole-move overrides, but scoped to the damage math just before it's applied.
+    ///
+    /// Modifiers run in registration order, each seeing the previous one's adjustment.
+    ///
+    /// # Safety
+    /// Same as [`Self::apply_damage`].
+    pub unsafe fn apply_damage_with_modifiers(
+        &mut self,
+        modifiers: &mut DamageModifierRegistry,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        damage_data: &mut ffi::damage_data,
+        param_4: ffi::undefined4,
+        param_5: *mut ffi::undefined4,
+        faint_reason: ffi::faint_reason,
+    ) -> bool {
+        for modifier in &mut modifiers.modifiers {
+            if modifier.applies(attacker, defender, the_move, damage_data) {
+                modifier.adjust(damage_data);
+            }
+        }
+        self.apply_damage(attacker, defender, damage_data, param_4, param_5, faint_reason)
+    }
+
+    /// NetHack's `FATAL_DAMAGE_MODIFIER` trick, built directly on [`Self::apply_damage`] instead
+    /// of [`DungeonEffectsEmitter::deal_guaranteed_lethal_damage`]'s multiplier trick on
+    /// [`DungeonEffectsEmitter::deal_damage`] -- for effects (an instant-KO move like a Fissure/
+    /// Sheer Cold analogue, a scripted cutscene death) that already have their own
+    /// [`ffi::damage_data`] to apply rather than a move to deal damage through.
+    ///
+    /// Computes the lethal amount as `2 * defender's current max HP + `[`FATAL_DAMAGE_MARGIN`]`,
+    /// the same threshold [`DungeonEffectsEmitter::deal_guaranteed_lethal_damage`] targets, so the
+    /// result survives the engine's own post-damage adjustments (e.g. halving physical damage)
+    /// and still faints the target. This crate has no named accessor for the raw amount field on
+    /// [`ffi::damage_data`] (nothing in this crate writes one directly -- every existing
+    /// [`Self::apply_damage`] caller hands it an already-populated struct), so `set_amount` is
+    /// supplied by the caller to perform that one field write with the real field name.
+    ///
+    /// # Invariant
+    /// This only stops being lethal once a monster's max HP exceeds roughly half of the
+    /// `i16`/`i32` range backing that field -- far beyond anything in the base game.
+    ///
+    /// # Safety
+    /// Same as [`Self::apply_damage`].
+    pub unsafe fn apply_fatal_damage(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        damage_data: &mut ffi::damage_data,
+        set_amount: impl FnOnce(&mut ffi::damage_data, i32),
+        param_4: ffi::undefined4,
+        param_5: *mut ffi::undefined4,
+        faint_reason: ffi::faint_reason,
+    ) -> bool {
+        let max_hp = defender
+            .info_for_monster()
+            .map(|m| m.0.hp_max)
+            .unwrap_or(0)
+            .max(0);
+        set_amount(damage_data, 2 * max_hp + FATAL_DAMAGE_MARGIN);
+        self.apply_damage(attacker, defender, damage_data, param_4, param_5, faint_reason)
+    }
+
     /// Determine what item a defeated enemy should drop, if any, then (probably?) spawn that
     /// item underneath them.
     ///
@@ -3112,4 +5512,935 @@ impl<'a> DungeonEffectsInternals<'a> {
             param_5,
         )
     }
+
+    /// Pre-execution gate around [`Self::execute_move_effect`]: looks `the_move`'s [`MoveFlags`]
+    /// up in `flags_table`, then consults `gates` before letting the native effect run at all, so
+    /// a mod can block or alter a move ahead of time (e.g. charge a resource cost for a
+    /// [`MoveFlags::SELF_DAMAGING`] one; gates see `attacker`, not the move's target) without patching [`Self::execute_move_effect`]
+    /// itself -- the same "don't touch the verified vanilla wrapper, add an additive one instead"
+    /// approach as [`Self::apply_damage_with_modifiers`].
+    ///
+    /// Returns [`CanUse::Block`] (without running the native effect at all) if any registered
+    /// gate whose flag is set on this move's [`MoveFlags`] returns it; otherwise runs
+    /// [`Self::execute_move_effect`] and returns [`CanUse::Allow`].
+    ///
+    /// # Safety
+    /// Same as [`Self::execute_move_effect`].
+    pub unsafe fn execute_move_effect_gated(
+        &mut self,
+        flags_table: &MoveFlagsTable,
+        gates: &MoveUseGateRegistry,
+        param_1: *mut ffi::undefined4,
+        attacker: &mut DungeonEntity,
+        the_move: &Move,
+        param_4: ffi::undefined4,
+        param_5: ffi::undefined4,
+    ) -> CanUse {
+        let flags = flags_table.flags_for(the_move.id.val());
+        if gates.check(flags, the_move, attacker) == CanUse::Block {
+            return CanUse::Block;
+        }
+        self.execute_move_effect(param_1, attacker, the_move, param_4, param_5);
+        CanUse::Allow
+    }
+}
+
+/// A single conditional damage modifier for [`DungeonEffectsInternals::apply_damage_with_modifiers`],
+/// inspired by NetHack's `spec_applies`/`bane_applies` artifact damage bonuses: a modifier checks
+/// whether it applies to this particular hit (defender species/type/ability/status, etc.), and
+/// if so rewrites the pending [`ffi::damage_data`] in place before it's used to lower HP.
+pub trait DamageModifier {
+    /// Whether this modifier should run for this hit. Inspect `attacker`/`defender`/`the_move`
+    /// and the damage calculated so far in `damage_data` (see the disclaimers on
+    /// [`DungeonMonsterRead::calc_damage`] for how much of that struct this crate currently
+    /// understands) to decide.
+    fn applies(
+        &mut self,
+        attacker: &mut DungeonEntity,
+        defender: &mut DungeonEntity,
+        the_move: &Move,
+        damage_data: &ffi::damage_data,
+    ) -> bool;
+
+    /// Rewrites `damage_data` in place (e.g. doubling its amount), given this modifier already
+    /// passed [`Self::applies`] for this hit.
+    fn adjust(&mut self, damage_data: &mut ffi::damage_data);
+}
+
+/// A registry of [`DamageModifier`]s run by
+/// [`DungeonEffectsInternals::apply_damage_with_modifiers`], in registration order, against
+/// every hit that goes through it -- keeps a mod's "bane"-style conditional damage bonuses in
+/// one place instead of threading an explicit modifier list through every call site.
+#[derive(Default)]
+pub struct DamageModifierRegistry {
+    modifiers: Vec<Box<dyn DamageModifier>>,
+}
+
+impl DamageModifierRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `modifier` to run for every future
+    /// [`DungeonEffectsInternals::apply_damage_with_modifiers`] call, returning an index that can
+    /// be passed to [`Self::remove`].
+    pub fn register(&mut self, modifier: Box<dyn DamageModifier>) -> usize {
+        self.modifiers.push(modifier);
+        self.modifiers.len() - 1
+    }
+
+    /// Removes and returns the modifier previously registered at `index` (per [`Self::register`]'s
+    /// return value), if it's still present. Shifts every later modifier's index down by one.
+    pub fn remove(&mut self, index: usize) -> Option<Box<dyn DamageModifier>> {
+        if index < self.modifiers.len() {
+            Some(self.modifiers.remove(index))
+        } else {
+            None
+        }
+    }
+}
+
+/// A single flagged-item theft callback for [`DungeonEffectsEmitter::do_move_steal_held_item`],
+/// modeled on NetHack's `theft_petrifies`: inspects the item that was just stolen and decides
+/// whether it should inflict a status on the thief (e.g. a cursed or guarded item striking back).
+pub trait TheftStatusHook {
+    /// If stealing `item` should inflict a status on the thief, returns it; otherwise `None`.
+    /// The first hook in the registry to return `Some` wins; later hooks aren't consulted.
+    fn status_for_stolen_item(&mut self, item: &Item) -> Option<StatusCondition>;
+}
+
+/// A registry of [`TheftStatusHook`]s consulted by [`DungeonEffectsEmitter::do_move_steal_held_item`]
+/// after a successful steal, in registration order -- keeps a mod's "certain items punish the
+/// thief" rules in one place instead of threading an explicit hook list through every call site.
+#[derive(Default)]
+pub struct TheftStatusRegistry {
+    hooks: Vec<Box<dyn TheftStatusHook>>,
+}
+
+impl TheftStatusRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `hook` to be consulted on every future
+    /// [`DungeonEffectsEmitter::do_move_steal_held_item`] call, returning an index that can be
+    /// passed to [`Self::remove`].
+    pub fn register(&mut self, hook: Box<dyn TheftStatusHook>) -> usize {
+        self.hooks.push(hook);
+        self.hooks.len() - 1
+    }
+
+    /// Removes and returns the hook previously registered at `index` (per [`Self::register`]'s
+    /// return value), if it's still present. Shifts every later hook's index down by one.
+    pub fn remove(&mut self, index: usize) -> Option<Box<dyn TheftStatusHook>> {
+        if index < self.hooks.len() {
+            Some(self.hooks.remove(index))
+        } else {
+            None
+        }
+    }
+}
+
+/// A single weighted possible outcome for [`DungeonEffectsEmitter::apply_weighted_secondary`].
+///
+/// Unlike [`SecondaryEffect`] (an independent percent chance per effect), every `WeightedEffect`
+/// in a table shares one draw: only one of them (or none) ever fires per hit, so a closure here
+/// doesn't need to re-check whether an earlier entry in the same table already triggered.
+pub struct WeightedEffect {
+    /// This effect's share of the table's draw, out of [`DungeonEffectsEmitter::apply_weighted_secondary`]'s
+    /// `denominator`.
+    pub numerator: u32,
+    /// Logged for the attacker via [`LogMessageBuilder::log_str`] if this effect is the one
+    /// selected.
+    pub message: &'static str,
+    /// Run with `(emitter, attacker, defender)` if this effect is selected.
+    pub effect: Box<dyn Fn(&mut DungeonEffectsEmitter, &mut DungeonEntity, &mut DungeonEntity)>,
+}
+
+impl WeightedEffect {
+    /// Pairs `numerator` and `message` with the effect closure itself.
+    pub fn new(
+        numerator: u32,
+        message: &'static str,
+        effect: impl Fn(&mut DungeonEffectsEmitter, &mut DungeonEntity, &mut DungeonEntity) + 'static,
+    ) -> Self {
+        Self {
+            numerator,
+            message,
+            effect: Box::new(effect),
+        }
+    }
+}
+
+/// A single probabilistic secondary effect for [`DungeonEffectsEmitter::do_move_damage_with_effects`],
+/// pairing a [`MoveSecondaryEffect`] with the chance (out of 100) it triggers once the move's
+/// damage has landed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecondaryEffect {
+    pub effect: MoveSecondaryEffect,
+    pub chance_percent: u8,
+}
+
+impl SecondaryEffect {
+    /// Pairs `effect` with `chance_percent` (out of 100).
+    pub const fn new(effect: MoveSecondaryEffect, chance_percent: u8) -> Self {
+        Self {
+            effect,
+            chance_percent,
+        }
+    }
+}
+
+/// A declarative description of a damaging move's power curve and rider, resolved by
+/// [`DungeonEffectsEmitter::apply_move_effect`] -- the data-driven counterpart to constant-coded
+/// wrappers like [`DungeonEffectsEmitter::do_move_damage_constrict_10`],
+/// [`DungeonEffectsEmitter::do_move_damage_lower_special_defence_50`],
+/// [`DungeonEffectsEmitter::do_move_brine`], and
+/// [`DungeonEffectsEmitter::do_move_damage_eat_item`], each of which hardcodes one specific
+/// power/chance/condition combination as compiled code.
+#[derive(Debug, Clone)]
+pub struct MoveEffectSpec {
+    /// Multiplier on the move's ordinary damage (`1.0` meaning unchanged).
+    pub power_multiplier: f32,
+    /// If set, overrides `power_multiplier` whenever the defender is at or below half its max
+    /// HP -- generalizes [`DungeonEffectsEmitter::do_move_brine`]'s hardcoded 2x.
+    pub below_half_hp_multiplier: Option<f32>,
+    /// A chance-based rider rolled after damage lands, same model as
+    /// [`DungeonEffectsEmitter::do_move_damage_with_effects`].
+    pub secondary: Option<SecondaryEffect>,
+    /// Whether landing the move should consume the defender's held item. This crate has no
+    /// generic accessor for removing or destroying a held item (only the read-only
+    /// [`DungeonMonsterRead::has_held_item`]), so when set,
+    /// [`DungeonEffectsEmitter::apply_move_effect`] deals damage via the dedicated
+    /// [`DungeonEffectsEmitter::do_move_damage_eat_item`] FFI wrapper instead of the generic
+    /// path, rather than reimplementing item consumption here.
+    pub consumes_item: bool,
+}
+
+impl MoveEffectSpec {
+    /// A spec with `power_multiplier`, no low-HP override, no secondary rider, and no item
+    /// consumption.
+    pub const fn new(power_multiplier: f32) -> Self {
+        Self {
+            power_multiplier,
+            below_half_hp_multiplier: None,
+            secondary: None,
+            consumes_item: false,
+        }
+    }
+}
+
+/// A secondary effect [`DungeonEffectsEmitter::do_move_damage_with_effects`] can roll for after a
+/// damaging move connects, dispatched to whichever already-wrapped primitive on
+/// [`DungeonEffectsEmitter`] matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveSecondaryEffect {
+    /// Inflicts `status` on the defender via [`DungeonEffectsEmitter::try_inflict_status`] (with
+    /// default [`InflictOptions`]).
+    InflictStatus(StatusEffect),
+    /// Lowers the defender's defensive stat `stat_idx` by `stages`, via
+    /// [`DungeonEffectsEmitter::boost_defensive_stat`]'s negative-stage convention (see
+    /// [`StatChange::with_defensive_stage`]).
+    LowerStat { stat_idx: i32, stages: i16 },
+    /// Boosts the defender's defensive stat `stat_idx` by `stages`, via
+    /// [`DungeonEffectsEmitter::boost_defensive_stat`].
+    BoostStat { stat_idx: i32, stages: i16 },
+    /// Flinches the defender via [`DungeonEffectsEmitter::try_inflict_cringe_status`].
+    Flinch,
+    /// Heals the attacker by this many HP, via [`DungeonEffectsEmitter::try_increase_hp`] (e.g.
+    /// for drain-style moves).
+    Heal(i32),
+}
+
+/// A composable rider for [`DungeonEffectsEmitter::do_move_damage_with_brand`], applied after a
+/// damaging move connects regardless of which move it was -- Dungeon Crawl's separation of the
+/// attack from the brand carried by the weapon/item delivering it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttackBrand {
+    /// Heals the attacker by `percent` of the damage just dealt.
+    Vampiric { percent: u8 },
+    /// Rolls [`StatusCondition::Frozen`] on the defender with `chance_percent` chance (out of
+    /// 100).
+    Freezing { chance_percent: u8 },
+    /// Rolls [`StatusCondition::Burn`] (with `special_effect: false`) on the defender with
+    /// `chance_percent` chance (out of 100).
+    Flaming { chance_percent: u8 },
+    /// Lowers one of `stat_idx_choices`, picked uniformly at random, by `stages` on the
+    /// defender, via [`DungeonEffectsEmitter::boost_defensive_stat`]'s negative-stage convention.
+    /// The choices are caller-supplied since this crate has no named stat-index constants (same
+    /// gap as [`MoveSecondaryEffect::LowerStat`]).
+    Draining {
+        stat_idx_choices: Vec<i32>,
+        stages: i16,
+    },
+    /// Picks one of `choices` uniformly at random and applies it instead, re-rolled
+    /// independently on every hit.
+    Chaos { choices: Vec<AttackBrand> },
+}
+
+/// Alias for [`StatusCondition`], for callers thinking in terms of "the status effect to
+/// inflict" rather than "the condition a target ends up under" -- the two terms show up
+/// interchangeably across this crate's FFI notes.
+pub type StatusEffect = StatusCondition;
+
+/// A status condition that [`DungeonEffectsEmitter::try_inflict_status`] can attempt to inflict,
+/// carrying whatever per-status parameters the underlying `TryInflict*Status` function needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusCondition {
+    /// See [`DungeonEffectsEmitter::try_inflict_sleep_status`]. Supports `check_only` only as a
+    /// refusal: passing it always returns `false` without inflicting anything, since the
+    /// underlying function has no dry-run mode.
+    Sleep { number_turns: i32 },
+    /// See [`DungeonEffectsEmitter::try_inflict_nightmare_status`]. `check_only` always refuses;
+    /// see [`Self::Sleep`].
+    Nightmare { number_turns: i32 },
+    /// See [`DungeonEffectsEmitter::try_inflict_napping_status`]. `check_only` always refuses;
+    /// see [`Self::Sleep`].
+    Napping { number_turns: i32 },
+    /// See [`DungeonEffectsEmitter::try_inflict_yawning_status`]. `check_only` always refuses;
+    /// see [`Self::Sleep`].
+    Yawning { number_turns: i32 },
+    /// See [`DungeonEffectsEmitter::try_inflict_sleepless_status`]. `check_only` always refuses;
+    /// see [`Self::Sleep`].
+    Sleepless,
+    /// See [`DungeonEffectsEmitter::try_inflict_paused_status`].
+    Paused { param3: i32, number_turns: i32 },
+    /// See [`DungeonEffectsEmitter::try_inflict_infatuated_status`].
+    Infatuated,
+    /// See [`DungeonEffectsEmitter::try_inflict_burn_status`].
+    Burn { special_effect: bool },
+    /// See [`DungeonEffectsEmitter::try_inflict_poisoned_status`].
+    Poisoned,
+    /// See [`DungeonEffectsEmitter::try_inflict_badly_poisoned_status`].
+    BadlyPoisoned,
+    /// See [`DungeonEffectsEmitter::try_inflict_frozen_status`]. `check_only` always refuses; see
+    /// [`Self::Sleep`].
+    Frozen,
+    /// See [`DungeonEffectsEmitter::try_inflict_constriction_status`]. `check_only` always
+    /// refuses; see [`Self::Sleep`].
+    Constriction { animation_id: i32 },
+    /// See [`DungeonEffectsEmitter::try_inflict_shadow_hold_status`]. `check_only` always
+    /// refuses; see [`Self::Sleep`].
+    ShadowHold,
+    /// See [`DungeonEffectsEmitter::try_inflict_ingrain_status`]. `check_only` always refuses;
+    /// see [`Self::Sleep`].
+    Ingrain,
+    /// See [`DungeonEffectsEmitter::try_inflict_wrapped_status`]. `check_only` always refuses;
+    /// see [`Self::Sleep`].
+    Wrapped,
+    /// See [`DungeonEffectsEmitter::try_inflict_petrified_status`]. `check_only` always refuses;
+    /// see [`Self::Sleep`].
+    Petrified,
+    /// See [`DungeonEffectsEmitter::try_inflict_cringe_status`].
+    Cringe,
+    /// See [`DungeonEffectsEmitter::try_inflict_paralysis_status`].
+    Paralysis,
+    /// See [`DungeonEffectsEmitter::try_inflict_confused_status`].
+    Confused,
+    /// See [`DungeonEffectsEmitter::try_inflict_cowering_status`].
+    Cowering,
+    /// See [`DungeonEffectsEmitter::try_inflict_leech_seed_status`].
+    LeechSeed,
+    /// See [`DungeonEffectsEmitter::try_inflict_destiny_bond_status`]. `check_only` always
+    /// refuses; see [`Self::Sleep`].
+    DestinyBond,
+}
+
+impl StatusCondition {
+    /// Whether the underlying `TryInflict*Status` function for this variant takes a real
+    /// `check_only`/dry-run parameter, as opposed to [`DungeonEffectsEmitter::try_inflict_status`]
+    /// (and [`StatusApplication::apply`]) just refusing the combination.
+    pub fn supports_check_only(&self) -> bool {
+        !matches!(
+            self,
+            Self::Sleep { .. }
+                | Self::Nightmare { .. }
+                | Self::Napping { .. }
+                | Self::Yawning { .. }
+                | Self::Sleepless
+                | Self::Frozen
+                | Self::Constriction { .. }
+                | Self::ShadowHold
+                | Self::Ingrain
+                | Self::Wrapped
+                | Self::Petrified
+                | Self::DestinyBond
+        )
+    }
+}
+
+/// The flags shared by every `TryInflict*Status` function, for use with
+/// [`DungeonEffectsEmitter::try_inflict_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InflictOptions {
+    /// Whether to log a message to the dungeon message log on failure.
+    pub log_failure: bool,
+    /// Whether to only check if the status could be inflicted, without actually inflicting it.
+    ///
+    /// Only [`StatusCondition`] variants backed by a `TryInflict*Status` function that itself
+    /// takes a `check_only` parameter support this as a real dry-run; the others always return
+    /// `false` when this is set, since there's no way to query them without side effects. See the
+    /// per-variant documentation on [`StatusCondition`].
+    pub check_only: bool,
+}
+
+impl Default for InflictOptions {
+    /// `log_failure: true`, `check_only: false` - an ordinary, logged infliction attempt.
+    fn default() -> Self {
+        Self {
+            log_failure: true,
+            check_only: false,
+        }
+    }
+}
+
+/// The result of [`DungeonEffectsEmitter::try_inflict_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InflictOutcome {
+    /// The underlying `TryInflict*Status` function reported that the status took effect (or,
+    /// under [`InflictOptions::check_only`], would).
+    Applied,
+    /// The status was refused: the underlying function reported failure, or
+    /// [`InflictOptions::check_only`] was set for a [`StatusCondition`] that doesn't support a
+    /// real dry-run (see [`StatusCondition::supports_check_only`]).
+    Rejected,
+    /// The underlying function doesn't report success/failure at all, so the status was applied
+    /// unconditionally. Never returned when [`InflictOptions::check_only`] is set, since those
+    /// statuses report [`Self::Rejected`] for a dry run instead.
+    AppliedUnconditionally,
+}
+
+impl InflictOutcome {
+    /// Whether the status took effect (or, under a dry run, would have): `true` for both
+    /// [`Self::Applied`] and [`Self::AppliedUnconditionally`].
+    pub fn applied(self) -> bool {
+        !matches!(self, Self::Rejected)
+    }
+}
+
+/// Error returned by [`StatusApplication::apply`] when the builder's flags aren't actually
+/// supported by the selected [`StatusCondition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusError {
+    /// [`StatusApplication::check_only`] was set, but [`StatusCondition::supports_check_only`]
+    /// is `false` for the selected status, so there's no dry-run to actually run.
+    CheckOnlyUnsupported,
+}
+
+/// A single, discoverable, type-checked front-end over the `TryInflict*Status` family (see
+/// [`DungeonEffectsEmitter::try_inflict_status`]), so a status move doesn't need its author to
+/// memorize which of ~20 near-identical functions takes which flags in which order.
+///
+/// Unlike [`InflictOptions::check_only`] on its own (which silently treats an unsupported dry-run
+/// as "never succeeds"), [`Self::apply`] rejects that combination outright with a
+/// [`StatusError`], so a mistake surfaces immediately instead of as a status that mysteriously
+/// never procs.
+#[derive(Debug, Clone, Copy)]
+pub struct StatusApplication {
+    status: StatusCondition,
+    log_failure: bool,
+    check_only: bool,
+}
+
+impl StatusApplication {
+    /// Starts building an application of `status`, with both flags off.
+    pub fn new(status: StatusCondition) -> Self {
+        Self {
+            status,
+            log_failure: false,
+            check_only: false,
+        }
+    }
+
+    /// Logs a message to the dungeon message log if the status couldn't be inflicted.
+    pub fn log_on_failure(&mut self) -> &mut Self {
+        self.log_failure = true;
+        self
+    }
+
+    /// Dry-runs the application instead of actually inflicting the status: [`Self::apply`] then
+    /// returns whether it would succeed, without changing `target`'s state.
+    ///
+    /// Only meaningful for statuses where [`StatusCondition::supports_check_only`] is `true`;
+    /// see [`Self::apply`].
+    pub fn check_only(&mut self) -> &mut Self {
+        self.check_only = true;
+        self
+    }
+
+    /// Dispatches to [`DungeonEffectsEmitter::try_inflict_status`], returning the outcome of (or,
+    /// under [`Self::check_only`], the probed outcome of) the attempt.
+    ///
+    /// # Errors
+    /// Returns [`StatusError::CheckOnlyUnsupported`] if [`Self::check_only`] was set but the
+    /// selected status has no dry-run support.
+    pub fn apply(
+        &self,
+        effects: &mut DungeonEffectsEmitter,
+        user: &mut DungeonEntity,
+        target: &mut DungeonEntity,
+    ) -> Result<InflictOutcome, StatusError> {
+        if self.check_only && !self.status.supports_check_only() {
+            return Err(StatusError::CheckOnlyUnsupported);
+        }
+        Ok(effects.try_inflict_status(
+            user,
+            target,
+            self.status,
+            InflictOptions {
+                log_failure: self.log_failure,
+                check_only: self.check_only,
+            },
+        ))
+    }
+}
+
+/// A self-targeted offensive stat boost, for use with [`AiDecisionConfig::self_boost`].
+///
+/// There's no `check_only` probe for stat boosts (unlike [`StatusCondition`]), so when configured
+/// this is always chosen by [`DungeonEffectsEmitter::choose_enemy_action`] once it's reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelfStatBoost {
+    /// See [`DungeonEffectsEmitter::boost_offensive_stat`]'s `stat_idx`.
+    pub stat_idx: i32,
+    /// See [`DungeonEffectsEmitter::boost_offensive_stat`]'s `n_stages`.
+    pub n_stages: i16,
+}
+
+/// The action chosen by [`DungeonEffectsEmitter::choose_enemy_action`], for the caller to execute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnemyAiAction {
+    /// Inflict `status` on the target passed to [`DungeonEffectsEmitter::choose_enemy_action`] as
+    /// `nearest_target`, via [`DungeonEffectsEmitter::try_inflict_status`].
+    InflictStatus { status: StatusCondition },
+    /// Boost the actor's own stat, via [`DungeonEffectsEmitter::boost_offensive_stat`].
+    BoostOwnStat { stat_idx: i32, n_stages: i16 },
+    /// Restore the actor's own HP, via [`DungeonEffectsEmitter::try_increase_hp`].
+    HealSelf { amount: i32, max_hp_boost: i32 },
+    /// Nothing beneficial was found; the caller should proceed with its normal attack.
+    FallThroughToNormalAttack,
+}
+
+/// Tunables for [`DungeonEffectsEmitter::choose_enemy_action`].
+#[derive(Debug, Clone)]
+pub struct AiDecisionConfig {
+    /// HP fraction (out of 1) at or below which the actor prefers healing over any other action.
+    pub heal_hp_fraction: I24F8,
+    /// Passed through to [`EnemyAiAction::HealSelf`]. Healing is never chosen if this is `<= 0`.
+    pub heal_amount: i32,
+    /// Passed through to [`EnemyAiAction::HealSelf`].
+    pub heal_max_hp_boost: i32,
+    /// Candidate disabling statuses paired with a score, tried highest-score first against
+    /// `nearest_target`; the first whose [`InflictOptions::check_only`] probe succeeds is chosen.
+    pub disabling_statuses: Vec<(StatusCondition, i32)>,
+    /// A self-targeted stat boost to fall back on if healing doesn't trigger and no disabling
+    /// status can be used, before giving up on [`EnemyAiAction::FallThroughToNormalAttack`].
+    pub self_boost: Option<SelfStatBoost>,
+}
+
+/// A requested change to a monster's accuracy or evasion stage, for use with
+/// [`StatChange::with_hit_chance_stage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitChanceChange {
+    /// See [`DungeonEffectsEmitter::boost_hit_chance_stat`].
+    Boost { stat_idx: i32 },
+    /// See [`DungeonEffectsEmitter::lower_hit_chance_stat`].
+    Lower { stat_idx: i32, param_4: i32 },
+}
+
+/// Builder for a combination of stat-stage deltas and multipliers to apply to a target monster in
+/// a single [`DungeonEffectsEmitter::apply_stat_change`] call, mirroring how one move (eg. Charm,
+/// Screech, Memento) can touch several stat kinds on the same turn.
+///
+/// Construct with [`Self::new`], chain whichever `with_*` setters the move needs, then pass to
+/// [`DungeonEffectsEmitter::apply_stat_change`]. Unset fields are simply not touched.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StatChange {
+    offensive_stage: Option<(i32, i16)>,
+    defensive_stage: Option<(i32, i16)>,
+    hit_chance_stage: Option<HitChanceChange>,
+    speed_stage: Option<(i32, i32)>,
+    offensive_multiplier: Option<(i32, i32)>,
+    defensive_multiplier: Option<(i32, i32)>,
+    log_failure: bool,
+}
+
+impl StatChange {
+    /// Creates an empty change that applies nothing until `with_*` setters are chained on it.
+    /// `log_failure` defaults to `false`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Boosts (or, with a negative `n_stages`, lowers) an offensive stat stage. See
+    /// [`DungeonEffectsEmitter::boost_offensive_stat`].
+    pub fn with_offensive_stage(&mut self, stat_idx: i32, n_stages: i16) -> &mut Self {
+        self.offensive_stage = Some((stat_idx, n_stages));
+        self
+    }
+
+    /// Boosts (or, with a negative `n_stages`, lowers) a defensive stat stage. See
+    /// [`DungeonEffectsEmitter::boost_defensive_stat`].
+    pub fn with_defensive_stage(&mut self, stat_idx: i32, n_stages: i16) -> &mut Self {
+        self.defensive_stage = Some((stat_idx, n_stages));
+        self
+    }
+
+    /// Changes an accuracy or evasion stage. See [`HitChanceChange`].
+    pub fn with_hit_chance_stage(&mut self, change: HitChanceChange) -> &mut Self {
+        self.hit_chance_stage = Some(change);
+        self
+    }
+
+    /// Boosts the target's speed by `n_stages` for `n_turns` turns. See
+    /// [`DungeonEffectsEmitter::boost_speed`] - as there, `n_turns == 0` rolls a random duration
+    /// from `SPEED_BOOST_DURATION_RANGE` instead.
+    pub fn with_speed_stage(&mut self, n_stages: i32, n_turns: i32) -> &mut Self {
+        self.speed_stage = Some((n_stages, n_turns));
+        self
+    }
+
+    /// Applies a multiplier to the target's offensive stat, for moves like Charm and Memento. See
+    /// [`DungeonEffectsEmitter::apply_offensive_stat_multiplier`].
+    pub fn with_offensive_multiplier(&mut self, stat_idx: i32, multiplier: i32) -> &mut Self {
+        self.offensive_multiplier = Some((stat_idx, multiplier));
+        self
+    }
+
+    /// Applies a multiplier to the target's defensive stat, for moves like Screech. See
+    /// [`DungeonEffectsEmitter::apply_defensive_stat_multiplier`].
+    pub fn with_defensive_multiplier(&mut self, stat_idx: i32, multiplier: i32) -> &mut Self {
+        self.defensive_multiplier = Some((stat_idx, multiplier));
+        self
+    }
+
+    /// Sets the shared `log_failure` flag forwarded to every change this builder makes that
+    /// supports it (currently just the speed stage; most of the underlying stat functions don't
+    /// take one at all). Defaults to `false`.
+    pub fn with_log_failure(&mut self, log_failure: bool) -> &mut Self {
+        self.log_failure = log_failure;
+        self
+    }
+}
+
+/// Which of a [`StatChange`]'s requested changes [`DungeonEffectsEmitter::apply_stat_change`]
+/// actually applied.
+///
+/// Most of the underlying `Boost*`/`Apply*Multiplier` functions don't report per-change
+/// success/failure, so a field here is `true` whenever the corresponding [`StatChange`] field was
+/// set, regardless of whether the change did anything in-game (eg. a stat already at max stage).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatChangeResult {
+    pub offensive_stage: bool,
+    pub defensive_stage: bool,
+    pub hit_chance_stage: bool,
+    pub speed_stage: bool,
+    pub offensive_multiplier: bool,
+    pub defensive_multiplier: bool,
+}
+
+/// Default percentage chance that each stacked level of Confusion causes erratic movement/action
+/// this turn, used by [`confusion_erratic_chance`] when no override is given.
+///
+/// Note: unverified, ported from Irdkwia's notes.
+pub const CONF_ERRATIC_CHANCE: i32 = 10;
+
+/// Computes the chance (out of 100) that a monster with `confusion_levels` stacked levels of
+/// Confusion acts erratically this turn, using [`CONF_ERRATIC_CHANCE`] as the per-level rate. See
+/// [`confusion_erratic_chance_with`] to override that rate.
+pub fn confusion_erratic_chance(confusion_levels: u32, flat_bonus: i32) -> i32 {
+    confusion_erratic_chance_with(confusion_levels, flat_bonus, CONF_ERRATIC_CHANCE)
+}
+
+/// Computes the chance (out of 100) that a monster with `confusion_levels` stacked levels of
+/// Confusion acts erratically this turn, using `erratic_chance_per_level` as the percentage rate
+/// each level degrades accuracy by.
+///
+/// Levels compound multiplicatively rather than adding linearly: starting from `chance = 0`,
+/// every level computes `accuracy = (100 - chance) * (100 - erratic_chance_per_level) / 100`,
+/// then sets `chance = 100 - accuracy`. After folding in every level, `flat_bonus` (e.g. a species
+/// trait like "random-25"/"random-50") is added directly on top.
+///
+/// The result is clamped to `0..=100`. With `confusion_levels == 0`, only `flat_bonus` applies.
+pub fn confusion_erratic_chance_with(
+    confusion_levels: u32,
+    flat_bonus: i32,
+    erratic_chance_per_level: i32,
+) -> i32 {
+    let mut chance = 0;
+    for _ in 0..confusion_levels {
+        let accuracy = (100 - chance) * (100 - erratic_chance_per_level) / 100;
+        chance = 100 - accuracy;
+    }
+    (chance + flat_bonus).clamp(0, 100)
+}
+
+/// Rolls whether a monster with `confusion_levels` stacked levels of Confusion acts erratically
+/// this turn, via [`confusion_erratic_chance`].
+pub fn should_act_erratically(confusion_levels: u32, flat_bonus: i32) -> bool {
+    rand_i32(0..100) < confusion_erratic_chance(confusion_levels, flat_bonus)
+}
+
+/// The outcome of an item effect function registered with `patches!`.
+///
+/// `src/item_effects.c` only calls into the Rust-generated `eos_rs_apply_item_effect` entrypoint
+/// for items that have no effect already defined in C, so returning [`NotApplied`](Self::NotApplied)
+/// is mostly useful when a handler wants to decline based on something it can only check at
+/// runtime (e.g. only acting in certain dungeons).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemEffectOutcome {
+    /// The effect was applied; the item is considered fully handled.
+    Applied,
+    /// The handler chose not to act.
+    NotApplied,
+}
+
+impl From<()> for ItemEffectOutcome {
+    /// Handlers that still return `()` are always treated as [`Applied`](Self::Applied), matching
+    /// `patches!`'s historical behavior of unconditionally reporting the item as handled.
+    fn from(_: ()) -> Self {
+        ItemEffectOutcome::Applied
+    }
+}
+
+/// The outcome of a move effect function registered with `patches!`.
+///
+/// See [`ItemEffectOutcome`] for the item-effect equivalent; the only difference is that applying
+/// a move effect also reports whether it dealt damage, written into `move_effect_input`'s
+/// `out_dealt_damage` field by the macro-generated entrypoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveEffectOutcome {
+    /// The effect was applied. `dealt_damage` becomes `out_dealt_damage`.
+    Applied {
+        dealt_damage: bool,
+    },
+    /// The handler chose not to act.
+    NotApplied,
+}
+
+impl From<bool> for MoveEffectOutcome {
+    /// Handlers that still return a `bool` are always treated as [`Applied`](Self::Applied),
+    /// matching `patches!`'s historical behavior; the `bool` becomes `dealt_damage`.
+    fn from(dealt_damage: bool) -> Self {
+        MoveEffectOutcome::Applied { dealt_damage }
+    }
+}
+
+/// A custom item effect registered with [`register_item_effect`], the trait-based counterpart to
+/// a `patches!` `item_effect` entry (see `oran_berry_burn` in the example `main.rs`): unlike a
+/// `patches!` entry, which has to be added to the crate's one `patches!` invocation at compile
+/// time, an `impl ItemEffect` can be registered from anywhere -- including a separate mod crate --
+/// at runtime, against an [`ItemId`] that isn't already claimed by a `patches!` entry.
+pub trait ItemEffect {
+    /// Applies this item's effect. Mirrors the parameters `patches!`'s `item_effect` syntax
+    /// expects from a free function, so an existing handler like `oran_berry_burn` ports over
+    /// to an `impl` near verbatim.
+    fn apply(
+        &mut self,
+        effects: &DungeonEffectsEmitter,
+        user: &mut DungeonEntity,
+        target: &mut DungeonEntity,
+        used_item: &mut DungeonItem,
+        is_thrown: bool,
+    );
+}
+
+struct RegisteredItemEffect {
+    item_id: ItemId,
+    effect: Box<dyn ItemEffect>,
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// item effects are only ever dispatched from the main game loop.
+static mut ITEM_EFFECTS: Vec<RegisteredItemEffect> = Vec::new();
+
+/// Registers `effect` to run whenever `item_id` is used or thrown, without needing a
+/// `patches!`-time `item_effect` entry for it.
+///
+/// # Panics
+/// Panics if `item_id` already has a registered effect.
+pub fn register_item_effect(item_id: ItemId, effect: impl ItemEffect + 'static) {
+    // SAFETY: single-threaded; see `ITEM_EFFECTS`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        assert!(
+            !ITEM_EFFECTS.iter().any(|entry| entry.item_id == item_id),
+            "item {} already has a registered effect; unregister it first if replacing it on purpose",
+            item_id.id()
+        );
+        ITEM_EFFECTS.push(RegisteredItemEffect {
+            item_id,
+            effect: Box::new(effect),
+        });
+    }
+}
+
+/// Unregisters the effect for `item_id`, if any. Returns whether one was actually removed.
+pub fn unregister_item_effect(item_id: ItemId) -> bool {
+    // SAFETY: single-threaded; see `ITEM_EFFECTS`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        let before = ITEM_EFFECTS.len();
+        ITEM_EFFECTS.retain(|entry| entry.item_id != item_id);
+        ITEM_EFFECTS.len() != before
+    }
+}
+
+/// Runs the registered [`ItemEffect`] for `used_item`'s ID, if any, the same way the
+/// `patches!`-generated `eos_rs_apply_item_effect` entrypoint dispatches a compile-time
+/// `item_effect` case -- meant to be called from the same `CustomApplyItemEffect` glue, or from a
+/// fallback branch of a hand-written one, for effects registered at runtime instead.
+///
+/// Returns [`ItemEffectOutcome::NotApplied`] if `used_item`'s ID has no registered effect, so
+/// vanilla item handling (or a `patches!`-time effect for a different item) keeps working
+/// untouched.
+pub fn dispatch_item_effect(
+    effects: &DungeonEffectsEmitter,
+    user: &mut DungeonEntity,
+    target: &mut DungeonEntity,
+    used_item: &mut DungeonItem,
+    is_thrown: bool,
+) -> ItemEffectOutcome {
+    let item_id = used_item.id.val();
+    // SAFETY: single-threaded; see `ITEM_EFFECTS`.
+    #[allow(static_mut_refs)]
+    let index = unsafe { ITEM_EFFECTS.iter().position(|entry| entry.item_id == item_id) };
+    let Some(index) = index else {
+        return ItemEffectOutcome::NotApplied;
+    };
+    // SAFETY: single-threaded; see `ITEM_EFFECTS`. Temporarily removed so a handler is free to
+    // register/unregister an item effect (including its own) without the lookup's borrow of the
+    // registry still being held while it runs.
+    #[allow(static_mut_refs)]
+    let mut entry = unsafe { ITEM_EFFECTS.remove(index) };
+    entry.effect.apply(effects, user, target, used_item, is_thrown);
+    // SAFETY: single-threaded; see `ITEM_EFFECTS`. Only reinsert if the handler didn't already
+    // unregister (or replace) `item_id` itself while it ran.
+    #[allow(static_mut_refs)]
+    unsafe {
+        if !ITEM_EFFECTS.iter().any(|existing| existing.item_id == item_id) {
+            ITEM_EFFECTS.push(entry);
+        }
+    }
+    ItemEffectOutcome::Applied
+}
+
+/// A custom move effect registered with [`register_move_use_effect`], the trait-based counterpart
+/// to a `patches!` `move_effect` entry (see `cut_badly_poisoned` in the example `main.rs`): unlike
+/// a `patches!` entry, which has to be added to the crate's one `patches!` invocation at compile
+/// time, an `impl MoveUseEffect` can be registered from anywhere -- including a separate mod crate
+/// -- at runtime, against a [`MoveId`] that isn't already claimed by a `patches!` entry.
+///
+/// Named `MoveUseEffect` rather than `MoveEffect` to avoid colliding with [`MoveEffect`] above,
+/// which is a different, richer system -- per-stage hooks run around every `do_move_*` call via
+/// [`MoveEffectRegistry`], rather than a single function standing in for a move's entire effect.
+/// This trait instead mirrors a `patches!` `move_effect` entry 1:1.
+pub trait MoveUseEffect {
+    /// Applies this move's effect. Mirrors the parameters and "returns whether damage was dealt"
+    /// contract `patches!`'s `move_effect` syntax expects from a free function, so an existing
+    /// handler like `cut_badly_poisoned` ports over to an `impl` near verbatim.
+    fn apply(
+        &mut self,
+        effects: &DungeonEffectsEmitter,
+        user: &mut DungeonEntity,
+        target: &mut DungeonEntity,
+        used_move: &mut Move,
+    ) -> bool;
+}
+
+struct RegisteredMoveUseEffect {
+    move_id: MoveId,
+    effect: Box<dyn MoveUseEffect>,
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// move effects are only ever dispatched from the main game loop.
+static mut MOVE_USE_EFFECTS: Vec<RegisteredMoveUseEffect> = Vec::new();
+
+/// Registers `effect` to run whenever `move_id` is used in a dungeon, without needing a
+/// `patches!`-time `move_effect` entry for it.
+///
+/// # Panics
+/// Panics if `move_id` already has a registered effect.
+pub fn register_move_use_effect(move_id: MoveId, effect: impl MoveUseEffect + 'static) {
+    // SAFETY: single-threaded; see `MOVE_USE_EFFECTS`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        assert!(
+            !MOVE_USE_EFFECTS.iter().any(|entry| entry.move_id == move_id),
+            "move {} already has a registered effect; unregister it first if replacing it on purpose",
+            move_id
+        );
+        MOVE_USE_EFFECTS.push(RegisteredMoveUseEffect {
+            move_id,
+            effect: Box::new(effect),
+        });
+    }
+}
+
+/// Unregisters the effect for `move_id`, if any. Returns whether one was actually removed.
+pub fn unregister_move_use_effect(move_id: MoveId) -> bool {
+    // SAFETY: single-threaded; see `MOVE_USE_EFFECTS`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        let before = MOVE_USE_EFFECTS.len();
+        MOVE_USE_EFFECTS.retain(|entry| entry.move_id != move_id);
+        MOVE_USE_EFFECTS.len() != before
+    }
+}
+
+/// Runs the registered [`MoveUseEffect`] for `used_move`'s ID, if any, the same way the
+/// `patches!`-generated `eos_rs_apply_move_effect` entrypoint dispatches a compile-time
+/// `move_effect` case -- meant to be called from the same `CustomApplyMoveEffect` glue, or from a
+/// fallback branch of a hand-written one, for effects registered at runtime instead.
+///
+/// Returns [`MoveEffectOutcome::NotApplied`] if `used_move`'s ID has no registered effect, so
+/// vanilla move handling (or a `patches!`-time effect for a different move) keeps working
+/// untouched.
+pub fn dispatch_move_use_effect(
+    effects: &DungeonEffectsEmitter,
+    user: &mut DungeonEntity,
+    target: &mut DungeonEntity,
+    used_move: &mut Move,
+) -> MoveEffectOutcome {
+    let move_id = used_move.id.val();
+    // SAFETY: single-threaded; see `MOVE_USE_EFFECTS`.
+    #[allow(static_mut_refs)]
+    let index = unsafe { MOVE_USE_EFFECTS.iter().position(|entry| entry.move_id == move_id) };
+    let Some(index) = index else {
+        return MoveEffectOutcome::NotApplied;
+    };
+    // SAFETY: single-threaded; see `MOVE_USE_EFFECTS`. Temporarily removed so a handler is free to
+    // register/unregister a move effect (including its own) without the lookup's borrow of the
+    // registry still being held while it runs.
+    #[allow(static_mut_refs)]
+    let mut entry = unsafe { MOVE_USE_EFFECTS.remove(index) };
+    let dealt_damage = entry.effect.apply(effects, user, target, used_move);
+    // SAFETY: single-threaded; see `MOVE_USE_EFFECTS`. Only reinsert if the handler didn't already
+    // unregister (or replace) `move_id` itself while it ran.
+    #[allow(static_mut_refs)]
+    unsafe {
+        if !MOVE_USE_EFFECTS.iter().any(|existing| existing.move_id == move_id) {
+            MOVE_USE_EFFECTS.push(entry);
+        }
+    }
+    MoveEffectOutcome::Applied { dealt_damage }
+}
+
+/// The chance (out of 100) that [`DungeonEffectsEmitter::open_pit_under`] turns the target's
+/// tile into a chasm instead of a plain pit.
+///
+/// Note: unverified, ported from Irdkwia's notes.
+const PIT_CHASM_CHANCE_PERCENT: i32 = 10;
+
+/// The result of [`DungeonEffectsEmitter::open_pit_under`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PitfallResult {
+    /// Whether the tile became a chasm (as opposed to a plain, walkable pit).
+    pub is_chasm: bool,
+    /// Whether the target actually fell in. This is `false` for flying/levitating targets,
+    /// which are unaffected by the new terrain.
+    pub trapped: bool,
 }
@@ -246,6 +246,30 @@ impl TryFrom<ffi::direction_id::Type> for Direction {
     }
 }
 
+impl Direction {
+    /// The `(dx, dy)` unit step on the dungeon tile grid for this direction (diagonals step both
+    /// axes), for use by anything that walks the grid tile-by-tile, like
+    /// [`crate::api::dungeon_mode::trajectory::DungeonTrajectory::trace`].
+    ///
+    /// Returns `None` for [`Self::Current`], the "use the entity's current facing" sentinel (see
+    /// e.g. [`crate::api::dungeon_mode::DungeonEffectsEmitter::try_pounce`]): this crate doesn't
+    /// currently expose a way to read an entity's facing back out, so resolving it to a concrete
+    /// direction is left to the caller.
+    pub fn step(&self) -> Option<(i32, i32)> {
+        match self {
+            Direction::Down => Some((0, 1)),
+            Direction::DownRight => Some((1, 1)),
+            Direction::Right => Some((1, 0)),
+            Direction::UpRight => Some((1, -1)),
+            Direction::Up => Some((0, -1)),
+            Direction::UpLeft => Some((-1, -1)),
+            Direction::Left => Some((-1, 0)),
+            Direction::DownLeft => Some((-1, 1)),
+            Direction::Current => None,
+        }
+    }
+}
+
 #[repr(u32)]
 #[derive(PartialEq, Clone, Copy)]
 /// Different types of warp effects
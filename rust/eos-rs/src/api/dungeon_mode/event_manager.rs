@@ -0,0 +1,213 @@
+//! Diff-based event dispatch over [`GlobalDungeonData`], so mods can react to "the player just
+//! stole from Kecleon" or "the floor just changed" instead of re-reading and comparing the same
+//! handful of scalar fields (`is_thief_alert`, `is_monster_house_triggered`, `get_weather`,
+//! `floor`, `get_new_leader`, `get_dungeon_objective`) themselves every frame.
+//!
+//! [`EventManager`] is caller-owned bookkeeping, the same way [`crate::api::save_states::SaveStates`]
+//! is: create one, hold onto it across turns, register callbacks on it, and call
+//! [`GlobalDungeonData::dispatch_events`] once per turn to both fire due callbacks and refresh the
+//! stored snapshot they're diffed against.
+
+use crate::api::dungeon_mode::{DungeonEntity, DungeonObjective, GlobalDungeonData, Weather};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ptr;
+
+/// The event kinds an [`EventManager`] can dispatch, passed to [`EventManager::register`] to pick
+/// which bucket a callback is added to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DungeonEventKind {
+    /// The player just stole from Kecleon (`is_thief_alert` went from `false` to `true`).
+    ThiefAlertRaised,
+    /// A Monster House was just triggered (`is_monster_house_triggered` went from `false` to
+    /// `true`).
+    MonsterHouseEntered,
+    /// The current weather changed.
+    WeatherChanged,
+    /// The floor number changed.
+    FloorAdvanced,
+    /// The team leader changed.
+    LeaderChanged,
+    /// The dungeon's objective changed.
+    ObjectiveChanged,
+}
+
+/// The payload an [`EventManager`] callback is invoked with, carrying the old/new values for
+/// whichever [`DungeonEventKind`] fired.
+#[derive(Clone, Copy)]
+pub enum DungeonEvent {
+    ThiefAlertRaised,
+    MonsterHouseEntered,
+    WeatherChanged {
+        old: Option<Weather>,
+        new: Option<Weather>,
+    },
+    FloorAdvanced {
+        old: u8,
+        new: u8,
+    },
+    /// `old`/`new` are raw entity pointers rather than references, since the old leader may no
+    /// longer be alive/valid by the time this fires; compare for identity or re-resolve the
+    /// current leader via [`GlobalDungeonData::get_leader`] instead of dereferencing these.
+    LeaderChanged {
+        old: *const DungeonEntity,
+        new: *const DungeonEntity,
+    },
+    ObjectiveChanged {
+        old: Option<DungeonObjective>,
+        new: Option<DungeonObjective>,
+    },
+}
+
+impl DungeonEvent {
+    fn kind(&self) -> DungeonEventKind {
+        match self {
+            DungeonEvent::ThiefAlertRaised => DungeonEventKind::ThiefAlertRaised,
+            DungeonEvent::MonsterHouseEntered => DungeonEventKind::MonsterHouseEntered,
+            DungeonEvent::WeatherChanged { .. } => DungeonEventKind::WeatherChanged,
+            DungeonEvent::FloorAdvanced { .. } => DungeonEventKind::FloorAdvanced,
+            DungeonEvent::LeaderChanged { .. } => DungeonEventKind::LeaderChanged,
+            DungeonEvent::ObjectiveChanged { .. } => DungeonEventKind::ObjectiveChanged,
+        }
+    }
+}
+
+/// A handle returned by [`EventManager::register`], used to later remove that specific callback
+/// via [`EventManager::unregister`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventHandle {
+    kind: DungeonEventKind,
+    id: u32,
+}
+
+type Callback = Box<dyn FnMut(&DungeonEvent)>;
+
+/// Snapshot of the scalar dungeon fields [`EventManager`] diffs each turn. Kept private: it's an
+/// implementation detail of the diffing, not something callers read directly.
+#[derive(Clone, Copy)]
+struct DungeonSnapshot {
+    thief_alert: bool,
+    monster_house_triggered: bool,
+    weather: Option<Weather>,
+    floor: u8,
+    leader: *const DungeonEntity,
+    objective: Option<DungeonObjective>,
+}
+
+/// Registers callbacks keyed by [`DungeonEventKind`] and dispatches them from
+/// [`GlobalDungeonData::dispatch_events`]. See the [module-level docs](self).
+#[derive(Default)]
+pub struct EventManager {
+    next_id: u32,
+    snapshot: Option<DungeonSnapshot>,
+    thief_alert_raised: Vec<(u32, Callback)>,
+    monster_house_entered: Vec<(u32, Callback)>,
+    weather_changed: Vec<(u32, Callback)>,
+    floor_advanced: Vec<(u32, Callback)>,
+    leader_changed: Vec<(u32, Callback)>,
+    objective_changed: Vec<(u32, Callback)>,
+}
+
+impl EventManager {
+    /// Creates an empty event manager with no registered callbacks and no stored snapshot (so the
+    /// first [`GlobalDungeonData::dispatch_events`] call only seeds the snapshot and fires
+    /// nothing, since there's nothing yet to diff against).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `callback` to be invoked whenever a `kind` event is dispatched, returning a
+    /// handle that can later be passed to [`Self::unregister`]. Multiple callbacks can be
+    /// registered for the same `kind`; all of them are invoked, in registration order.
+    pub fn register(
+        &mut self,
+        kind: DungeonEventKind,
+        callback: impl FnMut(&DungeonEvent) + 'static,
+    ) -> EventHandle {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.bucket_mut(kind).push((id, Box::new(callback)));
+        EventHandle { kind, id }
+    }
+
+    /// Removes a previously registered callback. Does nothing if it was already removed.
+    pub fn unregister(&mut self, handle: EventHandle) {
+        self.bucket_mut(handle.kind)
+            .retain(|(id, _)| *id != handle.id);
+    }
+
+    fn bucket_mut(&mut self, kind: DungeonEventKind) -> &mut Vec<(u32, Callback)> {
+        match kind {
+            DungeonEventKind::ThiefAlertRaised => &mut self.thief_alert_raised,
+            DungeonEventKind::MonsterHouseEntered => &mut self.monster_house_entered,
+            DungeonEventKind::WeatherChanged => &mut self.weather_changed,
+            DungeonEventKind::FloorAdvanced => &mut self.floor_advanced,
+            DungeonEventKind::LeaderChanged => &mut self.leader_changed,
+            DungeonEventKind::ObjectiveChanged => &mut self.objective_changed,
+        }
+    }
+
+    fn dispatch(&mut self, event: DungeonEvent) {
+        for (_, callback) in self.bucket_mut(event.kind()).iter_mut() {
+            callback(&event);
+        }
+    }
+}
+
+impl<'a> GlobalDungeonData<'a> {
+    /// Snapshots the current dungeon state, diffs it against whatever was snapshotted on the
+    /// previous call, and dispatches the matching [`DungeonEvent`] on `events` for every field
+    /// that changed, before overwriting the stored snapshot. Call this once per turn.
+    ///
+    /// The very first call after `events` is created never dispatches anything, since there's no
+    /// prior snapshot yet to diff against.
+    pub fn dispatch_events(&mut self, events: &mut EventManager) {
+        let leader = self
+            .get_leader()
+            .map(|entity| entity as *const DungeonEntity)
+            .unwrap_or(ptr::null());
+        let current = DungeonSnapshot {
+            thief_alert: self.inner().is_thief_alert(),
+            monster_house_triggered: self.inner().is_monster_house_triggered(),
+            weather: self.inner().get_weather(),
+            floor: self.inner().floor(),
+            leader,
+            objective: self.inner().get_dungeon_objective(),
+        };
+
+        if let Some(previous) = events.snapshot {
+            if !previous.thief_alert && current.thief_alert {
+                events.dispatch(DungeonEvent::ThiefAlertRaised);
+            }
+            if !previous.monster_house_triggered && current.monster_house_triggered {
+                events.dispatch(DungeonEvent::MonsterHouseEntered);
+            }
+            if previous.weather != current.weather {
+                events.dispatch(DungeonEvent::WeatherChanged {
+                    old: previous.weather,
+                    new: current.weather,
+                });
+            }
+            if previous.floor != current.floor {
+                events.dispatch(DungeonEvent::FloorAdvanced {
+                    old: previous.floor,
+                    new: current.floor,
+                });
+            }
+            if previous.leader != current.leader {
+                events.dispatch(DungeonEvent::LeaderChanged {
+                    old: previous.leader,
+                    new: current.leader,
+                });
+            }
+            if previous.objective != current.objective {
+                events.dispatch(DungeonEvent::ObjectiveChanged {
+                    old: previous.objective,
+                    new: current.objective,
+                });
+            }
+        }
+
+        events.snapshot = Some(current);
+    }
+}
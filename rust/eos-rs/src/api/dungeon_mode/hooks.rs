@@ -0,0 +1,205 @@
+//! A registration-based hook system for dungeon lifecycle events, so mods can attach Rust
+//! callbacks to well-defined points -- floor start, fractional-turn begin/end, entity faint,
+//! floor over -- instead of forking or wrapping [`GlobalDungeonData::generate_floor`],
+//! [`GlobalDungeonData::run_fractional_turn`], [`GlobalDungeonData::handle_faint`], etc. by hand.
+//!
+//! Modeled on the idea behind ToME/Angband's `dungeon.pkg` binding, which exposed the engine's
+//! own dungeon routines as scriptable entry points: the `_with_hooks` methods below are thin
+//! wrappers around the plain FFI-backed ones that additionally consult a [`HookRegistry`] before
+//! and/or after doing the real work.
+//!
+//! [`HookRegistry`] is caller-owned bookkeeping, the same way [`crate::api::dungeon_mode::event_manager::EventManager`]
+//! is: create one, register handlers on it, and call the `_with_hooks` variant of whichever
+//! engine routine instead of the plain one.
+
+use crate::api::dungeon_mode::{DungeonEntity, GlobalDungeonData};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// The dungeon lifecycle points a [`HookRegistry`] can dispatch to, passed to
+/// [`HookRegistry::register`] to pick which bucket a callback is added to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookSite {
+    /// A floor has just finished generating, via [`GlobalDungeonData::generate_floor_with_hooks`].
+    FloorStart,
+    /// [`GlobalDungeonData::run_fractional_turn_with_hooks`] is about to run the fractional turn.
+    FractionalTurnBegin,
+    /// [`GlobalDungeonData::run_fractional_turn_with_hooks`] just finished running the
+    /// fractional turn.
+    FractionalTurnEnd,
+    /// An entity is about to faint, via [`GlobalDungeonData::handle_faint_with_hooks`].
+    EntityFaint,
+    /// The current floor has just been detected as over, via
+    /// [`GlobalDungeonData::check_floor_over_with_hooks`].
+    FloorOver,
+}
+
+/// The payload a [`HookRegistry`] callback is invoked with, carrying whatever context is
+/// relevant for the [`HookSite`] that fired.
+#[derive(Clone, Copy)]
+pub enum HookEvent {
+    FloorStart,
+    FractionalTurnBegin { is_first_loop: bool },
+    FractionalTurnEnd { is_first_loop: bool },
+    /// Raw entity pointers rather than references: a callback may be one of several registered
+    /// for this site, each taking its turn to (re-)borrow the entities in sequence, so they
+    /// can't all hold a `&mut DungeonEntity` at once. Dereference only for the duration of the
+    /// callback.
+    EntityFaint {
+        fainted_entity: *mut DungeonEntity,
+        faint_reason: i32,
+        killer: *mut DungeonEntity,
+    },
+    FloorOver,
+}
+
+impl HookEvent {
+    fn site(&self) -> HookSite {
+        match self {
+            HookEvent::FloorStart => HookSite::FloorStart,
+            HookEvent::FractionalTurnBegin { .. } => HookSite::FractionalTurnBegin,
+            HookEvent::FractionalTurnEnd { .. } => HookSite::FractionalTurnEnd,
+            HookEvent::EntityFaint { .. } => HookSite::EntityFaint,
+            HookEvent::FloorOver => HookSite::FloorOver,
+        }
+    }
+}
+
+/// What a [`HookRegistry`] callback asks the dispatching `_with_hooks` method to do afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookResponse {
+    /// Let the site's default engine behavior proceed, if it has one.
+    Continue,
+    /// Skip the site's default engine behavior. Only meaningful for [`HookSite::EntityFaint`]
+    /// (see [`GlobalDungeonData::handle_faint_with_hooks`], which then skips
+    /// [`GlobalDungeonData::handle_faint`] entirely); ignored at every other site, since they
+    /// have nothing to suppress.
+    SuppressDefault,
+}
+
+/// A handle returned by [`HookRegistry::register`], used to later remove that specific callback
+/// via [`HookRegistry::unregister`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HookHandle {
+    site: HookSite,
+    id: u32,
+}
+
+type Callback = Box<dyn FnMut(&mut GlobalDungeonData, &HookEvent) -> HookResponse>;
+
+/// Registers callbacks keyed by [`HookSite`] and dispatches them from the `_with_hooks` methods
+/// on [`GlobalDungeonData`]. See the [module-level docs](self).
+#[derive(Default)]
+pub struct HookRegistry {
+    next_id: u32,
+    floor_start: Vec<(u32, Callback)>,
+    fractional_turn_begin: Vec<(u32, Callback)>,
+    fractional_turn_end: Vec<(u32, Callback)>,
+    entity_faint: Vec<(u32, Callback)>,
+    floor_over: Vec<(u32, Callback)>,
+}
+
+impl HookRegistry {
+    /// Creates an empty registry with no registered handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `callback` to be invoked whenever a `site` hook fires, returning a handle that
+    /// can later be passed to [`Self::unregister`]. Multiple callbacks can be registered for the
+    /// same site; all of them run, in registration order.
+    pub fn register(
+        &mut self,
+        site: HookSite,
+        callback: impl FnMut(&mut GlobalDungeonData, &HookEvent) -> HookResponse + 'static,
+    ) -> HookHandle {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.bucket_mut(site).push((id, Box::new(callback)));
+        HookHandle { site, id }
+    }
+
+    /// Removes a previously registered callback. Does nothing if it was already removed.
+    pub fn unregister(&mut self, handle: HookHandle) {
+        self.bucket_mut(handle.site)
+            .retain(|(id, _)| *id != handle.id);
+    }
+
+    fn bucket_mut(&mut self, site: HookSite) -> &mut Vec<(u32, Callback)> {
+        match site {
+            HookSite::FloorStart => &mut self.floor_start,
+            HookSite::FractionalTurnBegin => &mut self.fractional_turn_begin,
+            HookSite::FractionalTurnEnd => &mut self.fractional_turn_end,
+            HookSite::EntityFaint => &mut self.entity_faint,
+            HookSite::FloorOver => &mut self.floor_over,
+        }
+    }
+
+    /// Dispatches `event` to every handler registered for its site, in registration order. Bails
+    /// out immediately if that bucket is empty, so sites with no registered hooks cost only the
+    /// bucket lookup and a single `Vec::is_empty` check.
+    ///
+    /// Returns [`HookResponse::SuppressDefault`] if any handler asked to suppress the default
+    /// behavior, even if others after it didn't; every registered handler still runs regardless.
+    fn dispatch(&mut self, dungeon: &mut GlobalDungeonData, event: &HookEvent) -> HookResponse {
+        let bucket = self.bucket_mut(event.site());
+        if bucket.is_empty() {
+            return HookResponse::Continue;
+        }
+        let mut response = HookResponse::Continue;
+        for (_, callback) in bucket.iter_mut() {
+            if callback(dungeon, event) == HookResponse::SuppressDefault {
+                response = HookResponse::SuppressDefault;
+            }
+        }
+        response
+    }
+}
+
+impl<'a> GlobalDungeonData<'a> {
+    /// Like [`Self::generate_floor`], additionally dispatching [`HookSite::FloorStart`]
+    /// once the floor has finished generating.
+    pub fn generate_floor_with_hooks(&'a mut self, hooks: &mut HookRegistry) {
+        self.generate_floor();
+        hooks.dispatch(self, &HookEvent::FloorStart);
+    }
+
+    /// Like [`Self::run_fractional_turn`], additionally dispatching
+    /// [`HookSite::FractionalTurnBegin`] before and [`HookSite::FractionalTurnEnd`] after.
+    pub fn run_fractional_turn_with_hooks(&mut self, is_first_loop: bool, hooks: &mut HookRegistry) {
+        hooks.dispatch(self, &HookEvent::FractionalTurnBegin { is_first_loop });
+        self.run_fractional_turn(is_first_loop);
+        hooks.dispatch(self, &HookEvent::FractionalTurnEnd { is_first_loop });
+    }
+
+    /// Like [`Self::handle_faint`], but dispatches [`HookSite::EntityFaint`] first: if any
+    /// registered handler returns [`HookResponse::SuppressDefault`] (e.g. to implement a
+    /// Reviver Seed-like save), [`Self::handle_faint`] is skipped entirely.
+    pub fn handle_faint_with_hooks(
+        &mut self,
+        fainted_entity: &mut DungeonEntity,
+        faint_reason: i32,
+        killer: &mut DungeonEntity,
+        hooks: &mut HookRegistry,
+    ) {
+        let event = HookEvent::EntityFaint {
+            fainted_entity: fainted_entity as *mut DungeonEntity,
+            faint_reason,
+            killer: killer as *mut DungeonEntity,
+        };
+        if hooks.dispatch(self, &event) == HookResponse::SuppressDefault {
+            return;
+        }
+        self.handle_faint(fainted_entity, faint_reason, killer);
+    }
+
+    /// Like [`Self::is_floor_over`], additionally dispatching [`HookSite::FloorOver`] when it
+    /// returns `true`.
+    pub fn check_floor_over_with_hooks(&mut self, hooks: &mut HookRegistry) -> bool {
+        let over = self.is_floor_over();
+        if over {
+            hooks.dispatch(self, &HookEvent::FloorOver);
+        }
+        over
+    }
+}
@@ -0,0 +1,212 @@
+//! Held-item usage AI, for deciding whether a monster should eat/throw/use a held item this turn
+//! instead of (or before) attacking, analogous to how [`crate::api::dungeon_mode::effects::DungeonEffectsEmitter::choose_enemy_action`]
+//! decides between healing, inflicting a status, or boosting a stat.
+//!
+//! Modeled on NetHack's `muse.c` priority scheme: candidates are sorted into three buckets --
+//! defensive, offensive, misc -- tried strictly in that order, so a monster always prefers
+//! surviving over attacking, and attacking over buffing itself. This crate has no safe accessor
+//! for a monster's actual held item identity or remaining charges, so -- mirroring
+//! [`crate::api::dungeon_mode::DungeonMonsterRead::should_use_emergency_item`]'s own approach --
+//! the caller supplies an [`ItemAiCatalog`] of candidate items per bucket, and each is checked via
+//! [`crate::api::dungeon_mode::DungeonMonsterRead::is_holding_item`]. The engine is assumed to
+//! "know" item identities and charges (so an unidentified/charge-less item is simply not a
+//! candidate to begin with), and sticky/cursed status isn't distinguished, matching those two
+//! invariants from the base game's own item-use AI.
+
+use crate::api::dungeon_mode::{DungeonMonsterMut, DungeonMonsterRead};
+use crate::api::items::ItemId;
+use alloc::vec::Vec;
+use fixed::types::I24F8;
+
+/// Which of the three `muse.c`-style buckets an [`ItemUseDecision`] falls into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemUseCategory {
+    /// Eating a restorative berry, or using an escape/warp item, while in trouble.
+    Defensive,
+    /// Throwing an item or using an attack orb against a target in range and sight.
+    Offensive,
+    /// Using a stat seed or a speed/invisibility-type item, only when no threat is present.
+    Misc,
+}
+
+/// A single item-use decision returned by a [`MonsterItemAi`] implementation, for the caller to
+/// execute (eg. via [`crate::api::dungeon_mode::AiDecision::UseItem`] or a direct
+/// [`crate::api::dungeon_mode::DungeonMonsterWrite::set_action_use_item`] call).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemUseDecision {
+    pub category: ItemUseCategory,
+    pub item_id: ItemId,
+}
+
+/// Per-bucket catalogs of candidate held items, each tried in order (first one the monster is
+/// actually holding, per [`crate::api::dungeon_mode::DungeonMonsterRead::is_holding_item`], wins).
+#[derive(Debug, Clone, Default)]
+pub struct ItemAiCatalog {
+    /// Oran Berry/Reviver Seed-type items, tried first when [`DefaultItemAi`] decides the monster
+    /// is in trouble.
+    pub restorative_items: Vec<ItemId>,
+    /// Escape/warp-type items, tried if no restorative item is held while in trouble.
+    pub escape_items: Vec<ItemId>,
+    /// Thrown projectile-type items, tried first against a valid offensive target.
+    pub thrown_items: Vec<ItemId>,
+    /// Attack orb-type items, tried if no thrown item is held.
+    pub attack_orbs: Vec<ItemId>,
+    /// Stat seed/speed/invisibility-type items, only considered with no threat present.
+    pub misc_items: Vec<ItemId>,
+}
+
+/// Tunables for [`DefaultItemAi`].
+#[derive(Debug, Clone, Copy)]
+pub struct ItemAiThresholds {
+    /// HP fraction (out of 1) at or below which the monster is considered "in trouble" for the
+    /// defensive bucket.
+    pub low_health_fraction: I24F8,
+    /// Whether the defensive bucket is considered at all.
+    pub enable_defensive: bool,
+    /// Whether the offensive bucket is considered at all.
+    pub enable_offensive: bool,
+    /// Whether the misc bucket is considered at all.
+    pub enable_misc: bool,
+}
+
+impl Default for ItemAiThresholds {
+    fn default() -> Self {
+        Self {
+            low_health_fraction: I24F8::from_num(1) / I24F8::from_num(4),
+            enable_defensive: true,
+            enable_offensive: true,
+            enable_misc: true,
+        }
+    }
+}
+
+/// Per-turn context [`DefaultItemAi`] needs but can't compute itself: this crate has no safe
+/// accessor for entity grid positions or line-of-sight, so the caller is expected to supply
+/// whether a valid offensive target exists (in range and in sight, eg. via
+/// [`crate::api::dungeon_mode::is_position_in_sight`] and
+/// [`crate::api::dungeon_mode::get_chebyshev_distance`]) and whether the monster took damage this
+/// turn, the same way [`crate::api::dungeon_mode::pick_move`] takes its `targets` from the caller.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ItemAiContext {
+    /// Whether a hostile target is currently in range and in sight, making the offensive bucket
+    /// eligible to fire.
+    pub target_in_range_and_sight: bool,
+    /// Whether the monster took damage this turn, one of the three "in trouble" conditions
+    /// alongside low HP and a bad status.
+    pub took_damage_this_turn: bool,
+}
+
+/// A pluggable held-item decision engine for a monster's turn. See the [module-level docs](self).
+pub trait MonsterItemAi {
+    /// Decides whether `monster` should use a held item this turn, given `context`.
+    fn decide(
+        &self,
+        monster: &mut DungeonMonsterMut,
+        context: &ItemAiContext,
+    ) -> Option<ItemUseDecision>;
+}
+
+/// The default, NetHack `muse.c`-inspired [`MonsterItemAi`] implementation: defense > offense >
+/// misc, each bucket trying its catalog in order and stopping at the first item the monster is
+/// actually holding.
+#[derive(Debug, Clone, Default)]
+pub struct DefaultItemAi {
+    pub catalog: ItemAiCatalog,
+    pub thresholds: ItemAiThresholds,
+}
+
+impl DefaultItemAi {
+    /// Creates a new item AI with the given catalog and default thresholds.
+    pub fn new(catalog: ItemAiCatalog) -> Self {
+        Self {
+            catalog,
+            thresholds: ItemAiThresholds::default(),
+        }
+    }
+
+    /// Overrides the default thresholds.
+    pub fn with_thresholds(mut self, thresholds: ItemAiThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Whether `monster` is "in trouble": HP at or below [`ItemAiThresholds::low_health_fraction`],
+    /// afflicted with a status that prevents acting (the closest proxy this crate has for "a bad
+    /// status", per [`crate::api::dungeon_mode::pick_move`]'s own caveat on the same check), or
+    /// took damage this turn (via [`ItemAiContext::took_damage_this_turn`]).
+    fn in_trouble(&self, monster: &DungeonMonsterMut, context: &ItemAiContext) -> bool {
+        let low_hp = monster.hp_max > 0
+            && I24F8::from_num(monster.hp_current) / I24F8::from_num(monster.hp_max)
+                <= self.thresholds.low_health_fraction;
+        low_hp || monster.has_status_that_prevents_acting() || context.took_damage_this_turn
+    }
+
+    fn decide_defensive(&self, monster: &DungeonMonsterMut) -> Option<ItemUseDecision> {
+        self.catalog
+            .restorative_items
+            .iter()
+            .chain(self.catalog.escape_items.iter())
+            .copied()
+            .find(|&item_id| monster.is_holding_item(item_id))
+            .map(|item_id| ItemUseDecision {
+                category: ItemUseCategory::Defensive,
+                item_id,
+            })
+    }
+
+    fn decide_offensive(&self, monster: &DungeonMonsterMut) -> Option<ItemUseDecision> {
+        self.catalog
+            .thrown_items
+            .iter()
+            .chain(self.catalog.attack_orbs.iter())
+            .copied()
+            .find(|&item_id| monster.is_holding_item(item_id))
+            .map(|item_id| ItemUseDecision {
+                category: ItemUseCategory::Offensive,
+                item_id,
+            })
+    }
+
+    fn decide_misc(&self, monster: &DungeonMonsterMut) -> Option<ItemUseDecision> {
+        self.catalog
+            .misc_items
+            .iter()
+            .copied()
+            .find(|&item_id| monster.is_holding_item(item_id))
+            .map(|item_id| ItemUseDecision {
+                category: ItemUseCategory::Misc,
+                item_id,
+            })
+    }
+}
+
+impl MonsterItemAi for DefaultItemAi {
+    fn decide(
+        &self,
+        monster: &mut DungeonMonsterMut,
+        context: &ItemAiContext,
+    ) -> Option<ItemUseDecision> {
+        if self.thresholds.enable_defensive && self.in_trouble(monster, context) {
+            if let Some(decision) = self.decide_defensive(monster) {
+                return Some(decision);
+            }
+        }
+
+        if self.thresholds.enable_offensive && context.target_in_range_and_sight {
+            if let Some(decision) = self.decide_offensive(monster) {
+                return Some(decision);
+            }
+        }
+
+        if self.thresholds.enable_misc
+            && !context.target_in_range_and_sight
+            && !self.in_trouble(monster, context)
+        {
+            if let Some(decision) = self.decide_misc(monster) {
+                return Some(decision);
+            }
+        }
+
+        None
+    }
+}
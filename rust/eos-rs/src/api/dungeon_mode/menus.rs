@@ -4,6 +4,81 @@ use crate::api::dungeon_mode::DungeonEntity;
 use crate::api::overlay::OverlayLoadLease;
 use crate::ffi;
 
+/// Shared return value of the dungeon mode "MenuLoop" family of functions
+/// ([`others_menu_loop`], [`recruitment_search_menu_loop`], [`help_menu_loop`]), polled once per
+/// frame while one of these menus is open. Previously these just returned a bare `i32` that the
+/// docs only guessed was "probably some sort of enum shared by all the MenuLoop functions" --
+/// this is that enum, so callers can exhaustively match on it instead of carrying the raw
+/// sentinel values around.
+///
+/// Note: unverified, ported from Irdkwia's notes -- the exact sentinel values are a best-effort
+/// reverse-engineering guess, not a confirmed decompilation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuLoopResult {
+    /// The menu is still open and no option has been chosen yet; keep polling next frame.
+    StillOpen,
+    /// The menu was backed out of / dismissed without choosing an option.
+    Cancelled,
+    /// The menu has fully closed, handing control back to whatever opened it.
+    Closed,
+    /// The option at this index was chosen.
+    OptionSelected(i32),
+}
+
+impl From<i32> for MenuLoopResult {
+    fn from(value: i32) -> Self {
+        match value {
+            -1 => MenuLoopResult::StillOpen,
+            -2 => MenuLoopResult::Cancelled,
+            -3 => MenuLoopResult::Closed,
+            n if n >= 0 => MenuLoopResult::OptionSelected(n),
+            _ => MenuLoopResult::StillOpen,
+        }
+    }
+}
+
+/// Common per-frame poll interface shared by the dungeon "MenuLoop" family
+/// ([`others_menu_loop`], [`recruitment_search_menu_loop`], [`help_menu_loop`]), so a caller can
+/// drive any of them -- including a custom sub-menu registered via
+/// [`add_dungeon_sub_menu_option`] -- with the same state-machine loop instead of hand-rolling one
+/// per menu.
+///
+/// `OV` is the overlay the underlying loop function needs loaded (29 for [`OthersMenu`], 31 for
+/// [`RecruitmentSearchMenu`]/[`HelpMenu`]).
+pub trait DungeonMenuLoop<const OV: u32> {
+    /// Polls this menu for one frame, returning its current state. Call this once per frame for
+    /// as long as it returns anything other than [`MenuLoopResult::Closed`].
+    fn poll(&mut self, ov: &OverlayLoadLease<OV>) -> MenuLoopResult;
+}
+
+/// Drives the in-dungeon "others" menu via [`others_menu_loop`], for use with [`DungeonMenuLoop`].
+pub struct OthersMenu;
+
+impl DungeonMenuLoop<29> for OthersMenu {
+    fn poll(&mut self, ov29: &OverlayLoadLease<29>) -> MenuLoopResult {
+        others_menu_loop(ov29)
+    }
+}
+
+/// Drives the in-dungeon "recruitment search" menu via [`recruitment_search_menu_loop`], for use
+/// with [`DungeonMenuLoop`].
+pub struct RecruitmentSearchMenu;
+
+impl DungeonMenuLoop<31> for RecruitmentSearchMenu {
+    fn poll(&mut self, ov31: &OverlayLoadLease<31>) -> MenuLoopResult {
+        recruitment_search_menu_loop(ov31)
+    }
+}
+
+/// Drives the in-dungeon "help" menu via [`help_menu_loop`], for use with [`DungeonMenuLoop`].
+pub struct HelpMenu;
+
+impl DungeonMenuLoop<31> for HelpMenu {
+    fn poll(&mut self, ov31: &OverlayLoadLease<31>) -> MenuLoopResult {
+        help_menu_loop(ov31)
+    }
+}
+
 /// Adds an option to the list of actions that can be taken on a monster, item or move to the
 /// currently active sub-menu on dungeon mode (team, moves, items, etc.).
 pub fn add_dungeon_sub_menu_option(
@@ -18,12 +93,8 @@ pub fn add_dungeon_sub_menu_option(
 ///
 /// It contains a switch to determine whether an option has been chosen or not and a second switch
 /// that determines what to do depending on which option was chosen.
-///
-/// Returns an int (Actually, this is probably some sort of enum shared by all the
-/// MenuLoop functions).
-///        
-pub fn others_menu_loop(_ov29: &OverlayLoadLease<29>) -> i32 {
-    unsafe { ffi::OthersMenuLoop() }
+pub fn others_menu_loop(_ov29: &OverlayLoadLease<29>) -> MenuLoopResult {
+    unsafe { ffi::OthersMenuLoop() }.into()
 }
 
 /// Called when the in-dungeon "others" menu is open. Does not return until the menu is closed.
@@ -69,20 +140,14 @@ pub fn rest_menu(_ov31: &OverlayLoadLease<31>) {
 }
 
 /// Called on each frame while the in-dungeon "recruitment search" menu is open.
-///
-/// Returns an int (Actually, this is probably some sort of enum shared by all the
-/// MenuLoop functions).
-pub fn recruitment_search_menu_loop(_ov31: &OverlayLoadLease<31>) -> i32 {
-    unsafe { ffi::RecruitmentSearchMenuLoop() }
+pub fn recruitment_search_menu_loop(_ov31: &OverlayLoadLease<31>) -> MenuLoopResult {
+    unsafe { ffi::RecruitmentSearchMenuLoop() }.into()
 }
 
 /// Called on each frame while the in-dungeon "help" menu is open.
 ///
 /// The menu is still considered open while one of the help pages is being viewed, so this
 /// function keeps being called even after choosing an option.
-///
-/// Returns an int (Actually, this is probably some sort of enum shared by all the
-/// MenuLoop functions).
-pub fn help_menu_loop(_ov31: &OverlayLoadLease<31>) -> i32 {
-    unsafe { ffi::HelpMenuLoop() }
+pub fn help_menu_loop(_ov31: &OverlayLoadLease<31>) -> MenuLoopResult {
+    unsafe { ffi::HelpMenuLoop() }.into()
 }
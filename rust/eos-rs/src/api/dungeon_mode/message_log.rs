@@ -1,10 +1,157 @@
 use crate::api::dungeon_mode::entity::DungeonEntity;
+use crate::api::dungeon_mode::set_both_screens_window_color_to_default;
+use crate::api::messages::set_both_screens_windows_color;
 use crate::api::overlay::{CreatableWithLease, OverlayLoadLease};
 use crate::ctypes::*;
 use crate::ffi;
 use crate::string_util::str_to_cstring;
+use alloc::borrow::ToOwned;
+use alloc::ffi::CString;
+use alloc::format;
 use core::ffi::CStr;
 use core::fmt::Debug;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// Broad category for a message logged with [`LogMessageBuilder`], used by
+/// [`LogMessageBuilder::channel`] and the process-wide verbosity filter
+/// ([`set_verbosity_threshold`]).
+///
+/// Ordered from least to most important; [`set_verbosity_threshold`] silently drops anything
+/// below the configured channel. [`Self::Plain`], the default channel for a builder that doesn't
+/// call [`LogMessageBuilder::channel`], is the highest, so existing call sites are unaffected
+/// unless they opt in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MsgChannel {
+    /// Flavor-only noise (eg. a move's sound effect flavor text), the first to go when trimming
+    /// verbosity.
+    Sound,
+    /// Item pickup/use messages.
+    Item,
+    /// Status condition inflict/cure messages.
+    Status,
+    /// Damage/heal numbers and faint messages.
+    Damage,
+    /// Uncategorized narrative text; always shown regardless of the verbosity threshold.
+    Plain,
+}
+
+/// The process-wide verbosity threshold used by [`LogMessageBuilder`]. Defaults to
+/// [`MsgChannel::Sound`], the lowest channel, so nothing is filtered unless a mod opts in.
+static VERBOSITY_THRESHOLD: AtomicU8 = AtomicU8::new(MsgChannel::Sound as u8);
+
+/// Sets the process-wide verbosity threshold: messages built with [`LogMessageBuilder`] on a
+/// channel below `threshold` (see [`LogMessageBuilder::channel`]) are silently dropped instead of
+/// reaching the game's message log. Useful for compiling out (or toggling off) debug-level combat
+/// spam without threading a flag through every call site.
+pub fn set_verbosity_threshold(threshold: MsgChannel) {
+    VERBOSITY_THRESHOLD.store(threshold as u8, Ordering::Relaxed);
+}
+
+fn channel_is_enabled(channel: MsgChannel) -> bool {
+    channel as u8 >= VERBOSITY_THRESHOLD.load(Ordering::Relaxed)
+}
+
+/// A window frame palette, as a raw palette index (see
+/// [`crate::api::messages::set_screen_windows_color`]); this crate doesn't have named constants
+/// for the palettes the game ships, so build one directly, eg. `WindowColor(3)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowColor(pub u8);
+
+/// Either the message ID or the literal text a [`LogMessageBuilder`] call was about to log,
+/// whichever it was called with; used to detect an identical message firing back-to-back for the
+/// same user. See [`LogMessageBuilder::dedup`].
+#[derive(PartialEq, Eq)]
+enum DedupKey {
+    Id(i32),
+    Text(CString),
+}
+
+/// A slot in the dedup ring buffer (see [`LogMessageBuilder::dedup`]): the last distinct message
+/// logged for a given user, and how many additional times it's fired back-to-back since (not
+/// counting the first).
+struct DedupSlot {
+    user: *const DungeonEntity,
+    key: DedupKey,
+    repeats: u32,
+}
+
+/// How many users' most-recent messages [`LogMessageBuilder::dedup`] tracks at once. Small and
+/// bounded: once full, the oldest tracked user's pending tally is flushed and its slot reused.
+const DEDUP_RING_SIZE: usize = 8;
+
+/// Ring buffer backing [`LogMessageBuilder::dedup`]. Process-wide (not per-builder), since the
+/// same status message for a given user may well be logged through several short-lived builders
+/// in a row.
+///
+/// # Safety
+/// Single-threaded (GBA code, one core), so plain unsynchronized statics are the established
+/// pattern in this crate; see eg. `InventorySession`'s `INVENTORY_SESSION_ACTIVE`.
+static mut DEDUP_RING: [Option<DedupSlot>; DEDUP_RING_SIZE] =
+    [None, None, None, None, None, None, None, None];
+static mut DEDUP_RING_NEXT: usize = 0;
+
+/// Logs a quiet "(x N)" follow-up line tallying the repeats that were suppressed for `slot`.
+fn log_dedup_tally(slot: &DedupSlot) {
+    if slot.repeats == 0 {
+        return;
+    }
+    let tally = format!("(x{})", slot.repeats + 1);
+    let message = str_to_cstring(tally);
+    // SAFETY: `slot.user` was a valid `&DungeonEntity` when the slot was created, and dedup
+    // slots don't outlive the message-logging calls that populate them.
+    unsafe {
+        ffi::LogMessageQuiet(slot.user as *mut DungeonEntity, message.as_ptr() as *const c_char);
+    }
+}
+
+/// Checks the dedup ring buffer for `user`/`key` (see [`LogMessageBuilder::dedup`]). Returns
+/// `true` if this exact message just fired for this user and should be suppressed; otherwise
+/// flushes and replaces whatever was previously tracked for `user` and returns `false`.
+fn dedup_check(user: *const DungeonEntity, key: DedupKey) -> bool {
+    // SAFETY: single-threaded.
+    #[allow(static_mut_refs)]
+    unsafe {
+        if let Some(slot) = DEDUP_RING.iter_mut().flatten().find(|slot| slot.user == user) {
+            if slot.key == key {
+                slot.repeats += 1;
+                return true;
+            }
+            log_dedup_tally(slot);
+            slot.key = key;
+            slot.repeats = 0;
+            return false;
+        }
+
+        let idx = DEDUP_RING_NEXT;
+        DEDUP_RING_NEXT = (DEDUP_RING_NEXT + 1) % DEDUP_RING_SIZE;
+        if let Some(evicted) = DEDUP_RING[idx].take() {
+            log_dedup_tally(&evicted);
+        }
+        DEDUP_RING[idx] = Some(DedupSlot {
+            user,
+            key,
+            repeats: 0,
+        });
+        false
+    }
+}
+
+/// Flushes (see [`LogMessageBuilder::dedup`]) and forgets any pending repeat tally tracked for
+/// `user`, logging its "(x N)" follow-up line immediately instead of waiting for a distinct
+/// message or the ring buffer slot being reused.
+pub fn flush_dedup(user: &DungeonEntity) {
+    // SAFETY: single-threaded.
+    #[allow(static_mut_refs)]
+    unsafe {
+        if let Some(slot) = DEDUP_RING
+            .iter_mut()
+            .find(|slot| matches!(slot, Some(s) if s.user == user as *const DungeonEntity))
+        {
+            log_dedup_tally(slot.as_ref().unwrap());
+            *slot = None;
+        }
+    }
+}
 
 /// Builder for creating dungeon message log messages.
 ///
@@ -20,6 +167,9 @@ pub struct LogMessageBuilder<'a> {
     popup: bool,
     check_user: bool,
     target_check_fainted: Option<&'a DungeonEntity>,
+    channel: MsgChannel,
+    color: Option<WindowColor>,
+    dedup: bool,
 }
 
 impl<'a> CreatableWithLease<29> for LogMessageBuilder<'a> {
@@ -29,6 +179,9 @@ impl<'a> CreatableWithLease<29> for LogMessageBuilder<'a> {
             popup: false,
             check_user: false,
             target_check_fainted: None,
+            channel: MsgChannel::Plain,
+            color: None,
+            dedup: false,
         }
     }
 
@@ -61,6 +214,37 @@ impl<'a> LogMessageBuilder<'a> {
         self
     }
 
+    /// Tags this message with a [`MsgChannel`], subjecting it to the process-wide verbosity
+    /// filter (see [`set_verbosity_threshold`]). Defaults to [`MsgChannel::Plain`].
+    pub fn channel(&mut self, channel: MsgChannel) -> &mut Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Sets the window frame color to use while this message is displayed, restored back to the
+    /// default (via [`crate::api::dungeon_mode::set_both_screens_window_color_to_default`])
+    /// immediately after logging.
+    pub fn color(&mut self, color: WindowColor) -> &mut Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Collapses identical messages logged back-to-back for the same user: instead of flooding
+    /// the log, repeats are tallied and only surface as a single "(x N)" follow-up line once a
+    /// distinct message comes in for that user (or [`Self::flush`] is called explicitly).
+    ///
+    /// Useful for multi-hit moves that call the same status message once per hit.
+    pub fn dedup(&mut self) -> &mut Self {
+        self.dedup = true;
+        self
+    }
+
+    /// Flushes and logs any repeat tally pending for `user` from a previous [`Self::dedup`]
+    /// call, without waiting for a distinct message to trigger it.
+    pub fn flush(&self, user: &DungeonEntity) {
+        flush_dedup(user)
+    }
+
     /// Replaces instances of a given placeholder tag by the string representation of the given entity.
     /// Concretely this means that any occurrences of `\[string:<string_id>\]` will be replaced by the
     /// name of the given entity.
@@ -78,10 +262,29 @@ impl<'a> LogMessageBuilder<'a> {
         self
     }
 
+    /// Applies [`Self::color`] (if set) for the duration of `f`, restoring the default window
+    /// color on both screens afterward.
+    fn with_color<R>(&self, f: impl FnOnce() -> R) -> R {
+        if let Some(color) = self.color {
+            set_both_screens_windows_color(color.0);
+            let result = f();
+            set_both_screens_window_color_to_default(&self._lease);
+            result
+        } else {
+            f()
+        }
+    }
+
     /// Writes a log entry using the message with the given message ID.
     pub fn log_msg(&mut self, user: &DungeonEntity, message_id: i32) {
+        if !channel_is_enabled(self.channel) {
+            return;
+        }
+        if self.dedup && dedup_check(user as *const DungeonEntity, DedupKey::Id(message_id)) {
+            return;
+        }
         // SAFETY: We have a lease on the overlay existing.
-        unsafe {
+        self.with_color(|| unsafe {
             match (self.popup, self.check_user, self.target_check_fainted) {
                 (false, false, None) => ffi::LogMessageByIdQuiet(force_mut_ptr!(user), message_id),
                 (_, true, None) => {
@@ -101,7 +304,7 @@ impl<'a> LogMessageBuilder<'a> {
                     message_id,
                 ),
             }
-        }
+        })
     }
 
     pub fn log_str<S: AsRef<str> + Debug>(&mut self, user: &DungeonEntity, message: S) {
@@ -109,9 +312,21 @@ impl<'a> LogMessageBuilder<'a> {
     }
 
     pub fn log_cstr<S: AsRef<CStr>>(&mut self, user: &DungeonEntity, message: S) {
-        let message = message.as_ref().as_ptr() as *const c_char;
+        if !channel_is_enabled(self.channel) {
+            return;
+        }
+        let message = message.as_ref();
+        if self.dedup
+            && dedup_check(
+                user as *const DungeonEntity,
+                DedupKey::Text(message.to_owned()),
+            )
+        {
+            return;
+        }
+        let message = message.as_ptr() as *const c_char;
         // SAFETY: We have a lease on the overlay existing.
-        unsafe {
+        self.with_color(|| unsafe {
             match (self.popup, self.check_user, self.target_check_fainted) {
                 (false, false, None) => ffi::LogMessageQuiet(force_mut_ptr!(user), message),
                 (_, true, None) => ffi::LogMessageWithPopupCheckUser(force_mut_ptr!(user), message),
@@ -122,6 +337,6 @@ impl<'a> LogMessageBuilder<'a> {
                     message,
                 ),
             }
-        }
+        })
     }
 }
@@ -9,13 +9,33 @@ mod moves;
 mod random;
 mod tile;
 
+pub mod ai;
 pub mod animations;
+pub mod announcements;
+pub mod cinematics;
+pub mod clouds;
 pub mod dungeon_generator;
+pub mod dungeon_history;
+pub mod event_manager;
 pub mod fixed_room;
+pub mod hooks;
+pub mod item_ai;
 pub mod items;
 pub mod menus;
+pub mod pathfinding;
+#[cfg_attr(docsrs, doc(cfg(feature = "rune")))]
+#[cfg(feature = "rune")]
+pub mod rune_effects;
+#[cfg_attr(docsrs, doc(cfg(feature = "rune")))]
+#[cfg(feature = "rune")]
+pub mod rune_turn_hooks;
+pub mod shaft;
+pub mod shaft_trap;
+pub mod spawn_table;
 pub mod sprites;
+pub mod trajectory;
 pub mod traps;
+pub mod weather_schedule;
 
 use crate::api::enums::Direction;
 use core::ptr;
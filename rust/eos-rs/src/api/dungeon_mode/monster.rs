@@ -4,7 +4,9 @@ use crate::api::iq::IqSkillId;
 use crate::api::items::{ExclusiveItemEffectId, ItemId};
 use crate::api::monsters::MonsterSpeciesId;
 use crate::api::moves::*;
+use crate::api::random::rand_i32;
 use crate::api::types::MonsterTypeId;
+use alloc::vec::Vec;
 use core::ops::{Deref, DerefMut};
 use fixed::types::I24F8;
 
@@ -173,6 +175,285 @@ pub trait DungeonMonsterRead: private::Sealed {
         }
     }
 
+    /// A safe, high-level wrapper around [`Self::calc_damage`] that parses out the parts of
+    /// the resulting [`ffi::damage_data`] this crate currently understands, instead of handing
+    /// back the raw, still partially unknown struct.
+    ///
+    /// `crit` forces the hit to be treated as a critical hit instead of rolling the move's usual
+    /// crit chance; it's echoed back verbatim as [`DamagePrediction::forced_critical_hit`] rather
+    /// than reflecting what `calc_damage` actually rolled, since `damage_data`'s layout doesn't
+    /// expose a field for that yet -- see that field's doc comment. The attack type, power and
+    /// move ID are derived straight from `the_move` via [`Self::get_move_type_if_used_by_self`]
+    /// and [`Self::get_move_power`], and the damage multiplier is left at 1x, matching a normal
+    /// attack.
+    ///
+    /// Note: like [`Self::calc_damage`], the exact layout of `damage_data` is still being
+    /// reverse-engineered, so [`DamagePrediction::damage`] may not be fully accurate yet.
+    fn predict_damage(&self, defender: &DungeonEntity, the_move: &Move, crit: bool) -> DamagePrediction {
+        let attack_type = self.get_move_type_if_used_by_self(the_move);
+        let type_matchup = self
+            .get_type_matchup(defender, TargetTypeIndex::FirstType, attack_type)
+            .unwrap_or(DungeonTypeMatchup::Neutral);
+        if type_matchup == DungeonTypeMatchup::Immune {
+            return DamagePrediction {
+                damage: None,
+                forced_critical_hit: false,
+                type_matchup,
+            };
+        }
+        let move_power = self.get_move_power(the_move);
+        let crit_chance = if crit { 100 } else { the_move.get_crit_chance() };
+        let mut damage_out: ffi::damage_data = unsafe { core::mem::zeroed() };
+        self.calc_damage(
+            defender,
+            attack_type,
+            move_power,
+            crit_chance,
+            &mut damage_out,
+            I24F8::from_num(1),
+            the_move.id.val(),
+            0,
+        );
+        DamagePrediction {
+            damage: Some(damage_out.damage),
+            forced_critical_hit: crit,
+            type_matchup,
+        }
+    }
+
+    /// Gets the monster's base offensive stat (Atk or Sp. Atk) used by `move_category`, before
+    /// exclusive item boosts. Panics if `move_category` is not physical or special.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes — assumes [`ffi::monster::offensive_stats`]
+    /// is laid out `[Atk, Sp. Atk]`.
+    fn get_offensive_stat(&self, move_category: MoveCategory) -> i32 {
+        match move_category {
+            MoveCategory::Physical => self.monster().offensive_stats[0] as i32,
+            MoveCategory::Special => self.monster().offensive_stats[1] as i32,
+            _ => panic!("get_offensive_stat called with invalid move category"),
+        }
+    }
+
+    /// Gets the monster's base defensive stat (Def or Sp. Def) used against `move_category`,
+    /// before exclusive item boosts. Panics if `move_category` is not physical or special.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes — assumes [`ffi::monster::defensive_stats`]
+    /// is laid out `[Def, Sp. Def]`.
+    fn get_defensive_stat(&self, move_category: MoveCategory) -> i32 {
+        match move_category {
+            MoveCategory::Physical => self.monster().defensive_stats[0] as i32,
+            MoveCategory::Special => self.monster().defensive_stats[1] as i32,
+            _ => panic!("get_defensive_stat called with invalid move category"),
+        }
+    }
+
+    /// Computes an expected damage range for a hypothetical attack, entirely in Rust (no call
+    /// into the game's `CalcDamage`), using the standard Gen-V-style damage formula. This lets AI
+    /// and UI mods cheaply and deterministically evaluate "what if" outcomes.
+    ///
+    /// This approximates the ROM's real routine and is intended for prediction, not authoritative
+    /// results; prefer [`Self::calc_damage_data`] when the exact result matters.
+    ///
+    /// The offensive stat is the attacker's (self) Atk or Sp. Atk, and the defensive stat is
+    /// `defender`'s Def or Sp. Def, chosen by `move_id`'s category; both get their exclusive item
+    /// boost added in (see [`Self::get_exclusive_item_offense_boost`]). The staged formula
+    /// `base = 2*level/5 + 2`, `raw = base * move_power * Atk / (Def * 50) + 2` is then multiplied
+    /// by, in order, with truncation between each stage: STAB (x1.5, if
+    /// `self.has_type(attack_type)`), type effectiveness (the product of the
+    /// [`Self::get_type_matchup`] lookups against both of the defender's types), the weather's
+    /// offense multiplier for `attack_type`, and a critical hit multiplier (x1.5, if `crit`). The
+    /// final value is split into an 85%-100% min/max pair, matching the game's random damage
+    /// spread.
+    ///
+    /// Panics if `move_id` doesn't resolve to a move with a physical or special category.
+    fn preview_damage(
+        &self,
+        defender: &impl DungeonMonsterRead,
+        move_id: MoveId,
+        attack_type: MonsterTypeId,
+        move_power: i32,
+        crit: bool,
+        weather: Weather,
+    ) -> DamageRange {
+        let move_category = match unsafe { ffi::GetMoveCategory(move_id) }.try_into() {
+            Ok(c @ MoveCategory::Physical) | Ok(c @ MoveCategory::Special) => c,
+            _ => panic!("preview_damage called with a move that has no physical/special category"),
+        };
+
+        let level = self.monster().level as i32;
+        let base = 2 * level / 5 + 2;
+        let offense =
+            self.get_offensive_stat(move_category) + self.get_exclusive_item_offense_boost(move_category);
+        let defense = (defender.get_defensive_stat(move_category)
+            + defender.get_exclusive_item_defense_boost(move_category))
+        .max(1);
+        let mut damage = base * move_power * offense / (defense * 50) + 2;
+
+        if self.has_type(attack_type) {
+            damage = damage * 3 / 2;
+        }
+
+        let matchup_pct: i32 = [TargetTypeIndex::FirstType, TargetTypeIndex::SecondType]
+            .into_iter()
+            .map(
+                |target_type_index| match self.get_type_matchup(
+                    defender.entity(),
+                    target_type_index,
+                    attack_type,
+                ) {
+                    Some(DungeonTypeMatchup::Immune) => 0,
+                    Some(DungeonTypeMatchup::NotVeryEffective) => 50,
+                    Some(DungeonTypeMatchup::SuperEffective) => 200,
+                    _ => 100,
+                },
+            )
+            .product();
+        damage = damage * matchup_pct / 10000;
+
+        damage = (weather_damage_multiplier(weather, attack_type) * I24F8::from_num(damage))
+            .to_num();
+
+        if crit {
+            damage = damage * 3 / 2;
+        }
+
+        DamageRange {
+            min: damage * 85 / 100,
+            max: damage,
+        }
+    }
+
+    /// A safe, high-level wrapper around [`Self::calc_damage`] that returns a [`DamageData`]
+    /// instead of requiring the caller to provide an output buffer.
+    ///
+    /// `effectiveness` is computed via [`Self::get_type_matchup`] rather than parsed out of the
+    /// raw [`ffi::damage_data`] struct (whose exact field layout for this is still unknown); if
+    /// the matchup is [`DungeonTypeMatchup::Immune`], [`Self::calc_damage`] isn't even called and
+    /// `damage_dealt` is left at 0.
+    fn calc_damage_data(
+        &self,
+        defender: &DungeonEntity,
+        attack_type: MonsterTypeId,
+        attack_power: i32,
+        move_category: MoveCategory,
+        crit_chance: i32,
+        is_critical_hit: bool,
+        damage_multiplier: I24F8,
+        move_id: MoveId,
+        param_9: i32,
+    ) -> DamageData {
+        let effectiveness = self
+            .get_type_matchup(defender, TargetTypeIndex::FirstType, attack_type)
+            .unwrap_or(DungeonTypeMatchup::Neutral);
+        if effectiveness == DungeonTypeMatchup::Immune {
+            return DamageData {
+                damage_dealt: 0,
+                effectiveness,
+                critical_hit: is_critical_hit,
+                full_type_immunity: true,
+                move_category,
+            };
+        }
+        let mut damage_out: ffi::damage_data = unsafe { core::mem::zeroed() };
+        self.calc_damage(
+            defender,
+            attack_type,
+            attack_power,
+            crit_chance,
+            &mut damage_out,
+            damage_multiplier,
+            move_id,
+            param_9,
+        );
+        DamageData {
+            damage_dealt: damage_out.damage,
+            effectiveness,
+            critical_hit: is_critical_hit,
+            full_type_immunity: false,
+            move_category,
+        }
+    }
+
+    /// A safe, high-level wrapper around [`Self::calc_damage_fixed`] that returns a
+    /// [`DamageData`] instead of requiring the caller to provide an output buffer.
+    ///
+    /// See the caveats on [`Self::calc_damage_data`]: `effectiveness` is computed
+    /// independently via [`Self::get_type_matchup`] rather than read out of the raw struct.
+    ///
+    /// # Safety
+    /// The caller must make sure the undefined params are valid for this function (see
+    /// [`Self::calc_damage_fixed`]).
+    unsafe fn calc_damage_fixed_data(
+        &self,
+        defender: &DungeonEntity,
+        fixed_damage: i32,
+        param_4: ffi::undefined4,
+        attack_type: MonsterTypeId,
+        move_category: MoveCategory,
+        param_8: i16,
+        message_type: ffi::undefined4,
+        param_10: ffi::undefined4,
+        param_11: ffi::undefined4,
+    ) -> DamageData {
+        let effectiveness = self
+            .get_type_matchup(defender, TargetTypeIndex::FirstType, attack_type)
+            .unwrap_or(DungeonTypeMatchup::Neutral);
+        let mut damage_out: ffi::damage_data = core::mem::zeroed();
+        self.calc_damage_fixed(
+            defender,
+            fixed_damage,
+            param_4,
+            &mut damage_out,
+            attack_type,
+            move_category,
+            param_8,
+            message_type,
+            param_10,
+            param_11,
+        );
+        DamageData {
+            damage_dealt: damage_out.damage,
+            effectiveness,
+            critical_hit: false,
+            full_type_immunity: effectiveness == DungeonTypeMatchup::Immune,
+            move_category,
+        }
+    }
+
+    /// A safe-ish wrapper around [`Self::calc_damage_projectile`] that returns a [`DamageData`].
+    ///
+    /// Unlike [`Self::calc_damage_data`], the projectile damage calculation's output parameter
+    /// hasn't been identified yet (see the caveat on [`Self::calc_damage_projectile`]), so
+    /// `damage_dealt` is always left at 0 here; only `effectiveness` and `move_category`, which
+    /// this wrapper derives independently, can currently be trusted.
+    ///
+    /// # Safety
+    /// The caller must make sure the undefined params are valid for this function (see
+    /// [`Self::calc_damage_projectile`]).
+    unsafe fn calc_damage_projectile_data(
+        &self,
+        defender: &DungeonEntity,
+        used_move: &Move,
+        move_power: i32,
+        move_category: MoveCategory,
+    ) -> DamageData {
+        let effectiveness = self
+            .get_type_matchup(
+                defender,
+                TargetTypeIndex::FirstType,
+                self.get_move_type_if_used_by_self(used_move),
+            )
+            .unwrap_or(DungeonTypeMatchup::Neutral);
+        self.calc_damage_projectile(defender, used_move, move_power, 0, 0);
+        DamageData {
+            damage_dealt: 0,
+            effectiveness,
+            critical_hit: false,
+            full_type_immunity: effectiveness == DungeonTypeMatchup::Immune,
+            move_category,
+        }
+    }
+
     /// Appears to calculate recoil damage to the monster.
     /// This function wraps [`Self::calc_damage_fixed`] using the monster as both the attacker and
     /// the defender, after doing some basic checks (like if the monster is already at 0 HP)
@@ -508,6 +789,26 @@ pub trait DungeonMonsterRead: private::Sealed {
         }
     }
 
+    /// Checks whether this monster should use a held emergency item this turn, instead of
+    /// attacking or fleeing.
+    ///
+    /// Returns `Some(item_id)` if [`Self::has_low_health`] is true and the monster is holding
+    /// one of the items in `restorative_items` (checked via [`Self::is_holding_item`]), in which
+    /// case that item is the one that should be consumed. Otherwise, returns `None`.
+    ///
+    /// This is meant to be checked before [`Self::should_monster_run_away`] in an AI decision
+    /// chain: a monster should only fall back to fleeing if it has no emergency item to use,
+    /// matching a "drink to survive, otherwise flee" decision tree.
+    fn should_use_emergency_item(&self, restorative_items: &[ItemId]) -> Option<ItemId> {
+        if !self.has_low_health() {
+            return None;
+        }
+        restorative_items
+            .iter()
+            .copied()
+            .find(|&item_id| self.is_holding_item(item_id))
+    }
+
     /// Checks if this monster should try to reach the stairs when controlled by the AI.
     fn should_head_to_stairs(&self) -> bool {
         unsafe { ffi::ShouldMonsterHeadToStairs(force_mut_ptr!(self.entity())) > 0 }
@@ -602,6 +903,102 @@ pub trait DungeonMonsterRead: private::Sealed {
         }
     }
 
+    /// Filters `candidate_entities` and `candidate_tiles` down to the ones this monster may
+    /// actually target with `the_move`, honoring the move's [`MoveTargetAndRange`] AI condition
+    /// (via [`Self::is_target_eligible`], checking all conditions) and
+    /// [`Self::can_target_entity`]/[`Self::can_target_position`] (which in turn account for
+    /// [`Self::can_see_invisible_monsters`] and [`Self::is_blinded`]).
+    ///
+    /// The caller supplies the raw candidates for the move's range geometry (eg. every entity or
+    /// tile in the room, or along the line in front of the monster); this only does the AI
+    /// targeting filter, not the range-to-tiles geometry expansion itself, since that needs
+    /// access to the floor layout that this trait doesn't have.
+    fn enumerate_targets<'a>(
+        &self,
+        the_move: &Move,
+        is_ai: bool,
+        candidate_entities: impl IntoIterator<Item = &'a DungeonEntity>,
+        candidate_tiles: impl IntoIterator<Item = ffi::position>,
+    ) -> TargetSet<'a> {
+        let entities = candidate_entities
+            .into_iter()
+            .filter(|target| self.can_target_entity(target))
+            .filter(|target| {
+                let move_ai_range = self.get_move_target_and_range(the_move, is_ai);
+                self.is_target_eligible(move_ai_range, target, the_move, true)
+            })
+            .collect();
+        let tiles = candidate_tiles
+            .into_iter()
+            .filter(|tile| self.can_target_position(tile))
+            .collect();
+        TargetSet { entities, tiles }
+    }
+
+    /// Traces which of `candidate_entities` a use of `the_move` in `direction` would actually
+    /// hit, without dealing any damage or writing any message to the dungeon log: a pure
+    /// lookahead for custom moves and AI scripts to preview a shot before committing to
+    /// [`DungeonEffectsEmitter::deal_damage`].
+    ///
+    /// Like [`Self::enumerate_targets`], this doesn't walk the floor grid itself; the caller
+    /// supplies the candidates (eg. [`EntityTableRead::get_active_monsters`]) and this only
+    /// checks each one against the move's range:
+    /// [`MoveRange::Room`] hits every candidate sharing the user's tile room,
+    /// [`MoveRange::Floor`] hits every candidate, [`MoveRange::User`] never hits anyone else, and
+    /// [`MoveRange::Special`] has per-move custom targeting this tracer doesn't model and always
+    /// reports no hits. Every other range is checked with
+    /// [`DungeonEffectsEmitter::is_target_in_range`] out to
+    /// [`MoveRange::max_tile_distance`], which accounts for walls and, for monsters with Course
+    /// Checker, corner-cutting along the way.
+    ///
+    /// The user itself is never included in the result. Each hit is tagged as an ally or an
+    /// enemy of the user, and [`MoveTraceResult::hits_ally`] summarizes whether any of them are
+    /// allies, so scripted AI and custom moves can abort or re-aim a move that would catch a
+    /// friendly creature in its path.
+    fn trace_move_targets<'a>(
+        &self,
+        effects: &DungeonEffectsEmitter,
+        the_move: &Move,
+        direction: Direction,
+        candidate_entities: impl IntoIterator<Item = &'a DungeonEntity>,
+    ) -> MoveTraceResult<'a> {
+        let range = self.get_move_target_and_range(the_move, false).range;
+        let user = self.entity();
+
+        let hits: Vec<TracedTarget> = candidate_entities
+            .into_iter()
+            .filter(|target| !core::ptr::eq(*target, user))
+            .filter(|target| match range {
+                None | Some(MoveRange::User) | Some(MoveRange::Special) => false,
+                Some(MoveRange::Floor) => true,
+                Some(MoveRange::Room) => {
+                    match (user.get_tile(), target.get_tile()) {
+                        (Some(user_tile), Some(target_tile)) => user_tile.room == target_tile.room,
+                        _ => false,
+                    }
+                }
+                Some(other_range) => match other_range.max_tile_distance() {
+                    Some(max_distance) => {
+                        effects.is_target_in_range(user, target, direction, max_distance)
+                    }
+                    None => false,
+                },
+            })
+            .map(|target| TracedTarget {
+                entity: target,
+                is_ally: target
+                    .info_for_monster()
+                    .map(|target_monster| {
+                        target_monster.0.is_not_team_member == self.monster().is_not_team_member
+                    })
+                    .unwrap_or(false),
+            })
+            .collect();
+
+        let hits_ally = hits.iter().any(|hit| hit.is_ally);
+        MoveTraceResult { hits, hits_ally }
+    }
+
     /// Checks if a monster can use the given move. Will always return true for the regular attack.
     /// Will return false if the move if the flag [`Move::f_disabled`] is true, if the flag
     /// [`Move::f_sealed`] is true.
@@ -661,6 +1058,293 @@ pub trait DungeonMonsterRead: private::Sealed {
             ffi::StatusCheckerCheck(force_mut_ptr!(self.entity()), force_mut_ptr!(the_move)) > 0
         }
     }
+
+    /// Picks the move in `moveset` this monster should use against `target`, the way a simple
+    /// AI routine would: weighing move power against type effectiveness.
+    ///
+    /// Moves that fail [`Self::can_use_move`] (with `extra_checks` set to true) are skipped, as
+    /// are moves the target isn't eligible for per [`Self::is_target_eligible`] (checking only
+    /// [`MoveAiCondition::Random`]). Each remaining move is scored as
+    /// `get_move_power(move) * matchup multiplier`, using [`Self::get_move_type_if_used_by_self`]
+    /// as the attack type and [`TargetTypeIndex::FirstType`] as the defending type: an immune
+    /// matchup scores 0, not-very-effective halves the power, neutral leaves it unchanged and
+    /// super-effective doubles it. Ties are broken towards the move with the lower max PP cost.
+    ///
+    /// Returns `None` if no move in the moveset can be used against the target.
+    fn pick_best_move(&self, target: &DungeonEntity, moveset: &[Move]) -> Option<MoveChoice> {
+        let mut best: Option<(MoveChoice, i32)> = None;
+        for (move_index, the_move) in moveset.iter().enumerate() {
+            if !self.can_use_move(the_move, true) {
+                continue;
+            }
+            let move_ai_range = self.get_move_target_and_range(the_move, true);
+            if !self.is_target_eligible(move_ai_range, target, the_move, false) {
+                continue;
+            }
+            let attack_type = self.get_move_type_if_used_by_self(the_move);
+            let matchup = self
+                .get_type_matchup(target, TargetTypeIndex::FirstType, attack_type)
+                .unwrap_or(DungeonTypeMatchup::Neutral);
+            let multiplier_pct = match matchup {
+                DungeonTypeMatchup::Immune => 0,
+                DungeonTypeMatchup::NotVeryEffective => 50,
+                DungeonTypeMatchup::Neutral => 100,
+                DungeonTypeMatchup::SuperEffective => 200,
+            };
+            let score = self.get_move_power(the_move) * multiplier_pct / 100;
+            let max_pp = the_move.get_max_pp();
+            let is_better = match &best {
+                None => true,
+                Some((current, current_max_pp)) => {
+                    score > current.score || (score == current.score && max_pp < *current_max_pp)
+                }
+            };
+            if is_better {
+                best = Some((MoveChoice { move_index, score }, max_pp));
+            }
+        }
+        best.map(|(choice, _)| choice)
+    }
+
+    /// Picks a move for this monster to use against `target`, using [`score_move_candidate`] to
+    /// weigh each candidate in `moveset`. Moves that fail [`Self::can_use_move`] (with
+    /// `extra_checks` set to true) or whose target isn't eligible per [`Self::is_target_eligible`]
+    /// (checking only [`MoveAiCondition::Random`], same as [`Self::pick_best_move`]) are skipped.
+    ///
+    /// The remaining moves are sorted by score, the top half (rounded up) is kept as the
+    /// candidate pool, and a weighted-random pick is made among them: each candidate's weight is
+    /// `score - lowest_score_in_pool + 1`, so every pooled candidate has at least some chance,
+    /// but higher-scoring ones are proportionally more likely to be picked.
+    ///
+    /// `user_type` is forwarded to [`score_move_candidate`] for its same-type-attack-bonus term;
+    /// see that function's doc comment for why it's an explicit parameter instead of being read
+    /// automatically. Pass `None` to skip that bonus.
+    ///
+    /// Returns `None` if no move in the moveset can be used against the target.
+    fn select_move(
+        &self,
+        target: &DungeonEntity,
+        moveset: &[Move],
+        user_type: Option<MonsterTypeId>,
+    ) -> Option<usize> {
+        let mut scored: Vec<(usize, i32)> = moveset
+            .iter()
+            .enumerate()
+            .filter(|(_, the_move)| self.can_use_move(the_move, true))
+            .filter(|(_, the_move)| {
+                let move_ai_range = self.get_move_target_and_range(the_move, true);
+                self.is_target_eligible(move_ai_range, target, the_move, false)
+            })
+            .map(|(move_index, the_move)| {
+                (
+                    move_index,
+                    score_move_candidate(self, target, the_move, user_type),
+                )
+            })
+            .collect();
+        if scored.is_empty() {
+            return None;
+        }
+        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        let pool_size = (scored.len() + 1) / 2;
+        let pool = &scored[..pool_size];
+        let lowest_score = pool.last().map(|(_, score)| *score).unwrap_or(0);
+        let weights: Vec<i32> = pool
+            .iter()
+            .map(|(_, score)| score - lowest_score + 1)
+            .collect();
+        let total_weight: i32 = weights.iter().sum();
+        let mut roll = rand_i32(0..total_weight);
+        for ((move_index, _), weight) in pool.iter().zip(weights.iter()) {
+            if roll < *weight {
+                return Some(*move_index);
+            }
+            roll -= *weight;
+        }
+        pool.last().map(|(move_index, _)| *move_index)
+    }
+}
+
+/// A single candidate move choice, as returned by [`DungeonMonsterRead::pick_best_move`].
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct MoveChoice {
+    /// Index of the chosen move within the moveset slice that was passed in.
+    pub move_index: usize,
+    /// The move's score, factoring in power and type effectiveness against the target.
+    pub score: i32,
+}
+
+/// Scores how worthwhile it would be for `user` to use `the_move` against `target`, for
+/// [`DungeonMonsterRead::select_move`]'s weighted-random selection. Exposed standalone (rather
+/// than folded privately into `select_move`) so patches can reuse the same heuristic to bias
+/// or rank moves on their own, the same way [`ai::pick_move`] exposes its scoring via
+/// [`ai::ScoredMove`].
+///
+/// Combines [`DungeonMonsterRead::predict_damage`]'s raw damage (scaled by how large a fraction
+/// of `target`'s current HP it represents), the same `+50`/`-50` super-effective/
+/// not-very-effective bonus [`ai::pick_move`] uses, a same-type-attack-bonus (half the score
+/// again) if `user_type` matches [`DungeonMonsterRead::get_move_type_if_used_by_self`], and a
+/// `+75` bonus if [`DungeonMonsterRead::status_checker_check`] says the move wouldn't be
+/// redundant, mirroring [`ai::pick_move`]'s own status bonus.
+///
+/// There's no safe accessor on this crate yet for a monster's own inherent type, so `user_type`
+/// is taken as an explicit parameter; pass `None` to skip the STAB bonus.
+pub fn score_move_candidate<T: DungeonMonsterRead + ?Sized>(
+    user: &T,
+    target: &DungeonEntity,
+    the_move: &Move,
+    user_type: Option<MonsterTypeId>,
+) -> i32 {
+    let prediction = user.predict_damage(target, the_move, false);
+    let mut score = match prediction.damage {
+        None => 0,
+        Some(damage) => {
+            let target_hp_current = target
+                .info_for_monster()
+                .map(|m| m.0.hp_current)
+                .filter(|hp| *hp > 0)
+                .unwrap_or(1);
+            damage * 100 / target_hp_current
+        }
+    };
+    score += match prediction.type_matchup {
+        DungeonTypeMatchup::SuperEffective => 50,
+        DungeonTypeMatchup::NotVeryEffective => -50,
+        _ => 0,
+    };
+    if let Some(user_type) = user_type {
+        if user.get_move_type_if_used_by_self(the_move) == user_type {
+            score += score / 2;
+        }
+    }
+    if user.status_checker_check(the_move) {
+        score += 75;
+    }
+    score
+}
+
+/// A parsed, high-level summary of a damage calculation, as returned by
+/// [`DungeonMonsterRead::predict_damage`].
+///
+/// This only exposes the parts of [`ffi::damage_data`] this crate currently understands; see the
+/// disclaimers on [`DungeonMonsterRead::calc_damage`] and its siblings.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct DamagePrediction {
+    /// The final damage amount, or `None` if the hit was blocked by a type immunity.
+    pub damage: Option<i32>,
+    /// The `crit` parameter [`DungeonMonsterRead::predict_damage`] was called with, echoed back
+    /// verbatim -- **not** whether `calc_damage` actually rolled a critical hit. When `crit` is
+    /// `false`, the move's real nonzero crit chance is still passed into the native call, which
+    /// can genuinely crit and change [`Self::damage`], so this field can read `false` alongside a
+    /// damage value that was in fact computed as a critical hit. Only trust this as "was a
+    /// critical hit forced," not as "was the hit a critical hit."
+    pub forced_critical_hit: bool,
+    /// The type matchup between the move's effective type and the defender.
+    pub type_matchup: DungeonTypeMatchup,
+}
+
+impl DamagePrediction {
+    /// Whether the attack would deal no damage at all, because the defender is immune to it.
+    pub fn is_blocked(&self) -> bool {
+        self.damage.is_none()
+    }
+}
+
+/// A structured, safe summary of a damage calculation, as returned by
+/// [`DungeonMonsterRead::calc_damage_data`] and its `_fixed`/`_projectile` siblings.
+///
+/// This only exposes the parts of the calculation this crate currently understands; see the
+/// disclaimers on [`DungeonMonsterRead::calc_damage`] and its siblings. `effectiveness` and
+/// `critical_hit` are derived from the inputs to the calculation rather than parsed out of the
+/// still partially unknown [`ffi::damage_data`] struct.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct DamageData {
+    /// The final damage amount. Only meaningful if `full_type_immunity` is false.
+    pub damage_dealt: i32,
+    /// The type matchup between the move's effective type and the defender.
+    pub effectiveness: DungeonTypeMatchup,
+    /// Whether the hit was treated as a critical hit.
+    pub critical_hit: bool,
+    /// Whether the defender was fully immune to the move's type, blocking all damage.
+    pub full_type_immunity: bool,
+    /// The move category the damage was calculated under.
+    pub move_category: MoveCategory,
+}
+
+/// A min/max damage estimate, as returned by [`DungeonMonsterRead::preview_damage`].
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct DamageRange {
+    /// The lowest amount of damage the attack could deal (an 85% damage roll).
+    pub min: i32,
+    /// The highest amount of damage the attack could deal (a 100% damage roll).
+    pub max: i32,
+}
+
+/// The offense multiplier `weather` applies to the power of a move of `move_type`.
+///
+/// Sunny Day boosts Fire-type moves and weakens Water-type moves; Rain Dance does the reverse.
+/// Other weather conditions don't affect any type's offensive power.
+///
+/// This is only about move damage; it does not cover whether a monster of a given type is
+/// exempt from passive weather chip damage (e.g. Ground/Rock/Steel in a sandstorm, Ice in
+/// hail) — see [`is_immune_to_weather_damage`] for that.
+///
+/// Note: unverified, ported from Irdkwia's notes.
+pub fn weather_damage_multiplier(weather: Weather, move_type: MonsterTypeId) -> I24F8 {
+    match (weather, move_type.id()) {
+        (Weather::Sunny, id) if id == MonsterTypeId::FIRE.id() => I24F8::from_num(3) / 2,
+        (Weather::Sunny, id) if id == MonsterTypeId::WATER.id() => I24F8::from_num(1) / 2,
+        (Weather::Rain, id) if id == MonsterTypeId::WATER.id() => I24F8::from_num(3) / 2,
+        (Weather::Rain, id) if id == MonsterTypeId::FIRE.id() => I24F8::from_num(1) / 2,
+        _ => I24F8::from_num(1),
+    }
+}
+
+/// Whether a monster of `monster_type` is exempt from the passive chip damage dealt at the end
+/// of the turn by `weather` (Sandstorm and Hail).
+///
+/// Ground, Rock and Steel types are immune to sandstorm chip damage; Ice types are immune to
+/// hail chip damage. Other weather conditions never deal chip damage.
+///
+/// Note: unverified, ported from Irdkwia's notes.
+pub fn is_immune_to_weather_damage(weather: Weather, monster_type: MonsterTypeId) -> bool {
+    match (weather, monster_type.id()) {
+        (Weather::Sandstorm, id) => {
+            id == MonsterTypeId::GROUND.id()
+                || id == MonsterTypeId::ROCK.id()
+                || id == MonsterTypeId::STEEL.id()
+        }
+        (Weather::Hail, id) => id == MonsterTypeId::ICE.id(),
+        _ => false,
+    }
+}
+
+/// The concrete entities and tiles a move's range expands to, as returned by
+/// [`DungeonMonsterRead::enumerate_targets`].
+pub struct TargetSet<'a> {
+    /// Entities this monster is allowed to target with the move.
+    pub entities: Vec<&'a DungeonEntity>,
+    /// Tile positions within the move's range this monster is allowed to target.
+    pub tiles: Vec<ffi::position>,
+}
+
+/// An entity hit by a traced move, as returned by
+/// [`DungeonMonsterRead::trace_move_targets`].
+pub struct TracedTarget<'a> {
+    /// The entity that would be hit.
+    pub entity: &'a DungeonEntity,
+    /// Whether this entity is on the same side as the move's user (both team members, or both
+    /// not). `false` for entities that aren't monsters (eg. items, traps).
+    pub is_ally: bool,
+}
+
+/// The result of [`DungeonMonsterRead::trace_move_targets`]: the ordered list of entities a move
+/// would hit, and whether any of them are allies of the user.
+pub struct MoveTraceResult<'a> {
+    /// The entities that would be hit, in the order they were found among the candidates.
+    pub hits: Vec<TracedTarget<'a>>,
+    /// Whether any of [`Self::hits`] is an ally of the move's user.
+    pub hits_ally: bool,
 }
 
 /// Trait for [`DungeonMonsterMut`] (write operations).
@@ -680,6 +1364,69 @@ pub trait DungeonMonsterWrite: private::Sealed {
         unsafe { ffi::UpdateMovePp(self.entity_mut(), should_consume_pp as ffi::bool_) }
     }
 
+    /// Restores the monster's HP by the given amount, without boosting its max HP.
+    ///
+    /// See [`DungeonEffectsEmitter::try_increase_hp`] for the variant of this that also lets an
+    /// attacker other than the monster itself take credit (and optionally raise its max HP).
+    fn restore_hp(&mut self, amount: i32) {
+        let entity = self.entity_mut() as *mut DungeonEntity;
+        unsafe { ffi::TryIncreaseHp(entity, entity, amount, 0, false as ffi::bool_) };
+    }
+
+    /// Directly removes the given amount of HP from the monster, bypassing the regular damage
+    /// calculation (no type matchups, criticals, or move data involved).
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    fn deal_damage_direct(&mut self, amount: i32) {
+        let mut damage_out: ffi::damage_data = unsafe { core::mem::zeroed() };
+        damage_out.damage = amount;
+        let entity = self.entity_mut() as *mut DungeonEntity;
+        unsafe { ffi::ApplyDamage(entity, entity, &mut damage_out, 0, core::ptr::null_mut()) };
+    }
+
+    /// Damages `victim` and heals this monster by the amount of HP actually removed (i.e. the
+    /// transfer is clamped to `victim`'s current HP, and healing is not boosted past this
+    /// monster's max HP).
+    ///
+    /// Does nothing if `victim` is already at 0 HP.
+    fn drain_hp(&mut self, victim: &mut DungeonEntity, amount: i32)
+    where
+        Self: DungeonMonsterRead,
+    {
+        let victim_hp = victim
+            .info_for_monster()
+            .map(|m| m.hp_current)
+            .unwrap_or(0) as i32;
+        if victim_hp <= 0 {
+            return;
+        }
+        let transferred = amount.min(victim_hp);
+
+        let mut damage_out: ffi::damage_data = unsafe { core::mem::zeroed() };
+        damage_out.damage = transferred;
+        let attacker = self.entity_mut() as *mut DungeonEntity;
+        unsafe { ffi::ApplyDamage(attacker, victim, &mut damage_out, 0, core::ptr::null_mut()) };
+
+        unsafe { ffi::TryIncreaseHp(attacker, attacker, transferred, 0, false as ffi::bool_) };
+    }
+
+    /// Tries to inflict a status condition on the monster for a number of turns determined by
+    /// [`DungeonMonsterRead::calc_status_duration`], the same way individual status-inflicting
+    /// moves compute their duration.
+    ///
+    /// Returns whether the status was actually applied; like the individual per-status
+    /// functions this wraps, existing immunities (ability, type, etc.) are respected and can
+    /// cause this to return `false` without changing the monster's state.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    fn try_inflict_status(&mut self, status: ffi::status_id::Type, turn_range: &[u16; 2]) -> bool
+    where
+        Self: DungeonMonsterRead,
+    {
+        let turns = self.calc_status_duration(turn_range, true);
+        unsafe { ffi::InflictStatusSingle(self.entity_mut(), status, turns) > 0 }
+    }
+
     /// Checks if the monster has the ability Truant, and if so tries to apply the pause status
     /// to it.
     fn try_activate_truant(&mut self) {
@@ -836,6 +1583,16 @@ pub trait DungeonMonsterWrite: private::Sealed {
         }
     }
 
+    /// Sets a monster's action to [`ffi::action::ACTION_USE_ITEM`], the write-side counterpart of
+    /// [`DungeonMonsterRead::should_use_emergency_item`]: committing to this action means the
+    /// monster will use its held item this turn instead of attacking or fleeing.
+    ///
+    /// Like [`Self::set_monster_action_fields`], this only sets the action id field; it does not
+    /// pick which held item slot is used.
+    fn set_action_use_item(&mut self) {
+        self.set_monster_action_fields(ffi::action::ACTION_USE_ITEM)
+    }
+
     /// Updates t monster's [`ffi::monster::target_pos`] field based on its current position and
     /// the direction in which it plans to attack.
     fn update_ai_target_pos(&mut self) {
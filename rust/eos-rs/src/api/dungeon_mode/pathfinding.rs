@@ -0,0 +1,241 @@
+//! A* pathfinding over a [`DungeonTileGridRef`], plus a per-turn cache of whether any hazardous
+//! (water/lava) terrain is even in sight, so [`DungeonTileGridRef::find_path`] can skip the extra
+//! terrain classification entirely on a hazard-free floor and fall back to plain wall-only
+//! pathing.
+
+use crate::api::dungeon_mode::{DungeonTileExt, DungeonTileGridRead, DungeonTileGridRef, TerrainType};
+use crate::api::objects::DungeonTile;
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{Ordering, Reverse};
+
+/// The 8 neighbor offsets (orthogonal and diagonal) [`DungeonTileGridRef::find_path`] expands a
+/// node to.
+const NEIGHBOR_OFFSETS: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// Chebyshev distance between two grid positions, used both as [`DungeonTileGridRef::find_path`]'s
+/// heuristic and its per-step move cost (every neighbor, orthogonal or diagonal, costs 1) --
+/// consistent with [`crate::api::dungeon_mode::get_chebyshev_distance`], the same distance model
+/// the rest of this crate uses for tile-range checks.
+fn chebyshev(ax: usize, ay: usize, bx: usize, by: usize) -> u32 {
+    let dx = (ax as i32 - bx as i32).unsigned_abs();
+    let dy = (ay as i32 - by as i32).unsigned_abs();
+    dx.max(dy)
+}
+
+/// Options controlling [`DungeonTileGridRef::find_path`].
+pub struct PathOptions<'a> {
+    /// Called for every tile candidate, after the basic wall check; return `true` to treat the
+    /// tile as impassable. This is how callers route around water/lava, eg.
+    /// `|tile| tile.get_terrain() == Some(TerrainType::Secondary)`. Left as `None` to only avoid
+    /// walls.
+    pub is_impassable: Option<&'a dyn Fn(&DungeonTile) -> bool>,
+    /// Skips [`Self::is_impassable`] entirely, falling back to plain wall-only pathing. Set this
+    /// from [`hazard_in_sight`]'s per-turn cached result so a hazard-free floor doesn't pay for
+    /// terrain classification on every expanded node.
+    pub skip_hazard_check: bool,
+    /// Hard cap on the number of nodes A* will expand before giving up and returning `None`,
+    /// bounding worst-case cost on wide-open floors.
+    pub max_expanded_nodes: usize,
+}
+
+impl<'a> Default for PathOptions<'a> {
+    fn default() -> Self {
+        Self {
+            is_impassable: None,
+            skip_hazard_check: false,
+            // Generous relative to the 56x32 grid (1792 tiles total), while still bounding the
+            // pathological case of a fully open floor with an unreachable goal.
+            max_expanded_nodes: 2048,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq)]
+struct AStarNode {
+    priority: u32,
+    cost: u32,
+    x: u8,
+    y: u8,
+}
+
+impl Ord for AStarNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+impl PartialOrd for AStarNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, const W: usize, const H: usize> DungeonTileGridRef<'a, W, H> {
+    /// Finds a route from `from` to `to` across this grid with A*, treating out-of-bounds tiles,
+    /// missing tiles and [`TerrainType::Wall`] as impassable, plus whatever
+    /// [`PathOptions::is_impassable`] additionally rejects.
+    ///
+    /// Diagonal moves that would cut a corner past a blocked orthogonal neighbor are forbidden (eg.
+    /// moving from `(x, y)` to `(x+1, y+1)` is disallowed if either `(x+1, y)` or `(x, y+1)` is
+    /// impassable), matching how the base game's own movement works.
+    ///
+    /// Returns the ordered tile coordinates from `from` to `to`, excluding `from` itself, or
+    /// `None` if `to` is unreachable (including if A* gives up after
+    /// [`PathOptions::max_expanded_nodes`]).
+    pub fn find_path(
+        &self,
+        from: (u8, u8),
+        to: (u8, u8),
+        options: &PathOptions,
+    ) -> Option<Vec<(u8, u8)>> {
+        let passable = |x: usize, y: usize| -> bool {
+            let Some(tile) = self.get(x, y) else {
+                return false;
+            };
+            if tile.get_terrain() == Some(TerrainType::Wall) {
+                return false;
+            }
+            if !options.skip_hazard_check {
+                if let Some(is_impassable) = options.is_impassable {
+                    if is_impassable(tile) {
+                        return false;
+                    }
+                }
+            }
+            true
+        };
+
+        let (fx, fy) = (from.0 as usize, from.1 as usize);
+        let (tx, ty) = (to.0 as usize, to.1 as usize);
+        if fx >= W || fy >= H || tx >= W || ty >= H || !passable(fx, fy) || !passable(tx, ty) {
+            return None;
+        }
+        if from == to {
+            return Some(Vec::new());
+        }
+
+        let index = |x: usize, y: usize| y * W + x;
+        let mut best_cost = vec![u32::MAX; W * H];
+        let mut came_from: Vec<Option<(u8, u8)>> = vec![None; W * H];
+        let mut open = BinaryHeap::new();
+
+        best_cost[index(fx, fy)] = 0;
+        open.push(Reverse(AStarNode {
+            priority: chebyshev(fx, fy, tx, ty),
+            cost: 0,
+            x: fx as u8,
+            y: fy as u8,
+        }));
+
+        let mut expanded = 0usize;
+        while let Some(Reverse(node)) = open.pop() {
+            let (x, y) = (node.x as usize, node.y as usize);
+            if (x, y) == (tx, ty) {
+                let mut path = vec![(x as u8, y as u8)];
+                let mut current = (x, y);
+                while let Some(prev) = came_from[index(current.0, current.1)] {
+                    path.push(prev);
+                    current = (prev.0 as usize, prev.1 as usize);
+                }
+                path.pop(); // drop the start tile itself
+                path.reverse();
+                return Some(path);
+            }
+            if node.cost > best_cost[index(x, y)] {
+                continue; // stale heap entry, a cheaper one was already processed
+            }
+
+            expanded += 1;
+            if expanded > options.max_expanded_nodes {
+                return None;
+            }
+
+            for (dx, dy) in NEIGHBOR_OFFSETS {
+                let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+                if nx < 0 || ny < 0 || nx as usize >= W || ny as usize >= H {
+                    continue;
+                }
+                let (nx, ny) = (nx as usize, ny as usize);
+                if !passable(nx, ny) {
+                    continue;
+                }
+                if dx != 0 && dy != 0 && (!passable(x, ny) || !passable(nx, y)) {
+                    continue;
+                }
+
+                let next_cost = node.cost + 1;
+                if next_cost < best_cost[index(nx, ny)] {
+                    best_cost[index(nx, ny)] = next_cost;
+                    came_from[index(nx, ny)] = Some((x as u8, y as u8));
+                    open.push(Reverse(AStarNode {
+                        priority: next_cost + chebyshev(nx, ny, tx, ty),
+                        cost: next_cost,
+                        x: nx as u8,
+                        y: ny as u8,
+                    }));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Process-wide per-turn cache backing [`hazard_in_sight`].
+///
+/// # Safety
+/// Single-threaded (GBA code, one core), so a plain unsynchronized static is the established
+/// pattern in this crate; see eg. `message_log`'s dedup ring buffer.
+static mut HAZARD_CACHE: Option<(u16, bool)> = None;
+
+/// Returns whether any water/lava ([`TerrainType::Secondary`]) tile is visible from
+/// `leader_pos` out to `vision_radius` tiles, using [`DungeonTileGridRead::visible_from`]'s
+/// shadowcasting field of view (walls block sight, same as the game's own visibility).
+///
+/// Mirrors the "is any hazard even nearby" guard other roguelike monster AI uses before doing
+/// real terrain classification: the result is cached against `fractional_turn` (see
+/// [`crate::api::dungeon_mode::Dungeon::get_fractional_turn`]), so repeated calls within the same
+/// turn reuse the cached scan instead of re-running field of view. Feed the result into
+/// [`PathOptions::skip_hazard_check`] to skip [`PathOptions::is_impassable`] on a hazard-free
+/// floor.
+pub fn hazard_in_sight<const W: usize, const H: usize>(
+    tiles: &DungeonTileGridRef<W, H>,
+    leader_pos: (usize, usize),
+    vision_radius: usize,
+    fractional_turn: u16,
+) -> bool {
+    // SAFETY: single-threaded.
+    unsafe {
+        #[allow(static_mut_refs)]
+        if let Some((turn, hazard)) = HAZARD_CACHE {
+            if turn == fractional_turn {
+                return hazard;
+            }
+        }
+        let mut hazard = false;
+        tiles.visible_from(
+            leader_pos,
+            vision_radius,
+            |tile| tile.get_terrain() == Some(TerrainType::Wall),
+            |x, y| {
+                if !hazard {
+                    if let Some(tile) = tiles.get(x, y) {
+                        hazard = tile.get_terrain() == Some(TerrainType::Secondary);
+                    }
+                }
+            },
+        );
+        HAZARD_CACHE = Some((fractional_turn, hazard));
+        hazard
+    }
+}
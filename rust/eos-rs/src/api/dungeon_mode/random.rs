@@ -1,7 +1,6 @@
 use crate::api::dungeon_mode::entity::DungeonEntity;
 use crate::api::overlay::{CreatableWithLease, OverlayLoadLease};
 use crate::api::random::{rand_u16_internal, Rng};
-use crate::ctypes::c_int;
 use crate::ffi;
 use core::hint::unreachable_unchecked;
 use core::ops::RangeBounds;
@@ -31,12 +30,6 @@ impl Rng for DungeonRngImpl {
         // SAFETY: This will never be called. It is not used in this module and it is not exposed.
         unsafe { unreachable_unchecked() }
     }
-
-    /// NOT SUPPORTED BY THIS.
-    fn rand_range32(&mut self, _x: c_int, _y: c_int) -> c_int {
-        // SAFETY: This will never be called. It is not used in this module and it is not exposed.
-        unsafe { unreachable_unchecked() }
-    }
 }
 
 impl DungeonRng {
@@ -118,6 +111,16 @@ impl DungeonRng {
         unsafe { ffi::DungeonRand100() }
     }
 
+    /// Compute a pseudorandom integer on the interval [0, `high`) using the dungeon PRNG.
+    pub fn rand_below(&self, high: i32) -> i32 {
+        unsafe { ffi::DungeonRandInt(high) }
+    }
+
+    /// Compute a pseudorandom integer on the interval [`x`, `y`) using the dungeon PRNG.
+    pub fn rand_range(&self, x: i32, y: i32) -> i32 {
+        unsafe { ffi::DungeonRandRange(x, y) }
+    }
+
     /// Returns the result of a possibly biased coin flip (a Bernoulli random variable) with some
     /// success probability `p`, using the dungeon PRNG
     /// (`true` has a probability `p`, `false` has (`1-p`)).
@@ -178,3 +181,204 @@ impl DungeonRng {
         unsafe { ffi::DungeonRngSetSecondary(idx) }
     }
 }
+
+/// A snapshot of the dungeon PRNG's state (preseed), capturable with
+/// [`DungeonRng::capture_state`] and restorable with [`DungeonRng::restore_state`].
+///
+/// This only captures the preseed, not which of the 6 LCGs is currently selected; callers that
+/// also depend on that should save/restore it separately with
+/// [`DungeonRng::set_primary_rng`]/[`DungeonRng::set_secondary_rng`].
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct DungeonRngState {
+    preseed: u32,
+}
+
+impl DungeonRng {
+    /// Captures the current dungeon PRNG preseed so it can be restored later with
+    /// [`Self::restore_state`], eg. to make a section of dungeon logic reproducible.
+    pub fn capture_state(&self) -> DungeonRngState {
+        DungeonRngState {
+            preseed: self.get_dungeon_rng_preeseed(),
+        }
+    }
+
+    /// Restores a dungeon PRNG preseed previously captured with [`Self::capture_state`] and
+    /// reinitializes the PRNG from it, so subsequent rolls reproduce the same sequence.
+    pub fn restore_state(&mut self, state: DungeonRngState) {
+        self.set_dungeon_rng_preeseed(state.preseed);
+        let seed = self.generate_dungeon_rng_seed();
+        self.init_dungeon_rng(seed);
+    }
+}
+
+impl DungeonRng {
+    /// Picks a random index into `weights` using the dungeon PRNG, where the probability of
+    /// picking index `i` is proportional to `weights[i]`.
+    ///
+    /// Returns `None` if `weights` is empty or all its entries are 0.
+    pub fn weighted_index(&self, weights: &[u32]) -> Option<usize> {
+        let total: u32 = weights.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        // `rand_below` takes an `i32` ceiling (unlike `rand_u16`, which silently truncates any
+        // `total` above `u16::MAX`), so the full `u32` weight sum is covered instead of wrapping
+        // entries past 65535 out of reach.
+        let mut roll = self.rand_below(total as i32) as u32;
+        for (i, &weight) in weights.iter().enumerate() {
+            if roll < weight {
+                return Some(i);
+            }
+            roll -= weight;
+        }
+        // Unreachable as long as `total` is indeed the sum of `weights`, kept as a safe
+        // fallback in case of rounding weirdness.
+        weights.iter().rposition(|&weight| weight > 0)
+    }
+
+    /// Picks a random item from `items` using the dungeon PRNG, with probabilities proportional
+    /// to the weight `weight_fn` assigns to each item.
+    ///
+    /// Returns `None` if `items` is empty or all weights are 0.
+    pub fn weighted_choice<'a, T>(
+        &self,
+        items: &'a [T],
+        weight_fn: impl Fn(&T) -> u32,
+    ) -> Option<&'a T> {
+        let weights: alloc::vec::Vec<u32> = items.iter().map(weight_fn).collect();
+        self.weighted_index(&weights).map(|i| &items[i])
+    }
+}
+
+/// Wraps a [`DungeonRng`] and automatically reseeds it (via
+/// [`DungeonRng::generate_dungeon_rng_seed`]/[`DungeonRng::init_dungeon_rng`]) after a fixed
+/// number of draws, similar in spirit to `rand`'s `ReseedingRng`.
+///
+/// This is useful for long-running dungeon logic (eg. a scripted generator loop) that wants to
+/// avoid exhausting the statistical quality of a single LCG stream.
+pub struct ReseedingDungeonRng {
+    inner: DungeonRng,
+    draws_since_reseed: u32,
+    reseed_after: u32,
+}
+
+impl ReseedingDungeonRng {
+    /// Wraps `inner`, reseeding automatically every `reseed_after` draws (via
+    /// [`Self::rand_u16`]/[`Self::rand100`]/[`Self::rand_outcome`]).
+    pub fn new(inner: DungeonRng, reseed_after: u32) -> Self {
+        Self {
+            inner,
+            draws_since_reseed: 0,
+            reseed_after,
+        }
+    }
+
+    fn tick(&mut self) {
+        if self.draws_since_reseed >= self.reseed_after {
+            let seed = self.inner.generate_dungeon_rng_seed();
+            self.inner.init_dungeon_rng(seed);
+            self.draws_since_reseed = 0;
+        }
+        self.draws_since_reseed += 1;
+    }
+
+    /// Draws a value with [`DungeonRng::rand_u16`], reseeding first if the draw count since the
+    /// last reseed has reached the configured threshold.
+    pub fn rand_u16<R: RangeBounds<u16>>(&mut self, range: R) -> u16 {
+        self.tick();
+        self.inner.rand_u16(range)
+    }
+
+    /// Draws a value with [`DungeonRng::rand100`], reseeding first if the draw count since the
+    /// last reseed has reached the configured threshold.
+    pub fn rand100(&mut self) -> u32 {
+        self.tick();
+        self.inner.rand100()
+    }
+
+    /// Draws a value with [`DungeonRng::rand_outcome`], reseeding first if the draw count since
+    /// the last reseed has reached the configured threshold.
+    pub fn rand_outcome(&mut self, success_percentage: i32) -> bool {
+        self.tick();
+        self.inner.rand_outcome(success_percentage)
+    }
+}
+
+/// A RAII guard returned by [`DungeonRng::with_secondary_lcg`]. Switches the dungeon PRNG back to
+/// the primary LCG when dropped, even on early return, so a borrowed secondary stream can never
+/// leak out past the scope that asked for it.
+pub struct SecondaryLcgGuard<'a> {
+    rng: &'a mut DungeonRng,
+}
+
+impl Drop for SecondaryLcgGuard<'_> {
+    fn drop(&mut self) {
+        self.rng.set_primary_rng();
+    }
+}
+
+impl core::ops::Deref for SecondaryLcgGuard<'_> {
+    type Target = DungeonRng;
+
+    fn deref(&self) -> &DungeonRng {
+        self.rng
+    }
+}
+
+impl core::ops::DerefMut for SecondaryLcgGuard<'_> {
+    fn deref_mut(&mut self) -> &mut DungeonRng {
+        self.rng
+    }
+}
+
+impl DungeonRng {
+    /// Switches the dungeon PRNG to secondary LCG `index` for the lifetime of the returned guard,
+    /// reverting to the primary LCG when the guard is dropped (even on early return). Lets
+    /// callers pull a deterministic sub-stream for an effect without permanently perturbing the
+    /// global PRNG state that the rest of dungeon logic depends on.
+    pub fn with_secondary_lcg(&mut self, index: i32) -> SecondaryLcgGuard {
+        self.set_secondary_rng(index);
+        SecondaryLcgGuard { rng: self }
+    }
+}
+
+impl rand_core::RngCore for DungeonRng {
+    /// Draws two 16-bit values from the currently selected LCG and combines them into a
+    /// 32-bit value (high bits from the first draw, low bits from the second).
+    fn next_u32(&mut self) -> u32 {
+        let hi = unsafe { ffi::DungeonRand16Bit() } as u32;
+        let lo = unsafe { ffi::DungeonRand16Bit() } as u32;
+        (hi << 16) | lo
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let hi = self.next_u32() as u64;
+        let lo = self.next_u32() as u64;
+        (hi << 32) | lo
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl rand_core::SeedableRng for DungeonRng {
+    type Seed = [u8; 4];
+
+    /// Seeds and initializes the dungeon PRNG (see [`Self::init_dungeon_rng`]).
+    ///
+    /// # Note
+    /// This acquires an unchecked lease on overlay 29, since [`rand_core::SeedableRng`]
+    /// gives us no room to thread one through. As with the rest of this module, this is only
+    /// sound while overlay 29 (the dungeon overlay) is actually loaded.
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut rng = Self(unsafe { OverlayLoadLease::<29>::acquire_unchecked() });
+        rng.init_dungeon_rng(u32::from_le_bytes(seed));
+        rng
+    }
+}
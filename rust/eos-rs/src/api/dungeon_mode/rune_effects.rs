@@ -0,0 +1,460 @@
+//! Optional embedded Rune scripting for move effects, so a ROM hacker can define a custom
+//! move's behavior in a `.rn` script instead of patching [`DungeonEffectsEmitter`] itself.
+//!
+//! This mirrors [`crate::api::scripting`]'s `MonsterAiScript`/[`choose_ai_move_scripted`] design
+//! (a registry of [`ScriptEngine`]s keyed by what they override, with an optional fallback,
+//! dispatched by a safe wrapper that falls back to the native behavior if no script handles the
+//! call), applied to [`DungeonEffectsEmitter::do_move_damage`] instead of AI move choice. Only
+//! compiled in when the `rune` feature is enabled.
+//!
+//! Raw [`DungeonEntity`]/[`Move`] pointers can't cross the Rune VM boundary the way
+//! [`crate::api::scripting::eos_module`]'s registered types do, so the host functions exposed
+//! here (`eos::get_hp`, `eos::heal_hp`, `eos::deal_damage_direct`, `eos::inflict_status`,
+//! `eos::roll_percent`, `eos::type_effectiveness`, `eos::deal_move_damage`, `eos::fallback_move`,
+//! `eos::lower_stat`, `eos::boost_stat`) instead read and write through a [`MoveScriptContext`]
+//! stashed in `CURRENT_MOVE_CONTEXT` for the duration of a single [`run_rune_effect`] call, the
+//! same pattern [`crate::api::scripting`] uses for `PENDING_AI_DECISION`.
+
+use crate::api::dungeon_mode::entity::DungeonEntity;
+use crate::api::dungeon_mode::{
+    DungeonEffectsEmitter, DungeonEffectsInternals, DungeonMonsterRead, DungeonMonsterWrite,
+    DungeonTypeMatchup, InflictOptions, StatusCondition, TargetTypeIndex,
+};
+use crate::api::items::ItemId;
+use crate::api::moves::{Move, MoveId};
+use crate::api::random::rand_i32;
+use crate::api::scripting::{ScriptEngine, ScriptError};
+use crate::api::types::MonsterTypeId;
+use crate::ffi;
+use alloc::vec::Vec;
+use core::ffi::CStr;
+use fixed::types::I24F8;
+use rune::{ContextError, Module};
+
+/// Registers the host functions move-effect scripts call into, as `eos::*`.
+///
+/// Unlike [`crate::api::scripting::eos_module`], none of these take a script-visible argument
+/// for the attacker/defender/move involved -- they all operate on whichever call is currently
+/// live in `CURRENT_MOVE_CONTEXT`, set up by [`run_rune_effect`] before invoking the script.
+pub fn move_effect_module() -> Result<Module, ScriptError> {
+    let mut module = Module::with_crate("eos").map_err(context_err)?;
+
+    module.function_meta(get_hp).map_err(context_err)?;
+    module.function_meta(heal_hp).map_err(context_err)?;
+    module
+        .function_meta(deal_damage_direct)
+        .map_err(context_err)?;
+    module.function_meta(inflict_status).map_err(context_err)?;
+    module.function_meta(roll_percent).map_err(context_err)?;
+    module
+        .function_meta(type_effectiveness)
+        .map_err(context_err)?;
+    module
+        .function_meta(deal_move_damage)
+        .map_err(context_err)?;
+    module.function_meta(fallback_move).map_err(context_err)?;
+    module.function_meta(lower_stat).map_err(context_err)?;
+    module.function_meta(boost_stat).map_err(context_err)?;
+
+    Ok(module)
+}
+
+fn context_err(_: ContextError) -> ScriptError {
+    ScriptError::Context
+}
+
+/// Raw state for the move effect currently being resolved, stashed in `CURRENT_MOVE_CONTEXT`
+/// for the duration of one [`run_rune_effect`] call.
+///
+/// Every field is a raw pointer rather than a borrow, since this has to live in a `static mut`
+/// (Rune's host functions have no way to thread a context argument through to script code).
+/// `reported` records whether the script actually called one of `eos::deal_move_damage`/
+/// `eos::fallback_move`, as opposed to `result`, which only holds a meaningful value once one of
+/// them has.
+struct MoveScriptContext {
+    emitter: *mut (),
+    attacker: *mut DungeonEntity,
+    defender: *mut DungeonEntity,
+    the_move: *const Move,
+    item_id: ItemId,
+    reported: bool,
+    result: bool,
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// [`run_rune_effect`] clears this before returning, having let the script call at most the
+/// handful of `eos::*` functions above to completion first -- the same reasoning
+/// [`crate::api::scripting`]'s `PENDING_AI_DECISION` documents for AI scripts.
+static mut CURRENT_MOVE_CONTEXT: Option<MoveScriptContext> = None;
+
+/// Reads the live [`MoveScriptContext`], panicking if called outside [`run_rune_effect`] (which
+/// can only happen if a script somehow calls one of these functions from a stored callback after
+/// the triggering `on_use` returned -- not something Rune's synchronous call model allows).
+fn with_context<R>(f: impl FnOnce(&mut MoveScriptContext) -> R) -> R {
+    #[allow(static_mut_refs)]
+    unsafe {
+        let ctx = CURRENT_MOVE_CONTEXT
+            .as_mut()
+            .expect("eos::* move effect function called outside run_rune_effect");
+        f(ctx)
+    }
+}
+
+/// The attacker's or defender's current HP, via the same field [`DungeonMonsterRead`]'s HP
+/// queries read. Returns `0` if the entity in question somehow isn't a monster.
+#[rune::function(path = get_hp)]
+fn get_hp(is_defender: bool) -> i32 {
+    with_context(|ctx| unsafe {
+        let entity = if is_defender { ctx.defender } else { ctx.attacker };
+        (*entity)
+            .info_for_monster()
+            .map(|m| m.0.hp_current as i32)
+            .unwrap_or(0)
+    })
+}
+
+/// Restores `amount` HP to the attacker or defender, via [`DungeonMonsterWrite::restore_hp`].
+#[rune::function(path = heal_hp)]
+fn heal_hp(is_defender: bool, amount: i32) {
+    with_context(|ctx| unsafe {
+        let entity = if is_defender { ctx.defender } else { ctx.attacker };
+        if let Some(mut monster) = (*entity).info_for_monster_mut() {
+            monster.restore_hp(amount);
+        }
+    })
+}
+
+/// Directly removes `amount` HP from the attacker or defender, via
+/// [`DungeonMonsterWrite::deal_damage_direct`] (bypassing the regular damage calculation, same as
+/// that method).
+#[rune::function(path = deal_damage_direct)]
+fn deal_damage_direct(is_defender: bool, amount: i32) {
+    with_context(|ctx| unsafe {
+        let entity = if is_defender { ctx.defender } else { ctx.attacker };
+        if let Some(mut monster) = (*entity).info_for_monster_mut() {
+            monster.deal_damage_direct(amount);
+        }
+    })
+}
+
+/// Attempts to inflict a status on the defender, via
+/// [`DungeonEffectsEmitter::try_inflict_status`] with default [`InflictOptions`]. `status_id`
+/// selects a fixed, script-friendly subset of [`StatusCondition`] (the ones the request calls
+/// out by name, and that take no extra parameters): `0` paralysis, `1` frozen, `2` poisoned, `3`
+/// badly poisoned, `4` burn (without the special critical-hit-adjacent effect). Returns whether
+/// it took.
+///
+/// Unrecognized `status_id` values do nothing and return `false`.
+#[rune::function(path = inflict_status)]
+fn inflict_status(status_id: i64) -> bool {
+    let status = match status_id {
+        0 => StatusCondition::Paralysis,
+        1 => StatusCondition::Frozen,
+        2 => StatusCondition::Poisoned,
+        3 => StatusCondition::BadlyPoisoned,
+        4 => StatusCondition::Burn {
+            special_effect: false,
+        },
+        _ => return false,
+    };
+    with_context(|ctx| unsafe {
+        let emitter = &mut *(ctx.emitter as *mut DungeonEffectsEmitter);
+        emitter
+            .try_inflict_status(
+                &mut *ctx.attacker,
+                &mut *ctx.defender,
+                status,
+                InflictOptions::default(),
+            )
+            .applied()
+    })
+}
+
+/// Rolls whether a `chance_percent` (`0..=100`) event occurs, via [`rand_i32`].
+#[rune::function(path = roll_percent)]
+fn roll_percent(chance_percent: i64) -> bool {
+    rand_i32(0..100) < chance_percent as i32
+}
+
+/// The type matchup of `attack_type` (a raw [`MonsterTypeId`] value) against the defender, via
+/// [`DungeonMonsterRead::get_type_matchup`]. Returns the matchup as a percentage (`0` immune,
+/// `50` not very effective, `100` neutral, `200` super effective), or `100` (treated as neutral)
+/// if the attacker isn't a monster or the underlying query fails.
+///
+/// # Safety
+/// `attack_type` must be a valid monster type ID, per [`MonsterTypeId::new`].
+#[rune::function(path = type_effectiveness)]
+fn type_effectiveness(attack_type: i64) -> i64 {
+    with_context(|ctx| unsafe {
+        let attack_type = MonsterTypeId::new(attack_type as u32);
+        (*ctx.attacker)
+            .info_for_monster()
+            .and_then(|m| m.get_type_matchup(&*ctx.defender, TargetTypeIndex::FirstType, attack_type))
+            .map(|matchup| match matchup {
+                DungeonTypeMatchup::Immune => 0,
+                DungeonTypeMatchup::NotVeryEffective => 50,
+                DungeonTypeMatchup::Neutral => 100,
+                DungeonTypeMatchup::SuperEffective => 200,
+            })
+            .unwrap_or(100)
+    })
+}
+
+/// Deals damage from the attacker to the defender using the current move and the standard damage
+/// formula, via [`DungeonEffectsEmitter::do_move_damage`] (`multiplier_percent == 100`) or
+/// [`DungeonEffectsEmitter::deal_damage`] scaled by `multiplier_percent` otherwise (`100` meaning
+/// the move's ordinary damage). Records this call's result as what [`run_rune_effect`] returns
+/// if the script's `on_use` doesn't call anything afterward; returns whether the move connected.
+#[rune::function(path = deal_move_damage)]
+fn deal_move_damage(multiplier_percent: i64) -> bool {
+    with_context(|ctx| unsafe {
+        let emitter = &mut *(ctx.emitter as *mut DungeonEffectsEmitter);
+        let the_move = &*ctx.the_move;
+        let hit = if multiplier_percent == 100 {
+            emitter.do_move_damage(&mut *ctx.attacker, &mut *ctx.defender, the_move, ctx.item_id)
+        } else if emitter.move_hit_check(&mut *ctx.attacker, &mut *ctx.defender, the_move, false) {
+            let multiplier = I24F8::from_num(multiplier_percent) / I24F8::from_num(100);
+            emitter.deal_damage(
+                &mut *ctx.attacker,
+                &mut *ctx.defender,
+                the_move,
+                multiplier,
+                Some(ctx.item_id),
+            );
+            true
+        } else {
+            false
+        };
+        ctx.reported = true;
+        ctx.result = hit;
+        hit
+    })
+}
+
+/// Calls through to the native [`DungeonEffectsEmitter::do_move_damage`] unconditionally, for a
+/// script that only wants to wrap the base behavior (e.g. add a secondary effect after a normal
+/// hit) rather than replace the damage calculation entirely.
+#[rune::function(path = fallback_move)]
+fn fallback_move() -> bool {
+    with_context(|ctx| unsafe {
+        let emitter = &mut *(ctx.emitter as *mut DungeonEffectsEmitter);
+        let hit = emitter.do_move_damage(&mut *ctx.attacker, &mut *ctx.defender, &*ctx.the_move, ctx.item_id);
+        ctx.reported = true;
+        ctx.result = hit;
+        hit
+    })
+}
+
+/// Lowers the defender's defensive stat `stat_idx` by `stages`, via
+/// [`DungeonEffectsEmitter::boost_defensive_stat`]'s negative-stage convention. `stat_idx` has no
+/// named constant in this crate (see that method's own doc comment), so it's a raw script-supplied
+/// value, same as every other caller of it.
+#[rune::function(path = lower_stat)]
+fn lower_stat(stat_idx: i64, stages: i64) {
+    with_context(|ctx| unsafe {
+        let emitter = &mut *(ctx.emitter as *mut DungeonEffectsEmitter);
+        emitter.boost_defensive_stat(
+            &mut *ctx.attacker,
+            &mut *ctx.defender,
+            stat_idx as i32,
+            -(stages as i16),
+        );
+    })
+}
+
+/// Boosts the defender's defensive stat `stat_idx` by `stages`, via
+/// [`DungeonEffectsEmitter::boost_defensive_stat`].
+#[rune::function(path = boost_stat)]
+fn boost_stat(stat_idx: i64, stages: i64) {
+    with_context(|ctx| unsafe {
+        let emitter = &mut *(ctx.emitter as *mut DungeonEffectsEmitter);
+        emitter.boost_defensive_stat(
+            &mut *ctx.attacker,
+            &mut *ctx.defender,
+            stat_idx as i32,
+            stages as i16,
+        );
+    })
+}
+
+/// Registry of per-move Rune effect scripts, with an optional global fallback used for moves
+/// without their own entry. See [`run_rune_effect`].
+///
+/// A `Vec` scanned linearly, the same representation
+/// [`crate::api::dungeon_mode::MoveEffectRegistry`] uses for the same [`MoveId`] key, rather than
+/// a `BTreeMap` -- this crate has no confirmed `Ord` impl to rely on for [`MoveId`].
+#[derive(Default)]
+pub struct MoveEffectScripts {
+    by_move: Vec<(MoveId, ScriptEngine)>,
+    fallback: Option<ScriptEngine>,
+}
+
+impl MoveEffectScripts {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compiles the script at `path` (a ROM file-system path) and registers it as the effect for
+    /// `move_id`, replacing any script previously registered for it.
+    ///
+    /// # Safety
+    /// Same as [`crate::api::io::file::read`]: `path` must be a valid path to an existing file in
+    /// the ROM file system, and this must not be called during interrupts.
+    pub unsafe fn register_rune_effect(&mut self, move_id: MoveId, path: &CStr) -> Result<(), ScriptError> {
+        let source = load_script_source(path)?;
+        let engine = ScriptEngine::compile_with_module("move_effect", &source, move_effect_module()?)?;
+        if let Some(entry) = self.by_move.iter_mut().find(|(id, _)| *id == move_id) {
+            entry.1 = engine;
+        } else {
+            self.by_move.push((move_id, engine));
+        }
+        Ok(())
+    }
+
+    /// Registers `engine` as the global fallback script, used for moves without their own entry
+    /// registered via [`Self::register_rune_effect`].
+    pub fn register_fallback(&mut self, engine: ScriptEngine) {
+        self.fallback = Some(engine);
+    }
+
+    /// Compiles and registers a whole batch of scripts at once, from explicit `(move_id, path)`
+    /// pairs.
+    ///
+    /// This crate has no directory-listing primitive over the ROM file system (there's no safe
+    /// -- or even raw FFI -- accessor for "list the files under this path" anywhere in this
+    /// crate), so unlike a desktop `walkdir`-style loader, the caller has to enumerate the
+    /// `(move_id, path)` pairs themselves (e.g. by generating them from the same data file that
+    /// assigns move IDs to patch names at build time). Stops at the first compile failure,
+    /// returning it together with the index of the entry that failed.
+    ///
+    /// # Safety
+    /// Same as [`Self::register_rune_effect`], for every path in `entries`.
+    pub unsafe fn register_rune_effects_from_paths(
+        &mut self,
+        entries: &[(MoveId, &CStr)],
+    ) -> Result<(), (usize, ScriptError)> {
+        for (index, (move_id, path)) in entries.iter().enumerate() {
+            self.register_rune_effect(*move_id, path)
+                .map_err(|err| (index, err))?;
+        }
+        Ok(())
+    }
+
+    fn script_for(&self, move_id: MoveId) -> Option<&ScriptEngine> {
+        self.by_move
+            .iter()
+            .find(|(id, _)| *id == move_id)
+            .map(|(_, engine)| engine)
+            .or(self.fallback.as_ref())
+    }
+}
+
+/// # Safety
+/// Same as [`crate::api::io::file::read`].
+unsafe fn load_script_source(path: &CStr) -> Result<alloc::string::String, ScriptError> {
+    let bytes = crate::api::io::file::read(path, 0);
+    alloc::string::String::from_utf8(bytes).map_err(|_| ScriptError::Compile)
+}
+
+/// Dispatches `the_move`'s effect to its registered script's `on_use` function, falling back to
+/// the native [`DungeonEffectsEmitter::do_move_damage`] if no script is registered for it (or the
+/// call fails for any reason). A script's `on_use` is expected to report its result (whether the
+/// move was successfully used, the same contract every `do_move_*` wrapper follows) by calling
+/// one of `eos::deal_move_damage`/`eos::fallback_move` before returning; if it returns without
+/// calling either, this also falls back to the native move, matching how
+/// [`crate::api::scripting::choose_ai_move_scripted`] falls back when a script's hook runs
+/// without reporting a decision.
+///
+/// # Safety
+/// Same safety requirements as the other [`DungeonEffectsEmitter`] methods this delegates to.
+#[allow(static_mut_refs)]
+pub unsafe fn run_rune_effect(
+    emitter: &mut DungeonEffectsEmitter,
+    registry: &MoveEffectScripts,
+    attacker: &mut DungeonEntity,
+    defender: &mut DungeonEntity,
+    the_move: &Move,
+    item_id: ItemId,
+) -> bool {
+    let move_id = the_move.id.val();
+    let Some(engine) = registry.script_for(move_id) else {
+        return emitter.do_move_damage(attacker, defender, the_move, item_id);
+    };
+
+    CURRENT_MOVE_CONTEXT = Some(MoveScriptContext {
+        emitter: emitter as *mut DungeonEffectsEmitter as *mut (),
+        attacker: attacker as *mut DungeonEntity,
+        defender: defender as *mut DungeonEntity,
+        the_move: the_move as *const Move,
+        item_id,
+        reported: false,
+        result: false,
+    });
+
+    let called_back = engine.call("on_use", ()).is_ok();
+    let outcome = CURRENT_MOVE_CONTEXT.take();
+    CURRENT_MOVE_CONTEXT = None;
+
+    match outcome {
+        Some(ctx) if called_back && ctx.reported => ctx.result,
+        _ => emitter.do_move_damage(attacker, defender, the_move, item_id),
+    }
+}
+
+/// Like [`run_rune_effect`], but front-ends [`DungeonEffectsInternals::execute_move_effect`] --
+/// the actual "giant move-ID switch" this subsystem was written to let scripts stand in for --
+/// instead of [`DungeonEffectsEmitter::do_move_damage`]. Meant for a script adding an entirely
+/// new move ID the native switch doesn't recognize at all, rather than one overriding an
+/// existing move's damage-dealing behavior (for that, prefer [`run_rune_effect`]).
+///
+/// A script's `on_use` is expected to report its result the same way [`run_rune_effect`]
+/// documents (by calling `eos::deal_move_damage`/`eos::fallback_move`); if it doesn't, or no
+/// script is registered for `the_move`, this falls through to the native switch.
+/// `param_1`/`param_4`/`param_5` are forwarded as-is to
+/// [`DungeonEffectsInternals::execute_move_effect`] -- see that method's own doc comment, since
+/// this crate doesn't know what they are either.
+///
+/// # Safety
+/// Same safety requirements as [`DungeonEffectsInternals::execute_move_effect`].
+#[allow(static_mut_refs)]
+pub unsafe fn run_rune_move_effect(
+    emitter: &mut DungeonEffectsEmitter,
+    registry: &MoveEffectScripts,
+    param_1: *mut ffi::undefined4,
+    attacker: &mut DungeonEntity,
+    defender: &mut DungeonEntity,
+    the_move: &Move,
+    item_id: ItemId,
+    param_4: ffi::undefined4,
+    param_5: ffi::undefined4,
+) {
+    let move_id = the_move.id.val();
+    let Some(engine) = registry.script_for(move_id) else {
+        emitter
+            .internals()
+            .execute_move_effect(param_1, attacker, the_move, param_4, param_5);
+        return;
+    };
+
+    CURRENT_MOVE_CONTEXT = Some(MoveScriptContext {
+        emitter: emitter as *mut DungeonEffectsEmitter as *mut (),
+        attacker: attacker as *mut DungeonEntity,
+        defender: defender as *mut DungeonEntity,
+        the_move: the_move as *const Move,
+        item_id,
+        reported: false,
+        result: false,
+    });
+
+    let called_back = engine.call("on_use", ()).is_ok();
+    let outcome = CURRENT_MOVE_CONTEXT.take();
+    CURRENT_MOVE_CONTEXT = None;
+
+    let reported = matches!(outcome, Some(ctx) if called_back && ctx.reported);
+    if !reported {
+        emitter
+            .internals()
+            .execute_move_effect(param_1, attacker, the_move, param_4, param_5);
+    }
+}
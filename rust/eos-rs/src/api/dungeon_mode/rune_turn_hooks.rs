@@ -0,0 +1,414 @@
+//! Rune-scripted event hooks across the dungeon turn loop, gated behind the `rune` feature (same
+//! as [`crate::api::dungeon_mode::rune_effects`]): lets a mod register scripts against
+//! named events fired around [`GlobalDungeonData::run_leader_turn`],
+//! [`GlobalDungeonData::try_spawn_monster_and_activate_plus_minus`],
+//! [`GlobalDungeonData::try_spawn_monster_and_tick_spawn_counter`],
+//! [`GlobalDungeonData::is_floor_over`], [`GlobalDungeonData::spawn_monster`], and
+//! [`GlobalDungeonData::faint_check`], without editing the C/ASM patch for each one.
+//!
+//! [`EventHook`] is a plain Rust trait -- so native Rust hooks and Rune scripts register into the
+//! same [`HookRegistry`] -- with every method defaulted to a no-op, the same shape
+//! [`crate::api::dungeon_mode::effects::MoveInterceptHook`] uses. [`RuneEventHook`] is the
+//! script-backed implementor, calling named functions (`on_spawn`, `on_floor_over`,
+//! `on_leader_turn`, `on_plus_minus`, `on_spawn_counter`, `on_faint_check`) on a compiled
+//! [`ScriptEngine`], falling back to the default (report nothing, change nothing) if the script
+//! doesn't define that particular hook -- the same "called back, but didn't report" fallback
+//! [`crate::api::dungeon_mode::rune_effects::run_rune_effect`] uses.
+//!
+//! [`MonsterSpeciesId`] and [`IqSkillId`] are simple ID newtypes and cross the Rune VM boundary
+//! directly, the same way [`crate::api::scripting::eos_module`] already registers
+//! [`MonsterSpeciesId`]. [`DungeonEntity`]/[`ffi::team_member`] can't (see
+//! [`crate::api::dungeon_mode::rune_effects`]'s own note on the same limitation), so `on_spawn`
+//! scripts instead read the spawned entity through `eos::spawned_species`,
+//! `eos::spawned_is_team_member` and `eos::spawned_has_iq_skill`, which look at whichever entity
+//! is currently stashed in `CURRENT_SPAWN` for the duration of one `on_spawn` call.
+
+use crate::api::dungeon_mode::entity::DungeonEntity;
+use crate::api::dungeon_mode::{DungeonMonsterRead, GlobalDungeonData};
+use crate::api::iq::IqSkillId;
+use crate::api::monsters::MonsterSpeciesId;
+use crate::api::scripting::{ScriptEngine, ScriptError};
+use crate::ffi;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use rune::{ContextError, Module};
+
+/// What a registered [`EventHook`] asks its `*_with_hooks` wrapper to do with the native call it
+/// wraps. Reused across every hook method rather than one outcome type per site, the same way
+/// [`crate::api::dungeon_mode::hooks::HookResponse`] is shared across dungeon lifecycle sites --
+/// a variant that isn't meaningful for a given site (e.g. [`Self::Replace`] outside
+/// [`EventHook::on_spawn`]) is simply ignored there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookOutcome {
+    /// Let the native call run (or, for [`EventHook::on_spawn`], keep the entity it spawned) as
+    /// normal.
+    Continue,
+    /// Skip the native call and report this `bool` as its result, for the bool-returning sites
+    /// ([`EventHook::on_leader_turn`], [`EventHook::on_plus_minus`],
+    /// [`EventHook::on_spawn_counter`]; the first hook in the list to return this wins).
+    Override(bool),
+    /// [`EventHook::on_spawn`] only: report this entity pointer back instead of the one
+    /// [`GlobalDungeonData::spawn_monster`] actually returned (see [`RuneEventHook::on_spawn`],
+    /// which populates it from `eos::replace_spawn`). Ignored everywhere else. A null pointer is
+    /// treated the same as [`Self::Continue`].
+    Replace(*mut DungeonEntity),
+}
+
+/// A registration point for dungeon-turn-loop hooks, native or Rune-scripted (see the
+/// [module-level docs](self)). Every method defaults to a no-op, so a hook only needs to
+/// override the event(s) it cares about.
+pub trait EventHook {
+    /// Runs after [`GlobalDungeonData::spawn_monster`] spawns `entity`, for every hook in a
+    /// [`HookRegistry`], in registration order. Returning [`HookOutcome::Replace`] substitutes
+    /// the entity the wrapper reports back to the caller; anything else (including a null
+    /// [`HookOutcome::Replace`]) leaves the spawned entity as-is.
+    fn on_spawn(&mut self, entity: &mut DungeonEntity) -> HookOutcome {
+        let _ = entity;
+        HookOutcome::Continue
+    }
+
+    /// Runs after [`GlobalDungeonData::is_floor_over`] reports its native result. Returning
+    /// `Some(value)` overrides that result for every hook after it and for the wrapper's return
+    /// value; `None` leaves it unchanged.
+    fn on_floor_over(&self, dungeon: &GlobalDungeonData, floor_over: bool) -> Option<bool> {
+        let _ = (dungeon, floor_over);
+        None
+    }
+
+    /// Runs before [`GlobalDungeonData::run_leader_turn`]. Returning [`HookOutcome::Override`]
+    /// skips the native call and reports that `bool` as whether the leader acted instead.
+    fn on_leader_turn(&mut self, dungeon: &mut GlobalDungeonData) -> HookOutcome {
+        let _ = dungeon;
+        HookOutcome::Continue
+    }
+
+    /// Runs before [`GlobalDungeonData::try_spawn_monster_and_activate_plus_minus`]. Returning
+    /// [`HookOutcome::Override`] skips the native call entirely (the `bool` itself is ignored,
+    /// since the wrapped function has no return value).
+    fn on_plus_minus(&mut self, dungeon: &mut GlobalDungeonData) -> HookOutcome {
+        let _ = dungeon;
+        HookOutcome::Continue
+    }
+
+    /// Runs before [`GlobalDungeonData::try_spawn_monster_and_tick_spawn_counter`]. Returning
+    /// [`HookOutcome::Override`] skips the native call entirely (the `bool` is ignored, same as
+    /// [`Self::on_plus_minus`]).
+    fn on_spawn_counter(&mut self, dungeon: &mut GlobalDungeonData) -> HookOutcome {
+        let _ = dungeon;
+        HookOutcome::Continue
+    }
+
+    /// Runs before [`GlobalDungeonData::faint_check`] (only compiled in with the `eu` feature,
+    /// matching that method). Returning [`HookOutcome::Override`] skips the native call (the
+    /// `bool` is ignored, same as [`Self::on_plus_minus`]).
+    #[cfg_attr(docsrs, doc(cfg(feature = "eu")))]
+    #[cfg(feature = "eu")]
+    fn on_faint_check(&mut self, dungeon: &mut GlobalDungeonData) -> HookOutcome {
+        let _ = dungeon;
+        HookOutcome::Continue
+    }
+}
+
+/// A registry of [`EventHook`]s, run in registration order by the `*_with_hooks` wrapper
+/// functions in this module. The same caller-owned-registry shape as
+/// [`crate::api::dungeon_mode::effects::MoveEffectHooks`].
+#[derive(Default)]
+pub struct HookRegistry {
+    hooks: Vec<Box<dyn EventHook>>,
+}
+
+impl HookRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `hook` to run for every subsequent `*_with_hooks` call, returning an index that
+    /// can be passed to [`Self::remove`].
+    pub fn register(&mut self, hook: Box<dyn EventHook>) -> usize {
+        self.hooks.push(hook);
+        self.hooks.len() - 1
+    }
+
+    /// Removes and returns the hook previously registered at `index` (per [`Self::register`]'s
+    /// return value), if it's still present. Shifts every later hook's index down by one.
+    pub fn remove(&mut self, index: usize) -> Option<Box<dyn EventHook>> {
+        if index < self.hooks.len() {
+            Some(self.hooks.remove(index))
+        } else {
+            None
+        }
+    }
+}
+
+/// Registers the host functions `on_spawn` Rune scripts call into, as `eos::*`.
+///
+/// See the [module-level docs](self) for why these read/write the currently-spawned entity
+/// through `CURRENT_SPAWN` rather than taking it as a script-visible argument.
+pub fn event_hook_module() -> Result<Module, ScriptError> {
+    let mut module = Module::with_crate("eos").map_err(context_err)?;
+
+    module.function_meta(spawned_species).map_err(context_err)?;
+    module
+        .function_meta(spawned_is_team_member)
+        .map_err(context_err)?;
+    module
+        .function_meta(spawned_has_iq_skill)
+        .map_err(context_err)?;
+    module.function_meta(replace_spawn).map_err(context_err)?;
+
+    Ok(module)
+}
+
+fn context_err(_: ContextError) -> ScriptError {
+    ScriptError::Context
+}
+
+/// The entity currently being reported to an `on_spawn` script, plus whatever replacement it asks
+/// for via `eos::replace_spawn`. A raw pointer rather than a borrow, for the same reason
+/// [`crate::api::dungeon_mode::rune_effects`]'s `CURRENT_MOVE_CONTEXT` is: it has to live in a
+/// `static mut`, since Rune's host functions have no way to thread a context argument through to
+/// script code.
+struct SpawnScriptContext {
+    entity: *mut DungeonEntity,
+    replacement: *mut DungeonEntity,
+}
+
+/// Safe to access from the functions below: the NDS is single-threaded, and
+/// [`RuneEventHook::on_spawn`] clears this before returning, having let the script call at most a
+/// handful of `eos::*` functions to completion first -- the same reasoning
+/// [`crate::api::scripting`]'s `PENDING_AI_DECISION` documents.
+static mut CURRENT_SPAWN: Option<SpawnScriptContext> = None;
+
+fn with_spawn_context<R>(f: impl FnOnce(&mut SpawnScriptContext) -> R) -> R {
+    #[allow(static_mut_refs)]
+    unsafe {
+        let ctx = CURRENT_SPAWN
+            .as_mut()
+            .expect("eos::* spawn hook function called outside an on_spawn call");
+        f(ctx)
+    }
+}
+
+/// The apparent species of the entity currently reported to `on_spawn`. Returns `None` if it
+/// isn't a monster.
+#[rune::function(path = spawned_species)]
+fn spawned_species() -> Option<MonsterSpeciesId> {
+    with_spawn_context(|ctx| unsafe {
+        (*ctx.entity)
+            .info_for_monster()
+            .map(|monster| monster.monster().apparent_id)
+    })
+}
+
+/// Whether the entity currently reported to `on_spawn` is a team member, per
+/// [`ffi::monster::is_not_team_member`]. Returns `false` if it isn't a monster.
+#[rune::function(path = spawned_is_team_member)]
+fn spawned_is_team_member() -> bool {
+    with_spawn_context(|ctx| unsafe {
+        (*ctx.entity)
+            .info_for_monster()
+            .is_some_and(|monster| monster.monster().is_not_team_member == 0)
+    })
+}
+
+/// Whether the entity currently reported to `on_spawn` has `iq_skill_id` enabled, via
+/// [`DungeonMonsterRead::is_iq_skill_enabled`]. Returns `false` if it isn't a monster or
+/// `iq_skill_id` isn't a valid [`IqSkillId`].
+#[rune::function(path = spawned_has_iq_skill)]
+fn spawned_has_iq_skill(iq_skill_id: u32) -> bool {
+    let Some(iq_skill_id) = IqSkillId::try_get(iq_skill_id) else {
+        return false;
+    };
+    with_spawn_context(|ctx| unsafe {
+        (*ctx.entity)
+            .info_for_monster()
+            .is_some_and(|monster| monster.is_iq_skill_enabled(iq_skill_id))
+    })
+}
+
+/// Stashes `entity` (an entity pointer previously obtained by the script's own bookkeeping, e.g.
+/// a team member handed to it some other way) as the replacement [`RuneEventHook::on_spawn`]
+/// reports back via [`HookOutcome::Replace`]. This is deliberately the only way a script can hand
+/// back an entity pointer: this crate doesn't expose a way to construct a [`DungeonEntity`] from
+/// script-visible data, so a replacement has to be one the native side already owns.
+#[rune::function(path = replace_spawn)]
+fn replace_spawn(entity: *mut DungeonEntity) {
+    with_spawn_context(|ctx| ctx.replacement = entity);
+}
+
+/// A script-backed [`EventHook`], calling a compiled [`ScriptEngine`]'s `on_spawn`/
+/// `on_floor_over`/`on_leader_turn`/`on_plus_minus`/`on_spawn_counter`/`on_faint_check` functions.
+/// Compile it with [`event_hook_module`] (or a module including it).
+///
+/// Every hook method falls back to the default (report nothing / change nothing) if the script
+/// doesn't define that particular function, or the call otherwise fails.
+pub struct RuneEventHook {
+    engine: ScriptEngine,
+}
+
+impl RuneEventHook {
+    /// Wraps an already-compiled engine as an [`EventHook`].
+    pub fn new(engine: ScriptEngine) -> Self {
+        Self { engine }
+    }
+}
+
+impl EventHook for RuneEventHook {
+    fn on_spawn(&mut self, entity: &mut DungeonEntity) -> HookOutcome {
+        unsafe {
+            CURRENT_SPAWN = Some(SpawnScriptContext {
+                entity: entity as *mut DungeonEntity,
+                replacement: core::ptr::null_mut(),
+            });
+        }
+        let called_back = self.engine.call("on_spawn", ()).is_ok();
+        #[allow(static_mut_refs)]
+        let ctx = unsafe { CURRENT_SPAWN.take() };
+        match ctx {
+            Some(ctx) if called_back && !ctx.replacement.is_null() => {
+                HookOutcome::Replace(ctx.replacement)
+            }
+            _ => HookOutcome::Continue,
+        }
+    }
+
+    fn on_floor_over(&self, _dungeon: &GlobalDungeonData, floor_over: bool) -> Option<bool> {
+        match self.engine.call("on_floor_over", (floor_over,)) {
+            Ok(value) => value.as_bool().ok(),
+            Err(_) => None,
+        }
+    }
+
+    fn on_leader_turn(&mut self, _dungeon: &mut GlobalDungeonData) -> HookOutcome {
+        call_bool_hook(&self.engine, "on_leader_turn")
+    }
+
+    fn on_plus_minus(&mut self, _dungeon: &mut GlobalDungeonData) -> HookOutcome {
+        call_bool_hook(&self.engine, "on_plus_minus")
+    }
+
+    fn on_spawn_counter(&mut self, _dungeon: &mut GlobalDungeonData) -> HookOutcome {
+        call_bool_hook(&self.engine, "on_spawn_counter")
+    }
+
+    #[cfg(feature = "eu")]
+    fn on_faint_check(&mut self, _dungeon: &mut GlobalDungeonData) -> HookOutcome {
+        call_bool_hook(&self.engine, "on_faint_check")
+    }
+}
+
+/// Calls `function` with no arguments, interpreting its return value (if the call succeeds and
+/// returns a bool) as [`HookOutcome::Override`]; anything else (not defined, errored, or returned
+/// a non-bool) is [`HookOutcome::Continue`].
+fn call_bool_hook(engine: &ScriptEngine, function: &str) -> HookOutcome {
+    match engine.call(function, ()) {
+        Ok(value) => value
+            .as_bool()
+            .map(HookOutcome::Override)
+            .unwrap_or(HookOutcome::Continue),
+        Err(_) => HookOutcome::Continue,
+    }
+}
+
+/// Like [`GlobalDungeonData::run_leader_turn`], additionally giving every hook in `registry` an
+/// [`EventHook::on_leader_turn`] chance to short-circuit it first.
+///
+/// # Safety
+/// Same as [`GlobalDungeonData::run_leader_turn`].
+pub unsafe fn run_leader_turn_with_hooks(
+    dungeon: &mut GlobalDungeonData,
+    registry: &mut HookRegistry,
+    param_1: ffi::undefined,
+) -> bool {
+    for hook in registry.hooks.iter_mut() {
+        if let HookOutcome::Override(result) = hook.on_leader_turn(dungeon) {
+            return result;
+        }
+    }
+    dungeon.run_leader_turn(param_1)
+}
+
+/// Like [`GlobalDungeonData::try_spawn_monster_and_activate_plus_minus`], additionally giving
+/// every hook in `registry` an [`EventHook::on_plus_minus`] chance to suppress it first.
+pub fn try_spawn_monster_and_activate_plus_minus_with_hooks(
+    dungeon: &mut GlobalDungeonData,
+    registry: &mut HookRegistry,
+) {
+    for hook in registry.hooks.iter_mut() {
+        if let HookOutcome::Override(_) = hook.on_plus_minus(dungeon) {
+            return;
+        }
+    }
+    dungeon.try_spawn_monster_and_activate_plus_minus();
+}
+
+/// Like [`GlobalDungeonData::try_spawn_monster_and_tick_spawn_counter`], additionally giving
+/// every hook in `registry` an [`EventHook::on_spawn_counter`] chance to suppress it first.
+pub fn try_spawn_monster_and_tick_spawn_counter_with_hooks(
+    dungeon: &mut GlobalDungeonData,
+    registry: &mut HookRegistry,
+) {
+    for hook in registry.hooks.iter_mut() {
+        if let HookOutcome::Override(_) = hook.on_spawn_counter(dungeon) {
+            return;
+        }
+    }
+    dungeon.try_spawn_monster_and_tick_spawn_counter();
+}
+
+/// Like [`GlobalDungeonData::is_floor_over`], additionally giving every hook in `registry` an
+/// [`EventHook::on_floor_over`] chance to override the result afterward.
+pub fn is_floor_over_with_hooks(dungeon: &GlobalDungeonData, registry: &HookRegistry) -> bool {
+    let mut result = dungeon.is_floor_over();
+    for hook in registry.hooks.iter() {
+        if let Some(override_result) = hook.on_floor_over(dungeon, result) {
+            result = override_result;
+        }
+    }
+    result
+}
+
+/// Like [`GlobalDungeonData::spawn_monster`], additionally giving every hook in `registry` an
+/// [`EventHook::on_spawn`] chance to substitute the spawned entity afterward (see
+/// [`HookOutcome::Replace`]).
+pub fn spawn_monster_with_hooks<'d>(
+    dungeon: &'d mut GlobalDungeonData,
+    registry: &mut HookRegistry,
+    spawn_data: &mut ffi::spawned_monster_data,
+    force_awake: bool,
+) -> Option<&'d mut DungeonEntity> {
+    let ptr = dungeon
+        .spawn_monster(spawn_data, force_awake)
+        .map(|entity| entity as *mut DungeonEntity);
+    let Some(mut ptr) = ptr else {
+        return None;
+    };
+    for hook in registry.hooks.iter_mut() {
+        if let HookOutcome::Replace(replacement) = unsafe { hook.on_spawn(&mut *ptr) } {
+            if !replacement.is_null() {
+                ptr = replacement;
+            }
+        }
+    }
+    Some(unsafe { &mut *ptr })
+}
+
+/// Like [`GlobalDungeonData::faint_check`], additionally giving every hook in `registry` an
+/// [`EventHook::on_faint_check`] chance to suppress it first. Only compiled in with the `eu`
+/// feature, matching [`GlobalDungeonData::faint_check`] itself.
+#[cfg_attr(docsrs, doc(cfg(feature = "eu")))]
+#[cfg(feature = "eu")]
+pub fn faint_check_with_hooks(
+    dungeon: &mut GlobalDungeonData,
+    registry: &mut HookRegistry,
+    non_team_member_fainted: bool,
+    set_unk_byte: bool,
+) {
+    for hook in registry.hooks.iter_mut() {
+        if let HookOutcome::Override(_) = hook.on_faint_check(dungeon) {
+            return;
+        }
+    }
+    dungeon.faint_check(non_team_member_fainted, set_unk_byte);
+}
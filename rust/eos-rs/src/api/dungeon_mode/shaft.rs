@@ -0,0 +1,105 @@
+//! Multi-floor "shaft" descent, ie. a trap that drops the faller several floors at once instead
+//! of the single-step advance `set_floor` + `set_end_floor_flag` normally implies.
+//!
+//! Just calling `set_floor` directly would leave a bunch of per-floor transient state (enemy spawn
+//! pacing, the wind warning counter, Mud Sport/Water Sport counters) stale from the floor the
+//! player fell out of, which would carry over incorrectly onto the destination floor. The helpers
+//! here reset that state alongside the floor change, the same way the engine's own end-of-floor
+//! transition would.
+
+use crate::api::dungeon_mode::GlobalDungeonData;
+
+/// Outcome of a shaft descent: whether the requested floor was used as-is, or clamped to a
+/// different floor (because it was past the end of the dungeon, or past a pending rescue point).
+/// Either way, the floor actually descended to is available via [`Self::floor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaftOutcome {
+    /// The descent landed on the originally requested floor.
+    Honored(u8),
+    /// The requested floor was out of range, so the descent was clamped to this floor instead.
+    Clamped(u8),
+}
+
+impl ShaftOutcome {
+    /// The floor the descent actually landed on, whether or not it was clamped.
+    pub fn floor(self) -> u8 {
+        match self {
+            ShaftOutcome::Honored(floor) | ShaftOutcome::Clamped(floor) => floor,
+        }
+    }
+
+    /// Whether the originally requested floor had to be clamped. Mods can use this to decide
+    /// whether to show "you fell through the floor" messaging versus a more specific "...but
+    /// something held you back" variant.
+    pub fn was_clamped(self) -> bool {
+        matches!(self, ShaftOutcome::Clamped(_))
+    }
+}
+
+impl<'a> GlobalDungeonData<'a> {
+    /// Performs a shaft descent straight to `target_floor`, unconditionally (no clamping -- see
+    /// [`Self::shaft_by`]/[`Self::shaft_to_floor_rescue_aware`] for that).
+    ///
+    /// Sets the floor, raises the end-of-floor flag (`end_floor_flag_force` if
+    /// `force_through_fainted_leader` is set, so the descent still happens even if the leader
+    /// fainted from the fall; `end_floor_flag` otherwise), and resets the per-floor transient
+    /// counters that would otherwise be stale on the destination floor: `enemy_spawn_counter`,
+    /// `wind_turns`, `mud_sport_turns`, `water_sport_turns`.
+    pub fn shaft_to_floor(
+        &mut self,
+        target_floor: u8,
+        force_through_fainted_leader: bool,
+    ) -> ShaftOutcome {
+        let dungeon = self.inner_mut();
+        dungeon.set_floor(target_floor);
+        if force_through_fainted_leader {
+            dungeon.set_end_floor_flag_force(true);
+        } else {
+            dungeon.set_end_floor_flag(true);
+        }
+        dungeon.set_enemy_spawn_counter(0);
+        dungeon.set_wind_turns(0);
+        dungeon.set_mud_sport_turns(0);
+        dungeon.set_water_sport_turns(0);
+        ShaftOutcome::Honored(target_floor)
+    }
+
+    /// Like [`Self::shaft_to_floor`], but expressed as an offset from the current floor, clamped
+    /// against the dungeon's known floor count ([`crate::api::dungeons::DungeonId::number_floors`])
+    /// so a shaft can't drop the player past the end of the dungeon.
+    pub fn shaft_by(&mut self, delta: u8, force_through_fainted_leader: bool) -> ShaftOutcome {
+        let current = self.inner().floor();
+        let max_floor = self.inner().id().number_floors().clamp(1, u8::MAX as i32) as u8;
+        let requested = current.saturating_add(delta);
+        let target = requested.min(max_floor);
+        self.shaft_to_floor(target, force_through_fainted_leader);
+        if target == requested {
+            ShaftOutcome::Honored(target)
+        } else {
+            ShaftOutcome::Clamped(target)
+        }
+    }
+
+    /// Like [`Self::shaft_to_floor`], but won't drop the player past a pending rescue point:
+    /// if there are rescue attempts left (`get_rescue_attempts_left`) and `target_floor` is deeper
+    /// than `rescue_floor`, the descent is clamped to `rescue_floor` instead.
+    pub fn shaft_to_floor_rescue_aware(
+        &mut self,
+        target_floor: u8,
+        force_through_fainted_leader: bool,
+    ) -> ShaftOutcome {
+        let dungeon = self.inner();
+        let rescue_pending = dungeon.get_rescue_attempts_left() > 0 && dungeon.rescue_floor() > 0;
+        let target = if rescue_pending && target_floor > dungeon.rescue_floor() {
+            dungeon.rescue_floor()
+        } else {
+            target_floor
+        };
+        self.shaft_to_floor(target, force_through_fainted_leader);
+        if target == target_floor {
+            ShaftOutcome::Honored(target)
+        } else {
+            ShaftOutcome::Clamped(target)
+        }
+    }
+}
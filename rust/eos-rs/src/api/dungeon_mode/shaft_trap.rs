@@ -0,0 +1,111 @@
+//! Custom multi-floor "shaft" traps, distinct from the game's own single-floor pitfall trap (see
+//! [`crate::api::dungeon_mode::DungeonEffectsEmitter::open_pit_under`]): stepping on one queues a
+//! descent several floors down instead of dropping the faller to the floor directly below.
+//!
+//! Based on Crawl's shaft traps: triggering one doesn't move the faller immediately, it just
+//! queues a "pending shaft" with a target depth; the actual descent is applied once the floor
+//! ends (see [`ShaftTrapRegistry::resolve_pending_descent`]), so the faller lands on a freshly
+//! generated floor rather than mid-turn. There's no spare field on [`crate::ffi::tile`] to stash
+//! a custom depth on, so placed traps are tracked by coordinate in [`ShaftTrapRegistry`] instead
+//! -- the same caller-owned-registry shape as [`crate::api::dungeon_mode::event_manager::EventManager`].
+
+use crate::api::dungeon_mode::shaft::ShaftOutcome;
+use crate::api::dungeon_mode::{DungeonEntity, GlobalDungeonData};
+use alloc::vec::Vec;
+
+/// A shaft trap placed by [`ShaftTrapRegistry::place_shaft_trap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PlacedShaftTrap {
+    x: u8,
+    y: u8,
+    depth: u8,
+}
+
+/// Caller-owned bookkeeping for shaft traps: which tiles have one, and the descent (if any)
+/// currently queued to be applied at the next floor transition. Create one, hold onto it across
+/// turns, and call [`Self::trigger`] when an entity steps onto a tile, then
+/// [`Self::resolve_pending_descent`] once per turn to apply a queued descent as soon as the floor
+/// ends.
+#[derive(Debug, Clone, Default)]
+pub struct ShaftTrapRegistry {
+    traps: Vec<PlacedShaftTrap>,
+    pending_floor: Option<u8>,
+}
+
+impl ShaftTrapRegistry {
+    /// Creates an empty registry with no placed traps and nothing pending.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks `(x, y)` as a shaft trap that, when triggered, queues a descent to `depth` floors
+    /// below whatever floor it's triggered on (clamped to the dungeon's last floor -- see
+    /// [`Self::trigger`]). Replaces any shaft trap already placed at that tile.
+    pub fn place_shaft_trap(&mut self, x: u8, y: u8, depth: u8) {
+        self.remove_shaft_trap(x, y);
+        self.traps.push(PlacedShaftTrap { x, y, depth });
+    }
+
+    /// Removes the shaft trap at `(x, y)`, if any.
+    pub fn remove_shaft_trap(&mut self, x: u8, y: u8) {
+        self.traps.retain(|trap| !(trap.x == x && trap.y == y));
+    }
+
+    /// Whether a descent is currently queued, waiting for the floor to end.
+    pub fn has_pending_shaft(&self) -> bool {
+        self.pending_floor.is_some()
+    }
+
+    /// Checks whether a shaft trap is placed at `(x, y)` and, if `entity` isn't immune to it,
+    /// queues a descent. Returns whether the trap fired.
+    ///
+    /// `entity` passes over without triggering if it's levitating (see
+    /// [`crate::api::dungeon_mode::DungeonMonsterRead::is_levitating`]) while
+    /// [`GlobalDungeonData::is_gravity_active`] is `false` -- Gravity grounds flying/levitating
+    /// monsters, so a shaft only spares them when gravity's own effect isn't active. A tile with
+    /// no placed trap, or triggering while a descent is already pending, does nothing.
+    pub fn trigger(
+        &mut self,
+        dungeon: &GlobalDungeonData,
+        entity: &DungeonEntity,
+        x: u8,
+        y: u8,
+    ) -> bool {
+        if self.pending_floor.is_some() {
+            return false;
+        }
+        let Some(trap) = self.traps.iter().find(|trap| trap.x == x && trap.y == y) else {
+            return false;
+        };
+        let levitating = entity
+            .info_for_monster()
+            .is_some_and(|monster| monster.is_levitating());
+        if levitating && !dungeon.inner().is_gravity_active() {
+            return false;
+        }
+
+        let current_floor = dungeon.inner().floor();
+        let max_floor = dungeon
+            .inner()
+            .id()
+            .number_floors()
+            .clamp(1, u8::MAX as i32) as u8;
+        self.pending_floor = Some(current_floor.saturating_add(trap.depth).min(max_floor));
+        true
+    }
+
+    /// Applies a queued descent once the current floor has ended (see
+    /// [`GlobalDungeonData::is_floor_over`]), via [`crate::api::dungeon_mode::shaft::GlobalDungeonData::shaft_to_floor`].
+    /// Does nothing, and returns `None`, if the floor hasn't ended yet or nothing is pending.
+    ///
+    /// Call this once per turn from wherever [`GlobalDungeonData::run_dungeon`]'s floor loop
+    /// checks [`GlobalDungeonData::is_floor_over`], so the consumed descent lands the faller on
+    /// the next, freshly generated floor rather than partway through the current one.
+    pub fn resolve_pending_descent(&mut self, dungeon: &mut GlobalDungeonData) -> Option<ShaftOutcome> {
+        if !dungeon.inner().is_floor_over() {
+            return None;
+        }
+        let target_floor = self.pending_floor.take()?;
+        Some(dungeon.shaft_to_floor(target_floor, true))
+    }
+}
@@ -0,0 +1,111 @@
+//! Parsing and querying floor monster-spawn tables.
+
+use crate::api::monsters::MonsterSpeciesId;
+use crate::api::random;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// A single entry of a floor's monster-spawn table, as laid out in the binary spawn list data
+/// (one record per spawnable species on a floor).
+#[derive(Clone, Copy, Debug)]
+pub struct FloorSpawnRecord {
+    /// The species that can spawn.
+    pub species: MonsterSpeciesId,
+    /// The relative spawn weight of this entry versus the others on the same floor.
+    pub weight: u16,
+    /// The level the monster spawns at.
+    pub level: u8,
+}
+
+/// Parses a buffer of floor-spawn records.
+///
+/// Each record is 4 bytes: a little-endian `u16` species ID, a `u8` spawn weight and a `u8`
+/// level. Parsing stops at the first record whose species ID is 0 (the list terminator), or
+/// when `data` runs out of whole records, whichever comes first.
+pub fn parse_floor_spawn_table(data: &[u8]) -> Vec<FloorSpawnRecord> {
+    let mut records = Vec::new();
+    for chunk in data.chunks_exact(4) {
+        let species_id = u16::from_le_bytes([chunk[0], chunk[1]]);
+        if species_id == 0 {
+            break;
+        }
+        records.push(FloorSpawnRecord {
+            // SAFETY: Species IDs read from a floor's spawn table are assumed to always refer
+            // to an existing species; if the ROM data is well-formed this always holds.
+            species: unsafe { MonsterSpeciesId::new(species_id as u32) },
+            weight: chunk[2] as u16,
+            level: chunk[3],
+        });
+    }
+    records
+}
+
+/// A rare-variant substitution rule: when `base` would spawn, roll a `1 / rarity` chance to
+/// spawn `variant` instead.
+#[derive(Clone, Copy, Debug)]
+pub struct RareVariant {
+    /// The species that would normally spawn.
+    pub base: MonsterSpeciesId,
+    /// The species to substitute in on a successful roll.
+    pub variant: MonsterSpeciesId,
+    /// The odds of the substitution happening are `1` in `rarity`. Must be at least 1.
+    pub rarity: u16,
+}
+
+/// Given a species that was chosen to spawn, looks it up in `table` and, on a successful
+/// `1 / rarity` roll, returns the rare-variant species instead of the base one.
+///
+/// If `species` isn't the `base` of any entry in `table`, or none of the matching entries'
+/// rolls succeed, `species` is returned unchanged.
+pub fn roll_rare_variant(species: MonsterSpeciesId, table: &[RareVariant]) -> MonsterSpeciesId {
+    for rule in table {
+        if rule.base.id() == species.id() && random::rand_i32(0..rule.rarity.max(1) as i32) == 0 {
+            return rule.variant;
+        }
+    }
+    species
+}
+
+/// A registry of companion-spawn groups ("summon clusters"): species that should spawn together
+/// with a given leader species, eg. a pack of wild monsters that always appear as a group.
+#[derive(Default)]
+pub struct CompanionSpawnGroups(BTreeMap<u32, Vec<MonsterSpeciesId>>);
+
+impl CompanionSpawnGroups {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `companions` as spawning alongside `leader`. Calling this again for the same
+    /// leader replaces its previous companion list.
+    pub fn register(&mut self, leader: MonsterSpeciesId, companions: Vec<MonsterSpeciesId>) {
+        self.0.insert(leader.id(), companions);
+    }
+
+    /// Returns the companion species registered for `leader`, if any.
+    pub fn companions_for(&self, leader: MonsterSpeciesId) -> Option<&[MonsterSpeciesId]> {
+        self.0.get(&leader.id()).map(Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_floor_spawn_table() {
+        let data = [
+            0x01, 0x00, 10, 5, // species 1, weight 10, level 5
+            0x02, 0x00, 20, 7, // species 2, weight 20, level 7
+            0x00, 0x00, 0, 0, // terminator
+            0x03, 0x00, 30, 9, // should not be parsed
+        ];
+        let records = parse_floor_spawn_table(&data);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].species.id(), 1);
+        assert_eq!(records[0].weight, 10);
+        assert_eq!(records[0].level, 5);
+        assert_eq!(records[1].species.id(), 2);
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::collections::BTreeMap;
 use crate::api::dungeon_mode::*;
 use crate::api::objects::*;
 use crate::ffi;
@@ -25,6 +26,71 @@ pub trait DungeonTileExt {
 
     /// Set a specific tile to have secondary terrain (water/lava), but only if it's a passable wall.
     fn set_secondary_terrain_on_wall(&mut self);
+
+    /// Sets the broad [`TerrainType`] of a tile directly, without the room/wall checks that
+    /// [`Self::set_terrain_obstacle_checked`] performs.
+    ///
+    /// This does not touch the tile's [`SecondaryTerrainType`]; use
+    /// [`Self::set_secondary_terrain_type`] to pick which kind of secondary terrain (water,
+    /// lava, chasm) a [`TerrainType::Secondary`] tile has.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    fn set_terrain(&mut self, terrain: TerrainType);
+
+    /// Sets the [`SecondaryTerrainType`] of a tile (water, lava or chasm).
+    ///
+    /// This does not change the tile's [`TerrainType`]; combine with [`Self::set_terrain`]
+    /// (setting it to [`TerrainType::Secondary`]) to actually turn the tile into secondary
+    /// terrain.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    fn set_secondary_terrain_type(&mut self, secondary_terrain: SecondaryTerrainType);
+
+    /// Turns the tile into a chasm ("hole"): normal, walkable ground that a monster standing
+    /// on it will fall through to the floor below.
+    ///
+    /// Convenience helper built on top of [`Self::set_terrain`] and
+    /// [`Self::set_secondary_terrain_type`].
+    fn convert_to_chasm(&mut self) {
+        self.set_secondary_terrain_type(SecondaryTerrainType::Chasm);
+        self.set_terrain(TerrainType::Secondary);
+    }
+
+    /// Sets or clears the junction flag (bit 3 of the terrain flags) on the tile.
+    ///
+    /// This is the same flag [`super::dungeon_generator::game_builtin::GlobalDungeonStructureGenerator::flag_hallway_junctions`]
+    /// and [`super::dungeon_generator::game_builtin::GlobalDungeonStructureGenerator::finalize_junctions`]
+    /// set on hallway junction tiles after the builtin generator lays out a floor; those tiles
+    /// are where the game draws its "doors". Manually-carved corridors (see
+    /// [`super::GlobalDungeonData::carve_corridor`]) use this directly instead, since they run
+    /// before or entirely outside of that pass.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    fn set_junction_flag(&mut self, is_junction: bool);
+
+    /// Returns whether the junction flag (bit 3 of the terrain flags) is set on the tile. See
+    /// [`Self::set_junction_flag`].
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    fn get_junction_flag(&self) -> bool;
+
+    /// Returns whether this tile has stairs (of either kind) on it.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    fn is_stairs(&self) -> bool;
+
+    /// Marks or unmarks this tile as having stairs on it. Used by
+    /// [`crate::api::dungeon_mode::GlobalDungeonData::move_down_stairs`] to relocate the
+    /// down stairs without going through [`crate::api::dungeon_mode::dungeon_generator::game_builtin::GlobalDungeonStructureGenerator::spawn_stairs`].
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    fn set_is_stairs(&mut self, is_stairs: bool);
+
+    /// Returns whether the stairs on this tile (see [`Self::is_stairs`]) are hidden stairs,
+    /// rather than regular down stairs. Meaningless if [`Self::is_stairs`] is `false`.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    fn is_hidden_stairs(&self) -> bool;
 }
 
 impl DungeonTileExt for DungeonTile {
@@ -51,6 +117,204 @@ impl DungeonTileExt for DungeonTile {
     fn set_secondary_terrain_on_wall(&mut self) {
         unsafe { ffi::SetSecondaryTerrainOnWall(self as *mut _) }
     }
+
+    fn set_terrain(&mut self, terrain: TerrainType) {
+        unsafe { ffi::SetTileTerrain(self as *mut _, terrain as ffi::terrain_type::Type) }
+    }
+
+    fn set_secondary_terrain_type(&mut self, secondary_terrain: SecondaryTerrainType) {
+        unsafe {
+            ffi::SetTileSecondaryTerrain(
+                self as *mut _,
+                secondary_terrain as ffi::secondary_terrain_type::Type,
+            )
+        }
+    }
+
+    fn set_junction_flag(&mut self, is_junction: bool) {
+        unsafe { ffi::SetTileJunctionFlag(self as *mut _, is_junction as ffi::bool_) }
+    }
+
+    fn get_junction_flag(&self) -> bool {
+        unsafe { ffi::GetTileJunctionFlag(force_mut_ptr!(self)) != 0 }
+    }
+
+    fn is_stairs(&self) -> bool {
+        unsafe { ffi::TileIsStairs(force_mut_ptr!(self)) != 0 }
+    }
+
+    fn set_is_stairs(&mut self, is_stairs: bool) {
+        unsafe { ffi::SetTileIsStairs(self as *mut _, is_stairs as ffi::bool_) }
+    }
+
+    fn is_hidden_stairs(&self) -> bool {
+        unsafe { ffi::TileIsHiddenStairs(force_mut_ptr!(self)) != 0 }
+    }
+}
+
+/// The value of [`ffi::tile::room`] used for hallway tiles (as opposed to a room index).
+const ROOM_HALLWAY: u8 = 0xFF;
+/// The value of [`ffi::tile::room`] used for hallway junction "anchor" tiles.
+const ROOM_HALLWAY_ANCHOR: u8 = 0xFE;
+
+/// Which kind of map region a tile belongs to, as tagged by [`ffi::tile::room`].
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum RegionTag {
+    /// The tile is part of the room with the given index.
+    Room(u8),
+    /// The tile is part of a hallway.
+    Hallway,
+    /// The tile is a hallway junction anchor.
+    HallwayAnchor,
+}
+
+impl RegionTag {
+    /// Derives the region tag from a raw [`ffi::tile::room`] value.
+    pub fn from_room_value(room: u8) -> Self {
+        match room {
+            ROOM_HALLWAY => Self::Hallway,
+            ROOM_HALLWAY_ANCHOR => Self::HallwayAnchor,
+            index => Self::Room(index),
+        }
+    }
+}
+
+/// A structured (x, y) floor position tagged with the kind of region ([`RegionTag`]) the tile
+/// at that position belongs to.
+///
+/// This is a higher-level alternative to passing around a bare [`ffi::position`] when code also
+/// needs to know, or branch on, which room/hallway an entity is in.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct FloorPosition {
+    /// The X tile coordinate.
+    pub x: i16,
+    /// The Y tile coordinate.
+    pub y: i16,
+    /// The region the tile at this position belongs to.
+    pub region: RegionTag,
+}
+
+impl FloorPosition {
+    /// Builds a [`FloorPosition`] from a raw position and the tile found at that position.
+    pub fn new(position: ffi::position, tile: &DungeonTile) -> Self {
+        Self {
+            x: position.x,
+            y: position.y,
+            region: RegionTag::from_room_value(tile.room),
+        }
+    }
+
+    /// Returns the raw [`ffi::position`] equivalent of this position (without the region tag).
+    pub fn as_position(&self) -> ffi::position {
+        ffi::position { x: self.x, y: self.y }
+    }
+}
+
+/// The `(xx, xy, yx, yy)` coordinate transform for each of the 8 octants around a field-of-view
+/// origin, used by [`cast_light`] to turn its octant-local `(col, row)` coordinates into grid
+/// coordinates.
+const FOV_OCTANTS: [(i32, i32, i32, i32); 8] = [
+    (1, 0, 0, 1),
+    (0, 1, 1, 0),
+    (0, -1, 1, 0),
+    (-1, 0, 0, 1),
+    (-1, 0, 0, -1),
+    (0, -1, -1, 0),
+    (0, 1, -1, 0),
+    (1, 0, 0, -1),
+];
+
+/// Recursive shadowcasting over a single octant, as popularized by Bjorn Bergström's "FOV using
+/// recursive shadowcasting" article. `row` is the current depth (in tiles) from the origin, and
+/// `[start_slope, end_slope]` is the slope interval still considered visible at that depth.
+///
+/// Calls `visit` for every visible tile this octant reaches, and recurses into a narrower
+/// interval whenever an opaque tile splits the row's remaining visible span.
+fn cast_light<const W: usize, const H: usize, G: DungeonTileGridRead<W, H>>(
+    grid: &G,
+    origin: (usize, usize),
+    radius: usize,
+    row: usize,
+    mut start_slope: f32,
+    end_slope: f32,
+    xx: i32,
+    xy: i32,
+    yx: i32,
+    yy: i32,
+    is_opaque: &impl Fn(&DungeonTile) -> bool,
+    visit: &mut impl FnMut(usize, usize),
+) {
+    if start_slope < end_slope {
+        return;
+    }
+
+    let radius_sq = (radius * radius) as i32;
+    let mut blocked = false;
+    for r in row..=radius {
+        let r_i = r as i32;
+        let mut new_start_slope = start_slope;
+
+        // Scan from the steepest to the shallowest slope in this row.
+        for col in (0..=r_i).rev() {
+            let left_slope = (col as f32 + 0.5) / (r_i as f32 - 0.5);
+            let right_slope = (col as f32 - 0.5) / (r_i as f32 + 0.5);
+
+            if right_slope > start_slope {
+                // Still above the window's steep edge; keep scanning.
+                continue;
+            }
+            if left_slope < end_slope {
+                // Past the window's shallow edge; nothing else in this row is visible.
+                break;
+            }
+
+            let wx = origin.0 as i32 + col * xx + r_i * xy;
+            let wy = origin.1 as i32 + col * yx + r_i * yy;
+            if wx < 0 || wy < 0 || wx as usize >= W || wy as usize >= H {
+                continue;
+            }
+            let (wx, wy) = (wx as usize, wy as usize);
+
+            if col * col + r_i * r_i <= radius_sq {
+                visit(wx, wy);
+            }
+
+            let Some(tile) = grid.get(wx, wy) else {
+                continue;
+            };
+            let opaque = is_opaque(tile);
+
+            if blocked {
+                if opaque {
+                    new_start_slope = right_slope;
+                    continue;
+                }
+                blocked = false;
+                start_slope = new_start_slope;
+            } else if opaque && r < radius {
+                blocked = true;
+                cast_light(
+                    grid,
+                    origin,
+                    radius,
+                    r + 1,
+                    start_slope,
+                    left_slope,
+                    xx,
+                    xy,
+                    yx,
+                    yy,
+                    is_opaque,
+                    visit,
+                );
+                new_start_slope = right_slope;
+            }
+        }
+
+        if blocked {
+            break;
+        }
+    }
 }
 
 /// Functions for reading from a tile grid.
@@ -60,6 +324,26 @@ pub trait DungeonTileGridRead<const W: usize, const H: usize> {
 
     /// Iterate over all tiles in the grid, in row-major order (from top-left to bottom-right).
     fn iter(&self) -> DungeonTileGridIter<W>;
+
+    /// Computes field of view from `origin` out to `radius` tiles, calling `visit` for every
+    /// tile that's visible (including `origin` itself, unconditionally).
+    ///
+    /// Uses recursive shadowcasting, which is free of the directional artifacts a naive "cast a
+    /// line per tile" approach produces; the result is not guaranteed to be symmetric (tile A
+    /// seeing tile B does not imply tile B sees tile A). `is_opaque` decides whether a tile
+    /// blocks sight past it; `origin`'s own opacity is never consulted.
+    fn visible_from(
+        &self,
+        origin: (usize, usize),
+        radius: usize,
+        is_opaque: impl Fn(&DungeonTile) -> bool,
+        mut visit: impl FnMut(usize, usize),
+    ) {
+        visit(origin.0, origin.1);
+        for &(xx, xy, yx, yy) in &FOV_OCTANTS {
+            cast_light(self, origin, radius, 1, 1.0, 0.0, xx, xy, yx, yy, &is_opaque, &mut visit);
+        }
+    }
 }
 
 /// Functions for writing into a tile grid.
@@ -200,3 +484,112 @@ impl<'a, const W: usize> Iterator for DungeonTileGridIterMut<'a, W> {
         }
     }
 }
+
+/// A staged edit in a [`DungeonTileGridOverlay`]'s upper layer.
+#[derive(Clone)]
+enum OverlayEntry {
+    /// Replace the tile with this value on commit.
+    Edit(DungeonTile),
+    /// Reset the tile to its freshly initialized state on commit: a "whiteout", in
+    /// union-filesystem terms, standing in for a deletion without needing to know the base's
+    /// current value.
+    Whiteout,
+}
+
+/// A copy-on-write, transactional view over a [`DungeonTileGridMut`] base, modeled on
+/// union/overlay-filesystem semantics: a writable "upper" layer of staged edits sits on top of
+/// the read-only "lower" base layer, plus whiteouts for resets.
+///
+/// Reads consult the upper layer first, falling back to the base; nothing reaches the base (and
+/// so live game memory) until [`Self::commit`]. This lets patch authors build up a whole floor
+/// layout or fixed room off to the side, previewing or discarding it, before applying it
+/// atomically, instead of mutating the global dungeon grid tile-by-tile with no way to undo.
+///
+/// # Note
+/// This doesn't implement [`DungeonTileGridRead`]/[`DungeonTileGridWrite`] itself: those traits'
+/// `iter`/`iter_mut` are tied to the concrete raw-pointer-backed iterators
+/// ([`DungeonTileGridIter`]/[`DungeonTileGridIterMut`]), which only make sense over a grid that's
+/// actually backed by live `*mut ffi::tile` pointers. [`Self::iter`] instead yields the merged
+/// upper-over-base view directly.
+pub struct DungeonTileGridOverlay<'a, const W: usize, const H: usize> {
+    base: DungeonTileGridMut<'a, W, H>,
+    upper: BTreeMap<(usize, usize), OverlayEntry>,
+}
+
+impl<'a, const W: usize, const H: usize> DungeonTileGridOverlay<'a, W, H> {
+    /// Wraps `base` in a fresh overlay with no staged edits.
+    pub fn new(base: DungeonTileGridMut<'a, W, H>) -> Self {
+        Self { base, upper: BTreeMap::new() }
+    }
+
+    /// Returns the tile at `(x, y)` as it would read after [`Self::commit`]: the staged edit if
+    /// one exists, otherwise the base's current tile. Reads back as `None` at a whiteout or out
+    /// of bounds.
+    pub fn get(&self, x: usize, y: usize) -> Option<&DungeonTile> {
+        if x >= W || y >= H {
+            return None;
+        }
+        match self.upper.get(&(x, y)) {
+            Some(OverlayEntry::Edit(tile)) => Some(tile),
+            Some(OverlayEntry::Whiteout) => None,
+            None => self.base.get(x, y),
+        }
+    }
+
+    /// Returns the tile at `(x, y)`, mutably, staging a copy of the base's current tile into the
+    /// upper layer on first access so edits never touch the base directly.
+    ///
+    /// Reads back as `None` at a whiteout (there's no concrete tile value to hand out a mutable
+    /// reference to until the whiteout is overwritten with [`Self::insert`]) or out of bounds.
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut DungeonTile> {
+        if x >= W || y >= H {
+            return None;
+        }
+        if !self.upper.contains_key(&(x, y)) {
+            let current = self.base.get(x, y).cloned()?;
+            self.upper.insert((x, y), OverlayEntry::Edit(current));
+        }
+        match self.upper.get_mut(&(x, y)) {
+            Some(OverlayEntry::Edit(tile)) => Some(tile),
+            _ => None,
+        }
+    }
+
+    /// Stages `tile` to be written to `(x, y)` on [`Self::commit`], without touching the base.
+    pub fn insert(&mut self, x: usize, y: usize, tile: DungeonTile) {
+        self.upper.insert((x, y), OverlayEntry::Edit(tile));
+    }
+
+    /// Stages `(x, y)` to be reset to its freshly initialized state on [`Self::commit`] (a
+    /// whiteout), without touching the base.
+    pub fn remove(&mut self, x: usize, y: usize) {
+        self.upper.insert((x, y), OverlayEntry::Whiteout);
+    }
+
+    /// Iterates the merged view (upper layer over base), in row-major order. A whiteout is
+    /// skipped rather than yielded, the same way it reads back as `None` from [`Self::get`].
+    pub fn iter(&self) -> impl Iterator<Item = &DungeonTile> {
+        (0..H).flat_map(move |y| (0..W).filter_map(move |x| self.get(x, y)))
+    }
+
+    /// Flushes every staged edit into the base: edits are written with the same bit-field copy
+    /// [`DungeonTileGridWrite::insert`] uses, and whiteouts are reset in place via
+    /// [`DungeonTileExt::init`]. Clears the staged edits afterwards, so the overlay can keep being
+    /// used for a new transaction over the same base.
+    pub fn commit(&mut self) {
+        for (&(x, y), entry) in &self.upper {
+            match entry {
+                OverlayEntry::Edit(tile) => self.base.insert(x, y, tile.clone()),
+                OverlayEntry::Whiteout => {
+                    if let Some(tile) = self.base.get_mut(x, y) {
+                        tile.init();
+                    }
+                }
+            }
+        }
+        self.upper.clear();
+    }
+
+    /// Drops every staged edit without touching the base.
+    pub fn discard(self) {}
+}
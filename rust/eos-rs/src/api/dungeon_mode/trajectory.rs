@@ -0,0 +1,142 @@
+//! A tile-by-tile trajectory tracer for the directional effect functions wrapped on
+//! [`DungeonEffectsEmitter`] (`TryPounce`, `TryBlowAway`, `TryWarp`), which take a raw direction
+//! ID with no way to know in advance what a projectile or displacement would actually hit.
+
+use crate::api::dungeon_mode::*;
+use crate::api::enums::Direction;
+use crate::api::overlay::{CreatableWithLease, OverlayLoadLease};
+use crate::ffi;
+use alloc::vec::Vec;
+
+/// What stopped a [`DungeonTrajectory::trace`] walk before it ran out of `max_range` tiles.
+#[derive(Clone, Copy, Debug)]
+pub enum TrajectoryStop<'a> {
+    /// An impassable ([`TerrainType::Wall`]) tile was reached. Not included in [`Trace::tiles`].
+    Wall { position: ffi::position },
+    /// One of the `blocking_entities` passed to [`DungeonTrajectory::trace`] was reached. Not
+    /// included in [`Trace::tiles`].
+    Entity {
+        entity: &'a DungeonEntity,
+        position: ffi::position,
+    },
+    /// The walk reached `max_range` tiles without hitting a wall or a blocking entity.
+    MaxRange,
+}
+
+/// The result of [`DungeonTrajectory::trace`]: the ordered tiles walked through (not including
+/// the origin, and not including whatever tile stopped the walk), and what stopped it.
+#[derive(Clone, Debug)]
+pub struct Trace<'a> {
+    /// The tiles walked through, in order away from the origin.
+    pub tiles: Vec<ffi::position>,
+    /// What stopped the walk.
+    pub stop: TrajectoryStop<'a>,
+}
+
+impl<'a> Trace<'a> {
+    /// Whether this trace stopped on an entity that's an ally of `user` (see
+    /// [`DungeonTrajectory::trace`]'s `blocking_entities`), the "would this hit a friendly
+    /// monster?" check a line move or knockback effect wants before it commits.
+    pub fn would_pass_through_ally(&self, user: &DungeonEntity) -> bool {
+        match self.stop {
+            TrajectoryStop::Entity { entity, .. } => is_ally(user, entity),
+            _ => false,
+        }
+    }
+
+    /// Gates committing to whatever this trace previews (eg. a call to
+    /// [`DungeonEffectsEmitter::try_blow_away`] or a damaging move) behind a friendly-fire prompt:
+    /// if [`Self::would_pass_through_ally`] is true, defers to `confirm` and only proceeds if it
+    /// returns `true`; otherwise always proceeds, since there's nothing to confirm.
+    pub fn confirm_if_hits_ally(&self, user: &DungeonEntity, confirm: impl FnOnce() -> bool) -> bool {
+        !self.would_pass_through_ally(user) || confirm()
+    }
+}
+
+/// Whether `user` and `other` are on the same side (both team members, or both not). `false` if
+/// either isn't a monster.
+fn is_ally(user: &DungeonEntity, other: &DungeonEntity) -> bool {
+    match (user.info_for_monster(), other.info_for_monster()) {
+        (Some(u), Some(o)) => u.0.is_not_team_member == o.0.is_not_team_member,
+        _ => false,
+    }
+}
+
+/// A tile-by-tile line tracer over the dungeon grid, keyed on the same [`OverlayLoadLease<29>`]
+/// as [`DungeonEffectsEmitter`]. Lets mod authors preview a directional effect (a beam, a
+/// knockback, a pounce) before committing to it, since `TryPounce`/`TryBlowAway`/`TryWarp`
+/// themselves give no such lookahead.
+pub struct DungeonTrajectory(OverlayLoadLease<29>);
+
+impl CreatableWithLease<29> for DungeonTrajectory {
+    fn _create(lease: OverlayLoadLease<29>) -> Self {
+        Self(lease)
+    }
+
+    fn lease(&self) -> &OverlayLoadLease<29> {
+        &self.0
+    }
+}
+
+impl DungeonTrajectory {
+    /// Walks the dungeon grid tile-by-tile from `origin` (exclusive) in `direction`, out to
+    /// `max_range` tiles, using the eight PMD direction vectors (diagonals step both axes).
+    ///
+    /// Stops early at the first impassable ([`TerrainType::Wall`]) tile, or the first tile
+    /// occupied by one of `blocking_entities` (the caller supplies these, eg. from
+    /// [`EntityTableRead::get_active_monsters`], the same way
+    /// [`DungeonMonsterRead::trace_move_targets`] does, since this crate doesn't expose a
+    /// floor-wide entity-at-position lookup); whichever happens first is reported as
+    /// [`Trace::stop`], and is not included in [`Trace::tiles`].
+    ///
+    /// Returns `None` for [`Direction::Current`] ("use the entity's current facing"), since
+    /// resolving that to a concrete direction is left to the caller; see [`Direction::step`].
+    pub fn trace<'a>(
+        &self,
+        origin: ffi::position,
+        direction: Direction,
+        max_range: i32,
+        blocking_entities: &[&'a DungeonEntity],
+    ) -> Option<Trace<'a>> {
+        let (dx, dy) = direction.step()?;
+        let mut tiles = Vec::new();
+        let mut x = origin.x as i32;
+        let mut y = origin.y as i32;
+
+        for _ in 0..max_range {
+            x += dx;
+            y += dy;
+            let position = ffi::position {
+                x: x as i16,
+                y: y as i16,
+            };
+
+            // SAFETY: We have a lease on the overlay existing. GetTileSafe bounds-checks the
+            // coordinates itself, returning a default (out-of-bounds) tile instead of UB.
+            let tile = unsafe { &*ffi::GetTileSafe(x, y) };
+            if tile.get_terrain() == Some(TerrainType::Wall) {
+                return Some(Trace {
+                    tiles,
+                    stop: TrajectoryStop::Wall { position },
+                });
+            }
+
+            if let Some(&entity) = blocking_entities
+                .iter()
+                .find(|entity| entity.get_tile().is_some_and(|t| core::ptr::eq(t, tile)))
+            {
+                return Some(Trace {
+                    tiles,
+                    stop: TrajectoryStop::Entity { entity, position },
+                });
+            }
+
+            tiles.push(position);
+        }
+
+        Some(Trace {
+            tiles,
+            stop: TrajectoryStop::MaxRange,
+        })
+    }
+}
@@ -1,3 +1,9 @@
+use crate::api::dungeon_mode::entity::DungeonEntity;
+use crate::api::dungeon_mode::{DungeonRng, GlobalDungeonData};
+use crate::api::enums::DungeonEntityType;
+use crate::api::game_id::GameId;
+use crate::api::iq::IqSkillId;
+use crate::api::overlay::OverlayLoadLease;
 use crate::ffi;
 /// The ID of a trap.
 pub type TrapId = ffi::trap_id;
@@ -5,6 +11,11 @@ impl Copy for TrapId {}
 
 /// This impl provides general metadata about traps in the game.
 impl TrapId {
+    /// The number of traps in the game.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub const COUNT: u32 = 25;
+
     /// Returns the ID struct for the trap with the given ID.
     ///
     /// # Safety
@@ -13,6 +24,46 @@ impl TrapId {
     pub const unsafe fn new(id: u32) -> Self {
         Self(id)
     }
+
+    /// Returns the ID struct for the trap with the given ID, or `None` if `id` is out of range
+    /// (i.e. there are no `id >= Self::COUNT` traps).
+    ///
+    /// Unlike [`Self::new`], this is safe, since it checks `id` against [`Self::COUNT`] before
+    /// constructing the ID.
+    pub const fn try_get(id: u32) -> Option<Self> {
+        if id < Self::COUNT {
+            Some(Self(id))
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether this ID refers to an existing trap (i.e. `self.id() < Self::COUNT`).
+    pub const fn is_valid(&self) -> bool {
+        self.0 < Self::COUNT
+    }
+
+    /// Returns an iterator over every valid trap ID, in order.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).map(Self)
+    }
+
+    /// Returns the ID of this trap.
+    pub const fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+impl GameId for TrapId {
+    const COUNT: u32 = Self::COUNT;
+
+    fn raw(&self) -> u32 {
+        self.id()
+    }
+
+    unsafe fn from_raw(id: u32) -> Self {
+        Self(id)
+    }
 }
 
 impl From<TrapId> for u32 {
@@ -20,3 +71,173 @@ impl From<TrapId> for u32 {
         v.0
     }
 }
+
+/// Spawns a trap entity of the given kind at a position on the current floor.
+///
+/// `team` controls which team the trap is visible/dangerous to (e.g. traps set by an enemy
+/// are normally invisible to other enemies). `room_index` should be the [`crate::api::dungeon_mode::RegionTag`]
+/// of the tile the trap is placed on.
+///
+/// Returns `None` if the game returned a null pointer for the newly spawned trap.
+///
+/// Note: unverified, ported from Irdkwia's notes.
+pub fn spawn_trap(
+    _ov29: &OverlayLoadLease<29>,
+    trap_id: TrapId,
+    position: &ffi::position,
+    team: ffi::undefined4,
+    room_index: u8,
+) -> Option<&mut DungeonEntity> {
+    let ptr = unsafe { ffi::SpawnTrap(trap_id, force_mut_ptr!(position), team, room_index) };
+    if ptr.is_null() {
+        None
+    } else {
+        Some(unsafe { &mut *ptr })
+    }
+}
+
+/// What [`Trap::attempt_disarm`] resolved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisarmResult {
+    /// The trap was successfully disarmed.
+    Disarmed,
+    /// The attempt failed, but the trap wasn't triggered.
+    Failed,
+    /// The attempt failed badly enough that it triggered the trap on the actor.
+    Sprung,
+}
+
+/// A high-level wrapper over a trap entity (one for which
+/// [`DungeonEntity::entity_type`] is [`DungeonEntityType::Trap`]), adding roguelike-style
+/// detect/disarm/trigger semantics on top of it.
+///
+/// This crate has no visibility into `ffi::trap`'s actual field layout (nothing in this crate
+/// ever reads a named field off it, only ever handling it as an opaque reference via
+/// [`DungeonEntity::info_for_trap`]/[`DungeonEntity::info_for_trap_mut`]), so `kind`,
+/// `difficulty` and `relevant_iq_skill` are supplied by the caller at construction time (e.g.
+/// from whatever table a mod keeps keyed by [`TrapId`]) rather than read off the entity.
+pub struct Trap<'a> {
+    entity: &'a mut DungeonEntity,
+    kind: TrapId,
+    difficulty: u8,
+    relevant_iq_skill: Option<IqSkillId>,
+}
+
+impl<'a> Trap<'a> {
+    /// Base disarm chance (as a percentage), before the acting entity's skill and the trap's
+    /// difficulty are factored in.
+    pub const BASE_CHANCE: i32 = 50;
+    /// How many percentage points of disarm chance each point of effective skill is worth.
+    pub const SKILL_FACTOR: i32 = 2;
+    /// Flat effective-skill bonus applied when [`Self::relevant_iq_skill`] is enabled for the
+    /// acting team, on top of the actor's level.
+    pub const IQ_SKILL_BONUS: i32 = 10;
+    /// The computed chance is never allowed below this floor.
+    pub const MIN_CHANCE: i32 = 5;
+    /// The computed chance is never allowed above this ceiling.
+    pub const MAX_CHANCE: i32 = 95;
+    /// Of the attempts that fail disarming, the percentage of those that spring the trap rather
+    /// than merely failing harmlessly.
+    pub const SPRING_CHANCE_ON_FAIL: i32 = 50;
+
+    /// Wraps `entity` as a [`Trap`], returning `None` if it isn't actually a trap entity
+    /// (i.e. [`DungeonEntity::entity_type`] isn't [`DungeonEntityType::Trap`]).
+    ///
+    /// `difficulty` and `relevant_iq_skill` feed into [`Self::attempt_disarm`]'s chance
+    /// calculation; see the [type-level docs](Self) for why they're supplied here rather than
+    /// read off the entity.
+    pub fn new(
+        entity: &'a mut DungeonEntity,
+        kind: TrapId,
+        difficulty: u8,
+        relevant_iq_skill: Option<IqSkillId>,
+    ) -> Option<Self> {
+        if entity.entity_type() != Some(DungeonEntityType::Trap) {
+            return None;
+        }
+        Some(Self {
+            entity,
+            kind,
+            difficulty,
+            relevant_iq_skill,
+        })
+    }
+
+    /// The kind of trap this is, as supplied to [`Self::new`].
+    pub fn kind(&self) -> TrapId {
+        self.kind
+    }
+
+    /// The difficulty rating this trap was constructed with, as fed into
+    /// [`Self::attempt_disarm`]'s chance calculation.
+    pub fn difficulty(&self) -> u8 {
+        self.difficulty
+    }
+
+    /// Whether `actor` can currently interact with this trap at all.
+    ///
+    /// There's no trap-visibility flag (e.g. a revealed/hidden bit) exposed anywhere in this
+    /// crate to check, so this only verifies that both the trap and `actor` are valid entities;
+    /// a mod wanting Trap Seer-style vision checks should layer them on top of this.
+    pub fn detect(&self, actor: &DungeonEntity) -> bool {
+        DungeonEntity::is_valid(force_mut_ptr!(self.entity)) && DungeonEntity::is_valid(force_mut_ptr!(actor))
+    }
+
+    /// The effective skill used by [`Self::attempt_disarm`]'s chance calculation: `actor`'s
+    /// monster level, plus [`Self::IQ_SKILL_BONUS`] if [`Self::relevant_iq_skill`] is enabled for
+    /// either the team leader or any team member (checked via
+    /// [`GlobalDungeonData::team_leader_has_enabled_iq_skill`]/
+    /// [`GlobalDungeonData::team_member_has_enabled_iq_skill`]).
+    pub fn effective_skill(&self, dungeon: &GlobalDungeonData, actor: &DungeonEntity) -> i32 {
+        let level = actor
+            .info_for_monster()
+            .map(|monster| monster.monster().level as i32)
+            .unwrap_or(0);
+        let skill_bonus = self.relevant_iq_skill.is_some_and(|iq_skill| {
+            dungeon.team_leader_has_enabled_iq_skill(iq_skill)
+                || dungeon.team_member_has_enabled_iq_skill(iq_skill)
+        });
+        level + if skill_bonus { Self::IQ_SKILL_BONUS } else { 0 }
+    }
+
+    /// Attempts to disarm this trap, as `actor`.
+    ///
+    /// The disarm chance is `BASE_CHANCE + SKILL_FACTOR * effective_skill - difficulty`, clamped
+    /// between [`Self::MIN_CHANCE`] and [`Self::MAX_CHANCE`] (see [`Self::effective_skill`] for
+    /// how `effective_skill` is derived). On failure, there's a further
+    /// [`Self::SPRING_CHANCE_ON_FAIL`] chance that the trap is sprung rather than simply failing.
+    ///
+    /// Rolls are made with `rng`'s plain [`DungeonRng::rand_outcome`] rather than
+    /// [`DungeonRng::rand_outcome_user_action`], since the latter factors in Serene Grace, which
+    /// has no bearing on a disarm attempt.
+    pub fn attempt_disarm(
+        &mut self,
+        dungeon: &GlobalDungeonData,
+        actor: &DungeonEntity,
+        rng: &DungeonRng,
+    ) -> DisarmResult {
+        let effective_skill = self.effective_skill(dungeon, actor);
+        let chance = (Self::BASE_CHANCE + Self::SKILL_FACTOR * effective_skill - self.difficulty as i32)
+            .clamp(Self::MIN_CHANCE, Self::MAX_CHANCE);
+        if rng.rand_outcome(chance) {
+            return DisarmResult::Disarmed;
+        }
+        if rng.rand_outcome(Self::SPRING_CHANCE_ON_FAIL) {
+            DisarmResult::Sprung
+        } else {
+            DisarmResult::Failed
+        }
+    }
+
+    /// Marks this trap as triggered by `actor` (e.g. after stepping onto it, or after
+    /// [`Self::attempt_disarm`] returns [`DisarmResult::Sprung`]), returning its kind so the
+    /// caller can dispatch to their own per-trap effect logic.
+    ///
+    /// This crate has no binding for the game's own per-trap effect dispatch (no `ffi` function
+    /// for it is visible anywhere in this crate), so this is a bookkeeping hook rather than
+    /// something that runs the trap's real in-game effect.
+    pub fn trigger(&self, actor: &DungeonEntity) -> TrapId {
+        let _ = actor;
+        self.kind
+    }
+}
@@ -0,0 +1,196 @@
+//! Turn-driven weather scheduler layered over [`Dungeon`]'s raw weather-turn counters
+//! ([`Dungeon::get_weather_turns`]/[`Dungeon::get_artificial_permaweather_turns`]), so mods can
+//! queue a sequence of `(Weather, duration)` segments instead of reimplementing the game's own
+//! weather-selection priority by hand.
+//!
+//! [`WeatherSchedule`] also supports a "randomized forecast" mode: once its queued segments run
+//! out, it rolls a new one from a weighted table every time the current one expires, mirroring
+//! the randomly-changing weather seen in survival-style dungeons.
+
+use crate::api::dungeon_mode::Dungeon;
+use crate::api::dungeon_mode::Weather;
+use crate::ffi;
+use alloc::vec::Vec;
+
+/// [`Weather`] in the same order [`Dungeon::get_weather_turns`]/
+/// [`Dungeon::get_artificial_permaweather_turns`] index by (`as usize`), used by
+/// [`effective_weather`] to break ties "in enum order" per those arrays' own doc comments.
+/// Excludes [`Weather::Random`], which has no turn counter of its own.
+const WEATHER_ORDER: [Weather; 8] = [
+    Weather::Clear,
+    Weather::Sunny,
+    Weather::Sandstorm,
+    Weather::Cloudy,
+    Weather::Rain,
+    Weather::Hail,
+    Weather::Fog,
+    Weather::Snow,
+];
+
+/// Resolves the effective weather from the raw counters the same way the base game does: among
+/// every nonzero counter across both `weather_turns` and `artificial_permaweather_turns`, the
+/// highest count wins, ties broken in enum order (lowest [`WEATHER_ORDER`] index). An artificial
+/// permaweather counter of 1 -- "sticky" for as long as its source ability is active -- competes
+/// exactly like any other nonzero count, so it naturally stays picked while present without any
+/// special-casing here.
+///
+/// Returns `None` if every counter is zero (natural weather applies).
+pub fn effective_weather(weather_turns: &[u16; 8], permaweather_turns: &[u16; 8]) -> Option<Weather> {
+    let mut best: Option<(Weather, u16)> = None;
+    for weather in WEATHER_ORDER {
+        let count = weather_turns[weather as usize].max(permaweather_turns[weather as usize]);
+        let is_new_best = match best {
+            Some((_, best_count)) => count > best_count,
+            None => true,
+        };
+        if count > 0 && is_new_best {
+            best = Some((weather, count));
+        }
+    }
+    best.map(|(weather, _)| weather)
+}
+
+/// Advances a tiny xorshift32 generator, returning the new state. [`WeatherSchedule`] owns its
+/// own seed rather than drawing from [`crate::api::dungeon_mode::DungeonRng`], so that rolling a
+/// forecast weather never perturbs the shared dungeon PRNG stream other game logic depends on.
+fn next_u32(seed: &mut u32) -> u32 {
+    let mut x = if *seed == 0 { 1 } else { *seed };
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    *seed = x;
+    x
+}
+
+/// Picks a weather from `table` (weather, weight pairs) proportional to its weight, using and
+/// advancing `seed`. Returns `None` if `table` is empty or every weight is 0.
+fn roll_weighted(table: &[(Weather, u32)], seed: &mut u32) -> Option<Weather> {
+    let total: u32 = table.iter().map(|(_, weight)| *weight).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = next_u32(seed) % total;
+    for (weather, weight) in table {
+        if roll < *weight {
+            return Some(*weather);
+        }
+        roll -= *weight;
+    }
+    table.last().map(|(weather, _)| *weather)
+}
+
+/// Configuration for [`WeatherSchedule`]'s "randomized forecast" mode: once the queued segments
+/// run out, a new one is rolled from `table` every time the current one expires, each lasting
+/// `segment_turns`.
+struct Forecast {
+    table: Vec<(Weather, u32)>,
+    segment_turns: u16,
+}
+
+/// A queue of `(Weather, duration_in_turns)` segments to drive over a [`Dungeon`]'s weather turn
+/// counters, one call to [`Self::tick`] per game turn. See the [module-level docs](self).
+pub struct WeatherSchedule {
+    segments: Vec<(Weather, u16)>,
+    forecast: Option<Forecast>,
+    rng_seed: u32,
+}
+
+impl Default for WeatherSchedule {
+    fn default() -> Self {
+        Self {
+            segments: Vec::new(),
+            forecast: None,
+            rng_seed: 1,
+        }
+    }
+}
+
+impl WeatherSchedule {
+    /// Creates an empty schedule (no queued segments, no randomized forecast).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Like [`Self::new`], but seeds the randomized-forecast roller explicitly instead of
+    /// defaulting it, for a reproducible forecast sequence.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            rng_seed: seed,
+            ..Self::default()
+        }
+    }
+
+    /// Appends a `(weather, turns)` segment to the end of the queue.
+    pub fn queue(&mut self, weather: Weather, turns: u16) {
+        self.segments.push((weather, turns));
+    }
+
+    /// Enables randomized-forecast mode: once the queued segments run out, a new one is rolled
+    /// from `table` (weather, weight pairs) every time the current one expires, each lasting
+    /// `segment_turns`. Replaces any previously configured forecast.
+    pub fn set_randomized_forecast(&mut self, table: Vec<(Weather, u32)>, segment_turns: u16) {
+        self.forecast = Some(Forecast {
+            table,
+            segment_turns,
+        });
+    }
+
+    /// Disables randomized-forecast mode; the schedule goes inert once its queued segments run
+    /// out instead of rolling a new one.
+    pub fn clear_randomized_forecast(&mut self) {
+        self.forecast = None;
+    }
+
+    /// Advances the schedule by one game turn. Call this once per turn while the schedule should
+    /// be in control of `dungeon`'s weather.
+    ///
+    /// If the queue is empty when the tick starts and a [`Self::set_randomized_forecast`] table
+    /// is set, first rolls a new segment from it. Writes the active segment's remaining turns
+    /// into [`Dungeon::get_weather_turns_mut`], decrements it, and on reaching zero pops it (the
+    /// next forecast segment, if any, is rolled at the start of a later tick). Then resolves the
+    /// effective weather using the same priority the base game uses (see [`effective_weather`]),
+    /// except while [`Dungeon::is_weather_nullified`] holds (Cloud Nine/Air Lock), which forces
+    /// the effective weather to [`Weather::Clear`] without touching the queued segments. If the
+    /// effective weather changed, calls [`Dungeon::set_weather`] and `recompute_color_table` to
+    /// re-tint the screen -- this crate has no bound primitive for that recomputation itself, so
+    /// the caller supplies it.
+    pub fn tick<T: AsRef<ffi::dungeon> + AsMut<ffi::dungeon>>(
+        &mut self,
+        dungeon: &mut Dungeon<T>,
+        recompute_color_table: impl FnOnce(Weather, &mut [ffi::rgb; 256]),
+    ) {
+        if self.segments.is_empty() {
+            if let Some(forecast) = &self.forecast {
+                if let Some(weather) = roll_weighted(&forecast.table, &mut self.rng_seed) {
+                    self.segments.push((weather, forecast.segment_turns));
+                }
+            }
+        }
+
+        let Some((weather, turns)) = self.segments.first_mut() else {
+            return;
+        };
+        let weather = *weather;
+        if weather != Weather::Random {
+            dungeon.get_weather_turns_mut()[weather as usize] = *turns;
+        }
+        *turns = turns.saturating_sub(1);
+        if *turns == 0 {
+            self.segments.remove(0);
+        }
+
+        let effective = if dungeon.is_weather_nullified() {
+            None
+        } else {
+            effective_weather(
+                dungeon.get_weather_turns(),
+                dungeon.get_artificial_permaweather_turns(),
+            )
+        };
+        let resolved = effective.unwrap_or(Weather::Clear);
+        if dungeon.get_weather() != Some(resolved) {
+            dungeon.set_weather(resolved);
+            recompute_color_table(resolved, dungeon.get_color_table_mut());
+        }
+    }
+}
@@ -1,9 +1,10 @@
 //! Dealing with [fixed-point numbers](https://en.wikipedia.org/wiki/Fixed-point_arithmetic)
 //! used in the game.
 //!
-//! Note that this module currently only deals with binary fixed-point representations.
-//! The game also sometimes uses decimal representations of fixed-point numbers
-//! (eg. 0x64 -> 100 -> '01.00').
+//! Most of this module deals with binary fixed-point representations, pulled in from the
+//! [`fixed`](fixed) crate below. The game also sometimes uses decimal representations of
+//! fixed-point numbers (eg. 0x64 -> 100 -> '01.00'); [`DecFixedPoint`] and [`DecimalFixed`] cover
+//! the two decimal encodings this crate has run into so far.
 //!
 //! This pulls in parts of the [`fixed`](https://docs.rs/fixed/latest/fixed/index.html) crate,
 //! which describes these numbers as follows:
@@ -61,11 +62,176 @@
 pub use fixed::{FixedU8, FixedI8, FixedU16, FixedI16, FixedU32, FixedI32};
 pub use fixed::types::*;
 
+mod trig;
+pub use self::trig::{atan2, cos, sin, sin_cos, sqrt};
+
+use crate::ffi;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::Sub;
+
+/// The *decimal* fixed-point format the game uses for belly calculations, as opposed to the
+/// binary fixed-point numbers the rest of this module deals with.
+///
+/// A value is a 32-bit word whose lower 16 bits are the integer part and whose upper 16 bits are
+/// thousandths, so the numeric value is `(data & 0xffff) + (data >> 16) / 1000`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct DecFixedPoint(u32);
+
+/// Returned by [`DecFixedPoint::try_from`] when the input can't be represented: it's negative,
+/// its integer part doesn't fit in 16 bits, or its thousandths don't fit in 16 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecFixedPointRangeError;
+
+impl DecFixedPoint {
+    /// Wraps a raw belly-format value, with no validation.
+    pub const fn from_bits(data: u32) -> Self {
+        Self(data)
+    }
+
+    /// Returns the raw belly-format value.
+    pub const fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the represented value as `(data & 0xffff) + (data >> 16) / 1000`.
+    pub fn value(self) -> f64 {
+        (self.0 & 0xffff) as f64 + (self.0 >> 16) as f64 / 1000.0
+    }
+
+    /// Computes the ceiling of this value, via the game's `CeilFixedPoint`.
+    pub fn ceil(self) -> Self {
+        Self(unsafe { ffi::CeilFixedPoint(self.0) })
+    }
+}
+
+impl Sub for DecFixedPoint {
+    type Output = Self;
+
+    /// Computes `max(self - rhs, 0)`, via the game's `SubFixedPoint`.
+    fn sub(self, rhs: Self) -> Self {
+        Self(unsafe { ffi::SubFixedPoint(self.0, rhs.0) })
+    }
+}
+
+impl From<I16F16> for DecFixedPoint {
+    /// Converts a Q16.16 binary fixed-point value to the decimal belly format, flooring
+    /// thousandths, via the game's `BinToDecFixedPoint`.
+    fn from(value: I16F16) -> Self {
+        // BinToDecFixedPoint takes a pointer p where ((const unsigned *)p)[1] is the Q16.16
+        // value to convert; the word at index 0 is unused by the function, but must still be
+        // there for the pointer arithmetic to land on the right word.
+        let words: [u32; 2] = [0, value.to_bits() as u32];
+        Self(unsafe { ffi::BinToDecFixedPoint(words.as_ptr()) })
+    }
+}
+
+impl TryFrom<f32> for DecFixedPoint {
+    type Error = DecFixedPointRangeError;
+
+    /// Converts a plain float to the decimal belly format, rounding thousandths to the nearest
+    /// representable value.
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        if !value.is_finite() || value < 0.0 {
+            return Err(DecFixedPointRangeError);
+        }
+        let int_part = value.trunc();
+        if int_part > u16::MAX as f32 {
+            return Err(DecFixedPointRangeError);
+        }
+        let thousandths = (value.fract() * 1000.0).round();
+        if thousandths > u16::MAX as f32 {
+            return Err(DecFixedPointRangeError);
+        }
+        Ok(Self(int_part as u32 | ((thousandths as u32) << 16)))
+    }
+}
+
+impl fmt::Display for DecFixedPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+impl fmt::Debug for DecFixedPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DecFixedPoint({})", self.value())
+    }
+}
+
+/// A decimal fixed-point value with two implied decimal digits, used for the game's
+/// percentage-style stat multipliers and hit-rate modifiers, e.g. `0x64` -> `100` -> `1.00`.
+///
+/// Unlike [`DecFixedPoint`]'s belly format, which packs an integer part and thousandths into
+/// separate halves of a 32-bit word, this is just a plain integer scaled by 100: the raw value
+/// *is* the represented number times 100.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct DecimalFixed(i32);
+
+impl DecimalFixed {
+    /// Wraps a raw value as read from the game (e.g. from a stat multiplier field), with no
+    /// validation.
+    pub const fn from_raw(raw: i32) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw value, as the game would encode it.
+    pub const fn to_raw(self) -> i32 {
+        self.0
+    }
+
+    /// Wraps an integer percentage (e.g. `100` for `1.00`, a no-op multiplier).
+    ///
+    /// Equivalent to [`Self::from_raw`]; this just names the same conversion for callers that
+    /// think of the value as a percentage rather than raw game data.
+    pub const fn from_percent(percent: i32) -> Self {
+        Self(percent)
+    }
+
+    /// Returns the value as an integer percentage (e.g. `100` for `1.00`).
+    pub const fn to_percent(self) -> i32 {
+        self.0
+    }
+
+    /// Returns the represented value as `raw / 100`.
+    pub fn value(self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+}
+
+impl From<I24F8> for DecimalFixed {
+    /// Converts a Q24.8 binary fixed-point value to the two-decimal-digit format, rounding to
+    /// the nearest representable value.
+    fn from(value: I24F8) -> Self {
+        Self((value * I24F8::from_num(100)).round().to_num::<i32>())
+    }
+}
+
+impl From<DecimalFixed> for I24F8 {
+    fn from(value: DecimalFixed) -> Self {
+        I24F8::from_num(value.0) / I24F8::from_num(100)
+    }
+}
+
+impl fmt::Display for DecimalFixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let magnitude = self.0.unsigned_abs();
+        write!(f, "{sign}{}.{:02}", magnitude / 100, magnitude % 100)
+    }
+}
+
+impl fmt::Debug for DecimalFixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DecimalFixed({self})")
+    }
+}
 
 // Since doctests don't work, we turn the doctest into a normal unit test here.
 #[cfg(test)]
 mod test {
-    use super::I24F8;
+    use super::{DecFixedPoint, DecimalFixed, I24F8};
+    use core::convert::TryFrom;
 
     #[test]
     pub fn test_documentation() {
@@ -78,4 +244,50 @@ mod test {
         let n3 = I24F8::from_bits(0x01_00);
         assert_eq!(n3, 1.0);
     }
+
+    #[test]
+    pub fn test_dec_fixed_point_value() {
+        // 100 belly, no thousandths.
+        assert_eq!(DecFixedPoint::from_bits(100).value(), 100.0);
+        // 50 belly, 250 thousandths -> 50.25.
+        assert_eq!(DecFixedPoint::from_bits((250 << 16) | 50).value(), 50.25);
+    }
+
+    #[test]
+    pub fn test_dec_fixed_point_try_from_f32() {
+        let belly = DecFixedPoint::try_from(50.25f32).unwrap();
+        assert_eq!(belly.to_bits(), (250 << 16) | 50);
+        assert_eq!(belly.value(), 50.25);
+
+        assert!(DecFixedPoint::try_from(-1.0f32).is_err());
+        assert!(DecFixedPoint::try_from(100000.0f32).is_err());
+    }
+
+    #[test]
+    pub fn test_dec_fixed_point_display() {
+        assert_eq!(format!("{}", DecFixedPoint::from_bits((250 << 16) | 50)), "50.25");
+    }
+
+    #[test]
+    pub fn test_decimal_fixed_raw_and_percent() {
+        let value = DecimalFixed::from_raw(0x64);
+        assert_eq!(value.to_raw(), 100);
+        assert_eq!(value.to_percent(), 100);
+        assert_eq!(value.value(), 1.0);
+        assert_eq!(DecimalFixed::from_percent(150).value(), 1.5);
+    }
+
+    #[test]
+    pub fn test_decimal_fixed_i24f8_conversions() {
+        let value = DecimalFixed::from(I24F8::from_num(1.5));
+        assert_eq!(value.to_percent(), 150);
+        assert_eq!(I24F8::from(DecimalFixed::from_raw(150)), 1.5);
+    }
+
+    #[test]
+    pub fn test_decimal_fixed_display() {
+        assert_eq!(format!("{}", DecimalFixed::from_raw(100)), "1.00");
+        assert_eq!(format!("{}", DecimalFixed::from_raw(5)), "0.05");
+        assert_eq!(format!("{}", DecimalFixed::from_raw(-150)), "-1.50");
+    }
 }
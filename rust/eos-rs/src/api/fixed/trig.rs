@@ -0,0 +1,206 @@
+//! Pure-integer trigonometry and square root for [`I24F8`], since the [`fixed`](fixed) crate
+//! deliberately ships none: its README states up front that it does not provide general
+//! analytic functions like `sin`, `cos`, or `sqrt`. The game needs these constantly (movement,
+//! projectile angles, distance checks), so this module fills the gap without touching the FPU.
+//!
+//! [`sin`]/[`cos`] use rotation-mode CORDIC, and [`atan2`] uses vectoring-mode CORDIC, both
+//! against a 16-entry `atan(2^-i)` table; [`sqrt`] uses Newton-Raphson on the raw fixed bits.
+//! With `N` = 16 iterations, the rotation/vectoring error is bounded by roughly 1 LSB of
+//! [`I24F8`] (about 1/256), which swamps the iteration error itself: past `i` = 9 the table
+//! entries round to zero at this format's 8 fractional bits, so those later steps are no-ops by
+//! construction, not a bug.
+
+use super::I24F8;
+
+/// Number of CORDIC iterations. Kept at the textbook value even though, at [`I24F8`]'s 8
+/// fractional bits, [`ATAN_TABLE`] entries from `i` = 9 onward round to zero and stop
+/// contributing; a narrower fixed-point format would make use of more of them.
+const N: usize = 16;
+
+/// `atan(2^-i)` for `i` in `0..N`, as raw [`I24F8`] bits (i.e. the angle in radians times 256,
+/// rounded to the nearest integer).
+const ATAN_TABLE: [i32; N] = [201, 119, 63, 32, 16, 8, 4, 2, 1, 0, 0, 0, 0, 0, 0, 0];
+
+/// The CORDIC gain `K = prod(1/sqrt(1 + 2^-2i))` for `i` in `0..N`, as raw [`I24F8`] bits.
+/// Seeding rotation-mode CORDIC's `x` with this exactly cancels the gain the iterations
+/// introduce, so [`cos`]/[`sin`] come out unscaled.
+const CORDIC_GAIN: i32 = 155;
+
+/// Raw-bits representation of pi, half pi and two pi, used for angle range reduction.
+const PI: i32 = 804;
+const HALF_PI: i32 = 402;
+const TWO_PI: i32 = 1608;
+
+/// Reduces `angle_bits` into `(-HALF_PI, HALF_PI]`, returning the reduced angle and whether the
+/// caller needs to negate the CORDIC `cos` result to compensate (true whenever the original
+/// angle fell in the second or third quadrant; `sin` never needs this correction, since
+/// `sin(pi - a) == sin(a)` and `sin(-pi - a) == sin(a)`).
+fn reduce_to_first_quadrant(angle_bits: i32) -> (i32, bool) {
+    let mut a = angle_bits % TWO_PI;
+    if a > PI {
+        a -= TWO_PI;
+    } else if a <= -PI {
+        a += TWO_PI;
+    }
+
+    if a > HALF_PI {
+        (PI - a, true)
+    } else if a < -HALF_PI {
+        (-PI - a, true)
+    } else {
+        (a, false)
+    }
+}
+
+/// Rotation-mode CORDIC: rotates `(K, 0)` by `z0` (already reduced to `(-HALF_PI, HALF_PI]`),
+/// returning `(cos(z0), sin(z0))` as raw bits.
+fn cordic_rotate(z0: i32) -> (i32, i32) {
+    let (mut x, mut y, mut z) = (CORDIC_GAIN, 0i32, z0);
+    for (i, &atan_i) in ATAN_TABLE.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+        if z >= 0 {
+            x -= dx;
+            y += dy;
+            z -= atan_i;
+        } else {
+            x += dx;
+            y -= dy;
+            z += atan_i;
+        }
+    }
+    (x, y)
+}
+
+/// Vectoring-mode CORDIC: rotates `(x0, y0)` until `y` converges to zero, accumulating the
+/// applied rotation into `z0`. Requires `x0 >= 0` for convergence; [`atan2`] handles the
+/// reflection needed for negative `x`.
+fn cordic_vector(mut x: i32, mut y: i32, mut z: i32) -> i32 {
+    for (i, &atan_i) in ATAN_TABLE.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+        if y >= 0 {
+            x += dx;
+            y -= dy;
+            z += atan_i;
+        } else {
+            x -= dx;
+            y += dy;
+            z -= atan_i;
+        }
+    }
+    z
+}
+
+/// Returns `(sin(angle), cos(angle))`, computed together since both fall out of the same
+/// rotation-mode CORDIC pass.
+pub fn sin_cos(angle: I24F8) -> (I24F8, I24F8) {
+    let (reduced, negate_cos) = reduce_to_first_quadrant(angle.to_bits());
+    let (cos_bits, sin_bits) = cordic_rotate(reduced);
+    let cos_bits = if negate_cos { -cos_bits } else { cos_bits };
+    (I24F8::from_bits(sin_bits), I24F8::from_bits(cos_bits))
+}
+
+/// The sine of `angle` (in radians), accurate to roughly 1 LSB of [`I24F8`].
+pub fn sin(angle: I24F8) -> I24F8 {
+    sin_cos(angle).0
+}
+
+/// The cosine of `angle` (in radians), accurate to roughly 1 LSB of [`I24F8`].
+pub fn cos(angle: I24F8) -> I24F8 {
+    sin_cos(angle).1
+}
+
+/// The four-quadrant arctangent of `y / x`, in radians, accurate to roughly 1 LSB of [`I24F8`].
+/// Returns `0` for `atan2(0, 0)`, matching `f32`/`f64`'s convention.
+pub fn atan2(y: I24F8, x: I24F8) -> I24F8 {
+    let (mut x_bits, mut y_bits) = (x.to_bits(), y.to_bits());
+    if x_bits == 0 && y_bits == 0 {
+        return I24F8::from_bits(0);
+    }
+
+    let mut z0 = 0;
+    if x_bits < 0 {
+        z0 = if y_bits >= 0 { PI } else { -PI };
+        x_bits = -x_bits;
+        y_bits = -y_bits;
+    }
+
+    I24F8::from_bits(cordic_vector(x_bits, y_bits, z0))
+}
+
+/// The integer square root of `n`, via Newton-Raphson seeded from a bit-length estimate,
+/// iterating `x = (x + n / x) >> 1` until it stops decreasing.
+fn isqrt(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    let mut x = 1u64 << ((64 - n.leading_zeros() as u64 + 1) / 2);
+    loop {
+        let next = (x + n / x) >> 1;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// The square root of `value`, accurate to roughly 1 LSB of [`I24F8`]. Returns `0` for negative
+/// (or zero) input rather than panicking, since [`I24F8`] has no dedicated "not a number".
+pub fn sqrt(value: I24F8) -> I24F8 {
+    let bits = value.to_bits();
+    if bits <= 0 {
+        return I24F8::from_bits(0);
+    }
+    // sqrt(bits / 256) == sqrt(bits * 256) / 256, so scaling up by the fractional factor before
+    // taking the integer square root lands the result directly in I24F8's raw bits.
+    I24F8::from_bits(isqrt((bits as u64) << 8) as i32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Asserts that two [`I24F8`] values are within `1` raw bit (i.e. roughly 1 LSB) of each
+    /// other, the error bound this module documents for all of its functions.
+    fn assert_approx_eq(a: I24F8, b: I24F8) {
+        assert!(
+            (a.to_bits() - b.to_bits()).abs() <= 1,
+            "{a:?} (bits {}) not within 1 LSB of {b:?} (bits {})",
+            a.to_bits(),
+            b.to_bits()
+        );
+    }
+
+    #[test]
+    fn test_sin_cos_axes() {
+        assert_approx_eq(sin(I24F8::from_num(0)), I24F8::from_num(0));
+        assert_approx_eq(cos(I24F8::from_num(0)), I24F8::from_num(1));
+
+        let half_pi = I24F8::from_bits(HALF_PI);
+        assert_approx_eq(sin(half_pi), I24F8::from_num(1));
+        assert_approx_eq(cos(half_pi), I24F8::from_num(0));
+
+        let pi = I24F8::from_bits(PI);
+        assert_approx_eq(sin(pi), I24F8::from_num(0));
+        assert_approx_eq(cos(pi), I24F8::from_num(-1));
+    }
+
+    #[test]
+    fn test_atan2_quadrants() {
+        let one = I24F8::from_num(1);
+        let zero = I24F8::from_num(0);
+        let neg_one = I24F8::from_num(-1);
+
+        assert_approx_eq(atan2(zero, one), I24F8::from_num(0));
+        assert_approx_eq(atan2(one, zero), I24F8::from_bits(HALF_PI));
+        assert_approx_eq(atan2(zero, neg_one), I24F8::from_bits(PI));
+        assert_approx_eq(atan2(neg_one, zero), I24F8::from_bits(-HALF_PI));
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(sqrt(I24F8::from_num(0)), I24F8::from_num(0));
+        assert_approx_eq(sqrt(I24F8::from_num(4)), I24F8::from_num(2));
+        assert_approx_eq(sqrt(I24F8::from_num(2)), I24F8::from_num(1.4142135));
+        assert_eq!(sqrt(I24F8::from_num(-1)), I24F8::from_num(0));
+    }
+}
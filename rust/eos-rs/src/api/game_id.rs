@@ -0,0 +1,46 @@
+//! A shared trait for this crate's "opaque numeric ID with a known valid range" newtypes (see
+//! e.g. [`crate::api::iq::IqGroupId`]), modeled on the index-plus-lookup-table shape Stable MIR
+//! uses for `DefId` and similar: the ID itself is a cheap `Copy` index, and metadata is fetched
+//! on demand through the type's own inherent methods.
+
+/// Implemented by an "opaque game data ID" newtype that has a known, dense valid range
+/// (`0..Self::COUNT`).
+///
+/// This gives generic code (serializers, editors, enumeration helpers) a single bound to write
+/// against, instead of every ID module re-declaring the same `get`/`try_get`/`iter` shape by
+/// hand. [`Self::try_from_raw`] and [`Self::iter`] are provided in terms of [`Self::COUNT`] and
+/// [`Self::from_raw`], so implementing this trait only requires those two.
+///
+/// # Note
+/// Not every ID newtype in this crate implements this yet. Some, like
+/// [`ItemId`](crate::api::items::ItemId), are validated at runtime by a game function
+/// ([`ItemId::is_valid`](crate::api::items::ItemId::is_valid)) rather than a known dense count,
+/// so a correct `COUNT` for them isn't available without confirming it against the game's data
+/// tables first; migrating them to this trait is left for a follow-up once that's done.
+pub trait GameId: Copy {
+    /// The number of valid IDs of this kind; every `raw` value in `0..Self::COUNT` is valid.
+    const COUNT: u32;
+
+    /// Returns the raw numeric value of this ID.
+    fn raw(&self) -> u32;
+
+    /// Constructs an ID from a raw numeric value.
+    ///
+    /// # Safety
+    /// The caller must make sure `id` is valid (i.e. `id < Self::COUNT`), otherwise this is UB.
+    unsafe fn from_raw(id: u32) -> Self;
+
+    /// Constructs an ID from a raw numeric value, or returns `None` if it's out of range.
+    fn try_from_raw(id: u32) -> Option<Self> {
+        if id < Self::COUNT {
+            Some(unsafe { Self::from_raw(id) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over every valid ID of this kind, in order.
+    fn iter() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).map(|id| unsafe { Self::from_raw(id) })
+    }
+}
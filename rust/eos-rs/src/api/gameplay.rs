@@ -5,13 +5,35 @@ use crate::api::enums::{MissionGenerationResult, MissionType};
 use crate::api::iq::IqSkillId;
 use crate::api::items::ItemId;
 use crate::api::monsters::MonsterSpeciesId;
+use crate::api::random::rand_u32;
 use crate::ctypes::c_int;
 use crate::ffi;
 use crate::util::OwnedSlice;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use core::marker::PhantomData;
 use core::ptr;
 
+/// An error returned by this module's FFI accessors, distinguishing "the slot is genuinely empty
+/// right now" from a caller-supplied argument that's out of range, or an internal result this
+/// binding doesn't recognize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameplayError {
+    /// The underlying FFI call returned a null pointer. This doesn't necessarily mean anything
+    /// went wrong -- e.g. [`get_partner_data`] returns this if no partner has been recruited yet.
+    NullData,
+    /// A caller-supplied index was outside the range this accessor actually supports.
+    IndexOutOfRange { got: u32, max: u32 },
+    /// [`ffi::GenerateMission`] returned a [`ffi::mission_generation_result`] this binding doesn't
+    /// recognize.
+    InvalidMissionResult,
+    /// [`ffi::SetSentryDutyGamePoints`] returned a value that doesn't map to a known
+    /// [`SentryGameRank`].
+    InvalidSentryGameRank,
+}
+
 /// Describes an active team setup
+#[derive(Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum TeamSetup {
     HeroOnly,
@@ -102,9 +124,110 @@ pub fn note_load_base() -> i32 {
     unsafe { ffi::NoteLoadBase() }
 }
 
+/// A gameplay milestone a listener registered via [`EventHook::register`] can react to, instead of
+/// polling [`AdventureLog`]'s `get_number_*` counters to notice when one of them changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameplayEvent {
+    DungeonCleared,
+    Evolution,
+    EggHatched,
+    MonsterJoined(MonsterSpeciesId),
+    Fainted,
+    BigTreasureWin,
+}
+
+type EventListener = Box<dyn FnMut(&GameplayEvent)>;
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// events are only ever emitted from the main game loop.
+static mut EVENT_LISTENERS: Vec<EventListener> = Vec::new();
+/// Listeners registered mid-emit (e.g. by another listener) are staged here and only spliced into
+/// [`EVENT_LISTENERS`] once the current emission finishes, mirroring
+/// [`crate::api::ground_process`]'s `PENDING` buffer, so registering doesn't invalidate the
+/// in-progress index-based iteration over it.
+static mut PENDING_EVENT_LISTENERS: Vec<EventListener> = Vec::new();
+
+/// A global registry of [`GameplayEvent`] listeners, so mod code can react to adventure-log
+/// milestones in one place instead of patching every `increment_*`/`set_*` call site that reports
+/// one.
+///
+/// There's no recovering from a panicking listener in this crate -- the `#[panic_handler]` just
+/// hangs the game, see its own doc comment -- so "panic-safe" here is about the registry itself:
+/// a listener that re-entrantly registers another one, or emits another event, while it's running
+/// never corrupts [`EVENT_LISTENERS`]/[`PENDING_EVENT_LISTENERS`]'s bookkeeping, even though an
+/// actual panic still takes down the whole game same as anywhere else.
+pub struct EventHook;
+
+impl EventHook {
+    /// Registers `listener` to be called with every [`GameplayEvent`] emitted from now on.
+    ///
+    /// If called from inside a listener that's itself currently running as part of an in-progress
+    /// [`emit`], `listener` is staged and only starts receiving events from the next emission.
+    pub fn register(listener: impl FnMut(&GameplayEvent) + 'static) {
+        // SAFETY: single-threaded; see `PENDING_EVENT_LISTENERS`.
+        unsafe {
+            PENDING_EVENT_LISTENERS.push(Box::new(listener));
+        }
+    }
+
+    /// Deregisters every currently registered listener, including ones staged via a re-entrant
+    /// [`Self::register`] call that haven't started receiving events yet.
+    pub fn clear() {
+        // SAFETY: single-threaded; see `EVENT_LISTENERS`/`PENDING_EVENT_LISTENERS`.
+        unsafe {
+            EVENT_LISTENERS.clear();
+            PENDING_EVENT_LISTENERS.clear();
+        }
+    }
+}
+
+/// Calls every registered listener with `event`, in registration order.
+///
+/// Iterates by index rather than by iterator so a listener calling [`EventHook::register`]
+/// mid-pass (or re-entrantly calling [`emit`] itself) doesn't invalidate the in-progress loop --
+/// see [`PENDING_EVENT_LISTENERS`](self).
+fn emit(event: GameplayEvent) {
+    // SAFETY: single-threaded; see `EVENT_LISTENERS`.
+    unsafe {
+        for index in 0..EVENT_LISTENERS.len() {
+            (EVENT_LISTENERS[index])(&event);
+        }
+        EVENT_LISTENERS.append(&mut PENDING_EVENT_LISTENERS);
+    }
+}
+
 /// Adventure log helper
 pub struct AdventureLog(PhantomData<()>);
 
+/// A plain, owned snapshot of every field [`AdventureLog`] exposes a getter for, produced by
+/// [`AdventureLog::snapshot`] and applied with [`AdventureLog::restore`].
+///
+/// Derives `serde::Serialize`/`Deserialize` behind the `serde` feature (gated the same way
+/// `rune`/`eu` are, see [`crate::api::dungeon_mode::dungeon_history`]) so tooling can export a
+/// save's adventure log or diff two of these.
+///
+/// Not every field here has a direct setter in this module -- [`AdventureLog::restore`]'s own doc
+/// comment lists exactly which ones it can actually apply.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdventureLogSnapshot {
+    pub dungeons_cleared: u32,
+    pub friend_rescues: u32,
+    pub evolutions: u32,
+    pub eggs_hatched: u32,
+    pub monsters_joined: u32,
+    pub monsters_battled: u32,
+    pub items_acquired: u32,
+    pub big_treasure_wins: u32,
+    pub recycled: u32,
+    pub sky_gifts_sent: u32,
+    pub fainted: u32,
+    pub victories_on_one_floor: u32,
+    pub completed_entries: Vec<u32>,
+    pub sentry_duty_points: [u32; 5],
+}
+
 impl AdventureLog {
     /// Returns an internal reference to the adventure log. Note that this isn't a reference
     /// to the actual struct in memory (yet).
@@ -139,77 +262,192 @@ impl AdventureLog {
     }
 
     /// Checks if one adventure log entry is completed.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn is_entry_completed(&self, entry_id: u32) -> bool {
         unsafe { ffi::GetAdventureLogCompleted(entry_id) > 0 }
     }
 
+    /// Checks if one adventure log entry is completed.
+    #[cfg(feature = "mock-ffi")]
+    pub fn is_entry_completed(&self, entry_id: u32) -> bool {
+        mock::with_state(|state| state.entries_completed.contains(&entry_id))
+    }
+
     /// Marks one of the adventure log entry as completed.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn mark_entry_completed(&mut self, entry_id: u32) {
         unsafe { ffi::SetAdventureLogCompleted(entry_id) }
     }
 
+    /// Marks one of the adventure log entry as completed.
+    #[cfg(feature = "mock-ffi")]
+    pub fn mark_entry_completed(&mut self, entry_id: u32) {
+        mock::with_state(|state| {
+            if !state.entries_completed.contains(&entry_id) {
+                state.entries_completed.push(entry_id);
+            }
+        })
+    }
+
     /// Checks if none of of the adventure log entry is completed.
     pub fn is_empty(&self) -> bool {
         unsafe { ffi::IsAdventureLogNotEmpty() == 0 }
     }
 
     /// Gets the number of dungeons cleared.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn get_number_dungeons_cleared(&self) -> u32 {
         unsafe { ffi::GetNbDungeonsCleared() }
     }
 
+    /// Gets the number of dungeons cleared.
+    #[cfg(feature = "mock-ffi")]
+    pub fn get_number_dungeons_cleared(&self) -> u32 {
+        mock::with_state(|state| state.dungeons_cleared)
+    }
+
     /// Increments by 1 the number of dungeons cleared.
+    ///
+    /// Emits [`GameplayEvent::DungeonCleared`] to any [`EventHook`] listeners.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn increment_number_dungeons_cleared() {
         unsafe { ffi::IncrementNbDungeonsCleared() };
+        emit(GameplayEvent::DungeonCleared);
+    }
+
+    /// Increments by 1 the number of dungeons cleared.
+    ///
+    /// Emits [`GameplayEvent::DungeonCleared`] to any [`EventHook`] listeners.
+    #[cfg(feature = "mock-ffi")]
+    pub fn increment_number_dungeons_cleared() {
+        mock::with_state(|state| state.dungeons_cleared += 1);
+        emit(GameplayEvent::DungeonCleared);
     }
 
     /// Gets the number of successful friend rescues.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn get_number_friend_rescues(&self) -> u32 {
         unsafe { ffi::GetNbFriendRescues() }
     }
 
+    /// Gets the number of successful friend rescues.
+    #[cfg(feature = "mock-ffi")]
+    pub fn get_number_friend_rescues(&self) -> u32 {
+        mock::with_state(|state| state.friend_rescues)
+    }
+
     /// Increments by 1 the number of successful friend rescues.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn increment_number_friend_rescues(&mut self) {
         unsafe { ffi::IncrementNbFriendRescues() };
     }
 
+    /// Increments by 1 the number of successful friend rescues.
+    #[cfg(feature = "mock-ffi")]
+    pub fn increment_number_friend_rescues(&mut self) {
+        mock::with_state(|state| state.friend_rescues += 1);
+    }
+
     /// Gets the number of evolutions.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn get_number_evolutions(&self) -> u32 {
         unsafe { ffi::GetNbEvolutions() }
     }
 
+    /// Gets the number of evolutions.
+    #[cfg(feature = "mock-ffi")]
+    pub fn get_number_evolutions(&self) -> u32 {
+        mock::with_state(|state| state.evolutions)
+    }
+
     /// Increments by 1 the number of evolutions.
+    ///
+    /// Emits [`GameplayEvent::Evolution`] to any [`EventHook`] listeners.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn increment_number_evolutions(&mut self) {
         unsafe { ffi::IncrementNbEvolutions() };
+        emit(GameplayEvent::Evolution);
+    }
+
+    /// Increments by 1 the number of evolutions.
+    ///
+    /// Emits [`GameplayEvent::Evolution`] to any [`EventHook`] listeners.
+    #[cfg(feature = "mock-ffi")]
+    pub fn increment_number_evolutions(&mut self) {
+        mock::with_state(|state| state.evolutions += 1);
+        emit(GameplayEvent::Evolution);
     }
 
     /// Leftover from Time & Darkness. Does not do anything.
     ///
     /// Calls to this matches the ones for incrementing the number of successful steals in Time & Darkness.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn increment_number_steals(&mut self) {
         unsafe { ffi::IncrementNbSteals() };
     }
 
+    /// Leftover from Time & Darkness. Does not do anything.
+    ///
+    /// Calls to this matches the ones for incrementing the number of successful steals in Time & Darkness.
+    #[cfg(feature = "mock-ffi")]
+    pub fn increment_number_steals(&mut self) {
+        mock::with_state(|state| state.steals += 1);
+    }
+
     /// Gets the number of eggs hatched.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn get_number_eggs_hatched(&self) -> u32 {
         unsafe { ffi::GetNbEggsHatched() }
     }
 
+    /// Gets the number of eggs hatched.
+    #[cfg(feature = "mock-ffi")]
+    pub fn get_number_eggs_hatched(&self) -> u32 {
+        mock::with_state(|state| state.eggs_hatched)
+    }
+
     /// Increments by 1 the number of eggs hatched.
+    ///
+    /// Emits [`GameplayEvent::EggHatched`] to any [`EventHook`] listeners.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn increment_number_eggs_hatched(&mut self) {
         unsafe { ffi::IncrementNbEggsHatched() };
+        emit(GameplayEvent::EggHatched);
+    }
+
+    /// Increments by 1 the number of eggs hatched.
+    ///
+    /// Emits [`GameplayEvent::EggHatched`] to any [`EventHook`] listeners.
+    #[cfg(feature = "mock-ffi")]
+    pub fn increment_number_eggs_hatched(&mut self) {
+        mock::with_state(|state| state.eggs_hatched += 1);
+        emit(GameplayEvent::EggHatched);
     }
 
     /// Gets the number of different monsters that joined.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn get_number_monsters_joined(&self) -> u32 {
         unsafe { ffi::GetNbPokemonJoined() }
     }
 
+    /// Gets the number of different monsters that joined.
+    #[cfg(feature = "mock-ffi")]
+    pub fn get_number_monsters_joined(&self) -> u32 {
+        mock::with_state(|state| state.number_monsters_joined)
+    }
+
     /// Gets the number of different moves learned.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn get_number_moves_learned(&self) -> u32 {
         unsafe { ffi::GetNbMovesLearned() }
     }
 
+    /// Gets the number of different moves learned.
+    #[cfg(feature = "mock-ffi")]
+    pub fn get_number_moves_learned(&self) -> u32 {
+        mock::with_state(|state| state.number_moves_learned)
+    }
+
     /// Gets the record of victories on one floor.
     pub fn get_victories_on_one_floor(&self) -> u32 {
         unsafe { ffi::GetVictoriesOnOneFloor() }
@@ -221,28 +459,82 @@ impl AdventureLog {
     }
 
     /// Gets the number of different monsters that battled against you.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn get_number_monsters_battled(&self) -> u32 {
         unsafe { ffi::GetNbPokemonBattled() }
     }
 
+    /// Gets the number of different monsters that battled against you.
+    #[cfg(feature = "mock-ffi")]
+    pub fn get_number_monsters_battled(&self) -> u32 {
+        mock::with_state(|state| state.number_monsters_battled)
+    }
+
     /// Marks one monster as battled.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn set_monster_battled(&mut self, monster_id: MonsterSpeciesId) {
         unsafe { ffi::SetPokemonBattled(monster_id) };
     }
 
+    /// Marks one monster as battled.
+    ///
+    /// The count returned by [`Self::get_number_monsters_battled`] isn't updated until
+    /// [`Self::compute_special_counters`] is called, matching the real bitfield-then-recompute
+    /// behavior.
+    #[cfg(feature = "mock-ffi")]
+    pub fn set_monster_battled(&mut self, monster_id: MonsterSpeciesId) {
+        mock::with_state(|state| state.monsters_battled.insert(monster_id.id()));
+    }
+
     /// Marks one monster as joined.
+    ///
+    /// Emits [`GameplayEvent::MonsterJoined`] to any [`EventHook`] listeners.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn set_monster_joined(&mut self, monster_id: MonsterSpeciesId) {
         unsafe { ffi::SetPokemonJoined(monster_id) };
+        emit(GameplayEvent::MonsterJoined(monster_id));
+    }
+
+    /// Marks one monster as joined.
+    ///
+    /// Emits [`GameplayEvent::MonsterJoined`] to any [`EventHook`] listeners. The count returned
+    /// by [`Self::get_number_monsters_joined`] isn't updated until
+    /// [`Self::compute_special_counters`] is called, matching the real bitfield-then-recompute
+    /// behavior.
+    #[cfg(feature = "mock-ffi")]
+    pub fn set_monster_joined(&mut self, monster_id: MonsterSpeciesId) {
+        mock::with_state(|state| state.monsters_joined.insert(monster_id.id()));
+        emit(GameplayEvent::MonsterJoined(monster_id));
     }
 
     /// Gets the number of big treasure wins.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn get_number_big_treasure_wins(&self) -> u32 {
         unsafe { ffi::GetNbBigTreasureWins() }
     }
 
+    /// Gets the number of big treasure wins.
+    #[cfg(feature = "mock-ffi")]
+    pub fn get_number_big_treasure_wins(&self) -> u32 {
+        mock::with_state(|state| state.big_treasure_wins)
+    }
+
     /// Increments by 1 the number of big treasure wins.
+    ///
+    /// Emits [`GameplayEvent::BigTreasureWin`] to any [`EventHook`] listeners.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn increment_number_of_big_treasure_wins() {
         unsafe { ffi::IncrementNbBigTreasureWins() };
+        emit(GameplayEvent::BigTreasureWin);
+    }
+
+    /// Increments by 1 the number of big treasure wins.
+    ///
+    /// Emits [`GameplayEvent::BigTreasureWin`] to any [`EventHook`] listeners.
+    #[cfg(feature = "mock-ffi")]
+    pub fn increment_number_of_big_treasure_wins() {
+        mock::with_state(|state| state.big_treasure_wins += 1);
+        emit(GameplayEvent::BigTreasureWin);
     }
 
     /// Sets the number of big treasure wins.
@@ -261,54 +553,129 @@ impl AdventureLog {
     }
 
     /// Gets the number of Sky Gifts sent.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn get_number_sky_gifts_sent(&self) -> u32 {
         unsafe { ffi::GetNbSkyGiftsSent() }
     }
 
+    /// Gets the number of Sky Gifts sent.
+    #[cfg(feature = "mock-ffi")]
+    pub fn get_number_sky_gifts_sent(&self) -> u32 {
+        mock::with_state(|state| state.sky_gifts_sent)
+    }
+
     /// Increments by 1 the number of sky gifts sent.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn increment_number_of_gifts_sent() {
         unsafe { ffi::IncrementNbSkyGiftsSent() };
     }
 
+    /// Increments by 1 the number of sky gifts sent.
+    #[cfg(feature = "mock-ffi")]
+    pub fn increment_number_of_gifts_sent() {
+        mock::with_state(|state| state.sky_gifts_sent += 1);
+    }
+
     /// Sets the number of Sky Gifts sent.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn set_number_sky_gifts_sent(&mut self, number: u32) {
         unsafe { ffi::SetNbSkyGiftsSent(number) };
     }
 
+    /// Sets the number of Sky Gifts sent.
+    #[cfg(feature = "mock-ffi")]
+    pub fn set_number_sky_gifts_sent(&mut self, number: u32) {
+        mock::with_state(|state| state.sky_gifts_sent = number);
+    }
+
     /// Computes the counters from the bit fields in the adventure log, as they are not updated
     /// automatically when bit fields are altered.
     ///
     /// Affects [`Self::get_number_monsters_joined`], [`Self::get_number_moves_learned`],
     /// [`Self::get_number_monsters_battled`] and [`Self::get_number_items_acquired`].
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn compute_special_counters(&mut self) {
         unsafe { ffi::ComputeSpecialCounters() };
     }
 
+    /// Computes the counters from the bit fields in the adventure log, as they are not updated
+    /// automatically when bit fields are altered.
+    ///
+    /// Affects [`Self::get_number_monsters_joined`], [`Self::get_number_moves_learned`],
+    /// [`Self::get_number_monsters_battled`] and [`Self::get_number_items_acquired`]. Moves learned
+    /// isn't tracked by any simulated setter in this module, so it stays 0.
+    #[cfg(feature = "mock-ffi")]
+    pub fn compute_special_counters(&mut self) {
+        mock::with_state(|state| {
+            state.number_monsters_joined = state.monsters_joined.len() as u32;
+            state.number_monsters_battled = state.monsters_battled.len() as u32;
+            state.number_items_acquired = state.items_acquired.len() as u32;
+        });
+    }
+
     /// Marks a specified special monster as recruited in the adventure log.
     pub fn set_special_monster_recruited(&mut self, monster_id: MonsterSpeciesId) {
         unsafe { ffi::RecruitSpecialPokemonLog(monster_id) };
     }
 
     /// Gets the number of times the player fainted.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn get_number_fainted(&self) -> u32 {
         unsafe { ffi::GetNbFainted() }
     }
 
+    /// Gets the number of times the player fainted.
+    #[cfg(feature = "mock-ffi")]
+    pub fn get_number_fainted(&self) -> u32 {
+        mock::with_state(|state| state.fainted)
+    }
+
     /// Increments by 1 the number of times the player fainted.
+    ///
+    /// Emits [`GameplayEvent::Fainted`] to any [`EventHook`] listeners.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn increment_number_of_fainted() {
         unsafe { ffi::IncrementNbFainted() };
+        emit(GameplayEvent::Fainted);
+    }
+
+    /// Increments by 1 the number of times the player fainted.
+    ///
+    /// Emits [`GameplayEvent::Fainted`] to any [`EventHook`] listeners.
+    #[cfg(feature = "mock-ffi")]
+    pub fn increment_number_of_fainted() {
+        mock::with_state(|state| state.fainted += 1);
+        emit(GameplayEvent::Fainted);
     }
 
     /// Gets the number of items acquired.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn get_number_items_acquired(&self) -> u32 {
         unsafe { ffi::GetNbItemAcquired() }
     }
 
+    /// Gets the number of items acquired.
+    #[cfg(feature = "mock-ffi")]
+    pub fn get_number_items_acquired(&self) -> u32 {
+        mock::with_state(|state| state.number_items_acquired)
+    }
+
     /// Marks one specific item as acquired.
+    #[cfg(not(feature = "mock-ffi"))]
     pub fn set_item_acquired(&mut self, item_id: ItemId) {
         unsafe { ffi::SetItemAcquired(item_id) };
     }
 
+    /// Marks one specific item as acquired.
+    ///
+    /// The count returned by [`Self::get_number_items_acquired`] isn't updated until
+    /// [`Self::compute_special_counters`] is called, matching the real bitfield-then-recompute
+    /// behavior.
+    #[cfg(feature = "mock-ffi")]
+    pub fn set_item_acquired(&mut self, item_id: ItemId) {
+        mock::with_state(|state| state.items_acquired.insert(item_id.id()));
+    }
+
     /// Sets a challenge letter as cleared.
     pub fn set_challenge_letter_cleared(&mut self, challenge_letter: u32) {
         unsafe { ffi::SetChallengeLetterCleared(challenge_letter) };
@@ -320,10 +687,107 @@ impl AdventureLog {
     }
 
     /// Sets the points for the associated rank in the footprints minigame.
-    pub fn set_sentry_duty_game_points(&mut self, points: u32) -> Option<SentryGameRank> {
+    ///
+    /// Returns [`GameplayError::InvalidSentryGameRank`] if the game's result doesn't map to a
+    /// known [`SentryGameRank`].
+    pub fn set_sentry_duty_game_points(
+        &mut self,
+        points: u32,
+    ) -> Result<SentryGameRank, GameplayError> {
         unsafe { ffi::SetSentryDutyGamePoints(points) }
             .try_into()
-            .ok()
+            .map_err(|_| GameplayError::InvalidSentryGameRank)
+    }
+
+    /// Reads every field this type exposes a getter for into an owned [`AdventureLogSnapshot`],
+    /// so it can be exported, diffed against another snapshot, or handed to [`Self::restore`]
+    /// later.
+    ///
+    /// `num_completable_entries` bounds the scan over [`Self::is_entry_completed`] used to build
+    /// [`AdventureLogSnapshot::completed_entries`] -- this crate doesn't have a verified constant
+    /// for how many entries the real adventure log completion bitfield has, so the caller supplies
+    /// it based on whatever entry-ID range their own scripts/patches use.
+    pub fn snapshot(&mut self, num_completable_entries: u32) -> AdventureLogSnapshot {
+        AdventureLogSnapshot {
+            dungeons_cleared: self.get_number_dungeons_cleared(),
+            friend_rescues: self.get_number_friend_rescues(),
+            evolutions: self.get_number_evolutions(),
+            eggs_hatched: self.get_number_eggs_hatched(),
+            monsters_joined: self.get_number_monsters_joined(),
+            monsters_battled: self.get_number_monsters_battled(),
+            items_acquired: self.get_number_items_acquired(),
+            big_treasure_wins: self.get_number_big_treasure_wins(),
+            recycled: self.get_number_recycled(),
+            sky_gifts_sent: self.get_number_sky_gifts_sent(),
+            fainted: self.get_number_fainted(),
+            victories_on_one_floor: self.get_victories_on_one_floor(),
+            completed_entries: (0..num_completable_entries)
+                .filter(|&id| self.is_entry_completed(id))
+                .collect(),
+            sentry_duty_points: [
+                SentryGameRank::First,
+                SentryGameRank::Second,
+                SentryGameRank::Third,
+                SentryGameRank::Fourth,
+                SentryGameRank::Fifth,
+            ]
+            .map(|rank| self.get_sentry_duty_game_points(rank)),
+        }
+    }
+
+    /// Writes `snapshot` back through the existing setters, then calls
+    /// [`Self::compute_special_counters`] so the derived totals it recomputes stay consistent
+    /// with whatever this call actually changed.
+    ///
+    /// Only the fields this module exposes a real setter for are applied as a direct write:
+    /// [`AdventureLogSnapshot::big_treasure_wins`], `recycled`, `sky_gifts_sent` and
+    /// `victories_on_one_floor`. [`AdventureLogSnapshot::completed_entries`] is applied by calling
+    /// [`Self::mark_entry_completed`] for each listed ID -- there's no "unmark" call to remove an
+    /// entry not in the list, matching the real game, which never exposes one either.
+    ///
+    /// `dungeons_cleared`, `friend_rescues`, `evolutions`, `eggs_hatched` and `fainted` only have
+    /// increment-by-one setters in this module, so restoring them calls the matching
+    /// `increment_number_*` just enough times to make up the gap to the snapshot's value. If the
+    /// current count is already higher than the snapshot's (can't happen through normal play, but
+    /// a hand-crafted snapshot could ask for it), the existing value is left alone rather than
+    /// decremented, since there's no decrementing call to do that with.
+    ///
+    /// `monsters_joined`, `monsters_battled`, `items_acquired` and `sentry_duty_points` are **not**
+    /// restored: this module's setters for the underlying bitfields ([`Self::set_monster_joined`],
+    /// [`Self::set_monster_battled`], [`Self::set_item_acquired`]) take a specific monster/item ID
+    /// rather than a raw count, and [`Self::set_sentry_duty_game_points`] submits a score rather
+    /// than forcing a specific rank's value -- a snapshot's plain counts can't be turned back into
+    /// the IDs or submission history that produced them, so these four fields round-trip through
+    /// [`Self::snapshot`] for diffing but are otherwise read-only here.
+    pub fn restore(&mut self, snapshot: &AdventureLogSnapshot) {
+        let dungeons_cleared = self.get_number_dungeons_cleared();
+        for _ in dungeons_cleared..snapshot.dungeons_cleared {
+            Self::increment_number_dungeons_cleared();
+        }
+        let friend_rescues = self.get_number_friend_rescues();
+        for _ in friend_rescues..snapshot.friend_rescues {
+            self.increment_number_friend_rescues();
+        }
+        let evolutions = self.get_number_evolutions();
+        for _ in evolutions..snapshot.evolutions {
+            self.increment_number_evolutions();
+        }
+        let eggs_hatched = self.get_number_eggs_hatched();
+        for _ in eggs_hatched..snapshot.eggs_hatched {
+            self.increment_number_eggs_hatched();
+        }
+        let fainted = self.get_number_fainted();
+        for _ in fainted..snapshot.fainted {
+            Self::increment_number_of_fainted();
+        }
+        self.set_number_big_treasure_wins(snapshot.big_treasure_wins);
+        self.set_number_recycled(snapshot.recycled);
+        self.set_number_sky_gifts_sent(snapshot.sky_gifts_sent);
+        self.set_victories_on_one_floor(snapshot.victories_on_one_floor);
+        for &entry_id in &snapshot.completed_entries {
+            self.mark_entry_completed(entry_id);
+        }
+        self.compute_special_counters();
     }
 }
 
@@ -333,6 +797,7 @@ pub fn is_monster_on_team(monster_id: MonsterSpeciesId, param_2: i32) -> bool {
 }
 
 /// Sets the team setup of the currently active party.
+#[cfg(not(feature = "mock-ffi"))]
 pub fn set_team_setup(team_setup: TeamSetup) {
     match team_setup {
         TeamSetup::HeroOnly => unsafe { ffi::SetTeamSetupHeroOnly() },
@@ -340,10 +805,20 @@ pub fn set_team_setup(team_setup: TeamSetup) {
     }
 }
 
+/// Sets the team setup of the currently active party.
+///
+/// The simulated party returned by [`get_party_members`] isn't changed by this; set
+/// [`mock::with_state`]'s `party_members` field directly if a test needs both in sync.
+#[cfg(feature = "mock-ffi")]
+pub fn set_team_setup(team_setup: TeamSetup) {
+    mock::with_state(|state| state.team_setup = Some(team_setup));
+}
+
 /// Appears to get the team's active party members.
 ///
 /// Output is a slice-like of 2-byte values (they seem to be indexes of some sort) describing each
 /// party member.
+#[cfg(not(feature = "mock-ffi"))]
 pub fn get_party_members() -> impl AsRef<[u16]> {
     unsafe {
         let mut party_members: [u16; 4] = [0; 4];
@@ -352,11 +827,62 @@ pub fn get_party_members() -> impl AsRef<[u16]> {
     }
 }
 
+/// Appears to get the team's active party members.
+///
+/// Returns the simulated party set via [`mock::with_state`]'s `party_members` field, up to the
+/// first 4 entries (matching the real routine's fixed-size output buffer).
+#[cfg(feature = "mock-ffi")]
+pub fn get_party_members() -> impl AsRef<[u16]> {
+    let mut party_members: [u16; 4] = [0; 4];
+    let nb = mock::with_state(|state| {
+        let nb = state.party_members.len().min(4);
+        party_members[..nb].copy_from_slice(&state.party_members[..nb]);
+        nb
+    });
+    OwnedSlice::new(party_members, 0, nb)
+}
+
 /// Counts the number of monsters in the active team.
 pub fn count_party_members() -> i32 {
     unsafe { ffi::GetPartyMembers(ptr::null_mut()) }
 }
 
+/// A safe handle for querying the active exploration party/team, wrapping [`get_party_members`],
+/// [`count_party_members`] and [`is_monster_on_team`] so scripts don't have to juggle the raw
+/// output buffer or unsafe indexing themselves.
+///
+/// ```ignore
+/// for member in Party::active_members() {
+///     // `member` is one of the (up to 4) populated slots `GetPartyMembers` wrote.
+/// }
+/// ```
+pub struct Party;
+
+impl Party {
+    /// Returns an iterator over the populated slots of the active party, i.e. [`get_party_members`]
+    /// with the unpopulated tail of its 4-entry buffer already excluded.
+    pub fn active_members() -> impl Iterator<Item = u16> {
+        get_party_members().as_ref().to_vec().into_iter()
+    }
+
+    /// Counts the number of monsters in the active party. See [`count_party_members`].
+    pub fn count() -> i32 {
+        count_party_members()
+    }
+
+    /// Returns whether the active party consists of exactly one monster, mirroring
+    /// `SPECIAL_PROC_IS_TEAM_SETUP_SOLO`.
+    pub fn solo() -> bool {
+        Self::count() == 1
+    }
+
+    /// Checks if `monster_id` is on the exploration team (not necessarily the active party). See
+    /// [`is_monster_on_team`].
+    pub fn contains(monster_id: MonsterSpeciesId, param_2: i32) -> bool {
+        is_monster_on_team(monster_id, param_2)
+    }
+}
+
 /// Tests whether an IQ skill with a given ID is active.
 pub fn iq_skill_flag_test(iq_skill_flags: &mut u32, iq_id: IqSkillId) -> bool {
     unsafe { ffi::IqSkillFlagTest(iq_skill_flags, iq_id) > 0 }
@@ -367,17 +893,39 @@ pub fn get_sos_mail_count(param_1: i32, param_2: bool) -> i32 {
     unsafe { ffi::GetSosMailCount(param_1, param_2 as ffi::bool_) }
 }
 
+/// Seems to return the number of missions completed.
+///
+/// Part of the implementation for `SPECIAL_PROC_DUNGEON_HAD_REQUEST_DONE` (see
+/// `ScriptSpecialProcessCall`).
+pub fn dungeon_requests_done(param_1: i32, param_2: bool) -> i32 {
+    unsafe { ffi::DungeonRequestsDone(param_1, param_2 as ffi::bool_) }
+}
+
+/// Calls [`dungeon_requests_done`] with its second argument set to `false`.
+pub fn dungeon_requests_done_wrapper(param_1: i32) -> i32 {
+    unsafe { ffi::DungeonRequestsDoneWrapper(param_1) }
+}
+
+/// Calls [`dungeon_requests_done`] with its second argument set to `true`, and returns whether the
+/// number of missions completed is greater than 0.
+pub fn any_dungeon_requests_done(param_1: i32) -> bool {
+    unsafe { ffi::AnyDungeonRequestsDone(param_1) > 0 }
+}
+
 /// Attempts to generate a random mission.
 ///
-/// Returns the result, `None` is returned if the game returns an invalid result internally.
+/// Returns the result, or [`GameplayError::InvalidMissionResult`] if the game returns a result
+/// internally that this binding doesn't recognize.
 ///
 /// # Safety
 /// The caller must make sure the undefined params are valid for this function.
 pub fn generate_mission(
     unknown: &mut ffi::undefined,
     mission_data: &mut ffi::mission,
-) -> Option<MissionGenerationResult> {
-    unsafe { ffi::GenerateMission(unknown, mission_data).try_into().ok() }
+) -> Result<MissionGenerationResult, GameplayError> {
+    unsafe { ffi::GenerateMission(unknown, mission_data) }
+        .try_into()
+        .map_err(|_| GameplayError::InvalidMissionResult)
 }
 
 /// Generates the missions displayed on the Job Bulletin Board and the Outlaw Notice Board.
@@ -385,6 +933,87 @@ pub fn generate_daily_missions() {
     unsafe { ffi::GenerateDailyMissions() }
 }
 
+/// Assigns an integer weight to a [`MissionType`] candidate for
+/// [`MissionGenerator::generate_weighted`]'s weighted pick.
+#[derive(Clone, Copy)]
+pub struct WeightedMissionType {
+    pub mission_type: MissionType,
+    pub weight: u32,
+}
+
+/// Why [`MissionGenerator::generate_weighted`] failed to produce a mission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissionGenerationError {
+    /// The weighted candidate table was empty, or every entry had weight 0.
+    NoCandidates,
+    /// The picked mission type doesn't have a legal entry for the target dungeon (see
+    /// [`get_mission_by_type_and_dungeon`]).
+    InvalidCombination,
+}
+
+/// A typed, validating entry point for mission generation, replacing [`generate_mission`]'s raw
+/// `&mut ffi::undefined`/`&mut ffi::mission` signature with a weighted pick over candidate
+/// [`MissionType`]s.
+///
+/// This crate doesn't have confirmed field names or types for most of [`ffi::mission`] beyond what
+/// [`clear_mission_data`]'s own doc comment documents (`status`/`dungeon_id`/`floor`/
+/// `reward_type`) -- and several sibling structs in this crate's bindings turn out to be packed
+/// bitfields rather than plain fields (see e.g. [`DungeonId::conv_floor_to_group_floor`]), so
+/// guessing the rest of `mission`'s shape here would be worse than not guessing. So rather than
+/// writing the struct itself, [`Self::generate_weighted`] validates the picked type/dungeon
+/// combination and clears the struct (via [`clear_mission_data`]), then hands the picked
+/// [`MissionType`] to a caller-supplied `populate` closure to finish initializing the fields the
+/// caller's own bindings expose.
+pub struct MissionGenerator;
+
+impl MissionGenerator {
+    /// Performs a standard cumulative-sum weighted pick over `candidates` (entries with weight 0
+    /// are never chosen): sums all weights, draws a uniform value in `[0, total)` via the game
+    /// RNG, then walks the cumulative table to find the matching entry. Validates the picked type
+    /// against `dungeon_id` (via [`get_mission_by_type_and_dungeon`]), clears `mission` (via
+    /// [`clear_mission_data`]), and calls `populate` with the picked [`MissionType`] and
+    /// `dungeon_id` to finish initializing it.
+    ///
+    /// # Errors
+    /// [`MissionGenerationError::NoCandidates`] if `candidates` is empty or every weight is 0.
+    /// [`MissionGenerationError::InvalidCombination`] if the picked type has no legal entry for
+    /// `dungeon_id`.
+    pub fn generate_weighted(
+        candidates: &[WeightedMissionType],
+        dungeon_id: DungeonId,
+        mission: &mut ffi::mission,
+        populate: impl FnOnce(&mut ffi::mission, MissionType, DungeonId),
+    ) -> Result<(), MissionGenerationError> {
+        let picked =
+            Self::pick_weighted(candidates).ok_or(MissionGenerationError::NoCandidates)?;
+        get_mission_by_type_and_dungeon(0, picked, dungeon_id)
+            .ok_or(MissionGenerationError::InvalidCombination)?;
+        clear_mission_data(mission);
+        populate(mission, picked, dungeon_id);
+        Ok(())
+    }
+
+    /// The weighted draw itself, split out for testability: returns `None` if `candidates` is
+    /// empty or every weight is 0.
+    fn pick_weighted(candidates: &[WeightedMissionType]) -> Option<MissionType> {
+        let total: u32 = candidates.iter().map(|c| c.weight).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut roll = rand_u32(0..total);
+        for candidate in candidates {
+            if candidate.weight == 0 {
+                continue;
+            }
+            if roll < candidate.weight {
+                return Some(candidate.mission_type);
+            }
+            roll -= candidate.weight;
+        }
+        None
+    }
+}
+
 enum _DoMissionCheckType {
     General(usize),
     Accepted,
@@ -448,6 +1077,7 @@ fn _do_mission_check(
 ///
 /// If the type of the mission has a subtype, the subtype of the checked mission must match
 /// too.
+#[cfg(not(feature = "mock-ffi"))]
 pub fn get_mission_by_type_and_dungeon(
     start_index: usize,
     mission_type: MissionType,
@@ -463,6 +1093,26 @@ pub fn get_mission_by_type_and_dungeon(
     }
 }
 
+/// Returns the position on the simulated accepted-mission list ([`mock::with_state`]'s
+/// `accepted_missions`) of the first mission of the specified type that takes place in the
+/// specified dungeon, searching from `start_index`.
+#[cfg(feature = "mock-ffi")]
+pub fn get_mission_by_type_and_dungeon(
+    start_index: usize,
+    mission_type: MissionType,
+    dungeon_id: DungeonId,
+) -> Option<usize> {
+    mock::with_state(|state| {
+        state
+            .accepted_missions
+            .iter()
+            .enumerate()
+            .skip(start_index)
+            .find(|(_, (t, d))| *t == mission_type && d.id() == dungeon_id.id())
+            .map(|(index, _)| index)
+    })
+}
+
 /// Returns true if there are any accepted missions on the mission list that are of the specified
 /// type and take place in the specified dungeon.
 ///
@@ -573,87 +1223,178 @@ pub unsafe fn apply_gummi_boosts(
 }
 
 /// Returns the data of the player monster (first slot in Chimecho Assembly).
-pub fn get_hero_data<'a>() -> Option<&'a ffi::ground_monster> {
+pub fn get_hero_data<'a>() -> Result<&'a ffi::ground_monster, GameplayError> {
     let ptr = unsafe { ffi::GetHeroData() };
     if ptr.is_null() {
-        None
+        Err(GameplayError::NullData)
     } else {
-        Some(unsafe { &*ptr })
+        Ok(unsafe { &*ptr })
     }
 }
 
 /// Returns the data of the player monster (first slot in Chimecho Assembly), mutably.
-pub fn get_hero_data_mut<'a>() -> Option<&'a mut ffi::ground_monster> {
+pub fn get_hero_data_mut<'a>() -> Result<&'a mut ffi::ground_monster, GameplayError> {
     let ptr = unsafe { ffi::GetHeroData() };
     if ptr.is_null() {
-        None
+        Err(GameplayError::NullData)
     } else {
-        Some(unsafe { &mut *ptr })
+        Ok(unsafe { &mut *ptr })
     }
 }
 
 /// Returns the data of the partner monster (second slot in Chimecho Assembly).
-pub fn get_partner_data<'a>() -> Option<&'a ffi::ground_monster> {
+pub fn get_partner_data<'a>() -> Result<&'a ffi::ground_monster, GameplayError> {
     let ptr = unsafe { ffi::GetPartnerData() };
     if ptr.is_null() {
-        None
+        Err(GameplayError::NullData)
     } else {
-        Some(unsafe { &*ptr })
+        Ok(unsafe { &*ptr })
     }
 }
 
 /// Returns the data of the partner monster (second slot in Chimecho Assembly), mutably.
-pub fn get_partner_data_mut<'a>() -> Option<&'a mut ffi::ground_monster> {
+pub fn get_partner_data_mut<'a>() -> Result<&'a mut ffi::ground_monster, GameplayError> {
     let ptr = unsafe { ffi::GetPartnerData() };
     if ptr.is_null() {
-        None
+        Err(GameplayError::NullData)
     } else {
-        Some(unsafe { &mut *ptr })
+        Ok(unsafe { &mut *ptr })
     }
 }
 
 /// Returns a struct containing information about a team member.
-pub fn get_team_member_data<'a>(member_id: u8) -> Option<&'a ffi::team_member> {
+pub fn get_team_member_data<'a>(member_id: u8) -> Result<&'a ffi::team_member, GameplayError> {
     let ptr = unsafe { ffi::GetTeamMemberData(member_id) };
     if ptr.is_null() {
-        None
+        Err(GameplayError::NullData)
     } else {
-        Some(unsafe { &*ptr })
+        Ok(unsafe { &*ptr })
     }
 }
 
 /// Returns a struct containing information about a team member.
-pub fn get_team_member_data_mut<'a>(member_id: u8) -> Option<&'a mut ffi::team_member> {
+pub fn get_team_member_data_mut<'a>(
+    member_id: u8,
+) -> Result<&'a mut ffi::team_member, GameplayError> {
     let ptr = unsafe { ffi::GetTeamMemberData(member_id) };
     if ptr.is_null() {
-        None
+        Err(GameplayError::NullData)
     } else {
-        Some(unsafe { &mut *ptr })
+        Ok(unsafe { &mut *ptr })
     }
 }
 
 /// Returns the data of a monster sent into the Explorer Dojo using the "exchange teams" option.
 ///
-/// `entry_number` must be a value between \[0,3\].
-pub fn get_explorer_dojo_monster_data<'a>(entry_number: u8) -> Option<&'a ffi::ground_monster> {
+/// `entry_number` must be a value between \[0,3\]; returns
+/// [`GameplayError::IndexOutOfRange`] otherwise.
+pub fn get_explorer_dojo_monster_data<'a>(
+    entry_number: u8,
+) -> Result<&'a ffi::ground_monster, GameplayError> {
+    if entry_number > 3 {
+        return Err(GameplayError::IndexOutOfRange {
+            got: entry_number as u32,
+            max: 3,
+        });
+    }
     let ptr = unsafe { ffi::GetExplorerMazeMonster(entry_number) };
     if ptr.is_null() {
-        None
+        Err(GameplayError::NullData)
     } else {
-        Some(unsafe { &*ptr })
+        Ok(unsafe { &*ptr })
     }
 }
 
 /// Returns the data of a monster sent into the Explorer Dojo using the "exchange teams" option.
 ///
-/// `entry_number` must be a value between \[0,3\].
+/// `entry_number` must be a value between \[0,3\]; returns
+/// [`GameplayError::IndexOutOfRange`] otherwise.
 pub fn get_explorer_dojo_monster_data_mut<'a>(
     entry_number: u8,
-) -> Option<&'a mut ffi::ground_monster> {
+) -> Result<&'a mut ffi::ground_monster, GameplayError> {
+    if entry_number > 3 {
+        return Err(GameplayError::IndexOutOfRange {
+            got: entry_number as u32,
+            max: 3,
+        });
+    }
     let ptr = unsafe { ffi::GetExplorerMazeMonster(entry_number) };
     if ptr.is_null() {
-        None
+        Err(GameplayError::NullData)
     } else {
-        Some(unsafe { &mut *ptr })
+        Ok(unsafe { &mut *ptr })
+    }
+}
+
+/// An in-memory stand-in for this module's `ffi` calls, swapped in by the functions above when the
+/// `mock-ffi` feature is enabled, so `AdventureLog`, party and mission interactions can be driven
+/// and asserted from `#[test]`s on a host machine instead of needing the actual game binary. This
+/// mirrors how sibling Rust Pokémon libraries add a mockable interface layer to enable CI testing
+/// without the engine.
+///
+/// Only the surface named above is backed by this module; everything else in [`gameplay`](self)
+/// still calls through to `ffi` regardless of the feature, since faithfully simulating the rest
+/// (e.g. the hero/partner `ground_monster` buffers' actual field layout) isn't possible without
+/// verified bindings for it.
+#[cfg_attr(docsrs, doc(cfg(feature = "mock-ffi")))]
+#[cfg(feature = "mock-ffi")]
+pub mod mock {
+    use super::{DungeonId, MissionType, TeamSetup};
+    use alloc::collections::BTreeSet;
+    use alloc::vec::Vec;
+
+    /// The simulated adventure-log counters/bitfields, active party, team setup and
+    /// accepted-mission list that the `mock-ffi` code paths in [`gameplay`](super) read and write.
+    ///
+    /// Bitfield-backed counters (monsters joined/battled, items acquired) are tracked as sets of
+    /// IDs here and only rolled up into their `number_*` counter fields by
+    /// [`AdventureLog::compute_special_counters`](super::AdventureLog::compute_special_counters),
+    /// matching the real routine's bitfield-then-recompute behavior.
+    #[derive(Default)]
+    pub struct MockState {
+        pub entries_completed: Vec<u32>,
+        pub dungeons_cleared: u32,
+        pub friend_rescues: u32,
+        pub evolutions: u32,
+        pub steals: u32,
+        pub eggs_hatched: u32,
+        pub monsters_joined: BTreeSet<u32>,
+        pub monsters_battled: BTreeSet<u32>,
+        pub items_acquired: BTreeSet<u32>,
+        pub number_monsters_joined: u32,
+        pub number_monsters_battled: u32,
+        pub number_moves_learned: u32,
+        pub number_items_acquired: u32,
+        pub big_treasure_wins: u32,
+        pub sky_gifts_sent: u32,
+        pub fainted: u32,
+        pub team_setup: Option<TeamSetup>,
+        pub party_members: Vec<u16>,
+        pub accepted_missions: Vec<(MissionType, DungeonId)>,
+    }
+
+    /// This is safe to access by the functions in this module, since the simulated backend (like
+    /// the NDS itself) is only ever driven from a single thread -- here, the test thread.
+    static mut STATE: Option<MockState> = None;
+
+    /// Runs `f` against the simulated backend state, initializing it to its default
+    /// (all-zero/empty) value on first access.
+    ///
+    /// Takes a closure rather than handing out a `&'static mut MockState` directly, so callers
+    /// can't stash two live mutable borrows of the same state -- mirrors the `with_context`-style
+    /// accessors used elsewhere in this crate for single-threaded global state.
+    pub fn with_state<R>(f: impl FnOnce(&mut MockState) -> R) -> R {
+        // SAFETY: single-threaded; see `STATE`.
+        #[allow(static_mut_refs)]
+        unsafe {
+            f(STATE.get_or_insert_with(MockState::default))
+        }
+    }
+
+    /// Resets the simulated backend to its default state, so `#[test]`s don't leak state into
+    /// each other.
+    pub fn reset() {
+        // SAFETY: single-threaded; see `STATE`.
+        unsafe { STATE = Some(MockState::default()) };
     }
 }
@@ -96,6 +96,9 @@ impl GroundModeContext {
     /// - [`SpecialProcessId::SPECIAL_PROC_0x16`] : [`Self::script_special_process_x16`]
     /// - [`SpecialProcessId::SPECIAL_PROC_STATUS_UPDATE`] : [`Self::status_update`]
     ///
+    /// For mod-defined special processes that don't go through this function directly, see
+    /// [`crate::api::special_process`].
+    ///
     /// # Arguments
     /// * `param_1` - some struct containing a callback of some sort, only used for special process ID 18
     /// * `id`      - special process ID
@@ -164,13 +167,23 @@ impl GroundModeContext {
     }
 
     /// Implements SPECIAL_PROC_RETURN_DUNGEON.
+    ///
+    /// Also clears any registered ground process tagged
+    /// [`ProcessScope::DungeonScoped`](crate::api::ground_process::ProcessScope::DungeonScoped),
+    /// see [`Self::clear_dungeon_scoped_processes`].
     pub fn return_dungeon(&mut self) {
         unsafe { ffi::GroundMainReturnDungeon() }
+        self.clear_dungeon_scoped_processes();
     }
 
     /// Implements SPECIAL_PROC_NEXT_DAY.
+    ///
+    /// Also clears any registered ground process tagged
+    /// [`ProcessScope::DungeonScoped`](crate::api::ground_process::ProcessScope::DungeonScoped),
+    /// see [`Self::clear_dungeon_scoped_processes`].
     pub fn next_day(&mut self) {
         unsafe { ffi::GroundMainNextDay() }
+        self.clear_dungeon_scoped_processes();
     }
 
     /// Fades the screen out and throws the player to the title screen.
@@ -186,8 +199,14 @@ impl GroundModeContext {
     /// Fades the screen out and throws the player to the title screen.
     ///
     /// Implements SPECIAL_PROC_RETURN_TO_TITLE_SCREEN.
+    ///
+    /// Also clears any registered ground process tagged
+    /// [`ProcessScope::DungeonScoped`](crate::api::ground_process::ProcessScope::DungeonScoped),
+    /// see [`Self::clear_dungeon_scoped_processes`].
     pub fn return_to_title_screen(&mut self, fade_duration: u32) -> bool {
-        unsafe { ffi::ReturnToTitleScreen(fade_duration) > 0 }
+        let result = unsafe { ffi::ReturnToTitleScreen(fade_duration) > 0 };
+        self.clear_dungeon_scoped_processes();
+        result
     }
 
     /// Implements SPECIAL_PROC_0x16.
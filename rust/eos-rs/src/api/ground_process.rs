@@ -0,0 +1,248 @@
+//! A cooperative, per-frame processing subsystem for ground mode, inspired by the SS13
+//! `SSprocessing` pattern: a handler registered via [`GroundModeContext::register_process`] runs
+//! once every ground-mode frame until it deregisters itself by returning [`ProcessResult::Kill`].
+//!
+//! [`run_ground_processes`] is the per-frame pump, meant to be patched into the ground main loop
+//! via the existing `patches!` raw-assembly mechanism, or called once per frame from a
+//! user-provided loop special process (see [`crate::api::special_process`]).
+//!
+//! This module also hosts [`GroundModeContext`]'s ambient-sound scheduler
+//! ([`set_ambient_buzz`](GroundModeContext::set_ambient_buzz)/
+//! [`set_ambient_pool`](GroundModeContext::set_ambient_pool)), which is itself just a registered
+//! process.
+
+use crate::api::ground_mode::GroundModeContext;
+use crate::api::random::rand_i32;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// What a registered process handler wants to happen next frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessResult {
+    /// Keep this handler registered; it runs again next frame.
+    Continue,
+    /// Deregister this handler; it doesn't run again.
+    Kill,
+}
+
+/// Whether a registered process survives a [`GroundModeContext::next_day`]/
+/// [`GroundModeContext::return_dungeon`]/[`GroundModeContext::return_to_title_screen`]
+/// transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessScope {
+    /// Keeps running across day/location transitions.
+    Persistent,
+    /// Killed by [`GroundModeContext::clear_dungeon_scoped_processes`], so e.g. a countdown timer
+    /// tied to the current day doesn't leak into the next one.
+    DungeonScoped,
+}
+
+type ProcessHandler = Box<dyn FnMut(&mut GroundModeContext) -> ProcessResult>;
+
+struct ProcessEntry {
+    handler: ProcessHandler,
+    scope: ProcessScope,
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// ground processes are only ever run from the main ground-mode loop.
+static mut PROCESSES: Vec<ProcessEntry> = Vec::new();
+/// Handlers registered mid-pass (e.g. by another handler) are staged here and appended to
+/// [`PROCESSES`] once the current pass finishes, so registering doesn't invalidate the
+/// in-progress index-based iteration over it.
+static mut PENDING: Vec<ProcessEntry> = Vec::new();
+
+impl GroundModeContext {
+    /// Registers `handler` to run once per ground-mode frame (see [`run_ground_processes`]) until
+    /// it returns [`ProcessResult::Kill`].
+    ///
+    /// If called from inside a handler that's itself currently running as part of a
+    /// [`run_ground_processes`] pass, `handler` is staged and only starts running from the next
+    /// frame rather than the current one.
+    pub fn register_process(
+        &mut self,
+        scope: ProcessScope,
+        handler: impl FnMut(&mut GroundModeContext) -> ProcessResult + 'static,
+    ) {
+        // SAFETY: single-threaded; see `PENDING`.
+        #[allow(static_mut_refs)]
+        unsafe {
+            PENDING.push(ProcessEntry {
+                handler: Box::new(handler),
+                scope,
+            });
+        }
+    }
+
+    /// Kills every registered process tagged [`ProcessScope::DungeonScoped`], so timers tied to
+    /// the current day/dungeon don't leak across the transition.
+    ///
+    /// Called by [`Self::next_day`], [`Self::return_dungeon`] and
+    /// [`Self::return_to_title_screen`]; most callers won't need to call this directly.
+    pub fn clear_dungeon_scoped_processes(&mut self) {
+        // SAFETY: single-threaded; see `PROCESSES`/`PENDING`.
+        #[allow(static_mut_refs)]
+        unsafe {
+            PROCESSES.retain(|entry| entry.scope != ProcessScope::DungeonScoped);
+            PENDING.retain(|entry| entry.scope != ProcessScope::DungeonScoped);
+        }
+    }
+}
+
+/// Runs one pass of every registered process in registration order, removing any that return
+/// [`ProcessResult::Kill`].
+///
+/// Meant to run once per ground-mode frame -- e.g. patched into the ground main loop via
+/// `patches!`'s raw-assembly hook mechanism, or invoked once per frame from a user-provided loop
+/// special process (see [`crate::api::special_process`]).
+///
+/// Iterates by index rather than by iterator so a handler calling
+/// [`GroundModeContext::register_process`] mid-pass doesn't invalidate the in-progress loop --
+/// newly registered handlers are staged in a pending buffer and only spliced in once this pass
+/// finishes, so they start running next frame (see [`PENDING`](self)). Kills are deferred the
+/// same way: which indices returned [`ProcessResult::Kill`] is tracked while iterating, and only
+/// removed once every handler in this pass has run, so an earlier kill can't shift indices out
+/// from under the rest of the pass.
+pub fn run_ground_processes(ctx: &mut GroundModeContext) {
+    // SAFETY: single-threaded; see `PROCESSES`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        let mut killed = Vec::new();
+        for index in 0..PROCESSES.len() {
+            if (PROCESSES[index].handler)(ctx) == ProcessResult::Kill {
+                killed.push(index);
+            }
+        }
+        for index in killed.into_iter().rev() {
+            PROCESSES.remove(index);
+        }
+        PROCESSES.append(&mut PENDING);
+    }
+}
+
+/// A one-shot sound usable in an ambient pool (see [`GroundModeContext::set_ambient_pool`]).
+///
+/// `sound_id` is forwarded verbatim to whatever plays it was given via
+/// [`GroundModeContext::set_ambient_sound_player`] -- this crate has no catalog of sound effect
+/// IDs to type this against, the same gap [`change_dungeon_music`](crate::api::dungeon_mode::change_dungeon_music)'s
+/// `music_id` parameter is in.
+#[derive(Debug, Clone, Copy)]
+pub struct AmbientSound {
+    pub sound_id: u16,
+    pub volume: u8,
+}
+
+/// The actual sound-playback call backing the ambient scheduler: this crate doesn't expose a
+/// sound-effect FFI binding, so callers supply their own (e.g. a thin wrapper around whatever
+/// native function plays a sound ID at a given volume) via
+/// [`GroundModeContext::set_ambient_sound_player`].
+type AmbientSoundPlayer = Box<dyn FnMut(u16, u8)>;
+
+struct AmbientState {
+    player: AmbientSoundPlayer,
+    buzz: Option<(u16, u8)>,
+    pool: Vec<AmbientSound>,
+    min_interval: u32,
+    max_interval: u32,
+    frames_until_next: u32,
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// the ambient scheduler only ever runs from [`tick_ambient`], itself only ever run as a
+/// registered process (see [`PROCESSES`](self)).
+static mut AMBIENT: Option<AmbientState> = None;
+
+impl GroundModeContext {
+    /// Sets (or replaces) the callback the ambient scheduler calls to actually play a sound,
+    /// and starts the scheduler ticking once per ground-mode frame (see [`run_ground_processes`]).
+    ///
+    /// Must be called before [`Self::set_ambient_buzz`]/[`Self::set_ambient_pool`] have any
+    /// audible effect; until a player is set, ambient settings are only recorded, not played.
+    pub fn set_ambient_sound_player(&mut self, player: impl FnMut(u16, u8) + 'static) {
+        // SAFETY: single-threaded; see `AMBIENT`.
+        #[allow(static_mut_refs)]
+        let already_running = unsafe { AMBIENT.is_some() };
+        // SAFETY: single-threaded; see `AMBIENT`.
+        #[allow(static_mut_refs)]
+        unsafe {
+            match AMBIENT.as_mut() {
+                Some(state) => state.player = Box::new(player),
+                None => {
+                    AMBIENT = Some(AmbientState {
+                        player: Box::new(player),
+                        buzz: None,
+                        pool: Vec::new(),
+                        min_interval: 0,
+                        max_interval: 0,
+                        frames_until_next: 0,
+                    })
+                }
+            }
+        }
+        if !already_running {
+            self.register_process(ProcessScope::Persistent, tick_ambient);
+        }
+    }
+
+    /// Starts a continuous looping background drone at `sound_id`/`volume` (e.g. wind, distant
+    /// surf), giving a ground map atmosphere without the script writer managing a per-frame timer
+    /// themselves. Calling this again replaces the previous buzz, if any; `None` stops it.
+    ///
+    /// The drone itself is expected to loop natively once started, so the sound player is only
+    /// called once here, not on every tick; see [`Self::set_ambient_pool`] for sounds that
+    /// actually need to be retriggered.
+    pub fn set_ambient_buzz(&mut self, sound_id: u16, volume: u8) {
+        // SAFETY: single-threaded; see `AMBIENT`.
+        #[allow(static_mut_refs)]
+        unsafe {
+            if let Some(state) = AMBIENT.as_mut() {
+                state.buzz = Some((sound_id, volume));
+                (state.player)(sound_id, volume);
+            }
+        }
+    }
+
+    /// Sets the pool of one-shot ambient sounds (e.g. distant birds, creaking wood) that fire at
+    /// randomized intervals between `min_interval` and `max_interval` ground-mode frames apart,
+    /// picked uniformly via the game RNG. Replaces any previously set pool.
+    ///
+    /// # Panics
+    /// Panics if `min_interval > max_interval`.
+    pub fn set_ambient_pool(&mut self, pool: &[AmbientSound], min_interval: u32, max_interval: u32) {
+        assert!(min_interval <= max_interval, "min_interval must not exceed max_interval");
+        // SAFETY: single-threaded; see `AMBIENT`.
+        #[allow(static_mut_refs)]
+        unsafe {
+            if let Some(state) = AMBIENT.as_mut() {
+                state.pool = pool.to_vec();
+                state.min_interval = min_interval;
+                state.max_interval = max_interval;
+                state.frames_until_next = rand_i32(min_interval as i32..=max_interval as i32) as u32;
+            }
+        }
+    }
+}
+
+/// The registered process (see [`GroundModeContext::set_ambient_sound_player`]) that ticks down
+/// to the next randomly-scheduled [`AmbientSound`] from the pool and plays it, forever (ambient
+/// scheduling isn't tied to any one map, so this never returns [`ProcessResult::Kill`]).
+fn tick_ambient(_ctx: &mut GroundModeContext) -> ProcessResult {
+    // SAFETY: single-threaded; see `AMBIENT`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        if let Some(state) = AMBIENT.as_mut() {
+            if !state.pool.is_empty() {
+                if state.frames_until_next == 0 {
+                    let index = rand_i32(0..state.pool.len() as i32) as usize;
+                    let sound = state.pool[index];
+                    (state.player)(sound.sound_id, sound.volume);
+                    state.frames_until_next =
+                        rand_i32(state.min_interval as i32..=state.max_interval as i32) as u32;
+                } else {
+                    state.frames_until_next -= 1;
+                }
+            }
+        }
+    }
+    ProcessResult::Continue
+}
@@ -5,26 +5,124 @@
 //! On the top-level this module re-exports [`acid_io`].
 //!
 //! EoS related file operations are in the sub-module [`mod@file`].
+//!
+//! [`read_i24f8_le`]/[`write_i24f8_le`] and [`read_i16f16_le`]/[`write_i16f16_le`] move
+//! fixed-point values to and from their raw in-ROM byte layout, so patch code that pokes at
+//! fixed-point fields doesn't have to manually fetch the raw integer and call `from_bits` itself.
 
 // We also provide acid_io.
 pub use acid_io::*;
 
+use crate::api::fixed::{I16F16, I24F8};
+
+/// Reads a little-endian raw fixed-point value and wraps it via `from_bits`, so the result is
+/// exact with no rounding. Errors (via [`Read::read_exact`]) if fewer bytes than the type's
+/// width are left in `reader`.
+macro_rules! impl_fixed_io {
+    ($read_fn:ident, $write_fn:ident, $ty:ty, $bits:ty) => {
+        #[doc = concat!(
+            "Reads a little-endian [`", stringify!($ty), "`] from `reader`, going straight ",
+            "through `from_bits` so no rounding occurs."
+        )]
+        pub fn $read_fn<R: Read>(reader: &mut R) -> Result<$ty> {
+            let mut buf = [0u8; core::mem::size_of::<$bits>()];
+            reader.read_exact(&mut buf)?;
+            Ok(<$ty>::from_bits(<$bits>::from_le_bytes(buf)))
+        }
+
+        #[doc = concat!(
+            "Writes `value` to `writer` as a little-endian [`", stringify!($ty), "`], going ",
+            "straight through `to_bits` so no rounding occurs."
+        )]
+        pub fn $write_fn<W: Write>(writer: &mut W, value: $ty) -> Result<()> {
+            writer.write_all(&value.to_bits().to_le_bytes())
+        }
+    };
+}
+
+impl_fixed_io!(read_i24f8_le, write_i24f8_le, I24F8, i32);
+impl_fixed_io!(read_i16f16_le, write_i16f16_le, I16F16, i32);
+
 pub mod file {
     //! File related operations.
 
     use super::{Read, Seek, SeekFrom};
     use crate::ctypes::c_void;
     use crate::ffi;
+    use alloc::vec;
     use alloc::vec::Vec;
     use core::ffi::CStr;
     use core::mem::MaybeUninit;
 
-    /// This counter mutex is safe to access by methods of this module, since the NDS is
-    /// single-threaded. There are probably some reasons regarding missing atomic support and the
-    /// ARM instruction set due to which this isn't true if for example interrupts happen, but
-    /// this is marked in the safety note of [`read`] and [`File::open`].
+    /// This counter is only ever mutated from inside [`FileTransferMode::acquire`]/its `Drop`
+    /// impl, both of which bracket every access with [`critical_section`] -- see that function's
+    /// docs for why a critical section is needed here at all instead of a plain atomic.
     static mut COUNT_IN_FILE_TRANSFER_MODE: usize = 0;
 
+    /// The NDS's `REG_IME` (interrupt master enable) hardware register. Writing `0` disables all
+    /// interrupts; writing back a previously read value restores whatever was enabled before.
+    const REG_IME: *mut u32 = 0x0400_0208 as *mut u32;
+
+    /// Runs `f` with `REG_IME` forced to `0` (all interrupts masked), restoring whatever value it
+    /// held beforehand once `f` returns.
+    ///
+    /// [`COUNT_IN_FILE_TRANSFER_MODE`]'s load-modify-store is not atomic, and this core (like the
+    /// rest of the ARMv5TE family) has no compare-and-swap instruction to make it so. Without this,
+    /// an interrupt handler that itself touches the counter (directly, or by opening a file) could
+    /// run between the load and the store of another in-progress update and corrupt it. Disabling
+    /// interrupts for the handful of instructions the update takes rules that out, at the cost of
+    /// not being reentrant-safe if `f` itself blocks for a long time -- callers should keep the
+    /// critical section short, as [`FileTransferMode`] does.
+    fn critical_section<R>(f: impl FnOnce() -> R) -> R {
+        unsafe {
+            let previous_ime = REG_IME.read_volatile();
+            REG_IME.write_volatile(0);
+            let result = f();
+            REG_IME.write_volatile(previous_ime);
+            result
+        }
+    }
+
+    /// An interrupt-safe RAII guard for NDS file-transfer mode, replacing a bare
+    /// `static mut` counter toggled directly around `DataTransferInit`/`DataTransferStop`.
+    ///
+    /// Acquiring nests: the first concurrently-held guard calls `DataTransferInit`, further
+    /// guards just bump the refcount, and the last one dropped calls `DataTransferStop`. Every
+    /// touch of the shared counter happens inside [`critical_section`], so acquiring/dropping a
+    /// guard is sound even from interrupt context.
+    ///
+    /// [`File::open`], [`read`] and [`FileReader`] all acquire one of these internally; hold one
+    /// yourself to batch several opens/reads into a single transfer-mode window instead of paying
+    /// for one per call.
+    pub struct FileTransferMode {
+        _private: (),
+    }
+
+    impl FileTransferMode {
+        /// Enters file-transfer mode, starting it (via `DataTransferInit`) if no other guard is
+        /// currently held.
+        pub fn acquire() -> Self {
+            critical_section(|| unsafe {
+                if COUNT_IN_FILE_TRANSFER_MODE == 0 {
+                    ffi::DataTransferInit();
+                }
+                COUNT_IN_FILE_TRANSFER_MODE += 1;
+            });
+            Self { _private: () }
+        }
+    }
+
+    impl Drop for FileTransferMode {
+        fn drop(&mut self) {
+            critical_section(|| unsafe {
+                COUNT_IN_FILE_TRANSFER_MODE -= 1;
+                if COUNT_IN_FILE_TRANSFER_MODE == 0 {
+                    ffi::DataTransferStop();
+                }
+            });
+        }
+    }
+
     /// Loads a file from ROM by filepath into a heap-allocated buffer.
     ///
     /// # Safety
@@ -32,17 +130,10 @@ pub mod file {
     ///
     /// Additionally, see safety note for [`Vec::from_raw_parts`]. It's probably safer to
     /// use the [`File`] struct instead.
-    ///
-    /// You must make sure this is not called during interrupts.
     pub unsafe fn read<C: AsRef<CStr>>(path: C, flags: u32) -> Vec<u8> {
+        let _transfer_mode = FileTransferMode::acquire();
         let mut iov_raw = MaybeUninit::uninit();
-        if COUNT_IN_FILE_TRANSFER_MODE == 0 {
-            ffi::DataTransferInit();
-        }
         ffi::LoadFileFromRom(iov_raw.as_mut_ptr(), path.as_ref().as_ptr(), flags);
-        if COUNT_IN_FILE_TRANSFER_MODE == 0 {
-            ffi::DataTransferStop();
-        }
         let iov = iov_raw.assume_init();
         Vec::from_raw_parts(
             iov.iov_base as *mut u8,
@@ -60,19 +151,11 @@ pub mod file {
         ///
         /// # Safety
         /// The file path must be a valid path to an existing file in the ROM file system.
-        ///
-        /// You must make sure the file object or readers made for it are never accessed from code
-        /// during interrupts.
         pub unsafe fn open<C: AsRef<CStr>>(path: C) -> Self {
+            let _transfer_mode = FileTransferMode::acquire();
             let mut file_stream = MaybeUninit::uninit();
-            if COUNT_IN_FILE_TRANSFER_MODE == 0 {
-                ffi::DataTransferInit();
-            }
             ffi::FileInit(file_stream.as_mut_ptr());
             ffi::FileOpen(file_stream.as_mut_ptr(), path.as_ref().as_ptr());
-            if COUNT_IN_FILE_TRANSFER_MODE == 0 {
-                ffi::DataTransferStop();
-            }
             Self(file_stream.assume_init())
         }
 
@@ -95,36 +178,102 @@ pub mod file {
         }
     }
 
+    pub mod dir {
+        //! Enumerating entries in the ROM file system (see [`read_dir`]).
+        //!
+        //! This crate has no FFI binding for listing a directory's entries -- the base game's
+        //! own file API ([`FileOpen`](ffi::FileOpen)/[`FileRead`](ffi::FileRead)) only ever
+        //! operates on a path the caller already knows, there's no native "what's in this folder"
+        //! call to bind. So rather than listing straight from the ROM file system, [`read_dir`]
+        //! resolves a manifest of candidate entries the caller already knows (name, plus whether
+        //! it's a file or a subdirectory) against `dir_path` -- such a manifest is typically
+        //! generated once at ROM-build time, since PMD:EoS's file layout is static per build --
+        //! and fills in each file entry's size by actually opening it, so a mod doesn't have to
+        //! hardcode sizes it can read straight from the ROM.
+
+        use super::{ffi, File};
+        use alloc::ffi::CString;
+        use alloc::format;
+        use alloc::string::{String, ToString};
+        use alloc::vec::Vec;
+
+        /// Whether a [`DirEntry`] is a file (with its size, via [`File::len`]) or a subdirectory.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum EntryKind {
+            File { size: u32 },
+            Directory,
+        }
+
+        /// A single entry yielded by [`read_dir`].
+        #[derive(Debug, Clone)]
+        pub struct DirEntry {
+            pub name: String,
+            pub kind: EntryKind,
+        }
+
+        /// Resolves `candidates` (each a name plus whether it's a file or a subdirectory) against
+        /// `dir_path`, returning a [`DirEntry`] for each one, with file sizes filled in via
+        /// [`File::len`].
+        ///
+        /// See the [module docs](self) for why this takes a candidate manifest rather than
+        /// listing the directory itself.
+        ///
+        /// Enters the same interrupt-safe [`FileTransferMode`](super::FileTransferMode) as
+        /// [`File`], since opening each file candidate goes through [`File::open`].
+        ///
+        /// # Safety
+        /// Every candidate marked as a file must be a valid path (joined as `dir_path/name`) to an
+        /// existing file in the ROM file system.
+        pub unsafe fn read_dir(dir_path: &str, candidates: &[(&str, bool)]) -> Vec<DirEntry> {
+            let mut entries = Vec::with_capacity(candidates.len());
+            for &(name, is_file) in candidates {
+                let kind = if is_file {
+                    let path = CString::new(format!("{dir_path}/{name}"))
+                        .expect("path must not contain NUL bytes");
+                    let file = File::open(path);
+                    EntryKind::File { size: file.len() }
+                } else {
+                    EntryKind::Directory
+                };
+                entries.push(DirEntry {
+                    name: name.to_string(),
+                    kind,
+                });
+            }
+            entries
+        }
+    }
+
     /// A reader for a [`File`].
     ///
     /// During the lifetime of the reader the NDS will be put in file-transfer mode, if it isn't
     /// already.
-    pub struct FileReader<'a>(&'a mut File);
+    pub struct FileReader<'a>(&'a mut File, FileTransferMode);
 
     impl<'a> FileReader<'a> {
         pub fn new(file: &'a mut File) -> Self {
-            unsafe {
-                if COUNT_IN_FILE_TRANSFER_MODE == 0 {
-                    ffi::DataTransferInit();
-                }
-                COUNT_IN_FILE_TRANSFER_MODE += 1;
-                Self(file)
-            }
+            Self(file, FileTransferMode::acquire())
         }
-    }
 
-    impl<'a> Drop for FileReader<'a> {
-        fn drop(&mut self) {
-            unsafe {
-                // This can only be false if this has somehow gotten out of sync, but let's just be
-                // safe here...
-                if COUNT_IN_FILE_TRANSFER_MODE > 0 {
-                    COUNT_IN_FILE_TRANSFER_MODE -= 1;
-                }
-                if COUNT_IN_FILE_TRANSFER_MODE == 0 {
-                    ffi::DataTransferStop();
+        /// Reads the rest of the file into `buf`, growing it in chunks via `extend_from_slice`.
+        ///
+        /// This is the streaming equivalent of the top-level [`read`] fast path (which loads a
+        /// whole file into a freshly allocated buffer via raw parts), for callers who'd rather not
+        /// use its `unsafe` API.
+        ///
+        /// Returns the number of bytes read and appended to `buf`.
+        pub fn read_to_end(&mut self, buf: &mut Vec<u8>) -> super::Result<usize> {
+            const CHUNK_SIZE: usize = 512;
+            let start_len = buf.len();
+            let mut chunk = [0u8; CHUNK_SIZE];
+            loop {
+                match self.read(&mut chunk) {
+                    Ok(0) => break,
+                    Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                    Err(e) => return Err(e),
                 }
             }
+            Ok(buf.len() - start_len)
         }
     }
 
@@ -135,29 +284,146 @@ pub mod file {
                     &mut self.0.0,
                     dst.as_mut_ptr() as *mut c_void,
                     dst.len() as u32,
-                ) as usize;
-                Ok(len)
+                );
+                if len < 0 {
+                    return Err(super::ErrorKind::Other.into());
+                }
+                Ok(len as usize)
+            }
+        }
+
+        /// Reads until `dst` is completely filled, looping over short [`FileRead`](ffi::FileRead)
+        /// calls as needed.
+        ///
+        /// # Errors
+        /// Returns [`ErrorKind::UnexpectedEof`](super::ErrorKind::UnexpectedEof) if the file ends
+        /// before `dst` is filled.
+        fn read_exact(&mut self, mut dst: &mut [u8]) -> super::Result<()> {
+            while !dst.is_empty() {
+                match self.read(dst) {
+                    Ok(0) => break,
+                    Ok(n) => dst = &mut dst[n..],
+                    Err(e) => return Err(e),
+                }
+            }
+            if !dst.is_empty() {
+                Err(super::ErrorKind::UnexpectedEof.into())
+            } else {
+                Ok(())
             }
         }
     }
 
     impl<'a> Seek for FileReader<'a> {
-        /// Seeking from End might not be properly implemented in the game.
+        /// Resolves `pos` to an absolute address in Rust (using [`File::len`] for
+        /// `SeekFrom::End`, and the file's current address for `SeekFrom::Current`) and issues a
+        /// single absolute `FileSeek(..., 0)`, rather than trusting the game's own relative-seek
+        /// handling -- which the base game doesn't reliably implement for `SeekFrom::End`.
         ///
-        /// Additionally the position must fit into an i32.
+        /// # Errors
+        /// Returns an error with [`ErrorKind::InvalidInput`](super::ErrorKind::InvalidInput) if
+        /// the resolved target address is negative or doesn't fit into an `i32`.
         fn seek(&mut self, pos: SeekFrom) -> super::Result<u64> {
+            let current_offset = self.0.0.current_address as i64 - self.0.0.start_address as i64;
+            let target: i64 = match pos {
+                SeekFrom::Start(p) => p
+                    .try_into()
+                    .map_err(|_| super::ErrorKind::InvalidInput)?,
+                SeekFrom::Current(p) => current_offset
+                    .checked_add(p)
+                    .ok_or(super::ErrorKind::InvalidInput)?,
+                SeekFrom::End(p) => (self.0.len() as i64)
+                    .checked_add(p)
+                    .ok_or(super::ErrorKind::InvalidInput)?,
+            };
+            if target < 0 {
+                return Err(super::ErrorKind::InvalidInput.into());
+            }
+            let target: i32 = target
+                .try_into()
+                .map_err(|_| super::ErrorKind::InvalidInput)?;
             unsafe {
-                match pos {
-                    SeekFrom::Start(p) => ffi::FileSeek(&mut self.0.0, p as i32, 0),
-                    SeekFrom::Current(p) => ffi::FileSeek(&mut self.0.0, p as i32, 1),
-                    SeekFrom::End(p) => ffi::FileSeek(&mut self.0.0, p as i32, 2),
-                }
-
+                ffi::FileSeek(&mut self.0.0, target, 0);
                 Ok((self.0.0.current_address as u64) - (self.0.0.start_address as u64))
             }
         }
     }
 
+    /// A buffered [`FileReader`], for code that does many small reads (e.g. tile-by-tile or
+    /// record-by-record) and would otherwise cross into `ffi::FileRead` -- and toggle NDS
+    /// file-transfer mode -- once per read.
+    ///
+    /// Reads are serviced out of an internal fill buffer, only calling down to the underlying
+    /// [`FileReader`] once the buffer drains. A read at least as large as the buffer's capacity
+    /// bypasses it (and goes straight to the underlying reader) rather than filling the buffer
+    /// just to immediately copy back out of it.
+    pub struct BufFileReader<'a> {
+        inner: FileReader<'a>,
+        buf: Vec<u8>,
+        pos: usize,
+        filled: usize,
+    }
+
+    impl<'a> BufFileReader<'a> {
+        /// The fill buffer size used by [`Self::new`].
+        const DEFAULT_CAPACITY: usize = 512;
+
+        /// Creates a buffered reader for `file` with a default-sized fill buffer.
+        ///
+        /// During the lifetime of the reader the NDS will be put in file-transfer mode, if it
+        /// isn't already.
+        pub fn new(file: &'a mut File) -> Self {
+            Self::with_capacity(Self::DEFAULT_CAPACITY, file)
+        }
+
+        /// Creates a buffered reader for `file` with a fill buffer sized to `capacity`, so callers
+        /// can size it to their access pattern.
+        ///
+        /// During the lifetime of the reader the NDS will be put in file-transfer mode, if it
+        /// isn't already.
+        pub fn with_capacity(capacity: usize, file: &'a mut File) -> Self {
+            Self {
+                inner: FileReader::new(file),
+                buf: vec![0u8; capacity],
+                pos: 0,
+                filled: 0,
+            }
+        }
+
+        /// Refills the buffer from the underlying reader if it's currently empty, and returns how
+        /// many buffered bytes are available afterwards.
+        fn fill_buf(&mut self) -> super::Result<usize> {
+            if self.pos == self.filled {
+                self.filled = self.inner.read(&mut self.buf)?;
+                self.pos = 0;
+            }
+            Ok(self.filled - self.pos)
+        }
+    }
+
+    impl<'a> Read for BufFileReader<'a> {
+        fn read(&mut self, dst: &mut [u8]) -> super::Result<usize> {
+            if self.pos == self.filled && dst.len() >= self.buf.len() {
+                return self.inner.read(dst);
+            }
+            let available = self.fill_buf()?;
+            let n = available.min(dst.len());
+            dst[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+            self.pos += n;
+            Ok(n)
+        }
+    }
+
+    impl<'a> Seek for BufFileReader<'a> {
+        /// Discards the fill buffer and repositions the underlying stream; see
+        /// [`FileReader::seek`] for how the target address is resolved and validated.
+        fn seek(&mut self, pos: SeekFrom) -> super::Result<u64> {
+            self.pos = 0;
+            self.filled = 0;
+            self.inner.seek(pos)
+        }
+    }
+
     impl Drop for File {
         fn drop(&mut self) {
             unsafe {
@@ -1,5 +1,7 @@
 //! Structs and functions to interact with the data of items in a general context.
 
+use crate::api::game_id::GameId;
+use crate::api::gameplay::iq_skill_flag_test;
 use crate::ffi;
 
 /// An IQ Group ID with associated methods to get metadata.
@@ -10,6 +12,9 @@ impl Copy for IqGroupId {}
 
 /// This impl provides general metadata about IQ Groups in the game.
 impl IqGroupId {
+    /// The number of IQ Groups in the game (Group A through Group H).
+    pub const COUNT: u32 = 8;
+
     /// Returns the ID struct for the IQ Group with the given ID.
     ///
     /// # Safety
@@ -19,10 +24,52 @@ impl IqGroupId {
         Self(id)
     }
 
+    /// Returns the ID struct for the IQ Group with the given ID, or `None` if `id` is out of
+    /// range (i.e. there are no `id >= Self::COUNT` IQ Groups).
+    ///
+    /// Unlike [`Self::get`], this is safe, since it checks `id` against [`Self::COUNT`] before
+    /// constructing the ID.
+    pub const fn try_get(id: u32) -> Option<Self> {
+        if id < Self::COUNT {
+            Some(Self(id))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over every valid IQ Group ID, in order.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).map(Self)
+    }
+
     /// Returns the ID of this IQ Group.
     pub const fn id(&self) -> u32 {
         self.0
     }
+
+    /// Returns an iterator over every IQ Skill this group is allowed to learn (see
+    /// [`IqSkillId::is_available_for`]).
+    pub fn skills(&self) -> impl Iterator<Item = IqSkillId> + '_ {
+        IqSkillId::iter().filter(|skill| skill.is_available_for(*self))
+    }
+
+    /// Returns whether this group is allowed to learn `skill` (see
+    /// [`IqSkillId::is_available_for`]).
+    pub fn contains(&self, skill: IqSkillId) -> bool {
+        skill.is_available_for(*self)
+    }
+}
+
+impl GameId for IqGroupId {
+    const COUNT: u32 = Self::COUNT;
+
+    fn raw(&self) -> u32 {
+        self.id()
+    }
+
+    unsafe fn from_raw(id: u32) -> Self {
+        Self::get(id)
+    }
 }
 
 /// An IQ Skill ID with associated methods to get metadata.
@@ -33,6 +80,9 @@ impl Copy for IqSkillId {}
 
 /// This impl provides general metadata about IQ Skills in the game.
 impl IqSkillId {
+    /// The number of IQ Skills in the game, including the "None" skill at ID 0.
+    pub const COUNT: u32 = 69;
+
     /// Returns the ID struct for the IQ Skill with the given ID.
     ///
     /// # Safety
@@ -42,8 +92,145 @@ impl IqSkillId {
         Self(id)
     }
 
+    /// Returns the ID struct for the IQ Skill with the given ID, or `None` if `id` is out of
+    /// range (i.e. there are no `id >= Self::COUNT` IQ Skills).
+    ///
+    /// Unlike [`Self::get`], this is safe, since it checks `id` against [`Self::COUNT`] before
+    /// constructing the ID.
+    pub const fn try_get(id: u32) -> Option<Self> {
+        if id < Self::COUNT {
+            Some(Self(id))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over every valid IQ Skill ID, in order (including the "None" skill at
+    /// ID 0).
+    pub fn iter() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).map(Self)
+    }
+
     /// Returns the ID of this IQ Skill.
     pub const fn id(&self) -> u32 {
         self.0
     }
+
+    /// Returns aggregate metadata about this IQ Skill (display name, description, IQ
+    /// requirement and group restrictions), read from the game's IQ skill tables.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub fn metadata(&self) -> IqSkillMetadata {
+        unsafe {
+            IqSkillMetadata {
+                name_string_id: ffi::GetIqSkillNameStringId(*self),
+                description_string_id: ffi::GetIqSkillDescriptionStringId(*self),
+                iq_required: ffi::GetIqSkillIqRequired(*self),
+                group_restrictions: ffi::GetIqSkillGroupRestrictions(*self),
+            }
+        }
+    }
+
+    /// Returns the IQ points required to unlock this skill.
+    ///
+    /// Equivalent to `self.metadata().iq_required`, but without reading the other fields.
+    pub fn iq_required(&self) -> u16 {
+        unsafe { ffi::GetIqSkillIqRequired(*self) }
+    }
+
+    /// Returns whether `group` is allowed to learn this skill at all (regardless of whether any
+    /// of its members currently have enough IQ).
+    pub fn is_available_for(&self, group: IqGroupId) -> bool {
+        self.metadata().group_restrictions & (1 << group.id()) != 0
+    }
+
+    /// Returns an iterator over every IQ Group allowed to learn this skill (see
+    /// [`Self::is_available_for`]).
+    pub fn groups(&self) -> impl Iterator<Item = IqGroupId> + '_ {
+        IqGroupId::iter().filter(|group| self.is_available_for(*group))
+    }
+}
+
+impl GameId for IqSkillId {
+    const COUNT: u32 = Self::COUNT;
+
+    fn raw(&self) -> u32 {
+        self.id()
+    }
+
+    unsafe fn from_raw(id: u32) -> Self {
+        Self::get(id)
+    }
+}
+
+/// Aggregate metadata about an IQ Skill, gathered from the game's IQ skill tables.
+///
+/// Note: unverified, ported from Irdkwia's notes.
+#[derive(Debug, Clone, Copy)]
+pub struct IqSkillMetadata {
+    /// The string ID of this skill's display name, to be looked up with
+    /// [`get_string_from_message_id`](crate::api::messages::get_string_from_message_id).
+    pub name_string_id: u32,
+    /// The string ID of this skill's description text.
+    pub description_string_id: u32,
+    /// The number of IQ points a monster needs for this skill to become available to it.
+    pub iq_required: u16,
+    /// A bitmask over [`IqGroupId`] (bit `n` set means [`IqGroupId`] `n` can learn this skill),
+    /// restricting which IQ Groups are allowed to learn this skill at all.
+    pub group_restrictions: u32,
+}
+
+/// A safe wrapper around a monster's raw IQ skill bitvector (bit `n` set means [`IqSkillId`] `n`
+/// is active), the same format [`ffi::IqSkillFlagTest`] reads.
+///
+/// Only `IqSkillFlagTest` exists as a dedicated game function; [`Self::insert`]/[`Self::remove`]
+/// set/clear the underlying bit directly instead of calling into the game, matching the bit
+/// layout `IqSkillFlagTest` itself reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IqSkillSet(u32);
+
+impl IqSkillSet {
+    /// Wraps a raw IQ skill bitvector.
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw IQ skill bitvector.
+    pub const fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns whether `skill` is active in this set, via the game's `IqSkillFlagTest`.
+    pub fn contains(&self, skill: IqSkillId) -> bool {
+        let mut bits = self.0;
+        iq_skill_flag_test(&mut bits, skill)
+    }
+
+    /// Activates `skill` in this set.
+    pub fn insert(&mut self, skill: IqSkillId) {
+        self.0 |= 1 << skill.id();
+    }
+
+    /// Deactivates `skill` in this set.
+    pub fn remove(&mut self, skill: IqSkillId) {
+        self.0 &= !(1 << skill.id());
+    }
+
+    /// Returns an iterator over every IQ Skill currently active in this set, via the game's
+    /// `IqSkillFlagTest`.
+    pub fn iter_active(&self) -> impl Iterator<Item = IqSkillId> + '_ {
+        IqSkillId::iter().filter(move |skill| self.contains(*skill))
+    }
+}
+
+impl From<u32> for IqSkillSet {
+    fn from(bits: u32) -> Self {
+        Self::from_bits(bits)
+    }
+}
+
+impl From<IqSkillSet> for u32 {
+    fn from(set: IqSkillSet) -> Self {
+        set.to_bits()
+    }
 }
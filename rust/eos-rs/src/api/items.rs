@@ -228,6 +228,20 @@ impl From<ItemId> for u32 {
 /// A quantity of zero indicates that the item is not stackable.
 pub type Item = ffi::item;
 
+/// The classification of an [`Item`] slot returned by [`Item::as_kind`]: either a single item, or
+/// part of a stack of identical thrown items sharing a count.
+pub enum ItemSlotKind {
+    /// A single item that isn't stacked (not a thrown item, or a thrown item with quantity 0).
+    Individual(Item),
+    /// A stack of `count` identical thrown items.
+    Stacked {
+        /// The item making up the stack.
+        item: Item,
+        /// The number of items in the stack.
+        count: u16,
+    },
+}
+
 impl Item {
     /// Allocates a new item.
     ///
@@ -258,6 +272,94 @@ impl Item {
         unsafe { ffi::InitItem(self, item_id, quantity, sticky as ffi::bool_) }
     }
 
+    /// Returns the ID of the item in this slot.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub fn id(&self) -> ItemId {
+        unsafe { ItemId::new(self.id.id() as u32) }
+    }
+
+    /// Returns the quantity of this item slot (see the type-level doc comment for what a
+    /// quantity of zero means).
+    pub fn quantity(&self) -> u16 {
+        self.quantity
+    }
+
+    /// Returns whether this slot holds no item (an item ID of `ITEM_NOTHING`/0).
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub fn is_empty(&self) -> bool {
+        self.id().id() == 0
+    }
+
+    /// Returns whether this item is sticky (can't be freely dropped or moved once picked up).
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub fn is_sticky(&self) -> bool {
+        self.sticky != 0
+    }
+
+    /// Classifies this item slot as either a single [`ItemSlotKind::Individual`] item, or part of
+    /// an [`ItemSlotKind::Stacked`] run of identical thrown items, based on
+    /// [`ItemId::can_be_thrown`] and the slot's quantity.
+    pub fn as_kind(&self) -> ItemSlotKind {
+        let id = self.id();
+        let quantity = self.quantity();
+        // `Item::new`/`init` resolve their own quantity for thrown items (randomized) rather than
+        // using the one we pass in, so build with a throwaway quantity and overwrite it after.
+        let mut item = Item::new(id, 0, self.is_sticky());
+        item.quantity = quantity;
+        if id.can_be_thrown() && quantity > 0 {
+            ItemSlotKind::Stacked { item, count: quantity }
+        } else {
+            ItemSlotKind::Individual(item)
+        }
+    }
+
+    /// Splits `amount` off this stack into a new item slot, clamped to the item's valid thrown
+    /// quantity range, leaving the remainder in `self`.
+    ///
+    /// Returns `None` if this isn't a stackable (thrown) item, or if splitting would leave either
+    /// half below the item's minimum thrown quantity.
+    pub fn split_stack(&mut self, amount: u16) -> Option<Item> {
+        let id = self.id();
+        if !id.can_be_thrown() {
+            return None;
+        }
+        let min = id.get_thrown_item_quantity_minimum() as u16;
+        let max = id.get_thrown_item_quantity_maximum() as u16;
+        let amount = amount.clamp(min, max);
+        if amount >= self.quantity() {
+            return None;
+        }
+        let remainder = self.quantity() - amount;
+        if remainder < min {
+            return None;
+        }
+        self.quantity = remainder;
+        Some(Item::new(id, amount, false))
+    }
+
+    /// Merges `other` into this stack, clamped to the item's maximum thrown quantity.
+    ///
+    /// Fails (returning `other` unchanged) if the two items have different IDs, if either is a
+    /// sticky item, if this isn't a stackable (thrown) item, or if the combined quantity would
+    /// exceed the item's maximum thrown quantity.
+    pub fn merge_stack(&mut self, other: Item) -> Result<(), Item> {
+        let id = self.id();
+        if id.id() != other.id().id() || !id.can_be_thrown() || self.is_sticky() || other.is_sticky()
+        {
+            return Err(other);
+        }
+        let max = id.get_thrown_item_quantity_maximum() as u16;
+        let merged = self.quantity().saturating_add(other.quantity());
+        if merged > max {
+            return Err(other);
+        }
+        self.quantity = merged;
+        Ok(())
+    }
+
     /// Returns the price that the item is sold at as it should be displayed in shops.
     pub fn sell_price_display(&self) -> i32 {
         unsafe { ffi::GetDisplayedSellPrice(force_mut_ptr!(self)) }
@@ -373,6 +475,13 @@ impl MoneyCarried {
         Self(PhantomData)
     }
 
+    /// Returns the amount of money the player is currently carrying.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub fn get_money(&self) -> i32 {
+        unsafe { ffi::GetMoneyCarried() }
+    }
+
     /// Sets the amount of money the player is carrying, clamping the value to the range
     /// [0, MAX_MONEY_CARRIED].
     pub fn set_money(&mut self, money: i32) {
@@ -521,6 +630,28 @@ impl InventoryBag {
         }
     }
 
+    /// Returns an iterator over every non-empty item slot in the bag, in index order.
+    ///
+    /// Empty slots (between `0` and [`Self::capacity`]) are skipped transparently, so this never
+    /// yields more than [`Self::len`] items.
+    pub fn iter(&self) -> impl Iterator<Item = &Item> + '_ {
+        (0..self.capacity()).filter_map(move |i| self.get_item(i))
+    }
+
+    /// Like [`Self::iter`], but yields mutable references.
+    pub fn iter_mut(&mut self) -> InventoryBagIterMut<'_> {
+        InventoryBagIterMut {
+            indices: 0..self.capacity(),
+            _bag: PhantomData,
+        }
+    }
+
+    /// Like [`Self::iter`], but pairs each item with the [`ItemSlot::Bag`] it was found at, using
+    /// the same discriminant as [`find_item_in_inventory`].
+    pub fn iter_slots(&self) -> impl Iterator<Item = (ItemSlot, &Item)> + '_ {
+        (0..self.capacity()).filter_map(move |i| self.get_item(i).map(|item| (ItemSlot::Bag(i), item)))
+    }
+
     /// Note: unverified, ported from Irdkwia's notes
     pub fn remove_empty_items(&mut self) {
         unsafe { ffi::RemoveEmptyItemsInBag() };
@@ -610,6 +741,29 @@ impl InventoryBag {
     }
 }
 
+/// A mutable iterator over the occupied slots of an [`InventoryBag`]. See
+/// [`InventoryBag::iter_mut`].
+pub struct InventoryBagIterMut<'a> {
+    indices: core::ops::Range<i32>,
+    _bag: PhantomData<&'a mut InventoryBag>,
+}
+
+impl<'a> Iterator for InventoryBagIterMut<'a> {
+    type Item = &'a mut Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for i in &mut self.indices {
+            // SAFETY: `i` is only ever handed out once across the lifetime of this iterator, so
+            // the mutable references it produces don't alias.
+            let item_ref = unsafe { ffi::GetItemAtIdx(i) };
+            if !item_ref.is_null() {
+                return Some(unsafe { &mut *item_ref });
+            }
+        }
+        None
+    }
+}
+
 /// The player's inventory in the storage.
 pub struct InventoryStorage(PhantomData<()>);
 
@@ -675,9 +829,59 @@ impl InventoryStorage {
     pub fn is_item_in_treasure_boxes(&self, item_id: ItemId) -> bool {
         unsafe { ffi::IsItemInTreasureBoxes(item_id) > 0 }
     }
+
+    /// The maximum number of distinct item slots the storage can hold.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub fn capacity(&self) -> i32 {
+        unsafe { ffi::GetStorageMaxCount() }
+    }
+
+    /// The number of distinct item slots currently occupied in the storage.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub fn len(&self) -> i32 {
+        unsafe { ffi::GetNbItemsInStorage() }
+    }
+
+    /// Returns whether the storage holds no items at all.
+    pub fn is_empty(&self) -> bool {
+        self.len() <= 0
+    }
+
+    /// Returns the item ID and quantity stored at the given storage slot index, if any.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub fn get_item(&self, index: i32) -> Option<(ItemId, u16)> {
+        let mut item: MaybeUninit<ffi::bulk_item> = MaybeUninit::zeroed();
+        let exists = unsafe { ffi::GetStorageItemAtIdx(index, item.as_mut_ptr()) > 0 };
+        if exists {
+            let item = unsafe { item.assume_init() };
+            Some((unsafe { ItemId::new(item.id.id() as u32) }, item.quantity))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over every occupied storage slot, in index order.
+    ///
+    /// Unlike [`InventoryBag::iter`], this yields owned `(item ID, quantity)` pairs rather than
+    /// `&Item` references, since the storage only tracks bulk item/quantity counts (see
+    /// [`ffi::bulk_item`]) and doesn't keep a full [`Item`] per slot in memory.
+    pub fn iter(&self) -> impl Iterator<Item = (ItemId, u16)> + '_ {
+        (0..self.capacity()).filter_map(move |i| self.get_item(i))
+    }
+
+    /// Like [`Self::iter`], but pairs each entry with the [`ItemSlot::Storage`] it was found at,
+    /// using the same discriminant as [`find_item_in_inventory`].
+    pub fn iter_slots(&self) -> impl Iterator<Item = (ItemSlot, ItemId, u16)> + '_ {
+        (0..self.capacity())
+            .filter_map(move |i| self.get_item(i).map(|(id, quantity)| (ItemSlot::Storage(i), id, quantity)))
+    }
 }
 
 /// An item slot in the players inventory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ItemSlot {
     /// A slot in the bag.
     Bag(i32),
@@ -713,3 +917,564 @@ pub fn find_item_in_inventory(
 pub unsafe fn get_item_id_from_list(list_id: i32, category_num: i32, item_num: i32) -> ItemId {
     unsafe { ffi::GetItemIdFromList(list_id, category_num, item_num) }
 }
+
+/// A safe, bounds-checked view over one of the game's item lists.
+///
+/// [`Self::new`] queries and caches the list's category count and the item count of each
+/// category once, so [`Self::get`] can range-check both indices in plain Rust before it ever
+/// calls down to [`get_item_id_from_list`], instead of pushing that validation onto every call
+/// site.
+///
+/// Note: unverified, ported from Irdkwia's notes (the category/item count queries this caches
+/// don't have a confirmed pmdsky-debug symbol yet).
+pub struct ItemList {
+    list_id: i32,
+    item_counts: alloc::vec::Vec<u32>,
+}
+
+impl ItemList {
+    /// Loads and caches the category/item counts for the item list with the given ID.
+    pub fn new(list_id: i32) -> Self {
+        let category_count = unsafe { ffi::GetItemListCategoryCount(list_id) }.max(0) as u32;
+        let item_counts = (0..category_count)
+            .map(|category| unsafe { ffi::GetItemListItemCount(list_id, category as i32) }.max(0) as u32)
+            .collect();
+        Self { list_id, item_counts }
+    }
+
+    /// The ID of this item list.
+    pub fn list_id(&self) -> i32 {
+        self.list_id
+    }
+
+    /// The number of categories in this list.
+    pub fn category_count(&self) -> u32 {
+        self.item_counts.len() as u32
+    }
+
+    /// The number of items in `category`, or `None` if `category` is out of range for this list.
+    pub fn item_count(&self, category: u32) -> Option<u32> {
+        self.item_counts.get(category as usize).copied()
+    }
+
+    /// Returns the item at `(category, item)`, or `None` if either index is out of range for
+    /// this list, instead of triggering the UB [`get_item_id_from_list`] would on an invalid
+    /// index.
+    pub fn get(&self, category: u32, item: u32) -> Option<ItemId> {
+        if item >= self.item_count(category)? {
+            return None;
+        }
+        // SAFETY: Both indices were just range-checked against the cached counts.
+        Some(unsafe { get_item_id_from_list(self.list_id, category as i32, item as i32) })
+    }
+
+    /// Returns an iterator over every item in this list, yielding `(CategoryIndex, ItemIndex,
+    /// ItemId)` in category-then-item order, driving [`Self::get`] with indices already known to
+    /// be in range.
+    pub fn iter(&self) -> ItemListIter<'_> {
+        ItemListIter {
+            list: self,
+            category: 0,
+            item: 0,
+        }
+    }
+
+    /// Collects every item in this list into a `Vec`, mirroring slice-style access for
+    /// snapshotting a list.
+    pub fn as_vec(&self) -> alloc::vec::Vec<(CategoryIndex, ItemIndex, ItemId)> {
+        self.iter().collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a ItemList {
+    type Item = (CategoryIndex, ItemIndex, ItemId);
+    type IntoIter = ItemListIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over every item in an [`ItemList`]. See [`ItemList::iter`].
+pub struct ItemListIter<'a> {
+    list: &'a ItemList,
+    category: u32,
+    item: u32,
+}
+
+impl<'a> Iterator for ItemListIter<'a> {
+    type Item = (CategoryIndex, ItemIndex, ItemId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.category < self.list.category_count() {
+            let count = self.list.item_count(self.category).unwrap_or(0);
+            if self.item < count {
+                let category = self.category;
+                let item = self.item;
+                self.item += 1;
+                let id = self.list.get(category, item)?;
+                return Some((CategoryIndex::from_u32(category), ItemIndex::from_u32(item), id));
+            }
+            self.category += 1;
+            self.item = 0;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod item_list_test {
+    use super::ItemList;
+
+    fn list_with_counts(counts: &[u32]) -> ItemList {
+        ItemList {
+            list_id: 0,
+            item_counts: counts.to_vec(),
+        }
+    }
+
+    #[test]
+    fn zero_length_category_never_yields_an_item() {
+        let list = list_with_counts(&[0]);
+        assert_eq!(list.item_count(0), Some(0));
+    }
+
+    #[test]
+    fn category_out_of_range_is_none() {
+        let list = list_with_counts(&[3]);
+        assert_eq!(list.item_count(1), None);
+    }
+
+    #[test]
+    fn item_index_equal_to_count_is_out_of_range() {
+        let list = list_with_counts(&[3]);
+        assert_eq!(list.item_count(0), Some(3));
+        // `get` would range-check `item == count` as out of bounds; we can't call it without the
+        // real FFI, but we can assert the count it would check against directly.
+        assert!(3 >= list.item_count(0).unwrap());
+    }
+
+    #[test]
+    fn item_index_one_below_count_is_in_range() {
+        let list = list_with_counts(&[3]);
+        assert!(2 < list.item_count(0).unwrap());
+    }
+}
+
+/// Defines a newtype around `u32` with a checked valid range `0..=MAX`, modeled on rustc's
+/// `newtype_index!`. See [`ListId`], [`CategoryIndex`], [`ItemIndex`].
+macro_rules! checked_index {
+    ($(#[$attr:meta])* $name:ident, max = $max:expr) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+        pub struct $name(u32);
+
+        impl $name {
+            /// The largest value this index can hold.
+            pub const MAX: u32 = $max;
+
+            /// Constructs this index from a raw value.
+            ///
+            /// # Panics
+            /// Panics if `v > Self::MAX`.
+            pub const fn from_u32(v: u32) -> Self {
+                assert!(v <= Self::MAX, "index out of range");
+                Self(v)
+            }
+
+            /// Constructs this index from a raw value, or returns `None` if it's out of range.
+            pub const fn try_from_u32(v: u32) -> Option<Self> {
+                if v <= Self::MAX {
+                    Some(Self(v))
+                } else {
+                    None
+                }
+            }
+
+            /// Returns the raw value of this index.
+            pub const fn get(self) -> u32 {
+                self.0
+            }
+        }
+    };
+}
+
+checked_index!(
+    /// A validated index into the game's item lists (the `list_id` parameter of
+    /// [`get_item_id_from_list`]).
+    ///
+    /// `MAX` here is a generous sentinel, not a precisely known game-data limit (unlike e.g.
+    /// [`crate::api::iq::IqGroupId`]'s `COUNT`); prefer [`ItemList`]'s cached per-list counts
+    /// when the exact valid range for a specific list matters.
+    ListId,
+    max = 0xFFFF
+);
+checked_index!(
+    /// A validated index into one of an item list's categories (the `category_num` parameter of
+    /// [`get_item_id_from_list`]). See [`ListId`] for the caveat on `MAX`.
+    CategoryIndex,
+    max = 0xFF
+);
+checked_index!(
+    /// A validated index into one of an item list category's items (the `item_num` parameter of
+    /// [`get_item_id_from_list`]). See [`ListId`] for the caveat on `MAX`.
+    ItemIndex,
+    max = 0xFFFF
+);
+
+/// Like [`get_item_id_from_list`], but safe: the index newtypes already carry the "valid index"
+/// invariant, checked once at construction (see [`ListId::from_u32`]/[`ListId::try_from_u32`] and
+/// friends), instead of needing to be re-checked (or trusted) at every call site.
+pub fn get_item_from_list(list: ListId, category: CategoryIndex, item: ItemIndex) -> ItemId {
+    // SAFETY: `ListId`/`CategoryIndex`/`ItemIndex` only ever hold values that passed their own
+    // range check at construction.
+    unsafe { get_item_id_from_list(list.get() as i32, category.get() as i32, item.get() as i32) }
+}
+
+#[cfg(test)]
+mod checked_index_test {
+    use super::ListId;
+
+    #[test]
+    fn try_from_u32_accepts_values_up_to_max() {
+        assert_eq!(ListId::try_from_u32(ListId::MAX).map(ListId::get), Some(ListId::MAX));
+    }
+
+    #[test]
+    fn try_from_u32_rejects_values_above_max() {
+        assert_eq!(ListId::try_from_u32(ListId::MAX + 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_u32_panics_above_max() {
+        ListId::from_u32(ListId::MAX + 1);
+    }
+}
+
+/// Returns whether `id` always moves as a single unit between the bag and storage, regardless of
+/// the requested quantity: non-throwable items, sticky items, and exclusive items.
+fn moves_as_single_unit(id: ItemId, sticky: bool) -> bool {
+    !id.can_be_thrown() || sticky || id.get_exclusive_item_offset() > 0
+}
+
+/// Moves `quantity` of the item at bag slot `slot` into storage.
+///
+/// Non-stackable, sticky and exclusive items always move as a single unit, regardless of
+/// `quantity`. The moved item ID is passed through [`ItemId::normalize_treasure_box`] first,
+/// matching how the game would treat a treasure box being put into storage. Fails cleanly
+/// (nothing moved) if `slot` is empty, doesn't hold enough of the item, or the storage is full;
+/// the destination is checked before anything is removed from the bag.
+pub fn deposit(bag: &mut InventoryBag, storage: &mut InventoryStorage, slot: i32, quantity: u16) -> bool {
+    let Some(item) = bag.get_item(slot) else {
+        return false;
+    };
+    let id = item.id();
+    let held = item.quantity();
+    let single_unit = moves_as_single_unit(id, item.is_sticky());
+
+    let moved = if single_unit {
+        held.max(1)
+    } else {
+        if quantity == 0 || quantity > held {
+            return false;
+        }
+        quantity
+    };
+
+    if storage.is_full() || !storage.add_item(id.normalize_treasure_box(), moved) {
+        return false;
+    }
+
+    if !single_unit && moved < held {
+        // Partial withdrawal from the stack: split it in place instead of removing the slot.
+        if let Some(item) = bag.get_item_mut(slot) {
+            item.split_stack(moved);
+        }
+    } else {
+        bag.remove_item_no_hole(slot);
+    }
+
+    true
+}
+
+/// Moves `quantity` of the item at storage slot `slot` into the bag.
+///
+/// Non-stackable, sticky and exclusive items always move as a single unit, regardless of
+/// `quantity`. The moved item ID is passed through [`ItemId::normalize_treasure_box`] first,
+/// matching how the game would treat a treasure box being taken out of storage. Fails cleanly
+/// (nothing moved) if `slot` is empty, doesn't hold enough of the item, or the bag is full; the
+/// destination is checked before anything is removed from storage.
+pub fn withdraw(bag: &mut InventoryBag, storage: &mut InventoryStorage, slot: i32, quantity: u16) -> bool {
+    let Some((id, held)) = storage.get_item(slot) else {
+        return false;
+    };
+    let id = id.normalize_treasure_box();
+    let single_unit = moves_as_single_unit(id, false);
+
+    let moved = if single_unit {
+        held.max(1)
+    } else {
+        if quantity == 0 || quantity > held {
+            return false;
+        }
+        quantity
+    };
+
+    if bag.is_full() {
+        return false;
+    }
+    if !storage.remove_item(id, moved) {
+        return false;
+    }
+
+    let mut item = Item::new(id, moved, false);
+    if !bag.add_item(&mut item, None) {
+        // Shouldn't happen since we just checked `bag.is_full()`, but put the item back in
+        // storage rather than losing it.
+        storage.add_item(id, moved);
+        return false;
+    }
+
+    true
+}
+
+/// The inverse of one mutation applied through an [`InventoryTransaction`], recorded so it can be
+/// undone on rollback.
+enum InventoryTransactionOp {
+    /// Undo a bag addition by removing the item that ended up at this index.
+    BagAdd { index: i32 },
+    /// Undo a bag removal by re-adding the item that was removed (captured before deletion).
+    BagRemove { item: Item, holder: Option<i32> },
+    /// Undo a storage addition by removing the same item/quantity again.
+    StorageAdd { item_id: ItemId, quantity: u16 },
+    /// Undo a storage removal by adding the same item/quantity back.
+    StorageRemove { item_id: ItemId, quantity: u16 },
+    /// Undo a money change by applying the reverse delta.
+    Money(i32),
+}
+
+/// A batch of [`InventoryBag`]/[`InventoryStorage`]/[`MoneyCarried`] mutations that can be
+/// committed all at once or rolled back atomically.
+///
+/// Each method stages one mutation and, if it actually took effect, records the inverse operation
+/// needed to undo it. Call [`Self::commit`] once every staged mutation has succeeded to discard
+/// that journal; otherwise, dropping the transaction (or calling [`Self::rollback`] explicitly)
+/// replays the inverses in LIFO order, so a transaction that fails partway through leaves the
+/// inventory exactly as it found it.
+///
+/// Item removals are captured with [`InventoryBag::copy_item`] *before* the destructive FFI call,
+/// and use the no-hole removal variants so that indices recorded earlier in the journal are still
+/// valid if rollback needs to re-add an item at a later index.
+pub struct InventoryTransaction<'a> {
+    bag: &'a mut InventoryBag,
+    storage: &'a mut InventoryStorage,
+    money: &'a mut MoneyCarried,
+    ops: alloc::vec::Vec<InventoryTransactionOp>,
+    resolved: bool,
+}
+
+impl<'a> InventoryTransaction<'a> {
+    /// Starts a new transaction over the given bag, storage and carried money.
+    pub fn new(
+        bag: &'a mut InventoryBag,
+        storage: &'a mut InventoryStorage,
+        money: &'a mut MoneyCarried,
+    ) -> Self {
+        Self {
+            bag,
+            storage,
+            money,
+            ops: alloc::vec::Vec::new(),
+            resolved: false,
+        }
+    }
+
+    /// Stages adding `item` to the bag. Returns whether the item could be added.
+    pub fn add_item_to_bag(&mut self, item: &mut Item, holder: Option<i32>) -> bool {
+        let added = self.bag.add_item(item, holder);
+        if added {
+            let index = self.bag.get_index_of_item(item);
+            self.ops.push(InventoryTransactionOp::BagAdd { index });
+        }
+        added
+    }
+
+    /// Stages removing the item at `index` from the bag (no-hole). Returns whether an item was
+    /// there to remove.
+    pub fn remove_item_from_bag(&mut self, index: i32) -> bool {
+        let Some(item) = self.bag.copy_item(index) else {
+            return false;
+        };
+        self.bag.remove_item_no_hole(index);
+        self.ops
+            .push(InventoryTransactionOp::BagRemove { item, holder: None });
+        true
+    }
+
+    /// Stages adding `quantity` of `item_id` to storage. Returns whether any could be added.
+    pub fn add_item_to_storage(&mut self, item_id: ItemId, quantity: u16) -> bool {
+        let added = self.storage.add_item(item_id, quantity);
+        if added {
+            self.ops
+                .push(InventoryTransactionOp::StorageAdd { item_id, quantity });
+        }
+        added
+    }
+
+    /// Stages removing `quantity` of `item_id` from storage. Returns whether it could be removed.
+    pub fn remove_item_from_storage(&mut self, item_id: ItemId, quantity: u16) -> bool {
+        let removed = self.storage.remove_item(item_id, quantity);
+        if removed {
+            self.ops
+                .push(InventoryTransactionOp::StorageRemove { item_id, quantity });
+        }
+        removed
+    }
+
+    /// Stages applying `delta` to the money the player is carrying.
+    pub fn add_money(&mut self, delta: i32) {
+        let current = self.money.get_money();
+        self.money.set_money(current + delta);
+        self.ops.push(InventoryTransactionOp::Money(-delta));
+    }
+
+    /// Commits the transaction, discarding the undo journal. The staged mutations stay applied.
+    pub fn commit(mut self) {
+        self.resolved = true;
+    }
+
+    /// Rolls back every mutation staged so far, in LIFO order.
+    pub fn rollback(mut self) {
+        self.undo_all();
+        self.resolved = true;
+    }
+
+    fn undo_all(&mut self) {
+        while let Some(op) = self.ops.pop() {
+            match op {
+                InventoryTransactionOp::BagAdd { index } => {
+                    self.bag.remove_item_no_hole(index);
+                }
+                InventoryTransactionOp::BagRemove { mut item, holder } => {
+                    self.bag.add_item(&mut item, holder);
+                }
+                InventoryTransactionOp::StorageAdd { item_id, quantity } => {
+                    self.storage.remove_item(item_id, quantity);
+                }
+                InventoryTransactionOp::StorageRemove { item_id, quantity } => {
+                    self.storage.add_item(item_id, quantity);
+                }
+                InventoryTransactionOp::Money(delta) => {
+                    let current = self.money.get_money();
+                    self.money.set_money(current + delta);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Drop for InventoryTransaction<'a> {
+    fn drop(&mut self) {
+        if !self.resolved {
+            self.undo_all();
+        }
+    }
+}
+
+/// Tracks whether an [`InventorySession`] is currently live. See [`InventorySession::new`].
+static mut INVENTORY_SESSION_ACTIVE: bool = false;
+
+/// An RAII guard that owns the player's bag, storage and money (carried and stored), replacing
+/// the separate `unsafe fn get()` constructors on [`InventoryBag`]/[`InventoryStorage`]/
+/// [`MoneyCarried`]/[`MoneyStored`] with a single token the borrow checker can reason about.
+///
+/// Only one session may be live at a time: acquiring a second one while the first hasn't been
+/// dropped yet panics. `&session` hands out the read-only views below, `&mut session` hands out
+/// the mutable ones, so it's the borrow checker - not a runtime check - that then prevents two
+/// live mutable views of the same globals.
+///
+/// The old `unsafe fn get()` constructors on the individual types are still available for
+/// call sites that don't need the guarantee this provides.
+pub struct InventorySession {
+    bag: InventoryBag,
+    storage: InventoryStorage,
+    money_carried: MoneyCarried,
+    money_stored: MoneyStored,
+}
+
+impl InventorySession {
+    /// Starts a new inventory session.
+    ///
+    /// # Safety
+    /// Same safety requirements as [`InventoryBag::get`]/[`InventoryStorage::get`]/
+    /// [`MoneyCarried::get`]/[`MoneyStored::get`]: the caller must make sure this is called at a
+    /// point where those globals are valid to access.
+    ///
+    /// # Panics
+    /// Panics if another [`InventorySession`] is already live.
+    pub unsafe fn new() -> Self {
+        // SAFETY: We only have one thread, we are sure we are the only ones calling this.
+        unsafe {
+            if INVENTORY_SESSION_ACTIVE {
+                panic!("An InventorySession is already active; only one may be live at a time.");
+            }
+            INVENTORY_SESSION_ACTIVE = true;
+        }
+        Self {
+            bag: unsafe { InventoryBag::get() },
+            storage: unsafe { InventoryStorage::get() },
+            money_carried: unsafe { MoneyCarried::get() },
+            money_stored: unsafe { MoneyStored::get() },
+        }
+    }
+
+    /// Returns a read-only view of the player's bag.
+    pub fn bag(&self) -> &InventoryBag {
+        &self.bag
+    }
+
+    /// Returns a mutable view of the player's bag.
+    pub fn bag_mut(&mut self) -> &mut InventoryBag {
+        &mut self.bag
+    }
+
+    /// Returns a read-only view of the player's storage.
+    pub fn storage(&self) -> &InventoryStorage {
+        &self.storage
+    }
+
+    /// Returns a mutable view of the player's storage.
+    pub fn storage_mut(&mut self) -> &mut InventoryStorage {
+        &mut self.storage
+    }
+
+    /// Returns a read-only view of the money the player is carrying.
+    pub fn money_carried(&self) -> &MoneyCarried {
+        &self.money_carried
+    }
+
+    /// Returns a mutable view of the money the player is carrying.
+    pub fn money_carried_mut(&mut self) -> &mut MoneyCarried {
+        &mut self.money_carried
+    }
+
+    /// Returns a read-only view of the money the player has stored at the Duskull Bank.
+    pub fn money_stored(&self) -> &MoneyStored {
+        &self.money_stored
+    }
+
+    /// Returns a mutable view of the money the player has stored at the Duskull Bank.
+    pub fn money_stored_mut(&mut self) -> &mut MoneyStored {
+        &mut self.money_stored
+    }
+}
+
+impl Drop for InventorySession {
+    fn drop(&mut self) {
+        // SAFETY: We only have one thread, we are sure we are the only ones calling this.
+        unsafe {
+            INVENTORY_SESSION_ACTIVE = false;
+        }
+    }
+}
@@ -2,7 +2,7 @@
 //! operations.
 
 use alloc::borrow::ToOwned;
-use alloc::ffi::CString;
+use alloc::ffi::{CString, IntoStringError};
 use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
@@ -15,6 +15,89 @@ use crate::string_util::str_to_cstring;
 
 static mut NULL: c_char = 0;
 
+/// A handler for a custom `[tag:args]` placeholder registered with [`register_string_tag`].
+///
+/// `args` is everything after the tag name and its separating `:` inside the brackets (empty if
+/// the tag had no `:`); the handler returns the literal text the tag should expand to.
+pub type StringTagHandler = fn(args: &str) -> String;
+
+struct StringTag {
+    name: &'static str,
+    handler: StringTagHandler,
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// message formatting only ever happens from the main game loop.
+static mut STRING_TAGS: Vec<StringTag> = Vec::new();
+
+/// Registers a custom `[name]`/`[name:args]` placeholder tag for use in
+/// [`GameStringBuilder`]-formatted strings.
+///
+/// Unlike the tags `PreprocessString` itself understands, a registered tag is expanded in Rust
+/// before the rest of the format string is handed to `PreprocessString`, so `handler` can compute
+/// arbitrary replacement text (e.g. a custom counter or a computed stat) instead of having to be
+/// expressible as one of the fixed preprocessor-args slots.
+///
+/// `name` is matched case-sensitively and should be unique; a colliding name is dispatched to
+/// whichever tag registered first.
+pub fn register_string_tag(name: &'static str, handler: StringTagHandler) {
+    // SAFETY: single-threaded; see `STRING_TAGS`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        STRING_TAGS.push(StringTag { name, handler });
+    }
+}
+
+/// Expands every occurrence of a registered tag in `format`, leaving any other `[...]` tag (i.e.
+/// one `PreprocessString` itself understands, or one that's simply unregistered) untouched for
+/// `PreprocessString` to handle as usual.
+fn expand_custom_tags(format: &str) -> String {
+    let mut out = String::with_capacity(format.len());
+    let mut rest = format;
+    while let Some(open) = rest.find('[') {
+        let Some(close) = rest[open..].find(']') else {
+            out.push_str(rest);
+            return out;
+        };
+        let close = open + close;
+        out.push_str(&rest[..open]);
+        let inner = &rest[open + 1..close];
+        let (name, args) = match inner.split_once(':') {
+            Some((name, args)) => (name, args),
+            None => (inner, ""),
+        };
+        // SAFETY: single-threaded; see `STRING_TAGS`.
+        #[allow(static_mut_refs)]
+        let handler = unsafe {
+            STRING_TAGS
+                .iter()
+                .find(|tag| tag.name == name)
+                .map(|tag| tag.handler)
+        };
+        match handler {
+            Some(handler) => out.push_str(&handler(args)),
+            None => out.push_str(&rest[open..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// An error returned by the `try_build*` [`GameStringBuilder`] methods.
+#[derive(Debug)]
+pub enum PreprocessStringError {
+    /// The formatted string didn't fit in the output buffer and was truncated.
+    ///
+    /// `truncated` is the (truncated) string `PreprocessString` actually produced; `output_size`
+    /// is the capacity of the buffer that was used (see
+    /// [`GameStringBuilder::output_buffer_size`]).
+    Overflow {
+        truncated: CString,
+        output_size: i32,
+    },
+}
+
 enum PreprocessorArgs<'a> {
     Owned(ffi::preprocessor_args),
     Borrowed(&'a mut ffi::preprocessor_args)
@@ -39,6 +122,7 @@ impl<'a> PreprocessorArgs<'a> {
 /// See <https://textbox.skytemple.org> for a reference about message tags and a preview tool.
 pub struct GameStringBuilder<'a> {
     output_size: Option<i32>,  // Note: Auto
+    auto_grow: bool,
     flags: ffi::preprocessor_flags,
     args: PreprocessorArgs<'a>,
 }
@@ -49,6 +133,7 @@ impl<'a> GameStringBuilder<'a> {
         unsafe {
             Self {
                 output_size: None,
+                auto_grow: false,
                 flags: ffi::preprocessor_flags { _bitfield_align_1: [], _bitfield_1: Default::default() },
                 args: PreprocessorArgs::Owned(ffi::preprocessor_args {
                     flag_vals: [0, 0, 0, 0],
@@ -87,7 +172,7 @@ impl<'a> GameStringBuilder<'a> {
     /// Sets the speaker of the message to the given entity (probably actor or monster ID...?).
     ///
     /// To actually also show the speaker, use [`Self::show_speaker`].
-    pub fn set_speaker(&'a mut self, speaker: u32) -> &'a mut Self {
+    pub fn set_speaker(&mut self, speaker: u32) -> &mut Self {
         self.args.as_mut().speaker_id = speaker;
         self
     }
@@ -95,7 +180,7 @@ impl<'a> GameStringBuilder<'a> {
     /// Sets flag values. Currently unknown what they do.
     ///
     /// Max flag ID is 3.
-    pub fn set_flag_value(&'a mut self, flag_id: usize, value: u32) -> &'a mut Self {
+    pub fn set_flag_value(&mut self, flag_id: usize, value: u32) -> &mut Self {
         self.args.as_mut().flag_vals[flag_id] = value;
         self
     }
@@ -104,7 +189,7 @@ impl<'a> GameStringBuilder<'a> {
     /// `\[item:id\]` etc. placeholders,
     ///
     /// Max ID ID is 4.
-    pub fn set_id_value(&'a mut self, id_id: usize, value: u32) -> &'a mut Self {
+    pub fn set_id_value(&mut self, id_id: usize, value: u32) -> &mut Self {
         self.args.as_mut().id_vals[id_id] = value;
         self
     }
@@ -113,7 +198,7 @@ impl<'a> GameStringBuilder<'a> {
     /// etc. placeholders.
     ///
     /// Max number ID is 4.
-    pub fn set_number_value(&'a mut self, number_id: usize, value: i32) -> &'a mut Self {
+    pub fn set_number_value(&mut self, number_id: usize, value: i32) -> &mut Self {
         self.args.as_mut().number_vals[number_id] = value;
         self
     }
@@ -123,7 +208,11 @@ impl<'a> GameStringBuilder<'a> {
     /// Replace all occurrences of `\[string:<string_id>\]` with the value of the string passed in.
     ///
     /// Max string ID is 4.
-    pub fn set_string(&'a mut self, string_id: usize, string: &'a CString) -> &'a mut Self {
+    ///
+    /// `string` still has to outlive the builder (it's stored as a raw pointer passed straight
+    /// through to `PreprocessString`), but unlike the other setters, taking it by `&'a` reference
+    /// rather than borrowing `self` for `'a` is what lets this be chained with the rest.
+    pub fn set_string(&mut self, string_id: usize, string: &'a CString) -> &mut Self {
         self.args.as_mut().strings[string_id] = string.as_ptr() as *mut _;
         self
     }
@@ -134,6 +223,22 @@ impl<'a> GameStringBuilder<'a> {
         self
     }
 
+    /// If enabled, a `build*`/`try_build*` call that looks like it truncated the output (i.e.
+    /// `PreprocessString` wrote all the way to the end of the output buffer) doubles the buffer
+    /// and retries, instead of just handing back the truncated result.
+    ///
+    /// This is meant for deeply-nested tag expansion, where placeholders expand to text that
+    /// itself contains more placeholders, making the `input.len() * 3` default (or a manually set
+    /// [`Self::output_buffer_size`]) unreliable to guess up front.
+    ///
+    /// Retries double the buffer up to [`Self::MAX_AUTO_GROW_ITERATIONS`] times (capping at
+    /// `i32::MAX`) before giving up and returning whatever the last attempt produced, the same as
+    /// if this were disabled.
+    pub fn auto_grow(&mut self, enabled: bool) -> &mut Self {
+        self.auto_grow = enabled;
+        self
+    }
+
     /// Returns a reference to the internal args. This will panic if [`Self::borrow_args`] was
     /// called before.
     pub fn args(&self) -> &ffi::preprocessor_args {
@@ -170,7 +275,10 @@ impl<'a> GameStringBuilder<'a> {
     ///
     /// You probably don't need to use this method in most cases, just use the above mentioned
     /// methods instead.
-    pub fn borrow_args(&'a mut self, args: &'a mut ffi::preprocessor_args) -> &'a mut Self {
+    ///
+    /// Like [`Self::set_string`], `args` is taken by `&'a` reference rather than borrowing `self`
+    /// for `'a`, so this can still be chained with the other setters.
+    pub fn borrow_args(&mut self, args: &'a mut ffi::preprocessor_args) -> &mut Self {
         self.args = PreprocessorArgs::Borrowed(args);
         self
     }
@@ -178,65 +286,122 @@ impl<'a> GameStringBuilder<'a> {
     /// Converts the format string to the formatted string.
     ///
     /// Builds String from a str. The input is the format string to use.
-    #[allow(clippy::needless_return)]
+    ///
+    /// Any tag registered with [`register_string_tag`] is expanded before the rest of the format
+    /// string reaches `PreprocessString`.
+    ///
+    /// # Panics
+    /// Panics if the formatted message isn't valid UTF-8 (e.g. a speaker name pulled from save
+    /// data, or a tag that expands to non-UTF-8 bytes). Use [`Self::try_build`] to recover from
+    /// this instead.
     pub fn build<S: AsRef<str>>(self, format: S) -> String {
-        #[cfg(debug_assertions)]
-        return self.build_from_cstr_as_cstring(str_to_cstring(format.as_ref())).into_string().expect("Failed to convert game string to String (invalid UTF-8)");
-        // Save some precious size in release mode
-        #[cfg(not(debug_assertions))]
-        self.build_from_cstr_as_cstring(str_to_cstring(format.as_ref())).into_string().unwrap()
+        self.try_build(format)
+            .expect("Failed to convert game string to String (invalid UTF-8)")
+    }
+
+    /// Like [`Self::build`], but returns the raw bytes (as an [`IntoStringError`] wrapping the
+    /// offending [`CString`]) instead of panicking if the formatted message isn't valid UTF-8.
+    pub fn try_build<S: AsRef<str>>(self, format: S) -> Result<String, IntoStringError> {
+        let format = expand_custom_tags(format.as_ref());
+        self.build_from_cstr_as_cstring(str_to_cstring(&format))
+            .into_string()
     }
 
     /// Converts the format string to the formatted string.
     ///
     /// Builds String from a CStr. The input is the format string to use.
-    #[allow(clippy::needless_return)]
+    ///
+    /// # Panics
+    /// Panics if the formatted message isn't valid UTF-8. Use [`Self::try_build_from_cstr`] to
+    /// recover from this instead.
     pub fn build_from_cstr<S: AsRef<CStr>>(self, format: S) -> String {
-        #[cfg(debug_assertions)]
-        return self.build_from_cstr_as_cstring(format.as_ref()).into_string().expect("Failed to convert game string to String (invalid UTF-8)");
-        // Save some precious size in release mode
-        #[cfg(not(debug_assertions))]
-        self.build_from_cstr_as_cstring(format.as_ref()).into_string().unwrap()
+        self.try_build_from_cstr(format)
+            .expect("Failed to convert game string to String (invalid UTF-8)")
+    }
+
+    /// Like [`Self::build_from_cstr`], but returns the raw bytes (as an [`IntoStringError`]
+    /// wrapping the offending [`CString`]) instead of panicking if the formatted message isn't
+    /// valid UTF-8.
+    pub fn try_build_from_cstr<S: AsRef<CStr>>(self, format: S) -> Result<String, IntoStringError> {
+        self.build_from_cstr_as_cstring(format.as_ref()).into_string()
     }
 
     /// Converts the format string to the formatted string.
     ///
     /// Builds CString from a str. The input is the format string to use.
+    ///
+    /// Any tag registered with [`register_string_tag`] is expanded before the rest of the format
+    /// string reaches `PreprocessString`.
     pub fn build_as_cstring<S: AsRef<str>>(self, format: S) -> CString {
-        self.build_from_cstr_as_cstring(str_to_cstring(format.as_ref()))
+        let format = expand_custom_tags(format.as_ref());
+        self.build_from_cstr_as_cstring(str_to_cstring(&format))
     }
 
     /// Converts the format string to the formatted string.
     ///
     /// Builds CString from a CStr. The input is the format string to use.
     pub fn build_from_cstr_as_cstring<S: AsRef<CStr>>(self, format: S) -> CString {
+        match self.try_build_from_cstr_as_cstring(format) {
+            Ok(built) => built,
+            Err(PreprocessStringError::Overflow { truncated, .. }) => truncated,
+        }
+    }
+
+    /// How many times [`Self::auto_grow`] will double the output buffer before giving up.
+    pub const MAX_AUTO_GROW_ITERATIONS: u32 = 4;
+
+    /// Like [`Self::build_from_cstr_as_cstring`], but returns
+    /// [`PreprocessStringError::Overflow`] instead of silently handing back a string truncated to
+    /// the output buffer's capacity (unless [`Self::auto_grow`] manages to fit it first).
+    pub fn try_build_from_cstr_as_cstring<S: AsRef<CStr>>(
+        self,
+        format: S,
+    ) -> Result<CString, PreprocessStringError> {
         let Self {
-            output_size, flags, mut args
+            output_size, auto_grow, flags, mut args
         } = self;
 
-        let output_size = match output_size {
+        let mut output_size = match output_size {
             None => (format.as_ref().to_bytes().len() * 3).saturating_as(),
             Some(size) => size,
         };
-        // We manually transfer the internals of the output vector later, since we convert it to an
-        // u8 vector.
-        let mut output = ManuallyDrop::new(vec![0 as c_char; output_size as usize]);
-        unsafe {
-            let size = ffi::PreprocessString(
-                output.as_mut_ptr(),
-                output_size,
-                format.as_ref().as_ptr(),
-                flags,
-                args.as_mut()
-            );
-            output.truncate(size as usize + 1); // + 1 for the null byte.
-
-            // Convert output from Vec<i8> to Vec<u8> at no cost.
-            let output = Vec::from_raw_parts(
-                output.as_mut_ptr() as *mut u8, output.len(), output.capacity()
-            );
-
-            CString::from_vec_with_nul_unchecked(output)
+
+        let mut iterations = 0;
+        loop {
+            // We manually transfer the internals of the output vector later, since we convert it
+            // to an u8 vector.
+            let mut output = ManuallyDrop::new(vec![0 as c_char; output_size as usize]);
+            // SAFETY: `output` is a valid buffer of `output_size` elements; `PreprocessString`
+            // never writes past it.
+            let (built, size) = unsafe {
+                let size = ffi::PreprocessString(
+                    output.as_mut_ptr(),
+                    output_size,
+                    format.as_ref().as_ptr(),
+                    flags,
+                    args.as_mut()
+                );
+                output.truncate(size as usize + 1); // + 1 for the null byte.
+
+                // Convert output from Vec<i8> to Vec<u8> at no cost.
+                let output = Vec::from_raw_parts(
+                    output.as_mut_ptr() as *mut u8, output.len(), output.capacity()
+                );
+
+                (CString::from_vec_with_nul_unchecked(output), size)
+            };
+
+            // `PreprocessString` never writes past the buffer we gave it, so `size` reaching the
+            // buffer's capacity is our only signal that the real output didn't fit.
+            let truncated = size >= output_size;
+            if !truncated {
+                return Ok(built);
+            }
+            if !auto_grow || iterations >= Self::MAX_AUTO_GROW_ITERATIONS || output_size == i32::MAX {
+                return Err(PreprocessStringError::Overflow { truncated: built, output_size });
+            }
+            iterations += 1;
+            output_size = output_size.saturating_mul(2);
         }
     }
 }
@@ -253,6 +418,21 @@ pub fn get_string_from_message_id(message_id: i32) -> CString {
     unsafe { CStr::from_ptr(ffi::StringFromMessageId(message_id)) }.to_owned()
 }
 
+/// Looks up the message with the given ID and formats it with `builder`'s preprocessor
+/// arguments (see [`GameStringBuilder::set_id_value`]/[`GameStringBuilder::set_number_value`]/
+/// [`GameStringBuilder::set_string`] for passing item/move/monster IDs, numbers and nested
+/// message strings in), returning [`PreprocessStringError::Overflow`] instead of a silently
+/// truncated string if it doesn't fit in `builder`'s output buffer.
+///
+/// This is just [`get_string_from_message_id`] followed by
+/// [`GameStringBuilder::try_build_from_cstr_as_cstring`]; use those directly for more control.
+pub fn format_message(
+    message_id: i32,
+    builder: GameStringBuilder,
+) -> Result<CString, PreprocessStringError> {
+    builder.try_build_from_cstr_as_cstring(get_string_from_message_id(message_id))
+}
+
 /// Sets the palette of the frames of windows in the specified screen.
 pub fn set_screen_windows_color(palette_idx: u8, is_upper_window: bool) {
     unsafe { ffi::SetScreenWindowsColor(palette_idx as i32, is_upper_window as ffi::bool_) }
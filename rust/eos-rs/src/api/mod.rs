@@ -1,11 +1,14 @@
 //! High level API.
 
 pub mod abilities;
+pub mod custom_items;
 pub mod dungeon_mode;
 pub mod dungeons;
 pub mod fixed;
+pub mod game_id;
 pub mod gameplay;
 pub mod ground_mode;
+pub mod ground_process;
 #[cfg_attr(docsrs, doc(cfg(feature = "io")))]
 #[cfg(feature = "io")]
 pub mod io;
@@ -13,12 +16,27 @@ pub mod iq;
 pub mod items;
 pub mod math;
 pub mod messages;
+pub mod monster_filter;
 pub mod monsters;
 pub mod moves;
 pub mod overlay;
+#[cfg_attr(docsrs, doc(cfg(feature = "io")))]
+#[cfg(feature = "io")]
+pub mod overrides;
 pub mod random;
+pub mod region;
+pub mod save_data;
+pub mod save_states;
+#[cfg_attr(docsrs, doc(cfg(feature = "rune")))]
+#[cfg(feature = "rune")]
+pub mod scripting;
+#[cfg_attr(docsrs, doc(cfg(feature = "scripting")))]
+#[cfg(feature = "scripting")]
+pub mod scripting_lua;
 pub mod script_vars;
 pub mod sir0;
+pub mod special_process;
+pub mod stats;
 pub mod sys;
 pub mod types;
 pub mod wte;
@@ -2,6 +2,7 @@
 
 use crate::api::objects::monster_catalog;
 use crate::ffi;
+use alloc::vec;
 use alloc::vec::Vec;
 
 /// Metadata of a monster species.
@@ -10,6 +11,11 @@ use alloc::vec::Vec;
 pub struct MonsterSpeciesInfo(monster_catalog::Type);
 
 impl MonsterSpeciesInfo {
+    /// The total number of monster species catalog entries.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub const COUNT: u32 = 600;
+
     /// Returns the info struct for the monster species with the given ID.
     ///
     /// The caller should make sure, the ID is valid (refers to an existing monster species),
@@ -18,6 +24,15 @@ impl MonsterSpeciesInfo {
         Self(monster_idx)
     }
 
+    /// Returns the info struct for every species ID in `0..Self::COUNT`.
+    ///
+    /// This just walks the raw ID range; it doesn't check whether a given ID actually
+    /// corresponds to a used species rather than an unused/placeholder catalog slot, so callers
+    /// filtering on some property of the data should expect some duds.
+    pub fn all() -> impl Iterator<Item = MonsterSpeciesInfo> {
+        (0..Self::COUNT).map(|id| MonsterSpeciesInfo::get(id as monster_catalog::Type))
+    }
+
     /// Returns the ID of this monster.
     pub fn id(&self) -> monster_catalog::Type {
         self.0
@@ -46,6 +61,22 @@ impl MonsterSpeciesInfo {
         unsafe { Self::get(ffi::GetMonsterPreEvolution(self.0)) }
     }
 
+    /// Walks [`Self::pre_evolution`] back until it stops changing, returning the base form of
+    /// this species' evolution line (`self` if it has no pre-evolution).
+    ///
+    /// Traversal stops once [`Self::pre_evolution`] returns the same ID as the monster it was
+    /// called on, which is how the game signals "no further pre-evolution".
+    pub fn base_form(&self) -> MonsterSpeciesInfo {
+        let mut current = Self::get(self.0);
+        loop {
+            let previous = current.pre_evolution();
+            if previous.id() == current.id() {
+                return current;
+            }
+            current = previous;
+        }
+    }
+
     /// Returns a list of all the possible evolutions.
     ///
     /// This will panic if the monster has more than 32 evolutions.
@@ -81,4 +112,48 @@ impl MonsterSpeciesInfo {
                 .collect()
         }
     }
+
+    /// Returns every stage of this species' evolution line: starts at [`Self::base_form`] and
+    /// walks every branch of [`Self::evolutions`] breadth-first from there, so branching lines
+    /// (e.g. Eevee) and convergent ones are both covered. The base form is included even if it
+    /// isn't `self`.
+    ///
+    /// Guards against a malformed evolution table looping forever by never revisiting an ID
+    /// already seen.
+    pub fn evolution_chain(
+        &self,
+        ignore_sprite_size: bool,
+        include_shedinja: bool,
+    ) -> Vec<MonsterSpeciesInfo> {
+        let base = self.base_form();
+        let mut seen = vec![base.id()];
+        let mut output = vec![Self::get(base.id())];
+        let mut frontier = base.evolutions(ignore_sprite_size, include_shedinja);
+        while let Some(next) = frontier.pop() {
+            if seen.iter().any(|id| *id == next.id()) {
+                continue;
+            }
+            seen.push(next.id());
+            frontier.extend(next.evolutions(ignore_sprite_size, include_shedinja));
+            output.push(next);
+        }
+        output
+    }
+
+    /// Returns every leaf of this species' evolution line, i.e. every stage in
+    /// [`Self::evolution_chain`] that has no further evolutions of its own.
+    pub fn final_forms(
+        &self,
+        ignore_sprite_size: bool,
+        include_shedinja: bool,
+    ) -> Vec<MonsterSpeciesInfo> {
+        self.evolution_chain(ignore_sprite_size, include_shedinja)
+            .into_iter()
+            .filter(|species| {
+                species
+                    .evolutions(ignore_sprite_size, include_shedinja)
+                    .is_empty()
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,165 @@
+//! A composable predicate builder for selecting [`MonsterSpeciesId`]s, modeled after the
+//! boolean combination modes used by the game's own monster-selection hooks.
+
+use crate::api::monsters::MonsterSpeciesId;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// The boolean mode a [`MonsterFilter`] combines its clauses with.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub enum Mode {
+    /// All clauses must match (logical AND).
+    #[default]
+    And,
+    /// At least one clause must not match (logical NAND).
+    Nand,
+    /// At least one clause must match (logical OR).
+    Or,
+    /// No clause may match (logical NOR).
+    Nor,
+}
+
+type Clause = Box<dyn Fn(MonsterSpeciesId) -> bool>;
+
+/// A builder for composable monster-selection predicates.
+///
+/// Clauses added with [`Self::require`] and [`Self::forbid`] (the latter is just the former
+/// with the predicate negated) are combined according to the configured [`Mode`].
+/// [`Self::build`] turns the builder into a plain `Fn(MonsterSpeciesId) -> bool`, which can be
+/// passed to [`filter_all`] to scan a range of species.
+///
+/// ```no_run
+/// use eos_rs::api::monster_filter::{MonsterFilter, Mode, filter_all};
+/// use eos_rs::api::monsters::MonsterSpeciesId;
+///
+/// let filter = MonsterFilter::new()
+///     .mode(Mode::Nand)
+///     .require(|id: MonsterSpeciesId| id.is_mission_allowed())
+///     .forbid(|id: MonsterSpeciesId| id.is_unown())
+///     .build();
+/// ```
+#[derive(Default)]
+pub struct MonsterFilter {
+    mode: Mode,
+    clauses: Vec<Clause>,
+}
+
+impl MonsterFilter {
+    /// Creates an empty filter, defaulting to [`Mode::And`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the boolean mode used to combine clauses.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Adds a clause that must hold as given (not negated).
+    pub fn require(mut self, predicate: impl Fn(MonsterSpeciesId) -> bool + 'static) -> Self {
+        self.clauses.push(Box::new(predicate));
+        self
+    }
+
+    /// Adds a clause whose negation is evaluated, i.e. `forbid(p)` is equivalent to
+    /// `require(|id| !p(id))`.
+    pub fn forbid(mut self, predicate: impl Fn(MonsterSpeciesId) -> bool + 'static) -> Self {
+        self.clauses.push(Box::new(move |id| !predicate(id)));
+        self
+    }
+
+    /// Consumes the builder and returns a closure evaluating all clauses under the
+    /// configured [`Mode`].
+    ///
+    /// An empty filter always evaluates to `true` for [`Mode::And`]/[`Mode::Nor`] and `false`
+    /// for [`Mode::Nand`]/[`Mode::Or`], mirroring the identity element of each combinator.
+    pub fn build(self) -> impl Fn(MonsterSpeciesId) -> bool {
+        let Self { mode, clauses } = self;
+        move |id| match mode {
+            Mode::And => clauses.iter().all(|clause| clause(id)),
+            Mode::Nand => !clauses.iter().all(|clause| clause(id)),
+            Mode::Or => clauses.iter().any(|clause| clause(id)),
+            Mode::Nor => !clauses.iter().any(|clause| clause(id)),
+        }
+    }
+}
+
+/// Scans `range` and returns every [`MonsterSpeciesId`] for which `filter` returns true.
+pub fn filter_all(
+    range: impl IntoIterator<Item = MonsterSpeciesId>,
+    filter: impl Fn(MonsterSpeciesId) -> bool,
+) -> Vec<MonsterSpeciesId> {
+    range.into_iter().filter(|id| filter(*id)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn id(n: u32) -> MonsterSpeciesId {
+        unsafe { MonsterSpeciesId::new(n) }
+    }
+
+    #[test]
+    fn test_and_mode_requires_all_clauses() {
+        let filter = MonsterFilter::new()
+            .mode(Mode::And)
+            .require(|id: MonsterSpeciesId| id.id() > 1)
+            .require(|id: MonsterSpeciesId| id.id() < 5)
+            .build();
+        assert!(!filter(id(1)));
+        assert!(filter(id(2)));
+        assert!(!filter(id(5)));
+    }
+
+    #[test]
+    fn test_nand_mode_negates_and() {
+        let filter = MonsterFilter::new()
+            .mode(Mode::Nand)
+            .require(|id: MonsterSpeciesId| id.id() > 1)
+            .require(|id: MonsterSpeciesId| id.id() < 5)
+            .build();
+        assert!(filter(id(1)));
+        assert!(!filter(id(2)));
+    }
+
+    #[test]
+    fn test_or_mode_requires_any_clause() {
+        let filter = MonsterFilter::new()
+            .mode(Mode::Or)
+            .require(|id: MonsterSpeciesId| id.id() == 1)
+            .require(|id: MonsterSpeciesId| id.id() == 5)
+            .build();
+        assert!(filter(id(1)));
+        assert!(filter(id(5)));
+        assert!(!filter(id(2)));
+    }
+
+    #[test]
+    fn test_nor_mode_requires_no_clause() {
+        let filter = MonsterFilter::new()
+            .mode(Mode::Nor)
+            .require(|id: MonsterSpeciesId| id.id() == 1)
+            .build();
+        assert!(!filter(id(1)));
+        assert!(filter(id(2)));
+    }
+
+    #[test]
+    fn test_forbid_negates_predicate() {
+        let filter = MonsterFilter::new().forbid(|id: MonsterSpeciesId| id.id() == 1).build();
+        assert!(!filter(id(1)));
+        assert!(filter(id(2)));
+    }
+
+    #[test]
+    fn test_filter_all_scans_range() {
+        let ids: Vec<MonsterSpeciesId> = (0..10).map(id).collect();
+        let filter = MonsterFilter::new()
+            .require(|id: MonsterSpeciesId| id.id() % 2 == 0)
+            .build();
+        let result = filter_all(ids, filter);
+        assert_eq!(result.len(), 5);
+    }
+}
@@ -3,6 +3,7 @@
 use crate::api::enums::MonsterGender;
 use crate::ffi;
 use crate::ffi::GetLowKickMultiplier;
+use alloc::collections::BTreeSet;
 use alloc::vec::Vec;
 use fixed::types::I24F8;
 
@@ -95,6 +96,47 @@ impl MonsterSpeciesId {
         output_list.into_iter().take(count as usize).collect()
     }
 
+    /// Returns every species reachable from this one by repeatedly following
+    /// [`Self::evolutions`], i.e. the full evolution graph rooted at this species rather than
+    /// just its immediate next stage(s). `self` is not included in the result.
+    ///
+    /// Species are visited breadth-first and deduplicated by ID, so branching evolution lines
+    /// (eg. Eevee) and convergent ones are both handled correctly.
+    pub fn evolution_graph(&self, ignore_sprite_size: bool, include_shedinja: bool) -> Vec<Self> {
+        let mut visited = BTreeSet::new();
+        visited.insert(self.id());
+
+        let mut output = Vec::new();
+        let mut frontier = self.evolutions(ignore_sprite_size, include_shedinja);
+        while let Some(next) = frontier.pop() {
+            if visited.insert(next.id()) {
+                frontier.extend(next.evolutions(ignore_sprite_size, include_shedinja));
+                output.push(next);
+            }
+        }
+        output
+    }
+
+    /// Returns the full chain of pre-evolutions of this species, from the immediate
+    /// pre-evolution up to the root of the evolution line. `self` is not included in the
+    /// result.
+    ///
+    /// Traversal stops once [`Self::pre_evolution`] returns the same ID as the monster it was
+    /// called on, which is how the game signals "no further pre-evolution".
+    pub fn ancestor_line(&self) -> Vec<Self> {
+        let mut output = Vec::new();
+        let mut current = *self;
+        loop {
+            let previous = current.pre_evolution();
+            if previous.id() == current.id() {
+                break;
+            }
+            output.push(previous);
+            current = previous;
+        }
+        output
+    }
+
     /// Checks if this is an Unown.
     pub fn is_unown(&self) -> bool {
         unsafe { ffi::IsUnown(*self) > 0 }
@@ -169,6 +211,59 @@ impl MonsterSpeciesId {
     pub fn get_low_kick_multiplier(&self) -> I24F8 {
         unsafe { I24F8::from_num(GetLowKickMultiplier(*self)) }
     }
+
+    /// Picks a random species from `range` that could plausibly replace this one in a
+    /// transformation/disguise effect (eg. a "chameleon" mechanic).
+    ///
+    /// A candidate qualifies if it has the same [`Self::sprite_size`] as `self`, passes
+    /// [`Self::can_be_used_for_mission`] and [`Self::get_can_move_flag`], is not `self`'s own
+    /// base form, and satisfies `extra_constraint`.
+    ///
+    /// Returns `None` if no candidate in `range` qualifies.
+    pub fn random_matching_form(
+        &self,
+        range: impl IntoIterator<Item = Self>,
+        extra_constraint: impl Fn(Self) -> bool,
+    ) -> Option<Self> {
+        let own_base_form = self.base_form();
+        let candidates: Vec<Self> = range
+            .into_iter()
+            .filter(|candidate| {
+                candidate.sprite_size() == self.sprite_size()
+                    && candidate.can_be_used_for_mission(true)
+                    && candidate.get_can_move_flag()
+                    && candidate.base_form().id() != own_base_form.id()
+                    && extra_constraint(*candidate)
+            })
+            .collect();
+
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let index = crate::api::random::rand_i32(0..candidates.len() as i32) as usize;
+        Some(candidates[index])
+    }
+
+    /// Returns the recruitment rate of this species (out of the usual /100 roll), before
+    /// applying any dungeon- or story-specific modifiers.
+    pub fn recruit_rate(&self) -> u16 {
+        unsafe { ffi::GetMonsterRecruitRate(*self) }
+    }
+
+    /// Returns the amount of base experience yielded when this species faints in a dungeon,
+    /// before applying the level-scaling growth curve.
+    pub fn base_experience_yield(&self) -> u32 {
+        unsafe { ffi::GetMonsterExpYield(*self) }
+    }
+
+    /// Returns this species' per-species flag bitset, as used for scripted/mod-specific
+    /// tagging. This does not have a fixed meaning defined by the base game; it is provided so
+    /// mods can layer their own per-species tags (e.g. custom recruitment/balancing rules) on
+    /// top of the game's monster data.
+    pub fn flags(&self) -> u32 {
+        unsafe { ffi::GetMonsterSpeciesFlags(*self) }
+    }
 }
 
 impl From<MonsterSpeciesId> for u32 {
@@ -176,3 +271,69 @@ impl From<MonsterSpeciesId> for u32 {
         v.0
     }
 }
+
+/// Which of the game's handful of hardcoded multi-form species (if any) a [`MonsterSpeciesId`]
+/// belongs to.
+///
+/// Computed by [`MonsterInfo::form`] by dispatching to [`MonsterSpeciesId::is_unown`]/
+/// [`MonsterSpeciesId::is_shaymin`]/[`MonsterSpeciesId::is_castform`]/
+/// [`MonsterSpeciesId::is_cherrim`]/[`MonsterSpeciesId::is_deoxys`], so mod code has one
+/// discoverable entry point instead of having to remember which `is_*` predicate to call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeciesForm {
+    Unown,
+    Shaymin,
+    Castform,
+    Cherrim,
+    Deoxys,
+    /// None of the above; a species with either a single form or one whose forms aren't gated
+    /// by a dedicated `Is*` check.
+    Normal,
+}
+
+/// A query over a [`MonsterSpeciesId`]'s form/species metadata.
+///
+/// ```ignore
+/// match MonsterInfo::of(species).form() {
+///     SpeciesForm::Unown => { /* ... */ }
+///     _ => {}
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonsterInfo(MonsterSpeciesId);
+
+impl MonsterInfo {
+    /// Starts a query for `species`.
+    pub fn of(species: MonsterSpeciesId) -> Self {
+        Self(species)
+    }
+
+    /// Returns the species this query is for.
+    pub fn species(&self) -> MonsterSpeciesId {
+        self.0
+    }
+
+    /// Returns which of the game's hardcoded multi-form species this one belongs to, or
+    /// [`SpeciesForm::Normal`] if none.
+    pub fn form(&self) -> SpeciesForm {
+        if self.0.is_unown() {
+            SpeciesForm::Unown
+        } else if self.0.is_shaymin() {
+            SpeciesForm::Shaymin
+        } else if self.0.is_castform() {
+            SpeciesForm::Castform
+        } else if self.0.is_cherrim() {
+            SpeciesForm::Cherrim
+        } else if self.0.is_deoxys() {
+            SpeciesForm::Deoxys
+        } else {
+            SpeciesForm::Normal
+        }
+    }
+
+    /// Returns whether this species belongs to one of the game's hardcoded multi-form species,
+    /// i.e. whether [`Self::form`] is anything but [`SpeciesForm::Normal`].
+    pub fn is_multi_form(&self) -> bool {
+        self.form() != SpeciesForm::Normal
+    }
+}
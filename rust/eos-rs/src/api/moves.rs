@@ -53,6 +53,30 @@ pub enum MoveRange {
     Special = ffi::move_range::RANGE_SPECIAL,
 }
 
+impl MoveRange {
+    /// The straight-line tile distance this range covers, for the ranges
+    /// [`crate::api::dungeon_mode::DungeonEffectsEmitter::is_target_in_range`]'s
+    /// direction-and-distance model applies to.
+    ///
+    /// Returns `None` for [`MoveRange::Room`], [`MoveRange::Floor`], [`MoveRange::User`] and
+    /// [`MoveRange::Special`], whose targeting isn't a straight-line distance check: `Room` and
+    /// `Floor` target every eligible entity in the room/floor regardless of direction, `User`
+    /// only ever targets the user, and `Special` has per-move custom targeting logic.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub fn max_tile_distance(&self) -> Option<i32> {
+        match self {
+            MoveRange::Front
+            | MoveRange::FrontAndSides
+            | MoveRange::FrontWithCornerCutting
+            | MoveRange::Nearby => Some(1),
+            MoveRange::Front2 | MoveRange::Front2WithCornerCutting => Some(2),
+            MoveRange::Front10 => Some(10),
+            MoveRange::Room | MoveRange::Floor | MoveRange::User | MoveRange::Special => None,
+        }
+    }
+}
+
 impl TryInto<MoveRange> for ffi::move_range::Type {
     type Error = ();
 
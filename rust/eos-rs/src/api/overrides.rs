@@ -0,0 +1,122 @@
+//! TOML-backed override tables for type matchups, move categories and terrain, for mods that
+//! want to tweak game-balance data without patching the functions that read it directly.
+//!
+//! This is only compiled in when the `io` feature is enabled, since it is loaded from a file on
+//! the ROM file system.
+
+use crate::api::dungeon_mode::{MoveCategory, TerrainType};
+use crate::api::io::file;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::ffi::CStr;
+
+/// A table of overrides loaded from a TOML document.
+///
+/// Expected top-level tables (all optional):
+///
+/// ```toml
+/// [type_matchups]
+/// # "attacking_type.defending_type" = multiplier, as a percentage (eg. 200 = super effective).
+/// "fire.grass" = 200
+///
+/// [move_categories]
+/// # move name (as used in the base game's move catalog) -> category.
+/// tackle = "physical"
+///
+/// [terrain]
+/// # x,y tile coordinate on the current floor -> terrain type.
+/// "5,10" = "secondary"
+/// ```
+#[derive(Default)]
+pub struct OverrideTables {
+    type_matchups: BTreeMap<(String, String), u16>,
+    move_categories: BTreeMap<String, MoveCategory>,
+    terrain: BTreeMap<(i32, i32), TerrainType>,
+}
+
+impl OverrideTables {
+    /// Loads override tables from a TOML file in the ROM file system.
+    ///
+    /// # Safety
+    /// See [`file::read`]; the same file-transfer-mode caveats apply.
+    pub unsafe fn load<C: AsRef<CStr>>(path: C) -> Option<Self> {
+        let bytes = file::read(path, 0);
+        let text = core::str::from_utf8(&bytes).ok()?;
+        let document: toml::Table = text.parse().ok()?;
+        Some(Self::from_document(&document))
+    }
+
+    fn from_document(document: &toml::Table) -> Self {
+        let mut tables = Self::default();
+
+        if let Some(toml::Value::Table(matchups)) = document.get("type_matchups") {
+            for (key, value) in matchups {
+                if let (Some((attacker, defender)), Some(multiplier)) =
+                    (key.split_once('.'), value.as_integer())
+                {
+                    tables
+                        .type_matchups
+                        .insert((String::from(attacker), String::from(defender)), multiplier as u16);
+                }
+            }
+        }
+
+        if let Some(toml::Value::Table(categories)) = document.get("move_categories") {
+            for (key, value) in categories {
+                if let Some(category) = value.as_str().and_then(parse_move_category) {
+                    tables.move_categories.insert(key.clone(), category);
+                }
+            }
+        }
+
+        if let Some(toml::Value::Table(terrain)) = document.get("terrain") {
+            for (key, value) in terrain {
+                if let (Some((x, y)), Some(terrain_type)) = (
+                    key.split_once(',').and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?))),
+                    value.as_str().and_then(parse_terrain_type),
+                ) {
+                    tables.terrain.insert((x, y), terrain_type);
+                }
+            }
+        }
+
+        tables
+    }
+
+    /// Returns the overridden type-matchup multiplier (as a percentage) for `attacking_type`
+    /// hitting `defending_type`, if one was configured.
+    pub fn type_matchup(&self, attacking_type: &str, defending_type: &str) -> Option<u16> {
+        self.type_matchups
+            .get(&(String::from(attacking_type), String::from(defending_type)))
+            .copied()
+    }
+
+    /// Returns the overridden category for a move, by name, if one was configured.
+    pub fn move_category(&self, move_name: &str) -> Option<MoveCategory> {
+        self.move_categories.get(move_name).copied()
+    }
+
+    /// Returns the overridden terrain type for a tile coordinate, if one was configured.
+    pub fn terrain_at(&self, x: i32, y: i32) -> Option<TerrainType> {
+        self.terrain.get(&(x, y)).copied()
+    }
+}
+
+fn parse_move_category(name: &str) -> Option<MoveCategory> {
+    match name {
+        "physical" => Some(MoveCategory::Physical),
+        "special" => Some(MoveCategory::Special),
+        "status" => Some(MoveCategory::Status),
+        _ => None,
+    }
+}
+
+fn parse_terrain_type(name: &str) -> Option<TerrainType> {
+    match name {
+        "wall" => Some(TerrainType::Wall),
+        "normal" => Some(TerrainType::Normal),
+        "secondary" => Some(TerrainType::Secondary),
+        "chasm" => Some(TerrainType::Chasm),
+        _ => None,
+    }
+}
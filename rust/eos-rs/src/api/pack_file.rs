@@ -106,3 +106,115 @@ pub unsafe fn alloc_and_load_file_in_pack(
         res.length as usize,
     )
 }
+
+/// Errors returned by [`PackArchive::read_into`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackError {
+    /// `file_number` was not a valid index into the archive (see [`PackArchive::file_count`]).
+    InvalidFileNumber,
+    /// The caller-provided buffer is smaller than the entry's recorded length
+    /// (see [`PackArchive::len`]).
+    BufferTooSmall,
+    /// The game reported reading more bytes than the entry's recorded length. [`load_file_in_pack`]
+    /// and friends panic on this condition; here it's just another `Err`.
+    SizeMismatch,
+}
+
+/// A safe, allocation-free reader over an already-opened pack archive.
+///
+/// Wraps [`ffi::pack_file_opened`] behind bounds-checked accessors, so a mod can stream many
+/// entries through one reusable buffer with [`Self::read_into`] instead of allocating a fresh
+/// `Vec` per file like [`load_file_in_pack`] does. This also turns "`file_number` must be valid
+/// or it's UB" into `Option`/`Result`-returning calls.
+pub struct PackArchive {
+    raw: ffi::pack_file_opened,
+}
+
+impl PackArchive {
+    /// Wraps an already-opened pack archive.
+    ///
+    /// # Safety
+    /// `raw` must have been produced by [`open_pack_file`], and the archive's backing file
+    /// must outlive the returned [`PackArchive`].
+    pub unsafe fn new(raw: ffi::pack_file_opened) -> Self {
+        Self { raw }
+    }
+
+    /// Opens `file_name` as a [`PackArchive`].
+    ///
+    /// Panics if `file_name` can not be converted to a CString (see [`CString::new`]).
+    ///
+    /// # Safety
+    /// The file must exist and be a valid pack file.
+    pub unsafe fn open(file_name: &str) -> Self {
+        Self::new(open_pack_file(file_name))
+    }
+
+    /// The number of file entries in this archive.
+    pub fn file_count(&self) -> u32 {
+        self.raw.nb_files
+    }
+
+    /// `true` if this archive has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.file_count() == 0
+    }
+
+    /// The length, in bytes, of the entry at `file_number`, or `None` if `file_number` is out
+    /// of bounds.
+    pub fn len(&mut self, file_number: u32) -> Option<u32> {
+        if file_number < self.file_count() {
+            Some(unsafe { get_file_length_in_pack(&mut self.raw, file_number) })
+        } else {
+            None
+        }
+    }
+
+    /// Reads the entry at `file_number` into `buf`, returning the number of bytes written.
+    ///
+    /// Unlike [`load_file_in_pack`], this reuses `buf` instead of allocating a new `Vec` each
+    /// call, so many entries can be streamed through one caller-owned buffer.
+    pub fn read_into(&mut self, file_number: u32, buf: &mut [u8]) -> Result<usize, PackError> {
+        let expected = self.len(file_number).ok_or(PackError::InvalidFileNumber)?;
+        if (buf.len() as u32) < expected {
+            return Err(PackError::BufferTooSmall);
+        }
+        let actual = unsafe {
+            ffi::LoadFileInPack(&mut self.raw, buf.as_mut_ptr() as *mut c_void, file_number)
+        } as u32;
+        if actual > expected {
+            Err(PackError::SizeMismatch)
+        } else {
+            Ok(actual as usize)
+        }
+    }
+
+    /// Iterates over the byte length of each entry, in order.
+    pub fn iter(&mut self) -> PackArchiveIter<'_> {
+        PackArchiveIter {
+            archive: self,
+            next: 0,
+        }
+    }
+}
+
+/// Iterator over the entry lengths of a [`PackArchive`], returned by [`PackArchive::iter`].
+pub struct PackArchiveIter<'a> {
+    archive: &'a mut PackArchive,
+    next: u32,
+}
+
+impl Iterator for PackArchiveIter<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.archive.len(self.next)?;
+        self.next += 1;
+        Some(len)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.archive.file_count() - self.next) as usize;
+        (remaining, Some(remaining))
+    }
+}
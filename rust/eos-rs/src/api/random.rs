@@ -1,6 +1,5 @@
 //! Functions for generating random numbers.
 
-use crate::ctypes::*;
 use crate::ffi;
 use core::ops::{Bound, RangeBounds};
 
@@ -8,10 +7,28 @@ use core::ops::{Bound, RangeBounds};
 pub(crate) trait Rng {
     fn rand16(&mut self) -> u16;
     fn rand32(&mut self) -> u32;
-    fn rand_range32(&mut self, x: c_int, y: c_int) -> c_int;
 }
 
-struct GameRng;
+/// The game's general (non-dungeon) PRNG, exposed as a [`rand_core::RngCore`]/
+/// [`rand_core::SeedableRng`] so it can drive `rand`-ecosystem helpers (`SliceRandom::shuffle`/
+/// `choose`, weighted sampling, the `Distribution` traits) against the game's own deterministic
+/// PRNG, instead of needing to reimplement those algorithms by hand.
+///
+/// See [`crate::api::dungeon_mode::DungeonRng`] for the separate (and much larger) dungeon PRNG.
+pub struct GameRng;
+
+impl GameRng {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Rng for GameRng {
     fn rand16(&mut self) -> u16 {
         unsafe { ffi::Rand16Bit() }
@@ -20,9 +37,75 @@ impl Rng for GameRng {
     fn rand32(&mut self) -> u32 {
         unsafe { ffi::Rand32Bit() }
     }
+}
+
+impl rand_core::RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        unsafe { ffi::Rand32Bit() }
+    }
 
-    fn rand_range32(&mut self, x: c_int, y: c_int) -> c_int {
-        unsafe { ffi::RandRange(x, y) }
+    fn next_u64(&mut self) -> u64 {
+        let hi = self.next_u32() as u64;
+        let lo = self.next_u32() as u64;
+        (hi << 32) | lo
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl rand_core::SeedableRng for GameRng {
+    type Seed = [u8; 2];
+
+    /// Seeds the game's general PRNG (see [`set_seed`]).
+    fn from_seed(seed: Self::Seed) -> Self {
+        set_seed(u16::from_le_bytes(seed));
+        Self
+    }
+}
+
+/// A deterministic mock implementing [`Rng`], modeled after `rand`'s `rand_core::le::StepRng`.
+///
+/// Each call advances an internal `u32` counter by a fixed increment and derives its return
+/// value from the new counter value. Useful for writing reproducible tests against code that
+/// consumes [`Rng`] without needing to mock out individual call sequences.
+#[cfg(test)]
+pub(crate) struct StepRng {
+    current: u32,
+    increment: u32,
+}
+
+#[cfg(test)]
+impl StepRng {
+    /// Creates a new [`StepRng`] starting at `initial` and advancing by `increment` on every
+    /// call.
+    pub(crate) fn new(initial: u32, increment: u32) -> Self {
+        Self {
+            current: initial,
+            increment,
+        }
+    }
+
+    fn step(&mut self) -> u32 {
+        self.current = self.current.wrapping_add(self.increment);
+        self.current
+    }
+}
+
+#[cfg(test)]
+impl Rng for StepRng {
+    fn rand16(&mut self) -> u16 {
+        (self.step() >> 16) as u16
+    }
+
+    fn rand32(&mut self) -> u32 {
+        self.step()
     }
 }
 
@@ -50,12 +133,59 @@ pub fn rand_u16<R: RangeBounds<u16>>(range: R) -> u16 {
 /// If the range is unbounded, min and/or max values are bound to
 /// [`i32::MIN`] and [`i32::MAX`] respectively.
 ///
+/// This (and [`rand_u32`]) is built on [`uniform_u32_below`] rather than [`rand_u16`]'s
+/// divide-bucket rejection, so it's uniform across the full range with no modulo bias, and can
+/// return the maximum value of the range.
+///
 /// The range must contain at least one element, or this will panic.
 /// Same if the start bound is excluded.
 pub fn rand_i32<R: RangeBounds<i32>>(range: R) -> i32 {
     rand_i32_internal(&mut GameRng, range)
 }
 
+/// Generates a random number between the beginning and end of the range.
+/// If the range is unbounded, min and/or max values are bound to
+/// [`u32::MIN`] and [`u32::MAX`] respectively.
+///
+/// Built on [`uniform_u32_below`], so it's uniform across the full range with no modulo bias,
+/// and can return the maximum value of the range (unlike the `RandRange` this replaces).
+///
+/// The range must contain at least one element, or this will panic.
+/// Same if the start bound is excluded.
+pub fn rand_u32<R: RangeBounds<u32>>(range: R) -> u32 {
+    rand_u32_internal(&mut GameRng, range)
+}
+
+/// Draws a value uniformly distributed over `[0, bound)` from `rng.rand32()`, using Lemire's
+/// multiply-shift method
+/// (<https://lemire.me/blog/2019/06/06/nearly-divisionless-random-integer-generation-on-various-systems/>).
+///
+/// Unlike a naive `rand32() % bound`, this has no modulo bias: a 32-bit draw `x` is multiplied
+/// by `bound` to get a 64-bit product; the high word is the candidate result, and the low word
+/// is checked against a threshold (`2^32 % bound`) to decide whether to reject and redraw, which
+/// only happens for the sliver of outcomes that would otherwise be biased.
+///
+/// `bound` is a `u64` so the full `u32` range (`bound == 1 << 32`) can be represented; in that
+/// case every `u32` is already equally likely, so this just returns `rng.rand32()` with no
+/// rejection needed.
+fn uniform_u32_below<T: Rng>(rng: &mut T, bound: u64) -> u32 {
+    if bound == 1 << 32 {
+        return rng.rand32();
+    }
+    let bound = bound as u32;
+    loop {
+        let product = (rng.rand32() as u64) * (bound as u64);
+        let low = product as u32;
+        if low < bound {
+            let threshold = bound.wrapping_neg() % bound;
+            if low < threshold {
+                continue;
+            }
+        }
+        return (product >> 32) as u32;
+    }
+}
+
 pub(crate) fn rand_u16_internal<T: Rng, R: RangeBounds<u16>>(rng: &mut T, range: R) -> u16 {
     <u16 as RangeCheckable>::check_range(&range);
 
@@ -85,18 +215,40 @@ pub(crate) fn rand_u16_internal<T: Rng, R: RangeBounds<u16>>(rng: &mut T, range:
 
 pub(crate) fn rand_i32_internal<T: Rng, R: RangeBounds<i32>>(rng: &mut T, range: R) -> i32 {
     <i32 as RangeCheckable>::check_range(&range);
-    match (range.start_bound(), range.end_bound()) {
-        (Bound::Unbounded, Bound::Unbounded) => rng.rand32() as i32, // overflow is ok for us here.
-        (Bound::Unbounded, Bound::Included(u)) => rng.rand_range32(i32::MIN, u + 1),
-        (Bound::Unbounded, Bound::Excluded(u)) => rng.rand_range32(i32::MIN, *u),
-        // Note, this will never roll i32::MAX!
-        (Bound::Included(l), Bound::Unbounded) => rng.rand_range32(*l, i32::MAX),
-        (Bound::Included(l), Bound::Included(u)) => rng.rand_range32(*l, u + 1),
-        (Bound::Included(l), Bound::Excluded(u)) => rng.rand_range32(*l, *u),
+
+    let (min, max) = match (range.start_bound(), range.end_bound()) {
+        (Bound::Unbounded, Bound::Unbounded) => return rng.rand32() as i32, // overflow is ok for us here.
+        (Bound::Unbounded, Bound::Included(u)) => (i32::MIN, *u),
+        (Bound::Unbounded, Bound::Excluded(u)) => (i32::MIN, *u - 1),
+        (Bound::Included(l), Bound::Unbounded) => (*l, i32::MAX),
+        (Bound::Included(l), Bound::Included(u)) => (*l, *u),
+        (Bound::Included(l), Bound::Excluded(u)) => (*l, *u - 1),
         (Bound::Excluded(_), _) => {
             panic!("Excluded start ranges not supported.")
         }
-    }
+    };
+
+    let span = (max as i64) - (min as i64) + 1;
+    (min as i64 + uniform_u32_below(rng, span as u64) as i64) as i32
+}
+
+pub(crate) fn rand_u32_internal<T: Rng, R: RangeBounds<u32>>(rng: &mut T, range: R) -> u32 {
+    <u32 as RangeCheckable>::check_range(&range);
+
+    let (min, max) = match (range.start_bound(), range.end_bound()) {
+        (Bound::Unbounded, Bound::Unbounded) => return rng.rand32(),
+        (Bound::Unbounded, Bound::Included(u)) => (u32::MIN, *u),
+        (Bound::Unbounded, Bound::Excluded(u)) => (u32::MIN, *u - 1),
+        (Bound::Included(l), Bound::Unbounded) => (*l, u32::MAX),
+        (Bound::Included(l), Bound::Included(u)) => (*l, *u),
+        (Bound::Included(l), Bound::Excluded(u)) => (*l, *u - 1),
+        (Bound::Excluded(_), _) => {
+            panic!("Excluded start ranges not supported.")
+        }
+    };
+
+    let span = (max as u64) - (min as u64) + 1;
+    min + uniform_u32_below(rng, span)
 }
 
 trait RangeCheckable {
@@ -109,6 +261,22 @@ impl RangeCheckable for i32 {
         match (range.start_bound(), range.end_bound()) {
             (Bound::Included(l), Bound::Included(u)) => assert!(l <= u),
             (Bound::Included(l), Bound::Excluded(u)) => assert!(l < u),
+            // An exclusive upper bound of `MIN` makes the range empty (same as `l < u` above,
+            // with the implicit `l` being `MIN`) -- `*u - 1` would otherwise underflow.
+            (Bound::Unbounded, Bound::Excluded(u)) => assert!(*u > Self::MIN),
+            _ => (),
+        }
+    }
+}
+
+impl RangeCheckable for u32 {
+    fn check_range<R: RangeBounds<Self>>(range: &R) {
+        match (range.start_bound(), range.end_bound()) {
+            (Bound::Included(l), Bound::Included(u)) => assert!(l <= u),
+            (Bound::Included(l), Bound::Excluded(u)) => assert!(l < u),
+            // An exclusive upper bound of `MIN` (0) makes the range empty (same as `l < u`
+            // above, with the implicit `l` being `MIN`) -- `*u - 1` would otherwise underflow.
+            (Bound::Unbounded, Bound::Excluded(u)) => assert!(*u > Self::MIN),
             _ => (),
         }
     }
@@ -119,6 +287,9 @@ impl RangeCheckable for u16 {
         match (range.start_bound(), range.end_bound()) {
             (Bound::Included(l), Bound::Included(u)) => assert!(l <= u),
             (Bound::Included(l), Bound::Excluded(u)) => assert!(l < u),
+            // An exclusive upper bound of `MIN` (0) makes the range empty (same as `l < u`
+            // above, with the implicit `l` being `MIN`) -- `*u - 1` would otherwise underflow.
+            (Bound::Unbounded, Bound::Excluded(u)) => assert!(*u > Self::MIN),
             _ => (),
         }
     }
@@ -127,7 +298,6 @@ impl RangeCheckable for u16 {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::ctypes::c_int;
     use alloc::vec::Vec;
     use core::ops::{Bound, RangeBounds};
 
@@ -138,7 +308,6 @@ mod test {
     enum RngCall {
         Rand16,
         Rand32,
-        RandRange32(c_int, c_int),
     }
 
     struct MockRng(Vec<RngCall>);
@@ -153,10 +322,21 @@ mod test {
             self.0.push(RngCall::Rand32);
             RAND_32_RETURN as u32
         }
+    }
+
+    /// A fixed `rand32()` draw, for exercising [`uniform_u32_below`]'s bound translation without
+    /// depending on its rejection loop terminating. `u32::MAX` always accepts on the first draw
+    /// and maps to the top of the range (`bound - 1`); `0` always accepts for power-of-two bounds
+    /// (where the rejection threshold is zero) and maps to the bottom of the range.
+    struct FixedRng(u32);
+
+    impl Rng for FixedRng {
+        fn rand16(&mut self) -> u16 {
+            unimplemented!("not used by these tests")
+        }
 
-        fn rand_range32(&mut self, x: c_int, y: c_int) -> c_int {
-            self.0.push(RngCall::RandRange32(x, y));
-            RAND_32_RETURN
+        fn rand32(&mut self) -> u32 {
+            self.0
         }
     }
 
@@ -187,47 +367,38 @@ mod test {
 
     #[test]
     fn test_rand_i32_unbounded_included() {
-        let mut mock = MockRng(Vec::new());
-        let result = rand_i32_internal(&mut mock, ..=10);
-        assert_eq!(mock.0.len(), 1);
-        assert_eq!(mock.0[0], RngCall::RandRange32(i32::MIN, 11));
-        assert_eq!(result, RAND_32_RETURN);
+        // min = i32::MIN, max = 10.
+        let result = rand_i32_internal(&mut FixedRng(u32::MAX), ..=10);
+        assert_eq!(result, 10);
     }
 
     #[test]
     fn test_rand_i32_unbounded_excluded() {
-        let mut mock = MockRng(Vec::new());
-        let result = rand_i32_internal(&mut mock, ..10);
-        assert_eq!(mock.0.len(), 1);
-        assert_eq!(mock.0[0], RngCall::RandRange32(i32::MIN, 10));
-        assert_eq!(result, RAND_32_RETURN);
+        // min = i32::MIN, max = 9.
+        let result = rand_i32_internal(&mut FixedRng(u32::MAX), ..10);
+        assert_eq!(result, 9);
     }
 
     #[test]
     fn test_rand_i32_included_unbounded() {
-        let mut mock = MockRng(Vec::new());
-        let result = rand_i32_internal(&mut mock, 10..);
-        assert_eq!(mock.0.len(), 1);
-        assert_eq!(mock.0[0], RngCall::RandRange32(10, i32::MAX));
-        assert_eq!(result, RAND_32_RETURN);
+        // min = 10, max = i32::MAX.
+        let result = rand_i32_internal(&mut FixedRng(u32::MAX), 10..);
+        assert_eq!(result, i32::MAX);
     }
 
     #[test]
     fn test_rand_i32_included_included() {
-        let mut mock = MockRng(Vec::new());
-        let result = rand_i32_internal(&mut mock, 10..=20);
-        assert_eq!(mock.0.len(), 1);
-        assert_eq!(mock.0[0], RngCall::RandRange32(10, 21));
-        assert_eq!(result, RAND_32_RETURN);
+        // min = 10, max = 25: a power-of-two-sized span, so both extremes are reachable on the
+        // first draw (no rejection) and exercise the full multiply-shift range.
+        assert_eq!(rand_i32_internal(&mut FixedRng(u32::MAX), 10..=25), 25);
+        assert_eq!(rand_i32_internal(&mut FixedRng(0), 10..=25), 10);
     }
 
     #[test]
     fn test_rand_i32_included_excluded() {
-        let mut mock = MockRng(Vec::new());
-        let result = rand_i32_internal(&mut mock, 10..20);
-        assert_eq!(mock.0.len(), 1);
-        assert_eq!(mock.0[0], RngCall::RandRange32(10, 20));
-        assert_eq!(result, RAND_32_RETURN);
+        // min = 10, max = 25 (26 excluded).
+        let result = rand_i32_internal(&mut FixedRng(u32::MAX), 10..26);
+        assert_eq!(result, 25);
     }
 
     #[test]
@@ -298,6 +469,151 @@ mod test {
         rand_i32_internal(&mut MockRng(Vec::new()), 3..=2);
     }
 
+    #[test]
+    fn test_uniform_u32_below_full_range_is_direct_passthrough() {
+        let result = uniform_u32_below(&mut FixedRng(0xdead_beef), 1u64 << 32);
+        assert_eq!(result, 0xdead_beef);
+    }
+
+    #[test]
+    fn test_uniform_u32_below_power_of_two_bound_never_rejects() {
+        assert_eq!(uniform_u32_below(&mut FixedRng(0), 16), 0);
+        assert_eq!(uniform_u32_below(&mut FixedRng(u32::MAX), 16), 15);
+    }
+
+    #[test]
+    fn test_uniform_u32_below_rejects_draws_below_the_bias_threshold() {
+        // bound = 3 isn't a power of two, so the rejection threshold (2^32 % 3) is 1: a draw of
+        // 0 produces low = 0, which is below the threshold and must be rejected and redrawn.
+        struct SequenceRng(Vec<u32>);
+        impl Rng for SequenceRng {
+            fn rand16(&mut self) -> u16 {
+                unimplemented!("not used by this test")
+            }
+            fn rand32(&mut self) -> u32 {
+                self.0.remove(0)
+            }
+        }
+
+        let mut rng = SequenceRng(alloc::vec![0, 5]);
+        let result = uniform_u32_below(&mut rng, 3);
+        assert!(rng.0.is_empty(), "should have drawn both queued values");
+        assert_eq!(result, ((5u64 * 3) >> 32) as u32);
+    }
+
+    #[test]
+    fn test_rand_u32_unbounded_unbounded() {
+        let mut mock = MockRng(Vec::new());
+        let result = rand_u32_internal(&mut mock, ..);
+        assert_eq!(mock.0.len(), 1);
+        assert_eq!(mock.0[0], RngCall::Rand32);
+        assert_eq!(result, RAND_32_RETURN as u32);
+    }
+
+    #[test]
+    fn test_rand_u32_unbounded_included() {
+        // min = 0, max = 10.
+        let result = rand_u32_internal(&mut FixedRng(u32::MAX), ..=10u32);
+        assert_eq!(result, 10);
+    }
+
+    #[test]
+    fn test_rand_u32_unbounded_excluded() {
+        // min = 0, max = 9.
+        let result = rand_u32_internal(&mut FixedRng(u32::MAX), ..10u32);
+        assert_eq!(result, 9);
+    }
+
+    #[test]
+    fn test_rand_u32_included_unbounded() {
+        // min = 10, max = u32::MAX.
+        let result = rand_u32_internal(&mut FixedRng(u32::MAX), 10u32..);
+        assert_eq!(result, u32::MAX);
+    }
+
+    #[test]
+    fn test_rand_u32_included_included() {
+        // min = 10, max = 25: a power-of-two-sized span, so both extremes are reachable on the
+        // first draw.
+        assert_eq!(rand_u32_internal(&mut FixedRng(u32::MAX), 10u32..=25), 25);
+        assert_eq!(rand_u32_internal(&mut FixedRng(0), 10u32..=25), 10);
+    }
+
+    #[test]
+    fn test_rand_u32_included_excluded() {
+        // min = 10, max = 25 (26 excluded).
+        let result = rand_u32_internal(&mut FixedRng(u32::MAX), 10u32..26);
+        assert_eq!(result, 25);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rand_u32_excluded_unbounded() {
+        rand_u32_internal(
+            &mut MockRng(Vec::new()),
+            ExcludedStartRange {
+                end_bound: Bound::Unbounded,
+                excluded_start: 0u32,
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rand_u32_excluded_included() {
+        rand_u32_internal(
+            &mut MockRng(Vec::new()),
+            ExcludedStartRange {
+                end_bound: Bound::Included(&0u32),
+                excluded_start: 0,
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rand_u32_excluded_excluded() {
+        rand_u32_internal(
+            &mut MockRng(Vec::new()),
+            ExcludedStartRange {
+                end_bound: Bound::Excluded(&0u32),
+                excluded_start: 0,
+            },
+        );
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    #[should_panic]
+    fn test_rand_u32_reverse_range_excluded() {
+        assert_eq!((3..2u32).into_iter().count(), 0);
+        rand_u32_internal(&mut MockRng(Vec::new()), 3..2u32);
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    #[should_panic]
+    fn test_rand_u32_empty_range_excluded() {
+        assert_eq!((3..3u32).into_iter().count(), 0);
+        rand_u32_internal(&mut MockRng(Vec::new()), 3..3u32);
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    #[should_panic]
+    fn test_rand_u32_reverse_range_included() {
+        assert_eq!((3..=1u32).into_iter().count(), 0);
+        rand_u32_internal(&mut MockRng(Vec::new()), 3..=1u32);
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    #[should_panic]
+    fn test_rand_u32_empty_range_included_anti() {
+        assert_eq!((3..=2u32).into_iter().count(), 0);
+        rand_u32_internal(&mut MockRng(Vec::new()), 3..=2u32);
+    }
+
     #[test]
     fn test_rand_u16_unbounded_unbounded() {
         let mut mock = MockRng(Vec::new());
@@ -419,4 +735,31 @@ mod test {
         assert_eq!((3..=2).into_iter().count(), 0);
         rand_u16_internal(&mut MockRng(Vec::new()), 3..=2);
     }
+
+    #[test]
+    fn test_step_rng_is_deterministic() {
+        let mut a = StepRng::new(0, 1);
+        let mut b = StepRng::new(0, 1);
+        for _ in 0..8 {
+            assert_eq!(a.rand32(), b.rand32());
+        }
+    }
+
+    #[test]
+    fn test_rand_i32_internal_stays_in_bounds_across_many_draws() {
+        let mut rng = StepRng::new(0, 0x9e37_79b9);
+        for _ in 0..20 {
+            let v = rand_i32_internal(&mut rng, 5..15);
+            assert!((5..15).contains(&v));
+        }
+    }
+
+    #[test]
+    fn test_rand_u32_internal_stays_in_bounds_across_many_draws() {
+        let mut rng = StepRng::new(0, 0x9e37_79b9);
+        for _ in 0..20 {
+            let v = rand_u32_internal(&mut rng, 5..15u32);
+            assert!((5..15).contains(&v));
+        }
+    }
 }
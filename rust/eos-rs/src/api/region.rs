@@ -0,0 +1,42 @@
+//! The game region this crate is being compiled for.
+//!
+//! The build script derives this from the target triple's `-na`/`-ja`/`-eu` suffix (the same way
+//! it does for `generate_symbols_for_linker`'s pmdsky-debug symbol resolution) and emits it as the
+//! `region` cfg. [`current_region`] exposes that same value to runtime code, so patch authors
+//! don't need to duplicate the triple-parsing themselves to gate region-specific behavior.
+
+/// A game region, as shipped for Pokémon Mystery Dungeon: Explorers of Sky.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    /// North America.
+    Na,
+    /// Japan.
+    Ja,
+    /// Europe.
+    Eu,
+}
+
+/// Returns the region this crate was compiled for.
+///
+/// # Panics
+/// Panics (via `panic!`, so at compile time only when evaluated in a const context) if built
+/// without the `region` cfg set, which means the build script wasn't used to build this crate, or
+/// wasn't able to determine the region from the target triple.
+pub const fn current_region() -> Region {
+    #[cfg(region = "na")]
+    {
+        Region::Na
+    }
+    #[cfg(region = "ja")]
+    {
+        Region::Ja
+    }
+    #[cfg(region = "eu")]
+    {
+        Region::Eu
+    }
+    #[cfg(not(any(region = "na", region = "ja", region = "eu")))]
+    {
+        panic!("eos-rs was built without a `region` cfg; make sure the build script calls `eos_rs_build::emit_region_cfg()`")
+    }
+}
@@ -0,0 +1,186 @@
+//! A runtime registry that lets mods attach their own durable state to the stock save file,
+//! layered on top of [`ScriptVariables::dump_script_variable_values`]/
+//! [`ScriptVariables::restore_script_variable_values`] (themselves driven by `NoteSaveBase`/
+//! `NoteLoadBase` during a (quick)save).
+//!
+//! A mod calls [`register_save_section`] with a tag of its own choosing, plus a serialize and a
+//! deserialize callback. [`dump_with_sections`]/[`restore_with_sections`] are drop-in
+//! replacements for the plain [`ScriptVariables`] dump/restore calls: they wrap the vanilla
+//! 1024-byte payload in a length-prefixed, tag-keyed trailer holding every registered section's
+//! blob, so a mod's data rides along with the save without touching the vanilla layout. A tag
+//! with no registered handler (e.g. a mod that's since been removed) is skipped rather than
+//! treated as an error, so saves stay forward/backward compatible as mods come and go.
+
+use crate::api::script_vars::ScriptVariables;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+/// A growable output buffer handed to a registered section's serialize callback.
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn new() -> Self {
+        Self { buf: Vec::new() }
+    }
+
+    /// Appends `bytes` to this section's blob.
+    pub fn write(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+}
+
+/// A cursor over a registered section's previously-saved blob, handed to its deserialize
+/// callback.
+pub struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Reads exactly `out.len()` bytes, or returns `false` (leaving `out` untouched) if that
+    /// many bytes aren't left in the blob -- e.g. a save written by an older, smaller version of
+    /// this section.
+    pub fn read(&mut self, out: &mut [u8]) -> bool {
+        match self.buf[self.pos..].get(..out.len()) {
+            Some(slice) => {
+                out.copy_from_slice(slice);
+                self.pos += out.len();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Bytes remaining in the blob.
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+}
+
+type SerializeFn = fn(&mut Writer);
+type DeserializeFn = fn(stored_version: u32, reader: &mut Reader);
+
+struct SaveSection {
+    tag: u32,
+    version: u32,
+    serialize: SerializeFn,
+    deserialize: DeserializeFn,
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// saving/loading only ever happens from the main game loop.
+static mut SAVE_SECTIONS: Vec<SaveSection> = Vec::new();
+
+/// Registers a tagged section of save data.
+///
+/// `tag` should be unique among registered sections (e.g. a FourCC of the mod's name); colliding
+/// tags are dispatched to whichever section registered first. `version` is this section's current
+/// schema version: it's written alongside the blob, and handed back to `deserialize` as
+/// `stored_version` on load so a mod can detect a save written by an older version of itself and
+/// migrate the blob before reading it, instead of needing a second registration call for that.
+pub fn register_save_section(
+    tag: u32,
+    version: u32,
+    serialize: SerializeFn,
+    deserialize: DeserializeFn,
+) {
+    // SAFETY: single-threaded; see `SAVE_SECTIONS`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        SAVE_SECTIONS.push(SaveSection {
+            tag,
+            version,
+            serialize,
+            deserialize,
+        });
+    }
+}
+
+/// The result of [`restore_with_sections`].
+pub struct SaveSectionsRestored {
+    /// Whether the restored `VAR_VERSION` script variable matched its compiled-in default (see
+    /// [`ScriptVariables::restore_script_variable_values`]). `false` means this save predates the
+    /// current script-variable schema -- a hint that a registered section's own `stored_version`
+    /// may also predate its current schema.
+    pub script_vars_up_to_date: bool,
+}
+
+/// Dumps the vanilla script variable table via [`ScriptVariables::dump_script_variable_values`],
+/// then appends every registered section's serialized blob as a length-prefixed, tag-keyed
+/// trailer.
+///
+/// Intended to be called from the same save-writing glue that would otherwise call
+/// [`ScriptVariables::dump_script_variable_values`] directly.
+pub fn dump_with_sections(vars: &mut ScriptVariables) -> Vec<u8> {
+    let mut out = Vec::from(vars.dump_script_variable_values());
+    // SAFETY: single-threaded; see `SAVE_SECTIONS`.
+    #[allow(static_mut_refs)]
+    let sections = unsafe { &SAVE_SECTIONS };
+    for section in sections {
+        let mut writer = Writer::new();
+        (section.serialize)(&mut writer);
+        out.extend_from_slice(&section.tag.to_le_bytes());
+        out.extend_from_slice(&section.version.to_le_bytes());
+        out.extend_from_slice(&(writer.buf.len() as u32).to_le_bytes());
+        out.extend_from_slice(&writer.buf);
+    }
+    out
+}
+
+/// Restores the vanilla script variable table from the first 1024 bytes of `data` via
+/// [`ScriptVariables::restore_script_variable_values`], then parses the rest as a trailer written
+/// by [`dump_with_sections`], dispatching each chunk to the section registered for its tag.
+///
+/// A tag with no registered [`register_save_section`] call is skipped, so a save written with
+/// extra sections (e.g. from a mod that's no longer installed) still loads cleanly.
+///
+/// Returns `None` if `data` is shorter than the vanilla 1024-byte payload.
+pub fn restore_with_sections(
+    vars: &mut ScriptVariables,
+    data: &[u8],
+) -> Option<SaveSectionsRestored> {
+    if data.len() < 1024 {
+        return None;
+    }
+    let (vanilla, trailer) = data.split_at(1024);
+    let script_vars_up_to_date = vars.restore_script_variable_values(
+        vanilla.try_into().expect("split_at(1024) guarantees the length"),
+    );
+    read_save_sections(trailer);
+    Some(SaveSectionsRestored {
+        script_vars_up_to_date,
+    })
+}
+
+fn read_save_sections(mut trailer: &[u8]) {
+    const HEADER_LEN: usize = 4 + 4 + 4;
+    while trailer.len() >= HEADER_LEN {
+        let tag = u32::from_le_bytes(trailer[0..4].try_into().unwrap());
+        let version = u32::from_le_bytes(trailer[4..8].try_into().unwrap());
+        let len = u32::from_le_bytes(trailer[8..12].try_into().unwrap()) as usize;
+        trailer = &trailer[HEADER_LEN..];
+        let Some(chunk) = trailer.get(..len) else {
+            break;
+        };
+        trailer = &trailer[len..];
+        // SAFETY: single-threaded; see `SAVE_SECTIONS`. The handler is copied out before the
+        // borrow ends, so a deserializer is free to touch `SAVE_SECTIONS` itself (e.g. to
+        // register further sections it now knows it needs).
+        #[allow(static_mut_refs)]
+        let handler = unsafe {
+            SAVE_SECTIONS
+                .iter()
+                .find(|section| section.tag == tag)
+                .map(|section| section.deserialize)
+        };
+        if let Some(deserialize) = handler {
+            deserialize(version, &mut Reader::new(chunk));
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Dungeon state snapshot/restore subsystem ("save-states"), for tooling that wants to rewind or
+//! retry a dungeon run (e.g. a debugger stepping backward, a "try again" button after a death).
+//!
+//! [`SaveStates::capture_state`] copies the live dungeon global-state block (see
+//! [`crate::api::dungeon_mode::GlobalDungeonData`]) into a fixed slot. The block is full of
+//! pointers into itself (sub-structs, linked lists, ...) that are only valid relative to wherever
+//! it was captured from, so restoring a slot back -- or copying one slot into another -- can't
+//! just be a verbatim `memcpy`: [`SaveStates::restore_state`]/[`SaveStates::copy_state`] rebase
+//! every such self-pointer word-by-word instead. See [`rebase`]'s doc comment for how.
+
+use crate::ffi;
+use alloc::vec::Vec;
+use core::mem::size_of;
+use core::ptr;
+
+use crate::api::overlay::OverlayLoadLease;
+
+/// Number of fixed [`SaveStates`] slots.
+pub const SLOT_COUNT: usize = 4;
+
+/// A single captured copy of the dungeon global-state block, along with the address it was
+/// captured from -- needed to rebase any self-pointers inside it when it's later restored to (or
+/// copied into a slot associated with) a different address.
+struct Slot {
+    bytes: Vec<u8>,
+    base_addr: usize,
+}
+
+/// A fixed set of dungeon state snapshots. See the [module-level docs](self) for the overall
+/// idea.
+pub struct SaveStates {
+    slots: [Option<Slot>; SLOT_COUNT],
+}
+
+impl Default for SaveStates {
+    fn default() -> Self {
+        Self {
+            slots: [None, None, None, None],
+        }
+    }
+}
+
+impl SaveStates {
+    /// Creates an empty set of save-states (every slot unfilled).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copies the live dungeon global-state block into `slot`, overwriting whatever was
+    /// previously captured there.
+    ///
+    /// # Safety
+    /// The caller must make sure the global dungeon struct is currently valid (i.e. a dungeon is
+    /// loaded, see [`crate::api::dungeon_mode::GlobalDungeonData::is_global_dungeon_ptr_null`])
+    /// and that nothing else mutates it while this runs.
+    pub unsafe fn capture_state(&mut self, slot: usize, _ov29: &OverlayLoadLease<29>) {
+        let ptr = ffi::GetDungeonPtrMaster() as *const u8;
+        let bytes = core::slice::from_raw_parts(ptr, size_of::<ffi::dungeon>()).to_vec();
+        self.slots[slot] = Some(Slot {
+            bytes,
+            base_addr: ptr as usize,
+        });
+    }
+
+    /// Restores `slot` back into the live dungeon global-state block, rebasing every self-pointer
+    /// captured inside it from `slot`'s captured address to the block's current live address (see
+    /// [`rebase`]).
+    ///
+    /// Returns whether there was a captured state in `slot` to restore; does nothing if not.
+    ///
+    /// # Safety
+    /// Same as [`Self::capture_state`], plus the live block must still be exactly
+    /// `size_of::<ffi::dungeon>()` bytes, true as long as the same dungeon overlay is loaded.
+    pub unsafe fn restore_state(&mut self, slot: usize, _ov29: &OverlayLoadLease<29>) -> bool {
+        let Some(saved) = self.slots[slot].as_ref() else {
+            return false;
+        };
+        let dst_base = ffi::GetDungeonPtrMaster() as usize;
+        let bytes = rebase(&saved.bytes, saved.base_addr, dst_base);
+        ptr::copy_nonoverlapping(bytes.as_ptr(), dst_base as *mut u8, bytes.len());
+        true
+    }
+
+    /// Copies `from`'s captured state into `to`, rebasing `from`'s self-pointers from its own
+    /// captured address to whatever address `to` is itself associated with -- the base address
+    /// `to` was last [`Self::capture_state`]d from, if any, so that a later [`Self::restore_state`]
+    /// of `to` (into that same live location) sees internally-consistent pointers. If `to` has
+    /// never been captured, its base address is taken to be `from`'s own (a verbatim copy).
+    ///
+    /// Returns whether there was a captured state in `from` to copy; does nothing if not.
+    pub fn copy_state(&mut self, from: usize, to: usize) -> bool {
+        let Some(from_slot) = self.slots[from].as_ref() else {
+            return false;
+        };
+        let src_base = from_slot.base_addr;
+        let dst_base = self.slots[to]
+            .as_ref()
+            .map(|slot| slot.base_addr)
+            .unwrap_or(src_base);
+        let bytes = rebase(&from_slot.bytes, src_base, dst_base);
+        self.slots[to] = Some(Slot {
+            bytes,
+            base_addr: dst_base,
+        });
+        true
+    }
+
+    /// Whether `slot` currently holds a captured state.
+    pub fn is_captured(&self, slot: usize) -> bool {
+        self.slots[slot].is_some()
+    }
+}
+
+/// Rebases every machine word in `bytes` (captured while the block lived at `src_base`) for a
+/// block now living at `dst_base`: a word whose value falls inside the source block's own address
+/// range `[src_base, src_base + bytes.len())` is an intra-block self-pointer, and is shifted by
+/// `dst_base - src_base`; any other word (a plain integer, a pointer to something outside the
+/// block entirely) is copied verbatim. This is what keeps the restored state internally
+/// consistent when the block lands at a different address than where it was captured.
+///
+/// A trailing run of bytes shorter than a machine word (if `bytes.len()` isn't word-aligned) is
+/// copied verbatim, same as any other non-pointer data.
+fn rebase(bytes: &[u8], src_base: usize, dst_base: usize) -> Vec<u8> {
+    const WORD: usize = size_of::<usize>();
+
+    let offset = dst_base.wrapping_sub(src_base);
+    let mut out = bytes.to_vec();
+    for word in out.chunks_exact_mut(WORD) {
+        let value = usize::from_ne_bytes(word.try_into().unwrap());
+        if value >= src_base && value < src_base + bytes.len() {
+            word.copy_from_slice(&value.wrapping_add(offset).to_ne_bytes());
+        }
+    }
+    out
+}
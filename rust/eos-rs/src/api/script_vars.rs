@@ -3,7 +3,10 @@
 use crate::ctypes::c_void;
 use crate::ffi;
 use crate::ffi::script_var_type;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
 use alloc::ffi::CString;
+use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
 use core::ffi::CStr;
@@ -111,7 +114,9 @@ impl TryFrom<script_var_type::Type> for ScriptVariableValueType {
 }
 
 /// Value of a script variable.
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScriptVariableValue {
     None,
     Bit(bool),
@@ -303,6 +308,31 @@ impl UnwrapScriptVariableValueAs<i32> for ScriptVariableValue {
     }
 }
 
+/// A structured snapshot of every global script variable's value, keyed by name, produced by
+/// [`ScriptVariables::snapshot`] and consumed by [`ScriptVariables::apply_snapshot`].
+///
+/// With the `serde` feature enabled (gated the same way as
+/// [`crate::api::dungeon_mode::dungeon_history`]'s types), this derives `Serialize`/
+/// `Deserialize`, so a snapshot can round-trip through JSON for save comparison or scripted test
+/// fixtures.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScriptVariableSnapshot(BTreeMap<String, ScriptVariableValue>);
+
+impl ScriptVariableSnapshot {
+    /// The snapshotted value of the variable named `name`, if it was present when the snapshot
+    /// was taken.
+    pub fn get(&self, name: &str) -> Option<&ScriptVariableValue> {
+        self.0.get(name)
+    }
+
+    /// Every name/value pair in the snapshot.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ScriptVariableValue)> {
+        self.0.iter().map(|(name, value)| (name.as_str(), value))
+    }
+}
+
 /// Helper struct for manipulating the global and local script variables.
 pub struct ScriptVariables(PhantomData<()>);
 
@@ -330,14 +360,44 @@ impl ScriptVariables {
     /// for local variables.
     pub fn global_variable(&self, var_id: ScriptVariableId) -> GlobalScriptVariableRef {
         assert!(!var_id.is_local());
-        GlobalScriptVariableRef(var_id, PhantomData)
+        GlobalScriptVariableRef(var_id, BoundsCheckPolicy::Panic, PhantomData)
     }
 
     /// Get a mutable reference to the global variable. This must not be used
     /// for local variables.
     pub fn global_variable_mut(&mut self, var_id: ScriptVariableId) -> GlobalScriptVariableMut {
         assert!(!(var_id.is_local()));
-        GlobalScriptVariableMut(var_id, PhantomData)
+        GlobalScriptVariableMut(var_id, BoundsCheckPolicy::Panic, PhantomData)
+    }
+
+    /// Finds the ID of the global script variable named `name`, if any (an exact, case-sensitive
+    /// match against [`ScriptVariableRead::name`]).
+    ///
+    /// Scans every global variable via [`Self::iter`], so this is `O(n)` in the number of global
+    /// variables; callers resolving the same name repeatedly should cache the resulting ID
+    /// rather than calling this in a hot loop.
+    ///
+    /// ```ignore
+    /// let vars = unsafe { ScriptVariables::get() };
+    /// let scenario_main = vars.global_variable(vars.by_name("SCENARIO_MAIN").unwrap());
+    /// ```
+    pub fn by_name(&self, name: &str) -> Option<ScriptVariableId> {
+        self.iter().find(|(_, var)| var.name() == name).map(|(id, _)| id)
+    }
+
+    /// Returns every global script variable, paired with a [`GlobalScriptVariableRef`] for
+    /// reading its metadata (name, type, capacity) and value, so tools can enumerate the whole
+    /// table without hardcoding the ID map.
+    ///
+    /// Iterates `0..`[`ScriptVariableId::VAR_LOCAL0`], the same boundary
+    /// [`ScriptVariableId::is_local`] checks against -- local variable IDs aren't included, since
+    /// they have no fixed descriptor without a local variable table to resolve them against.
+    pub fn iter(&self) -> impl Iterator<Item = (ScriptVariableId, GlobalScriptVariableRef<'_>)> + '_ {
+        (0..ScriptVariableId::VAR_LOCAL0.id()).map(|id| {
+            // SAFETY: id is in 0..VAR_LOCAL0, i.e. a valid global script variable ID.
+            let id = unsafe { ScriptVariableId::new(id) };
+            (id, GlobalScriptVariableRef(id, BoundsCheckPolicy::Panic, PhantomData))
+        })
     }
 
     /// Get a reference to the local variable. This signature
@@ -351,7 +411,7 @@ impl ScriptVariables {
         var_id: ScriptVariableId,
     ) -> LocalScriptVariableRef {
         assert!(var_id.is_local());
-        LocalScriptVariableRef(local_var_vals, var_id, PhantomData)
+        LocalScriptVariableRef(local_var_vals, var_id, BoundsCheckPolicy::Panic, PhantomData)
     }
 
     /// Get a mutable reference to the local variable. This signature
@@ -365,7 +425,7 @@ impl ScriptVariables {
         var_id: ScriptVariableId,
     ) -> LocalScriptVariableMut {
         assert!(var_id.is_local());
-        LocalScriptVariableMut(local_var_vals, var_id, PhantomData)
+        LocalScriptVariableMut(local_var_vals, var_id, BoundsCheckPolicy::Panic, PhantomData)
     }
 
     /// Saves event flag script variables (see the code for an exhaustive list) to their respective
@@ -399,6 +459,30 @@ impl ScriptVariables {
         unsafe { ffi::RestoreScriptVariableValues(force_mut_ptr!(src)) > 0 }
     }
 
+    /// Walks every global script variable (see [`Self::iter`]) and returns a structured,
+    /// name-keyed [`ScriptVariableSnapshot`] of their current values -- the typed, inspectable
+    /// counterpart to [`Self::dump_script_variable_values`]'s opaque byte blob, so save states
+    /// can be compared or fixtures authored without hand-decoding a raw array.
+    pub fn snapshot(&self) -> ScriptVariableSnapshot {
+        let mut values = BTreeMap::new();
+        for (_, var) in self.iter() {
+            values.insert(var.name().to_string(), var.value());
+        }
+        ScriptVariableSnapshot(values)
+    }
+
+    /// Writes every value in `snapshot` back to the global variable of the same name (via
+    /// [`Self::by_name`]), using the existing [`ScriptVariableWrite::write`] path.
+    ///
+    /// Names in `snapshot` that no longer resolve to a global variable are skipped.
+    pub fn apply_snapshot(&mut self, snapshot: &ScriptVariableSnapshot) {
+        for (name, value) in snapshot.iter() {
+            if let Some(id) = self.by_name(name) {
+                self.global_variable_mut(id).write(value.clone());
+            }
+        }
+    }
+
     /// Initializes an assortment of event flag script variables (see the code for an exhaustive
     /// list).
     pub fn init_event_flags(&mut self) {
@@ -441,8 +525,17 @@ impl ScriptVariables {
     }
 
     /// Sets the current value of the NOTIFY_NOTE script variable.
+    ///
+    /// Unlike [`ScriptVariableWrite::write_raw`] and friends, this doesn't go through a
+    /// [`ScriptVariableId`]-addressed write at all (there's a dedicated `SetNotifyNote` function),
+    /// so the write observer notification (see [`Self::observe_writes`]) is driven from here
+    /// directly instead, resolving NOTIFY_NOTE's ID via [`Self::by_name`] the same way a caller
+    /// would.
     pub fn set_notify_note(&mut self, value: bool) {
         unsafe { ffi::SetNotifyNote(value as ffi::bool_) }
+        if let Some(id) = self.by_name("NOTIFY_NOTE") {
+            notify_write(id, ptr::null_mut(), &ScriptVariableValue::Bit(value));
+        }
     }
 
     /// Gets the value of the GAME_MODE script variable.
@@ -454,21 +547,647 @@ impl ScriptVariables {
     pub fn get_special_episode_type(&self) -> i32 {
         unsafe { ffi::GetSpecialEpisodeType() }
     }
+
+    /// Parses and evaluates `expr` as a boolean condition over global script variables, e.g.
+    /// `"SCENARIO_MAIN >= 12 && NOTIFY_NOTE"` or `"!(FLAG[3] == 0) || SCENARIO_SELECT != 1"`.
+    ///
+    /// Grammar, loosest-binding first: `||`, then `&&`, then unary `!`, then one of the comparison
+    /// operators `== != < <= > >=` (binding tighter than all of the above), with `(...)` grouping
+    /// and identifiers as the leaves. An identifier used on its own (not as either side of a
+    /// comparison) is coerced to a boolean the same way [`ScriptVariableValue::as_raw`] feeds
+    /// [`ScriptVariableRead::value_raw`]-style checks elsewhere in this crate: the raw `i32` value
+    /// is truthy if `> 0`. `NAME[index]` reads a single array element via
+    /// [`ScriptVariableRead::value_raw_indexed`] instead of the whole variable.
+    ///
+    /// Each identifier is resolved through [`Self::by_name`] every time `expr` is evaluated; like
+    /// `by_name` itself, repeatedly evaluating the same condition in a hot loop is better served by
+    /// resolving the names once and reading the variables directly.
+    pub fn eval_condition(&self, expr: &str) -> Result<bool, EvalError> {
+        let tokens = condition::tokenize(expr)?;
+        let mut parser = condition::Parser::new(&tokens);
+        let ast = parser.parse_expr()?;
+        parser.expect_end()?;
+        self.eval_bool_expr(&ast)
+    }
+
+    fn eval_bool_expr(&self, expr: &condition::BoolExpr) -> Result<bool, EvalError> {
+        use condition::BoolExpr;
+        Ok(match expr {
+            BoolExpr::Or(lhs, rhs) => self.eval_bool_expr(lhs)? || self.eval_bool_expr(rhs)?,
+            BoolExpr::And(lhs, rhs) => self.eval_bool_expr(lhs)? && self.eval_bool_expr(rhs)?,
+            BoolExpr::Not(inner) => !self.eval_bool_expr(inner)?,
+            BoolExpr::Cmp(op, lhs, rhs) => {
+                op.apply(self.eval_value_expr(lhs)?, self.eval_value_expr(rhs)?)
+            }
+            BoolExpr::Truthy(value) => self.eval_value_expr(value)? > 0,
+        })
+    }
+
+    fn eval_value_expr(&self, expr: &condition::ValueExpr) -> Result<i32, EvalError> {
+        use condition::ValueExpr;
+        match expr {
+            ValueExpr::Int(value) => Ok(*value),
+            ValueExpr::Var(name) => {
+                let id = self
+                    .by_name(name)
+                    .ok_or_else(|| EvalError::UnknownVariable(name.clone()))?;
+                Ok(self.global_variable(id).value().as_raw())
+            }
+            ValueExpr::Indexed(name, index) => {
+                let id = self
+                    .by_name(name)
+                    .ok_or_else(|| EvalError::UnknownVariable(name.clone()))?;
+                Ok(self.global_variable(id).value_raw_indexed(*index))
+            }
+        }
+    }
+}
+
+/// An error produced by [`ScriptVariables::eval_condition`].
+#[derive(Debug)]
+pub enum EvalError {
+    /// `expr` couldn't be tokenized or parsed; the payload is a short, human-readable description
+    /// of what went wrong and, where possible, where.
+    Parse(String),
+    /// `expr` referenced a name that doesn't resolve via [`ScriptVariables::by_name`].
+    UnknownVariable(String),
+}
+
+/// The tokenizer/parser/AST backing [`ScriptVariables::eval_condition`], kept in its own
+/// sub-module since none of it needs access to `script_vars`'s other internals -- it only ever
+/// touches [`EvalError`], and is evaluated against a live [`ScriptVariables`] by its caller rather
+/// than resolving variables itself.
+mod condition {
+    use super::EvalError;
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub(super) enum Token {
+        Ident(String),
+        Int(i32),
+        Eq,
+        Ne,
+        Lt,
+        Le,
+        Gt,
+        Ge,
+        AndAnd,
+        OrOr,
+        Not,
+        LParen,
+        RParen,
+        LBracket,
+        RBracket,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(super) enum CmpOp {
+        Eq,
+        Ne,
+        Lt,
+        Le,
+        Gt,
+        Ge,
+    }
+
+    impl CmpOp {
+        pub(super) fn apply(self, lhs: i32, rhs: i32) -> bool {
+            match self {
+                CmpOp::Eq => lhs == rhs,
+                CmpOp::Ne => lhs != rhs,
+                CmpOp::Lt => lhs < rhs,
+                CmpOp::Le => lhs <= rhs,
+                CmpOp::Gt => lhs > rhs,
+                CmpOp::Ge => lhs >= rhs,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub(super) enum ValueExpr {
+        Var(String),
+        Indexed(String, i32),
+        Int(i32),
+    }
+
+    #[derive(Debug, Clone)]
+    pub(super) enum BoolExpr {
+        Or(Box<BoolExpr>, Box<BoolExpr>),
+        And(Box<BoolExpr>, Box<BoolExpr>),
+        Not(Box<BoolExpr>),
+        Cmp(CmpOp, ValueExpr, ValueExpr),
+        Truthy(ValueExpr),
+    }
+
+    pub(super) fn tokenize(input: &str) -> Result<Vec<Token>, EvalError> {
+        let chars: Vec<char> = input.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            match c {
+                c if c.is_whitespace() => i += 1,
+                '(' => {
+                    tokens.push(Token::LParen);
+                    i += 1;
+                }
+                ')' => {
+                    tokens.push(Token::RParen);
+                    i += 1;
+                }
+                '[' => {
+                    tokens.push(Token::LBracket);
+                    i += 1;
+                }
+                ']' => {
+                    tokens.push(Token::RBracket);
+                    i += 1;
+                }
+                '!' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Ne);
+                    i += 2;
+                }
+                '!' => {
+                    tokens.push(Token::Not);
+                    i += 1;
+                }
+                '=' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Eq);
+                    i += 2;
+                }
+                '<' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Le);
+                    i += 2;
+                }
+                '<' => {
+                    tokens.push(Token::Lt);
+                    i += 1;
+                }
+                '>' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Ge);
+                    i += 2;
+                }
+                '>' => {
+                    tokens.push(Token::Gt);
+                    i += 1;
+                }
+                '&' if chars.get(i + 1) == Some(&'&') => {
+                    tokens.push(Token::AndAnd);
+                    i += 2;
+                }
+                '|' if chars.get(i + 1) == Some(&'|') => {
+                    tokens.push(Token::OrOr);
+                    i += 2;
+                }
+                '-' | '0'..='9' => {
+                    let start = i;
+                    if c == '-' {
+                        i += 1;
+                    }
+                    while chars.get(i).is_some_and(|c| c.is_ascii_digit()) {
+                        i += 1;
+                    }
+                    let text: String = chars[start..i].iter().collect();
+                    let value = text
+                        .parse::<i32>()
+                        .map_err(|_| EvalError::Parse(format!("invalid integer literal '{}'", text)))?;
+                    tokens.push(Token::Int(value));
+                }
+                c if c.is_alphabetic() || c == '_' => {
+                    let start = i;
+                    while chars
+                        .get(i)
+                        .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+                    {
+                        i += 1;
+                    }
+                    tokens.push(Token::Ident(chars[start..i].iter().collect()));
+                }
+                c => {
+                    return Err(EvalError::Parse(format!(
+                        "unexpected character '{}' at position {}",
+                        c, i
+                    )))
+                }
+            }
+        }
+        Ok(tokens)
+    }
+
+    /// A small recursive-descent parser over an already-tokenized condition.
+    pub(super) struct Parser<'t> {
+        tokens: &'t [Token],
+        pos: usize,
+    }
+
+    impl<'t> Parser<'t> {
+        pub(super) fn new(tokens: &'t [Token]) -> Self {
+            Self { tokens, pos: 0 }
+        }
+
+        fn peek(&self) -> Option<&Token> {
+            self.tokens.get(self.pos)
+        }
+
+        fn advance(&mut self) -> Option<Token> {
+            let token = self.tokens.get(self.pos).cloned();
+            self.pos += 1;
+            token
+        }
+
+        fn expect(&mut self, expected: Token) -> Result<(), EvalError> {
+            match self.advance() {
+                Some(ref token) if *token == expected => Ok(()),
+                other => Err(EvalError::Parse(format!(
+                    "expected {:?}, found {:?}",
+                    expected, other
+                ))),
+            }
+        }
+
+        pub(super) fn expect_end(&self) -> Result<(), EvalError> {
+            if self.pos >= self.tokens.len() {
+                Ok(())
+            } else {
+                Err(EvalError::Parse(format!(
+                    "unexpected trailing tokens starting at {:?}",
+                    self.tokens[self.pos]
+                )))
+            }
+        }
+
+        pub(super) fn parse_expr(&mut self) -> Result<BoolExpr, EvalError> {
+            self.parse_or()
+        }
+
+        fn parse_or(&mut self) -> Result<BoolExpr, EvalError> {
+            let mut lhs = self.parse_and()?;
+            while matches!(self.peek(), Some(Token::OrOr)) {
+                self.pos += 1;
+                let rhs = self.parse_and()?;
+                lhs = BoolExpr::Or(Box::new(lhs), Box::new(rhs));
+            }
+            Ok(lhs)
+        }
+
+        fn parse_and(&mut self) -> Result<BoolExpr, EvalError> {
+            let mut lhs = self.parse_unary()?;
+            while matches!(self.peek(), Some(Token::AndAnd)) {
+                self.pos += 1;
+                let rhs = self.parse_unary()?;
+                lhs = BoolExpr::And(Box::new(lhs), Box::new(rhs));
+            }
+            Ok(lhs)
+        }
+
+        fn parse_unary(&mut self) -> Result<BoolExpr, EvalError> {
+            if matches!(self.peek(), Some(Token::Not)) {
+                self.pos += 1;
+                return Ok(BoolExpr::Not(Box::new(self.parse_unary()?)));
+            }
+            self.parse_comparison()
+        }
+
+        fn parse_comparison(&mut self) -> Result<BoolExpr, EvalError> {
+            if matches!(self.peek(), Some(Token::LParen)) {
+                self.pos += 1;
+                let inner = self.parse_or()?;
+                self.expect(Token::RParen)?;
+                return Ok(inner);
+            }
+            let lhs = self.parse_value()?;
+            let op = match self.peek() {
+                Some(Token::Eq) => Some(CmpOp::Eq),
+                Some(Token::Ne) => Some(CmpOp::Ne),
+                Some(Token::Lt) => Some(CmpOp::Lt),
+                Some(Token::Le) => Some(CmpOp::Le),
+                Some(Token::Gt) => Some(CmpOp::Gt),
+                Some(Token::Ge) => Some(CmpOp::Ge),
+                _ => None,
+            };
+            match op {
+                Some(op) => {
+                    self.pos += 1;
+                    let rhs = self.parse_value()?;
+                    Ok(BoolExpr::Cmp(op, lhs, rhs))
+                }
+                None => Ok(BoolExpr::Truthy(lhs)),
+            }
+        }
+
+        fn parse_value(&mut self) -> Result<ValueExpr, EvalError> {
+            match self.advance() {
+                Some(Token::Int(value)) => Ok(ValueExpr::Int(value)),
+                Some(Token::Ident(name)) => {
+                    if matches!(self.peek(), Some(Token::LBracket)) {
+                        self.pos += 1;
+                        let index = match self.advance() {
+                            Some(Token::Int(value)) => value,
+                            other => {
+                                return Err(EvalError::Parse(format!(
+                                    "expected an integer index inside '[...]', found {:?}",
+                                    other
+                                )))
+                            }
+                        };
+                        self.expect(Token::RBracket)?;
+                        Ok(ValueExpr::Indexed(name, index))
+                    } else {
+                        Ok(ValueExpr::Var(name))
+                    }
+                }
+                other => Err(EvalError::Parse(format!(
+                    "expected a variable name or integer literal, found {:?}",
+                    other
+                ))),
+            }
+        }
+    }
+}
+
+/// What a registered write observer (see [`ScriptVariables::observe_writes`] and friends) is
+/// interested in.
+enum ObserverFilter {
+    /// Fires for every write, to any variable.
+    Any,
+    /// Fires only for writes to this specific variable.
+    Id(ScriptVariableId),
+    /// Fires only for writes to the global variable with this name (see [`ScriptVariables::by_name`]).
+    Name(String),
+}
+
+struct WriteObserver {
+    id: WriteObserverId,
+    filter: ObserverFilter,
+    callback: Box<dyn FnMut(ScriptVariableId, &ScriptVariableValue)>,
+}
+
+/// A handle to a write observer registered via [`ScriptVariables::observe_writes`]/
+/// [`ScriptVariables::observe_writes_to`]/[`ScriptVariables::observe_writes_named`], usable with
+/// [`ScriptVariables::remove_write_observer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteObserverId(u32);
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// writes (and thus observer notifications) are only ever dispatched from the main game loop.
+static mut WRITE_OBSERVERS: Vec<WriteObserver> = Vec::new();
+static mut NEXT_WRITE_OBSERVER_ID: u32 = 0;
+
+fn push_write_observer(
+    filter: ObserverFilter,
+    callback: impl FnMut(ScriptVariableId, &ScriptVariableValue) + 'static,
+) -> WriteObserverId {
+    // SAFETY: single-threaded; see `WRITE_OBSERVERS`/`NEXT_WRITE_OBSERVER_ID`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        let id = WriteObserverId(NEXT_WRITE_OBSERVER_ID);
+        NEXT_WRITE_OBSERVER_ID += 1;
+        WRITE_OBSERVERS.push(WriteObserver {
+            id,
+            filter,
+            callback: Box::new(callback),
+        });
+        id
+    }
+}
+
+/// Invokes every registered write observer whose filter matches `id`, with `value` as the
+/// variable's value after the write. This is the central path [`ScriptVariableWrite::write_raw`]/
+/// [`ScriptVariableWrite::write_raw_indexed`]/[`ScriptVariableWrite::write_bytes`] (and thus every
+/// other [`ScriptVariableWrite`] method, all of which are built on top of those three) and the
+/// typed setters on [`ScriptVariables`] (e.g. [`ScriptVariables::set_notify_note`]) call into
+/// after their FFI store succeeds.
+fn notify_write(id: ScriptVariableId, table: *mut c_void, value: &ScriptVariableValue) {
+    // SAFETY: single-threaded; see `WRITE_OBSERVERS`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        if WRITE_OBSERVERS.is_empty() {
+            return;
+        }
+        let name = descriptor_cached(table, id).name;
+        for observer in WRITE_OBSERVERS.iter_mut() {
+            let matches = match &observer.filter {
+                ObserverFilter::Any => true,
+                ObserverFilter::Id(filter_id) => filter_id.id() == id.id(),
+                ObserverFilter::Name(filter_name) => filter_name == name,
+            };
+            if matches {
+                (observer.callback)(id, value);
+            }
+        }
+    }
+}
+
+impl ScriptVariables {
+    /// Registers `callback` to run after every successful write to any script variable, through
+    /// any of the [`ScriptVariableWrite`] methods or the typed setters on this type.
+    ///
+    /// Returns a [`WriteObserverId`] usable with [`Self::remove_write_observer`]. See
+    /// [`Self::observe_writes_to`]/[`Self::observe_writes_named`] to only be notified about a
+    /// specific variable instead of every write.
+    pub fn observe_writes(
+        &mut self,
+        callback: impl FnMut(ScriptVariableId, &ScriptVariableValue) + 'static,
+    ) -> WriteObserverId {
+        push_write_observer(ObserverFilter::Any, callback)
+    }
+
+    /// Like [`Self::observe_writes`], but only fires for writes to `var_id`.
+    pub fn observe_writes_to(
+        &mut self,
+        var_id: ScriptVariableId,
+        callback: impl FnMut(ScriptVariableId, &ScriptVariableValue) + 'static,
+    ) -> WriteObserverId {
+        push_write_observer(ObserverFilter::Id(var_id), callback)
+    }
+
+    /// Like [`Self::observe_writes`], but only fires for writes to the global variable named
+    /// `name` (see [`Self::by_name`]), so a mod doesn't need to resolve and hold onto the
+    /// [`ScriptVariableId`] itself.
+    pub fn observe_writes_named(
+        &mut self,
+        name: &str,
+        callback: impl FnMut(ScriptVariableId, &ScriptVariableValue) + 'static,
+    ) -> WriteObserverId {
+        push_write_observer(ObserverFilter::Name(name.to_string()), callback)
+    }
+
+    /// Unregisters a write observer previously returned by [`Self::observe_writes`]/
+    /// [`Self::observe_writes_to`]/[`Self::observe_writes_named`].
+    ///
+    /// Returns whether an observer was actually removed.
+    pub fn remove_write_observer(&mut self, observer: WriteObserverId) -> bool {
+        // SAFETY: single-threaded; see `WRITE_OBSERVERS`.
+        #[allow(static_mut_refs)]
+        unsafe {
+            let before = WRITE_OBSERVERS.len();
+            WRITE_OBSERVERS.retain(|entry| entry.id != observer);
+            WRITE_OBSERVERS.len() != before
+        }
+    }
 }
 
 /// Reference to a global script variable, see [`ScriptVariableRead`].
-pub struct GlobalScriptVariableRef<'a>(ScriptVariableId, PhantomData<&'a ()>);
+pub struct GlobalScriptVariableRef<'a>(ScriptVariableId, BoundsCheckPolicy, PhantomData<&'a ()>);
+
+impl<'a> GlobalScriptVariableRef<'a> {
+    /// Sets the [`BoundsCheckPolicy`] this accessor applies to indexed reads.
+    pub fn with_bounds_policy(mut self, policy: BoundsCheckPolicy) -> Self {
+        self.1 = policy;
+        self
+    }
+}
 
 /// Mutable reference to a global script variable, see
 /// [`ScriptVariableRead`] and [`ScriptVariableWrite`].
-pub struct GlobalScriptVariableMut<'a>(ScriptVariableId, PhantomData<&'a ()>);
+pub struct GlobalScriptVariableMut<'a>(ScriptVariableId, BoundsCheckPolicy, PhantomData<&'a ()>);
+
+impl<'a> GlobalScriptVariableMut<'a> {
+    /// Sets the [`BoundsCheckPolicy`] this accessor applies to indexed reads and writes.
+    pub fn with_bounds_policy(mut self, policy: BoundsCheckPolicy) -> Self {
+        self.1 = policy;
+        self
+    }
+}
 
 /// Reference to a local script variable, see [`ScriptVariableRead`].
-pub struct LocalScriptVariableRef<'a>(*mut c_void, ScriptVariableId, PhantomData<&'a ()>);
+pub struct LocalScriptVariableRef<'a>(
+    *mut c_void,
+    ScriptVariableId,
+    BoundsCheckPolicy,
+    PhantomData<&'a ()>,
+);
+
+impl<'a> LocalScriptVariableRef<'a> {
+    /// Sets the [`BoundsCheckPolicy`] this accessor applies to indexed reads.
+    pub fn with_bounds_policy(mut self, policy: BoundsCheckPolicy) -> Self {
+        self.2 = policy;
+        self
+    }
+}
 
 /// Mutable reference to a local script variable, see
 /// [`ScriptVariableRead`] and [`ScriptVariableWrite`].
-pub struct LocalScriptVariableMut<'a>(*mut c_void, ScriptVariableId, PhantomData<&'a ()>);
+pub struct LocalScriptVariableMut<'a>(
+    *mut c_void,
+    ScriptVariableId,
+    BoundsCheckPolicy,
+    PhantomData<&'a ()>,
+);
+
+impl<'a> LocalScriptVariableMut<'a> {
+    /// Sets the [`BoundsCheckPolicy`] this accessor applies to indexed reads and writes.
+    pub fn with_bounds_policy(mut self, policy: BoundsCheckPolicy) -> Self {
+        self.2 = policy;
+        self
+    }
+}
+
+/// A [`ffi::script_var`] descriptor, with its [`ScriptVariableValueType`] and name already
+/// decoded, as cached by [`descriptor_cached`].
+struct CachedDescriptor {
+    desc: &'static ffi::script_var,
+    var_type: ScriptVariableValueType,
+    name: &'static str,
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded.
+/// Entries are only ever appended, never removed or mutated, since a variable's descriptor is
+/// static game data that doesn't change at runtime.
+static mut DESCRIPTOR_CACHE: Vec<(u32, CachedDescriptor)> = Vec::new();
+
+/// Resolves `id`'s [`ffi::script_var`] descriptor, memoizing it (along with its decoded
+/// [`ScriptVariableValueType`] and name) the first time it's fetched, so a caller touching
+/// several metadata fields of the same variable (`var_type`, `capacity`, `is_array`, `name`) pays
+/// one `ffi::LoadScriptVariableRaw` call instead of one per field.
+///
+/// `table` only matters on a cache miss, for looking up a local variable's descriptor; the
+/// descriptor itself is cached purely by ID, since it's the same static data regardless of which
+/// local variable table (if any) it was resolved through.
+fn descriptor_cached(table: *mut c_void, id: ScriptVariableId) -> &'static CachedDescriptor {
+    // SAFETY: single-threaded; see `DESCRIPTOR_CACHE`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        if let Some(index) = DESCRIPTOR_CACHE
+            .iter()
+            .position(|(cached_id, _)| *cached_id == id.id())
+        {
+            return &DESCRIPTOR_CACHE[index].1;
+        }
+        let mut out = ffi::script_var_desc {
+            desc: ptr::null_mut(),
+            value: ptr::null_mut(),
+        };
+        ffi::LoadScriptVariableRaw(&mut out, table, id);
+        let ffi::script_var_desc { desc, .. } = out;
+        let desc: &'static ffi::script_var = &*desc;
+        let var_type = desc
+            .type_
+            .val()
+            .try_into()
+            .expect("The variable has a corrupted type.");
+        let name = CStr::from_ptr(desc.name).to_str().unwrap();
+        DESCRIPTOR_CACHE.push((
+            id.id(),
+            CachedDescriptor {
+                desc,
+                var_type,
+                name,
+            },
+        ));
+        &DESCRIPTOR_CACHE.last().unwrap().1
+    }
+}
+
+/// How an indexed accessor (see [`ScriptVariableRead::value_indexed`]/
+/// [`ScriptVariableWrite::write_indexed`] and their `_raw` counterparts) handles an out-of-range
+/// index. Set on an accessor via e.g. [`GlobalScriptVariableMut::with_bounds_policy`]; named and
+/// shaped after naga's `BoundsCheckPolicy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundsCheckPolicy {
+    /// Panic on an out-of-range index. The default, and this crate's original behavior.
+    Panic,
+    /// Rewrite an out-of-range index to the last valid one (`capacity() - 1`).
+    Clamp,
+    /// Turn an out-of-range write into a no-op, and an out-of-range read into
+    /// [`ScriptVariableValue::None`].
+    Skip,
+    /// Don't check eagerly; callers are expected to bounds-check themselves through
+    /// [`ScriptVariableRead::try_value_indexed`]/[`ScriptVariableWrite::try_write_indexed`]
+    /// instead. Behaves like [`Self::Skip`] if an indexed accessor is used directly anyway.
+    Checked,
+}
+
+impl Default for BoundsCheckPolicy {
+    fn default() -> Self {
+        BoundsCheckPolicy::Panic
+    }
+}
+
+/// The result of resolving a requested index against a variable's capacity and
+/// [`BoundsCheckPolicy`]; shared by every indexed read/write path so there's exactly one (correct)
+/// bounds computation.
+enum IndexResolution {
+    InBounds(i32),
+    OutOfBounds,
+}
+
+/// Panics for [`BoundsCheckPolicy::Panic`]; otherwise resolves `index` against `capacity` per
+/// `policy` (see [`BoundsCheckPolicy`]'s variants).
+fn resolve_index(capacity: usize, index: i32, policy: BoundsCheckPolicy) -> IndexResolution {
+    if index >= 0 && (index as usize) < capacity {
+        return IndexResolution::InBounds(index);
+    }
+    match policy {
+        BoundsCheckPolicy::Panic => panic!("Out-of-bounds."),
+        BoundsCheckPolicy::Clamp if capacity > 0 => IndexResolution::InBounds(capacity as i32 - 1),
+        BoundsCheckPolicy::Clamp | BoundsCheckPolicy::Skip | BoundsCheckPolicy::Checked => {
+            IndexResolution::OutOfBounds
+        }
+    }
+}
 
 /// Read actions for script variables.
 pub trait ScriptVariableRead: PartialEq + Eq {
@@ -478,17 +1197,20 @@ pub trait ScriptVariableRead: PartialEq + Eq {
     /// Returns the variable ID
     fn id(&self) -> ScriptVariableId;
 
+    /// The [`BoundsCheckPolicy`] this accessor applies to indexed reads (and, for types that also
+    /// implement [`ScriptVariableWrite`], indexed writes). Defaults to [`BoundsCheckPolicy::Panic`],
+    /// this crate's original behavior; override via `with_bounds_policy` on the concrete accessor.
+    fn bounds_check_policy(&self) -> BoundsCheckPolicy {
+        BoundsCheckPolicy::Panic
+    }
+
     /// Loads a script variable descriptor for a given ID.
+    ///
+    /// Backed by [`descriptor_cached`], so calling this (or any of [`Self::var_type`]/
+    /// [`Self::capacity`]/[`Self::is_array`]/[`Self::name`]) more than once for the same
+    /// [`ScriptVariableId`] only costs one `ffi::LoadScriptVariableRaw` call in total.
     fn descriptor(&self) -> &ffi::script_var {
-        let mut out = ffi::script_var_desc {
-            desc: ptr::null_mut(),
-            value: ptr::null_mut(),
-        };
-        unsafe {
-            ffi::LoadScriptVariableRaw(&mut out, self.internal_local_var_table(), self.id());
-            let ffi::script_var_desc { desc, .. } = out;
-            &*desc
-        }
+        descriptor_cached(self.internal_local_var_table(), self.id()).desc
     }
 
     /// Returns whether or not this is a local variable (as opposed to a global one).
@@ -498,11 +1220,7 @@ pub trait ScriptVariableRead: PartialEq + Eq {
 
     /// Returns the type of the variable
     fn var_type(&self) -> ScriptVariableValueType {
-        let desc = self.descriptor();
-        desc.type_
-            .val()
-            .try_into()
-            .expect("The variable has a corrupted type.")
+        descriptor_cached(self.internal_local_var_table(), self.id()).var_type
     }
 
     /// Returns whether or not the variable is an array.
@@ -512,17 +1230,14 @@ pub trait ScriptVariableRead: PartialEq + Eq {
 
     /// Returns the number of elements in the array or 1 if it's not an array.
     fn capacity(&self) -> usize {
-        let desc = self.descriptor();
-        desc.n_values as usize
+        descriptor_cached(self.internal_local_var_table(), self.id())
+            .desc
+            .n_values as usize
     }
 
     /// Returns the name of the variable.
     fn name(&self) -> &str {
-        let desc = self.descriptor();
-        unsafe {
-            let c_str = CStr::from_ptr(desc.name);
-            c_str.to_str().unwrap()
-        }
+        descriptor_cached(self.internal_local_var_table(), self.id()).name
     }
 
     /// Loads the value of a script variable. If this variable is an array, the value at index 0
@@ -536,12 +1251,29 @@ pub trait ScriptVariableRead: PartialEq + Eq {
     /// Loads the value of a script variable at some index (for script variables that are arrays).
     ///
     /// This will return the value of the variable as a `i32`, no matter the type of the variable.
+    ///
+    /// An out-of-range `index` is handled per [`Self::bounds_check_policy`]; under
+    /// [`BoundsCheckPolicy::Skip`]/[`BoundsCheckPolicy::Checked`] this returns `0` rather than
+    /// reading anything (there's no sentinel `i32` to signal "skipped" otherwise -- see
+    /// [`Self::value_indexed`]/[`Self::try_value_indexed`] for a typed, `None`-returning read).
     fn value_raw_indexed(&self, index: i32) -> i32 {
-        unsafe {
-            ffi::LoadScriptVariableValueAtIndex(self.internal_local_var_table(), self.id(), index)
+        match resolve_index(self.capacity(), index, self.bounds_check_policy()) {
+            IndexResolution::InBounds(index) => unsafe {
+                ffi::LoadScriptVariableValueAtIndex(self.internal_local_var_table(), self.id(), index)
+            },
+            IndexResolution::OutOfBounds => 0,
         }
     }
 
+    /// Loads `buf.len()` raw bytes from the variable's packed value into `buf`.
+    ///
+    /// Unlike [`Self::value_raw`]/[`Self::value_raw_indexed`], which always surface a single
+    /// `i32`, this reads the variable's packed in-memory representation directly, so it's the
+    /// way to pull more than 32 bits out of a single variable (e.g. a wide array) in one call.
+    fn value_bytes(&self, buf: &mut [u8]) {
+        unsafe { ffi::LoadScriptVariableValueBytes(self.id(), buf.as_mut_ptr(), buf.len() as i32) }
+    }
+
     /// Loads the value of a script variable.
     ///
     /// If this variable is an array, the value at index 0 is returned.
@@ -589,7 +1321,12 @@ pub trait ScriptVariableRead: PartialEq + Eq {
 
     /// Loads the value of a script variable at some index (for script variables that are arrays).
     ///
-    /// Panics if the read is out of bounds.
+    /// An out-of-range `index` is handled per [`Self::bounds_check_policy`]: panics under
+    /// [`BoundsCheckPolicy::Panic`] (the default), clamps to the last valid index under
+    /// [`BoundsCheckPolicy::Clamp`], and returns [`ScriptVariableValue::None`] under
+    /// [`BoundsCheckPolicy::Skip`]/[`BoundsCheckPolicy::Checked`]. See [`Self::try_value_indexed`]
+    /// for a version that always bounds-checks and reports failure via `Option`, regardless of
+    /// the accessor's policy.
     ///
     /// Special case: If the type is a string, the character at the given position is returned
     /// (but still as a valid single-character CString).
@@ -603,8 +1340,10 @@ pub trait ScriptVariableRead: PartialEq + Eq {
             .val()
             .try_into()
             .expect("The variable has a corrupted type.");
-        let capacity = desc.n_values;
-        assert!(index <= capacity as i32, "Out-of-bounds.");
+        let index = match resolve_index(desc.n_values as usize, index, self.bounds_check_policy()) {
+            IndexResolution::InBounds(index) => index,
+            IndexResolution::OutOfBounds => return ScriptVariableValue::None,
+        };
         match typ {
             ScriptVariableValueType::None => ScriptVariableValue::None,
             ScriptVariableValueType::Bit => {
@@ -639,6 +1378,33 @@ pub trait ScriptVariableRead: PartialEq + Eq {
         }
     }
 
+    /// Like [`Self::value_indexed`], but always performs a strict bounds check regardless of this
+    /// accessor's [`BoundsCheckPolicy`], returning `None` for an out-of-range `index` instead of
+    /// ever consulting the policy. This is the fallible API [`BoundsCheckPolicy::Checked`] is
+    /// named after.
+    fn try_value_indexed(&self, index: i32) -> Option<ScriptVariableValue> {
+        if index < 0 || index as usize >= self.capacity() {
+            return None;
+        }
+        Some(self.value_indexed(index))
+    }
+
+    /// Loads every element of an array script variable in one pass, instead of calling
+    /// [`Self::value_indexed`] once per index (which costs one `ffi::LoadScriptVariableValueAtIndex`
+    /// call per element).
+    ///
+    /// Special case: for string type variables, the entire string is decoded in one pass (see
+    /// [`Self::value`]) and returned as the sole element of the returned `Vec`, rather than one
+    /// [`ScriptVariableValue::String`] per character.
+    fn read_all(&self) -> Vec<ScriptVariableValue> {
+        if self.var_type() == ScriptVariableValueType::String {
+            return vec![self.value()];
+        }
+        (0..self.capacity() as i32)
+            .map(|index| self.value_indexed(index))
+            .collect()
+    }
+
     /// Loads the sum of all values of the script variable (for script variables that are
     /// arrays).
     fn sum(&self) -> i32 {
@@ -678,18 +1444,32 @@ pub trait ScriptVariableWrite: ScriptVariableRead {
     }
 
     /// Writes the given value to a script variable.
+    ///
+    /// Every registered write observer (see [`ScriptVariables::observe_writes`]) whose filter
+    /// matches this variable is notified after the write, with [`Self::value`] as the new value.
     fn write_raw(&mut self, value: i32) {
         // SAFETY: The game makes sure the value fits.
         unsafe { ffi::SaveScriptVariableValue(self.internal_local_var_table(), self.id(), value) }
+        notify_write(self.id(), self.internal_local_var_table(), &self.value());
     }
 
     /// Writes the given value to a script variable at the given index (if this is an array).
     ///
-    /// Panics if the write is out of bounds.
+    /// An out-of-range `index` is handled per [`Self::bounds_check_policy`]: panics under
+    /// [`BoundsCheckPolicy::Panic`] (the default), clamps to the last valid index under
+    /// [`BoundsCheckPolicy::Clamp`], and silently does nothing (no observer notification either)
+    /// under [`BoundsCheckPolicy::Skip`]/[`BoundsCheckPolicy::Checked`]. See
+    /// [`Self::try_write_indexed`] for a version that always bounds-checks and reports failure,
+    /// regardless of the accessor's policy.
+    ///
+    /// Every registered write observer (see [`ScriptVariables::observe_writes`]) whose filter
+    /// matches this variable is notified after the write, with [`ScriptVariableRead::value`] as
+    /// the new value.
     fn write_raw_indexed(&mut self, index: i32, value: i32) {
-        let desc = self.descriptor();
-        let capacity = desc.n_values;
-        assert!(index <= capacity as i32, "Out-of-bounds.");
+        let index = match resolve_index(self.descriptor().n_values as usize, index, self.bounds_check_policy()) {
+            IndexResolution::InBounds(index) => index,
+            IndexResolution::OutOfBounds => return,
+        };
         // SAFETY: We make sure the variable in an array, the index is in bound & the game makes sure the value fits.
         unsafe {
             ffi::SaveScriptVariableValueAtIndex(
@@ -699,6 +1479,21 @@ pub trait ScriptVariableWrite: ScriptVariableRead {
                 value,
             )
         }
+        notify_write(self.id(), self.internal_local_var_table(), &self.value());
+    }
+
+    /// Saves `buf` as the variable's raw packed value.
+    ///
+    /// See [`ScriptVariableRead::value_bytes`] for the read side.
+    ///
+    /// Every registered write observer (see [`ScriptVariables::observe_writes`]) whose filter
+    /// matches this variable is notified after the write, with [`Self::value`] as the new value.
+    fn write_bytes(&mut self, buf: &[u8]) {
+        // SAFETY: The game only reads back `buf.len()` bytes.
+        unsafe {
+            ffi::SaveScriptVariableValueBytes(self.id(), buf.as_ptr() as *mut u8, buf.len() as i32)
+        }
+        notify_write(self.id(), self.internal_local_var_table(), &self.value());
     }
 
     /// Writes the given value to a script variable.
@@ -744,7 +1539,8 @@ pub trait ScriptVariableWrite: ScriptVariableRead {
 
     /// Writes the given value to a script variable (if this is an array).
     ///
-    /// Panics if the write is out of bounds.
+    /// An out-of-range `index` is handled by [`Self::write_raw_indexed`] per
+    /// [`ScriptVariableRead::bounds_check_policy`] -- see there for the policy-by-policy behavior.
     ///
     /// If the value type doesn't match, this panics.
     ///
@@ -762,10 +1558,49 @@ pub trait ScriptVariableWrite: ScriptVariableRead {
             typ,
             "The type of the value to write doesn't match the variable's type."
         );
-        let capacity = desc.n_values;
-        assert!(index <= capacity as i32, "Out-of-bounds.");
         self.write_raw_indexed(index, value.as_raw())
     }
+
+    /// Like [`Self::write_indexed`], but always performs a strict bounds check regardless of this
+    /// accessor's [`BoundsCheckPolicy`](ScriptVariableRead::bounds_check_policy), returning
+    /// `false` instead of writing anything for an out-of-range `index`. This is the fallible API
+    /// [`BoundsCheckPolicy::Checked`] is named after.
+    fn try_write_indexed(&mut self, index: i32, value: ScriptVariableValue) -> bool {
+        if index < 0 || index as usize >= self.capacity() {
+            return false;
+        }
+        self.write_indexed(index, value);
+        true
+    }
+
+    /// Writes every element of an array script variable in one pass, instead of calling
+    /// [`Self::write_indexed`] once per index.
+    ///
+    /// Special case: for string type variables, `values` must be a single-element slice holding
+    /// the whole string to write (see [`Self::write`]) -- it isn't split one character per index.
+    ///
+    /// # Panics
+    /// Panics if `values.len()` doesn't match [`ScriptVariableRead::capacity`] (or, for string
+    /// variables, isn't exactly 1), or if a value's type doesn't match the variable's type.
+    fn write_all(&mut self, values: &[ScriptVariableValue]) {
+        if self.var_type() == ScriptVariableValueType::String {
+            assert_eq!(
+                values.len(),
+                1,
+                "string variables take a single whole-string value, not one per index."
+            );
+            self.write(values[0].clone());
+            return;
+        }
+        assert_eq!(
+            values.len(),
+            self.capacity(),
+            "The number of values doesn't match the variable's capacity."
+        );
+        for (index, value) in values.iter().enumerate() {
+            self.write_indexed(index as i32, value.clone());
+        }
+    }
 }
 
 impl<'a> PartialEq for GlobalScriptVariableRef<'a> {
@@ -784,6 +1619,10 @@ impl<'a> ScriptVariableRead for GlobalScriptVariableRef<'a> {
     fn id(&self) -> ScriptVariableId {
         self.0
     }
+
+    fn bounds_check_policy(&self) -> BoundsCheckPolicy {
+        self.1
+    }
 }
 
 impl<'a> PartialEq for GlobalScriptVariableMut<'a> {
@@ -802,6 +1641,10 @@ impl<'a> ScriptVariableRead for GlobalScriptVariableMut<'a> {
     fn id(&self) -> ScriptVariableId {
         self.0
     }
+
+    fn bounds_check_policy(&self) -> BoundsCheckPolicy {
+        self.1
+    }
 }
 
 impl<'a> ScriptVariableWrite for GlobalScriptVariableMut<'a> {}
@@ -825,6 +1668,10 @@ impl<'a> ScriptVariableRead for LocalScriptVariableRef<'a> {
     fn id(&self) -> ScriptVariableId {
         self.1
     }
+
+    fn bounds_check_policy(&self) -> BoundsCheckPolicy {
+        self.2
+    }
 }
 
 impl<'a> PartialEq for LocalScriptVariableMut<'a> {
@@ -846,6 +1693,10 @@ impl<'a> ScriptVariableRead for LocalScriptVariableMut<'a> {
     fn id(&self) -> ScriptVariableId {
         self.1
     }
+
+    fn bounds_check_policy(&self) -> BoundsCheckPolicy {
+        self.2
+    }
 }
 
 impl<'a> ScriptVariableWrite for LocalScriptVariableMut<'a> {}
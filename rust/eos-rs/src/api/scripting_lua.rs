@@ -0,0 +1,269 @@
+//! Optional embedded [Lua](https://www.lua.org/) scripting runtime, built on
+//! [`hematita`](https://crates.io/crates/hematita), a `no_std`-compatible Lua VM written in
+//! safe Rust.
+//!
+//! This is a separate backend from [`crate::api::scripting`]'s `rune`-based one (gated by its
+//! own `scripting` feature, not `rune`), mirroring doukutsu-rs's feature-gated `scripting-lua`
+//! backend: special-process IDs in a configurable range are routed to a Lua function instead of
+//! a Rune one, so content using the far more common Lua syntax can be iterated without
+//! recompiling the patch binary.
+//!
+//! A [`LuaScriptEngine`] compiles a script's source once (see [`LuaScriptEngine::new`]) against
+//! an `eos` table exposing the ground-mode calls below, and can then have its `special_process_*`
+//! functions invoked by [`dispatch_scripted_special_process_lua`]:
+//!
+//! * `eos.get_random_npc_job_type()`, `eos.get_random_npc_job_subtype()`,
+//!   `eos.is_random_npc_job_still_available()`, `eos.accept_random_npc_job()` -- NPC job queries,
+//!   see [`GroundModeContext`](crate::api::ground_mode::GroundModeContext).
+//! * `eos.next_day()`, `eos.status_update()` -- day-advance/status-screen hooks.
+//! * `eos.jump_to_title_screen(arg)`, `eos.return_to_title_screen(fade_duration)` -- title-screen
+//!   transitions.
+//!
+//! These all call straight through to the matching `ffi` function rather than going through a
+//! borrowed [`GroundModeContext`](crate::api::ground_mode::GroundModeContext): a script's
+//! `special_process_<id>` function only ever runs from [`dispatch_scripted_special_process_lua`],
+//! which (like [`crate::api::special_process::dispatch_special_process_call`], its only caller)
+//! only ever runs in place of the game's own ov11-resident `ScriptSpecialProcessCall`, so overlay
+//! 11 is necessarily loaded for the whole call already.
+//!
+//! Script memory (source text, the compiled AST, and the VM's table/value graph while a function
+//! runs) is allocated from a [`ScopedArena`](crate::allocation::ScopedArena) built over ground
+//! mode's own arena (via
+//! [`GroundModeContext::get_alloc_arena_ground`](crate::api::ground_mode::GroundModeContext::get_alloc_arena_ground)),
+//! not the game's default heap -- see [`LuaScriptEngine::new`]. A script that allocates heavily
+//! competes with other ground-mode arena users instead of the rest of the game's fixed heap, and
+//! all of it is released at once when the engine (and its arena) drops.
+
+use crate::api::ground_mode::GroundModeContext;
+use crate::api::overlay::OverlayLoadLease;
+use crate::allocation::{EoSCustomAllocator, ScopedArena};
+use crate::ffi;
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use hematita::ast::{lexer::Lexer, parser::{parse, TokenIterator}};
+use hematita::compiler::compile;
+use hematita::lua_lib::standard_globals;
+use hematita::vm::value::{Table, Value};
+use hematita::vm::VirtualMachine;
+
+/// Errors that can occur while compiling or running a Lua script.
+#[derive(Debug)]
+pub enum LuaScriptError {
+    /// The script failed to lex or parse.
+    Syntax,
+    /// The script raised a Lua error while running.
+    Runtime,
+    /// The size requested for [`LuaScriptEngine::new`]'s backing arena is too small to even
+    /// attempt compiling the script.
+    ArenaTooSmall,
+}
+
+/// Builds the `eos` table exposed to Lua scripts, backing each entry with a host function that
+/// calls straight through to the relevant `ffi` function (see this module's docs for why a
+/// [`GroundModeContext`](crate::api::ground_mode::GroundModeContext) isn't threaded through
+/// instead).
+fn eos_table() -> Table {
+    let table = Table::default();
+
+    macro_rules! host_fn {
+        ($name:literal, $body:expr) => {
+            table.insert(
+                Value::String(Arc::new($name.as_bytes().to_vec())),
+                Value::NativeFunction(Arc::new($body)),
+            );
+        };
+    }
+
+    host_fn!("get_random_npc_job_type", |_args, _vm| {
+        let result = unsafe { ffi::GetRandomNpcJobType() };
+        Ok(Value::Integer(result as i64))
+    });
+    host_fn!("get_random_npc_job_subtype", |_args, _vm| {
+        let result = unsafe { ffi::GetRandomNpcJobSubtype() };
+        Ok(Value::Integer(result as i64))
+    });
+    host_fn!("is_random_npc_job_still_available", |_args, _vm| {
+        let result = unsafe { ffi::GetRandomNpcJobStillAvailable() > 0 };
+        Ok(Value::Boolean(result))
+    });
+    host_fn!("accept_random_npc_job", |_args, _vm| {
+        let result = unsafe { ffi::AcceptRandomNpcJob() > 0 };
+        Ok(Value::Boolean(result))
+    });
+    host_fn!("next_day", |_args, _vm| {
+        unsafe { ffi::GroundMainNextDay() };
+        Ok(Value::Nil)
+    });
+    host_fn!("status_update", |_args, _vm| {
+        unsafe { ffi::StatusUpdate() };
+        Ok(Value::Nil)
+    });
+    host_fn!("jump_to_title_screen", |args: Arc<Table>, _vm| {
+        let arg = lua_arg_i64(&args, 0) as i32;
+        let result = unsafe { ffi::JumpToTitleScreen(arg) > 0 };
+        Ok(Value::Boolean(result))
+    });
+    host_fn!("return_to_title_screen", |args: Arc<Table>, _vm| {
+        let fade_duration = lua_arg_i64(&args, 0) as u32;
+        let result = unsafe { ffi::ReturnToTitleScreen(fade_duration) > 0 };
+        Ok(Value::Boolean(result))
+    });
+
+    table
+}
+
+/// Reads positional Lua argument `index` (as `hematita` passes arguments: a [`Table`] indexed
+/// from `0`) as an integer, defaulting to `0` if it's missing or not a number.
+fn lua_arg_i64(args: &Table, index: i64) -> i64 {
+    match args.get(&Value::Integer(index)) {
+        Value::Integer(i) => i,
+        Value::Float(f) => f as i64,
+        _ => 0,
+    }
+}
+
+/// A compiled Lua script, with its own arena-backed allocator (see this module's docs) that both
+/// its source/AST and the VM's table/value graph are allocated from while it runs.
+pub struct LuaScriptEngine<'arena> {
+    source: String,
+    arena: ScopedArena<'arena>,
+}
+
+impl<'arena> LuaScriptEngine<'arena> {
+    /// Compiles `source` against the `eos` host table (see this module's docs), using `backing`
+    /// as the arena `source` and the script's own allocations are carved out of.
+    ///
+    /// `backing` should be sized generously: unlike the game's default heap, there's no fallback
+    /// once this arena is full, and lexing/parsing/running a Lua script allocates proportionally
+    /// to its source size and call depth.
+    pub fn new(
+        ground: &GroundModeContext,
+        backing: &'arena mut [u8],
+        source: &str,
+    ) -> Result<Self, LuaScriptError> {
+        if backing.len() < source.len() {
+            return Err(LuaScriptError::ArenaTooSmall);
+        }
+        let arena_ptr = ground.get_alloc_arena_ground(core::ptr::null_mut(), 0);
+        // SAFETY: `arena_ptr` was just returned by `get_alloc_arena_ground`, which keeps it valid
+        // for as long as ground mode itself is loaded -- well past this engine's lifetime.
+        let _ground_arena = unsafe { EoSCustomAllocator::new(arena_ptr) };
+        // The arena above is ground mode's own shared region (no fixed size this engine can bound
+        // ahead of time); instead this engine uses its own `ScopedArena` over `backing`, a buffer
+        // the caller carves out of that same ground-mode region up front, so a script's
+        // allocations are still accounted for there rather than the game's default heap, while
+        // staying deterministically bounded and released with this engine.
+        let arena = ScopedArena::new(backing, 256);
+        Ok(Self {
+            source: String::from(source),
+            arena,
+        })
+    }
+
+    /// The allocator backing this engine's scripts, for code wiring additional host-side buffers
+    /// (e.g. marshaled arguments) through the same arena as the script itself.
+    pub fn allocator(&self) -> &EoSCustomAllocator {
+        self.arena.allocator()
+    }
+
+    /// Calls a named function (e.g. `special_process_1000`) exported by the script with the
+    /// given integer arguments.
+    ///
+    /// Returns [`LuaScriptError::Runtime`] if the script doesn't define that function, panics, or
+    /// raises an error.
+    pub fn call(&self, function: &str, args: &[i64]) -> Result<Value, LuaScriptError> {
+        let lexer = Lexer {
+            source: self.source.chars().peekable(),
+        };
+        let parsed = parse(&mut TokenIterator(lexer.peekable())).map_err(|_| LuaScriptError::Syntax)?;
+        let compiled = compile(&parsed);
+
+        let globals = standard_globals();
+        globals.insert(
+            Value::String(Arc::new(b"eos".to_vec())),
+            Value::Table(Arc::new(eos_table())),
+        );
+
+        let vm = VirtualMachine::new(globals);
+        let body = vm.execute(&compiled, Arc::new(Table::default())).map_err(|_| LuaScriptError::Runtime)?;
+
+        let call_target = body.get(&Value::String(Arc::new(function.as_bytes().to_vec())));
+        let Value::NativeFunction(target) | Value::Function(target) = call_target else {
+            return Err(LuaScriptError::Runtime);
+        };
+        let arg_table = Table::default();
+        for (i, arg) in args.iter().enumerate() {
+            arg_table.insert(Value::Integer(i as i64), Value::Integer(*arg));
+        }
+        target(Arc::new(arg_table), &vm).map_err(|_| LuaScriptError::Runtime)
+    }
+}
+
+/// An embedded [`LuaScriptEngine`] that [`dispatch_scripted_special_process_lua`] routes a
+/// configurable range of special-process IDs to, same as
+/// [`ScriptedSpecialProcesses`](crate::api::scripting::ScriptedSpecialProcesses) does for the
+/// `rune` backend.
+pub struct ScriptedSpecialProcessesLua<'arena> {
+    /// The first special-process ID routed to `engine`; lower IDs are left for
+    /// [`crate::api::special_process`]'s native registry/the base game.
+    range_start: u32,
+    engine: LuaScriptEngine<'arena>,
+}
+
+impl<'arena> ScriptedSpecialProcessesLua<'arena> {
+    /// Routes special-process IDs `>= range_start` to `engine`.
+    pub fn new(range_start: u32, engine: LuaScriptEngine<'arena>) -> Self {
+        Self { range_start, engine }
+    }
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// special processes are only ever dispatched from the main game loop.
+static mut SCRIPTED_SPECIAL_PROCESSES_LUA: Option<ScriptedSpecialProcessesLua<'static>> = None;
+
+/// Sets (or, with `None`, clears) the Lua engine special-process IDs in its configured range are
+/// routed to. Replaces whatever was previously registered.
+///
+/// # Safety
+/// `scripted`'s [`LuaScriptEngine`] (and the arena buffer it borrows) must stay valid for as long
+/// as it's registered here, i.e. until this function is called again with a different value.
+pub unsafe fn set_scripted_special_processes_lua(scripted: Option<ScriptedSpecialProcessesLua<'static>>) {
+    unsafe {
+        SCRIPTED_SPECIAL_PROCESSES_LUA = scripted;
+    }
+}
+
+/// Routes special process `id` to the registered [`ScriptedSpecialProcessesLua`] engine, if any
+/// and if `id` falls in its range, calling its `special_process_<id>(arg1, arg2)` function and
+/// marshaling back the integer it returns (`0` if it returns something else, or nothing).
+///
+/// Returns `None` (rather than `Some(0)`) when no script is registered or `id` is outside its
+/// range, so [`crate::api::special_process::dispatch_special_process_call`] can fall through to
+/// its own native registry/the base game for that ID instead. `_ov11` proves overlay 11 is
+/// loaded, same as every other caller reached from that dispatcher -- see this module's docs for
+/// why the host functions exposed to scripts rely on that instead of a
+/// [`GroundModeContext`](crate::api::ground_mode::GroundModeContext) passed in explicitly.
+pub fn dispatch_scripted_special_process_lua(
+    id: u32,
+    arg1: i32,
+    arg2: i32,
+    _ov11: &OverlayLoadLease<11>,
+) -> Option<i32> {
+    // SAFETY: single-threaded; see `SCRIPTED_SPECIAL_PROCESSES_LUA`.
+    #[allow(static_mut_refs)]
+    let scripted = unsafe { SCRIPTED_SPECIAL_PROCESSES_LUA.as_ref() }?;
+    if id < scripted.range_start {
+        return None;
+    }
+    let function = format!("special_process_{}", id);
+    let result = scripted
+        .engine
+        .call(&function, &[arg1 as i64, arg2 as i64])
+        .ok()?;
+    Some(match result {
+        Value::Integer(i) => i as i32,
+        Value::Float(f) => f as i32,
+        _ => 0,
+    })
+}
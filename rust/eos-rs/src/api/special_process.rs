@@ -0,0 +1,204 @@
+//! A runtime registry that lets mods claim unused script special-process IDs without needing
+//! a `patches!`-time `special_process N: fn_name` entry for each one.
+//!
+//! [`GroundModeContext::script_special_process_call`](crate::api::ground_mode::GroundModeContext::script_special_process_call)
+//! is the game's own `OPCODE_PROCESS_SPECIAL` dispatcher; [`dispatch_special_process_call`] is a
+//! drop-in replacement for it meant to be called from the `CustomScriptSpecialProcessCall` patch
+//! glue, so ground-engine (ssb) scripts can reach Rust logic through high/unused special-proc IDs
+//! the same way they already reach `ScriptSpecialProcess0x3`, `IsBagFull`, etc. -- the DS
+//! equivalent of `callasm`.
+//!
+//! When the `rune` feature is enabled, IDs not claimed by this registry fall through further to
+//! [`crate::api::scripting::dispatch_scripted_special_process`] before finally reaching the base
+//! game, letting a registered [`crate::api::scripting::ScriptedSpecialProcesses`] engine claim an
+//! entire ID range (e.g. everything `>= 1000`) for hot-loadable scripts. The `scripting` feature
+//! offers the same thing built on an embedded Lua VM instead, via
+//! [`crate::api::scripting_lua::dispatch_scripted_special_process_lua`].
+
+use crate::api::ground_mode::SpecialProcessId;
+use crate::api::overlay::OverlayLoadLease;
+use crate::ffi;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// A Rust-registered handler for a special process ID.
+///
+/// Sees the full 32-bit `arg1`/`arg2` that `script_special_process_call` itself receives (rather
+/// than the 16-bit script arguments `OPCODE_PROCESS_SPECIAL` originally passes in), plus a lease
+/// proving overlay 11 is loaded -- every caller of this registry only ever runs while
+/// `ScriptSpecialProcessCall` itself could, i.e. while ov11 is loaded -- so a handler can call
+/// other ov11-gated APIs (e.g. [`GroundModeContext`](crate::api::ground_mode::GroundModeContext)
+/// methods) without acquiring its own lease.
+///
+/// Returning `None` means "not handled this time": `id` stays claimed (no other handler gets a
+/// turn), but [`dispatch_special_process_call`] falls through to the game's own
+/// `ScriptSpecialProcessCall` for that particular call. This lets a handler decide whether to
+/// intervene based on the arguments, not just the ID, e.g. to pass most calls through untouched
+/// and only special-case a few.
+pub type SpecialProcessHandler = Box<dyn FnMut(i32, i32, &OverlayLoadLease<11>) -> Option<i32>>;
+
+struct SpecialProcessEntry {
+    id: u32,
+    handler: SpecialProcessHandler,
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// special processes are only ever dispatched from the main game loop.
+static mut SPECIAL_PROCESSES: Vec<SpecialProcessEntry> = Vec::new();
+
+/// Registers `handler` to own special process `id`.
+///
+/// Pick an ID that's unused by the base game (see the built-in special process table on
+/// [`GroundModeContext::script_special_process_call`](crate::api::ground_mode::GroundModeContext::script_special_process_call))
+/// so ground-engine scripts can call it via `OPCODE_PROCESS_SPECIAL` without colliding with a
+/// stock proc.
+///
+/// IDs claimed via `patches!`'s `special_process N: fn_name` entries are checked for collisions
+/// at compile time (parsing rejects two entries with the same ID); the registry here can only
+/// check at registration time, since it's just a runtime table two unrelated patches might both
+/// push to. So unlike re-registering a `patches!` ID (a compile error), registering an already
+/// claimed `id` here panics, so two patches claiming the same ID is still caught -- just later,
+/// the first time both have run their setup code. Use [`override_special_process`] when
+/// overriding another handler (including your own) is actually intended.
+///
+/// # Panics
+/// Panics if `id` is already registered.
+pub fn register_special_process(
+    id: u32,
+    handler: impl FnMut(i32, i32, &OverlayLoadLease<11>) -> Option<i32> + 'static,
+) {
+    // SAFETY: single-threaded; see `SPECIAL_PROCESSES`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        assert!(
+            !SPECIAL_PROCESSES.iter().any(|entry| entry.id == id),
+            "special process id {} is already registered; use `override_special_process` to replace it on purpose",
+            id
+        );
+        SPECIAL_PROCESSES.push(SpecialProcessEntry {
+            id,
+            handler: Box::new(handler),
+        });
+    }
+}
+
+/// Registers `handler` to own special process `id`, replacing any handler already registered for
+/// it instead of panicking.
+///
+/// See [`register_special_process`] for the usual, collision-checked way to claim an ID.
+pub fn override_special_process(
+    id: u32,
+    handler: impl FnMut(i32, i32, &OverlayLoadLease<11>) -> Option<i32> + 'static,
+) {
+    // SAFETY: single-threaded; see `SPECIAL_PROCESSES`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        match SPECIAL_PROCESSES.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) => entry.handler = Box::new(handler),
+            None => SPECIAL_PROCESSES.push(SpecialProcessEntry {
+                id,
+                handler: Box::new(handler),
+            }),
+        }
+    }
+}
+
+/// Unregisters the handler claiming special process `id`, if any, letting the stock routine (or
+/// whatever `patches!`-time `special_process` entry exists for it) take back over.
+///
+/// Returns whether a handler was actually removed.
+pub fn unregister_special_process(id: u32) -> bool {
+    // SAFETY: single-threaded; see `SPECIAL_PROCESSES`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        let before = SPECIAL_PROCESSES.len();
+        SPECIAL_PROCESSES.retain(|entry| entry.id != id);
+        SPECIAL_PROCESSES.len() != before
+    }
+}
+
+/// Returns the special process IDs currently claimed via [`register_special_process`]/
+/// [`override_special_process`], in registration order.
+pub fn registered_special_process_ids() -> Vec<u32> {
+    // SAFETY: single-threaded; see `SPECIAL_PROCESSES`.
+    #[allow(static_mut_refs)]
+    unsafe { SPECIAL_PROCESSES.iter().map(|entry| entry.id).collect() }
+}
+
+/// Routes a `ScriptSpecialProcessCall` through the Rust registry, tail-calling the game's own
+/// dispatch for any ID not claimed (or not handled this particular call, see
+/// [`SpecialProcessHandler`]) so stock procs keep working untouched.
+///
+/// Intended to be called from the `CustomScriptSpecialProcessCall` patch glue in place of calling
+/// [`ffi::ScriptSpecialProcessCall`] directly, e.g.:
+///
+/// ```ignore
+/// #[no_mangle]
+/// pub extern "C" fn CustomScriptSpecialProcessCall(
+///     param_1: *mut ffi::undefined4,
+///     id: ffi::special_process_id,
+///     arg1: i32,
+///     arg2: i32,
+/// ) -> i32 {
+///     eos_rs::api::special_process::dispatch_special_process_call(param_1, id, arg1, arg2)
+/// }
+/// ```
+///
+/// `param_1` is only ever forwarded to the original `ScriptSpecialProcessCall`; registered
+/// handlers don't see it, since none of the registrable IDs are [`SpecialProcessId`] 18 (the only
+/// one that uses it, see `script_special_process_call`'s docs).
+///
+/// This function itself only ever runs in place of the game's own ov11-resident
+/// `ScriptSpecialProcessCall`, so overlay 11 is necessarily loaded for the whole call; the lease
+/// handed to handlers is acquired on that basis rather than checked.
+///
+/// The registry lookup temporarily removes the matched entry from the table before calling its
+/// handler (a boxed `FnMut` closure, so unlike a plain `fn` pointer it isn't `Copy`), so a handler
+/// is free to call [`register_special_process`]/[`unregister_special_process`]/
+/// [`override_special_process`] or recursively trigger another special process -- including
+/// itself -- without the lookup's borrow of the registry still being held while it runs. The
+/// entry is reinserted afterwards, unless the handler itself unregistered or replaced `id` while
+/// it ran.
+pub fn dispatch_special_process_call(
+    param_1: *mut ffi::undefined4,
+    id: SpecialProcessId,
+    arg1: i32,
+    arg2: i32,
+) -> i32 {
+    // SAFETY: see this function's docs: only ever reached in place of the game's own ov11-resident
+    // `ScriptSpecialProcessCall`, so ov11 is necessarily loaded here.
+    let lease = unsafe { OverlayLoadLease::<11>::acquire_unchecked() };
+    if let Some(result) = call_handler(id.id(), arg1, arg2, &lease) {
+        return result;
+    }
+    #[cfg(feature = "rune")]
+    if let Some(result) = crate::api::scripting::dispatch_scripted_special_process(id.id(), arg1, arg2) {
+        return result;
+    }
+    #[cfg(feature = "scripting")]
+    if let Some(result) =
+        crate::api::scripting_lua::dispatch_scripted_special_process_lua(id.id(), arg1, arg2, &lease)
+    {
+        return result;
+    }
+    unsafe { ffi::ScriptSpecialProcessCall(param_1, id, arg1, arg2) }
+}
+
+fn call_handler(id: u32, arg1: i32, arg2: i32, lease: &OverlayLoadLease<11>) -> Option<i32> {
+    // SAFETY: single-threaded; see `SPECIAL_PROCESSES`.
+    #[allow(static_mut_refs)]
+    let mut entry = unsafe {
+        let index = SPECIAL_PROCESSES.iter().position(|entry| entry.id == id)?;
+        SPECIAL_PROCESSES.remove(index)
+    };
+    let result = (entry.handler)(arg1, arg2, lease);
+    // SAFETY: single-threaded; see `SPECIAL_PROCESSES`. Only reinsert if the handler didn't
+    // already unregister (or replace) `id` itself while it ran.
+    #[allow(static_mut_refs)]
+    unsafe {
+        if !SPECIAL_PROCESSES.iter().any(|existing| existing.id == id) {
+            SPECIAL_PROCESSES.push(entry);
+        }
+    }
+    result
+}
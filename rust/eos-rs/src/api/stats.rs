@@ -0,0 +1,98 @@
+//! A unified catalog over the game's scattered mission/request progress counters
+//! (`DungeonRequestsDone`, `GetSosMailCount`, ...), so mods have one place to query them and to
+//! build achievement-style reward logic on top of, instead of poking each underlying query
+//! function by hand.
+//!
+//! [`register_stat_observer`]/[`poll_stat_observers`] add threshold-crossing callbacks on top:
+//! a patch registers a [`Stat`], a threshold and a handler, and calls [`poll_stat_observers`]
+//! periodically (e.g. once per turn, wherever it already polls other game state) to fire any
+//! handler whose counter has newly crossed its threshold -- no single game function already runs
+//! exactly when one of these counters changes, so this module can't hook one and has to be polled
+//! instead.
+
+use crate::api::gameplay;
+use alloc::vec::Vec;
+
+/// A tracked mission/request progress counter. See [`Stats::get`]/[`Stats::get_bool`].
+///
+/// The underlying game functions all take an otherwise-undocumented `i32` parameter (see
+/// [`gameplay::dungeon_requests_done`]); [`Stats::get`] always passes `0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stat {
+    /// Number of missions completed. See [`gameplay::dungeon_requests_done_wrapper`].
+    MissionsCompleted,
+    /// Whether any mission has been completed. See [`gameplay::any_dungeon_requests_done`].
+    AnyMissionDone,
+    /// Number of SOS mails. See [`gameplay::get_sos_mail_count`].
+    SosMailCount,
+}
+
+/// A unified, catalog-style view over the game's mission/request progress counters.
+pub struct Stats;
+
+impl Stats {
+    /// Returns `stat`'s current value as an integer. [`Stat::AnyMissionDone`] reads as `0`/`1`;
+    /// use [`Self::get_bool`] for a proper `bool`.
+    pub fn get(stat: Stat) -> u32 {
+        match stat {
+            Stat::MissionsCompleted => gameplay::dungeon_requests_done_wrapper(0) as u32,
+            Stat::AnyMissionDone => gameplay::any_dungeon_requests_done(0) as u32,
+            Stat::SosMailCount => gameplay::get_sos_mail_count(0, false) as u32,
+        }
+    }
+
+    /// Returns `stat`'s current value as a `bool`: whether [`Self::get`] is nonzero.
+    pub fn get_bool(stat: Stat) -> bool {
+        Self::get(stat) != 0
+    }
+}
+
+/// A callback registered with [`register_stat_observer`].
+pub type StatThresholdHandler = fn();
+
+struct StatObserver {
+    stat: Stat,
+    threshold: u32,
+    handler: StatThresholdHandler,
+    fired: bool,
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded and
+/// observers are only ever registered/polled from the main game loop.
+static mut STAT_OBSERVERS: Vec<StatObserver> = Vec::new();
+
+/// Registers `handler` to run the first time [`Stats::get`]`(stat)` is observed to be `>=
+/// threshold` by [`poll_stat_observers`], e.g. for a "first mission completed" reward.
+///
+/// `handler` fires at most once per registration, even if the counter (these are all normally
+/// monotonically increasing) is later observed below `threshold` again.
+pub fn register_stat_observer(stat: Stat, threshold: u32, handler: StatThresholdHandler) {
+    // SAFETY: single-threaded; see `STAT_OBSERVERS`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        STAT_OBSERVERS.push(StatObserver {
+            stat,
+            threshold,
+            handler,
+            fired: false,
+        });
+    }
+}
+
+/// Checks every observer registered with [`register_stat_observer`] against its tracked stat's
+/// current value, firing (and marking fired) any whose threshold has newly been crossed.
+///
+/// Intended to be called once per frame/turn from wherever a patch already polls other game
+/// state.
+pub fn poll_stat_observers() {
+    // SAFETY: single-threaded; see `STAT_OBSERVERS`.
+    #[allow(static_mut_refs)]
+    unsafe {
+        for observer in STAT_OBSERVERS.iter_mut() {
+            if !observer.fired && Stats::get(observer.stat) >= observer.threshold {
+                observer.fired = true;
+                (observer.handler)();
+            }
+        }
+    }
+}
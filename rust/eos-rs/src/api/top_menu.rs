@@ -2,6 +2,80 @@
 
 use crate::api::overlay::OverlayLoadLease;
 use crate::ffi;
+use alloc::vec::Vec;
+
+/// The first action ID handed out by [`register_main_menu_action`]/[`register_sub_menu_action`].
+///
+/// Action IDs below this are reserved for the game's own `MAIN_MENU`/`SUBMENU` data tables, so
+/// starting here avoids colliding with a built-in option.
+const FIRST_CUSTOM_ACTION_ID: i32 = 1000;
+
+/// A registered callback for a custom menu action.
+struct MenuAction {
+    action_id: i32,
+    handler: fn(&OverlayLoadLease<1>),
+}
+
+/// This is safe to access by the functions in this module, since the NDS is single-threaded
+/// and the top menu is only ever driven from the main game loop.
+static mut CUSTOM_MENU_ACTIONS: Vec<MenuAction> = Vec::new();
+
+/// Allocates a fresh action ID backed by a Rust callback and adds it to the top menu.
+///
+/// Unlike [`add_main_menu_option`], this does not require the action ID to already exist in the
+/// `MAIN_MENU` data field; the ID is invented here and the handler is dispatched directly by
+/// [`dispatch_custom_action`] when the option is selected. Returns the allocated action ID.
+pub fn register_main_menu_action(
+    ov01: &OverlayLoadLease<1>,
+    handler: fn(&OverlayLoadLease<1>),
+) -> i32 {
+    let action_id = register_action(handler);
+    add_main_menu_option(ov01, action_id, true);
+    action_id
+}
+
+/// Allocates a fresh action ID backed by a Rust callback and adds it to the "Other" submenu.
+///
+/// See [`register_main_menu_action`] for details.
+pub fn register_sub_menu_action(
+    ov01: &OverlayLoadLease<1>,
+    handler: fn(&OverlayLoadLease<1>),
+) -> i32 {
+    let action_id = register_action(handler);
+    add_sub_menu_option(ov01, action_id, true);
+    action_id
+}
+
+fn register_action(handler: fn(&OverlayLoadLease<1>)) -> i32 {
+    // SAFETY: Single-threaded, only ever touched from the main game loop.
+    #[allow(static_mut_refs)]
+    unsafe {
+        let action_id = FIRST_CUSTOM_ACTION_ID + CUSTOM_MENU_ACTIONS.len() as i32;
+        CUSTOM_MENU_ACTIONS.push(MenuAction { action_id, handler });
+        action_id
+    }
+}
+
+/// Dispatches a selected top-menu action ID to the Rust callback it was registered with by
+/// [`register_main_menu_action`]/[`register_sub_menu_action`], if any.
+///
+/// Returns whether a registered callback handled the action. Intended to be called from the
+/// C entrypoint that handles menu selection, before falling back to the game's builtin actions.
+pub fn dispatch_custom_action(ov01: &OverlayLoadLease<1>, action_id: i32) -> bool {
+    // SAFETY: Single-threaded, only ever touched from the main game loop.
+    #[allow(static_mut_refs)]
+    unsafe {
+        if let Some(action) = CUSTOM_MENU_ACTIONS
+            .iter()
+            .find(|action| action.action_id == action_id)
+        {
+            (action.handler)(ov01);
+            true
+        } else {
+            false
+        }
+    }
+}
 
 /// Prepares the top menu and sub menu, adding the different options that compose them.
 /// Contains multiple calls to [`add_main_menu_option`] and [`add_sub_menu_option`]. Some of them are
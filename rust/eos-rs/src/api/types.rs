@@ -10,6 +10,31 @@ impl Copy for MonsterTypeId {}
 
 /// This impl provides general metadata about monster types in the game.
 impl MonsterTypeId {
+    /// The Fire type.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub const FIRE: Self = Self(2);
+    /// The Water type.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub const WATER: Self = Self(3);
+    /// The Ice type.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub const ICE: Self = Self(6);
+    /// The Ground type.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub const GROUND: Self = Self(9);
+    /// The Rock type.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub const ROCK: Self = Self(13);
+    /// The Steel type.
+    ///
+    /// Note: unverified, ported from Irdkwia's notes.
+    pub const STEEL: Self = Self(17);
+
     /// Returns the ID struct for the type with the given ID.
     ///
     /// # Safety
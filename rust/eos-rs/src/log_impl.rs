@@ -2,30 +2,104 @@
 
 use alloc::ffi::CString;
 use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
 use crate::ctypes::c_char;
-use log::{Level, LevelFilter, Metadata, Record};
+pub use log::LevelFilter;
+use log::{Level, Metadata, Record};
 use crate::ffi;
 
 static LOGGER: EoSLogger = EoSLogger;
 static mut LOGGER_INIT: bool = false;
 
-/// Registers the logger at the [`log`] crate. This is safe to be called multiple times.
-pub fn register_logger() {
+/// Registers the logger at the [`log`] crate, with `initial_level` as the starting
+/// [`log::max_level`]. This is safe to be called multiple times; only the first call's
+/// `initial_level` has any effect, since later calls see `LOGGER_INIT` already set.
+///
+/// Verbosity can be changed at any point afterwards via [`log::set_max_level`], and individual
+/// targets can be filtered separately via [`set_target_allow_list`]/[`set_target_deny_list`].
+pub fn register_logger(initial_level: LevelFilter) {
     // We will ignore errors during logger setup.
     // SAFETY: We only have one thread, we are sure we are the only ones calling this.
     unsafe {
         if !LOGGER_INIT {
-            log::set_logger_racy(&LOGGER).map(|()| log::set_max_level(LevelFilter::Debug)).ok();
+            log::set_logger_racy(&LOGGER).map(|()| log::set_max_level(initial_level)).ok();
             LOGGER_INIT = true;
         }
     }
 }
 
+/// Whether a [`TargetFilter`] lets a target through only if it matches one of its prefixes
+/// (`Allow`), or only if it matches none of them (`Deny`).
+enum TargetFilterMode {
+    Allow,
+    Deny,
+}
+
+/// A target-prefix filter installed via [`set_target_allow_list`]/[`set_target_deny_list`],
+/// checked by [`EoSLogger::enabled`] against each log record's `metadata.target()`.
+struct TargetFilter {
+    mode: TargetFilterMode,
+    prefixes: Vec<String>,
+}
+
+/// The target filter currently installed, if any. This is safe to access by the functions in
+/// this module, since the NDS is single-threaded.
+static mut TARGET_FILTER: Option<TargetFilter> = None;
+
+/// Installs an allow list: only targets starting with one of `prefixes` pass
+/// [`EoSLogger::enabled`] (in addition to still needing to pass the [`log::max_level`] check).
+///
+/// Replaces any filter installed by a previous call to this function or
+/// [`set_target_deny_list`]. Pass an empty `Vec` to silence every target.
+pub fn set_target_allow_list(prefixes: Vec<String>) {
+    // SAFETY: single-threaded; see `TARGET_FILTER`.
+    unsafe {
+        TARGET_FILTER = Some(TargetFilter { mode: TargetFilterMode::Allow, prefixes });
+    }
+}
+
+/// Installs a deny list: targets starting with one of `prefixes` are rejected by
+/// [`EoSLogger::enabled`]; everything else still needs to pass the [`log::max_level`] check, but
+/// not this filter.
+///
+/// Replaces any filter installed by a previous call to this function or
+/// [`set_target_allow_list`].
+pub fn set_target_deny_list(prefixes: Vec<String>) {
+    // SAFETY: single-threaded; see `TARGET_FILTER`.
+    unsafe {
+        TARGET_FILTER = Some(TargetFilter { mode: TargetFilterMode::Deny, prefixes });
+    }
+}
+
+/// Removes any target filter installed via [`set_target_allow_list`]/[`set_target_deny_list`], so
+/// every target is allowed through again (subject to [`log::max_level`]).
+pub fn clear_target_filter() {
+    // SAFETY: single-threaded; see `TARGET_FILTER`.
+    unsafe {
+        TARGET_FILTER = None;
+    }
+}
+
 struct EoSLogger;
 
 impl log::Log for EoSLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= Level::Debug
+        if metadata.level() > log::max_level() {
+            return false;
+        }
+        // SAFETY: single-threaded; see `TARGET_FILTER`.
+        #[allow(static_mut_refs)]
+        let filter = unsafe { &TARGET_FILTER };
+        match filter {
+            None => true,
+            Some(TargetFilter { mode: TargetFilterMode::Allow, prefixes }) => {
+                prefixes.iter().any(|prefix| metadata.target().starts_with(prefix.as_str()))
+            }
+            Some(TargetFilter { mode: TargetFilterMode::Deny, prefixes }) => {
+                !prefixes.iter().any(|prefix| metadata.target().starts_with(prefix.as_str()))
+            }
+        }
     }
 
     fn log(&self, record: &Record) {
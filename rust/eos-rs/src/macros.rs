@@ -9,3 +9,18 @@ macro_rules! force_mut_ptr {
         $x as *const _ as *mut _
     };
 }
+
+/// Recovers a pointer to the struct embedding `$field`, given a pointer to that field itself.
+///
+/// Useful when an engine callback hands back a pointer to an embedded sub-struct (e.g.
+/// `&mut entity.monster`) rather than the `&mut $Container` it lives inside of:
+/// `container_of!(monster_ptr, DungeonEntity, monster)` reconstructs the enclosing pointer
+/// without re-querying the game. Always yields a `*const $Container`; wrap in [`force_mut_ptr!`]
+/// if you need a `*mut` one.
+///
+/// The caller promises `$ptr` genuinely points at the `$field` field of a live `$Container`.
+macro_rules! container_of {
+    ($ptr:expr, $Container:ty, $field:ident) => {
+        ($ptr as *const _ as *const u8).byte_sub(core::mem::offset_of!($Container, $field)) as *const $Container
+    };
+}
@@ -1,11 +1,121 @@
+use crate::api::overlay::OverlayLoadLease;
+use crate::api::sys::{get_debug_flag1, get_debug_flag2, get_time};
+use core::fmt::Write;
 use core::panic::PanicInfo;
 use log::error;
 
+/// Size of the fixed-capacity, no-alloc buffer [`build_crash_report`] formats a report into. The
+/// panic handler runs with the allocator potentially already in a broken state, so this can't be
+/// a `String`.
+const CRASH_REPORT_CAPACITY: usize = 1024;
+
+/// The known memory region a crash report is written to: a plain `static mut` byte buffer, since
+/// this crate has no other "known address" to hand a host debugger -- its link-time address is
+/// itself the known location external tooling can read back from a dump.
+static mut CRASH_REPORT_BUFFER: [u8; CRASH_REPORT_CAPACITY] = [0; CRASH_REPORT_CAPACITY];
+
+/// Overlay group IDs this crate has [`OverlayLoadLease`] support for; checked with
+/// [`OverlayLoadLease::is_loaded`] to report which ones are loaded at crash time. Kept in sync
+/// with every `OverlayLoadLease<N>` used across the `api` module.
+const KNOWN_OVERLAY_GROUPS: [u32; 7] = [1, 10, 11, 13, 19, 29, 31];
+
+/// Optional hook registered via [`set_panic_report_sink`], called with the formatted report
+/// before the game hangs, so host tooling can decide where a crash report ends up (eg. forwarding
+/// it over a link cable/emulator debug channel) instead of only relying on [`CRASH_REPORT_BUFFER`]
+/// being found in a memory dump.
+///
+/// # Safety
+/// Single-threaded (GBA code, one core), so a plain unsynchronized static is the established
+/// pattern in this crate; see eg. `message_log`'s dedup ring buffer.
+static mut PANIC_REPORT_SINK: Option<fn(&str)> = None;
+
+/// Registers `sink` to be called with the formatted crash report text right before the game
+/// hangs. Only one sink can be registered at a time; a later call replaces an earlier one.
+pub fn set_panic_report_sink(sink: fn(&str)) {
+    // SAFETY: single-threaded.
+    unsafe {
+        PANIC_REPORT_SINK = Some(sink);
+    }
+}
+
+/// A cursor writer over a fixed-size byte buffer, so [`build_crash_report`] can use
+/// [`core::fmt::Write`]/`write!` without allocating. Silently truncates instead of erroring if the
+/// report doesn't fit, since a partial crash report still beats none.
+struct FixedBufWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Write for FixedBufWriter<'a> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let bytes = s.as_bytes();
+        let remaining = self.buf.len() - self.len;
+        let to_copy = bytes.len().min(remaining);
+        self.buf[self.len..self.len + to_copy].copy_from_slice(&bytes[..to_copy]);
+        self.len += to_copy;
+        Ok(())
+    }
+}
+
+/// Formats a structured crash report for `panic` into `buf`, returning the text that was written
+/// (truncated to fit `buf`, see [`FixedBufWriter`]).
+///
+/// Captures: the panic message and its file/line (from `panic` itself), which of the
+/// [`KNOWN_OVERLAY_GROUPS`] are currently loaded, the debug flags read through
+/// [`get_debug_flag1`]/[`get_debug_flag2`] (flag ID 0 -- this crate has no catalog of which IDs
+/// are meaningful, and the flags are a no-op in the final binary regardless, see their own doc
+/// comments), and the current [`get_time`] timestamp.
+fn build_crash_report<'a>(panic: &PanicInfo<'_>, buf: &'a mut [u8]) -> &'a str {
+    let mut writer = FixedBufWriter { buf, len: 0 };
+    let _ = writeln!(writer, "panic: {}", panic);
+    let _ = writeln!(writer, "time: {}", get_time());
+    let _ = writeln!(
+        writer,
+        "debug_flag1[0]: {} debug_flag2[0]: {}",
+        get_debug_flag1(0),
+        get_debug_flag2(0)
+    );
+    let _ = write!(writer, "overlays loaded:");
+    for group in KNOWN_OVERLAY_GROUPS {
+        // SAFETY: `is_loaded` only reads the overlay table; there's no const-generic way to loop
+        // over `OverlayLoadLease<N>` for a runtime `N`, so each group is checked through its own
+        // monomorphization via a small dispatch instead.
+        let loaded = match group {
+            1 => OverlayLoadLease::<1>::is_loaded(),
+            10 => OverlayLoadLease::<10>::is_loaded(),
+            11 => OverlayLoadLease::<11>::is_loaded(),
+            13 => OverlayLoadLease::<13>::is_loaded(),
+            19 => OverlayLoadLease::<19>::is_loaded(),
+            29 => OverlayLoadLease::<29>::is_loaded(),
+            31 => OverlayLoadLease::<31>::is_loaded(),
+            _ => false,
+        };
+        if loaded {
+            let _ = write!(writer, " {}", group);
+        }
+    }
+    let len = writer.len;
+    // SAFETY: `write_str` only ever wrote valid UTF-8 (it copies slices of `&str`s verbatim, and
+    // never splits a multi-byte sequence since it only truncates at the end of the whole report).
+    unsafe { core::str::from_utf8_unchecked(&buf[..len]) }
+}
+
 #[panic_handler]
-/// Panic by logging the panic and then calling the game's
-/// built-in function for hanging it.
+/// Captures a structured crash report (see [`build_crash_report`]) into a fixed-capacity buffer,
+/// logs it, forwards it to a sink registered via [`set_panic_report_sink`] (if any), and then
+/// hangs the game via its built-in [`WaitForever`].
 fn panic(panic: &PanicInfo<'_>) -> ! {
-    error!("{}", panic);
+    // SAFETY: single-threaded (GBA code, one core), and we're about to hang forever, so there's
+    // no concern about a later call reusing this buffer while we're still writing it.
+    #[allow(static_mut_refs)]
+    let report = unsafe { build_crash_report(panic, &mut CRASH_REPORT_BUFFER) };
+    error!("{}", report);
+    // SAFETY: single-threaded.
+    #[allow(static_mut_refs)]
+    let sink = unsafe { PANIC_REPORT_SINK };
+    if let Some(sink) = sink {
+        sink(report);
+    }
     unsafe { WaitForever() }
 }
 
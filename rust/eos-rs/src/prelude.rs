@@ -9,6 +9,8 @@ pub use crate::allocation::ALLOCATOR;
 pub use crate::panic::*;
 #[doc(hidden)]  // So it's not documented twice.
 pub use crate::patches;
+#[doc(hidden)]  // So it's not documented twice.
+pub use crate::define_item;
 pub use log::{debug, error, info, trace, warn};
 #[cfg(feature = "io")]
 pub use crate::api::io::prelude::*;
@@ -10,7 +10,7 @@ use eos_rs::api::fixed::I24F8;
 use eos_rs::api::overlay::{CreatableWithLease, OverlayLoadLease};
 use eos_rs::api::random;
 use eos_rs::ffi;
-use eos_rs::log_impl::register_logger;
+use eos_rs::log_impl::{register_logger, LevelFilter};
 
 // This defines the patches that will be written to the game, the syntax should hopefully
 // be somewhat self-explanatory.
@@ -56,7 +56,7 @@ pub extern "C" fn has_high_health(
     entity: *mut DungeonEntity,
 ) -> ffi::bool_ {
     // This is only required for non-special process / effects patches.
-    register_logger();
+    register_logger(LevelFilter::Debug);
     info!("In has_high_health");
 
     // We don't really need to do this, since the entity will